@@ -0,0 +1,480 @@
+//! Workspace activity histograms for the dashboard.
+//!
+//! Counts user messages, assistant messages and completed tasks per local day
+//! and per hour of day. Session summaries select which sessions to load: only
+//! sessions of the workspace updated inside the window are read in full.
+//!
+//! Computed days are cached per workspace. The cache is keyed by a fingerprint
+//! of the sessions and tasks in the window, so any write that touches them
+//! (which bumps `updated_at`) invalidates the workspace's cached days.
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Timelike, Utc};
+use orcs_core::error::{OrcsError, Result};
+use orcs_core::repository::SessionRepository;
+use orcs_core::session::{MessageRole, SessionSummary};
+use orcs_core::task::{Task, TaskRepository, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Longest window accepted by [`ActivityService::get_workspace_activity`].
+pub const MAX_ACTIVITY_DAYS: u32 = 366;
+
+/// Activity counts for one bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityCounts {
+    pub user_messages: usize,
+    pub assistant_messages: usize,
+    pub tasks_completed: usize,
+}
+
+impl ActivityCounts {
+    fn add(&mut self, other: &ActivityCounts) {
+        self.user_messages += other.user_messages;
+        self.assistant_messages += other.assistant_messages;
+        self.tasks_completed += other.tasks_completed;
+    }
+}
+
+/// Activity on one local calendar day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayActivity {
+    /// Local date (`YYYY-MM-DD`).
+    pub date: String,
+    #[serde(flatten)]
+    pub counts: ActivityCounts,
+}
+
+/// Activity in one local hour of day, summed over the window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HourActivity {
+    /// Hour of day (0-23).
+    pub hour: u32,
+    #[serde(flatten)]
+    pub counts: ActivityCounts,
+}
+
+/// Per-day and per-hour-of-day activity of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityHistogram {
+    pub workspace_id: String,
+    /// Offset from UTC, in minutes, used for bucketing.
+    pub tz_offset_minutes: i32,
+    /// One entry per day, oldest first, ending today.
+    pub daily: Vec<DayActivity>,
+    /// 24 entries, hour 0 first.
+    pub hourly: Vec<HourActivity>,
+    pub totals: ActivityCounts,
+}
+
+/// Counts for one day, split by hour.
+#[derive(Debug, Clone, Copy, Default)]
+struct DayBucket {
+    hours: [ActivityCounts; 24],
+}
+
+impl DayBucket {
+    fn total(&self) -> ActivityCounts {
+        let mut total = ActivityCounts::default();
+        for hour in &self.hours {
+            total.add(hour);
+        }
+        total
+    }
+}
+
+/// Cached days of one workspace, valid while the fingerprint matches.
+struct WorkspaceActivityCache {
+    tz_offset_minutes: i32,
+    fingerprint: u64,
+    days: HashMap<NaiveDate, DayBucket>,
+}
+
+/// Service computing workspace activity histograms.
+pub struct ActivityService {
+    session_repository: Arc<dyn SessionRepository>,
+    task_repository: Arc<dyn TaskRepository>,
+    cache: Mutex<HashMap<String, WorkspaceActivityCache>>,
+}
+
+impl ActivityService {
+    pub fn new(
+        session_repository: Arc<dyn SessionRepository>,
+        task_repository: Arc<dyn TaskRepository>,
+    ) -> Self {
+        Self {
+            session_repository,
+            task_repository,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns activity for the last `days` local days (including today).
+    ///
+    /// `tz_offset_minutes` is the user's offset from UTC (e.g. `540` for UTC+9);
+    /// all buckets use that local time.
+    pub async fn get_workspace_activity(
+        &self,
+        workspace_id: &str,
+        days: u32,
+        tz_offset_minutes: i32,
+    ) -> Result<ActivityHistogram> {
+        self.activity_at(workspace_id, days, tz_offset_minutes, Utc::now())
+            .await
+    }
+
+    async fn activity_at(
+        &self,
+        workspace_id: &str,
+        days: u32,
+        tz_offset_minutes: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ActivityHistogram> {
+        if days == 0 || days > MAX_ACTIVITY_DAYS {
+            return Err(OrcsError::config(format!(
+                "days must be between 1 and {}",
+                MAX_ACTIVITY_DAYS
+            )));
+        }
+        let offset = FixedOffset::east_opt(tz_offset_minutes * 60).ok_or_else(|| {
+            OrcsError::config(format!("Invalid timezone offset: {}", tz_offset_minutes))
+        })?;
+
+        let today = now.with_timezone(&offset).date_naive();
+        let first_day = today - Duration::days(i64::from(days) - 1);
+        let window_start = first_day
+            .and_hms_opt(0, 0, 0)
+            .and_then(|t| t.and_local_timezone(offset).single())
+            .map(|t| t.with_timezone(&Utc))
+            .ok_or_else(|| OrcsError::internal("Failed to compute activity window"))?;
+
+        let workspace_sessions: Vec<SessionSummary> = self
+            .session_repository
+            .list_summaries()
+            .await?
+            .into_iter()
+            .filter(|s| s.workspace_id == workspace_id)
+            .collect();
+        let session_ids: HashSet<&str> = workspace_sessions.iter().map(|s| s.id.as_str()).collect();
+
+        // Sessions not updated since the window opened cannot hold messages inside it
+        let sessions_in_window: Vec<&SessionSummary> = workspace_sessions
+            .iter()
+            .filter(|s| parse_time(&s.updated_at).is_none_or(|t| t >= window_start))
+            .collect();
+        let completed_tasks: Vec<(Task, DateTime<Utc>)> = self
+            .task_repository
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|t| {
+                t.status == TaskStatus::Completed && session_ids.contains(t.session_id.as_str())
+            })
+            .filter_map(|t| {
+                let completed_at = parse_time(t.completed_at.as_deref().unwrap_or(&t.updated_at))?;
+                (completed_at >= window_start).then_some((t, completed_at))
+            })
+            .collect();
+
+        let fingerprint = {
+            let mut hasher = DefaultHasher::new();
+            for summary in &sessions_in_window {
+                (&summary.id, &summary.updated_at).hash(&mut hasher);
+            }
+            for (task, _) in &completed_tasks {
+                (&task.id, &task.updated_at).hash(&mut hasher);
+            }
+            hasher.finish()
+        };
+        let window: Vec<NaiveDate> = first_day.iter_days().take(days as usize).collect();
+
+        let mut cache = self.cache.lock().await;
+        let entry =
+            cache
+                .entry(workspace_id.to_string())
+                .or_insert_with(|| WorkspaceActivityCache {
+                    tz_offset_minutes,
+                    fingerprint,
+                    days: HashMap::new(),
+                });
+        if entry.fingerprint != fingerprint || entry.tz_offset_minutes != tz_offset_minutes {
+            entry.fingerprint = fingerprint;
+            entry.tz_offset_minutes = tz_offset_minutes;
+            entry.days.clear();
+        }
+
+        if !window.iter().all(|day| entry.days.contains_key(day)) {
+            let mut buckets: HashMap<NaiveDate, DayBucket> = window
+                .iter()
+                .map(|day| (*day, DayBucket::default()))
+                .collect();
+            let mut record = |time: DateTime<Utc>, apply: &dyn Fn(&mut ActivityCounts)| {
+                let local = time.with_timezone(&offset);
+                if let Some(bucket) = buckets.get_mut(&local.date_naive()) {
+                    apply(&mut bucket.hours[local.hour() as usize]);
+                }
+            };
+
+            for summary in &sessions_in_window {
+                let Some(session) = self.session_repository.find_by_id(&summary.id).await? else {
+                    continue;
+                };
+                for message in session.persona_histories.values().flatten() {
+                    let Some(time) = parse_time(&message.timestamp) else {
+                        continue;
+                    };
+                    match message.role {
+                        MessageRole::User => record(time, &|c| c.user_messages += 1),
+                        MessageRole::Assistant => record(time, &|c| c.assistant_messages += 1),
+                        MessageRole::System => {}
+                    }
+                }
+            }
+            for (_, completed_at) in &completed_tasks {
+                record(*completed_at, &|c| c.tasks_completed += 1);
+            }
+
+            entry.days.extend(buckets);
+        }
+
+        let mut hourly = [ActivityCounts::default(); 24];
+        let mut totals = ActivityCounts::default();
+        let daily = window
+            .iter()
+            .map(|day| {
+                let bucket = entry.days.get(day).copied().unwrap_or_default();
+                for (hour, counts) in hourly.iter_mut().zip(bucket.hours.iter()) {
+                    hour.add(counts);
+                }
+                let counts = bucket.total();
+                totals.add(&counts);
+                DayActivity {
+                    date: day.format("%Y-%m-%d").to_string(),
+                    counts,
+                }
+            })
+            .collect();
+
+        Ok(ActivityHistogram {
+            workspace_id: workspace_id.to_string(),
+            tz_offset_minutes,
+            daily,
+            hourly: hourly
+                .into_iter()
+                .enumerate()
+                .map(|(hour, counts)| HourActivity {
+                    hour: hour as u32,
+                    counts,
+                })
+                .collect(),
+            totals,
+        })
+    }
+}
+
+fn parse_time(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_toolkit::agent::dialogue::ExecutionModel;
+    use orcs_core::session::{AppMode, ConversationMessage, MessageMetadata, Session};
+    use orcs_infrastructure::{AsyncDirSessionRepository, AsyncDirTaskRepository};
+    use tempfile::TempDir;
+
+    const TASK_ID: &str = "3a1f6c2e-8b4d-4f7a-9c0e-5d2b7a9e1f33";
+    const JST: i32 = 9 * 60;
+
+    fn message(role: MessageRole, timestamp: &str) -> ConversationMessage {
+        ConversationMessage {
+            role,
+            content: "hi".to_string(),
+            timestamp: timestamp.to_string(),
+            metadata: MessageMetadata::default(),
+            attachments: vec![],
+        }
+    }
+
+    fn session(
+        id: &str,
+        workspace_id: &str,
+        updated_at: &str,
+        messages: Vec<ConversationMessage>,
+    ) -> Session {
+        Session {
+            id: id.to_string(),
+            title: format!("Session {}", id),
+            created_at: "2026-10-01T00:00:00Z".to_string(),
+            updated_at: updated_at.to_string(),
+            current_persona_id: String::new(),
+            persona_histories: HashMap::from([("mai".to_string(), messages)]),
+            app_mode: AppMode::Idle,
+            workspace_id: workspace_id.to_string(),
+            active_participant_ids: vec![],
+            execution_strategy: ExecutionModel::Broadcast,
+            system_messages: vec![],
+            participants: HashMap::new(),
+            participant_icons: HashMap::new(),
+            participant_colors: HashMap::new(),
+            participant_backends: HashMap::new(),
+            participant_models: HashMap::new(),
+            conversation_mode: Default::default(),
+            talk_style: None,
+            is_favorite: false,
+            is_archived: false,
+            sort_order: None,
+            auto_chat_config: None,
+            is_muted: false,
+            context_mode: Default::default(),
+            sandbox_state: None,
+            last_memory_sync_at: None,
+            is_generating: false,
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: 8_000,
+        }
+    }
+
+    fn completed_task(session_id: &str, completed_at: &str) -> Task {
+        Task {
+            id: TASK_ID.to_string(),
+            session_id: session_id.to_string(),
+            title: "Build".to_string(),
+            description: String::new(),
+            status: TaskStatus::Completed,
+            created_at: completed_at.to_string(),
+            updated_at: completed_at.to_string(),
+            completed_at: Some(completed_at.to_string()),
+            steps_executed: 1,
+            steps_skipped: 0,
+            context_keys: 0,
+            error: None,
+            result: None,
+            execution_details: None,
+            strategy: None,
+            journal_log: None,
+            origin_message_timestamp: None,
+            origin_author: None,
+            dry_run: false,
+        }
+    }
+
+    fn counts(user: usize, assistant: usize, tasks: usize) -> ActivityCounts {
+        ActivityCounts {
+            user_messages: user,
+            assistant_messages: assistant,
+            tasks_completed: tasks,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_activity_buckets_by_local_day_and_hour() {
+        // Each repository treats an existing base directory as its own root
+        let dirs = [(); 2].map(|_| TempDir::new().unwrap());
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let tasks = Arc::new(
+            AsyncDirTaskRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+
+        // 14:30Z is 23:30 on the 17th in JST; 15:30Z is 00:30 on the 18th
+        let active = session(
+            "s-active",
+            "ws-1",
+            "2026-10-17T16:00:00Z",
+            vec![
+                message(MessageRole::User, "2026-10-17T14:30:00Z"),
+                message(MessageRole::Assistant, "2026-10-17T14:31:00Z"),
+                message(MessageRole::User, "2026-10-17T15:30:00Z"),
+                message(MessageRole::Assistant, "2026-10-17T15:31:00Z"),
+                message(MessageRole::Assistant, "2026-10-17T15:32:00Z"),
+                message(MessageRole::System, "2026-10-17T15:33:00Z"),
+                // Before the window
+                message(MessageRole::User, "2026-10-01T00:00:00Z"),
+            ],
+        );
+        let other_workspace = session(
+            "s-other",
+            "ws-2",
+            "2026-10-17T16:00:00Z",
+            vec![message(MessageRole::User, "2026-10-17T15:30:00Z")],
+        );
+        sessions.save(&active).await.unwrap();
+        sessions.save(&other_workspace).await.unwrap();
+        tasks
+            .save(&completed_task("s-active", "2026-10-17T15:45:00Z"))
+            .await
+            .unwrap();
+
+        let service = ActivityService::new(sessions.clone(), tasks);
+        let now = DateTime::parse_from_rfc3339("2026-10-18T03:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let histogram = service.activity_at("ws-1", 3, JST, now).await.unwrap();
+
+        let daily: Vec<(&str, ActivityCounts)> = histogram
+            .daily
+            .iter()
+            .map(|d| (d.date.as_str(), d.counts))
+            .collect();
+        assert_eq!(
+            daily,
+            vec![
+                ("2026-10-16", counts(0, 0, 0)),
+                ("2026-10-17", counts(1, 1, 0)),
+                ("2026-10-18", counts(1, 2, 1)),
+            ]
+        );
+        assert_eq!(histogram.hourly.len(), 24);
+        assert_eq!(histogram.hourly[23].counts, counts(1, 1, 0));
+        assert_eq!(histogram.hourly[0].counts, counts(1, 2, 1));
+        assert_eq!(histogram.totals, counts(2, 3, 1));
+
+        // In UTC the same messages all fall on the 17th
+        let utc = service.activity_at("ws-1", 3, 0, now).await.unwrap();
+        assert_eq!(utc.daily[2].date, "2026-10-18");
+        assert_eq!(utc.daily[1].counts, counts(2, 3, 1));
+
+        // A write bumps updated_at and invalidates the cached days
+        let mut updated = active.clone();
+        updated.updated_at = "2026-10-18T02:00:00Z".to_string();
+        updated
+            .persona_histories
+            .get_mut("mai")
+            .unwrap()
+            .push(message(MessageRole::User, "2026-10-18T01:00:00Z"));
+        sessions.save(&updated).await.unwrap();
+
+        let histogram = service.activity_at("ws-1", 3, JST, now).await.unwrap();
+        assert_eq!(histogram.daily[2].counts, counts(2, 2, 1));
+        assert_eq!(histogram.hourly[10].counts, counts(1, 0, 0));
+
+        assert!(service.activity_at("ws-1", 0, JST, now).await.is_err());
+    }
+}
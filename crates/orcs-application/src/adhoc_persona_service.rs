@@ -8,7 +8,9 @@ use anyhow::Result;
 use llm_toolkit::ToPrompt;
 use llm_toolkit::agent::Agent;
 use orcs_core::agent::build_enhanced_path;
-use orcs_core::persona::{Persona, PersonaBackend, PersonaSource};
+use orcs_core::persona::{
+    CreatePersonaRequest, Persona, PersonaBackend, PersonaOverrides, PersonaSource,
+};
 use orcs_core::repository::PersonaRepository;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -123,6 +125,12 @@ impl AdhocPersonaService {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         // Save adhoc persona to repository (temporary)
@@ -187,11 +195,287 @@ impl AdhocPersonaService {
 
         Ok(saved_persona)
     }
+
+    /// Creates a new user persona from an existing one with `overrides` applied.
+    ///
+    /// The clone gets a new UUID and is never a system preset. Without a name
+    /// override it is named after the source with a "(copy)" suffix. An
+    /// uploaded icon is shared with the source unless an emoji icon is given.
+    /// The source persona is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source does not exist, the cloned definition
+    /// is invalid (e.g. its name is taken), or the clone cannot be saved.
+    pub async fn clone_persona(
+        &self,
+        persona_id: &str,
+        overrides: PersonaOverrides,
+    ) -> Result<Persona> {
+        let source = self
+            .persona_repository
+            .find_by_id(persona_id)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?
+            .ok_or_else(|| anyhow::anyhow!("Persona not found: {}", persona_id))?;
+        let existing = self
+            .persona_repository
+            .get_all_including_archived()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        let mut request = CreatePersonaRequest::from_persona(&source);
+        request.name = copy_name(&source.name, &existing);
+        let icon_overridden = overrides.icon.is_some();
+        overrides.apply_to(&mut request);
+        request.validate_against(&existing, None)?;
+
+        let mut persona = request.into_persona();
+        if !icon_overridden {
+            persona.icon_asset = source.icon_asset.clone();
+        }
+        self.persona_repository
+            .save(&persona)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        tracing::info!(
+            "[AdhocPersonaService] Cloned persona {} ({}) as {} ({})",
+            source.name,
+            source.id,
+            persona.name,
+            persona.id
+        );
+        Ok(persona)
+    }
+
+    /// Lists all personas marked as default participants.
+    ///
+    /// Sorted by name (case-insensitive), then id, so the order does not
+    /// depend on storage iteration order.
+    pub async fn default_participants(&self) -> Result<Vec<Persona>> {
+        let mut personas: Vec<Persona> = self
+            .persona_repository
+            .get_all()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?
+            .into_iter()
+            .filter(|p| p.default_participant)
+            .collect();
+        personas.sort_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(personas)
+    }
+
+    /// Searches personas whose name, role, or background contains `query`
+    /// (case-insensitive).
+    ///
+    /// Name matches rank first, then role, then background; ties are sorted
+    /// by name, then id. A blank query matches nothing.
+    pub async fn search_personas(&self, query: &str) -> Result<Vec<Persona>> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut ranked: Vec<(usize, Persona)> = self
+            .persona_repository
+            .get_all()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?
+            .into_iter()
+            .filter_map(|p| {
+                [&p.name, &p.role, &p.background]
+                    .iter()
+                    .position(|field| field.to_lowercase().contains(&query))
+                    .map(|rank| (rank, p))
+            })
+            .collect();
+        ranked.sort_by(|(rank_a, a), (rank_b, b)| {
+            rank_a
+                .cmp(rank_b)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(ranked.into_iter().map(|(_, p)| p).collect())
+    }
+}
+
+/// Default name of a clone of `name`: "<name> (copy)", numbered when taken.
+fn copy_name(name: &str, existing: &[Persona]) -> String {
+    let taken = |candidate: &str| {
+        existing
+            .iter()
+            .any(|p| p.name.trim().eq_ignore_ascii_case(candidate))
+    };
+    let mut candidate = format!("{} (copy)", name.trim());
+    let mut n = 2;
+    while taken(&candidate) {
+        candidate = format!("{} (copy {})", name.trim(), n);
+        n += 1;
+    }
+    candidate
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use orcs_infrastructure::AsyncDirPersonaRepository;
+    use tempfile::TempDir;
+
+    fn persona(id: &str, name: &str, default_participant: bool) -> Persona {
+        Persona {
+            id: id.to_string(),
+            name: name.to_string(),
+            role: "Engineer".to_string(),
+            background: "Background".to_string(),
+            communication_style: "Direct".to_string(),
+            default_participant,
+            source: PersonaSource::User,
+            backend: PersonaBackend::ClaudeCli,
+            model_name: None,
+            icon: None,
+            base_color: None,
+            gemini_options: None,
+            kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_participants_returns_only_defaulted_personas() {
+        let dir = TempDir::new().unwrap();
+        let repository = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dir.path()))
+                .await
+                .unwrap(),
+        );
+        repository
+            .save_all(&[
+                persona("6fa459ea-ee8a-3ca4-894e-db77e160355e", "yui", true),
+                persona("886313e1-3b8a-5372-9b90-0c9aee199e5d", "Ken", false),
+                persona("1b4e28ba-2fa1-11d2-883f-0016d3cca427", "Mai", true),
+            ])
+            .await
+            .unwrap();
+
+        let service = AdhocPersonaService::new(repository);
+        let names: Vec<String> = service
+            .default_participants()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["Mai", "yui"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_personas_matches_background_and_ranks_names_first() {
+        let dir = TempDir::new().unwrap();
+        let repository = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dir.path()))
+                .await
+                .unwrap(),
+        );
+        let mut historian = persona("6fa459ea-ee8a-3ca4-894e-db77e160355e", "Yui", false);
+        historian.background = "Studied Film history for a decade".to_string();
+        let mut critic = persona("886313e1-3b8a-5372-9b90-0c9aee199e5d", "Film Critic", false);
+        critic.background = "Reviews movies".to_string();
+        repository
+            .save_all(&[
+                historian,
+                critic,
+                persona("1b4e28ba-2fa1-11d2-883f-0016d3cca427", "Mai", true),
+            ])
+            .await
+            .unwrap();
+
+        let service = AdhocPersonaService::new(repository);
+        let names = |personas: Vec<Persona>| -> Vec<String> {
+            personas.into_iter().map(|p| p.name).collect()
+        };
+        assert_eq!(
+            names(service.search_personas("HISTORY").await.unwrap()),
+            vec!["Yui"]
+        );
+        assert_eq!(
+            names(service.search_personas("film").await.unwrap()),
+            vec!["Film Critic", "Yui"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_persona_applies_overrides_with_new_id() {
+        let dir = TempDir::new().unwrap();
+        let repository = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dir.path()))
+                .await
+                .unwrap(),
+        );
+        let mut source = persona("1b4e28ba-2fa1-11d2-883f-0016d3cca427", "Mai", true);
+        source.source = PersonaSource::System;
+        source.background = "Builds reliable backend services".to_string();
+        source.communication_style = "Direct and concise".to_string();
+        repository.save(&source).await.unwrap();
+
+        let service = AdhocPersonaService::new(repository.clone());
+        let clone = service
+            .clone_persona(
+                &source.id,
+                PersonaOverrides {
+                    name: Some("Mai Reviewer".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_ne!(clone.id, source.id);
+        assert!(uuid::Uuid::parse_str(&clone.id).is_ok());
+        assert_eq!(clone.name, "Mai Reviewer");
+        assert_eq!(clone.background, source.background);
+        assert_eq!(clone.source, PersonaSource::User);
+        assert_eq!(
+            repository
+                .find_by_id(&source.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .name,
+            "Mai"
+        );
+        assert_eq!(
+            repository
+                .find_by_id(&clone.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .name,
+            "Mai Reviewer"
+        );
+
+        // Without a name override, clones get distinct "(copy)" names
+        let first = service
+            .clone_persona(&source.id, PersonaOverrides::default())
+            .await
+            .unwrap();
+        let second = service
+            .clone_persona(&source.id, PersonaOverrides::default())
+            .await
+            .unwrap();
+        assert_eq!(first.name, "Mai (copy)");
+        assert_eq!(second.name, "Mai (copy 2)");
+    }
 
     #[test]
     fn test_expert_prompt_dto_to_prompt_debug() {
@@ -0,0 +1,726 @@
+//! Code Apply Service
+//!
+//! Review loop for code proposed by agents as fenced blocks: a block the user
+//! marks for applying is written to a file in the session's workspace (its
+//! sandbox worktree, if any), then a verification command such as `cargo check`
+//! runs there. The outcome is posted back to the session as context info, so
+//! agents see it on their next turn and can iterate.
+//!
+//! Each step stops the flow when it fails, leaving the effects of earlier steps
+//! in place; the [`ApplyReport`] tells which step it stopped at. A verification
+//! command the shell policy considers destructive is not run: it is queued as a
+//! pending action for the user to confirm instead.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use orcs_core::agent::build_enhanced_path;
+use orcs_core::config::ShellPolicy;
+use orcs_core::pending_action::{DestructiveCommandDetector, PendingAction, PendingActionKind};
+use orcs_core::session::{
+    PLACEHOLDER_WORKSPACE_ID, Session, extract_code_blocks, timestamps_match,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{PendingActionRequest, PendingActionService, SessionUseCase};
+
+/// Longest verification output kept in reports and notes, in characters.
+pub const MAX_VERIFY_OUTPUT_CHARS: usize = 4_000;
+
+/// Message type of the notes posted back to the session.
+const NOTE_MESSAGE_TYPE: &str = "context_info";
+
+/// Output of a finished shell command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellOutput {
+    /// Exit code, or `None` when the process was terminated by a signal.
+    pub exit_code: Option<i32>,
+    /// Standard output followed by standard error.
+    pub output: String,
+}
+
+/// Runs verification commands (replaceable in tests).
+#[async_trait]
+pub trait ShellRunner: Send + Sync {
+    /// Runs `command` in `working_dir`.
+    ///
+    /// A non-zero exit is reported through [`ShellOutput::exit_code`]; `Err`
+    /// means the command could not be started at all.
+    async fn run(&self, command: &str, working_dir: &Path) -> Result<ShellOutput>;
+}
+
+/// Runs commands with the system shell and the workspace's tool directories
+/// on `PATH`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemShell;
+
+#[async_trait]
+impl ShellRunner for SystemShell {
+    async fn run(&self, command: &str, working_dir: &Path) -> Result<ShellOutput> {
+        let mut cmd = if cfg!(target_os = "windows") {
+            let mut c = tokio::process::Command::new("cmd");
+            c.args(["/C", command]);
+            c
+        } else {
+            let mut c = tokio::process::Command::new("sh");
+            c.args(["-c", command]);
+            c
+        };
+        let output = cmd
+            .current_dir(working_dir)
+            .env("PATH", build_enhanced_path(working_dir, None))
+            .output()
+            .await
+            .map_err(|e| anyhow!("Failed to execute command: {}", e))?;
+
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok(ShellOutput {
+            exit_code: output.status.code(),
+            output: text,
+        })
+    }
+}
+
+/// Step of the apply-and-verify flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApplyStage {
+    /// Finding the message, the code block and the target file.
+    Locate,
+    /// Writing the code block to the target file.
+    Write,
+    /// Running the verification command.
+    Verify,
+}
+
+/// Which code block to apply, where, and how to verify it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyRequest {
+    pub session_id: String,
+    /// Timestamp of the message containing the code block.
+    pub message_timestamp: String,
+    /// Zero-based index of the code block within the message.
+    pub block_index: usize,
+    /// Target file, relative to the workspace root or absolute inside it.
+    pub target_path: String,
+    /// Command run in the workspace root after writing, e.g. `cargo check`.
+    pub verify_command: Option<String>,
+}
+
+/// Result of running a verification command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyResult {
+    pub command: String,
+    /// Exit code, or `None` when the process was terminated by a signal.
+    pub exit_code: Option<i32>,
+    /// Combined output, cut to [`MAX_VERIFY_OUTPUT_CHARS`].
+    pub output: String,
+    /// Whether `output` was cut.
+    pub truncated: bool,
+}
+
+impl VerifyResult {
+    /// Returns true if the command exited with code 0.
+    pub fn passed(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Per-step results of [`CodeApplyService::apply_and_verify`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyReport {
+    /// File the code block was written to.
+    pub written_path: Option<String>,
+    /// Outcome of the verification command, if it ran.
+    pub verification: Option<VerifyResult>,
+    /// Pending action holding a destructive verification command.
+    pub pending_action: Option<PendingAction>,
+    /// Step the flow stopped at, if one failed.
+    pub stopped_at: Option<ApplyStage>,
+    /// Why that step failed.
+    pub error: Option<String>,
+}
+
+impl ApplyReport {
+    fn stop(mut self, stage: ApplyStage, error: impl Into<String>) -> Self {
+        self.stopped_at = Some(stage);
+        self.error = Some(error.into());
+        self
+    }
+}
+
+/// Applies agent-proposed code blocks to workspace files and verifies them.
+pub struct CodeApplyService {
+    sessions: Arc<SessionUseCase>,
+    pending_actions: Arc<PendingActionService>,
+    shell: Arc<dyn ShellRunner>,
+}
+
+impl CodeApplyService {
+    /// Creates a new service running verification commands with [`SystemShell`].
+    pub fn new(sessions: Arc<SessionUseCase>, pending_actions: Arc<PendingActionService>) -> Self {
+        Self {
+            sessions,
+            pending_actions,
+            shell: Arc::new(SystemShell),
+        }
+    }
+
+    /// Runs verification commands with `shell` instead of the system shell.
+    pub fn with_shell(mut self, shell: Arc<dyn ShellRunner>) -> Self {
+        self.shell = shell;
+        self
+    }
+
+    /// Writes a code block of a message to a workspace file and runs the
+    /// verification command, posting the outcome to the session as context info.
+    ///
+    /// Step failures are reported in the returned [`ApplyReport`]; files written
+    /// before the failure are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist or cannot be loaded.
+    pub async fn apply_and_verify(
+        &self,
+        request: ApplyRequest,
+        shell_policy: &ShellPolicy,
+    ) -> Result<ApplyReport> {
+        let session = self.sessions.current_session(&request.session_id).await?;
+        let session = self.sessions.enrich_session_participants(session).await;
+        let block_label = format!("code block {}", request.block_index + 1);
+        let mut report = ApplyReport::default();
+
+        // Locate the block and the file it goes to
+        let turn = session
+            .conversation_turns()
+            .into_iter()
+            .find(|turn| timestamps_match(&turn.timestamp, &request.message_timestamp));
+        let Some(turn) = turn else {
+            let report = report.stop(
+                ApplyStage::Locate,
+                format!("Message not found: {}", request.message_timestamp),
+            );
+            return Ok(self.finish(&request, &block_label, None, report).await);
+        };
+        let Some(block) = extract_code_blocks(&turn.content)
+            .into_iter()
+            .nth(request.block_index)
+        else {
+            let report = report.stop(
+                ApplyStage::Locate,
+                format!("The message has no {}", block_label),
+            );
+            return Ok(self.finish(&request, &block_label, None, report).await);
+        };
+        let block_label = format!("{} from {}", block_label, turn.author);
+        let root = match self.workspace_root(&session).await {
+            Ok(root) => root,
+            Err(e) => {
+                let report = report.stop(ApplyStage::Locate, e.to_string());
+                return Ok(self.finish(&request, &block_label, None, report).await);
+            }
+        };
+        let target = match resolve_in_root(&root, &request.target_path) {
+            Ok(target) => target,
+            Err(e) => {
+                let report = report.stop(ApplyStage::Locate, e.to_string());
+                return Ok(self.finish(&request, &block_label, None, report).await);
+            }
+        };
+
+        // Write the block
+        let mut code = block.code;
+        if !code.ends_with('\n') {
+            code.push('\n');
+        }
+        if let Some(parent) = target.parent()
+            && let Err(e) = tokio::fs::create_dir_all(parent).await
+        {
+            let report = report.stop(
+                ApplyStage::Write,
+                format!("Failed to create directory: {}", e),
+            );
+            return Ok(self.finish(&request, &block_label, None, report).await);
+        }
+        if let Err(e) = tokio::fs::write(&target, code).await {
+            let report = report.stop(ApplyStage::Write, format!("Failed to write file: {}", e));
+            return Ok(self.finish(&request, &block_label, None, report).await);
+        }
+        report.written_path = Some(target.to_string_lossy().into_owned());
+
+        // Verify, unless the command needs the user's confirmation first
+        let Some(command) = request
+            .verify_command
+            .clone()
+            .filter(|command| !command.trim().is_empty())
+        else {
+            return Ok(self.finish(&request, &block_label, None, report).await);
+        };
+        if DestructiveCommandDetector::from_policy(shell_policy).is_destructive(&command) {
+            let action = self
+                .pending_actions
+                .enqueue(PendingActionRequest {
+                    kind: PendingActionKind::Shell,
+                    session_id: request.session_id.clone(),
+                    requested_by: turn.author.clone(),
+                    rendered_command: command.clone(),
+                    command_name: None,
+                    args: None,
+                    working_dir: Some(root.to_string_lossy().into_owned()),
+                })
+                .await;
+            report.pending_action = Some(action);
+            return Ok(self
+                .finish(&request, &block_label, Some(&command), report)
+                .await);
+        }
+        match self.shell.run(&command, &root).await {
+            Ok(result) => {
+                let (output, truncated) = truncate_output(result.output.trim_end());
+                let verification = VerifyResult {
+                    command: command.clone(),
+                    exit_code: result.exit_code,
+                    output,
+                    truncated,
+                };
+                let failure = (!verification.passed()).then(|| match verification.exit_code {
+                    Some(code) => format!("`{}` exited with code {}", command, code),
+                    None => format!("`{}` was terminated", command),
+                });
+                report.verification = Some(verification);
+                if let Some(failure) = failure {
+                    report = report.stop(ApplyStage::Verify, failure);
+                }
+            }
+            Err(e) => report = report.stop(ApplyStage::Verify, e.to_string()),
+        }
+        Ok(self
+            .finish(&request, &block_label, Some(&command), report)
+            .await)
+    }
+
+    /// Returns the directory code is applied in: the session's sandbox worktree,
+    /// or else its workspace root.
+    async fn workspace_root(&self, session: &Session) -> Result<PathBuf> {
+        if let Some(sandbox) = &session.sandbox_state {
+            return Ok(PathBuf::from(&sandbox.worktree_path));
+        }
+        if session.workspace_id == PLACEHOLDER_WORKSPACE_ID {
+            return Err(anyhow!("No workspace associated with the session"));
+        }
+        let workspace = self
+            .sessions
+            .workspace_storage_service()
+            .get_workspace(&session.workspace_id)
+            .await?
+            .ok_or_else(|| anyhow!("Workspace not found: {}", session.workspace_id))?;
+        Ok(workspace.root_path)
+    }
+
+    /// Posts the outcome to the session and logs it; returns `report`.
+    async fn finish(
+        &self,
+        request: &ApplyRequest,
+        block_label: &str,
+        command: Option<&str>,
+        report: ApplyReport,
+    ) -> ApplyReport {
+        let note = outcome_note(block_label, command, &report);
+        tracing::info!(
+            "[CodeApply] {} in session {}: stopped at {:?}",
+            block_label,
+            request.session_id,
+            report.stopped_at
+        );
+        if let Err(e) = self
+            .sessions
+            .add_system_message_to_session(
+                &request.session_id,
+                note,
+                Some(NOTE_MESSAGE_TYPE.to_string()),
+                None,
+            )
+            .await
+        {
+            tracing::warn!("[CodeApply] Failed to post outcome note: {}", e);
+        }
+        report
+    }
+}
+
+/// Describes the outcome of a flow for the agents.
+fn outcome_note(block_label: &str, command: Option<&str>, report: &ApplyReport) -> String {
+    let Some(path) = &report.written_path else {
+        return format!(
+            "⚠️ Could not apply {}: {}",
+            block_label,
+            report.error.as_deref().unwrap_or("unknown error")
+        );
+    };
+    let applied = format!("Applied {} to `{}`.", block_label, path);
+    let Some(command) = command else {
+        return format!("✅ {}", applied);
+    };
+    if let Some(action) = &report.pending_action {
+        return format!(
+            "⏸ {} Verification `{}` requires user confirmation and was not executed yet (pending action {}).",
+            applied, command, action.id
+        );
+    }
+    match &report.verification {
+        Some(verification) => {
+            let truncated = if verification.truncated {
+                "\n… (output truncated)"
+            } else {
+                ""
+            };
+            let status = if verification.passed() {
+                format!("✅ {} Verification `{}` passed", applied, command)
+            } else {
+                format!(
+                    "⚠️ {} Verification failed: {}",
+                    applied,
+                    report.error.as_deref().unwrap_or("unknown error")
+                )
+            };
+            format!(
+                "{}.\n\nOutput:\n```\n{}{}\n```",
+                status, verification.output, truncated
+            )
+        }
+        None => format!(
+            "⚠️ {} Verification `{}` could not run: {}",
+            applied,
+            command,
+            report.error.as_deref().unwrap_or("unknown error")
+        ),
+    }
+}
+
+/// Resolves `target` against `root`, refusing paths that leave it.
+///
+/// `..` components are resolved lexically, as the file may not exist yet; the
+/// nearest existing ancestor is then canonicalized so symlinks cannot lead out
+/// of the root either.
+fn resolve_in_root(root: &Path, target: &str) -> Result<PathBuf> {
+    let outside = || anyhow!("Refusing to write outside the workspace root: {}", target);
+    if target.trim().is_empty() {
+        return Err(anyhow!("No target path given"));
+    }
+
+    let mut resolved = PathBuf::new();
+    for component in root.join(target).components() {
+        match component {
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return Err(outside());
+                }
+            }
+            Component::CurDir => {}
+            other => resolved.push(other),
+        }
+    }
+    if !resolved.starts_with(root) || resolved == root {
+        return Err(outside());
+    }
+
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| anyhow!("Workspace root is not accessible: {}", e))?;
+    let existing = resolved
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(root);
+    let canonical = existing.canonicalize()?;
+    if !canonical.starts_with(&canonical_root) {
+        return Err(outside());
+    }
+    Ok(resolved)
+}
+
+/// Cuts `output` to [`MAX_VERIFY_OUTPUT_CHARS`], keeping its beginning where
+/// the first errors are. Returns whether it was cut.
+fn truncate_output(output: &str) -> (String, bool) {
+    match output.char_indices().nth(MAX_VERIFY_OUTPUT_CHARS) {
+        Some((cut, _)) => (output[..cut].to_string(), true),
+        None => (output.to_string(), false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use orcs_core::repository::SessionRepository;
+    use orcs_core::session::{ConversationMessage, MessageRole};
+    use orcs_core::workspace::manager::WorkspaceStorageService;
+    use orcs_infrastructure::user_service::ConfigBasedUserService;
+    use orcs_infrastructure::workspace_storage_service::FileSystemWorkspaceManager;
+    use orcs_infrastructure::{
+        AppStateService, AsyncDirPersonaRepository, AsyncDirSessionRepository,
+    };
+    use tempfile::TempDir;
+
+    const TIMESTAMP: &str = "2026-01-01T10:00:00.000000+00:00";
+
+    /// Shell stub that records commands and answers with a fixed result.
+    struct StubShell {
+        result: ShellOutput,
+        calls: Mutex<Vec<(String, PathBuf)>>,
+    }
+
+    impl StubShell {
+        fn exiting(exit_code: i32, output: &str) -> Arc<Self> {
+            Arc::new(Self {
+                result: ShellOutput {
+                    exit_code: Some(exit_code),
+                    output: output.to_string(),
+                },
+                calls: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl ShellRunner for StubShell {
+        async fn run(&self, command: &str, working_dir: &Path) -> Result<ShellOutput> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((command.to_string(), working_dir.to_path_buf()));
+            Ok(self.result.clone())
+        }
+    }
+
+    struct Fixture {
+        _dirs: [TempDir; 5],
+        root: PathBuf,
+        session_id: String,
+        sessions: Arc<AsyncDirSessionRepository>,
+        pending_actions: Arc<PendingActionService>,
+        usecase: Arc<SessionUseCase>,
+    }
+
+    impl Fixture {
+        async fn new() -> Self {
+            let dirs = [(); 5].map(|_| TempDir::new().unwrap());
+            let workspaces = Arc::new(
+                FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                    .await
+                    .unwrap(),
+            );
+            let sessions = Arc::new(
+                AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                    .await
+                    .unwrap(),
+            );
+            let personas = Arc::new(
+                AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                    .await
+                    .unwrap(),
+            );
+            let app_state = Arc::new(
+                AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                    .await
+                    .unwrap(),
+            );
+            let new_usecase = || {
+                Arc::new(SessionUseCase::new(
+                    sessions.clone(),
+                    workspaces.clone(),
+                    app_state.clone(),
+                    personas.clone(),
+                    Arc::new(ConfigBasedUserService::new()),
+                ))
+            };
+
+            let root = dirs[4].path().join("project");
+            std::fs::create_dir_all(&root).unwrap();
+            let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+            let session = new_usecase().create_session(&workspace.id).await.unwrap();
+
+            let mut stored = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+            stored.persona_histories = HashMap::from([(
+                "mai".to_string(),
+                vec![ConversationMessage {
+                    role: MessageRole::Assistant,
+                    content: "Split the parser:\n\n```rust\npub fn parse() {}\n```\n\nand test it:\n```rust\n#[test]\nfn parses() {}\n```".to_string(),
+                    timestamp: TIMESTAMP.to_string(),
+                    metadata: Default::default(),
+                    attachments: vec![],
+                }],
+            )]);
+            sessions.save(&stored).await.unwrap();
+
+            // A fresh use case has not loaded the session, so it sees the stored turns
+            let usecase = new_usecase();
+            let pending_actions = Arc::new(PendingActionService::new(usecase.clone(), 60));
+            Self {
+                root: workspace.root_path,
+                _dirs: dirs,
+                session_id: session.id,
+                sessions,
+                pending_actions,
+                usecase,
+            }
+        }
+
+        fn service(&self, shell: Arc<StubShell>) -> CodeApplyService {
+            CodeApplyService::new(self.usecase.clone(), self.pending_actions.clone())
+                .with_shell(shell)
+        }
+
+        fn request(&self, target_path: &str, verify_command: Option<&str>) -> ApplyRequest {
+            ApplyRequest {
+                session_id: self.session_id.clone(),
+                // The frontend sends millisecond timestamps
+                message_timestamp: "2026-01-01T10:00:00.000Z".to_string(),
+                block_index: 1,
+                target_path: target_path.to_string(),
+                verify_command: verify_command.map(str::to_string),
+            }
+        }
+
+        async fn notes(&self) -> Vec<String> {
+            let session = self
+                .sessions
+                .find_by_id(&self.session_id)
+                .await
+                .unwrap()
+                .unwrap();
+            session
+                .system_messages
+                .into_iter()
+                .filter(|m| m.metadata.system_message_type.as_deref() == Some(NOTE_MESSAGE_TYPE))
+                .map(|m| m.content)
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_applies_block_and_reports_passing_verification() {
+        let fixture = Fixture::new().await;
+        let shell = StubShell::exiting(0, "Finished `dev` profile\n");
+
+        let report = fixture
+            .service(shell.clone())
+            .apply_and_verify(
+                fixture.request("tests/parser.rs", Some("cargo check")),
+                &ShellPolicy::default(),
+            )
+            .await
+            .unwrap();
+
+        let target = fixture.root.join("tests/parser.rs");
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            "#[test]\nfn parses() {}\n"
+        );
+        assert_eq!(
+            report.written_path.as_deref(),
+            Some(target.to_str().unwrap())
+        );
+        assert_eq!(report.stopped_at, None);
+        let verification = report.verification.unwrap();
+        assert_eq!(verification.exit_code, Some(0));
+        assert_eq!(verification.output, "Finished `dev` profile");
+        assert_eq!(
+            *shell.calls.lock().unwrap(),
+            vec![("cargo check".to_string(), fixture.root.clone())]
+        );
+
+        let notes = fixture.notes().await;
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].starts_with("✅ Applied code block 2 from mai"));
+        assert!(notes[0].contains("`cargo check` passed"));
+    }
+
+    #[tokio::test]
+    async fn test_failing_verification_keeps_written_file() {
+        let fixture = Fixture::new().await;
+        let output = format!("error[E0425]: cannot find function\n{}", "x".repeat(5_000));
+        let shell = StubShell::exiting(101, &output);
+
+        let report = fixture
+            .service(shell)
+            .apply_and_verify(
+                fixture.request("src/parser.rs", Some("cargo check")),
+                &ShellPolicy::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(fixture.root.join("src/parser.rs").exists());
+        assert!(report.written_path.is_some());
+        assert_eq!(report.stopped_at, Some(ApplyStage::Verify));
+        assert_eq!(
+            report.error.as_deref(),
+            Some("`cargo check` exited with code 101")
+        );
+        let verification = report.verification.unwrap();
+        assert!(verification.truncated);
+        assert_eq!(verification.output.chars().count(), MAX_VERIFY_OUTPUT_CHARS);
+        assert!(verification.output.starts_with("error[E0425]"));
+
+        let notes = fixture.notes().await;
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].contains("Verification failed: `cargo check` exited with code 101"));
+        assert!(notes[0].contains("error[E0425]"));
+    }
+
+    #[tokio::test]
+    async fn test_refuses_paths_outside_workspace_root() {
+        let fixture = Fixture::new().await;
+        let shell = StubShell::exiting(0, "");
+
+        let report = fixture
+            .service(shell.clone())
+            .apply_and_verify(
+                fixture.request("../escaped.rs", Some("cargo check")),
+                &ShellPolicy::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.stopped_at, Some(ApplyStage::Locate));
+        assert!(report.written_path.is_none());
+        assert!(!fixture.root.parent().unwrap().join("escaped.rs").exists());
+        assert!(shell.calls.lock().unwrap().is_empty());
+        assert!(fixture.notes().await[0].starts_with("⚠️ Could not apply"));
+    }
+
+    #[tokio::test]
+    async fn test_destructive_verification_waits_for_confirmation() {
+        let fixture = Fixture::new().await;
+        let shell = StubShell::exiting(0, "");
+
+        let report = fixture
+            .service(shell.clone())
+            .apply_and_verify(
+                fixture.request("src/parser.rs", Some("rm -rf target && cargo check")),
+                &ShellPolicy::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(report.written_path.is_some());
+        assert!(report.verification.is_none());
+        assert!(shell.calls.lock().unwrap().is_empty());
+        let action = report.pending_action.unwrap();
+        assert_eq!(action.rendered_command, "rm -rf target && cargo check");
+        assert_eq!(
+            action.working_dir.as_deref(),
+            Some(fixture.root.to_str().unwrap())
+        );
+        assert_eq!(fixture.pending_actions.list().await.len(), 1);
+    }
+}
@@ -0,0 +1,646 @@
+//! Backend of the Cmd-K command palette.
+//!
+//! One query fans out to slash commands (builtin and custom), sessions,
+//! personas, the uploaded files of the active session's workspace and tasks.
+//! Every source is scored with the same [`fuzzy_score`] and merged into one
+//! ranked list. The palette queries on every keystroke, so the sources are
+//! read into an in-memory index that is reused for [`INDEX_TTL`] instead of
+//! listing sessions and tasks from disk each time.
+
+use orcs_core::error::Result;
+use orcs_core::repository::{PersonaRepository, SessionRepository};
+use orcs_core::slash_command::{SlashCommandRepository, builtin_commands};
+use orcs_core::task::TaskRepository;
+use orcs_core::workspace::manager::WorkspaceStorageService;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// How long a built index is reused before the sources are read again.
+pub const INDEX_TTL: Duration = Duration::from_secs(10);
+
+/// What a palette item refers to.
+///
+/// Declaration order breaks ties between undated items, so commands come
+/// before personas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaletteKind {
+    Command,
+    Session,
+    Persona,
+    File,
+    Task,
+}
+
+/// What the frontend does when an item is picked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaletteAction {
+    /// Puts `/name ` into the chat input
+    InsertCommand(String),
+    /// Switches to the session with this ID
+    SwitchSession(String),
+    /// Adds the persona with this ID to the active session
+    AddParticipant(String),
+    /// Opens the workspace file at this path
+    OpenFile(String),
+    /// Opens the task with this ID
+    OpenTask(String),
+}
+
+/// A ranked palette result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteItem {
+    pub kind: PaletteKind,
+    pub title: String,
+    /// Secondary text: command description, persona role, task status, ...
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    pub action: PaletteAction,
+    /// Match score; higher is better, 0 for recent items of an empty query
+    pub score: u32,
+}
+
+/// A searchable item, as read from its source.
+#[derive(Debug, Clone)]
+struct PaletteEntry {
+    kind: PaletteKind,
+    title: String,
+    subtitle: Option<String>,
+    action: PaletteAction,
+    /// Last update (ISO 8601), for sessions and tasks
+    updated_at: Option<String>,
+    /// Owning workspace, for sessions and files
+    workspace_id: Option<String>,
+}
+
+struct CachedIndex {
+    built_at: Instant,
+    entries: Arc<Vec<PaletteEntry>>,
+}
+
+/// Answers palette queries from a cached index of every source.
+pub struct CommandPaletteService {
+    slash_command_repository: Arc<dyn SlashCommandRepository>,
+    session_repository: Arc<dyn SessionRepository>,
+    persona_repository: Arc<dyn PersonaRepository>,
+    workspace_storage_service: Arc<dyn WorkspaceStorageService>,
+    task_repository: Arc<dyn TaskRepository>,
+    index: RwLock<Option<CachedIndex>>,
+}
+
+impl CommandPaletteService {
+    pub fn new(
+        slash_command_repository: Arc<dyn SlashCommandRepository>,
+        session_repository: Arc<dyn SessionRepository>,
+        persona_repository: Arc<dyn PersonaRepository>,
+        workspace_storage_service: Arc<dyn WorkspaceStorageService>,
+        task_repository: Arc<dyn TaskRepository>,
+    ) -> Self {
+        Self {
+            slash_command_repository,
+            session_repository,
+            persona_repository,
+            workspace_storage_service,
+            task_repository,
+            index: RwLock::new(None),
+        }
+    }
+
+    /// Returns the `limit` best matches for `query`, best first.
+    ///
+    /// Files are only searched in the workspace of `active_session_id`. An
+    /// empty query returns the most recently updated sessions and tasks.
+    pub async fn query(
+        &self,
+        query: &str,
+        limit: usize,
+        active_session_id: Option<&str>,
+    ) -> Result<Vec<PaletteItem>> {
+        let entries = self.entries().await?;
+        let workspace_id = active_session_id.and_then(|session_id| {
+            entries
+                .iter()
+                .find(|e| e.action == PaletteAction::SwitchSession(session_id.to_string()))
+                .and_then(|e| e.workspace_id.clone())
+        });
+        Ok(rank(&entries, query, limit, workspace_id.as_deref()))
+    }
+
+    /// Drops the cached index, so the next query reads every source again.
+    pub async fn invalidate(&self) {
+        *self.index.write().await = None;
+    }
+
+    async fn entries(&self) -> Result<Arc<Vec<PaletteEntry>>> {
+        if let Some(cached) = self.index.read().await.as_ref()
+            && cached.built_at.elapsed() < INDEX_TTL
+        {
+            return Ok(cached.entries.clone());
+        }
+
+        let entries = Arc::new(self.build_index().await?);
+        *self.index.write().await = Some(CachedIndex {
+            built_at: Instant::now(),
+            entries: entries.clone(),
+        });
+        Ok(entries)
+    }
+
+    async fn build_index(&self) -> Result<Vec<PaletteEntry>> {
+        let mut entries = Vec::new();
+
+        for command in builtin_commands() {
+            entries.push(command_entry(command.name, command.description));
+        }
+        for command in self.slash_command_repository.list_commands().await? {
+            entries.push(command_entry(&command.name, &command.description));
+        }
+
+        for session in self.session_repository.list_summaries().await? {
+            entries.push(PaletteEntry {
+                kind: PaletteKind::Session,
+                title: session.title,
+                subtitle: None,
+                action: PaletteAction::SwitchSession(session.id),
+                updated_at: Some(session.updated_at),
+                workspace_id: Some(session.workspace_id),
+            });
+        }
+
+        for persona in self.persona_repository.get_all().await? {
+            entries.push(PaletteEntry {
+                kind: PaletteKind::Persona,
+                title: persona.name,
+                subtitle: Some(persona.role),
+                action: PaletteAction::AddParticipant(persona.id),
+                updated_at: None,
+                workspace_id: None,
+            });
+        }
+
+        for workspace in self.workspace_storage_service.list_all_workspaces().await? {
+            for file in workspace.resources.uploaded_files {
+                if file.is_archived {
+                    continue;
+                }
+                entries.push(PaletteEntry {
+                    kind: PaletteKind::File,
+                    title: file.name,
+                    subtitle: None,
+                    action: PaletteAction::OpenFile(file.path.to_string_lossy().into_owned()),
+                    updated_at: None,
+                    workspace_id: Some(workspace.id.clone()),
+                });
+            }
+        }
+
+        for task in self.task_repository.list_all().await? {
+            entries.push(PaletteEntry {
+                kind: PaletteKind::Task,
+                title: task.title,
+                subtitle: Some(task.status.as_str().to_string()),
+                action: PaletteAction::OpenTask(task.id),
+                updated_at: Some(task.updated_at),
+                workspace_id: None,
+            });
+        }
+
+        Ok(entries)
+    }
+}
+
+fn command_entry(name: &str, description: &str) -> PaletteEntry {
+    PaletteEntry {
+        kind: PaletteKind::Command,
+        title: format!("/{}", name),
+        subtitle: Some(description.to_string()),
+        action: PaletteAction::InsertCommand(format!("/{} ", name)),
+        updated_at: None,
+        workspace_id: None,
+    }
+}
+
+/// Scores and orders `entries` for `query`.
+///
+/// Ties go to the more recently updated entry (undated ones last), then the
+/// earlier [`PaletteKind`], then the title, so the order never depends on the
+/// sources' listing order.
+fn rank(
+    entries: &[PaletteEntry],
+    query: &str,
+    limit: usize,
+    workspace_id: Option<&str>,
+) -> Vec<PaletteItem> {
+    let in_scope = |entry: &&PaletteEntry| {
+        entry.kind != PaletteKind::File
+            || workspace_id.is_some_and(|id| entry.workspace_id.as_deref() == Some(id))
+    };
+
+    let mut scored: Vec<(u32, &PaletteEntry)> = if query.trim().is_empty() {
+        entries
+            .iter()
+            .filter(|e| e.updated_at.is_some())
+            .map(|e| (0, e))
+            .collect()
+    } else {
+        entries
+            .iter()
+            .filter(in_scope)
+            .filter_map(|e| entry_score(query, e).map(|score| (score, e)))
+            .collect()
+    };
+    scored.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| b.updated_at.is_some().cmp(&a.updated_at.is_some()))
+            .then_with(|| b.updated_at.cmp(&a.updated_at))
+            .then_with(|| a.kind.cmp(&b.kind))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(score, entry)| PaletteItem {
+            kind: entry.kind,
+            title: entry.title.clone(),
+            subtitle: entry.subtitle.clone(),
+            action: entry.action.clone(),
+            score,
+        })
+        .collect()
+}
+
+/// Best score of the entry's title or, at half weight, its subtitle.
+fn entry_score(query: &str, entry: &PaletteEntry) -> Option<u32> {
+    let title = fuzzy_score(query, &entry.title);
+    let subtitle = entry
+        .subtitle
+        .as_deref()
+        .and_then(|subtitle| fuzzy_score(query, subtitle))
+        .map(|score| score / 2);
+    title.max(subtitle)
+}
+
+/// Scores how well `text` matches `query` as a case-insensitive subsequence.
+///
+/// Returns `None` unless every non-space character of `query` occurs in
+/// `text` in order. Matches score higher when they are consecutive, start
+/// words, or start the text, and lower the later the match begins.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(fold_case)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.chars().collect();
+    let folded: Vec<char> = text.iter().copied().map(fold_case).collect();
+
+    // Try every occurrence of the first character; greedy from the first one
+    // alone misses "ses" in "user session"
+    folded
+        .iter()
+        .enumerate()
+        .filter(|&(_, &c)| c == query[0])
+        .filter_map(|(start, _)| score_from(&query, &text, &folded, start))
+        .max()
+}
+
+fn score_from(query: &[char], text: &[char], folded: &[char], start: usize) -> Option<u32> {
+    let mut score: u32 = 0;
+    let mut previous: Option<usize> = None;
+    let mut position = start;
+
+    for &wanted in query {
+        let index = position + folded[position..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == index) {
+            score += 8;
+        }
+        if is_word_start(text, index) {
+            score += if index == 0 { 10 } else { 8 };
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+    if query.len() == folded.len() {
+        score += 10;
+    }
+    Some(score.saturating_sub(start.min(10) as u32).max(1))
+}
+
+fn is_word_start(text: &[char], index: usize) -> bool {
+    match index.checked_sub(1).map(|i| text[i]) {
+        None => true,
+        Some(previous) => {
+            !previous.is_alphanumeric() || (previous.is_lowercase() && text[index].is_uppercase())
+        }
+    }
+}
+
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use orcs_core::persona::Persona;
+    use orcs_core::session::{Session, SessionSummary};
+    use orcs_core::slash_command::SlashCommand;
+    use orcs_core::task::{Task, TaskStatus};
+    use orcs_infrastructure::workspace_storage_service::FileSystemWorkspaceManager;
+    use orcs_interaction::testing::{MockPersonaRepository, persona};
+    use tempfile::TempDir;
+
+    struct SummaryRepository(Vec<SessionSummary>);
+
+    #[async_trait]
+    impl SessionRepository for SummaryRepository {
+        async fn find_by_id(&self, _session_id: &str) -> Result<Option<Session>> {
+            Ok(None)
+        }
+        async fn save(&self, _session: &Session) -> Result<()> {
+            Ok(())
+        }
+        async fn delete(&self, _session_id: &str) -> Result<()> {
+            Ok(())
+        }
+        async fn list_all(&self) -> Result<Vec<Session>> {
+            Ok(Vec::new())
+        }
+        async fn list_summaries(&self) -> Result<Vec<SessionSummary>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    struct CommandList(Vec<SlashCommand>);
+
+    #[async_trait]
+    impl SlashCommandRepository for CommandList {
+        async fn list_commands(&self) -> Result<Vec<SlashCommand>> {
+            Ok(self.0.clone())
+        }
+        async fn get_command(&self, name: &str) -> Result<Option<SlashCommand>> {
+            Ok(self.0.iter().find(|c| c.name == name).cloned())
+        }
+        async fn save_command(&self, _command: SlashCommand) -> Result<()> {
+            Ok(())
+        }
+        async fn remove_command(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct TaskList(Vec<Task>);
+
+    #[async_trait]
+    impl TaskRepository for TaskList {
+        async fn find_by_id(&self, task_id: &str) -> Result<Option<Task>> {
+            Ok(self.0.iter().find(|t| t.id == task_id).cloned())
+        }
+        async fn save(&self, _task: &Task) -> Result<()> {
+            Ok(())
+        }
+        async fn delete(&self, _task_id: &str) -> Result<()> {
+            Ok(())
+        }
+        async fn list_all(&self) -> Result<Vec<Task>> {
+            Ok(self.0.clone())
+        }
+        async fn list_by_session(&self, session_id: &str) -> Result<Vec<Task>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|t| t.session_id == session_id)
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn summary(id: &str, title: &str, workspace_id: &str, updated_at: &str) -> SessionSummary {
+        SessionSummary {
+            id: id.to_string(),
+            title: title.to_string(),
+            workspace_id: workspace_id.to_string(),
+            updated_at: updated_at.to_string(),
+        }
+    }
+
+    fn task(id: &str, title: &str, updated_at: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            session_id: "s1".to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            status: TaskStatus::Pending,
+            created_at: updated_at.to_string(),
+            updated_at: updated_at.to_string(),
+            completed_at: None,
+            steps_executed: 0,
+            steps_skipped: 0,
+            context_keys: 0,
+            error: None,
+            result: None,
+            execution_details: None,
+            strategy: None,
+            journal_log: None,
+            origin_message_timestamp: None,
+            origin_author: None,
+            dry_run: false,
+        }
+    }
+
+    struct Fixture {
+        _dirs: [TempDir; 2],
+        service: CommandPaletteService,
+    }
+
+    async fn fixture() -> Fixture {
+        let dirs = [(); 2].map(|_| TempDir::new().unwrap());
+        let workspaces = FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+            .await
+            .unwrap();
+        let workspace_id = workspaces
+            .get_or_create_workspace(dirs[1].path())
+            .await
+            .unwrap()
+            .id;
+        workspaces
+            .add_file_from_bytes(
+                &workspace_id,
+                "review-checklist.md",
+                b"- tests",
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let sessions = SummaryRepository(vec![
+            summary("s1", "Parser review", &workspace_id, "2026-10-10T00:00:00Z"),
+            summary("s2", "Lexer rewrite", &workspace_id, "2026-10-12T00:00:00Z"),
+            summary(
+                "s3",
+                "Release notes",
+                "other-workspace",
+                "2026-10-11T00:00:00Z",
+            ),
+            summary("s4", "Standup", &workspace_id, "2026-10-01T00:00:00Z"),
+            summary("s5", "Standup", &workspace_id, "2026-10-05T00:00:00Z"),
+        ]);
+        let personas = MockPersonaRepository::new()
+            .with_persona(Persona {
+                role: "Code Reviewer".to_string(),
+                ..persona("rin", "Rin")
+            })
+            .with_persona(persona("mai", "Mai"));
+        let commands = CommandList(vec![SlashCommand::new_prompt(
+            "review-pr".to_string(),
+            "🔍".to_string(),
+            "Review the open pull request".to_string(),
+            "Review {args}".to_string(),
+        )]);
+        let tasks = TaskList(vec![
+            task("t1", "Review flaky tests", "2026-10-13T00:00:00Z"),
+            task("t2", "Bump dependencies", "2026-10-09T00:00:00Z"),
+        ]);
+
+        Fixture {
+            _dirs: dirs,
+            service: CommandPaletteService::new(
+                Arc::new(commands),
+                Arc::new(sessions),
+                Arc::new(personas),
+                Arc::new(workspaces),
+                Arc::new(tasks),
+            ),
+        }
+    }
+
+    fn titles(items: &[PaletteItem]) -> Vec<&str> {
+        items.iter().map(|item| item.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_prefixes_and_word_starts() {
+        assert_eq!(fuzzy_score("lxr", "Parser review"), None);
+        assert!(fuzzy_score("PR", "Parser review").is_some());
+
+        let prefix = fuzzy_score("lex", "Lexer rewrite").unwrap();
+        let inner = fuzzy_score("lex", "Flex layout").unwrap();
+        let scattered = fuzzy_score("lex", "Large export").unwrap();
+        assert!(prefix > inner, "{prefix} <= {inner}");
+        assert!(inner > scattered, "{inner} <= {scattered}");
+        // Not fooled by an early partial match
+        assert!(fuzzy_score("ses", "user session") > fuzzy_score("ses", "user sxexs"));
+    }
+
+    #[tokio::test]
+    async fn test_query_mixes_every_kind_in_one_ranking() {
+        let fixture = fixture().await;
+        let items = fixture
+            .service
+            .query("review", 20, Some("s1"))
+            .await
+            .unwrap();
+
+        let find = |title: &str| items.iter().find(|item| item.title == title).unwrap();
+        assert_eq!(
+            find("/review-pr").action,
+            PaletteAction::InsertCommand("/review-pr ".to_string())
+        );
+        assert_eq!(
+            find("Parser review").action,
+            PaletteAction::SwitchSession("s1".to_string())
+        );
+        assert_eq!(
+            find("Rin").action,
+            PaletteAction::AddParticipant("rin".to_string())
+        );
+        assert_eq!(find("review-checklist.md").kind, PaletteKind::File);
+        assert_eq!(
+            find("Review flaky tests").action,
+            PaletteAction::OpenTask("t1".to_string())
+        );
+        // Matched only through its role, so below every title match
+        let rin = find("Rin").score;
+        for title in [
+            "/review-pr",
+            "Parser review",
+            "review-checklist.md",
+            "Review flaky tests",
+        ] {
+            assert!(find(title).score > rin, "{title} <= Rin");
+        }
+
+        let top = fixture
+            .service
+            .query("review", 3, Some("s1"))
+            .await
+            .unwrap();
+        assert_eq!(top, items[..3]);
+    }
+
+    #[tokio::test]
+    async fn test_ranking_is_stable_across_queries_and_rebuilds() {
+        let fixture = fixture().await;
+        let first = fixture.service.query("standup", 10, None).await.unwrap();
+        // Equal titles tie on score; the more recently updated session wins
+        assert_eq!(
+            first.iter().map(|item| &item.action).collect::<Vec<_>>(),
+            vec![
+                &PaletteAction::SwitchSession("s5".to_string()),
+                &PaletteAction::SwitchSession("s4".to_string()),
+            ]
+        );
+
+        let mixed = fixture.service.query("re", 50, Some("s1")).await.unwrap();
+        fixture.service.invalidate().await;
+        let rebuilt = fixture.service.query("re", 50, Some("s1")).await.unwrap();
+        assert_eq!(mixed, rebuilt);
+        assert!(mixed.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    }
+
+    #[tokio::test]
+    async fn test_empty_query_lists_recent_sessions_and_tasks() {
+        let fixture = fixture().await;
+        let items = fixture.service.query("  ", 3, Some("s1")).await.unwrap();
+        assert_eq!(
+            titles(&items),
+            vec!["Review flaky tests", "Lexer rewrite", "Release notes"]
+        );
+        assert!(items.iter().all(|item| item.score == 0));
+    }
+
+    #[tokio::test]
+    async fn test_files_come_only_from_the_active_sessions_workspace() {
+        let fixture = fixture().await;
+        let file_titles = |items: Vec<PaletteItem>| -> Vec<String> {
+            items
+                .into_iter()
+                .filter(|item| item.kind == PaletteKind::File)
+                .map(|item| item.title)
+                .collect()
+        };
+
+        let in_workspace = fixture.service.query("checklist", 10, Some("s2")).await;
+        assert_eq!(
+            file_titles(in_workspace.unwrap()),
+            vec!["review-checklist.md"]
+        );
+        let elsewhere = fixture.service.query("checklist", 10, Some("s3")).await;
+        assert!(file_titles(elsewhere.unwrap()).is_empty());
+        let no_session = fixture.service.query("checklist", 10, None).await;
+        assert!(file_titles(no_session.unwrap()).is_empty());
+    }
+}
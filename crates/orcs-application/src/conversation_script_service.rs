@@ -0,0 +1,397 @@
+//! Runs conversation scripts against a session.
+//!
+//! The interpreter executes a script's steps in order with the session's own
+//! primitives: framing messages are System messages, rounds are targeted
+//! rounds of the listed participants. Progress is kept in the session's
+//! [`ScriptRun`], which is saved with the session, so a script paused for the
+//! user (or interrupted) resumes with [`ConversationScriptService::advance`],
+//! also after a restart.
+
+use orcs_core::conversation_script::{
+    ConversationScript, ConversationScriptRepository, ScriptRun, ScriptStep,
+};
+use orcs_core::error::{OrcsError, Result};
+use orcs_interaction::{DialogueMessage, InteractionManager};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Message type of the System messages a script posts.
+const SCRIPT_MESSAGE_TYPE: &str = "conversation_script";
+
+/// How a run or advance of a script ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "run", rename_all = "snake_case")]
+pub enum ScriptOutcome {
+    /// Waiting at a Pause step; advancing continues after it
+    Paused(ScriptRun),
+    /// Cancelled by the user; advancing resumes with the next step
+    Cancelled(ScriptRun),
+    /// Stopped before a round because the session is muted; advancing
+    /// (after unmuting) runs the round
+    Muted(ScriptRun),
+    /// All steps ran
+    Completed,
+}
+
+/// Lists conversation scripts and runs them against sessions.
+pub struct ConversationScriptService {
+    repository: Arc<dyn ConversationScriptRepository>,
+}
+
+impl ConversationScriptService {
+    pub fn new(repository: Arc<dyn ConversationScriptRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Returns all scripts, system scripts first.
+    pub async fn list(&self) -> Result<Vec<ConversationScript>> {
+        self.repository.get_all().await
+    }
+
+    /// Starts `script_id` in the session of `manager` and runs it up to its
+    /// first pause.
+    ///
+    /// An unfinished run of another script in the session is abandoned.
+    /// Turns are streamed through `on_turn`; setting `cancel_flag` stops the
+    /// script before its next step.
+    pub async fn run<F>(
+        &self,
+        manager: &InteractionManager,
+        script_id: &str,
+        cancel_flag: Option<&AtomicBool>,
+        on_turn: F,
+    ) -> Result<ScriptOutcome>
+    where
+        F: Fn(&DialogueMessage),
+    {
+        let script = self
+            .repository
+            .find_by_id(script_id)
+            .await?
+            .ok_or_else(|| OrcsError::not_found("conversation_script", script_id))?;
+        script.validate()?;
+
+        let run = ScriptRun::start(&script);
+        manager.set_script_run(Some(run.clone())).await;
+        self.execute(manager, &script, run, cancel_flag, on_turn)
+            .await
+    }
+
+    /// Resumes the script running in the session of `manager`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no script is running, or the running script no
+    /// longer exists (its run is then cleared).
+    pub async fn advance<F>(
+        &self,
+        manager: &InteractionManager,
+        cancel_flag: Option<&AtomicBool>,
+        on_turn: F,
+    ) -> Result<ScriptOutcome>
+    where
+        F: Fn(&DialogueMessage),
+    {
+        let Some(mut run) = manager.script_run().await else {
+            return Err(OrcsError::config(
+                "No conversation script is running in this session",
+            ));
+        };
+        let Some(script) = self.repository.find_by_id(&run.script_id).await? else {
+            manager.set_script_run(None).await;
+            return Err(OrcsError::not_found("conversation_script", run.script_id));
+        };
+
+        run.paused = false;
+        self.execute(manager, &script, run, cancel_flag, on_turn)
+            .await
+    }
+
+    /// Executes the steps of `script` from `run.next_step` on.
+    async fn execute<F>(
+        &self,
+        manager: &InteractionManager,
+        script: &ConversationScript,
+        mut run: ScriptRun,
+        cancel_flag: Option<&AtomicBool>,
+        on_turn: F,
+    ) -> Result<ScriptOutcome>
+    where
+        F: Fn(&DialogueMessage),
+    {
+        let is_cancelled = || cancel_flag.is_some_and(|flag| flag.load(Ordering::SeqCst));
+
+        // The script may have been edited since the run started
+        run.total_steps = script.steps.len();
+        while let Some(step) = script.steps.get(run.next_step) {
+            if is_cancelled() {
+                manager.set_script_run(Some(run.clone())).await;
+                return Ok(ScriptOutcome::Cancelled(run));
+            }
+
+            match step {
+                ScriptStep::PostSystemMessage { content } => {
+                    post(manager, content, &on_turn).await;
+                }
+                ScriptStep::RunRound {
+                    participants,
+                    prompt,
+                } => {
+                    if manager.is_muted().await {
+                        manager.set_script_run(Some(run.clone())).await;
+                        return Ok(ScriptOutcome::Muted(run));
+                    }
+                    if let Err(e) = manager
+                        .run_targeted_round(participants, prompt, cancel_flag, Some(&on_turn))
+                        .await
+                    {
+                        // The failed round is retried when the script is advanced
+                        manager.set_script_run(Some(run.clone())).await;
+                        return Err(OrcsError::internal(format!(
+                            "Step {} of '{}' failed: {}",
+                            run.next_step + 1,
+                            script.name,
+                            e
+                        )));
+                    }
+                }
+                ScriptStep::Pause { message } => {
+                    if let Some(message) = message {
+                        post(manager, message, &on_turn).await;
+                    }
+                    run.next_step += 1;
+                    run.paused = true;
+                    manager.set_script_run(Some(run.clone())).await;
+                    return Ok(ScriptOutcome::Paused(run));
+                }
+            }
+
+            run.next_step += 1;
+            manager.set_script_run(Some(run.clone())).await;
+        }
+
+        manager.set_script_run(None).await;
+        Ok(ScriptOutcome::Completed)
+    }
+}
+
+/// Posts `content` as a script System message and streams it.
+async fn post<F>(manager: &InteractionManager, content: &str, on_turn: &F)
+where
+    F: Fn(&DialogueMessage),
+{
+    manager
+        .add_system_conversation_message(
+            content.to_string(),
+            Some(SCRIPT_MESSAGE_TYPE.to_string()),
+            None,
+        )
+        .await;
+    on_turn(&DialogueMessage {
+        session_id: manager.session_id().to_string(),
+        author: "System".to_string(),
+        content: content.to_string(),
+        failure: None,
+        thinking: None,
+        heartbeat: None,
+        turn_stats: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orcs_core::dialogue::PresetSource;
+    use orcs_core::session::{AppMode, MessageRole};
+    use orcs_interaction::testing::{
+        MockPersonaRepository, RecordingCallback, ScriptedAgent, ScriptedAgentFactory, persona,
+        restore_scripted_session, scripted_session,
+    };
+    use std::sync::Mutex;
+
+    /// In-memory repository holding the given scripts.
+    struct MemoryScripts(Mutex<Vec<ConversationScript>>);
+
+    #[async_trait::async_trait]
+    impl ConversationScriptRepository for MemoryScripts {
+        async fn find_by_id(&self, script_id: &str) -> Result<Option<ConversationScript>> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|s| s.id == script_id)
+                .cloned())
+        }
+
+        async fn save(&self, script: &ConversationScript) -> Result<()> {
+            self.0.lock().unwrap().push(script.clone());
+            Ok(())
+        }
+
+        async fn delete(&self, script_id: &str) -> Result<()> {
+            self.0.lock().unwrap().retain(|s| s.id != script_id);
+            Ok(())
+        }
+
+        async fn get_all(&self) -> Result<Vec<ConversationScript>> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+    }
+
+    fn standup() -> ConversationScript {
+        ConversationScript {
+            id: "standup".to_string(),
+            name: "Standup".to_string(),
+            icon: None,
+            description: None,
+            steps: vec![
+                ScriptStep::PostSystemMessage {
+                    content: "Part 1: Yesterday".to_string(),
+                },
+                ScriptStep::Pause {
+                    message: Some("Share your update, then continue.".to_string()),
+                },
+                ScriptStep::RunRound {
+                    participants: vec!["yui".to_string()],
+                    prompt: "What did you work on yesterday?".to_string(),
+                },
+            ],
+            source: PresetSource::User,
+        }
+    }
+
+    fn service() -> ConversationScriptService {
+        ConversationScriptService::new(Arc::new(MemoryScripts(Mutex::new(vec![standup()]))))
+    }
+
+    fn personas() -> MockPersonaRepository {
+        MockPersonaRepository::new()
+            .with_persona(persona("mai", "Mai"))
+            .with_persona(persona("yui", "Yui"))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_script_runs_in_order_and_resumes_after_restart() {
+        let mai = ScriptedAgent::new().repeat("Mai here.");
+        let yui = ScriptedAgent::new().repeat("Reviewed the parser.");
+        let agents = || {
+            ScriptedAgentFactory::new()
+                .with_agent("mai", mai.clone())
+                .with_agent("yui", yui.clone())
+        };
+        let service = service();
+        let manager = scripted_session(personas(), agents());
+
+        let events = RecordingCallback::new();
+        let outcome = service
+            .run(&manager, "standup", None, events.callback())
+            .await
+            .unwrap();
+
+        let ScriptOutcome::Paused(run) = outcome else {
+            panic!("expected the script to pause, got {:?}", outcome);
+        };
+        assert_eq!(run.next_step, 2);
+        assert!(run.paused);
+        assert_eq!(
+            events.replies(),
+            vec![
+                ("System".to_string(), "Part 1: Yesterday".to_string()),
+                (
+                    "System".to_string(),
+                    "Share your update, then continue.".to_string()
+                ),
+            ]
+        );
+        assert_eq!(yui.calls(), 0);
+
+        // The pause survives a restart
+        let session = manager.to_session(AppMode::Idle, "ws".to_string()).await;
+        assert_eq!(session.script_run, Some(run));
+        let restored = restore_scripted_session(session, personas(), agents());
+
+        let events = RecordingCallback::new();
+        let outcome = service
+            .advance(&restored, None, events.callback())
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, ScriptOutcome::Completed);
+        assert_eq!(
+            events.replies(),
+            vec![
+                (
+                    "System".to_string(),
+                    "What did you work on yesterday?".to_string()
+                ),
+                ("Yui".to_string(), "Reviewed the parser.".to_string()),
+            ]
+        );
+        assert_eq!(mai.calls(), 0);
+        assert_eq!(restored.script_run().await, None);
+        assert!(service.advance(&restored, None, |_| {}).await.is_err());
+
+        // Framing messages are stored in order, before the reply
+        let session = restored.to_session(AppMode::Idle, "ws".to_string()).await;
+        let scripted: Vec<&str> = session
+            .system_messages
+            .iter()
+            .filter(|m| m.metadata.system_message_type.as_deref() == Some(SCRIPT_MESSAGE_TYPE))
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(
+            scripted,
+            vec!["Part 1: Yesterday", "Share your update, then continue."]
+        );
+        let replies: Vec<&str> = session.persona_histories["yui"]
+            .iter()
+            .filter(|m| m.role == MessageRole::Assistant)
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(replies, vec!["Reviewed the parser."]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_script_waits_while_muted_and_stops_when_cancelled() {
+        let yui = ScriptedAgent::new().repeat("Reviewed the parser.");
+        let service = service();
+        let manager = scripted_session(
+            personas(),
+            ScriptedAgentFactory::new().with_agent("yui", yui.clone()),
+        );
+
+        // Cancelled before the first step: nothing is posted
+        let cancel = AtomicBool::new(true);
+        let outcome = service
+            .run(&manager, "standup", Some(&cancel), |_| {})
+            .await
+            .unwrap();
+        assert!(matches!(outcome, ScriptOutcome::Cancelled(ref run) if run.next_step == 0));
+        assert!(manager.script_run().await.is_some());
+
+        cancel.store(false, Ordering::SeqCst);
+        service
+            .advance(&manager, Some(&cancel), |_| {})
+            .await
+            .unwrap();
+
+        // Muted sessions stop before the round, which runs once unmuted
+        manager.set_mute(true).await;
+        let outcome = service
+            .advance(&manager, Some(&cancel), |_| {})
+            .await
+            .unwrap();
+        assert!(matches!(outcome, ScriptOutcome::Muted(ref run) if run.next_step == 2));
+        assert_eq!(yui.calls(), 0);
+
+        manager.set_mute(false).await;
+        let outcome = service
+            .advance(&manager, Some(&cancel), |_| {})
+            .await
+            .unwrap();
+        assert_eq!(outcome, ScriptOutcome::Completed);
+        assert_eq!(yui.calls(), 1);
+    }
+}
@@ -0,0 +1,635 @@
+//! Decision Records
+//!
+//! Extracts an ADR-style record (context, options considered, decision,
+//! consequences and dissenting opinions) from a conversation using the utility
+//! backend (Gemini Flash API).
+//!
+//! Model output is parsed leniently: a JSON object is looked for anywhere in
+//! the reply (code fences and prose around it are ignored, common key variants
+//! are accepted), falling back to Markdown sections. If the reply still lacks a
+//! required field, the request is retried once with a stricter prompt.
+
+use crate::session_export::DateRange;
+use anyhow::{Result, anyhow};
+use llm_toolkit::ToPrompt;
+use llm_toolkit::agent::Agent;
+use orcs_core::session::{ConversationMessage, MessageRole, Session};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Longest message (in characters) kept in the transcript sent to the model.
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// An objection to the decision, attributed to the participant who raised it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToPrompt)]
+#[prompt(mode = "full")]
+#[serde(rename_all = "camelCase")]
+pub struct DissentingOpinion {
+    /// Name of the participant who disagreed
+    pub author: String,
+    /// The objection, in one or two sentences
+    pub opinion: String,
+}
+
+/// ADR-style record of a decision reached in a conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToPrompt)]
+#[prompt(mode = "full")]
+#[serde(rename_all = "camelCase")]
+pub struct DecisionRecord {
+    /// Short name of the decision (3-8 words)
+    pub title: String,
+    /// The problem and the forces that led to the decision
+    pub context: String,
+    /// Every option that was discussed
+    pub options_considered: Vec<String>,
+    /// The chosen option and the reason it was chosen
+    pub decision: String,
+    /// What follows from the decision, good and bad
+    pub consequences: Vec<String>,
+    /// Objections that were not resolved
+    pub dissenting_opinions: Vec<DissentingOpinion>,
+}
+
+impl DecisionRecord {
+    /// Parses a model reply into a record, tolerating imperfect output.
+    ///
+    /// Accepts a JSON object (bare, fenced or surrounded by prose) or Markdown
+    /// with one section per field. A missing title is derived from the
+    /// decision.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the missing fields if `context` or `decision`
+    /// cannot be found.
+    pub fn parse(output: &str) -> Result<Self> {
+        let draft = json_object(output)
+            .map(|value| Draft::from_json(&value))
+            .filter(|draft| !draft.is_empty())
+            .unwrap_or_else(|| Draft::from_markdown(output));
+        draft.validate()
+    }
+
+    /// File name for the stored record: `<date>-<title slug>.md`.
+    pub fn file_name(&self, date: &str) -> String {
+        let mut slug = String::new();
+        for c in self.title.chars().flat_map(char::to_lowercase) {
+            if c.is_alphanumeric() {
+                slug.push(c);
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+            if slug.chars().count() >= 60 {
+                break;
+            }
+        }
+        let slug = slug.trim_end_matches('-');
+        if slug.is_empty() {
+            format!("{}-decision.md", date)
+        } else {
+            format!("{}-{}.md", date, slug)
+        }
+    }
+
+    /// Markdown document in ADR layout.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "# 📋 {}\n\n## Context\n\n{}\n\n## Options Considered\n\n",
+            self.title, self.context
+        );
+        push_list(&mut markdown, &self.options_considered, true);
+        markdown.push_str(&format!(
+            "\n## Decision\n\n{}\n\n## Consequences\n\n",
+            self.decision
+        ));
+        push_list(&mut markdown, &self.consequences, false);
+        markdown.push_str("\n## Dissenting Opinions\n\n");
+        let dissent: Vec<String> = self
+            .dissenting_opinions
+            .iter()
+            .map(|d| format!("**{}**: {}", d.author, d.opinion))
+            .collect();
+        push_list(&mut markdown, &dissent, false);
+        markdown
+    }
+}
+
+fn push_list(markdown: &mut String, items: &[String], numbered: bool) {
+    if items.is_empty() {
+        markdown.push_str("None recorded.\n");
+    }
+    for (idx, item) in items.iter().enumerate() {
+        if numbered {
+            markdown.push_str(&format!("{}. {}\n", idx + 1, item));
+        } else {
+            markdown.push_str(&format!("- {}\n", item));
+        }
+    }
+}
+
+/// Record fields found so far; validated into a [`DecisionRecord`].
+#[derive(Default)]
+struct Draft {
+    title: Option<String>,
+    context: Option<String>,
+    options_considered: Vec<String>,
+    decision: Option<String>,
+    consequences: Vec<String>,
+    dissenting_opinions: Vec<DissentingOpinion>,
+}
+
+/// Record field a JSON key or Markdown heading refers to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Context,
+    Options,
+    Decision,
+    Consequences,
+    Dissent,
+}
+
+impl Field {
+    /// Matches key and heading variants ("optionsConsidered", "Options
+    /// considered", "Dissenting Opinions", "Status / Decision", ...).
+    fn from_label(label: &str) -> Option<Self> {
+        let label: String = label
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .flat_map(char::to_lowercase)
+            .collect();
+        if label.contains("dissent") || label.contains("objection") {
+            Some(Field::Dissent)
+        } else if label.contains("option") || label.contains("alternative") {
+            Some(Field::Options)
+        } else if label.contains("consequence") || label.contains("implication") {
+            Some(Field::Consequences)
+        } else if label.contains("context") || label.contains("background") {
+            Some(Field::Context)
+        } else if label.contains("decision") || label.contains("outcome") {
+            Some(Field::Decision)
+        } else if label == "title" || label == "name" {
+            Some(Field::Title)
+        } else {
+            None
+        }
+    }
+}
+
+impl Draft {
+    fn is_empty(&self) -> bool {
+        self.context.is_none() && self.decision.is_none()
+    }
+
+    fn from_json(value: &Value) -> Self {
+        let mut draft = Draft::default();
+        let Some(object) = value.as_object() else {
+            return draft;
+        };
+        for (key, value) in object {
+            match Field::from_label(key) {
+                Some(Field::Title) => draft.title = json_text(value),
+                Some(Field::Context) => draft.context = json_text(value),
+                Some(Field::Decision) => draft.decision = json_text(value),
+                Some(Field::Options) => draft.options_considered = json_list(value),
+                Some(Field::Consequences) => draft.consequences = json_list(value),
+                Some(Field::Dissent) => {
+                    draft.dissenting_opinions = match value {
+                        Value::Array(items) => items.iter().filter_map(json_dissent).collect(),
+                        other => json_dissent(other).into_iter().collect(),
+                    }
+                }
+                None => {}
+            }
+        }
+        draft
+    }
+
+    fn from_markdown(output: &str) -> Self {
+        let mut draft = Draft::default();
+        let mut sections: Vec<(Field, Vec<String>)> = Vec::new();
+        for line in output.lines() {
+            let trimmed = line.trim();
+            if let Some(heading) = trimmed.strip_prefix('#') {
+                let is_top_level = !heading.starts_with('#');
+                let heading = heading.trim_start_matches('#').trim();
+                if is_top_level && draft.title.is_none() && sections.is_empty() {
+                    draft.title = Some(strip_leading_symbols(heading)).filter(|t| !t.is_empty());
+                } else if let Some(field) = Field::from_label(heading) {
+                    sections.push((field, Vec::new()));
+                }
+                continue;
+            }
+            // "**Decision:** Use Postgres" starts a section inline
+            let unbolded = trimmed.replace("**", "");
+            if let Some((label, rest)) = unbolded.split_once(':')
+                && label.chars().all(|c| c.is_alphabetic() || c == ' ')
+                && let Some(field) = Field::from_label(label)
+            {
+                let rest = rest.trim().to_string();
+                if field == Field::Title {
+                    draft.title = Some(rest).filter(|t| !t.is_empty());
+                } else {
+                    sections.push((field, vec![rest]));
+                }
+                continue;
+            }
+            if let Some((_, lines)) = sections.last_mut() {
+                lines.push(trimmed.to_string());
+            }
+        }
+
+        for (field, lines) in sections {
+            let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+            match field {
+                Field::Title => {}
+                Field::Context => draft.context = section_text(&lines),
+                Field::Decision => draft.decision = section_text(&lines),
+                Field::Options => draft.options_considered.extend(section_items(&lines)),
+                Field::Consequences => draft.consequences.extend(section_items(&lines)),
+                Field::Dissent => draft.dissenting_opinions.extend(
+                    section_items(&lines)
+                        .iter()
+                        .filter_map(|item| dissent_from_text(item)),
+                ),
+            }
+        }
+        draft
+    }
+
+    fn validate(self) -> Result<DecisionRecord> {
+        let missing: Vec<&str> = [("context", &self.context), ("decision", &self.decision)]
+            .into_iter()
+            .filter(|(_, value)| value.is_none())
+            .map(|(name, _)| name)
+            .collect();
+        let (Some(context), Some(decision)) = (self.context, self.decision) else {
+            return Err(anyhow!(
+                "Decision record is missing required fields: {}",
+                missing.join(", ")
+            ));
+        };
+        let title = self.title.unwrap_or_else(|| {
+            let first_sentence = decision.split(['.', '\n']).next().unwrap_or(&decision);
+            first_sentence
+                .chars()
+                .take(60)
+                .collect::<String>()
+                .trim()
+                .to_string()
+        });
+        Ok(DecisionRecord {
+            title,
+            context,
+            options_considered: self.options_considered,
+            decision,
+            consequences: self.consequences,
+            dissenting_opinions: self.dissenting_opinions,
+        })
+    }
+}
+
+/// Finds the outermost JSON object in `output`, ignoring fences and prose.
+pub(crate) fn json_object(output: &str) -> Option<Value> {
+    let start = output.find('{')?;
+    let end = output.rfind('}')?;
+    if end <= start {
+        return None;
+    }
+    serde_json::from_str(&output[start..=end]).ok()
+}
+
+fn json_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(json_text)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    };
+    Some(text).filter(|t| !t.is_empty())
+}
+
+fn json_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                // {"name": "Postgres", "description": "..."} style options
+                Value::Object(object) => {
+                    let parts: Vec<String> = object.values().filter_map(json_text).collect();
+                    Some(parts.join(": ")).filter(|t| !t.is_empty())
+                }
+                other => json_text(other),
+            })
+            .collect(),
+        Value::String(s) => section_items(&s.lines().map(str::trim).collect::<Vec<_>>()),
+        _ => Vec::new(),
+    }
+}
+
+fn json_dissent(value: &Value) -> Option<DissentingOpinion> {
+    let Value::Object(object) = value else {
+        return json_text(value).and_then(|text| dissent_from_text(&text));
+    };
+    let field = |keys: &[&str]| {
+        object
+            .iter()
+            .find(|(key, _)| keys.contains(&key.to_lowercase().as_str()))
+            .and_then(|(_, value)| json_text(value))
+    };
+    let opinion = field(&["opinion", "objection", "view", "text", "reason", "content"])?;
+    let author = field(&["author", "participant", "persona", "by", "name", "who"])
+        .unwrap_or_else(|| "Unattributed".to_string());
+    Some(DissentingOpinion { author, opinion })
+}
+
+/// Parses "Yui: prefers SQLite" (or "Yui - ...") into an attributed opinion.
+fn dissent_from_text(text: &str) -> Option<DissentingOpinion> {
+    let text = text.replace("**", "");
+    let text = text.trim();
+    if text.is_empty() || text.eq_ignore_ascii_case("none") {
+        return None;
+    }
+    let split = text
+        .split_once(':')
+        .or_else(|| text.split_once(" — "))
+        .or_else(|| text.split_once(" - "))
+        .filter(|(author, _)| author.split_whitespace().count() <= 3);
+    Some(match split {
+        Some((author, opinion)) => DissentingOpinion {
+            author: author.trim().to_string(),
+            opinion: opinion.trim().to_string(),
+        },
+        None => DissentingOpinion {
+            author: "Unattributed".to_string(),
+            opinion: text.to_string(),
+        },
+    })
+}
+
+fn section_text(lines: &[&str]) -> Option<String> {
+    let text = lines.join("\n").trim().to_string();
+    Some(text).filter(|t| !t.is_empty())
+}
+
+/// Bullet or numbered items of a section; plain lines if there are none.
+fn section_items(lines: &[&str]) -> Vec<String> {
+    let bullet = |line: &str| -> Option<String> {
+        let item = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .or_else(|| {
+                let (number, rest) = line.split_once(". ")?;
+                number.chars().all(|c| c.is_ascii_digit()).then_some(rest)
+            })?;
+        Some(item.trim().to_string())
+    };
+    let is_placeholder = |item: &String| {
+        let item = item.trim_end_matches('.').to_lowercase();
+        item == "none" || item == "none recorded" || item == "n/a"
+    };
+    let items: Vec<String> = lines.iter().filter_map(|line| bullet(line)).collect();
+    let items = if items.is_empty() {
+        lines
+            .iter()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .collect()
+    } else {
+        items
+    };
+    items
+        .into_iter()
+        .filter(|item| !is_placeholder(item))
+        .collect()
+}
+
+fn strip_leading_symbols(text: &str) -> String {
+    text.trim_start_matches(|c: char| !c.is_alphanumeric())
+        .trim()
+        .to_string()
+}
+
+/// Builds the compacted transcript a record is extracted from.
+///
+/// Messages are ordered by timestamp and labelled with their author. Repeated
+/// system notices, errors and messages hidden from the dialogue are dropped,
+/// and long messages are cut to [`MAX_MESSAGE_CHARS`].
+pub fn decision_transcript(session: &Session, range: Option<&DateRange>) -> String {
+    let mut compacted = session.clone();
+    compacted.dedup_consecutive_system_messages();
+
+    let in_range = |message: &ConversationMessage| {
+        range.is_none_or(|range| range.contains(&message.timestamp))
+    };
+    let mut entries: Vec<(String, &ConversationMessage)> = Vec::new();
+    for (key, history) in &compacted.persona_histories {
+        for message in history {
+            let author = match message.role {
+                MessageRole::Assistant => compacted
+                    .participants
+                    .get(key)
+                    .cloned()
+                    .unwrap_or_else(|| key.clone()),
+                MessageRole::User => key.clone(),
+                MessageRole::System => "System".to_string(),
+            };
+            entries.push((author, message));
+        }
+    }
+    for message in &compacted.system_messages {
+        if message.metadata.include_in_dialogue && message.metadata.error_severity.is_none() {
+            entries.push(("System".to_string(), message));
+        }
+    }
+    entries.retain(|(_, message)| !message.content.trim().is_empty() && in_range(message));
+    entries.sort_by(|a, b| a.1.timestamp.cmp(&b.1.timestamp));
+
+    entries
+        .into_iter()
+        .map(|(author, message)| {
+            let content = message.content.trim();
+            let content = if content.chars().count() > MAX_MESSAGE_CHARS {
+                let cut: String = content.chars().take(MAX_MESSAGE_CHARS).collect();
+                format!("{}…", cut)
+            } else {
+                content.to_string()
+            };
+            format!("[{}]: {}", author, content)
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[derive(Debug, Clone, Serialize, ToPrompt, Default)]
+#[prompt(
+    template = r#"Extract an Architecture Decision Record (ADR) from this conversation:
+
+{{ transcript }}
+
+Requirements:
+- title: short name of the decision (3-8 words)
+- context: the problem and the forces that led to the decision
+- optionsConsidered: every option that was discussed, one entry each
+- decision: the option that was chosen and why
+- consequences: what follows from the decision, good and bad
+- dissentingOpinions: objections that were not resolved, each attributed to the participant who raised it
+- Use only what was said in the conversation; use empty lists when nothing applies
+
+Output a JSON object matching this schema:
+{{ output_schema }}
+{% if previous_error %}
+Your previous answer could not be used: {{ previous_error }}.
+Output ONLY the JSON object - no prose, no Markdown, no code blocks. "context" and "decision" must be non-empty strings.
+{% else %}
+IMPORTANT: Output ONLY valid JSON, no markdown formatting or code blocks.
+{% endif %}"#
+)]
+struct DecisionRecordRequest {
+    /// The compacted conversation transcript
+    transcript: String,
+
+    /// Output schema for DecisionRecord
+    output_schema: String,
+
+    /// Why the previous reply was rejected (empty on the first attempt)
+    previous_error: String,
+}
+
+/// Lightweight agent for extracting decision records using Gemini Flash API
+#[derive(llm_toolkit::Agent)]
+#[agent(
+    expertise = "Extract ADR-style decision records from multi-agent discussions: context, options, decision, consequences and attributed dissent.",
+    output = "String",
+    default_inner = "orcs_interaction::GeminiApiAgent"
+)]
+pub struct DecisionRecordAgent;
+
+/// Asks `agent` for a record of `transcript`, retrying once with a stricter
+/// prompt if the first reply cannot be parsed.
+///
+/// # Errors
+///
+/// Returns an error if the agent fails or neither reply yields a record.
+pub async fn request_decision_record(
+    agent: &dyn Agent<Output = String, Expertise = String>,
+    transcript: &str,
+) -> Result<DecisionRecord> {
+    let mut request = DecisionRecordRequest {
+        transcript: transcript.to_string(),
+        output_schema: DecisionRecord::prompt_schema(),
+        previous_error: String::new(),
+    };
+
+    let reply = agent.execute(request.to_prompt().as_str().into()).await?;
+    match DecisionRecord::parse(&reply) {
+        Ok(record) => Ok(record),
+        Err(e) => {
+            tracing::warn!("[DecisionRecord] Retrying with a stricter prompt: {}", e);
+            request.previous_error = e.to_string();
+            let reply = agent.execute(request.to_prompt().as_str().into()).await?;
+            DecisionRecord::parse(&reply)
+                .map_err(|e| anyhow!("Could not extract a decision record: {}", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLEAN: &str = r#"{
+        "title": "Use Postgres for storage",
+        "context": "Sessions outgrew TOML files.",
+        "optionsConsidered": ["Postgres", "SQLite"],
+        "decision": "Use Postgres for its concurrent writes.",
+        "consequences": ["Needs a running server"],
+        "dissentingOpinions": [{"author": "Yui", "opinion": "SQLite is simpler to ship."}]
+    }"#;
+
+    fn expected() -> DecisionRecord {
+        DecisionRecord {
+            title: "Use Postgres for storage".to_string(),
+            context: "Sessions outgrew TOML files.".to_string(),
+            options_considered: vec!["Postgres".to_string(), "SQLite".to_string()],
+            decision: "Use Postgres for its concurrent writes.".to_string(),
+            consequences: vec!["Needs a running server".to_string()],
+            dissenting_opinions: vec![DissentingOpinion {
+                author: "Yui".to_string(),
+                opinion: "SQLite is simpler to ship.".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_clean_and_fenced_json() {
+        assert_eq!(DecisionRecord::parse(CLEAN).unwrap(), expected());
+
+        // Prose, a code fence and snake_case/alias keys around the same content
+        let messy = r#"Sure! Here is the record:
+```json
+{
+  "title": "Use Postgres for storage",
+  "Context": "Sessions outgrew TOML files.",
+  "options": [{"name": "Postgres"}, "SQLite"],
+  "decision": "Use Postgres for its concurrent writes.",
+  "consequences": "- Needs a running server",
+  "dissent": ["Yui: SQLite is simpler to ship."]
+}
+```
+Let me know if you need anything else."#;
+        assert_eq!(DecisionRecord::parse(messy).unwrap(), expected());
+    }
+
+    #[test]
+    fn test_parse_markdown_sections() {
+        let markdown = "# 📋 Use Postgres for storage
+
+## Context
+Sessions outgrew TOML files.
+
+## Options Considered
+1. Postgres
+2. SQLite
+
+**Decision:** Use Postgres for its concurrent writes.
+
+## Consequences
+* Needs a running server
+
+## Dissenting Opinions
+- **Yui**: SQLite is simpler to ship.
+";
+        assert_eq!(DecisionRecord::parse(markdown).unwrap(), expected());
+    }
+
+    #[test]
+    fn test_parse_requires_context_and_decision() {
+        let error = DecisionRecord::parse(r#"{"title": "Storage", "decision": ""}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("context, decision"), "{}", error);
+
+        // A missing title is derived from the decision
+        let record =
+            DecisionRecord::parse("Context: Slow listing.\nDecision: Add an index. It is cheap.")
+                .unwrap();
+        assert_eq!(record.title, "Add an index");
+        assert!(record.options_considered.is_empty());
+    }
+
+    #[test]
+    fn test_markdown_round_trips_through_parse() {
+        let record = expected();
+        let markdown = record.to_markdown();
+        assert!(markdown.starts_with("# 📋 Use Postgres for storage\n"));
+        assert_eq!(DecisionRecord::parse(&markdown).unwrap(), record);
+        assert_eq!(
+            record.file_name("2026-10-18"),
+            "2026-10-18-use-postgres-for-storage.md"
+        );
+    }
+}
@@ -0,0 +1,890 @@
+//! Data Integrity Service
+//!
+//! Finds data left behind when its owner disappears: sessions whose workspace
+//! was deleted, tasks whose session was deleted, uploaded files no workspace
+//! references any more, and sandbox worktrees no session is using.
+//!
+//! Scanning never modifies anything. Cleaning only touches items that were
+//! explicitly selected *and* still show up in a fresh scan, so a stale or
+//! hand-crafted selection cannot delete arbitrary files. Sessions that belong
+//! to the placeholder workspace are reported for visibility but never cleaned.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use orcs_core::repository::{SessionRepository, TaskRepository};
+use orcs_core::session::{PLACEHOLDER_WORKSPACE_ID, Session};
+use orcs_core::workspace::Workspace;
+use orcs_core::workspace::manager::WorkspaceStorageService;
+use serde::{Deserialize, Serialize};
+
+/// Directory name used for sandbox worktrees created next to (or inside) a repository.
+const SANDBOX_DIR_NAME: &str = ".orcs-sandboxes";
+/// Prefix of sandbox branches and their worktree directories (`sandbox-{session_id[..8]}`).
+const SANDBOX_BRANCH_PREFIX: &str = "sandbox-";
+
+/// A session whose workspace no longer exists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedSession {
+    pub session_id: String,
+    pub title: String,
+    pub workspace_id: String,
+    /// Sessions in the placeholder workspace are reported but never cleaned.
+    pub is_placeholder_workspace: bool,
+    /// Approximate size of the stored session (serialized form).
+    pub bytes: u64,
+}
+
+/// A task whose session no longer exists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedTask {
+    pub task_id: String,
+    pub title: String,
+    pub session_id: String,
+    /// Approximate size of the stored task (serialized form).
+    pub bytes: u64,
+}
+
+/// A file under a workspace's upload directory that no workspace metadata references.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnreferencedUpload {
+    pub path: PathBuf,
+    pub workspace_id: String,
+    pub bytes: u64,
+}
+
+/// A sandbox worktree registered with git that no session's sandbox state points to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleWorktree {
+    pub path: PathBuf,
+    pub workspace_id: String,
+    pub bytes: u64,
+}
+
+/// Result of an integrity scan.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub orphaned_sessions: Vec<OrphanedSession>,
+    pub orphaned_tasks: Vec<OrphanedTask>,
+    pub unreferenced_uploads: Vec<UnreferencedUpload>,
+    pub stale_worktrees: Vec<StaleWorktree>,
+    /// Number of reported items across all categories.
+    pub total_items: usize,
+    /// Bytes that cleaning every cleanable item would free.
+    pub reclaimable_bytes: u64,
+}
+
+impl IntegrityReport {
+    fn finalize(mut self) -> Self {
+        self.total_items = self.orphaned_sessions.len()
+            + self.orphaned_tasks.len()
+            + self.unreferenced_uploads.len()
+            + self.stale_worktrees.len();
+        self.reclaimable_bytes = self
+            .orphaned_sessions
+            .iter()
+            .filter(|s| !s.is_placeholder_workspace)
+            .map(|s| s.bytes)
+            .chain(self.orphaned_tasks.iter().map(|t| t.bytes))
+            .chain(self.unreferenced_uploads.iter().map(|u| u.bytes))
+            .chain(self.stale_worktrees.iter().map(|w| w.bytes))
+            .sum();
+        self
+    }
+
+    /// Returns true when nothing was found.
+    pub fn is_clean(&self) -> bool {
+        self.total_items == 0
+    }
+}
+
+/// Items the user chose to clean. Empty selection cleans nothing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegritySelection {
+    #[serde(default)]
+    pub session_ids: Vec<String>,
+    #[serde(default)]
+    pub task_ids: Vec<String>,
+    #[serde(default)]
+    pub upload_paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub worktree_paths: Vec<PathBuf>,
+}
+
+/// Category of a cleaned item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityItemKind {
+    Session,
+    Task,
+    Upload,
+    Worktree,
+}
+
+/// Outcome of cleaning a single selected item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityCleanResult {
+    pub kind: IntegrityItemKind,
+    /// Session/task ID, or the path for uploads and worktrees.
+    pub item: String,
+    pub success: bool,
+    /// Reason the item was skipped or failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub bytes_reclaimed: u64,
+}
+
+impl IntegrityCleanResult {
+    fn ok(kind: IntegrityItemKind, item: String, bytes: u64) -> Self {
+        Self {
+            kind,
+            item,
+            success: true,
+            message: None,
+            bytes_reclaimed: bytes,
+        }
+    }
+
+    fn failed(kind: IntegrityItemKind, item: String, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            item,
+            success: false,
+            message: Some(message.into()),
+            bytes_reclaimed: 0,
+        }
+    }
+}
+
+/// Scans workspace data for orphans and cleans selected items.
+pub struct IntegrityService {
+    session_repository: Arc<dyn SessionRepository>,
+    task_repository: Arc<dyn TaskRepository>,
+    workspace_storage_service: Arc<dyn WorkspaceStorageService>,
+    /// Root directory holding per-workspace data (`{root}/{workspace_id}/resources/...`).
+    workspaces_root: PathBuf,
+}
+
+impl IntegrityService {
+    pub fn new(
+        session_repository: Arc<dyn SessionRepository>,
+        task_repository: Arc<dyn TaskRepository>,
+        workspace_storage_service: Arc<dyn WorkspaceStorageService>,
+        workspaces_root: PathBuf,
+    ) -> Self {
+        Self {
+            session_repository,
+            task_repository,
+            workspace_storage_service,
+            workspaces_root,
+        }
+    }
+
+    /// Scans all stored data and reports orphaned items. Nothing is modified.
+    pub async fn scan(&self) -> Result<IntegrityReport> {
+        let workspaces = self.workspace_storage_service.list_all_workspaces().await?;
+        let sessions = self.session_repository.list_all().await?;
+        let tasks = self.task_repository.list_all().await?;
+
+        let workspace_ids: HashSet<&str> = workspaces.iter().map(|w| w.id.as_str()).collect();
+        let session_ids: HashSet<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+
+        let orphaned_sessions = sessions
+            .iter()
+            .filter(|s| !workspace_ids.contains(s.workspace_id.as_str()))
+            .map(|s| OrphanedSession {
+                session_id: s.id.clone(),
+                title: s.title.clone(),
+                workspace_id: s.workspace_id.clone(),
+                is_placeholder_workspace: s.workspace_id == PLACEHOLDER_WORKSPACE_ID,
+                bytes: serialized_len(s),
+            })
+            .collect();
+
+        let orphaned_tasks = tasks
+            .iter()
+            .filter(|t| !session_ids.contains(t.session_id.as_str()))
+            .map(|t| OrphanedTask {
+                task_id: t.id.clone(),
+                title: t.title.clone(),
+                session_id: t.session_id.clone(),
+                bytes: serialized_len(t),
+            })
+            .collect();
+
+        let report = IntegrityReport {
+            orphaned_sessions,
+            orphaned_tasks,
+            unreferenced_uploads: self.find_unreferenced_uploads(&workspaces).await?,
+            stale_worktrees: find_stale_worktrees(&workspaces, &sessions).await?,
+            ..Default::default()
+        };
+        Ok(report.finalize())
+    }
+
+    /// Cleans the selected items.
+    ///
+    /// A fresh scan is run first; selected items that are not part of it are
+    /// reported as failures instead of being deleted.
+    pub async fn clean(&self, selection: IntegritySelection) -> Result<Vec<IntegrityCleanResult>> {
+        let report = self.scan().await?;
+        let mut results = Vec::new();
+
+        for session_id in selection.session_ids {
+            let Some(orphan) = report
+                .orphaned_sessions
+                .iter()
+                .find(|s| s.session_id == session_id)
+            else {
+                results.push(not_in_scan(IntegrityItemKind::Session, session_id));
+                continue;
+            };
+            if orphan.is_placeholder_workspace {
+                results.push(IntegrityCleanResult::failed(
+                    IntegrityItemKind::Session,
+                    session_id,
+                    "Sessions in the placeholder workspace are never cleaned",
+                ));
+                continue;
+            }
+            results.push(match self.session_repository.delete(&session_id).await {
+                Ok(()) => {
+                    IntegrityCleanResult::ok(IntegrityItemKind::Session, session_id, orphan.bytes)
+                }
+                Err(e) => IntegrityCleanResult::failed(
+                    IntegrityItemKind::Session,
+                    session_id,
+                    e.to_string(),
+                ),
+            });
+        }
+
+        for task_id in selection.task_ids {
+            let Some(orphan) = report.orphaned_tasks.iter().find(|t| t.task_id == task_id) else {
+                results.push(not_in_scan(IntegrityItemKind::Task, task_id));
+                continue;
+            };
+            results.push(match self.task_repository.delete(&task_id).await {
+                Ok(()) => IntegrityCleanResult::ok(IntegrityItemKind::Task, task_id, orphan.bytes),
+                Err(e) => {
+                    IntegrityCleanResult::failed(IntegrityItemKind::Task, task_id, e.to_string())
+                }
+            });
+        }
+
+        for path in selection.upload_paths {
+            let item = path.display().to_string();
+            let Some(orphan) = report.unreferenced_uploads.iter().find(|u| u.path == path) else {
+                results.push(not_in_scan(IntegrityItemKind::Upload, item));
+                continue;
+            };
+            results.push(match tokio::fs::remove_file(&path).await {
+                Ok(()) => IntegrityCleanResult::ok(IntegrityItemKind::Upload, item, orphan.bytes),
+                Err(e) => {
+                    IntegrityCleanResult::failed(IntegrityItemKind::Upload, item, e.to_string())
+                }
+            });
+        }
+
+        if !selection.worktree_paths.is_empty() {
+            let workspace_roots: HashMap<String, PathBuf> = self
+                .workspace_storage_service
+                .list_all_workspaces()
+                .await?
+                .into_iter()
+                .map(|w| (w.id, w.root_path))
+                .collect();
+
+            for path in selection.worktree_paths {
+                let item = path.display().to_string();
+                let Some(orphan) = report.stale_worktrees.iter().find(|w| w.path == path) else {
+                    results.push(not_in_scan(IntegrityItemKind::Worktree, item));
+                    continue;
+                };
+                if let Err(e) = tokio::fs::remove_dir_all(&path).await {
+                    results.push(IntegrityCleanResult::failed(
+                        IntegrityItemKind::Worktree,
+                        item,
+                        e.to_string(),
+                    ));
+                    continue;
+                }
+                if let Some(root) = workspace_roots.get(&orphan.workspace_id) {
+                    prune_git_worktrees(root.clone()).await;
+                }
+                results.push(IntegrityCleanResult::ok(
+                    IntegrityItemKind::Worktree,
+                    item,
+                    orphan.bytes,
+                ));
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Lists files under `{root}/{workspace_id}/resources/uploaded/` that no
+    /// workspace's uploaded file list points to, including the upload
+    /// directories of workspaces that no longer exist.
+    async fn find_unreferenced_uploads(
+        &self,
+        workspaces: &[Workspace],
+    ) -> Result<Vec<UnreferencedUpload>> {
+        let referenced: HashSet<PathBuf> = workspaces
+            .iter()
+            .flat_map(|w| w.resources.uploaded_files.iter())
+            .map(|f| canonical(&f.path))
+            .collect();
+
+        let mut uploads = Vec::new();
+        for workspace_dir in list_dirs(&self.workspaces_root).await? {
+            let Some(workspace_id) = workspace_dir.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let uploaded_dir = workspace_dir.join("resources").join("uploaded");
+            let Ok(mut entries) = tokio::fs::read_dir(&uploaded_dir).await else {
+                continue;
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                let path = entry.path();
+                if metadata.is_file() && !referenced.contains(&canonical(&path)) {
+                    uploads.push(UnreferencedUpload {
+                        path,
+                        workspace_id: workspace_id.to_string(),
+                        bytes: metadata.len(),
+                    });
+                }
+            }
+        }
+        uploads.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(uploads)
+    }
+}
+
+fn not_in_scan(kind: IntegrityItemKind, item: String) -> IntegrityCleanResult {
+    IntegrityCleanResult::failed(kind, item, "Not reported by the integrity scan")
+}
+
+fn serialized_len<T: Serialize>(value: &T) -> u64 {
+    serde_json::to_vec(value).map_or(0, |v| v.len() as u64)
+}
+
+/// Resolves symlinks and `.`/`..` so equal locations compare equal; paths
+/// that cannot be resolved (e.g. already deleted) are kept as they are.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Resolves the worktree base directory the same way sandbox creation does.
+fn resolve_sandbox_base(root: &Path, sandbox_root: Option<&str>) -> Option<PathBuf> {
+    match sandbox_root.unwrap_or("../") {
+        "../" | ".." => root.parent().map(|p| p.join(SANDBOX_DIR_NAME)),
+        r if r.starts_with("./") || r.starts_with(".\\") => Some(root.join(r)),
+        r => Some(PathBuf::from(r)),
+    }
+}
+
+/// Finds sandbox worktrees in known sandbox bases that no session's sandbox
+/// state refers to.
+///
+/// Only directories git lists as ORCS sandbox worktrees of a workspace
+/// repository are considered, so unrelated `sandbox-*` directories next to
+/// them are never reported.
+async fn find_stale_worktrees(
+    workspaces: &[Workspace],
+    sessions: &[Session],
+) -> Result<Vec<StaleWorktree>> {
+    let roots: HashMap<&str, &Path> = workspaces
+        .iter()
+        .map(|w| (w.id.as_str(), w.root_path.as_path()))
+        .collect();
+
+    // Candidate base directories, each attributed to the workspace it belongs to.
+    let mut bases: BTreeMap<PathBuf, String> = BTreeMap::new();
+    for workspace in workspaces {
+        let root = workspace.root_path.as_path();
+        for base in [
+            resolve_sandbox_base(root, None),
+            Some(root.join(SANDBOX_DIR_NAME)),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            bases.entry(base).or_insert_with(|| workspace.id.clone());
+        }
+    }
+    for session in sessions {
+        let Some(state) = &session.sandbox_state else {
+            continue;
+        };
+        if let Some(root) = roots.get(session.workspace_id.as_str())
+            && let Some(base) = resolve_sandbox_base(root, state.sandbox_root.as_deref())
+        {
+            bases
+                .entry(base)
+                .or_insert_with(|| session.workspace_id.clone());
+        }
+        if let Some(parent) = Path::new(&state.worktree_path).parent() {
+            bases
+                .entry(parent.to_path_buf())
+                .or_insert_with(|| session.workspace_id.clone());
+        }
+    }
+
+    let active: HashSet<PathBuf> = sessions
+        .iter()
+        .filter_map(|s| s.sandbox_state.as_ref())
+        .map(|state| canonical(Path::new(&state.worktree_path)))
+        .collect();
+
+    let mut registered = HashSet::new();
+    for root in roots.values().copied().collect::<HashSet<_>>() {
+        registered.extend(registered_sandbox_worktrees(root.to_path_buf()).await);
+    }
+
+    let mut worktrees = Vec::new();
+    let mut seen = HashSet::new();
+    for (base, workspace_id) in bases {
+        for dir in list_dirs(&base).await? {
+            let resolved = canonical(&dir);
+            if !registered.contains(&resolved)
+                || active.contains(&resolved)
+                || !seen.insert(resolved)
+            {
+                continue;
+            }
+            let bytes = dir_size(dir.clone()).await;
+            worktrees.push(StaleWorktree {
+                path: dir,
+                workspace_id: workspace_id.clone(),
+                bytes,
+            });
+        }
+    }
+    worktrees.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(worktrees)
+}
+
+/// Lists the sandbox worktrees git has registered for the repository at `root`.
+///
+/// A sandbox worktree is checked out on a `sandbox-*` branch in a directory of
+/// the same name, which is the layout sandbox creation produces. Returns an
+/// empty set when `root` is not a git repository.
+async fn registered_sandbox_worktrees(root: PathBuf) -> HashSet<PathBuf> {
+    let output = tokio::task::spawn_blocking(move || {
+        std::process::Command::new("git")
+            .args(["worktree", "list", "--porcelain"])
+            .current_dir(&root)
+            .output()
+    })
+    .await;
+    match output {
+        Ok(Ok(output)) if output.status.success() => {
+            parse_sandbox_worktrees(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => HashSet::new(),
+    }
+}
+
+/// Extracts sandbox worktree paths from `git worktree list --porcelain` output.
+fn parse_sandbox_worktrees(porcelain: &str) -> HashSet<PathBuf> {
+    porcelain
+        .split("\n\n")
+        .filter_map(|entry| {
+            let mut path = None;
+            let mut branch = None;
+            for line in entry.lines() {
+                if let Some(p) = line.strip_prefix("worktree ") {
+                    path = Some(PathBuf::from(p));
+                } else if let Some(b) = line.strip_prefix("branch refs/heads/") {
+                    branch = Some(b);
+                }
+            }
+            let (path, branch) = (path?, branch?);
+            let dir_name = path.file_name().and_then(|n| n.to_str())?;
+            (branch.starts_with(SANDBOX_BRANCH_PREFIX) && dir_name == branch)
+                .then(|| canonical(&path))
+        })
+        .collect()
+}
+
+/// Lists immediate subdirectories; a missing directory yields an empty list.
+async fn list_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut dirs = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+    Ok(dirs)
+}
+
+/// Total size of regular files below `dir` (symlinks are not followed).
+async fn dir_size(dir: PathBuf) -> u64 {
+    fn walk(dir: &Path) -> u64 {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return 0;
+        };
+        entries
+            .flatten()
+            .map(|entry| match entry.file_type() {
+                Ok(ft) if ft.is_dir() => walk(&entry.path()),
+                Ok(ft) if ft.is_file() => entry.metadata().map_or(0, |m| m.len()),
+                _ => 0,
+            })
+            .sum()
+    }
+    tokio::task::spawn_blocking(move || walk(&dir))
+        .await
+        .unwrap_or(0)
+}
+
+/// Best-effort `git worktree prune` so git forgets removed worktrees.
+async fn prune_git_worktrees(root: PathBuf) {
+    let result = tokio::task::spawn_blocking(move || {
+        std::process::Command::new("git")
+            .args(["worktree", "prune"])
+            .current_dir(&root)
+            .output()
+    })
+    .await;
+    if let Ok(Err(e)) = result {
+        tracing::debug!("[IntegrityService] git worktree prune failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_toolkit::agent::dialogue::ExecutionModel;
+    use orcs_core::session::{AppMode, SandboxState};
+    use orcs_core::task::{Task, TaskStatus};
+    use orcs_infrastructure::workspace_storage_service::FileSystemWorkspaceManager;
+    use orcs_infrastructure::{AsyncDirSessionRepository, AsyncDirTaskRepository};
+    use tempfile::TempDir;
+
+    // Task IDs are normalized to UUIDs on load
+    const KEPT_TASK_ID: &str = "7d0b9c1e-3f0a-4c55-9d4e-2a6b8f1c0a11";
+    const ORPHAN_TASK_ID: &str = "c2f4e8a0-5b7d-4e19-8a3c-6d9f0b2e4c22";
+
+    fn session(id: &str, workspace_id: &str, sandbox_state: Option<SandboxState>) -> Session {
+        Session {
+            id: id.to_string(),
+            title: format!("Session {}", id),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            current_persona_id: String::new(),
+            persona_histories: HashMap::new(),
+            app_mode: AppMode::Idle,
+            workspace_id: workspace_id.to_string(),
+            active_participant_ids: vec![],
+            execution_strategy: ExecutionModel::Broadcast,
+            system_messages: vec![],
+            participants: HashMap::new(),
+            participant_icons: HashMap::new(),
+            participant_colors: HashMap::new(),
+            participant_backends: HashMap::new(),
+            participant_models: HashMap::new(),
+            conversation_mode: Default::default(),
+            talk_style: None,
+            is_favorite: false,
+            is_archived: false,
+            sort_order: None,
+            auto_chat_config: None,
+            is_muted: false,
+            context_mode: Default::default(),
+            sandbox_state,
+            last_memory_sync_at: None,
+            is_generating: false,
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: 8_000,
+        }
+    }
+
+    fn task(id: &str, session_id: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            session_id: session_id.to_string(),
+            title: format!("Task {}", id),
+            description: String::new(),
+            status: TaskStatus::Completed,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            completed_at: None,
+            steps_executed: 0,
+            steps_skipped: 0,
+            context_keys: 0,
+            error: None,
+            result: None,
+            execution_details: None,
+            strategy: None,
+            journal_log: None,
+            origin_message_timestamp: None,
+            origin_author: None,
+            dry_run: false,
+        }
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = std::process::Command::new("git")
+            .args(["-c", "user.name=orcs", "-c", "user.email=orcs@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    struct Fixture {
+        _dirs: [TempDir; 3],
+        _projects: TempDir,
+        service: IntegrityService,
+        sessions: Arc<AsyncDirSessionRepository>,
+        tasks: Arc<AsyncDirTaskRepository>,
+        workspaces: Arc<FileSystemWorkspaceManager>,
+        kept_upload: PathBuf,
+        stray_upload: PathBuf,
+        active_worktree: PathBuf,
+        stale_worktree: PathBuf,
+        foreign_dir: PathBuf,
+    }
+
+    /// Builds one workspace with a live session and one of each orphan class.
+    async fn fixture() -> Fixture {
+        // Each repository treats an existing base directory as its own root
+        let dirs = [(); 3].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+        let project = projects.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        git(&project, &["init", "-q"]);
+        git(&project, &["commit", "-q", "--allow-empty", "-m", "init"]);
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let tasks = Arc::new(
+            AsyncDirTaskRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+
+        let workspace = workspaces.get_or_create_workspace(&project).await.unwrap();
+        let kept_upload = workspaces
+            .add_file_from_bytes(&workspace.id, "kept.txt", b"kept", None, None, None)
+            .await
+            .unwrap()
+            .path;
+        let stray_upload = kept_upload.with_file_name("stray.txt");
+        std::fs::write(&stray_upload, b"stray bytes").unwrap();
+
+        let sandboxes = projects.path().join(SANDBOX_DIR_NAME);
+        let active_worktree = sandboxes.join("sandbox-live0000");
+        let stale_worktree = sandboxes.join("sandbox-dead0000");
+        for dir in [&active_worktree, &stale_worktree] {
+            let branch = dir.file_name().unwrap().to_str().unwrap();
+            git(
+                &project,
+                &["worktree", "add", "-q", "-b", branch, dir.to_str().unwrap()],
+            );
+            std::fs::write(dir.join("README.md"), b"worktree").unwrap();
+        }
+        // Same naming scheme, but not a worktree of the workspace repository
+        let foreign_dir = sandboxes.join("sandbox-foreign0");
+        std::fs::create_dir_all(&foreign_dir).unwrap();
+        std::fs::write(foreign_dir.join("README.md"), b"not ours").unwrap();
+
+        let live_sandbox = SandboxState {
+            worktree_path: active_worktree.display().to_string(),
+            original_branch: "main".to_string(),
+            sandbox_branch: "sandbox-live0000".to_string(),
+            sandbox_root: None,
+        };
+        sessions
+            .save(&session("live", &workspace.id, Some(live_sandbox)))
+            .await
+            .unwrap();
+        sessions
+            .save(&session("orphan", "deleted-workspace", None))
+            .await
+            .unwrap();
+        sessions
+            .save(&session("placeholder", PLACEHOLDER_WORKSPACE_ID, None))
+            .await
+            .unwrap();
+        tasks.save(&task(KEPT_TASK_ID, "live")).await.unwrap();
+        tasks.save(&task(ORPHAN_TASK_ID, "gone")).await.unwrap();
+
+        let service = IntegrityService::new(
+            sessions.clone(),
+            tasks.clone(),
+            workspaces.clone(),
+            workspaces.workspaces_root_path().to_path_buf(),
+        );
+        Fixture {
+            _dirs: dirs,
+            _projects: projects,
+            service,
+            sessions,
+            tasks,
+            workspaces,
+            kept_upload,
+            stray_upload,
+            active_worktree,
+            stale_worktree,
+            foreign_dir,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_detects_each_orphan_class() {
+        let f = fixture().await;
+        let report = f.service.scan().await.unwrap();
+
+        let mut session_ids: Vec<_> = report
+            .orphaned_sessions
+            .iter()
+            .map(|s| (s.session_id.as_str(), s.is_placeholder_workspace))
+            .collect();
+        session_ids.sort();
+        assert_eq!(session_ids, vec![("orphan", false), ("placeholder", true)]);
+
+        let task_ids: Vec<_> = report
+            .orphaned_tasks
+            .iter()
+            .map(|t| t.task_id.as_str())
+            .collect();
+        assert_eq!(task_ids, vec![ORPHAN_TASK_ID]);
+
+        let uploads: Vec<_> = report
+            .unreferenced_uploads
+            .iter()
+            .map(|u| &u.path)
+            .collect();
+        assert_eq!(uploads, vec![&f.stray_upload]);
+
+        let worktrees: Vec<_> = report.stale_worktrees.iter().map(|w| &w.path).collect();
+        assert_eq!(worktrees, vec![&f.stale_worktree]);
+
+        assert_eq!(report.total_items, 5);
+        assert!(report.reclaimable_bytes >= 11 + 8);
+    }
+
+    #[tokio::test]
+    async fn test_clean_removes_only_selected_items() {
+        let f = fixture().await;
+        let selection = IntegritySelection {
+            session_ids: vec!["orphan".to_string(), "placeholder".to_string()],
+            upload_paths: vec![f.stray_upload.clone(), f.kept_upload.clone()],
+            ..Default::default()
+        };
+
+        let results = f.service.clean(selection).await.unwrap();
+        let outcome = |item: &str| results.iter().find(|r| r.item == item).unwrap().success;
+        assert!(outcome("orphan"));
+        assert!(!outcome("placeholder"));
+        assert!(outcome(&f.stray_upload.display().to_string()));
+        assert!(!outcome(&f.kept_upload.display().to_string()));
+
+        assert!(f.sessions.find_by_id("orphan").await.unwrap().is_none());
+        assert!(
+            f.sessions
+                .find_by_id("placeholder")
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(!f.stray_upload.exists());
+        assert!(f.kept_upload.exists());
+
+        // Unselected items are untouched
+        assert!(f.tasks.find_by_id(ORPHAN_TASK_ID).await.unwrap().is_some());
+        assert!(f.stale_worktree.exists());
+
+        let results = f
+            .service
+            .clean(IntegritySelection {
+                task_ids: vec![ORPHAN_TASK_ID.to_string()],
+                worktree_paths: vec![
+                    f.stale_worktree.clone(),
+                    f.active_worktree.clone(),
+                    f.foreign_dir.clone(),
+                ],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(results.iter().filter(|r| r.success).count(), 2);
+        assert!(f.tasks.find_by_id(ORPHAN_TASK_ID).await.unwrap().is_none());
+        assert!(!f.stale_worktree.exists());
+        assert!(f.active_worktree.exists());
+        assert!(f.foreign_dir.exists());
+
+        let report = f.service.scan().await.unwrap();
+        assert_eq!(
+            report.total_items, 1,
+            "only the placeholder session remains"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_uploads_are_matched_through_symlinked_root() {
+        let f = fixture().await;
+        let links = TempDir::new().unwrap();
+        let linked_root = links.path().join("workspaces");
+        std::os::unix::fs::symlink(f.workspaces.workspaces_root_path(), &linked_root).unwrap();
+
+        let service = IntegrityService::new(
+            f.sessions.clone(),
+            f.tasks.clone(),
+            f.workspaces.clone(),
+            linked_root,
+        );
+        let report = service.scan().await.unwrap();
+
+        let uploads: Vec<_> = report
+            .unreferenced_uploads
+            .iter()
+            .filter_map(|u| u.path.file_name())
+            .collect();
+        assert_eq!(uploads, vec!["stray.txt"]);
+    }
+}
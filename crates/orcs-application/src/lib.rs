@@ -3,14 +3,52 @@
 //! This crate provides use case implementations that coordinate between
 //! domain and infrastructure layers to implement application-level business logic.
 
+pub mod activity_service;
 pub mod adhoc_persona_service;
+pub mod code_apply;
+pub mod command_palette;
+pub mod conversation_script_service;
+#[allow(deprecated)] // llm_toolkit::Agent derive is kept until the attribute macro migration
+pub mod decision_record;
+pub mod integrity_service;
+pub mod pending_action_service;
+pub mod persona_service;
+pub mod quick_action_seeder;
 pub mod session;
+pub mod session_export;
+#[allow(deprecated)] // llm_toolkit::Agent derive is kept until the attribute macro migration
 pub mod session_support_agent_service;
 pub mod session_usecase;
+pub mod task_link_service;
+#[allow(deprecated)] // llm_toolkit::Agent derive is kept until the attribute macro migration
 pub mod utility_agent_service;
+#[allow(deprecated)] // llm_toolkit::Agent derive is kept until the attribute macro migration
+pub mod workspace_digest;
 
+pub use activity_service::{
+    ActivityCounts, ActivityHistogram, ActivityService, DayActivity, HourActivity,
+};
 pub use adhoc_persona_service::AdhocPersonaService;
-pub use session::{SessionMetadataService, SessionUpdater};
+pub use code_apply::{
+    ApplyReport, ApplyRequest, ApplyStage, CodeApplyService, ShellOutput, ShellRunner, SystemShell,
+    VerifyResult,
+};
+pub use command_palette::{CommandPaletteService, PaletteAction, PaletteItem, PaletteKind};
+pub use conversation_script_service::{ConversationScriptService, ScriptOutcome};
+pub use decision_record::{DecisionRecord, DissentingOpinion};
+pub use integrity_service::{
+    IntegrityCleanResult, IntegrityItemKind, IntegrityReport, IntegritySelection, IntegrityService,
+};
+pub use pending_action_service::{PendingActionRequest, PendingActionService};
+pub use persona_service::{PersonaService, PersonaSessionReference};
+pub use quick_action_seeder::{ProjectKind, QuickActionSeeder};
+pub use session::{ReadOnlySession, SessionMetadataService, SessionUpdater};
+pub use session_export::{DateRange, ExportFilter, ExportSummary, SessionExportService};
 pub use session_support_agent_service::SessionSupportAgentService;
-pub use session_usecase::SessionUseCase;
-pub use utility_agent_service::UtilityAgentService;
+pub use session_usecase::{SessionCompaction, SessionUseCase, TurnDistribution};
+pub use task_link_service::{TaskLinkService, TaskOriginMessage};
+pub use utility_agent_service::{UtilityAgentService, UtilityOps};
+pub use workspace_digest::{
+    DigestPeriod, DigestProgress, DigestSource, DigestTask, SessionDigest, WorkspaceDigest,
+    WorkspaceDigestService,
+};
@@ -0,0 +1,405 @@
+//! Pending Action Service
+//!
+//! Holds agent-triggered commands that require user confirmation before they run.
+//! Pending actions live in memory only; they are resolved by approval, denial or
+//! expiry, and each resolution posts a system note back to the originating session
+//! so the agent can see what happened to its request.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Duration;
+pub use orcs_core::clock::{Clock, SystemClock};
+use orcs_core::pending_action::{PendingAction, PendingActionKind, PendingActionStatus};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::SessionUseCase;
+
+/// Message type used for notes posted back to the session.
+///
+/// `context_info` invalidates the dialogue so the note is visible to the agent
+/// on its next turn.
+const NOTE_MESSAGE_TYPE: &str = "context_info";
+
+/// Destination for system notes about pending action resolutions.
+#[async_trait]
+pub trait SystemNoteSink: Send + Sync {
+    /// Posts a system note to the given session.
+    async fn post_note(&self, session_id: &str, content: String, message_type: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl SystemNoteSink for SessionUseCase {
+    async fn post_note(&self, session_id: &str, content: String, message_type: &str) -> Result<()> {
+        self.add_system_message_to_session(
+            session_id,
+            content,
+            Some(message_type.to_string()),
+            None,
+        )
+        .await
+    }
+}
+
+/// Parameters for queuing a new pending action.
+#[derive(Debug, Clone)]
+pub struct PendingActionRequest {
+    pub kind: PendingActionKind,
+    pub session_id: String,
+    pub requested_by: String,
+    pub rendered_command: String,
+    pub command_name: Option<String>,
+    pub args: Option<String>,
+    pub working_dir: Option<String>,
+}
+
+/// In-memory store of agent-triggered actions awaiting confirmation.
+pub struct PendingActionService {
+    actions: RwLock<HashMap<String, PendingAction>>,
+    notes: Arc<dyn SystemNoteSink>,
+    clock: Arc<dyn Clock>,
+    ttl: Duration,
+}
+
+impl PendingActionService {
+    /// Creates a new service using the system clock.
+    pub fn new(notes: Arc<dyn SystemNoteSink>, ttl_secs: u64) -> Self {
+        Self::with_clock(notes, ttl_secs, Arc::new(SystemClock))
+    }
+
+    /// Creates a new service with a custom clock.
+    pub fn with_clock(
+        notes: Arc<dyn SystemNoteSink>,
+        ttl_secs: u64,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            actions: RwLock::new(HashMap::new()),
+            notes,
+            clock,
+            ttl: Duration::seconds(ttl_secs as i64),
+        }
+    }
+
+    /// Queues a gated command instead of executing it.
+    pub async fn enqueue(&self, request: PendingActionRequest) -> PendingAction {
+        let now = self.clock.now();
+        let action = PendingAction {
+            id: Uuid::new_v4().to_string(),
+            kind: request.kind,
+            session_id: request.session_id,
+            requested_by: request.requested_by,
+            rendered_command: request.rendered_command,
+            command_name: request.command_name,
+            args: request.args,
+            working_dir: request.working_dir,
+            created_at: now.to_rfc3339(),
+            expires_at: (now + self.ttl).to_rfc3339(),
+        };
+
+        tracing::info!(
+            "[PendingAction] Queued {} from {}: {}",
+            action.id,
+            action.requested_by,
+            action.rendered_command
+        );
+
+        self.actions
+            .write()
+            .await
+            .insert(action.id.clone(), action.clone());
+        action
+    }
+
+    /// Lists pending actions, oldest first. Expired actions are resolved first.
+    pub async fn list(&self) -> Vec<PendingAction> {
+        self.expire_stale().await;
+
+        let mut actions: Vec<PendingAction> = self.actions.read().await.values().cloned().collect();
+        actions.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        actions
+    }
+
+    /// Approves a pending action and runs it through `execute`.
+    ///
+    /// The execution result (or error) is posted to the session as context info.
+    /// [`PendingActionKind::SlashCommand`] actions are run by the caller after
+    /// approval, so `execute` is not called for them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the action does not exist or has already expired.
+    pub async fn approve<F, Fut>(&self, id: &str, execute: F) -> Result<PendingAction>
+    where
+        F: FnOnce(PendingAction) -> Fut,
+        Fut: Future<Output = std::result::Result<String, String>>,
+    {
+        let action = self.take_live(id).await?;
+
+        if action.kind == PendingActionKind::SlashCommand {
+            let note = format!(
+                "✅ User approved `{}` requested by {}. It will run now.",
+                action.rendered_command, action.requested_by
+            );
+            self.post(&action, note).await;
+            return Ok(action);
+        }
+
+        let note = match execute(action.clone()).await {
+            Ok(output) => format!(
+                "✅ User approved `{}` requested by {}.\n\nOutput:\n```\n{}\n```",
+                action.rendered_command,
+                action.requested_by,
+                output.trim_end()
+            ),
+            Err(error) => format!(
+                "⚠️ User approved `{}` requested by {}, but it failed:\n```\n{}\n```",
+                action.rendered_command, action.requested_by, error
+            ),
+        };
+        self.post(&action, note).await;
+
+        Ok(action)
+    }
+
+    /// Denies a pending action and tells the agent it was blocked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the action does not exist or has already expired.
+    pub async fn deny(&self, id: &str) -> Result<PendingAction> {
+        let action = self.take_live(id).await?;
+        let note = Self::blocked_note(&action, PendingActionStatus::Denied);
+        self.post(&action, note).await;
+        Ok(action)
+    }
+
+    /// Resolves every expired action, posting a note for each.
+    ///
+    /// Returns the actions that expired.
+    pub async fn expire_stale(&self) -> Vec<PendingAction> {
+        let now = self.clock.now();
+        let expired: Vec<PendingAction> = {
+            let mut actions = self.actions.write().await;
+            let ids: Vec<String> = actions
+                .values()
+                .filter(|a| a.is_expired_at(now))
+                .map(|a| a.id.clone())
+                .collect();
+            ids.iter().filter_map(|id| actions.remove(id)).collect()
+        };
+
+        for action in &expired {
+            let note = Self::blocked_note(action, PendingActionStatus::Expired);
+            self.post(action, note).await;
+        }
+
+        expired
+    }
+
+    /// Removes a live action, expiring stale ones first.
+    async fn take_live(&self, id: &str) -> Result<PendingAction> {
+        let expired = self.expire_stale().await;
+        if expired.iter().any(|a| a.id == id) {
+            return Err(anyhow!("Pending action {} has expired", id));
+        }
+
+        self.actions
+            .write()
+            .await
+            .remove(id)
+            .ok_or_else(|| anyhow!("Pending action not found: {}", id))
+    }
+
+    fn blocked_note(action: &PendingAction, status: PendingActionStatus) -> String {
+        let reason = match status {
+            PendingActionStatus::Denied => "was denied by the user",
+            _ => "expired without user approval",
+        };
+        format!(
+            "⛔ `{}` requested by {} {} and was NOT executed.",
+            action.rendered_command, action.requested_by, reason
+        )
+    }
+
+    async fn post(&self, action: &PendingAction, note: String) {
+        if let Err(e) = self
+            .notes
+            .post_note(&action.session_id, note, NOTE_MESSAGE_TYPE)
+            .await
+        {
+            tracing::warn!(
+                "[PendingAction] Failed to post note for {}: {}",
+                action.id,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use std::sync::Mutex;
+
+    struct FakeClock(Mutex<DateTime<Utc>>);
+
+    impl FakeClock {
+        fn advance(&self, secs: i64) {
+            *self.0.lock().unwrap() += Duration::seconds(secs);
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingSink(Mutex<Vec<(String, String, String)>>);
+
+    #[async_trait]
+    impl SystemNoteSink for RecordingSink {
+        async fn post_note(
+            &self,
+            session_id: &str,
+            content: String,
+            message_type: &str,
+        ) -> Result<()> {
+            self.0.lock().unwrap().push((
+                session_id.to_string(),
+                content,
+                message_type.to_string(),
+            ));
+            Ok(())
+        }
+    }
+
+    fn setup() -> (PendingActionService, Arc<FakeClock>, Arc<RecordingSink>) {
+        let clock = Arc::new(FakeClock(Mutex::new(Utc::now())));
+        let sink = Arc::new(RecordingSink::default());
+        let service = PendingActionService::with_clock(sink.clone(), 60, clock.clone());
+        (service, clock, sink)
+    }
+
+    fn request() -> PendingActionRequest {
+        PendingActionRequest {
+            kind: PendingActionKind::Shell,
+            session_id: "session-1".to_string(),
+            requested_by: "Mai".to_string(),
+            rendered_command: "rm -rf dist".to_string(),
+            command_name: None,
+            args: None,
+            working_dir: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_approve_executes_and_posts_context_info() {
+        let (service, _clock, sink) = setup();
+        let action = service.enqueue(request()).await;
+        assert_eq!(service.list().await.len(), 1);
+
+        let approved = service
+            .approve(&action.id, |a| async move {
+                assert_eq!(a.rendered_command, "rm -rf dist");
+                Ok("removed".to_string())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(approved.id, action.id);
+        assert!(service.list().await.is_empty());
+
+        let notes = sink.0.lock().unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].0, "session-1");
+        assert!(notes[0].1.contains("removed"));
+        assert_eq!(notes[0].2, "context_info");
+    }
+
+    #[tokio::test]
+    async fn test_approving_slash_command_leaves_execution_to_caller() {
+        let (service, _clock, sink) = setup();
+        let action = service
+            .enqueue(PendingActionRequest {
+                kind: PendingActionKind::SlashCommand,
+                rendered_command: "/deploy prod".to_string(),
+                command_name: Some("deploy".to_string()),
+                args: Some("prod".to_string()),
+                ..request()
+            })
+            .await;
+
+        let approved = service
+            .approve(&action.id, |_| async move { Err("executed".to_string()) })
+            .await
+            .unwrap();
+
+        assert_eq!(approved.args.as_deref(), Some("prod"));
+        let notes = sink.0.lock().unwrap();
+        assert!(notes[0].1.contains("User approved `/deploy prod`"));
+        assert!(!notes[0].1.contains("executed"));
+    }
+
+    #[tokio::test]
+    async fn test_deny_posts_blocked_note_without_executing() {
+        let (service, _clock, sink) = setup();
+        let action = service.enqueue(request()).await;
+
+        service.deny(&action.id).await.unwrap();
+
+        assert!(service.list().await.is_empty());
+        let notes = sink.0.lock().unwrap();
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].1.contains("denied"));
+        assert!(notes[0].1.contains("NOT executed"));
+    }
+
+    #[tokio::test]
+    async fn test_expired_action_cannot_be_approved() {
+        let (service, clock, sink) = setup();
+        let action = service.enqueue(request()).await;
+
+        clock.advance(61);
+
+        let result = service
+            .approve(&action.id, |_| async { panic!("must not execute") })
+            .await;
+        assert!(result.is_err());
+        assert!(service.list().await.is_empty());
+
+        let notes = sink.0.lock().unwrap();
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].1.contains("expired"));
+    }
+
+    #[tokio::test]
+    async fn test_expire_stale_keeps_live_actions() {
+        let (service, clock, _sink) = setup();
+        let old = service.enqueue(request()).await;
+        clock.advance(30);
+        let fresh = service.enqueue(request()).await;
+        clock.advance(31);
+
+        let expired = service.expire_stale().await;
+
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, old.id);
+        let remaining = service.list().await;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, fresh.id);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_action_is_error() {
+        let (service, _clock, _sink) = setup();
+        assert!(service.deny("missing").await.is_err());
+    }
+}
@@ -0,0 +1,359 @@
+//! Quick Action Seeder
+//!
+//! Fills the Quick Action Dock of a workspace with defaults for its project
+//! type, so new workspaces do not start with empty slots. Default actions are
+//! backed by regular (global) SlashCommands, which are created when missing and
+//! never overwritten, so user edits to them are kept.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use orcs_core::quick_action::{QuickActionConfig, QuickActionRepository};
+use orcs_core::slash_command::{SlashCommand, SlashCommandRepository};
+use orcs_core::workspace::Workspace;
+
+/// Working directory of seeded shell commands.
+const WORKSPACE_DIR: &str = "{workspace_path}";
+
+/// Project type used to pick default quick actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectKind {
+    Rust,
+    Node,
+    Unknown,
+}
+
+impl ProjectKind {
+    /// Detects the project type of a workspace.
+    ///
+    /// Uses the build system recorded in the project context when known, and
+    /// otherwise looks for `Cargo.toml` / `package.json` in the project root.
+    pub fn detect(workspace: &Workspace) -> Self {
+        match workspace.project_context.build_system.as_deref() {
+            Some("cargo") => Self::Rust,
+            Some("npm" | "yarn" | "pnpm" | "bun") => Self::Node,
+            _ => Self::from_markers(&workspace.root_path),
+        }
+    }
+
+    fn from_markers(root: &Path) -> Self {
+        if root.join("Cargo.toml").is_file() {
+            Self::Rust
+        } else if root.join("package.json").is_file() {
+            Self::Node
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Default commands for this project type, in slot order.
+    pub fn default_commands(self) -> Vec<SlashCommand> {
+        match self {
+            Self::Rust => vec![
+                shell("cargo-test", "🧪", "Run the test suite", "cargo test"),
+                shell(
+                    "cargo-clippy",
+                    "📎",
+                    "Run clippy on all targets",
+                    "cargo clippy --all-targets",
+                ),
+                fix_failing_tests(),
+                search(),
+            ],
+            Self::Node => vec![
+                shell("npm-test", "🧪", "Run the test suite", "npm test"),
+                shell("npm-lint", "🧹", "Run the lint script", "npm run lint"),
+                fix_failing_tests(),
+                search(),
+            ],
+            Self::Unknown => vec![files(), search()],
+        }
+    }
+}
+
+fn shell(name: &str, icon: &str, description: &str, content: &str) -> SlashCommand {
+    SlashCommand::new_shell(
+        name.to_string(),
+        icon.to_string(),
+        description.to_string(),
+        content.to_string(),
+        Some(WORKSPACE_DIR.to_string()),
+    )
+}
+
+/// Prompt command that prefills the chat input, e.g. with a builtin command.
+fn prefill(name: &str, icon: &str, description: &str, content: &str) -> SlashCommand {
+    SlashCommand::new_prompt(
+        name.to_string(),
+        icon.to_string(),
+        description.to_string(),
+        content.to_string(),
+    )
+}
+
+fn fix_failing_tests() -> SlashCommand {
+    prefill(
+        "fix-failing-tests",
+        "🩹",
+        "Start a task that fixes the failing tests",
+        "/task Fix the failing tests",
+    )
+}
+
+fn search() -> SlashCommand {
+    prefill(
+        "search-workspace",
+        "🔍",
+        "Search this workspace's sessions and files",
+        "/search ",
+    )
+}
+
+fn files() -> SlashCommand {
+    prefill(
+        "workspace-files",
+        "📁",
+        "List files saved to workspace storage",
+        "/files",
+    )
+}
+
+/// Seeds default quick actions for workspaces.
+pub struct QuickActionSeeder {
+    slash_command_repository: Arc<dyn SlashCommandRepository>,
+    quick_action_repository: Arc<dyn QuickActionRepository>,
+}
+
+impl QuickActionSeeder {
+    /// Creates a new `QuickActionSeeder`.
+    pub fn new(
+        slash_command_repository: Arc<dyn SlashCommandRepository>,
+        quick_action_repository: Arc<dyn QuickActionRepository>,
+    ) -> Self {
+        Self {
+            slash_command_repository,
+            quick_action_repository,
+        }
+    }
+
+    /// Seeds a newly opened workspace whose dock has no configured slot yet.
+    ///
+    /// Workspaces with at least one configured slot are left as the user set them up.
+    pub async fn seed_new_workspace(&self, workspace: &Workspace) -> Result<QuickActionConfig> {
+        let config = self.load(&workspace.id).await?;
+        if !config.configured_slots().is_empty() {
+            return Ok(config);
+        }
+        self.seed(workspace).await
+    }
+
+    /// Binds the default actions for the workspace's project type to empty slots.
+    ///
+    /// Missing commands are created; existing commands and configured slots are
+    /// untouched, and actions already bound to a slot are not bound again, so
+    /// seeding is idempotent.
+    pub async fn seed(&self, workspace: &Workspace) -> Result<QuickActionConfig> {
+        let kind = ProjectKind::detect(workspace);
+        let commands = kind.default_commands();
+        for command in &commands {
+            self.ensure_command(command).await?;
+        }
+
+        let mut config = self.load(&workspace.id).await?;
+        let mut changed = false;
+        for command in &commands {
+            let bound = config
+                .slots
+                .iter()
+                .any(|slot| slot.command_name.as_deref() == Some(command.name.as_str()));
+            if bound {
+                continue;
+            }
+            let Some(slot) = config
+                .slots
+                .iter_mut()
+                .find(|slot| slot.command_name.is_none())
+            else {
+                break;
+            };
+            slot.command_name = Some(command.name.clone());
+            changed = true;
+        }
+
+        if changed {
+            self.quick_action_repository
+                .save(&workspace.id, &config)
+                .await
+                .map_err(|e| anyhow!("Failed to save quick actions: {}", e))?;
+            tracing::info!(
+                "[QuickActionSeeder] Seeded {:?} quick actions for workspace {}",
+                kind,
+                workspace.id
+            );
+        }
+        Ok(config)
+    }
+
+    async fn load(&self, workspace_id: &str) -> Result<QuickActionConfig> {
+        self.quick_action_repository
+            .load(workspace_id)
+            .await
+            .map_err(|e| anyhow!("Failed to load quick actions: {}", e))
+    }
+
+    async fn ensure_command(&self, command: &SlashCommand) -> Result<()> {
+        let existing = self
+            .slash_command_repository
+            .get_command(&command.name)
+            .await
+            .map_err(|e| anyhow!("Failed to look up /{}: {}", command.name, e))?;
+        if existing.is_none() {
+            self.slash_command_repository
+                .save_command(command.clone())
+                .await
+                .map_err(|e| anyhow!("Failed to create /{}: {}", command.name, e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orcs_core::workspace::{ProjectContext, WorkspaceResources};
+    use orcs_infrastructure::{AsyncDirSlashCommandRepository, FileQuickActionRepository};
+    use tempfile::TempDir;
+
+    fn workspace(root: &Path) -> Workspace {
+        Workspace {
+            id: "ws-1".to_string(),
+            name: "project".to_string(),
+            root_path: root.to_path_buf(),
+            workspace_dir: root.join(".orcs"),
+            resources: WorkspaceResources::default(),
+            project_context: ProjectContext::default(),
+            last_accessed: 0,
+            is_favorite: false,
+            last_active_session_id: None,
+            kaiba_rei_id: None,
+        }
+    }
+
+    async fn seeder(storage: &TempDir) -> (QuickActionSeeder, Arc<dyn SlashCommandRepository>) {
+        let commands: Arc<dyn SlashCommandRepository> = Arc::new(
+            AsyncDirSlashCommandRepository::new(Some(storage.path()))
+                .await
+                .unwrap(),
+        );
+        let quick_actions = Arc::new(FileQuickActionRepository::with_base_path(
+            storage.path().join("workspaces"),
+        ));
+        (
+            QuickActionSeeder::new(commands.clone(), quick_actions),
+            commands,
+        )
+    }
+
+    fn slot_commands(config: &QuickActionConfig) -> Vec<Option<&str>> {
+        config
+            .slots
+            .iter()
+            .take(5)
+            .map(|slot| slot.command_name.as_deref())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rust_project_gets_cargo_actions() {
+        let project = TempDir::new().unwrap();
+        std::fs::write(project.path().join("Cargo.toml"), "[package]\n").unwrap();
+        let storage = TempDir::new().unwrap();
+        let (seeder, commands) = seeder(&storage).await;
+
+        let config = seeder.seed(&workspace(project.path())).await.unwrap();
+
+        assert_eq!(
+            slot_commands(&config),
+            vec![
+                Some("cargo-test"),
+                Some("cargo-clippy"),
+                Some("fix-failing-tests"),
+                Some("search-workspace"),
+                None,
+            ]
+        );
+        let test = commands.get_command("cargo-test").await.unwrap().unwrap();
+        assert_eq!(test.content, "cargo test");
+        assert_eq!(test.working_dir.as_deref(), Some("{workspace_path}"));
+    }
+
+    #[tokio::test]
+    async fn test_node_project_gets_npm_actions_and_reseeding_is_idempotent() {
+        let project = TempDir::new().unwrap();
+        std::fs::write(project.path().join("package.json"), "{}").unwrap();
+        let storage = TempDir::new().unwrap();
+        let (seeder, commands) = seeder(&storage).await;
+        let workspace = workspace(project.path());
+
+        // A user-configured slot and a customized command survive seeding
+        let mut config = QuickActionConfig::default();
+        config.set_slot_command("A", Some("review".to_string()));
+        seeder
+            .quick_action_repository
+            .save(&workspace.id, &config)
+            .await
+            .unwrap();
+        let mut custom = fix_failing_tests();
+        custom.content = "/task Fix the flaky tests".to_string();
+        commands.save_command(custom).await.unwrap();
+
+        let first = seeder.seed(&workspace).await.unwrap();
+        let second = seeder.seed(&workspace).await.unwrap();
+
+        let expected = vec![
+            Some("review"),
+            Some("npm-test"),
+            Some("npm-lint"),
+            Some("fix-failing-tests"),
+            Some("search-workspace"),
+        ];
+        assert_eq!(slot_commands(&first), expected);
+        assert_eq!(slot_commands(&second), expected);
+        assert_eq!(second.configured_slots().len(), 5);
+        let fix = commands
+            .get_command("fix-failing-tests")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fix.content, "/task Fix the flaky tests");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_project_gets_generic_actions_once() {
+        let project = TempDir::new().unwrap();
+        let storage = TempDir::new().unwrap();
+        let (seeder, _) = seeder(&storage).await;
+        let workspace = workspace(project.path());
+
+        let config = seeder.seed_new_workspace(&workspace).await.unwrap();
+        assert_eq!(
+            slot_commands(&config)[..3],
+            [Some("workspace-files"), Some("search-workspace"), None]
+        );
+
+        // Once configured, opening the workspace again leaves the dock alone
+        let mut config = config;
+        config.set_slot_command("A", None);
+        seeder
+            .quick_action_repository
+            .save(&workspace.id, &config)
+            .await
+            .unwrap();
+        let reopened = seeder.seed_new_workspace(&workspace).await.unwrap();
+        assert_eq!(
+            slot_commands(&reopened)[..2],
+            [None, Some("search-workspace")]
+        );
+    }
+}
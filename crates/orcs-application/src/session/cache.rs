@@ -1,15 +1,35 @@
 use orcs_core::session::InteractionManagerTrait;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+/// A cached manager and when it was last handed out.
+struct CacheEntry<T> {
+    manager: Arc<T>,
+    last_access: Mutex<Instant>,
+}
+
+impl<T> CacheEntry<T> {
+    fn new(manager: Arc<T>) -> Self {
+        Self {
+            manager,
+            last_access: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_access.lock().unwrap().elapsed()
+    }
+}
+
 /// In-memory cache for InteractionManager instances.
 ///
 /// This cache stores loaded InteractionManager instances to avoid
 /// repeated deserialization and reconstruction from Session data.
 pub struct SessionCache<T: InteractionManagerTrait> {
     /// In-memory session cache
-    sessions: Arc<RwLock<HashMap<String, Arc<T>>>>,
+    sessions: Arc<RwLock<HashMap<String, CacheEntry<T>>>>,
 }
 
 impl<T: InteractionManagerTrait> SessionCache<T> {
@@ -28,10 +48,13 @@ impl<T: InteractionManagerTrait> SessionCache<T> {
     ///
     /// # Returns
     ///
-    /// `Some(manager)` if the session is cached, `None` otherwise.
+    /// `Some(manager)` if the session is cached, `None` otherwise. A hit
+    /// counts as activity for [`Self::idle_sessions`].
     pub async fn get(&self, session_id: &str) -> Option<Arc<T>> {
         let sessions = self.sessions.read().await;
-        sessions.get(session_id).cloned()
+        let entry = sessions.get(session_id)?;
+        *entry.last_access.lock().unwrap() = Instant::now();
+        Some(entry.manager.clone())
     }
 
     /// Inserts an InteractionManager into the cache.
@@ -42,7 +65,7 @@ impl<T: InteractionManagerTrait> SessionCache<T> {
     /// * `manager` - The InteractionManager to cache
     pub async fn insert(&self, session_id: String, manager: Arc<T>) {
         let mut sessions = self.sessions.write().await;
-        sessions.insert(session_id, manager);
+        sessions.insert(session_id, CacheEntry::new(manager));
     }
 
     /// Removes an InteractionManager from the cache.
@@ -55,6 +78,42 @@ impl<T: InteractionManagerTrait> SessionCache<T> {
         sessions.remove(session_id);
     }
 
+    /// Returns all cached InteractionManagers.
+    pub async fn all(&self) -> Vec<Arc<T>> {
+        let sessions = self.sessions.read().await;
+        sessions
+            .values()
+            .map(|entry| entry.manager.clone())
+            .collect()
+    }
+
+    /// Returns the managers not handed out by [`Self::get`] for at least `timeout`.
+    ///
+    /// Does not count as access, so the managers stay idle.
+    pub async fn idle_sessions(&self, timeout: Duration) -> Vec<Arc<T>> {
+        let sessions = self.sessions.read().await;
+        sessions
+            .values()
+            .filter(|entry| entry.idle_for() >= timeout)
+            .map(|entry| entry.manager.clone())
+            .collect()
+    }
+
+    /// Removes a manager only if it is still idle for at least `timeout`.
+    ///
+    /// Returns whether it was removed; a session accessed since it was found
+    /// idle stays cached.
+    pub async fn remove_if_idle(&self, session_id: &str, timeout: Duration) -> bool {
+        let mut sessions = self.sessions.write().await;
+        let idle = sessions
+            .get(session_id)
+            .is_some_and(|entry| entry.idle_for() >= timeout);
+        if idle {
+            sessions.remove(session_id);
+        }
+        idle
+    }
+
     /// Clears all cached sessions.
     pub async fn clear(&self) {
         let mut sessions = self.sessions.write().await;
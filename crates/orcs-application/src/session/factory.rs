@@ -1,7 +1,10 @@
+use orcs_core::offline::OfflineMode;
 use orcs_core::repository::PersonaRepository;
 use orcs_core::session::{AppMode, Session};
 use orcs_core::user::UserService;
+use orcs_core::workspace::manager::WorkspaceStorageService;
 use orcs_infrastructure::user_service::load_root_config;
+use orcs_infrastructure::{StreamRecoveryWriter, WireLog};
 use orcs_interaction::InteractionManager;
 use std::sync::Arc;
 
@@ -9,11 +12,20 @@ use std::sync::Arc;
 ///
 /// This factory handles the conversion between Session (persistent data)
 /// and InteractionManager (runtime state).
+#[derive(Clone)]
 pub struct SessionFactory {
     /// Repository for persona configurations
     persona_repository: Arc<dyn PersonaRepository>,
     /// Service for user information
     user_service: Arc<dyn UserService>,
+    /// Recovery writer handed to every created manager
+    stream_recovery: Option<StreamRecoveryWriter>,
+    /// Wire log handed to every created manager
+    wire_log: Option<WireLog>,
+    /// Offline mode switch shared with every created manager
+    offline_mode: OfflineMode,
+    /// Storage created managers keep long pastes in
+    workspace_storage: Option<Arc<dyn WorkspaceStorageService>>,
 }
 
 impl SessionFactory {
@@ -30,6 +42,50 @@ impl SessionFactory {
         Self {
             persona_repository,
             user_service,
+            stream_recovery: None,
+            wire_log: None,
+            offline_mode: OfflineMode::default(),
+            workspace_storage: None,
+        }
+    }
+
+    /// Makes created managers record their turns for crash recovery.
+    pub fn with_stream_recovery(mut self, writer: StreamRecoveryWriter) -> Self {
+        self.stream_recovery = Some(writer);
+        self
+    }
+
+    /// Makes created managers log backend exchanges of sessions with the wire log on.
+    pub fn with_wire_log(mut self, log: WireLog) -> Self {
+        self.wire_log = Some(log);
+        self
+    }
+
+    /// Makes created managers follow the app-wide offline mode switch.
+    pub fn with_offline_mode(mut self, offline_mode: OfflineMode) -> Self {
+        self.offline_mode = offline_mode;
+        self
+    }
+
+    /// Makes created managers store long non-prose pastes in `storage`.
+    pub fn with_workspace_storage(mut self, storage: Arc<dyn WorkspaceStorageService>) -> Self {
+        self.workspace_storage = Some(storage);
+        self
+    }
+
+    fn attach_stream_recovery(&self, manager: InteractionManager) -> InteractionManager {
+        let manager = manager.with_offline_mode(self.offline_mode.clone());
+        let manager = match &self.workspace_storage {
+            Some(storage) => manager.with_workspace_storage(storage.clone()),
+            None => manager,
+        };
+        let manager = match &self.wire_log {
+            Some(log) => manager.with_wire_log(log.clone()),
+            None => manager,
+        };
+        match &self.stream_recovery {
+            Some(writer) => manager.with_stream_recovery(writer.clone()),
+            None => manager,
         }
     }
 
@@ -43,16 +99,17 @@ impl SessionFactory {
     ///
     /// A new InteractionManager instance.
     pub fn create_interaction_manager(&self, session_id: String) -> InteractionManager {
-        // Load EnvSettings from config
-        let env_settings = load_root_config()
-            .map(|config| config.env_settings)
-            .unwrap_or_default(); // Use default if config load fails
+        // Load EnvSettings and default models from config
+        let config = load_root_config().unwrap_or_default(); // Use default if config load fails
 
-        InteractionManager::new_session(
-            session_id,
-            self.persona_repository.clone(),
-            self.user_service.clone(),
-            env_settings,
+        self.attach_stream_recovery(
+            InteractionManager::new_session(
+                session_id,
+                self.persona_repository.clone(),
+                self.user_service.clone(),
+                config.env_settings,
+            )
+            .with_default_models(config.default_models),
         )
     }
 
@@ -66,16 +123,17 @@ impl SessionFactory {
     ///
     /// An InteractionManager instance restored from the session data.
     pub fn from_session(&self, session: Session) -> InteractionManager {
-        // Load EnvSettings from config
-        let env_settings = load_root_config()
-            .map(|config| config.env_settings)
-            .unwrap_or_default(); // Use default if config load fails
+        // Load EnvSettings and default models from config
+        let config = load_root_config().unwrap_or_default(); // Use default if config load fails
 
-        InteractionManager::from_session(
-            session,
-            self.persona_repository.clone(),
-            self.user_service.clone(),
-            env_settings,
+        self.attach_stream_recovery(
+            InteractionManager::from_session(
+                session,
+                self.persona_repository.clone(),
+                self.user_service.clone(),
+                config.env_settings,
+            )
+            .with_default_models(config.default_models),
         )
     }
 
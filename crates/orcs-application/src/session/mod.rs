@@ -6,9 +6,11 @@
 mod cache;
 mod factory;
 mod metadata_service;
+mod readonly;
 mod updater;
 
 pub use cache::SessionCache;
 pub use factory::SessionFactory;
 pub use metadata_service::SessionMetadataService;
+pub use readonly::ReadOnlySession;
 pub use updater::SessionUpdater;
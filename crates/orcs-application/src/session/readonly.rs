@@ -0,0 +1,31 @@
+use orcs_core::session::{Session, SessionSummary};
+
+/// A session opened for reading only.
+///
+/// Built straight from stored session data: no InteractionManager or dialogue
+/// is constructed, and nothing is ever written back.
+#[derive(Debug, Clone)]
+pub struct ReadOnlySession {
+    session: Session,
+}
+
+impl ReadOnlySession {
+    /// Creates a view of `session`.
+    pub fn new(session: Session) -> Self {
+        Self { session }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.session.id
+    }
+
+    /// The stored session data.
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Session metadata without message contents.
+    pub fn summary(&self) -> SessionSummary {
+        SessionSummary::from(&self.session)
+    }
+}
@@ -0,0 +1,623 @@
+//! Session Export
+//!
+//! Machine-friendly JSONL export of conversation history for analysis and
+//! fine-tuning pipelines. Each line is one [`ExportRecord`].
+//!
+//! Sessions are streamed: IDs are listed first, then each session is loaded,
+//! written and dropped before the next one, so the full history is never held
+//! in memory at once.
+
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use orcs_core::repository::SessionRepository;
+use orcs_core::session::{ConversationMessage, MessageRole, Session, SourceRef, TurnStats};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+
+/// Inclusive time range for exported messages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DateRange {
+    /// Earliest message timestamp to include.
+    #[serde(default)]
+    pub start: Option<DateTime<Utc>>,
+    /// Latest message timestamp to include.
+    #[serde(default)]
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    pub(crate) fn contains(&self, timestamp: &str) -> bool {
+        let Ok(ts) = DateTime::parse_from_rfc3339(timestamp) else {
+            // Unparseable timestamps are only excluded when a range is set
+            return self.start.is_none() && self.end.is_none();
+        };
+        let ts = ts.with_timezone(&Utc);
+        self.start.is_none_or(|start| ts >= start) && self.end.is_none_or(|end| ts <= end)
+    }
+}
+
+/// Selects which sessions and messages are exported.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFilter {
+    /// Only export sessions from this workspace.
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    /// Only export messages within this time range.
+    #[serde(default)]
+    pub date_range: Option<DateRange>,
+    /// Include system messages (session notifications, command outputs).
+    #[serde(default)]
+    pub include_system: bool,
+    /// Include archived sessions.
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+/// One exported message (one JSONL line).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportRecord {
+    pub session_id: String,
+    pub workspace_id: String,
+    pub persona_id: Option<String>,
+    pub persona_name: Option<String>,
+    pub backend: Option<String>,
+    pub model: Option<String>,
+    pub role: MessageRole,
+    pub content: String,
+    pub timestamp: String,
+    /// Persona ID of the agent this turn reacted to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_response_to: Option<String>,
+    /// For context messages: the source the message provides.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceRef>,
+    /// For agent turns: the sources the turn copied passages from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<SourceRef>,
+    /// For agent turns: duration, token usage and estimated cost of the turn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_stats: Option<TurnStats>,
+    /// Rough token count (≈4 characters per token).
+    pub token_estimate: usize,
+}
+
+/// Result of an export run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSummary {
+    pub sessions_exported: usize,
+    pub messages_exported: usize,
+}
+
+/// Built-in redaction rules applied when redaction is enabled.
+static REDACTION_RULES: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        (r"sk-[A-Za-z0-9_\-]{16,}", "[REDACTED_API_KEY]"),
+        (r"AIza[0-9A-Za-z_\-]{35}", "[REDACTED_API_KEY]"),
+        (
+            r"(?i)bearer\s+[A-Za-z0-9._\-]{16,}",
+            "Bearer [REDACTED_TOKEN]",
+        ),
+        (
+            r"(?i)\b(api[_-]?key|secret|password|token)\s*[:=]\s*\S+",
+            "$1=[REDACTED]",
+        ),
+        (
+            r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}",
+            "[REDACTED_EMAIL]",
+        ),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| (Regex::new(pattern).expect("valid regex"), replacement))
+    .collect()
+});
+
+/// Applies the built-in redaction rules to a text.
+pub fn redact(text: &str) -> String {
+    REDACTION_RULES
+        .iter()
+        .fold(text.to_string(), |acc, (re, replacement)| {
+            re.replace_all(&acc, *replacement).into_owned()
+        })
+}
+
+/// Estimates the token count of a text (≈4 characters per token).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Exports sessions as JSONL.
+pub struct SessionExportService {
+    session_repository: Arc<dyn SessionRepository>,
+    redact: bool,
+}
+
+impl SessionExportService {
+    /// Creates a new export service.
+    pub fn new(session_repository: Arc<dyn SessionRepository>) -> Self {
+        Self {
+            session_repository,
+            redact: false,
+        }
+    }
+
+    /// Enables or disables the built-in redaction rules.
+    pub fn with_redaction(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    /// Streams matching sessions to `dest_path`, one JSON object per message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the destination cannot be written or sessions cannot be listed.
+    /// Sessions that fail to load are skipped.
+    pub async fn export_sessions_jsonl(
+        &self,
+        filter: &ExportFilter,
+        dest_path: &Path,
+    ) -> Result<ExportSummary> {
+        let file = File::create(dest_path)
+            .await
+            .with_context(|| format!("Failed to create export file: {}", dest_path.display()))?;
+        let mut writer = BufWriter::new(file);
+        let mut summary = ExportSummary::default();
+
+        for session_id in self.session_repository.list_ids().await? {
+            let session = match self.session_repository.find_by_id(&session_id).await {
+                Ok(Some(session)) => session,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!("[SessionExport] Skipping session {}: {}", session_id, e);
+                    continue;
+                }
+            };
+
+            if !Self::matches_session(&session, filter) {
+                continue;
+            }
+
+            let records = self.session_records(&session, filter);
+            if records.is_empty() {
+                continue;
+            }
+
+            for record in &records {
+                let mut line = serde_json::to_string(record)?;
+                line.push('\n');
+                writer.write_all(line.as_bytes()).await?;
+            }
+
+            summary.sessions_exported += 1;
+            summary.messages_exported += records.len();
+        }
+
+        writer.flush().await?;
+        Ok(summary)
+    }
+
+    fn matches_session(session: &Session, filter: &ExportFilter) -> bool {
+        if session.is_archived && !filter.include_archived {
+            return false;
+        }
+        filter
+            .workspace_id
+            .as_ref()
+            .is_none_or(|id| &session.workspace_id == id)
+    }
+
+    /// Builds the records for one session, ordered by timestamp.
+    fn session_records(&self, session: &Session, filter: &ExportFilter) -> Vec<ExportRecord> {
+        let in_range = |msg: &ConversationMessage| {
+            filter
+                .date_range
+                .as_ref()
+                .is_none_or(|range| range.contains(&msg.timestamp))
+        };
+
+        let mut records = Vec::new();
+
+        // History keys are persona IDs only for assistant messages; user
+        // messages are keyed by the user's name.
+        for (key, history) in &session.persona_histories {
+            for msg in history {
+                if msg.content.trim().is_empty() || !in_range(msg) {
+                    continue;
+                }
+                if matches!(msg.role, MessageRole::System) && !filter.include_system {
+                    continue;
+                }
+                let persona_id = matches!(msg.role, MessageRole::Assistant).then_some(key);
+                records.push(self.record(session, persona_id, msg));
+            }
+        }
+
+        if filter.include_system {
+            for msg in &session.system_messages {
+                if msg.content.trim().is_empty() || !in_range(msg) {
+                    continue;
+                }
+                records.push(self.record(session, None, msg));
+            }
+        }
+
+        records.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        records
+    }
+
+    fn record(
+        &self,
+        session: &Session,
+        persona_id: Option<&String>,
+        msg: &ConversationMessage,
+    ) -> ExportRecord {
+        // Long pastes are exported in full from their stored file
+        let full_content = msg
+            .metadata
+            .full_content_path
+            .as_deref()
+            .and_then(|path| std::fs::read_to_string(path).ok());
+        let full_content = full_content.as_deref().unwrap_or(&msg.content);
+        let content = if self.redact {
+            redact(full_content)
+        } else {
+            full_content.to_string()
+        };
+
+        ExportRecord {
+            session_id: session.id.clone(),
+            workspace_id: session.workspace_id.clone(),
+            persona_id: persona_id.cloned(),
+            persona_name: persona_id.and_then(|id| session.participants.get(id).cloned()),
+            backend: persona_id.and_then(|id| session.participant_backends.get(id).cloned()),
+            model: persona_id.and_then(|id| session.participant_models.get(id).cloned().flatten()),
+            role: msg.role.clone(),
+            token_estimate: estimate_tokens(&content),
+            content,
+            timestamp: msg.timestamp.clone(),
+            in_response_to: msg.metadata.in_response_to.clone(),
+            source: msg.metadata.source.clone(),
+            sources: msg.metadata.sources.clone(),
+            turn_stats: msg.metadata.turn_stats.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use llm_toolkit::agent::dialogue::ExecutionModel;
+    use orcs_core::session::{AppMode, MessageMetadata, SourceKind};
+    use std::collections::HashMap;
+    use tokio::sync::RwLock;
+
+    #[derive(Default)]
+    struct InMemorySessionRepository {
+        sessions: RwLock<HashMap<String, Session>>,
+    }
+
+    #[async_trait]
+    impl SessionRepository for InMemorySessionRepository {
+        async fn find_by_id(&self, session_id: &str) -> orcs_core::error::Result<Option<Session>> {
+            Ok(self.sessions.read().await.get(session_id).cloned())
+        }
+
+        async fn save(&self, session: &Session) -> orcs_core::error::Result<()> {
+            self.sessions
+                .write()
+                .await
+                .insert(session.id.clone(), session.clone());
+            Ok(())
+        }
+
+        async fn delete(&self, session_id: &str) -> orcs_core::error::Result<()> {
+            self.sessions.write().await.remove(session_id);
+            Ok(())
+        }
+
+        async fn list_all(&self) -> orcs_core::error::Result<Vec<Session>> {
+            Ok(self.sessions.read().await.values().cloned().collect())
+        }
+    }
+
+    fn message(role: MessageRole, content: &str, timestamp: &str) -> ConversationMessage {
+        ConversationMessage {
+            role,
+            content: content.to_string(),
+            timestamp: timestamp.to_string(),
+            metadata: MessageMetadata::default(),
+            attachments: vec![],
+        }
+    }
+
+    fn session(id: &str, workspace_id: &str, is_archived: bool) -> Session {
+        let mut persona_histories = HashMap::new();
+        persona_histories.insert(
+            "You".to_string(),
+            vec![message(MessageRole::User, "Hello", "2024-01-01T00:00:00Z")],
+        );
+        persona_histories.insert(
+            "mai".to_string(),
+            vec![message(
+                MessageRole::Assistant,
+                "Contact me at mai@example.com",
+                "2024-01-01T00:00:01Z",
+            )],
+        );
+
+        Session {
+            id: id.to_string(),
+            title: format!("Session {}", id),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            current_persona_id: "mai".to_string(),
+            persona_histories,
+            app_mode: AppMode::Idle,
+            workspace_id: workspace_id.to_string(),
+            active_participant_ids: vec!["mai".to_string()],
+            execution_strategy: ExecutionModel::Broadcast,
+            system_messages: vec![
+                message(MessageRole::System, "Mai joined", "2024-01-01T00:00:02Z"),
+                message(MessageRole::System, "Rate limited", "2024-01-01T00:00:03Z"),
+            ],
+            participants: HashMap::from([("mai".to_string(), "Mai".to_string())]),
+            participant_icons: HashMap::new(),
+            participant_colors: HashMap::new(),
+            participant_backends: HashMap::from([("mai".to_string(), "claude_api".to_string())]),
+            participant_models: HashMap::from([(
+                "mai".to_string(),
+                Some("claude-sonnet-4-6".to_string()),
+            )]),
+            conversation_mode: Default::default(),
+            talk_style: None,
+            is_favorite: false,
+            is_archived,
+            sort_order: None,
+            auto_chat_config: None,
+            is_muted: false,
+            context_mode: Default::default(),
+            sandbox_state: None,
+            last_memory_sync_at: None,
+            is_generating: false,
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: 8_000,
+        }
+    }
+
+    async fn fixture() -> Arc<dyn SessionRepository> {
+        let repo = InMemorySessionRepository::default();
+        repo.save(&session("s1", "ws-1", false)).await.unwrap();
+        repo.save(&session("s2", "ws-2", false)).await.unwrap();
+        Arc::new(repo)
+    }
+
+    fn read_lines(path: &Path) -> Vec<ExportRecord> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_export_two_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("export.jsonl");
+        let service = SessionExportService::new(fixture().await);
+
+        let summary = service
+            .export_sessions_jsonl(&ExportFilter::default(), &dest)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.sessions_exported, 2);
+        assert_eq!(summary.messages_exported, 4);
+
+        let records = read_lines(&dest);
+        assert_eq!(records.len(), 4);
+        let assistant = records
+            .iter()
+            .find(|r| r.role == MessageRole::Assistant)
+            .unwrap();
+        assert_eq!(assistant.persona_id.as_deref(), Some("mai"));
+        assert_eq!(assistant.persona_name.as_deref(), Some("Mai"));
+        assert_eq!(assistant.backend.as_deref(), Some("claude_api"));
+        assert_eq!(assistant.model.as_deref(), Some("claude-sonnet-4-6"));
+        assert_eq!(
+            assistant.token_estimate,
+            estimate_tokens(&assistant.content)
+        );
+
+        let user = records
+            .iter()
+            .find(|r| r.role == MessageRole::User)
+            .unwrap();
+        assert_eq!(user.persona_id, None);
+        assert_eq!(user.persona_name, None);
+
+        // Every line carries the full schema
+        let raw = std::fs::read_to_string(&dest).unwrap();
+        assert!(raw.contains(r#""role":"User""#));
+        for line in raw.lines() {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            for key in [
+                "session_id",
+                "workspace_id",
+                "persona_id",
+                "persona_name",
+                "backend",
+                "model",
+                "role",
+                "content",
+                "timestamp",
+                "token_estimate",
+            ] {
+                assert!(value.get(key).is_some(), "missing key {}", key);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_filters_and_redaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("export.jsonl");
+        let repo = fixture().await;
+        repo.save(&session("s3", "ws-1", true)).await.unwrap();
+        let service = SessionExportService::new(repo).with_redaction(true);
+
+        let filter = ExportFilter {
+            workspace_id: Some("ws-1".to_string()),
+            include_system: true,
+            ..Default::default()
+        };
+        let summary = service.export_sessions_jsonl(&filter, &dest).await.unwrap();
+
+        // s3 is archived, s2 is in another workspace
+        assert_eq!(summary.sessions_exported, 1);
+        assert_eq!(summary.messages_exported, 4);
+
+        let records = read_lines(&dest);
+        assert!(records.iter().all(|r| r.session_id == "s1"));
+        let system: Vec<_> = records
+            .iter()
+            .filter(|r| r.role == MessageRole::System)
+            .collect();
+        assert_eq!(system.len(), 2);
+        assert!(system.iter().all(|r| r.persona_id.is_none()));
+        assert!(
+            records
+                .iter()
+                .any(|r| r.content == "Contact me at [REDACTED_EMAIL]")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_date_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("export.jsonl");
+        let service = SessionExportService::new(fixture().await);
+
+        let filter = ExportFilter {
+            date_range: Some(DateRange {
+                start: Some("2024-01-01T00:00:01Z".parse().unwrap()),
+                end: None,
+            }),
+            ..Default::default()
+        };
+        let summary = service.export_sessions_jsonl(&filter, &dest).await.unwrap();
+
+        assert_eq!(summary.messages_exported, 2);
+        assert!(
+            read_lines(&dest)
+                .iter()
+                .all(|r| r.role == MessageRole::Assistant)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_includes_message_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("export.jsonl");
+        let log = SourceRef {
+            source_id: "source-1".to_string(),
+            kind: SourceKind::ShellOutput,
+            label: "$ cargo test".to_string(),
+        };
+        let mut s1 = session("s1", "ws-1", false);
+        s1.system_messages[0].metadata.source = Some(log.clone());
+        s1.persona_histories.get_mut("mai").unwrap()[0]
+            .metadata
+            .sources = vec![log.clone()];
+        let repo = InMemorySessionRepository::default();
+        repo.save(&s1).await.unwrap();
+        let service = SessionExportService::new(Arc::new(repo));
+
+        let filter = ExportFilter {
+            include_system: true,
+            ..Default::default()
+        };
+        service.export_sessions_jsonl(&filter, &dest).await.unwrap();
+
+        let records = read_lines(&dest);
+        let assistant = records
+            .iter()
+            .find(|r| r.role == MessageRole::Assistant)
+            .unwrap();
+        assert_eq!(assistant.sources, vec![log.clone()]);
+        let source = records.iter().find(|r| r.content == "Mai joined").unwrap();
+        assert_eq!(source.source, Some(log));
+        let user = records
+            .iter()
+            .find(|r| r.role == MessageRole::User)
+            .unwrap();
+        assert!(user.sources.is_empty());
+        assert!(
+            !std::fs::read_to_string(&dest)
+                .unwrap()
+                .contains("\"sources\":[]")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_includes_turn_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("export.jsonl");
+        let stats = TurnStats {
+            duration_ms: 900,
+            input_tokens: Some(120),
+            output_tokens: Some(40),
+            estimated_cost_microusd: Some(960),
+        };
+        let mut s1 = session("s1", "ws-1", false);
+        s1.persona_histories.get_mut("mai").unwrap()[0]
+            .metadata
+            .turn_stats = Some(stats.clone());
+        let repo = InMemorySessionRepository::default();
+        repo.save(&s1).await.unwrap();
+        let service = SessionExportService::new(Arc::new(repo));
+
+        service
+            .export_sessions_jsonl(&ExportFilter::default(), &dest)
+            .await
+            .unwrap();
+
+        let records = read_lines(&dest);
+        let assistant = records
+            .iter()
+            .find(|r| r.role == MessageRole::Assistant)
+            .unwrap();
+        assert_eq!(assistant.turn_stats, Some(stats));
+        let user = records
+            .iter()
+            .find(|r| r.role == MessageRole::User)
+            .unwrap();
+        assert_eq!(user.turn_stats, None);
+    }
+
+    #[test]
+    fn test_redact_api_keys() {
+        let redacted = redact("key sk-abcdefghijklmnopqrstuvwx and api_key=12345");
+        assert!(!redacted.contains("sk-abcdefghijklmnopqrstuvwx"));
+        assert!(!redacted.contains("12345"));
+    }
+}
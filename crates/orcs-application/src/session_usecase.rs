@@ -4,17 +4,37 @@
 //! between `SessionManager` and `WorkspaceStorageService` to ensure data consistency
 //! and proper state management across workspace-session relationships.
 
-use crate::session::{SessionCache, SessionFactory, SessionUpdater};
-use anyhow::{Result, anyhow};
+use crate::decision_record::{
+    DecisionRecord, DecisionRecordAgent, decision_transcript, request_decision_record,
+};
+use crate::quick_action_seeder::QuickActionSeeder;
+use crate::session::{ReadOnlySession, SessionCache, SessionFactory, SessionUpdater};
+use crate::session_export::DateRange;
+use anyhow::{Context, Result, anyhow};
+use llm_toolkit::agent::Agent;
+use orcs_core::error::OrcsError;
 use orcs_core::memory::MemorySyncService;
+use orcs_core::offline::OfflineMode;
+use orcs_core::persona::PersonaBackend;
 use orcs_core::repository::PersonaRepository;
-use orcs_core::session::{AppMode, PLACEHOLDER_WORKSPACE_ID, Session, SessionRepository};
+use orcs_core::session::{
+    AppMode, ConversationMessage, ConversationTurn, CostBreakdown, ErrorSeverity,
+    LANGUAGE_SAMPLE_MESSAGES, MessageMetadata, MessageRole, PLACEHOLDER_WORKSPACE_ID, Session,
+    SessionRepository, SourceRef, SystemEventType, WEB_CLIP_PREFIX, detect_language,
+};
 use orcs_core::state::repository::StateRepository;
 use orcs_core::user::UserService;
+use orcs_core::workspace::UploadedFile;
 use orcs_core::workspace::manager::WorkspaceStorageService;
-use orcs_interaction::InteractionManager;
+use orcs_infrastructure::{PartialTurnStore, StreamRecoveryWriter, WebClip, WireLog};
+use orcs_interaction::{DialogueMessage, InteractionManager, InteractionResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 /// Use case for managing sessions with workspace context.
@@ -35,6 +55,48 @@ use uuid::Uuid;
 ///
 /// All internal components are wrapped in `Arc` and use interior mutability
 /// (`RwLock`, `Mutex`) for thread-safe concurrent access.
+/// Result of [`SessionUseCase::compact_session`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCompaction {
+    pub session_id: String,
+    /// Repeated consecutive system messages that were dropped
+    pub system_messages_removed: usize,
+    /// Stored size before compaction (None if the repository cannot tell)
+    pub bytes_before: Option<u64>,
+    /// Stored size after compaction (None if the repository cannot tell)
+    pub bytes_after: Option<u64>,
+    /// Bytes reclaimed (0 if the sizes are unknown or the file grew)
+    pub bytes_saved: u64,
+}
+
+/// Assistant turns per persona name with a fairness score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TurnDistribution {
+    /// Assistant turns keyed by persona name
+    pub turns: HashMap<String, usize>,
+    /// Jain's fairness index: 1.0 when every persona spoke equally often,
+    /// approaching `1 / n` as a single persona dominates
+    pub fairness: f64,
+}
+
+impl TurnDistribution {
+    /// Computes the fairness score of `turns`.
+    ///
+    /// A distribution without any turns counts as fair.
+    pub fn new(turns: HashMap<String, usize>) -> Self {
+        let total: usize = turns.values().sum();
+        let squares: usize = turns.values().map(|count| count * count).sum();
+        let fairness = if squares == 0 {
+            1.0
+        } else {
+            (total * total) as f64 / (turns.len() * squares) as f64
+        };
+        Self { turns, fairness }
+    }
+}
+
 /// Callback type for memory sync errors.
 pub type MemorySyncErrorCallback = Arc<dyn Fn(String) + Send + Sync>;
 
@@ -57,6 +119,18 @@ pub struct SessionUseCase {
     memory_sync_service: Arc<RwLock<Option<Arc<dyn MemorySyncService>>>>,
     /// Optional callback for memory sync errors (for UI notifications)
     memory_sync_error_callback: Arc<RwLock<Option<MemorySyncErrorCallback>>>,
+    /// Optional store of partial output from interrupted turns
+    partial_turn_store: Option<Arc<PartialTurnStore>>,
+    /// Session currently opened read-only; blocks mutations until closed or promoted
+    readonly_session: Arc<RwLock<Option<Arc<ReadOnlySession>>>>,
+    /// Optional seeder of default quick actions for newly opened workspaces
+    quick_action_seeder: Option<Arc<QuickActionSeeder>>,
+    /// Utility agent that extracts decision records from transcripts
+    decision_agent: Arc<dyn Agent<Output = String, Expertise = String>>,
+    /// Whether switching sessions warms up the participants' API agents
+    agent_warmup: bool,
+    /// App-wide offline mode; memory sync pauses while it is on
+    offline_mode: OfflineMode,
 }
 
 impl SessionUseCase {
@@ -79,17 +153,125 @@ impl SessionUseCase {
         Self {
             session_repository: session_repository.clone(),
             session_cache: Arc::new(SessionCache::new()),
-            session_factory: Arc::new(SessionFactory::new(
-                persona_repository.clone(),
-                user_service.clone(),
-            )),
+            session_factory: Arc::new(
+                SessionFactory::new(persona_repository.clone(), user_service.clone())
+                    .with_workspace_storage(workspace_storage_service.clone()),
+            ),
             workspace_storage_service,
             app_state_service,
             persona_repository,
             user_service,
             memory_sync_service: Arc::new(RwLock::new(None)),
             memory_sync_error_callback: Arc::new(RwLock::new(None)),
+            partial_turn_store: None,
+            readonly_session: Arc::new(RwLock::new(None)),
+            quick_action_seeder: None,
+            decision_agent: Arc::new(DecisionRecordAgent),
+            agent_warmup: false,
+            offline_mode: OfflineMode::default(),
+        }
+    }
+
+    /// Recovers partial output of interrupted turns when sessions are loaded.
+    pub fn with_partial_turn_store(mut self, store: Arc<PartialTurnStore>) -> Self {
+        self.partial_turn_store = Some(store);
+        self
+    }
+
+    /// Seeds default quick actions when a workspace is opened without any.
+    pub fn with_quick_action_seeder(mut self, seeder: Arc<QuickActionSeeder>) -> Self {
+        self.quick_action_seeder = Some(seeder);
+        self
+    }
+
+    /// Extracts decision records with `agent` instead of the utility backend.
+    pub fn with_decision_agent(
+        mut self,
+        agent: Arc<dyn Agent<Output = String, Expertise = String>>,
+    ) -> Self {
+        self.decision_agent = agent;
+        self
+    }
+
+    /// Warms up the API agents of a session's participants in the background
+    /// whenever a session is switched to (see `InteractionManager::warmup_agents`).
+    pub fn with_agent_warmup(mut self, enabled: bool) -> Self {
+        self.agent_warmup = enabled;
+        self
+    }
+
+    /// Records the turns of every session's dialogue with `writer`.
+    ///
+    /// Callers complete the recovery file once they have saved the session.
+    pub fn with_stream_recovery(mut self, writer: StreamRecoveryWriter) -> Self {
+        self.session_factory = Arc::new(
+            self.session_factory
+                .as_ref()
+                .clone()
+                .with_stream_recovery(writer),
+        );
+        self
+    }
+
+    /// Logs the backend exchanges of sessions whose wire log is enabled on `log`.
+    pub fn with_wire_log(mut self, log: WireLog) -> Self {
+        self.session_factory = Arc::new(self.session_factory.as_ref().clone().with_wire_log(log));
+        self
+    }
+
+    /// Shares the app-wide offline mode switch with every session.
+    ///
+    /// While it is on, API-backed participants sit out (or use their CLI
+    /// fallback backend) and the memory sync scheduler skips its batches.
+    pub fn with_offline_mode(mut self, offline_mode: OfflineMode) -> Self {
+        self.session_factory = Arc::new(
+            self.session_factory
+                .as_ref()
+                .clone()
+                .with_offline_mode(offline_mode.clone()),
+        );
+        self.offline_mode = offline_mode;
+        self
+    }
+
+    /// Loads a session from storage, recovering output of an interrupted turn.
+    ///
+    /// A recovery file is appended to the history as `recovered_partial`
+    /// assistant messages, the session is saved, and the file is deleted.
+    async fn load_session(&self, session_id: &str) -> Result<Option<Session>> {
+        let Some(mut session) = self.session_repository.find_by_id(session_id).await? else {
+            return Ok(None);
+        };
+        let Some(store) = &self.partial_turn_store else {
+            return Ok(Some(session));
+        };
+
+        match store.load(session_id).await {
+            Ok(Some(turn)) => {
+                let recovered = turn.apply_to(&mut session);
+                if recovered > 0 {
+                    tracing::info!(
+                        "[SessionUseCase] Recovered {} partial message(s) for session {}",
+                        recovered,
+                        session_id
+                    );
+                    session.updated_at = chrono::Utc::now().to_rfc3339();
+                    self.session_repository.save(&session).await?;
+                }
+                if let Err(e) = store.remove(session_id).await {
+                    tracing::warn!("[SessionUseCase] Failed to remove recovery file: {}", e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(
+                    "[SessionUseCase] Ignoring unreadable recovery file for {}: {}",
+                    session_id,
+                    e
+                );
+            }
         }
+        Ok(Some(session))
     }
 
     /// Sets the memory sync service for RAG integration.
@@ -115,6 +297,9 @@ impl SessionUseCase {
     /// This approach avoids the parallel execution issues that occurred when
     /// triggering sync on every `save_active_session` call.
     ///
+    /// Batches are skipped while offline mode is on; sessions updated in the
+    /// meantime are synced by the first batch after it is turned off.
+    ///
     /// # Arguments
     ///
     /// * `interval_secs` - Interval in seconds between sync batches (default: 60)
@@ -138,6 +323,10 @@ impl SessionUseCase {
 
             loop {
                 ticker.tick().await;
+                if usecase.offline_mode.is_enabled() {
+                    tracing::debug!(target: "memory_sync", "Tick - paused while offline");
+                    continue;
+                }
                 tracing::debug!(target: "memory_sync", "Tick - checking for sessions to sync");
 
                 if let Err(e) = usecase.run_memory_sync_batch().await {
@@ -147,6 +336,71 @@ impl SessionUseCase {
         });
     }
 
+    /// Starts a background reaper that unloads sessions idle for `idle_timeout`.
+    ///
+    /// Idle sessions are saved and dropped from the cache; the next
+    /// `switch_session` loads them again. A zero timeout disables the reaper.
+    pub fn start_idle_session_reaper(self: &Arc<Self>, idle_timeout: Duration) {
+        if idle_timeout.is_zero() {
+            tracing::info!("[SessionUseCase] Idle session reaper disabled");
+            return;
+        }
+
+        let usecase = Arc::clone(self);
+        // Unloads a session at most 1.5x its timeout after its last access
+        let check_interval = (idle_timeout / 2).min(Duration::from_secs(60));
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(check_interval);
+            tracing::info!(
+                "[SessionUseCase] Idle session reaper started ({:?} timeout)",
+                idle_timeout
+            );
+
+            loop {
+                ticker.tick().await;
+                usecase.unload_idle_sessions(idle_timeout).await;
+            }
+        });
+    }
+
+    /// Saves and unloads the cached sessions idle for at least `idle_timeout`.
+    ///
+    /// The active session and sessions still generating stay loaded, as does
+    /// a session that fails to save. Returns the IDs of the unloaded sessions.
+    pub async fn unload_idle_sessions(&self, idle_timeout: Duration) -> Vec<String> {
+        let active_session_id = self.active_session_id().await;
+        let mut unloaded = Vec::new();
+
+        for manager in self.session_cache.idle_sessions(idle_timeout).await {
+            let session_id = manager.session_id().to_string();
+            if active_session_id.as_deref() == Some(session_id.as_str())
+                || manager.is_generating().await
+            {
+                continue;
+            }
+
+            if let Err(e) = self.save_manager(&manager, AppMode::Idle).await {
+                tracing::warn!(
+                    "[SessionUseCase] Keeping idle session {} loaded, save failed: {}",
+                    session_id,
+                    e
+                );
+                continue;
+            }
+            // A session accessed while it was being saved stays loaded
+            if self
+                .session_cache
+                .remove_if_idle(&session_id, idle_timeout)
+                .await
+            {
+                tracing::debug!("[SessionUseCase] Unloaded idle session {}", session_id);
+                unloaded.push(session_id);
+            }
+        }
+        unloaded
+    }
+
     /// Runs a single batch of memory synchronization.
     ///
     /// Scans all sessions and syncs those where `updated_at > last_memory_sync_at`.
@@ -291,7 +545,7 @@ impl SessionUseCase {
             };
 
             // Collect and sync messages
-            let messages = Self::collect_messages_for_sync(&session);
+            let messages = Self::collect_messages_for_sync(session);
             if messages.is_empty() {
                 // Update last_memory_sync_at even if no messages to sync
                 // (prevents re-checking unchanged sessions)
@@ -459,6 +713,7 @@ impl SessionUseCase {
             .to_session(manager.as_ref(), AppMode::Idle, workspace_id.to_string())
             .await;
         self.session_repository.save(&session).await?;
+        manager.mark_saved(&session);
 
         // 5. Set as active session
         self.app_state_service
@@ -530,6 +785,17 @@ impl SessionUseCase {
             workspace.id
         );
 
+        // Quick actions are a convenience; failing to seed them must not block the workspace
+        if let Some(seeder) = &self.quick_action_seeder
+            && let Err(e) = seeder.seed_new_workspace(&workspace).await
+        {
+            tracing::warn!(
+                "[SessionUseCase] Failed to seed quick actions for workspace {}: {}",
+                workspace.id,
+                e
+            );
+        }
+
         // 2. Update AppStateService to use this workspace
         self.app_state_service
             .set_last_selected_workspace(workspace.id.clone())
@@ -683,6 +949,7 @@ impl SessionUseCase {
             .to_session(manager.as_ref(), AppMode::Idle, workspace.id.clone())
             .await;
         self.session_repository.save(&session).await?;
+        manager.mark_saved(&session);
 
         // 7. Set as active session
         self.app_state_service
@@ -733,14 +1000,16 @@ impl SessionUseCase {
     pub async fn switch_session(&self, session_id: &str) -> Result<Session> {
         tracing::info!("[SessionUseCase] Switching to session: {}", session_id);
 
+        // Switching sessions leaves any read-only view
+        self.readonly_session.write().await.take();
+
         // 1. Get or load session
         let manager = if let Some(cached) = self.session_cache.get(session_id).await {
             cached
         } else {
             // Load from storage
             let session = self
-                .session_repository
-                .find_by_id(session_id)
+                .load_session(session_id)
                 .await?
                 .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
             let manager = Arc::new(self.session_factory.from_session(session));
@@ -787,26 +1056,9 @@ impl SessionUseCase {
                         workspace.root_path.display()
                     );
 
-                    manager
-                        .set_workspace_id(
-                            Some(workspace_id.clone()),
-                            Some(workspace.root_path.clone()),
-                        )
+                    self.attach_workspace_root(&manager, workspace_id, &workspace.root_path)
                         .await;
 
-                    // Check if session is in sandbox mode - if so, override workspace root
-                    let sandbox_state = manager.get_sandbox_state().await;
-                    if let Some(sandbox) = sandbox_state {
-                        use std::path::PathBuf;
-                        tracing::info!(
-                            "[SessionUseCase] Session is in sandbox mode, setting agent CWD to: {}",
-                            sandbox.worktree_path
-                        );
-                        manager
-                            .set_agent_workspace_root(Some(PathBuf::from(&sandbox.worktree_path)))
-                            .await;
-                    }
-
                     // Update workspace last active session
                     workspace.last_active_session_id = Some(session_id.to_string());
                     if let Err(e) = self
@@ -874,6 +1126,11 @@ impl SessionUseCase {
             tracing::debug!("[SessionUseCase] Session has no workspace association");
         }
 
+        // 5. Construct API agents ahead of the first turn (the task is detached)
+        if self.agent_warmup {
+            drop(manager.warmup_agents().await);
+        }
+
         // Return the session (potentially with cleared workspace_id)
         let final_session = self
             .session_factory
@@ -888,6 +1145,37 @@ impl SessionUseCase {
         Ok(final_session)
     }
 
+    /// Attaches a restored session's workspace to its manager.
+    ///
+    /// Agents run in the sandbox worktree while the session is in sandbox
+    /// mode, otherwise in `workspace_root`. The agent root is set right away,
+    /// so the first turn after a restore already runs in the right directory.
+    async fn attach_workspace_root(
+        &self,
+        manager: &InteractionManager,
+        workspace_id: &str,
+        workspace_root: &Path,
+    ) {
+        manager
+            .set_workspace_id(
+                Some(workspace_id.to_string()),
+                Some(workspace_root.to_path_buf()),
+            )
+            .await;
+
+        let agent_root = match manager.get_sandbox_state().await {
+            Some(sandbox) => {
+                tracing::info!(
+                    "[SessionUseCase] Session is in sandbox mode, setting agent CWD to: {}",
+                    sandbox.worktree_path
+                );
+                PathBuf::from(&sandbox.worktree_path)
+            }
+            None => workspace_root.to_path_buf(),
+        };
+        manager.set_agent_workspace_root(Some(agent_root)).await;
+    }
+
     /// Switches the current session to a different workspace.
     ///
     /// This method implements UC5 (Workspace Switching):
@@ -997,7 +1285,9 @@ impl SessionUseCase {
                                     workspace.id.clone(),
                                 )
                                 .await;
-                            let _ = self.session_repository.save(&session).await;
+                            if self.session_repository.save(&session).await.is_ok() {
+                                manager.mark_saved(&session);
+                            }
                         }
                         println!(
                             "[SessionUseCase] Successfully switched to workspace {} with last active session {}",
@@ -1052,7 +1342,9 @@ impl SessionUseCase {
                                 workspace.id.clone(),
                             )
                             .await;
-                        let _ = self.session_repository.save(&session).await;
+                        if self.session_repository.save(&session).await.is_ok() {
+                            manager.mark_saved(&session);
+                        }
                     }
                     println!(
                         "[SessionUseCase] Successfully switched to workspace {} with recent session {}",
@@ -1108,6 +1400,7 @@ impl SessionUseCase {
             )
             .await;
         self.session_repository.save(&session).await?;
+        manager.mark_saved(&session);
 
         // Set as active session
         self.app_state_service
@@ -1182,7 +1475,7 @@ impl SessionUseCase {
             Some(cached)
         } else {
             // Load from storage
-            if let Some(session) = self.session_repository.find_by_id(&session_id).await? {
+            if let Some(session) = self.load_session(&session_id).await? {
                 let manager = Arc::new(self.session_factory.from_session(session));
                 self.session_cache
                     .insert(session_id.clone(), manager.clone())
@@ -1235,11 +1528,7 @@ impl SessionUseCase {
                         workspace.root_path.display()
                     );
 
-                    manager
-                        .set_workspace_id(
-                            Some(workspace_id.clone()),
-                            Some(workspace.root_path.clone()),
-                        )
+                    self.attach_workspace_root(&manager, workspace_id, &workspace.root_path)
                         .await;
 
                     // Update workspace access timestamp
@@ -1326,6 +1615,79 @@ impl SessionUseCase {
         self.session_cache.get(&session_id).await
     }
 
+    /// Returns the manager of a session that is already loaded.
+    pub async fn loaded_session(&self, session_id: &str) -> Option<Arc<InteractionManager>> {
+        self.session_cache.get(session_id).await
+    }
+
+    /// Applies new per-backend default models to every cached session.
+    ///
+    /// Sessions loaded later read the defaults from the config instead.
+    pub async fn apply_default_models(&self, models: HashMap<PersonaBackend, String>) {
+        for manager in self.session_cache.all().await {
+            manager.set_default_models(models.clone()).await;
+        }
+    }
+
+    /// Opens a session for reading only.
+    ///
+    /// The session is read straight from the repository: no InteractionManager
+    /// is built, no recovery is applied, and nothing is saved. Other sessions
+    /// stay editable, but mutations of this one fail with [`OrcsError::ReadOnly`]
+    /// until the view is closed or promoted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist or cannot be read.
+    pub async fn open_session_readonly(&self, session_id: &str) -> Result<Session> {
+        let session = self
+            .session_repository
+            .find_by_id(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+        let view = Arc::new(ReadOnlySession::new(session));
+        *self.readonly_session.write().await = Some(view.clone());
+        tracing::info!("[SessionUseCase] Opened session read-only: {}", session_id);
+        Ok(view.session().clone())
+    }
+
+    /// Returns the session currently opened read-only, if any.
+    pub async fn readonly_session(&self) -> Option<Arc<ReadOnlySession>> {
+        self.readonly_session.read().await.clone()
+    }
+
+    /// Closes the read-only view, re-enabling mutations of its session.
+    pub async fn close_readonly_session(&self) {
+        self.readonly_session.write().await.take();
+    }
+
+    /// Opens the read-only session for editing and makes it the active session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `session_id` is not the session opened read-only or
+    /// if switching to it fails.
+    pub async fn promote_readonly_to_active(&self, session_id: &str) -> Result<Session> {
+        if self
+            .readonly_session()
+            .await
+            .is_none_or(|view| view.id() != session_id)
+        {
+            return Err(anyhow!("Session {} is not open read-only", session_id));
+        }
+        self.switch_session(session_id).await
+    }
+
+    /// Fails with [`OrcsError::ReadOnly`] while `session_id` is opened read-only.
+    ///
+    /// Commands that mutate a session call this first.
+    pub async fn ensure_editable(&self, session_id: &str) -> std::result::Result<(), OrcsError> {
+        match self.readonly_session.read().await.as_ref() {
+            Some(view) if view.id() == session_id => Err(OrcsError::read_only(session_id)),
+            _ => Ok(()),
+        }
+    }
+
     /// Saves the currently active session to storage.
     ///
     /// # Arguments
@@ -1347,13 +1709,94 @@ impl SessionUseCase {
             .await
             .ok_or_else(|| anyhow!("Active session {} not found in cache", session_id))?;
 
+        self.save_manager(manager.as_ref(), app_mode).await?;
+
+        // Memory sync is now handled by the background scheduler (start_memory_sync_scheduler)
+        // instead of being triggered on every save
+
+        Ok(())
+    }
+
+    /// Saves a specific (possibly inactive) session to storage.
+    ///
+    /// Sessions that are not loaded in the cache have nothing unsaved, so this
+    /// is a no-op for them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if storage fails.
+    pub async fn save_session(&self, session_id: &str, app_mode: AppMode) -> Result<()> {
+        match self.session_cache.get(session_id).await {
+            Some(manager) => self.save_manager(manager.as_ref(), app_mode).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Runs user input for the active session as a detached background dialogue.
+    ///
+    /// The session is saved as each turn arrives and carries `is_generating = true`
+    /// until the dialogue finishes, so the user can switch away and later poll or
+    /// reattach. Saves target the originating session even if another session
+    /// becomes active in the meantime.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_mode` - The current application mode
+    /// * `input` - The user's input string
+    /// * `file_paths` - Optional list of file paths to attach
+    /// * `on_turn` - Called for each dialogue turn as it arrives
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no active session or it is already generating.
+    pub async fn handle_input_background<F>(
+        self: &Arc<Self>,
+        app_mode: AppMode,
+        input: String,
+        file_paths: Option<Vec<String>>,
+        on_turn: F,
+    ) -> Result<JoinHandle<InteractionResult>>
+    where
+        F: Fn(&DialogueMessage) + Send + Sync + 'static,
+    {
+        let manager = self
+            .active_session()
+            .await
+            .ok_or_else(|| anyhow!("No active session"))?;
+        self.ensure_editable(manager.session_id()).await?;
+
+        let persist = {
+            let usecase = Arc::clone(self);
+            let manager = manager.clone();
+            let app_mode = app_mode.clone();
+            move || {
+                let usecase = usecase.clone();
+                let manager = manager.clone();
+                let app_mode = app_mode.clone();
+                async move {
+                    if let Err(e) = usecase.save_manager(manager.as_ref(), app_mode).await {
+                        tracing::warn!(
+                            "[SessionUseCase] Failed to persist background session {}: {}",
+                            manager.session_id(),
+                            e
+                        );
+                    }
+                }
+            }
+        };
+
+        manager
+            .handle_input_background(app_mode, input, file_paths, on_turn, persist)
+            .await
+            .ok_or_else(|| anyhow!("Session {} is already generating", manager.session_id()))
+    }
+
+    /// Saves the given session manager's state to storage.
+    async fn save_manager(&self, manager: &InteractionManager, app_mode: AppMode) -> Result<()> {
+        let session_id = manager.session_id();
+
         // Load existing session to preserve workspace_id and last_memory_sync_at
-        let existing_session = self
-            .session_repository
-            .list_all()
-            .await?
-            .into_iter()
-            .find(|s| s.id == session_id);
+        let existing_session = self.session_repository.find_by_id(session_id).await?;
 
         let existing_workspace_id = existing_session
             .as_ref()
@@ -1367,21 +1810,25 @@ impl SessionUseCase {
         // Convert to session and save
         let mut session = self
             .session_factory
-            .to_session(manager.as_ref(), app_mode, existing_workspace_id.clone())
+            .to_session(manager, app_mode, existing_workspace_id.clone())
             .await;
 
         // Preserve last_memory_sync_at from existing session (to_session always sets it to None)
         session.last_memory_sync_at = existing_last_memory_sync_at;
 
+        // Write only the sections changed since the last save; a session
+        // missing from storage is written whole
+        if existing_session.is_none() {
+            manager.mark_unsaved();
+        }
+        let delta = manager.take_dirty_delta(&session);
         self.session_repository
-            .save(&session)
+            .save_delta(&session, &delta)
             .await
-            .map_err(|e| anyhow!("Failed to save session: {}", e))?;
-
-        // Memory sync is now handled by the background scheduler (start_memory_sync_scheduler)
-        // instead of being triggered on every save
-
-        Ok(())
+            .map_err(|e| {
+                manager.mark_unsaved();
+                anyhow!("Failed to save session: {}", e)
+            })
     }
 
     /// Collects messages from a session for memory sync.
@@ -1416,13 +1863,12 @@ impl SessionUseCase {
 
                 // Differential sync: only include messages after last sync timestamp
                 // Use proper DateTime comparison instead of string comparison
-                if let Some(last_sync) = last_sync_datetime {
-                    if let Ok(msg_datetime) = DateTime::parse_from_rfc3339(&msg.timestamp) {
-                        if msg_datetime <= last_sync {
-                            continue;
-                        }
-                    }
-                    // If parsing fails, fall back to including the message (safer than excluding)
+                // If parsing fails, fall back to including the message (safer than excluding)
+                if let Some(last_sync) = last_sync_datetime
+                    && let Ok(msg_datetime) = DateTime::parse_from_rfc3339(&msg.timestamp)
+                    && msg_datetime <= last_sync
+                {
+                    continue;
                 }
 
                 messages.push(MemoryMessage {
@@ -1474,57 +1920,480 @@ impl SessionUseCase {
         Ok(())
     }
 
-    /// Returns a reference to the workspace manager.
+    /// Deletes archived sessions last updated more than `older_than_days` ago.
     ///
-    /// This provides direct access to the underlying workspace manager for
-    /// workspace-only operations.
-    pub fn workspace_storage_service(&self) -> &Arc<dyn WorkspaceStorageService> {
-        &self.workspace_storage_service
+    /// Favorites are kept even when archived, as are sessions whose
+    /// `updated_at` cannot be parsed.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the deleted sessions.
+    pub async fn prune_archived(&self, older_than_days: u32) -> Result<Vec<String>> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(older_than_days));
+        let expired: Vec<String> = self
+            .session_repository
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|session| session.is_archived && !session.is_favorite)
+            .filter(|session| {
+                chrono::DateTime::parse_from_rfc3339(&session.updated_at)
+                    .is_ok_and(|updated_at| updated_at < cutoff)
+            })
+            .map(|session| session.id)
+            .collect();
+
+        for session_id in &expired {
+            self.delete_session(session_id).await?;
+        }
+        tracing::info!(
+            "[SessionUseCase] Pruned {} archived session(s) older than {} days",
+            expired.len(),
+            older_than_days
+        );
+        Ok(expired)
     }
 
-    /// Enriches session participants field by resolving persona IDs to names.
+    /// Reassigns a session to a different workspace.
     ///
-    /// For sessions loaded from storage (especially after migration from older versions),
-    /// the participants HashMap may be empty. This method populates it by:
-    /// 1. Adding user name mapping (user_name -> user_name)
-    /// 2. Resolving persona IDs from persona_histories keys to persona names
+    /// Session files are stored in a single directory independent of the
+    /// workspace, so only the `workspace_id` reference changes on disk. If the
+    /// session is loaded, its manager is pointed at the new workspace root so
+    /// agents pick up the new CWD on their next turn.
     ///
     /// # Arguments
     ///
-    /// * `session` - The session to enrich
+    /// * `session_id` - The ID of the session to move
+    /// * `new_workspace_id` - The ID of the destination workspace
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// The enriched session with populated participants field.
-    pub async fn enrich_session_participants(&self, mut session: Session) -> Session {
-        use std::collections::HashMap;
+    /// Returns an error if:
+    /// - The target workspace or the session does not exist
+    /// - The session is in sandbox mode (its worktree belongs to the old repository)
+    /// - The update operation fails
+    pub async fn move_session(&self, session_id: &str, new_workspace_id: &str) -> Result<Session> {
+        let workspace = self
+            .workspace_storage_service
+            .get_workspace(new_workspace_id)
+            .await?
+            .ok_or_else(|| anyhow!("Workspace not found: {}", new_workspace_id))?;
 
-        // If participants is already populated, return as-is
-        if !session.participants.is_empty() {
-            return session;
+        let session = self
+            .session_repository
+            .find_by_id(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+        if session.workspace_id == new_workspace_id {
+            return Ok(session);
         }
 
-        let mut participants = HashMap::new();
+        let cached = self.session_cache.get(session_id).await;
+        let in_sandbox = match &cached {
+            Some(manager) => manager.get_sandbox_state().await.is_some(),
+            None => session.sandbox_state.is_some(),
+        };
+        if in_sandbox {
+            return Err(anyhow!(
+                "Session {} is in sandbox mode; exit the sandbox before moving it",
+                session_id
+            ));
+        }
 
-        // Add user name
-        let user_name = self.user_service.get_user_name();
-        participants.insert(user_name.clone(), user_name);
+        tracing::info!(
+            "[SessionUseCase] Moving session {} from workspace {} to {}",
+            session_id,
+            session.workspace_id,
+            new_workspace_id
+        );
 
-        // Resolve persona IDs to names
-        if let Ok(all_personas) = self.persona_repository.get_all().await {
-            for persona_id in session.persona_histories.keys() {
-                if let Some(persona) = all_personas.iter().find(|p| &p.id == persona_id) {
-                    participants.insert(persona_id.clone(), persona.name.clone());
-                }
-            }
+        if let Some(manager) = cached {
+            manager
+                .set_workspace_id(Some(new_workspace_id.to_string()), None)
+                .await;
+            manager
+                .set_agent_workspace_root(Some(workspace.root_path.clone()))
+                .await;
         }
 
-        session.participants = participants;
-        session
-    }
+        let updater = SessionUpdater::new(self.session_repository.clone());
+        updater
+            .update(session_id, |session| {
+                session.workspace_id = new_workspace_id.to_string();
+                Ok(())
+            })
+            .await?;
 
-    /// Adds a system message to the active session.
-    ///
+        // The old workspace should no longer resume this session
+        if let Ok(Some(mut old_workspace)) = self
+            .workspace_storage_service
+            .get_workspace(&session.workspace_id)
+            .await
+            && old_workspace.last_active_session_id.as_deref() == Some(session_id)
+        {
+            old_workspace.last_active_session_id = None;
+            if let Err(e) = self
+                .workspace_storage_service
+                .save_workspace(&old_workspace)
+                .await
+            {
+                tracing::warn!(
+                    "[SessionUseCase] Failed to clear last active session of workspace {}: {}",
+                    old_workspace.id,
+                    e
+                );
+            }
+        }
+
+        self.session_repository
+            .find_by_id(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Session not found: {}", session_id))
+    }
+
+    /// Merges the history of `secondary_id` into `primary_id`.
+    ///
+    /// Persona histories and system messages of both sessions are interleaved
+    /// chronologically; messages with identical author, timestamp and content
+    /// (e.g. from an earlier fork) are kept once. Participants are unioned, the
+    /// primary's settings are kept, and a system message records the merge.
+    /// With `delete_secondary`, the secondary is archived rather than removed,
+    /// so it can still be recovered. If the primary is loaded, its dialogue is
+    /// rebuilt from the merged history on the next turn.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the IDs are equal, either session does not exist or
+    /// is still generating, or storage fails.
+    pub async fn merge_sessions(
+        &self,
+        primary_id: &str,
+        secondary_id: &str,
+        delete_secondary: bool,
+    ) -> Result<Session> {
+        if primary_id == secondary_id {
+            return Err(anyhow!("Cannot merge session {} into itself", primary_id));
+        }
+        let mut primary = self.current_session(primary_id).await?;
+        let secondary = self.current_session(secondary_id).await?;
+        for session in [&primary, &secondary] {
+            if session.is_generating {
+                return Err(anyhow!(
+                    "Session {} is still generating; wait for it to finish before merging",
+                    session.id
+                ));
+            }
+        }
+
+        tracing::info!(
+            "[SessionUseCase] Merging session {} into {}",
+            secondary_id,
+            primary_id
+        );
+        merge_session_history(&mut primary, &secondary);
+        let message_count = secondary
+            .persona_histories
+            .values()
+            .map(Vec::len)
+            .sum::<usize>()
+            + secondary.system_messages.len();
+        primary.system_messages.push(ConversationMessage {
+            role: MessageRole::System,
+            content: format!(
+                "セッション「{}」の {} 件のメッセージを統合しました",
+                secondary.title, message_count
+            ),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            metadata: MessageMetadata {
+                system_event_type: Some(SystemEventType::Notification),
+                error_severity: None,
+                system_message_type: Some("session_merged".to_string()),
+                include_in_dialogue: true,
+                llm_debug_info: None,
+                recovered_partial: false,
+                pinned: false,
+                split_from_single_response: false,
+                spawned_task_ids: Vec::new(),
+                in_response_to: None,
+                source: None,
+                sources: Vec::new(),
+                full_content_path: None,
+                turn_stats: None,
+            },
+            attachments: vec![],
+        });
+        primary.updated_at = chrono::Utc::now().to_rfc3339();
+
+        if let Some(manager) = self.session_cache.get(primary_id).await {
+            manager.replace_conversation(&primary).await;
+        }
+        self.session_repository.save(&primary).await?;
+
+        if delete_secondary {
+            SessionUpdater::new(self.session_repository.clone())
+                .update(secondary_id, |session| {
+                    session.is_archived = true;
+                    Ok(())
+                })
+                .await?;
+        }
+
+        Ok(primary)
+    }
+
+    /// Rewrites a session file in the latest schema without redundant data.
+    ///
+    /// Loading moves the legacy `"Error"` history bucket into the system
+    /// messages; compaction then drops repeated consecutive system messages and
+    /// saves the session. User and assistant turns are left untouched. If the
+    /// session is loaded, its manager is updated as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist, is still generating, or
+    /// storage fails.
+    pub async fn compact_session(&self, session_id: &str) -> Result<SessionCompaction> {
+        let bytes_before = self.session_repository.stored_size(session_id).await?;
+        let mut session = self.current_session(session_id).await?;
+        if session.is_generating {
+            return Err(anyhow!(
+                "Session {} is still generating; wait for it to finish before compacting",
+                session_id
+            ));
+        }
+
+        let system_messages_removed = session.dedup_consecutive_system_messages();
+        if let Some(manager) = self.session_cache.get(session_id).await {
+            manager.replace_conversation(&session).await;
+        }
+        self.session_repository.save(&session).await?;
+        let bytes_after = self.session_repository.stored_size(session_id).await?;
+
+        let bytes_saved = match (bytes_before, bytes_after) {
+            (Some(before), Some(after)) => before.saturating_sub(after),
+            _ => 0,
+        };
+        tracing::info!(
+            "[SessionUseCase] Compacted session {}: {} system messages removed, {} bytes saved",
+            session_id,
+            system_messages_removed,
+            bytes_saved
+        );
+        Ok(SessionCompaction {
+            session_id: session_id.to_string(),
+            system_messages_removed,
+            bytes_before,
+            bytes_after,
+            bytes_saved,
+        })
+    }
+
+    /// Returns the conversation of a session as one chronological list of turns
+    /// with resolved author names, including unsaved turns of a loaded session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist or cannot be loaded.
+    pub async fn get_conversation(&self, session_id: &str) -> Result<Vec<ConversationTurn>> {
+        let session = self.current_session(session_id).await?;
+        let session = self.enrich_session_participants(session).await;
+        Ok(session.conversation_turns())
+    }
+
+    /// Detects the primary language of a session from its recent user messages.
+    ///
+    /// Returns the ISO 639-1 code (e.g. `"ja"`, `"en"`), or `None` when the
+    /// messages are too short to tell.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist or cannot be loaded.
+    pub async fn detect_language(&self, session_id: &str) -> Result<Option<String>> {
+        let session = self.current_session(session_id).await?;
+        let turns = session.conversation_turns();
+        let recent = turns
+            .iter()
+            .rev()
+            .filter(|turn| turn.role == MessageRole::User)
+            .take(LANGUAGE_SAMPLE_MESSAGES)
+            .map(|turn| turn.content.as_str());
+        Ok(detect_language(recent).map(str::to_string))
+    }
+
+    /// Counts the assistant turns of each persona in a session, keyed by name.
+    ///
+    /// Participants that have not spoken yet are listed with zero turns, so
+    /// quiet personas show up when rebalancing an AutoChat. Personas that no
+    /// longer resolve to a name are keyed by their ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist or cannot be loaded.
+    pub async fn turn_distribution(&self, session_id: &str) -> Result<HashMap<String, usize>> {
+        let session = self.current_session(session_id).await?;
+        let session = self.enrich_session_participants(session).await;
+        let name_of = |persona_id: &String| {
+            session
+                .participants
+                .get(persona_id)
+                .cloned()
+                .unwrap_or_else(|| persona_id.clone())
+        };
+
+        let mut turns: HashMap<String, usize> = session
+            .active_participant_ids
+            .iter()
+            .map(|persona_id| (name_of(persona_id), 0))
+            .collect();
+        for (persona_id, messages) in &session.persona_histories {
+            let count = messages
+                .iter()
+                .filter(|m| m.role == MessageRole::Assistant)
+                .count();
+            if count > 0 {
+                *turns.entry(name_of(persona_id)).or_default() += count;
+            }
+        }
+        Ok(turns)
+    }
+
+    /// Sums the duration, token usage and estimated cost of a session's agent
+    /// turns per persona and per day, including unsaved turns of a loaded session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist or cannot be loaded.
+    pub async fn cost_breakdown(&self, session_id: &str) -> Result<CostBreakdown> {
+        let session = self.current_session(session_id).await?;
+        let session = self.enrich_session_participants(session).await;
+        Ok(session.cost_breakdown())
+    }
+
+    /// Returns the sources the message created at `timestamp` copied from.
+    ///
+    /// Empty for messages that reused no source and for non-agent messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session or the message does not exist.
+    pub async fn message_sources(
+        &self,
+        session_id: &str,
+        timestamp: &str,
+    ) -> Result<Vec<SourceRef>> {
+        let session = self.current_session(session_id).await?;
+        let (_, message) = session
+            .find_message(timestamp)
+            .ok_or_else(|| anyhow!("Message not found in session {}: {}", session_id, timestamp))?;
+        Ok(message.metadata.sources.clone())
+    }
+
+    /// Returns the full text of the message created at `timestamp`.
+    ///
+    /// For a long paste sent as an attachment this is the stored file, whose
+    /// content the message only excerpts; for other messages, their content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session or the message does not exist, or the
+    /// stored paste cannot be read.
+    pub async fn full_message(&self, session_id: &str, timestamp: &str) -> Result<String> {
+        let session = self.current_session(session_id).await?;
+        let (_, message) = session
+            .find_message(timestamp)
+            .ok_or_else(|| anyhow!("Message not found in session {}: {}", session_id, timestamp))?;
+        match &message.metadata.full_content_path {
+            Some(path) => tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("Failed to read stored paste {}", path)),
+            None => Ok(message.content.clone()),
+        }
+    }
+
+    /// Returns the latest data of a session, including unsaved turns of a loaded one.
+    pub(crate) async fn current_session(&self, session_id: &str) -> Result<Session> {
+        let stored = self
+            .load_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+        let Some(manager) = self.session_cache.get(session_id).await else {
+            // A session that is not loaded cannot be generating
+            return Ok(Session {
+                is_generating: false,
+                ..stored
+            });
+        };
+        let mut session = self
+            .session_factory
+            .to_session(
+                manager.as_ref(),
+                stored.app_mode.clone(),
+                stored.workspace_id.clone(),
+            )
+            .await;
+        session.last_memory_sync_at = stored.last_memory_sync_at;
+        Ok(session)
+    }
+
+    /// Returns a reference to the workspace manager.
+    ///
+    /// This provides direct access to the underlying workspace manager for
+    /// workspace-only operations.
+    pub fn workspace_storage_service(&self) -> &Arc<dyn WorkspaceStorageService> {
+        &self.workspace_storage_service
+    }
+
+    /// Enriches session participants field by resolving persona IDs to names.
+    ///
+    /// For sessions loaded from storage (especially after migration from older versions),
+    /// the participants HashMap may be empty. This method populates it by:
+    /// 1. Adding user name mapping (user_name -> user_name)
+    /// 2. Resolving persona IDs from persona_histories keys to persona names
+    ///
+    /// It also fills the runtime-only `is_generating` flag from the loaded session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The session to enrich
+    ///
+    /// # Returns
+    ///
+    /// The enriched session with populated participants field.
+    pub async fn enrich_session_participants(&self, mut session: Session) -> Session {
+        use std::collections::HashMap;
+
+        // `is_generating` is runtime-only; only a loaded manager can be generating
+        session.is_generating = match self.session_cache.get(&session.id).await {
+            Some(manager) => manager.is_generating().await,
+            None => false,
+        };
+
+        // If participants is already populated, return as-is
+        if !session.participants.is_empty() {
+            return session;
+        }
+
+        let mut participants = HashMap::new();
+
+        // Add user name
+        let user_name = self.user_service.get_user_name();
+        participants.insert(user_name.clone(), user_name);
+
+        // Resolve persona IDs to names (archived personas stay resolvable)
+        if let Ok(all_personas) = self.persona_repository.get_all_including_archived().await {
+            for persona_id in session.persona_histories.keys() {
+                if let Some(persona) = all_personas.iter().find(|p| &p.id == persona_id) {
+                    participants.insert(persona_id.clone(), persona.name.clone());
+                }
+            }
+        }
+
+        session.participants = participants;
+        session
+    }
+
+    /// Adds a system message to the active session.
+    ///
     /// This method is part of the refactored message handling architecture where
     /// business logic is centralized in SessionUseCase. It delegates to the
     /// InteractionManager to actually add the message.
@@ -1569,16 +2438,1504 @@ impl SessionUseCase {
             return Err(anyhow!("No active session"));
         };
 
-        let manager = self
-            .session_cache
-            .get(&session_id)
+        self.add_system_message_to_session(&session_id, content, message_type, severity)
             .await
-            .ok_or_else(|| anyhow!("Active session {} not found in cache", session_id))?;
+    }
 
-        manager
-            .add_system_conversation_message(content, message_type, severity)
-            .await;
+    /// Adds a system message to a specific (possibly inactive) session.
+    ///
+    /// Like [`Self::add_system_message`], this does NOT save a loaded session.
+    /// A session that is not loaded in the cache is updated in storage directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist or storage fails.
+    pub async fn add_system_message_to_session(
+        &self,
+        session_id: &str,
+        content: String,
+        message_type: Option<String>,
+        severity: Option<orcs_core::session::ErrorSeverity>,
+    ) -> Result<()> {
+        self.add_system_message_with_attachments(
+            session_id,
+            content,
+            message_type,
+            severity,
+            vec![],
+        )
+        .await
+    }
+
+    /// Stores a clipped web page as a workspace file and announces it in a session.
+    ///
+    /// Adds a `context_info` system message with the page title, URL, summary
+    /// (the lead paragraph if `summary` is None) and the stored file's path, so
+    /// agents see the summary and can read the full text from the file. Like
+    /// [`Self::add_system_message`], this does NOT save a loaded session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist or storage fails.
+    pub async fn add_web_clip(
+        &self,
+        session_id: &str,
+        clip: &WebClip,
+        summary: Option<String>,
+    ) -> Result<UploadedFile> {
+        let session = self.current_session(session_id).await?;
+        let file = self
+            .workspace_storage_service
+            .add_file_from_bytes(
+                &session.workspace_id,
+                &clip.file_name(),
+                clip.to_markdown().as_bytes(),
+                Some(session_id.to_string()),
+                None,
+                Some("system".to_string()),
+            )
+            .await?;
+
+        let path = file.path.to_string_lossy().to_string();
+        let summary = summary
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| clip.lead_paragraph());
+        let mut content = format!(
+            "{}{}\nURL: {}\n\n{}\n\nFull text: {}",
+            WEB_CLIP_PREFIX, clip.title, clip.url, summary, path
+        );
+        for warning in &clip.warnings {
+            content.push_str(&format!("\nWarning: {}", warning));
+        }
+        let severity = (!clip.warnings.is_empty()).then_some(ErrorSeverity::Warning);
+
+        self.add_system_message_with_attachments(
+            session_id,
+            content,
+            Some("context_info".to_string()),
+            severity,
+            vec![path],
+        )
+        .await?;
+        Ok(file)
+    }
 
+    /// Extracts an ADR-style decision record from a session's conversation.
+    ///
+    /// The compacted transcript (limited to messages between the inclusive
+    /// `range` timestamps, if given) is sent to the utility backend. The record
+    /// is stored as Markdown under `decisions/` in the workspace storage
+    /// directory and announced with a `context_info` system message linking
+    /// to the file. Like [`Self::add_system_message`], this does NOT save a
+    /// loaded session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not exist, has no messages in
+    /// `range`, the backend fails, no record can be parsed from its replies, or
+    /// storage fails.
+    pub async fn extract_decision_record(
+        &self,
+        session_id: &str,
+        range: Option<(&str, &str)>,
+    ) -> Result<DecisionRecord> {
+        let range = match range {
+            Some((from, to)) => {
+                let parse = |timestamp: &str| {
+                    chrono::DateTime::parse_from_rfc3339(timestamp)
+                        .map(|ts| ts.with_timezone(&chrono::Utc))
+                        .map_err(|e| anyhow!("Invalid range timestamp '{}': {}", timestamp, e))
+                };
+                Some(DateRange {
+                    start: Some(parse(from)?),
+                    end: Some(parse(to)?),
+                })
+            }
+            None => None,
+        };
+
+        let session = self.current_session(session_id).await?;
+        let transcript = decision_transcript(&session, range.as_ref());
+        if transcript.is_empty() {
+            return Err(anyhow!(
+                "Session {} has no messages to extract a decision record from",
+                session_id
+            ));
+        }
+        let record = request_decision_record(self.decision_agent.as_ref(), &transcript).await?;
+
+        let workspace = self
+            .workspace_storage_service
+            .get_workspace(&session.workspace_id)
+            .await?
+            .ok_or_else(|| anyhow!("Workspace not found: {}", session.workspace_id))?;
+        let decisions_dir = workspace.workspace_dir.join("decisions");
+        tokio::fs::create_dir_all(&decisions_dir).await?;
+        let now = chrono::Utc::now();
+        let file_name = record.file_name(&now.format("%Y-%m-%d").to_string());
+        let mut path = decisions_dir.join(&file_name);
+        let mut suffix = 2;
+        while tokio::fs::try_exists(&path).await? {
+            let stem = file_name.trim_end_matches(".md");
+            path = decisions_dir.join(format!("{}-{}.md", stem, suffix));
+            suffix += 1;
+        }
+        let markdown = format!(
+            "{}\n---\n\nExtracted from session \"{}\" ({}) on {}.\n",
+            record.to_markdown(),
+            session.title,
+            session.id,
+            now.to_rfc3339()
+        );
+        tokio::fs::write(&path, markdown).await?;
+
+        let path = path.to_string_lossy().to_string();
+        self.add_system_message_with_attachments(
+            session_id,
+            format!(
+                "📋 Decision record: {}\nDecision: {}\n\nSaved to: {}",
+                record.title, record.decision, path
+            ),
+            Some("context_info".to_string()),
+            None,
+            vec![path],
+        )
+        .await?;
+        Ok(record)
+    }
+
+    async fn add_system_message_with_attachments(
+        &self,
+        session_id: &str,
+        content: String,
+        message_type: Option<String>,
+        severity: Option<orcs_core::session::ErrorSeverity>,
+        attachments: Vec<String>,
+    ) -> Result<()> {
+        if let Some(manager) = self.session_cache.get(session_id).await {
+            manager
+                .add_system_conversation_message_with_attachments(
+                    content,
+                    message_type,
+                    severity,
+                    attachments,
+                )
+                .await;
+            return Ok(());
+        }
+
+        let mut session = self
+            .load_session(session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
+        let source = SourceRef::for_system_message(message_type.as_deref(), &content, &attachments);
+        session.system_messages.push(ConversationMessage {
+            role: MessageRole::System,
+            content,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            metadata: MessageMetadata {
+                system_event_type: Some(SystemEventType::Notification),
+                error_severity: severity,
+                system_message_type: message_type,
+                include_in_dialogue: true,
+                llm_debug_info: None,
+                recovered_partial: false,
+                pinned: false,
+                split_from_single_response: false,
+                spawned_task_ids: Vec::new(),
+                in_response_to: None,
+                source,
+                sources: Vec::new(),
+                full_content_path: None,
+                turn_stats: None,
+            },
+            attachments,
+        });
+        session.updated_at = chrono::Utc::now().to_rfc3339();
+        self.session_repository.save(&session).await?;
         Ok(())
     }
 }
+
+/// Interleaves `secondary`'s messages and participants into `primary`.
+///
+/// Messages are ordered by timestamp (ties keep their original order) and a
+/// message already present under the same author is skipped.
+fn merge_session_history(primary: &mut Session, secondary: &Session) {
+    fn merge_messages(target: &mut Vec<ConversationMessage>, incoming: &[ConversationMessage]) {
+        for message in incoming {
+            let duplicate = target.iter().any(|existing| {
+                existing.timestamp == message.timestamp
+                    && existing.content == message.content
+                    && existing.role == message.role
+            });
+            if !duplicate {
+                target.push(message.clone());
+            }
+        }
+        target.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    }
+
+    for (author_id, history) in &secondary.persona_histories {
+        merge_messages(
+            primary
+                .persona_histories
+                .entry(author_id.clone())
+                .or_default(),
+            history,
+        );
+    }
+    merge_messages(&mut primary.system_messages, &secondary.system_messages);
+
+    for id in &secondary.active_participant_ids {
+        if !primary.active_participant_ids.contains(id) {
+            primary.active_participant_ids.push(id.clone());
+        }
+    }
+    for (id, name) in &secondary.participants {
+        primary
+            .participants
+            .entry(id.clone())
+            .or_insert_with(|| name.clone());
+    }
+    for (id, icon) in &secondary.participant_icons {
+        primary
+            .participant_icons
+            .entry(id.clone())
+            .or_insert_with(|| icon.clone());
+    }
+    for (id, color) in &secondary.participant_colors {
+        primary
+            .participant_colors
+            .entry(id.clone())
+            .or_insert_with(|| color.clone());
+    }
+    for (id, backend) in &secondary.participant_backends {
+        primary
+            .participant_backends
+            .entry(id.clone())
+            .or_insert_with(|| backend.clone());
+    }
+    for (id, model) in &secondary.participant_models {
+        primary
+            .participant_models
+            .entry(id.clone())
+            .or_insert_with(|| model.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orcs_core::session::{LEGACY_ERROR_HISTORY_KEY, SourceKind};
+    use orcs_infrastructure::user_service::ConfigBasedUserService;
+    use orcs_infrastructure::workspace_storage_service::FileSystemWorkspaceManager;
+    use orcs_infrastructure::{
+        AppStateService, AsyncDirPersonaRepository, AsyncDirSessionRepository, FlushPolicy,
+        StreamRecoveryWriter,
+    };
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_move_session_reassigns_workspace() {
+        // Each repository treats an existing base directory as its own root
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        );
+
+        let mut workspace_ids = Vec::new();
+        for name in ["alpha", "beta"] {
+            let root = projects.path().join(name);
+            std::fs::create_dir_all(&root).unwrap();
+            workspace_ids.push(workspaces.get_or_create_workspace(&root).await.unwrap().id);
+        }
+        let (from, to) = (&workspace_ids[0], &workspace_ids[1]);
+
+        let session = usecase.create_session(from).await.unwrap();
+        let moved = usecase.move_session(&session.id, to).await.unwrap();
+        assert_eq!(&moved.workspace_id, to);
+
+        let in_target: Vec<_> = sessions
+            .list_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|s| &s.workspace_id == to)
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(in_target, vec![session.id.clone()]);
+
+        // The loaded manager follows the move too
+        let manager = usecase.session_cache.get(&session.id).await.unwrap();
+        assert_eq!(
+            manager.get_agent_workspace_root().await,
+            Some(projects.path().join("beta"))
+        );
+
+        assert!(usecase.move_session(&session.id, "missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_idle_sessions_are_saved_unloaded_and_reloaded_on_access() {
+        let dirs = [(); 5].map(|_| TempDir::new().unwrap());
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = Arc::new(SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        ));
+        let workspace_id = workspaces
+            .get_or_create_workspace(dirs[4].path())
+            .await
+            .unwrap()
+            .id;
+
+        let idle = usecase.create_session(&workspace_id).await.unwrap();
+        // An unsaved change the reaper must not lose
+        usecase
+            .session_cache
+            .get(&idle.id)
+            .await
+            .unwrap()
+            .set_mute(true)
+            .await;
+        let active = usecase.create_session(&workspace_id).await.unwrap();
+        assert_eq!(usecase.active_session_id().await, Some(active.id.clone()));
+
+        usecase.start_idle_session_reaper(Duration::from_millis(100));
+        tokio::time::sleep(Duration::from_millis(400)).await;
+
+        assert!(usecase.loaded_session(&idle.id).await.is_none());
+        // The active session is never unloaded
+        assert!(usecase.session_cache.get(&active.id).await.is_some());
+        assert!(
+            sessions
+                .find_by_id(&idle.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .is_muted
+        );
+
+        let reloaded = usecase.switch_session(&idle.id).await.unwrap();
+        assert!(reloaded.is_muted);
+        let manager = usecase.loaded_session(&idle.id).await.unwrap();
+        assert!(manager.is_muted().await);
+    }
+
+    #[tokio::test]
+    async fn test_switch_session_reattaches_workspace_root() {
+        let dirs = [(); 5].map(|_| TempDir::new().unwrap());
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = SessionUseCase::new(
+            sessions,
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        );
+        let workspace = workspaces
+            .get_or_create_workspace(dirs[4].path())
+            .await
+            .unwrap();
+
+        let previous = usecase.create_session(&workspace.id).await.unwrap();
+        usecase.create_session(&workspace.id).await.unwrap();
+        assert_eq!(
+            usecase.unload_idle_sessions(Duration::ZERO).await,
+            vec![previous.id.clone()]
+        );
+
+        usecase.switch_session(&previous.id).await.unwrap();
+        let manager = usecase.loaded_session(&previous.id).await.unwrap();
+        assert_eq!(
+            manager.get_agent_workspace_root().await,
+            Some(workspace.root_path.clone())
+        );
+    }
+
+    /// Counts writes that reach the wrapped repository.
+    struct WriteCountingRepository {
+        inner: Arc<AsyncDirSessionRepository>,
+        writes: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl SessionRepository for WriteCountingRepository {
+        async fn find_by_id(&self, session_id: &str) -> orcs_core::error::Result<Option<Session>> {
+            self.inner.find_by_id(session_id).await
+        }
+
+        async fn save(&self, session: &Session) -> orcs_core::error::Result<()> {
+            self.writes
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.save(session).await
+        }
+
+        async fn delete(&self, session_id: &str) -> orcs_core::error::Result<()> {
+            self.writes
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.delete(session_id).await
+        }
+
+        async fn list_all(&self) -> orcs_core::error::Result<Vec<Session>> {
+            self.inner.list_all().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_readonly_browse_never_writes_and_promotion_activates_session() {
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let inner = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(WriteCountingRepository {
+            inner: inner.clone(),
+            writes: Default::default(),
+        });
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        // A fresh use case has an empty cache, like after an app restart
+        let restart = || {
+            Arc::new(SessionUseCase::new(
+                sessions.clone(),
+                workspaces.clone(),
+                app_state.clone(),
+                personas.clone(),
+                Arc::new(ConfigBasedUserService::new()),
+            ))
+        };
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let archived = restart().create_session(&workspace.id).await.unwrap();
+        let editable = restart().create_session(&workspace.id).await.unwrap();
+
+        let mut stored = inner.find_by_id(&archived.id).await.unwrap().unwrap();
+        for (author, content, timestamp) in [
+            ("You", "Which option?", "2026-01-01T10:00:00+00:00"),
+            ("mai", "Option B", "2026-01-01T10:00:05+00:00"),
+        ] {
+            stored
+                .persona_histories
+                .entry(author.to_string())
+                .or_default()
+                .push(ConversationMessage {
+                    role: if author == "You" {
+                        MessageRole::User
+                    } else {
+                        MessageRole::Assistant
+                    },
+                    content: content.to_string(),
+                    timestamp: timestamp.to_string(),
+                    metadata: Default::default(),
+                    attachments: vec![],
+                });
+        }
+        inner.save(&stored).await.unwrap();
+
+        let usecase = restart();
+        usecase.switch_session(&editable.id).await.unwrap();
+        let writes_before = sessions.writes.load(std::sync::atomic::Ordering::SeqCst);
+        let view = usecase.open_session_readonly(&archived.id).await.unwrap();
+        assert_eq!(view.persona_histories.len(), 2);
+
+        // Only the read-only session is blocked; the active one stays editable
+        assert_eq!(usecase.active_session_id().await, Some(editable.id.clone()));
+        assert!(usecase.ensure_editable(&editable.id).await.is_ok());
+        assert!(
+            usecase
+                .ensure_editable(&archived.id)
+                .await
+                .is_err_and(|e| e.is_read_only())
+        );
+
+        // Viewing the active session read-only blocks input to it
+        usecase.open_session_readonly(&editable.id).await.unwrap();
+        assert!(
+            usecase
+                .handle_input_background(AppMode::Idle, "Hello".to_string(), None, |_| {})
+                .await
+                .is_err()
+        );
+        usecase.open_session_readonly(&archived.id).await.unwrap();
+        assert_eq!(
+            sessions.writes.load(std::sync::atomic::Ordering::SeqCst),
+            writes_before
+        );
+        assert!(usecase.session_cache.get(&archived.id).await.is_none());
+
+        let promoted = usecase
+            .promote_readonly_to_active(&archived.id)
+            .await
+            .unwrap();
+        assert_eq!(promoted.persona_histories.len(), 2);
+        assert!(usecase.ensure_editable(&archived.id).await.is_ok());
+        assert!(usecase.readonly_session().await.is_none());
+
+        let manager = usecase.active_session().await.unwrap();
+        assert_eq!(manager.session_id(), archived.id);
+        manager.set_mute(true).await;
+        usecase.save_active_session(AppMode::Idle).await.unwrap();
+        assert!(
+            inner
+                .find_by_id(&archived.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .is_muted
+        );
+    }
+
+    fn message(role: MessageRole, content: &str, timestamp: &str) -> ConversationMessage {
+        ConversationMessage {
+            role,
+            content: content.to_string(),
+            timestamp: timestamp.to_string(),
+            metadata: Default::default(),
+            attachments: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_conversation_merges_histories_in_time_order() {
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        );
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let session = usecase.create_session(&workspace.id).await.unwrap();
+
+        let mut stored = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        stored.persona_histories = HashMap::from([
+            (
+                "You".to_string(),
+                vec![
+                    message(MessageRole::User, "Hi", "2026-01-01T10:00:00+00:00"),
+                    // Later than Mai's reply despite sorting first as a string
+                    message(MessageRole::User, "Thanks", "2026-01-01T09:02:00-01:00"),
+                ],
+            ),
+            (
+                "mai".to_string(),
+                vec![message(
+                    MessageRole::Assistant,
+                    "Hello!",
+                    "2026-01-01T10:01:00+00:00",
+                )],
+            ),
+        ]);
+        stored.system_messages = vec![message(
+            MessageRole::System,
+            "Mai joined",
+            "2026-01-01T10:00:30+00:00",
+        )];
+        stored.participants = HashMap::from([("mai".to_string(), "Mai".to_string())]);
+        sessions.save(&stored).await.unwrap();
+        usecase.session_cache.clear().await;
+
+        let turns = usecase.get_conversation(&session.id).await.unwrap();
+        let summary: Vec<(&str, &str, &str)> = turns
+            .iter()
+            .map(|t| (t.author_id.as_str(), t.author.as_str(), t.content.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("You", "You", "Hi"),
+                ("system", "System", "Mai joined"),
+                ("mai", "Mai", "Hello!"),
+                ("You", "You", "Thanks"),
+            ]
+        );
+        assert_eq!(turns[2].role, MessageRole::Assistant);
+        assert_eq!(turns[3].timestamp, "2026-01-01T09:02:00-01:00");
+
+        assert!(usecase.get_conversation("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_turn_distribution_counts_assistant_turns_per_persona() {
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        );
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let session = usecase.create_session(&workspace.id).await.unwrap();
+
+        let replies = |count: usize| -> Vec<ConversationMessage> {
+            (0..count)
+                .map(|i| {
+                    message(
+                        MessageRole::Assistant,
+                        "Reply",
+                        &format!("2026-01-01T10:0{}:00+00:00", i),
+                    )
+                })
+                .collect()
+        };
+        let mut stored = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        stored.persona_histories = HashMap::from([
+            (
+                "You".to_string(),
+                vec![message(
+                    MessageRole::User,
+                    "Go",
+                    "2026-01-01T09:59:00+00:00",
+                )],
+            ),
+            ("mai".to_string(), replies(6)),
+            ("yui".to_string(), replies(2)),
+        ]);
+        stored.active_participant_ids =
+            vec!["mai".to_string(), "yui".to_string(), "ren".to_string()];
+        stored.participants = HashMap::from([
+            ("mai".to_string(), "Mai".to_string()),
+            ("yui".to_string(), "Yui".to_string()),
+            ("ren".to_string(), "Ren".to_string()),
+        ]);
+        sessions.save(&stored).await.unwrap();
+        usecase.session_cache.clear().await;
+
+        let turns = usecase.turn_distribution(&session.id).await.unwrap();
+        assert_eq!(
+            turns,
+            HashMap::from([
+                ("Mai".to_string(), 6),
+                ("Yui".to_string(), 2),
+                ("Ren".to_string(), 0),
+            ])
+        );
+        // (6 + 2 + 0)^2 / (3 * (36 + 4 + 0))
+        let distribution = TurnDistribution::new(turns);
+        assert!((distribution.fairness - 64.0 / 120.0).abs() < 1e-9);
+        let even = TurnDistribution::new(HashMap::from([
+            ("Mai".to_string(), 3),
+            ("Yui".to_string(), 3),
+        ]));
+        assert!((even.fairness - 1.0).abs() < 1e-9);
+
+        assert!(usecase.turn_distribution("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_merge_sessions_interleaves_and_dedups_history() {
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        );
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let primary = usecase.create_session(&workspace.id).await.unwrap();
+        let secondary = usecase.create_session(&workspace.id).await.unwrap();
+
+        let mut stored = sessions.find_by_id(&primary.id).await.unwrap().unwrap();
+        stored.persona_histories.insert(
+            "mai".to_string(),
+            vec![
+                message(
+                    MessageRole::Assistant,
+                    "Plan A",
+                    "2026-01-01T10:00:00+00:00",
+                ),
+                message(
+                    MessageRole::Assistant,
+                    "Plan C",
+                    "2026-01-01T10:10:00+00:00",
+                ),
+            ],
+        );
+        stored.active_participant_ids = vec!["mai".to_string()];
+        stored
+            .participants
+            .insert("mai".to_string(), "Mai".to_string());
+        sessions.save(&stored).await.unwrap();
+
+        let mut stored = sessions.find_by_id(&secondary.id).await.unwrap().unwrap();
+        stored.title = "Split discussion".to_string();
+        stored.persona_histories.insert(
+            "mai".to_string(),
+            vec![
+                // Forked copy of the primary's first message
+                message(
+                    MessageRole::Assistant,
+                    "Plan A",
+                    "2026-01-01T10:00:00+00:00",
+                ),
+                // Overlaps the primary's time range
+                message(
+                    MessageRole::Assistant,
+                    "Plan B",
+                    "2026-01-01T10:05:00+00:00",
+                ),
+            ],
+        );
+        stored.persona_histories.insert(
+            "yui".to_string(),
+            // After the primary's time range
+            vec![message(
+                MessageRole::Assistant,
+                "Plan D",
+                "2026-01-01T11:00:00+00:00",
+            )],
+        );
+        stored.active_participant_ids = vec!["yui".to_string()];
+        stored
+            .participants
+            .insert("yui".to_string(), "Yui".to_string());
+        sessions.save(&stored).await.unwrap();
+
+        // Reload the fixtures; only the primary is loaded, so the merge must reach its manager
+        usecase.session_cache.clear().await;
+        usecase.switch_session(&primary.id).await.unwrap();
+        let merged = usecase
+            .merge_sessions(&primary.id, &secondary.id, true)
+            .await
+            .unwrap();
+
+        let contents = |session: &Session, author: &str| -> Vec<String> {
+            session.persona_histories[author]
+                .iter()
+                .map(|m| m.content.clone())
+                .collect()
+        };
+        assert_eq!(contents(&merged, "mai"), ["Plan A", "Plan B", "Plan C"]);
+        assert_eq!(contents(&merged, "yui"), ["Plan D"]);
+        assert!(merged.active_participant_ids.contains(&"yui".to_string()));
+        assert_eq!(merged.participants["yui"], "Yui");
+        let note = merged.system_messages.last().unwrap();
+        assert!(note.content.contains("Split discussion"));
+        assert!(note.content.contains('3'));
+
+        // Saving the loaded primary keeps the merged history
+        usecase.save_active_session(AppMode::Idle).await.unwrap();
+        let saved = sessions.find_by_id(&primary.id).await.unwrap().unwrap();
+        assert_eq!(contents(&saved, "mai"), ["Plan A", "Plan B", "Plan C"]);
+        assert_eq!(contents(&saved, "yui"), ["Plan D"]);
+
+        let archived = sessions.find_by_id(&secondary.id).await.unwrap().unwrap();
+        assert!(archived.is_archived);
+        assert!(
+            usecase
+                .merge_sessions(&primary.id, &primary.id, false)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compact_session_shrinks_file_and_keeps_turns() {
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        );
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let session = usecase.create_session(&workspace.id).await.unwrap();
+
+        let mut stored = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        stored.persona_histories.insert(
+            "user".to_string(),
+            vec![message(
+                MessageRole::User,
+                "Review the parser",
+                "2026-01-01T10:00:00+00:00",
+            )],
+        );
+        stored.persona_histories.insert(
+            "mai".to_string(),
+            vec![message(
+                MessageRole::Assistant,
+                "Looks good",
+                "2026-01-01T10:01:00+00:00",
+            )],
+        );
+        stored.persona_histories.insert(
+            LEGACY_ERROR_HISTORY_KEY.to_string(),
+            vec![message(
+                MessageRole::System,
+                "Error: rate limited",
+                "2026-01-01T10:02:00+00:00",
+            )],
+        );
+        stored.system_messages = (0..20)
+            .map(|i| {
+                message(
+                    MessageRole::System,
+                    "Workspace switched to alpha",
+                    &format!("2026-01-01T09:{:02}:00+00:00", i),
+                )
+            })
+            .collect();
+        sessions.save(&stored).await.unwrap();
+        let turns_before = stored.persona_histories.clone();
+
+        usecase.session_cache.clear().await;
+        let compaction = usecase.compact_session(&session.id).await.unwrap();
+        assert_eq!(compaction.system_messages_removed, 19);
+        assert!(compaction.bytes_after.unwrap() < compaction.bytes_before.unwrap());
+        assert!(compaction.bytes_saved > 0);
+
+        let compacted = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        assert!(
+            !compacted
+                .persona_histories
+                .contains_key(LEGACY_ERROR_HISTORY_KEY)
+        );
+        for author in ["user", "mai"] {
+            assert_eq!(
+                compacted.persona_histories[author], turns_before[author],
+                "turns of {} changed",
+                author
+            );
+        }
+        let system: Vec<&str> = compacted
+            .system_messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(
+            system,
+            ["Workspace switched to alpha", "Error: rate limited"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_web_clip_is_stored_and_announced() {
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        );
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let session = usecase.create_session(&workspace.id).await.unwrap();
+        usecase.session_cache.clear().await;
+
+        let clip = WebClip {
+            url: "https://example.com/errors".to_string(),
+            title: "Error Handling".to_string(),
+            text: "Error Handling\n\nResults make failures explicit.\n\nMore details.".to_string(),
+            warnings: vec![],
+        };
+        let file = usecase
+            .add_web_clip(&session.id, &clip, None)
+            .await
+            .unwrap();
+
+        assert_eq!(file.name, "error-handling.md");
+        assert_eq!(file.session_id.as_deref(), Some(session.id.as_str()));
+        let stored = std::fs::read_to_string(&file.path).unwrap();
+        assert!(stored.contains("Source: https://example.com/errors"));
+        assert!(stored.ends_with("More details.\n"));
+
+        let saved = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        let note = saved.system_messages.last().unwrap();
+        assert_eq!(
+            note.metadata.system_message_type.as_deref(),
+            Some("context_info")
+        );
+        assert!(note.content.contains("https://example.com/errors"));
+        // Without an LLM summary the lead paragraph stands in
+        assert!(note.content.contains("Results make failures explicit."));
+        assert!(!note.content.contains("More details."));
+        assert_eq!(note.attachments, [file.path.to_string_lossy().to_string()]);
+        let source = note.metadata.source.as_ref().unwrap();
+        assert_eq!(source.kind, SourceKind::WebClip);
+        assert_eq!(source.label, "Error Handling");
+    }
+
+    #[tokio::test]
+    async fn test_decision_record_is_extracted_stored_and_announced() {
+        use orcs_interaction::testing::ScriptedAgent;
+
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        // The first reply lacks the decision, so the stricter prompt is used
+        let agent = ScriptedAgent::new()
+            .reply("We talked about storage for a while.\n\nContext: Listing is slow.")
+            .reply(
+                r#"```json
+{"title": "Add a session index", "context": "Listing is slow.",
+ "options": ["Index file", "Full scan"], "decision": "Add an index file.",
+ "consequences": [], "dissentingOpinions": [{"author": "Yui", "opinion": "Scans are fast enough."}]}
+```"#,
+            );
+        let usecase = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        )
+        .with_decision_agent(Arc::new(agent.clone()));
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let session = usecase.create_session(&workspace.id).await.unwrap();
+        usecase.session_cache.clear().await;
+
+        let mut stored = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        stored.persona_histories.insert(
+            "user".to_string(),
+            vec![
+                message(
+                    MessageRole::User,
+                    "Greetings first",
+                    "2026-01-01T09:00:00+00:00",
+                ),
+                message(
+                    MessageRole::User,
+                    "How do we speed up listing?",
+                    "2026-01-01T10:00:00+00:00",
+                ),
+            ],
+        );
+        stored.persona_histories.insert(
+            "mai".to_string(),
+            vec![message(
+                MessageRole::Assistant,
+                "An index file would help.",
+                "2026-01-01T10:01:00+00:00",
+            )],
+        );
+        sessions.save(&stored).await.unwrap();
+
+        let record = usecase
+            .extract_decision_record(
+                &session.id,
+                Some(("2026-01-01T09:30:00Z", "2026-01-01T11:00:00Z")),
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.title, "Add a session index");
+        assert_eq!(record.options_considered, ["Index file", "Full scan"]);
+        assert_eq!(record.dissenting_opinions[0].author, "Yui");
+
+        let prompts = agent.received();
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[0].contains("[mai]: An index file would help."));
+        assert!(!prompts[0].contains("Greetings first"));
+        assert!(prompts[1].contains("missing required fields: decision"));
+
+        let saved = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        let note = saved.system_messages.last().unwrap();
+        assert!(
+            note.content
+                .contains("Decision record: Add a session index")
+        );
+        let path = std::path::PathBuf::from(&note.attachments[0]);
+        assert_eq!(
+            path.parent().unwrap(),
+            workspace.workspace_dir.join("decisions")
+        );
+        let markdown = std::fs::read_to_string(&path).unwrap();
+        assert!(markdown.contains("## Decision\n\nAdd an index file."));
+        assert!(markdown.contains("- **Yui**: Scans are fast enough."));
+
+        // Unusable replies on both attempts fail without writing anything
+        let failing = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            usecase.app_state_service.clone(),
+            usecase.persona_repository.clone(),
+            Arc::new(ConfigBasedUserService::new()),
+        )
+        .with_decision_agent(Arc::new(ScriptedAgent::new().repeat("No idea.")));
+        assert!(
+            failing
+                .extract_decision_record(&session.id, None)
+                .await
+                .is_err()
+        );
+        let saved_again = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        assert_eq!(
+            saved_again.system_messages.len(),
+            saved.system_messages.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_system_message_reaches_unloaded_session() {
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let restart = || {
+            SessionUseCase::new(
+                sessions.clone(),
+                workspaces.clone(),
+                app_state.clone(),
+                personas.clone(),
+                Arc::new(ConfigBasedUserService::new()),
+            )
+        };
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let session = restart().create_session(&workspace.id).await.unwrap();
+
+        let usecase = restart();
+        assert!(usecase.session_cache.get(&session.id).await.is_none());
+        usecase
+            .add_system_message_to_session(
+                &session.id,
+                "Action denied".to_string(),
+                Some("context_info".to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let stored = sessions.find_by_id(&session.id).await.unwrap().unwrap();
+        let note = stored.system_messages.last().unwrap();
+        assert_eq!(note.content, "Action denied");
+        assert_eq!(
+            note.metadata.system_message_type.as_deref(),
+            Some("context_info")
+        );
+        assert_eq!(
+            note.metadata.source.as_ref().map(|source| source.kind),
+            Some(SourceKind::ContextInfo)
+        );
+        assert!(
+            usecase
+                .add_system_message_to_session("missing", "x".to_string(), None, None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_archived_deletes_only_old_non_favorites() {
+        let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let usecase = SessionUseCase::new(
+            sessions.clone(),
+            workspaces.clone(),
+            app_state,
+            personas,
+            Arc::new(ConfigBasedUserService::new()),
+        );
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let old = (chrono::Utc::now() - chrono::Duration::days(45)).to_rfc3339();
+        let recent = (chrono::Utc::now() - chrono::Duration::days(5)).to_rfc3339();
+        let mut ids = Vec::new();
+        for (updated_at, archived, favorite) in [
+            (&old, true, false),
+            (&recent, true, false),
+            (&old, true, true),
+            (&old, false, false),
+        ] {
+            let mut session = usecase.create_session(&workspace.id).await.unwrap();
+            session.updated_at = updated_at.clone();
+            session.is_archived = archived;
+            session.is_favorite = favorite;
+            sessions.save(&session).await.unwrap();
+            ids.push(session.id);
+        }
+
+        let pruned = usecase.prune_archived(30).await.unwrap();
+        assert_eq!(pruned, vec![ids[0].clone()]);
+        assert!(sessions.find_by_id(&ids[0]).await.unwrap().is_none());
+        for kept in &ids[1..] {
+            assert!(sessions.find_by_id(kept).await.unwrap().is_some());
+        }
+        assert!(usecase.prune_archived(30).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_turn_is_recovered_once_on_reload() {
+        let dirs = [(); 5].map(|_| TempDir::new().unwrap());
+        let projects = TempDir::new().unwrap();
+
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let sessions = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        let personas = Arc::new(
+            AsyncDirPersonaRepository::new(Some(dirs[2].path()))
+                .await
+                .unwrap(),
+        );
+        let app_state = Arc::new(
+            AppStateService::with_base_dir(Some(&dirs[3].path().join("state")))
+                .await
+                .unwrap(),
+        );
+        let store = Arc::new(PartialTurnStore::new(Some(dirs[4].path())).await.unwrap());
+        // A fresh use case has an empty cache, like after an app restart
+        let restart = || {
+            SessionUseCase::new(
+                sessions.clone(),
+                workspaces.clone(),
+                app_state.clone(),
+                personas.clone(),
+                Arc::new(ConfigBasedUserService::new()),
+            )
+            .with_partial_turn_store(store.clone())
+        };
+
+        let root = projects.path().join("alpha");
+        std::fs::create_dir_all(&root).unwrap();
+        let workspace = workspaces.get_or_create_workspace(&root).await.unwrap();
+        let session = restart().create_session(&workspace.id).await.unwrap();
+
+        // Stream two chunks, then die before the turn completes
+        let writer = StreamRecoveryWriter::spawn(
+            store.clone(),
+            FlushPolicy {
+                max_chunks: 2,
+                max_interval: Duration::from_secs(60),
+            },
+        );
+        let stream_writer = writer.clone();
+        let session_id = session.id.clone();
+        let streaming = tokio::spawn(async move {
+            stream_writer.record_chunk(&session_id, "Mai", "Half of ");
+            stream_writer.record_chunk(&session_id, "Mai", "an answer");
+            std::future::pending::<()>().await;
+        });
+        while store
+            .load(&session.id)
+            .await
+            .unwrap()
+            .is_none_or(|turn| turn.chunks.len() < 2)
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        streaming.abort();
+        drop(writer);
+
+        let recovered_messages = |session: &Session| {
+            session
+                .persona_histories
+                .values()
+                .flatten()
+                .filter(|m| m.role == MessageRole::Assistant && m.metadata.recovered_partial)
+                .map(|m| m.content.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let reloaded = restart().switch_session(&session.id).await.unwrap();
+        assert_eq!(recovered_messages(&reloaded), vec!["Half of an answer"]);
+        assert_eq!(reloaded.system_messages.len(), 1);
+        assert!(store.load(&session.id).await.unwrap().is_none());
+
+        let reloaded = restart().switch_session(&session.id).await.unwrap();
+        assert_eq!(recovered_messages(&reloaded), vec!["Half of an answer"]);
+
+        // A completed turn leaves nothing to recover
+        let writer = StreamRecoveryWriter::spawn(
+            store.clone(),
+            FlushPolicy {
+                max_chunks: 1,
+                max_interval: Duration::from_secs(60),
+            },
+        );
+        writer.record_chunk(&session.id, "Mai", "Full answer");
+        writer.complete(&session.id);
+        drop(writer);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(store.load(&session.id).await.unwrap().is_none());
+    }
+}
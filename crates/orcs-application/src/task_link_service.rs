@@ -0,0 +1,290 @@
+//! Links between conversation messages and the tasks spawned from them.
+//!
+//! A task records the message it was spawned from (`origin_message_timestamp`,
+//! `origin_author`), and the message lists the task in `spawned_task_ids`. This
+//! service resolves both directions. Either side may be gone (a deleted task or
+//! a cleared session), so dangling references resolve to empty results instead
+//! of errors.
+
+use orcs_core::error::Result;
+use orcs_core::repository::SessionRepository;
+use orcs_core::session::{ConversationMessage, timestamps_match};
+use orcs_core::task::{Task, TaskRepository};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// The conversation message a task was spawned from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOriginMessage {
+    pub task_id: String,
+    pub session_id: String,
+    /// Timestamp of the originating message (ISO 8601 format).
+    pub message_timestamp: String,
+    /// Author of the originating message (persona ID, user or "system").
+    pub author: String,
+    /// The message itself, or `None` if it no longer exists.
+    pub message: Option<ConversationMessage>,
+}
+
+/// Resolves links between messages and the tasks they spawned.
+pub struct TaskLinkService {
+    session_repository: Arc<dyn SessionRepository>,
+    task_repository: Arc<dyn TaskRepository>,
+}
+
+impl TaskLinkService {
+    pub fn new(
+        session_repository: Arc<dyn SessionRepository>,
+        task_repository: Arc<dyn TaskRepository>,
+    ) -> Self {
+        Self {
+            session_repository,
+            task_repository,
+        }
+    }
+
+    /// Returns the tasks spawned from the message of `session_id` created at
+    /// `message_timestamp`, oldest first.
+    pub async fn tasks_for_message(
+        &self,
+        session_id: &str,
+        message_timestamp: &str,
+    ) -> Result<Vec<Task>> {
+        let mut tasks: Vec<Task> = self
+            .task_repository
+            .list_by_session(session_id)
+            .await?
+            .into_iter()
+            .filter(|task| {
+                task.origin_message_timestamp
+                    .as_deref()
+                    .is_some_and(|timestamp| timestamps_match(timestamp, message_timestamp))
+            })
+            .collect();
+        tasks.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(tasks)
+    }
+
+    /// Returns the message `task_id` was spawned from.
+    ///
+    /// Returns `None` if the task does not exist or was not spawned from a
+    /// message. If the session or message was deleted since, the origin is
+    /// still returned, without the message.
+    pub async fn origin_for_task(&self, task_id: &str) -> Result<Option<TaskOriginMessage>> {
+        let Some(task) = self.task_repository.find_by_id(task_id).await? else {
+            return Ok(None);
+        };
+        let (Some(message_timestamp), Some(author)) =
+            (task.origin_message_timestamp, task.origin_author)
+        else {
+            return Ok(None);
+        };
+
+        let message = self
+            .session_repository
+            .find_by_id(&task.session_id)
+            .await?
+            .and_then(|session| {
+                session
+                    .find_message(&message_timestamp)
+                    .map(|(_, message)| message.clone())
+            });
+
+        Ok(Some(TaskOriginMessage {
+            task_id: task.id,
+            session_id: task.session_id,
+            message_timestamp,
+            author,
+            message,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_toolkit::agent::dialogue::ExecutionModel;
+    use orcs_core::session::{AppMode, MessageMetadata, MessageRole, Session};
+    use orcs_core::task::TaskStatus;
+    use orcs_infrastructure::{AsyncDirSessionRepository, AsyncDirTaskRepository};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const MESSAGE_AT: &str = "2026-10-17T14:30:00.123456+00:00";
+
+    fn session(id: &str, messages: Vec<ConversationMessage>) -> Session {
+        Session {
+            id: id.to_string(),
+            title: format!("Session {}", id),
+            created_at: "2026-10-17T14:00:00Z".to_string(),
+            updated_at: "2026-10-17T15:00:00Z".to_string(),
+            current_persona_id: String::new(),
+            persona_histories: HashMap::from([("mai".to_string(), messages)]),
+            app_mode: AppMode::Idle,
+            workspace_id: "ws-1".to_string(),
+            active_participant_ids: vec![],
+            execution_strategy: ExecutionModel::Broadcast,
+            system_messages: vec![],
+            participants: HashMap::new(),
+            participant_icons: HashMap::new(),
+            participant_colors: HashMap::new(),
+            participant_backends: HashMap::new(),
+            participant_models: HashMap::new(),
+            conversation_mode: Default::default(),
+            talk_style: None,
+            is_favorite: false,
+            is_archived: false,
+            sort_order: None,
+            auto_chat_config: None,
+            is_muted: false,
+            context_mode: Default::default(),
+            sandbox_state: None,
+            last_memory_sync_at: None,
+            is_generating: false,
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: 8_000,
+        }
+    }
+
+    fn message(content: &str, timestamp: &str) -> ConversationMessage {
+        ConversationMessage {
+            role: MessageRole::Assistant,
+            content: content.to_string(),
+            timestamp: timestamp.to_string(),
+            metadata: MessageMetadata::default(),
+            attachments: vec![],
+        }
+    }
+
+    fn task(id: &str, created_at: &str, origin: Option<&str>) -> Task {
+        Task {
+            id: id.to_string(),
+            session_id: "s-1".to_string(),
+            title: "Refactor".to_string(),
+            description: String::new(),
+            status: TaskStatus::Pending,
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            completed_at: None,
+            steps_executed: 0,
+            steps_skipped: 0,
+            context_keys: 0,
+            error: None,
+            result: None,
+            execution_details: None,
+            strategy: None,
+            journal_log: None,
+            origin_message_timestamp: origin.map(str::to_string),
+            origin_author: origin.map(|_| "mai".to_string()),
+            dry_run: false,
+        }
+    }
+
+    async fn service() -> (
+        TaskLinkService,
+        Arc<dyn SessionRepository>,
+        Arc<dyn TaskRepository>,
+        [TempDir; 2],
+    ) {
+        let dirs = [(); 2].map(|_| TempDir::new().unwrap());
+        let sessions: Arc<dyn SessionRepository> = Arc::new(
+            AsyncDirSessionRepository::new(Some(dirs[0].path()))
+                .await
+                .unwrap(),
+        );
+        let tasks: Arc<dyn TaskRepository> = Arc::new(
+            AsyncDirTaskRepository::new(Some(dirs[1].path()))
+                .await
+                .unwrap(),
+        );
+        (
+            TaskLinkService::new(sessions.clone(), tasks.clone()),
+            sessions,
+            tasks,
+            dirs,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tasks_are_found_from_their_message_and_back() {
+        let (service, sessions, tasks, _dirs) = service().await;
+        sessions
+            .save(&session(
+                "s-1",
+                vec![message("Let's refactor.", MESSAGE_AT)],
+            ))
+            .await
+            .unwrap();
+        let later = "4b2e7d1a-9c3f-4e8b-a0d5-6f1c8e2b7a44";
+        let earlier = "3a1f6c2e-8b4d-4f7a-9c0e-5d2b7a9e1f33";
+        let unrelated = "5c3f8e2b-0d4a-4f9c-b1e6-7a2d9f3c8b55";
+        tasks
+            .save(&task(later, "2026-10-17T14:35:00Z", Some(MESSAGE_AT)))
+            .await
+            .unwrap();
+        tasks
+            .save(&task(earlier, "2026-10-17T14:31:00Z", Some(MESSAGE_AT)))
+            .await
+            .unwrap();
+        tasks
+            .save(&task(unrelated, "2026-10-17T14:32:00Z", None))
+            .await
+            .unwrap();
+
+        // The frontend sends millisecond timestamps with a 'Z' suffix
+        let spawned = service
+            .tasks_for_message("s-1", "2026-10-17T14:30:00.123Z")
+            .await
+            .unwrap();
+        let ids: Vec<&str> = spawned.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec![earlier, later]);
+
+        let origin = service.origin_for_task(later).await.unwrap().unwrap();
+        assert_eq!(origin.author, "mai");
+        assert_eq!(origin.message.unwrap().content, "Let's refactor.");
+        assert!(service.origin_for_task(unrelated).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dangling_links_resolve_without_errors() {
+        let (service, sessions, tasks, _dirs) = service().await;
+        let orphan = "3a1f6c2e-8b4d-4f7a-9c0e-5d2b7a9e1f33";
+        tasks
+            .save(&task(orphan, "2026-10-17T14:31:00Z", Some(MESSAGE_AT)))
+            .await
+            .unwrap();
+
+        // The session was deleted: the origin is kept, without its message
+        let origin = service.origin_for_task(orphan).await.unwrap().unwrap();
+        assert_eq!(origin.message_timestamp, MESSAGE_AT);
+        assert!(origin.message.is_none());
+
+        // The message was cleared from the session
+        sessions.save(&session("s-1", vec![])).await.unwrap();
+        let origin = service.origin_for_task(orphan).await.unwrap().unwrap();
+        assert!(origin.message.is_none());
+
+        // The task was deleted
+        tasks.delete(orphan).await.unwrap();
+        assert!(
+            service
+                .tasks_for_message("s-1", MESSAGE_AT)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(service.origin_for_task(orphan).await.unwrap().is_none());
+    }
+}
@@ -3,9 +3,11 @@
 //! Provides lightweight LLM operations for UI enhancements like title generation,
 //! summarization, and icon suggestions using fast models (Gemini Flash API).
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
 use llm_toolkit::ToPrompt;
 use llm_toolkit::agent::Agent;
+use orcs_core::offline::OfflineMode;
 use serde::{Deserialize, Serialize};
 
 /// Generic title/metadata response from lightweight LLM
@@ -71,15 +73,42 @@ struct TitleGenerationRequest {
 )]
 struct TitleGeneratorAgent;
 
+/// Lightweight LLM operations used outside of agent dialogues.
+///
+/// Implemented by [`UtilityAgentService`]; consumers such as the task
+/// executor take `Arc<dyn UtilityOps>` so tests can inject a mock.
+#[async_trait]
+pub trait UtilityOps: Send + Sync {
+    /// Generates a concise task title (3-8 words) from a task description.
+    async fn generate_task_title(&self, task_description: &str) -> Result<String>;
+
+    /// Summarizes `content` in 1-2 sentences.
+    async fn summarize(&self, content: &str) -> Result<String>;
+
+    /// Returns the one of `labels` that best describes `content`.
+    async fn classify(&self, content: &str, labels: &[String]) -> Result<String>;
+}
+
 /// Service providing lightweight LLM utilities
 pub struct UtilityAgentService {
     title_agent: TitleGeneratorAgent,
+    offline_mode: OfflineMode,
 }
 
 impl UtilityAgentService {
     pub fn new() -> Self {
         let title_agent = TitleGeneratorAgent;
-        Self { title_agent }
+        Self {
+            title_agent,
+            offline_mode: OfflineMode::default(),
+        }
+    }
+
+    /// Fails every LLM call while `offline_mode` is on, so callers take their
+    /// non-LLM fallback paths.
+    pub fn with_offline_mode(mut self, offline_mode: OfflineMode) -> Self {
+        self.offline_mode = offline_mode;
+        self
     }
 
     /// Generate title and metadata from content using Gemini Flash
@@ -94,6 +123,7 @@ impl UtilityAgentService {
     /// # Returns
     ///
     /// * `Ok(TitleResponse)` - Generated metadata
+    /// * `Err` - The request failed, or offline mode is on
     ///
     /// # Examples
     ///
@@ -125,8 +155,6 @@ impl UtilityAgentService {
         include_description: bool,
         include_icon: bool,
     ) -> Result<TitleResponse> {
-        use llm_toolkit::prompt::ToPrompt;
-
         let mut requirements =
             vec!["Generate a concise, descriptive title (3-8 words recommended)".to_string()];
 
@@ -143,13 +171,23 @@ impl UtilityAgentService {
             requirements.push("Set icon to null (not needed)".to_string());
         }
 
+        self.request_title(content, context, requirements).await
+    }
+
+    /// Runs the title agent on `content` with the given requirements.
+    async fn request_title(
+        &self,
+        content: &str,
+        context: &str,
+        requirements: Vec<String>,
+    ) -> Result<TitleResponse> {
+        use llm_toolkit::prompt::ToPrompt;
+
+        self.offline_mode.ensure_online("Gemini API")?;
+
         // Create typed request with Jinja2 template
         let request = TitleGenerationRequest {
-            content: if content.len() > 500 {
-                content[..500].to_string()
-            } else {
-                content.to_string()
-            },
+            content: content.chars().take(500).collect(),
             context: context.to_string(),
             requirements,
             output_schema: TitleResponse::prompt_schema(),
@@ -204,6 +242,41 @@ impl UtilityAgentService {
     }
 }
 
+#[async_trait]
+impl UtilityOps for UtilityAgentService {
+    async fn generate_task_title(&self, task_description: &str) -> Result<String> {
+        UtilityAgentService::generate_task_title(self, task_description).await
+    }
+
+    async fn summarize(&self, content: &str) -> Result<String> {
+        let response = self.generate_title(content, "summary", true, false).await?;
+        Ok(response.description.unwrap_or(response.title))
+    }
+
+    async fn classify(&self, content: &str, labels: &[String]) -> Result<String> {
+        if labels.is_empty() {
+            return Err(anyhow!("No labels to classify into"));
+        }
+        let requirements = vec![
+            format!(
+                "Set title to exactly one of these labels, whichever best describes the content: {}",
+                labels.join(", ")
+            ),
+            "Set description to null (not needed)".to_string(),
+            "Set icon to null (not needed)".to_string(),
+        ];
+        let response = self
+            .request_title(content, "classification", requirements)
+            .await?;
+        let answer = response.title.trim();
+        labels
+            .iter()
+            .find(|label| label.eq_ignore_ascii_case(answer))
+            .cloned()
+            .ok_or_else(|| anyhow!("Classifier answered an unknown label: {}", answer))
+    }
+}
+
 impl Default for UtilityAgentService {
     fn default() -> Self {
         Self::new()
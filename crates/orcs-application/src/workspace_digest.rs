@@ -0,0 +1,1060 @@
+//! Workspace Digests
+//!
+//! Summarizes the sessions of a workspace over the last full day or week:
+//! which sessions were active, key decisions, completed tasks and open
+//! questions. The digest is returned as a [`WorkspaceDigest`] and saved as a
+//! Markdown document under the workspace's `digests` directory.
+//!
+//! Summaries already in a session are reused: a "Conversation Summary" message
+//! written after the session's last in-period message, and decision records
+//! extracted during the period. Other sessions are summarized with the utility
+//! backend (Gemini Flash API) until the token budget runs out.
+//!
+//! Sessions are processed one at a time. After each one the partial digest is
+//! written to a checkpoint file, so a cancelled or failed run resumes where it
+//! stopped; the checkpoint is removed once the digest is saved.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use llm_toolkit::ToPrompt;
+use llm_toolkit::agent::Agent;
+use orcs_core::repository::{SessionRepository, TaskRepository};
+use orcs_core::session::{ConversationMessage, MessageRole, Session};
+use orcs_core::task::TaskStatus;
+use orcs_core::workspace::manager::WorkspaceStorageService;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::decision_record::{decision_transcript, json_object};
+use crate::session_export::{DateRange, estimate_tokens};
+
+/// Default token budget for summaries generated by one digest.
+pub const DEFAULT_DIGEST_TOKEN_BUDGET: usize = 100_000;
+
+/// Directory under the workspace directory that digests are saved to.
+const DIGESTS_DIR: &str = "digests";
+
+/// Heading of the summaries written by the session support agent.
+const SUMMARY_HEADING: &str = "# 📝 Conversation Summary";
+
+/// Prefix of the system message announcing an extracted decision record.
+const DECISION_RECORD_PREFIX: &str = "📋 Decision record: ";
+
+/// Time span covered by a digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestPeriod {
+    /// The previous calendar day (UTC)
+    Daily,
+    /// The previous week, Monday to Monday (UTC)
+    Weekly,
+}
+
+impl DigestPeriod {
+    /// Returns the last full period before `now` as a half-open `[start, end)` range.
+    pub fn window(self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let today = now
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc();
+        match self {
+            DigestPeriod::Daily => (today - Duration::days(1), today),
+            DigestPeriod::Weekly => {
+                let monday =
+                    today - Duration::days(i64::from(now.weekday().num_days_from_monday()));
+                (monday - Duration::weeks(1), monday)
+            }
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DigestPeriod::Daily => "daily",
+            DigestPeriod::Weekly => "weekly",
+        }
+    }
+}
+
+impl FromStr for DigestPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "daily" | "day" => Ok(DigestPeriod::Daily),
+            "weekly" | "week" => Ok(DigestPeriod::Weekly),
+            _ => Err(anyhow!(
+                "Invalid digest period '{}': use daily or weekly",
+                value
+            )),
+        }
+    }
+}
+
+/// Where the summary of a session digest came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestSource {
+    /// An existing summary in the session
+    Reused,
+    /// Generated for this digest
+    Generated,
+    /// No summary: the token budget ran out or generation failed
+    Skipped,
+}
+
+/// Digest of one session active in the period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDigest {
+    pub session_id: String,
+    pub title: String,
+    /// Number of user and assistant messages in the period
+    pub message_count: usize,
+    pub summary: Option<String>,
+    pub key_decisions: Vec<String>,
+    pub open_questions: Vec<String>,
+    pub source: DigestSource,
+    /// Why the summary is missing, for skipped sessions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<String>,
+}
+
+/// A task completed in the period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestTask {
+    pub title: String,
+    pub session_id: String,
+    pub completed_at: String,
+}
+
+/// Digest of a workspace's sessions over a [`DigestPeriod`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceDigest {
+    pub workspace_id: String,
+    pub workspace_name: String,
+    pub period: DigestPeriod,
+    /// Start of the period (inclusive, RFC3339)
+    pub period_start: String,
+    /// End of the period (exclusive, RFC3339)
+    pub period_end: String,
+    pub generated_at: String,
+    /// Sessions with messages in the period, most active first
+    pub sessions: Vec<SessionDigest>,
+    pub completed_tasks: Vec<DigestTask>,
+    /// Estimated tokens spent on generated summaries
+    pub tokens_used: usize,
+    /// Path of the saved Markdown document
+    pub markdown_path: PathBuf,
+}
+
+impl WorkspaceDigest {
+    /// Markdown document of the digest.
+    pub fn to_markdown(&self) -> String {
+        let start = &self.period_start[..10];
+        let mut markdown = format!(
+            "# 🗞️ {} digest: {}\n\nPeriod: {} to {} (UTC)\n\n## Active Sessions\n\n",
+            capitalize(self.period.as_str()),
+            self.workspace_name,
+            start,
+            &self.period_end[..10]
+        );
+        if self.sessions.is_empty() {
+            markdown.push_str("No sessions were active.\n");
+        }
+        for session in &self.sessions {
+            markdown.push_str(&format!(
+                "### {}\n\n{} messages\n\n",
+                session.title, session.message_count
+            ));
+            match (&session.summary, &session.skip_reason) {
+                (Some(summary), _) => markdown.push_str(&format!("{}\n\n", summary)),
+                (None, Some(reason)) => {
+                    markdown.push_str(&format!("_No summary: {}._\n\n", reason))
+                }
+                (None, None) => {}
+            }
+        }
+
+        let decisions: Vec<String> = self
+            .sessions
+            .iter()
+            .flat_map(|s| s.key_decisions.iter().map(move |d| (s, d)))
+            .map(|(s, d)| format!("{} ({})", d, s.title))
+            .collect();
+        markdown.push_str("## Key Decisions\n\n");
+        push_list(&mut markdown, &decisions);
+
+        let tasks: Vec<String> = self
+            .completed_tasks
+            .iter()
+            .map(|t| t.title.clone())
+            .collect();
+        markdown.push_str("\n## Completed Tasks\n\n");
+        push_list(&mut markdown, &tasks);
+
+        let questions: Vec<String> = self
+            .sessions
+            .iter()
+            .flat_map(|s| s.open_questions.iter().map(move |q| (s, q)))
+            .map(|(s, q)| format!("{} ({})", q, s.title))
+            .collect();
+        markdown.push_str("\n## Open Questions\n\n");
+        push_list(&mut markdown, &questions);
+        markdown
+    }
+}
+
+fn push_list(markdown: &mut String, items: &[String]) {
+    if items.is_empty() {
+        markdown.push_str("None recorded.\n");
+    }
+    for item in items {
+        markdown.push_str(&format!("- {}\n", item));
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Progress of a digest, reported after each session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestProgress {
+    pub workspace_id: String,
+    /// Sessions processed so far, including ones restored from a checkpoint
+    pub processed: usize,
+    pub total: usize,
+    pub session_id: String,
+    pub title: String,
+    pub source: DigestSource,
+}
+
+/// Partial digest saved between sessions so a run can resume.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DigestCheckpoint {
+    sessions: Vec<SessionDigest>,
+    tokens_used: usize,
+}
+
+/// Summary of a session's period, as returned by the utility backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToPrompt)]
+#[prompt(mode = "full")]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDigestResponse {
+    /// What the conversation was about and where it ended, in 2-4 sentences
+    pub summary: String,
+    /// Decisions that were reached
+    pub key_decisions: Vec<String>,
+    /// Questions raised but not answered
+    pub open_questions: Vec<String>,
+}
+
+impl SessionDigestResponse {
+    /// Parses a model reply: a JSON object anywhere in the text, or the whole
+    /// reply as the summary.
+    fn parse(output: &str) -> Option<Self> {
+        let list = |value: Option<&Value>| -> Vec<String> {
+            value
+                .and_then(Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let parsed = json_object(output).and_then(|value| {
+            let summary = value.get("summary")?.as_str()?.trim().to_string();
+            Some(Self {
+                summary,
+                key_decisions: list(value.get("keyDecisions").or(value.get("key_decisions"))),
+                open_questions: list(value.get("openQuestions").or(value.get("open_questions"))),
+            })
+        });
+        parsed
+            .or_else(|| {
+                Some(Self {
+                    summary: output.trim().to_string(),
+                    ..Default::default()
+                })
+            })
+            .filter(|response| !response.summary.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToPrompt, Default)]
+#[prompt(template = r#"Summarize this conversation for a team digest:
+
+{{ transcript }}
+
+Requirements:
+- summary: what the conversation was about and where it ended, in 2-4 sentences
+- keyDecisions: decisions that were reached, one short sentence each
+- openQuestions: questions raised but not answered, one short sentence each
+- Use only what was said in the conversation; use empty lists when nothing applies
+
+Output a JSON object matching this schema:
+{{ output_schema }}
+
+IMPORTANT: Output ONLY valid JSON, no markdown formatting or code blocks."#)]
+struct SessionDigestRequest {
+    /// The conversation transcript of the period
+    transcript: String,
+
+    /// Output schema for SessionDigestResponse
+    output_schema: String,
+}
+
+/// Lightweight agent for summarizing sessions into digests using Gemini Flash API
+#[derive(llm_toolkit::Agent)]
+#[agent(
+    expertise = "Summarize multi-agent conversations for a team digest: outcome, key decisions and open questions.",
+    output = "String",
+    default_inner = "orcs_interaction::GeminiApiAgent"
+)]
+pub struct DigestAgent;
+
+/// Service generating workspace digests.
+pub struct WorkspaceDigestService {
+    session_repository: Arc<dyn SessionRepository>,
+    task_repository: Arc<dyn TaskRepository>,
+    workspace_storage_service: Arc<dyn WorkspaceStorageService>,
+    agent: Arc<dyn Agent<Output = String, Expertise = String>>,
+    token_budget: usize,
+}
+
+impl WorkspaceDigestService {
+    pub fn new(
+        session_repository: Arc<dyn SessionRepository>,
+        task_repository: Arc<dyn TaskRepository>,
+        workspace_storage_service: Arc<dyn WorkspaceStorageService>,
+    ) -> Self {
+        Self {
+            session_repository,
+            task_repository,
+            workspace_storage_service,
+            agent: Arc::new(DigestAgent),
+            token_budget: DEFAULT_DIGEST_TOKEN_BUDGET,
+        }
+    }
+
+    /// Uses `agent` to summarize sessions instead of the utility backend.
+    pub fn with_agent(
+        mut self,
+        agent: Arc<dyn Agent<Output = String, Expertise = String>>,
+    ) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    /// Sets the token budget for generated summaries.
+    pub fn with_token_budget(mut self, token_budget: usize) -> Self {
+        self.token_budget = token_budget;
+        self
+    }
+
+    /// Generates and saves the digest of `workspace_id` for the last full `period`.
+    ///
+    /// `on_progress` is called after each session. Setting `cancel_flag` stops
+    /// the run before the next session; running again resumes from there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the workspace does not exist, the run is cancelled,
+    /// or storage fails.
+    pub async fn generate_workspace_digest(
+        &self,
+        workspace_id: &str,
+        period: DigestPeriod,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: impl Fn(&DigestProgress),
+    ) -> Result<WorkspaceDigest> {
+        self.digest_at(workspace_id, period, Utc::now(), cancel_flag, on_progress)
+            .await
+    }
+
+    async fn digest_at(
+        &self,
+        workspace_id: &str,
+        period: DigestPeriod,
+        now: DateTime<Utc>,
+        cancel_flag: Option<Arc<AtomicBool>>,
+        on_progress: impl Fn(&DigestProgress),
+    ) -> Result<WorkspaceDigest> {
+        let workspace = self
+            .workspace_storage_service
+            .get_workspace(workspace_id)
+            .await?
+            .ok_or_else(|| anyhow!("Workspace not found: {}", workspace_id))?;
+        let (start, end) = period.window(now);
+        let digests_dir = workspace.workspace_dir.join(DIGESTS_DIR);
+        tokio::fs::create_dir_all(&digests_dir).await?;
+        let stem = format!("{}-{}", period.as_str(), start.format("%Y-%m-%d"));
+        let checkpoint_path = digests_dir.join(format!(".{}.checkpoint.json", stem));
+
+        let workspace_sessions: Vec<_> = self
+            .session_repository
+            .list_summaries()
+            .await?
+            .into_iter()
+            .filter(|s| s.workspace_id == workspace_id)
+            .collect();
+        let session_ids: HashSet<&str> = workspace_sessions.iter().map(|s| s.id.as_str()).collect();
+        // Sessions not updated since the period started cannot hold messages inside it
+        let candidates: Vec<_> = workspace_sessions
+            .iter()
+            .filter(|s| parse_time(&s.updated_at).is_none_or(|t| t >= start))
+            .collect();
+
+        let mut checkpoint = load_checkpoint(&checkpoint_path).await;
+        let total = candidates.len();
+        for (index, summary) in candidates.iter().enumerate() {
+            if checkpoint
+                .sessions
+                .iter()
+                .any(|s| s.session_id == summary.id)
+            {
+                continue;
+            }
+            if cancel_flag
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::SeqCst))
+            {
+                return Err(anyhow!(
+                    "Digest cancelled after {} of {} sessions; run it again to resume",
+                    index,
+                    total
+                ));
+            }
+            let Some(session) = self.session_repository.find_by_id(&summary.id).await? else {
+                continue;
+            };
+            let message_count = session
+                .persona_histories
+                .values()
+                .flatten()
+                .filter(|m| m.role != MessageRole::System && in_window(m, start, end))
+                .count();
+            if message_count > 0 {
+                let digest = self
+                    .digest_session(&session, message_count, start, end, &mut checkpoint)
+                    .await;
+                on_progress(&DigestProgress {
+                    workspace_id: workspace_id.to_string(),
+                    processed: index + 1,
+                    total,
+                    session_id: digest.session_id.clone(),
+                    title: digest.title.clone(),
+                    source: digest.source,
+                });
+                checkpoint.sessions.push(digest);
+            }
+            save_checkpoint(&checkpoint_path, &checkpoint).await?;
+        }
+
+        let mut completed_tasks: Vec<DigestTask> = self
+            .task_repository
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|t| {
+                t.status == TaskStatus::Completed && session_ids.contains(t.session_id.as_str())
+            })
+            .filter_map(|t| {
+                let completed_at = t.completed_at.unwrap_or(t.updated_at);
+                let time = parse_time(&completed_at)?;
+                (time >= start && time < end).then_some(DigestTask {
+                    title: t.title,
+                    session_id: t.session_id,
+                    completed_at,
+                })
+            })
+            .collect();
+        completed_tasks.sort_by(|a, b| a.completed_at.cmp(&b.completed_at));
+
+        let mut sessions = checkpoint.sessions;
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.message_count));
+        let markdown_path = digests_dir.join(format!("{}.md", stem));
+        let digest = WorkspaceDigest {
+            workspace_id: workspace_id.to_string(),
+            workspace_name: workspace.name.clone(),
+            period,
+            period_start: start.to_rfc3339(),
+            period_end: end.to_rfc3339(),
+            generated_at: now.to_rfc3339(),
+            sessions,
+            completed_tasks,
+            tokens_used: checkpoint.tokens_used,
+            markdown_path,
+        };
+        tokio::fs::write(&digest.markdown_path, digest.to_markdown()).await?;
+        if let Err(e) = tokio::fs::remove_file(&checkpoint_path).await {
+            tracing::warn!("[WorkspaceDigest] Failed to remove checkpoint: {}", e);
+        }
+        tracing::info!(
+            "[WorkspaceDigest] Saved {} digest of {} ({} sessions, ~{} tokens)",
+            period.as_str(),
+            workspace_id,
+            digest.sessions.len(),
+            digest.tokens_used
+        );
+        Ok(digest)
+    }
+
+    /// Builds the digest of one session, reusing its summary when it is current.
+    async fn digest_session(
+        &self,
+        session: &Session,
+        message_count: usize,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        checkpoint: &mut DigestCheckpoint,
+    ) -> SessionDigest {
+        let in_period: Vec<&ConversationMessage> = session
+            .system_messages
+            .iter()
+            .filter(|m| in_window(m, start, end))
+            .collect();
+        let recorded_decisions: Vec<String> = in_period
+            .iter()
+            .filter_map(|m| m.content.strip_prefix(DECISION_RECORD_PREFIX))
+            .filter_map(|rest| rest.lines().next())
+            .map(|title| title.trim().to_string())
+            .collect();
+        let last_message = session
+            .persona_histories
+            .values()
+            .flatten()
+            .filter(|m| m.role != MessageRole::System && in_window(m, start, end))
+            .filter_map(|m| parse_time(&m.timestamp))
+            .max();
+        let current_summary = in_period
+            .iter()
+            .rev()
+            .filter(|m| parse_time(&m.timestamp) >= last_message)
+            .find_map(|m| reused_summary(&m.content));
+
+        let mut digest = SessionDigest {
+            session_id: session.id.clone(),
+            title: session.title.clone(),
+            message_count,
+            summary: None,
+            key_decisions: recorded_decisions.clone(),
+            open_questions: Vec::new(),
+            source: DigestSource::Skipped,
+            skip_reason: None,
+        };
+        if let Some(summary) = current_summary {
+            digest.summary = Some(summary);
+            digest.source = DigestSource::Reused;
+            return digest;
+        }
+
+        let range = DateRange {
+            start: Some(start),
+            end: Some(end - Duration::nanoseconds(1)),
+        };
+        let request = SessionDigestRequest {
+            transcript: decision_transcript(session, Some(&range)),
+            output_schema: SessionDigestResponse::prompt_schema(),
+        };
+        let prompt = request.to_prompt();
+        let prompt_tokens = estimate_tokens(&prompt);
+        if checkpoint.tokens_used + prompt_tokens > self.token_budget {
+            digest.skip_reason = Some("token budget exhausted".to_string());
+            return digest;
+        }
+        checkpoint.tokens_used += prompt_tokens;
+        match self.agent.execute(prompt.as_str().into()).await {
+            Ok(reply) => {
+                checkpoint.tokens_used += estimate_tokens(&reply);
+                match SessionDigestResponse::parse(&reply) {
+                    Some(response) => {
+                        digest.summary = Some(response.summary);
+                        for decision in response.key_decisions {
+                            if !digest.key_decisions.contains(&decision) {
+                                digest.key_decisions.push(decision);
+                            }
+                        }
+                        digest.open_questions = response.open_questions;
+                        digest.source = DigestSource::Generated;
+                    }
+                    None => digest.skip_reason = Some("the summary was empty".to_string()),
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "[WorkspaceDigest] Failed to summarize session {}: {}",
+                    session.id,
+                    e
+                );
+                digest.skip_reason = Some(format!("summary generation failed ({})", e));
+            }
+        }
+        digest
+    }
+}
+
+/// Text of a stored conversation summary, up to its first subsection.
+fn reused_summary(content: &str) -> Option<String> {
+    let body = content.trim_start().strip_prefix(SUMMARY_HEADING)?;
+    let body = body.split("\n## ").next().unwrap_or(body).trim();
+    (!body.is_empty()).then(|| body.to_string())
+}
+
+fn in_window(message: &ConversationMessage, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+    parse_time(&message.timestamp).is_some_and(|t| t >= start && t < end)
+}
+
+fn parse_time(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Loads a checkpoint, treating a missing or unreadable one as empty.
+async fn load_checkpoint(path: &Path) -> DigestCheckpoint {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("[WorkspaceDigest] Ignoring unreadable checkpoint: {}", e);
+            DigestCheckpoint::default()
+        }),
+        Err(_) => DigestCheckpoint::default(),
+    }
+}
+
+async fn save_checkpoint(path: &Path, checkpoint: &DigestCheckpoint) -> Result<()> {
+    tokio::fs::write(path, serde_json::to_string(checkpoint)?).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_toolkit::agent::dialogue::ExecutionModel;
+    use orcs_core::session::{AppMode, MessageMetadata};
+    use orcs_core::task::Task;
+    use orcs_infrastructure::workspace_storage_service::FileSystemWorkspaceManager;
+    use orcs_infrastructure::{AsyncDirSessionRepository, AsyncDirTaskRepository};
+    use orcs_interaction::testing::ScriptedAgent;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Monday; the weekly window is 2026-10-12 to 2026-10-19
+    const NOW: &str = "2026-10-19T09:00:00Z";
+
+    const GENERATED: &str = r#"{"summary": "Settled the cache design.",
+        "keyDecisions": ["Use an LRU cache"], "openQuestions": ["Which eviction size?"]}"#;
+
+    fn message(role: MessageRole, content: &str, timestamp: &str) -> ConversationMessage {
+        ConversationMessage {
+            role,
+            content: content.to_string(),
+            timestamp: timestamp.to_string(),
+            metadata: MessageMetadata::default(),
+            attachments: vec![],
+        }
+    }
+
+    fn session(
+        id: &str,
+        workspace_id: &str,
+        updated_at: &str,
+        messages: Vec<ConversationMessage>,
+    ) -> Session {
+        Session {
+            id: id.to_string(),
+            title: format!("Session {}", id),
+            created_at: "2026-10-01T00:00:00Z".to_string(),
+            updated_at: updated_at.to_string(),
+            current_persona_id: String::new(),
+            persona_histories: HashMap::from([("mai".to_string(), messages)]),
+            app_mode: AppMode::Idle,
+            workspace_id: workspace_id.to_string(),
+            active_participant_ids: vec![],
+            execution_strategy: ExecutionModel::Broadcast,
+            system_messages: vec![],
+            participants: HashMap::new(),
+            participant_icons: HashMap::new(),
+            participant_colors: HashMap::new(),
+            participant_backends: HashMap::new(),
+            participant_models: HashMap::new(),
+            conversation_mode: Default::default(),
+            talk_style: None,
+            is_favorite: false,
+            is_archived: false,
+            sort_order: None,
+            auto_chat_config: None,
+            is_muted: false,
+            context_mode: Default::default(),
+            sandbox_state: None,
+            last_memory_sync_at: None,
+            is_generating: false,
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: 8_000,
+        }
+    }
+
+    fn completed_task(id: &str, title: &str, session_id: &str, completed_at: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            session_id: session_id.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            status: TaskStatus::Completed,
+            created_at: completed_at.to_string(),
+            updated_at: completed_at.to_string(),
+            completed_at: Some(completed_at.to_string()),
+            steps_executed: 1,
+            steps_skipped: 0,
+            context_keys: 0,
+            error: None,
+            result: None,
+            execution_details: None,
+            strategy: None,
+            journal_log: None,
+            origin_message_timestamp: None,
+            origin_author: None,
+            dry_run: false,
+        }
+    }
+
+    struct Fixture {
+        _dirs: [TempDir; 4],
+        sessions: Arc<AsyncDirSessionRepository>,
+        tasks: Arc<AsyncDirTaskRepository>,
+        workspaces: Arc<FileSystemWorkspaceManager>,
+        workspace_id: String,
+    }
+
+    impl Fixture {
+        async fn new() -> Self {
+            // Each repository treats an existing base directory as its own root
+            let dirs = [(); 4].map(|_| TempDir::new().unwrap());
+            let sessions = Arc::new(
+                AsyncDirSessionRepository::new(Some(dirs[0].path()))
+                    .await
+                    .unwrap(),
+            );
+            let tasks = Arc::new(
+                AsyncDirTaskRepository::new(Some(dirs[1].path()))
+                    .await
+                    .unwrap(),
+            );
+            let workspaces = Arc::new(
+                FileSystemWorkspaceManager::new(Some(dirs[2].path()))
+                    .await
+                    .unwrap(),
+            );
+            let workspace_id = workspaces
+                .get_or_create_workspace(dirs[3].path())
+                .await
+                .unwrap()
+                .id;
+            Self {
+                _dirs: dirs,
+                sessions,
+                tasks,
+                workspaces,
+                workspace_id,
+            }
+        }
+
+        fn service(&self, agent: &ScriptedAgent) -> WorkspaceDigestService {
+            WorkspaceDigestService::new(
+                self.sessions.clone(),
+                self.tasks.clone(),
+                self.workspaces.clone(),
+            )
+            .with_agent(Arc::new(agent.clone()))
+        }
+
+        async fn digest(
+            &self,
+            service: &WorkspaceDigestService,
+            cancel_flag: Option<Arc<AtomicBool>>,
+            on_progress: impl Fn(&DigestProgress),
+        ) -> Result<WorkspaceDigest> {
+            let now = DateTime::parse_from_rfc3339(NOW)
+                .unwrap()
+                .with_timezone(&Utc);
+            service
+                .digest_at(
+                    &self.workspace_id,
+                    DigestPeriod::Weekly,
+                    now,
+                    cancel_flag,
+                    on_progress,
+                )
+                .await
+        }
+    }
+
+    #[test]
+    fn test_periods_end_at_the_last_boundary() {
+        let now = DateTime::parse_from_rfc3339("2026-10-18T15:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let (start, end) = DigestPeriod::Weekly.window(now);
+        assert_eq!(start.to_rfc3339(), "2026-10-05T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2026-10-12T00:00:00+00:00");
+        let (start, end) = DigestPeriod::Daily.window(now);
+        assert_eq!(start.to_rfc3339(), "2026-10-17T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2026-10-18T00:00:00+00:00");
+        assert_eq!(
+            "Weekly".parse::<DigestPeriod>().unwrap(),
+            DigestPeriod::Weekly
+        );
+        assert!("monthly".parse::<DigestPeriod>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_digest_covers_only_in_period_sessions() {
+        let fixture = Fixture::new().await;
+        let ws = fixture.workspace_id.as_str();
+
+        let mut reused = session(
+            "s-reused",
+            ws,
+            "2026-10-14T12:00:00Z",
+            vec![
+                message(
+                    MessageRole::User,
+                    "Plan the release",
+                    "2026-10-14T10:00:00Z",
+                ),
+                message(
+                    MessageRole::Assistant,
+                    "Friday works",
+                    "2026-10-14T10:01:00Z",
+                ),
+            ],
+        );
+        reused.system_messages = vec![
+            message(
+                MessageRole::System,
+                "📋 Decision record: Ship on Friday\nDecision: Friday.",
+                "2026-10-14T10:02:00Z",
+            ),
+            message(
+                MessageRole::System,
+                "# 📝 Conversation Summary\n\nRelease moves to Friday.\n\n## Key Points\n\n- Friday\n",
+                "2026-10-14T10:03:00Z",
+            ),
+        ];
+        let generated = session(
+            "s-generated",
+            ws,
+            "2026-10-20T08:00:00Z",
+            vec![
+                message(MessageRole::User, "Old cache chat", "2026-10-01T10:00:00Z"),
+                message(
+                    MessageRole::User,
+                    "Design the cache",
+                    "2026-10-15T10:00:00Z",
+                ),
+                message(MessageRole::Assistant, "LRU it is", "2026-10-15T10:01:00Z"),
+                message(
+                    MessageRole::User,
+                    "Next week's chat",
+                    "2026-10-19T08:00:00Z",
+                ),
+            ],
+        );
+        let before = session(
+            "s-before",
+            ws,
+            "2026-10-05T12:00:00Z",
+            vec![message(MessageRole::User, "Old", "2026-10-05T10:00:00Z")],
+        );
+        // Renamed during the period, but no messages in it
+        let renamed = session(
+            "s-renamed",
+            ws,
+            "2026-10-13T12:00:00Z",
+            vec![message(MessageRole::User, "Old", "2026-10-05T10:00:00Z")],
+        );
+        let other_workspace = session(
+            "s-other",
+            "ws-other",
+            "2026-10-14T12:00:00Z",
+            vec![message(
+                MessageRole::User,
+                "Elsewhere",
+                "2026-10-14T10:00:00Z",
+            )],
+        );
+        for s in [&reused, &generated, &before, &renamed, &other_workspace] {
+            fixture.sessions.save(s).await.unwrap();
+        }
+        for task in [
+            completed_task(
+                "3a1f6c2e-8b4d-4f7a-9c0e-5d2b7a9e1f31",
+                "Tag the release",
+                "s-reused",
+                "2026-10-16T09:00:00Z",
+            ),
+            completed_task(
+                "3a1f6c2e-8b4d-4f7a-9c0e-5d2b7a9e1f32",
+                "Earlier task",
+                "s-reused",
+                "2026-10-02T09:00:00Z",
+            ),
+            completed_task(
+                "3a1f6c2e-8b4d-4f7a-9c0e-5d2b7a9e1f33",
+                "Other workspace task",
+                "s-other",
+                "2026-10-16T09:00:00Z",
+            ),
+        ] {
+            fixture.tasks.save(&task).await.unwrap();
+        }
+
+        let agent = ScriptedAgent::new().repeat(GENERATED);
+        let progress = Mutex::new(Vec::new());
+        let digest = fixture
+            .digest(&fixture.service(&agent), None, |p| {
+                progress.lock().unwrap().push(p.session_id.clone())
+            })
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = digest
+            .sessions
+            .iter()
+            .map(|s| s.session_id.as_str())
+            .collect();
+        assert_eq!(ids, ["s-generated", "s-reused"]);
+        let mut reported = progress.into_inner().unwrap();
+        reported.sort();
+        assert_eq!(reported, ["s-generated", "s-reused"]);
+
+        let reused = &digest.sessions[1];
+        assert_eq!(reused.source, DigestSource::Reused);
+        assert_eq!(reused.summary.as_deref(), Some("Release moves to Friday."));
+        assert_eq!(reused.key_decisions, ["Ship on Friday"]);
+
+        let generated = &digest.sessions[0];
+        assert_eq!(generated.source, DigestSource::Generated);
+        assert_eq!(generated.message_count, 2);
+        assert_eq!(generated.open_questions, ["Which eviction size?"]);
+        let prompts = agent.received();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("Design the cache"));
+        assert!(!prompts[0].contains("Old cache chat"));
+        assert!(!prompts[0].contains("Next week's chat"));
+        assert!(digest.tokens_used > 0);
+
+        let tasks: Vec<&str> = digest
+            .completed_tasks
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(tasks, ["Tag the release"]);
+
+        let markdown = std::fs::read_to_string(&digest.markdown_path).unwrap();
+        assert!(
+            digest
+                .markdown_path
+                .ends_with("digests/weekly-2026-10-12.md")
+        );
+        assert!(markdown.contains("Use an LRU cache (Session s-generated)"));
+        assert!(markdown.contains("- Tag the release"));
+        assert!(!markdown.contains("Session s-before"));
+        assert!(!markdown.contains("Session s-renamed"));
+        assert!(!markdown.contains("Session s-other"));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_digest_resumes_without_repeating_sessions() {
+        let fixture = Fixture::new().await;
+        let ws = fixture.workspace_id.as_str();
+        for id in ["s-1", "s-2", "s-3"] {
+            let s = session(
+                id,
+                ws,
+                "2026-10-14T12:00:00Z",
+                vec![message(MessageRole::User, id, "2026-10-14T10:00:00Z")],
+            );
+            fixture.sessions.save(&s).await.unwrap();
+        }
+
+        let agent = ScriptedAgent::new().repeat(GENERATED);
+        let service = fixture.service(&agent);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let error = fixture
+            .digest(&service, Some(cancel.clone()), |_| {
+                cancel.store(true, Ordering::SeqCst)
+            })
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("run it again to resume"));
+        assert_eq!(agent.calls(), 1);
+
+        cancel.store(false, Ordering::SeqCst);
+        let digest = fixture
+            .digest(&service, Some(cancel), |_| {})
+            .await
+            .unwrap();
+        assert_eq!(agent.calls(), 3);
+        assert_eq!(digest.sessions.len(), 3);
+        let checkpoints: Vec<_> = std::fs::read_dir(digest.markdown_path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(checkpoints, ["weekly-2026-10-12.md"]);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_over_the_token_budget_are_listed_without_summary() {
+        let fixture = Fixture::new().await;
+        let s = session(
+            "s-1",
+            &fixture.workspace_id,
+            "2026-10-14T12:00:00Z",
+            vec![message(MessageRole::User, "Hello", "2026-10-14T10:00:00Z")],
+        );
+        fixture.sessions.save(&s).await.unwrap();
+
+        let agent = ScriptedAgent::new().repeat(GENERATED);
+        let service = fixture.service(&agent).with_token_budget(10);
+        let digest = fixture.digest(&service, None, |_| {}).await.unwrap();
+
+        assert_eq!(agent.calls(), 0);
+        assert_eq!(digest.sessions[0].source, DigestSource::Skipped);
+        assert_eq!(
+            digest.sessions[0].skip_reason.as_deref(),
+            Some("token budget exhausted")
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use anyhow::{Context, Result, anyhow, bail};
+use clap::ValueEnum;
+use orcs_application::session::SessionFactory;
+use orcs_core::session::AppMode;
+use orcs_infrastructure::AsyncDirPersonaRepository;
+use orcs_infrastructure::user_service::ConfigBasedUserService;
+use orcs_interaction::{DialogueMessage, InteractionManager, InteractionResult};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// How `orcs chat` reports the dialogue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One JSON object per turn or error as it arrives, then a summary line
+    #[default]
+    Lines,
+    /// A single JSON document with every turn, error and a summary, printed at the end
+    Json,
+}
+
+/// Options for `orcs chat`.
+pub struct ChatArgs {
+    pub message: String,
+    pub personas: Vec<String>,
+    pub output: OutputFormat,
+}
+
+/// A reply from a participant.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatTurn {
+    pub author: String,
+    pub content: String,
+}
+
+/// A failed turn, or a message the session answered with instead of running the dialogue.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatError {
+    pub message: String,
+    pub retryable: bool,
+}
+
+/// Totals of a chat run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ChatSummary {
+    pub session_id: String,
+    /// IDs of the personas that took part
+    pub participants: Vec<String>,
+    pub turn_count: usize,
+    pub error_count: usize,
+    pub duration_ms: u64,
+    pub success: bool,
+}
+
+/// Everything a chat run produced, as printed by `--output json`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ChatTranscript {
+    pub turns: Vec<ChatTurn>,
+    pub errors: Vec<ChatError>,
+    pub summary: ChatSummary,
+}
+
+/// A line printed by `--output lines`.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ChatEvent<'a> {
+    Turn(&'a ChatTurn),
+    Error(&'a ChatError),
+    Summary(&'a ChatSummary),
+}
+
+/// Sends one message to a new session and prints the replies to stdout.
+///
+/// Fails after printing if any turn failed, so CI jobs can rely on the exit code.
+pub fn run(args: ChatArgs) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start async runtime")?;
+    let transcript = runtime.block_on(async {
+        let personas = AsyncDirPersonaRepository::new(None)
+            .await
+            .context("Failed to open persona repository")?;
+        let manager =
+            SessionFactory::new(Arc::new(personas), Arc::new(ConfigBasedUserService::new()))
+                .create_interaction_manager(uuid::Uuid::new_v4().to_string());
+        chat(&manager, &args, &mut io::stdout().lock()).await
+    })?;
+
+    if !transcript.summary.success {
+        bail!("{} turn(s) failed", transcript.summary.error_count);
+    }
+    Ok(())
+}
+
+/// Runs the dialogue for `args.message` on `manager`, writing it to `out` in `args.output` format.
+///
+/// If `args.personas` is non-empty, exactly those personas take part.
+pub async fn chat<W: Write>(
+    manager: &InteractionManager,
+    args: &ChatArgs,
+    out: &mut W,
+) -> Result<ChatTranscript> {
+    let started = Instant::now();
+    if !args.personas.is_empty() {
+        select_participants(manager, &args.personas).await?;
+    }
+
+    // The turn callback is `Fn`, so the recorder sits behind a cell
+    let recorder = RefCell::new(Recorder {
+        out,
+        streams: args.output == OutputFormat::Lines,
+        transcript: ChatTranscript::default(),
+        write_result: Ok(()),
+    });
+    let result = manager
+        .handle_input_with_streaming(&AppMode::Idle, &args.message, None, |message| {
+            recorder.borrow_mut().record(message)
+        })
+        .await;
+    let mut recorder = recorder.into_inner();
+    // Set up failures (no personas, dialogue init errors) come back as a plain message
+    if let InteractionResult::NewMessage(message) = result {
+        recorder.push_error(ChatError {
+            message,
+            retryable: false,
+        });
+    }
+
+    let participants = manager.get_active_participants().await.unwrap_or_default();
+    let Recorder {
+        out,
+        streams,
+        mut transcript,
+        write_result,
+    } = recorder;
+    write_result.context("Failed to write chat output")?;
+
+    transcript.summary = ChatSummary {
+        session_id: manager.session_id().to_string(),
+        participants,
+        turn_count: transcript.turns.len(),
+        error_count: transcript.errors.len(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        success: transcript.errors.is_empty(),
+    };
+    if streams {
+        write_line(out, &ChatEvent::Summary(&transcript.summary))
+    } else {
+        serde_json::to_writer_pretty(&mut *out, &transcript)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(out))
+    }
+    .context("Failed to write chat output")?;
+
+    Ok(transcript)
+}
+
+/// Makes `persona_ids` the session's only participants.
+async fn select_participants(manager: &InteractionManager, persona_ids: &[String]) -> Result<()> {
+    let current = manager
+        .get_active_participants()
+        .await
+        .map_err(|e| anyhow!(e))?;
+    for persona_id in current.iter().filter(|id| !persona_ids.contains(id)) {
+        manager
+            .remove_participant(persona_id, false)
+            .await
+            .map_err(|e| anyhow!(e))?;
+    }
+    for persona_id in persona_ids {
+        manager
+            .add_participant(persona_id)
+            .await
+            .map_err(|e| anyhow!(e))?;
+    }
+    Ok(())
+}
+
+/// Collects streamed turns and, for `--output lines`, prints them as they arrive.
+struct Recorder<'a, W> {
+    out: &'a mut W,
+    streams: bool,
+    transcript: ChatTranscript,
+    /// First write error; later lines are dropped once writing failed
+    write_result: io::Result<()>,
+}
+
+impl<W: Write> Recorder<'_, W> {
+    fn record(&mut self, message: &DialogueMessage) {
+        if message.is_thinking() {
+            return;
+        }
+        match &message.failure {
+            Some(failure) => self.push_error(ChatError {
+                message: message.content.clone(),
+                retryable: failure.retryable,
+            }),
+            None => {
+                let turn = ChatTurn {
+                    author: message.author.clone(),
+                    content: message.content.clone(),
+                };
+                self.write(&ChatEvent::Turn(&turn));
+                self.transcript.turns.push(turn);
+            }
+        }
+    }
+
+    fn push_error(&mut self, error: ChatError) {
+        self.write(&ChatEvent::Error(&error));
+        self.transcript.errors.push(error);
+    }
+
+    fn write(&mut self, event: &ChatEvent) {
+        if self.streams && self.write_result.is_ok() {
+            self.write_result = write_line(self.out, event);
+        }
+    }
+}
+
+fn write_line<W: Write>(out: &mut W, event: &ChatEvent) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, event)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cli, Commands};
+    use clap::Parser;
+    use llm_toolkit::agent::AgentError;
+    use orcs_interaction::testing::{
+        MockPersonaRepository, ScriptedAgent, ScriptedAgentFactory, persona, scripted_session,
+    };
+
+    #[tokio::test]
+    async fn test_json_output_is_a_single_document_with_turns_errors_and_summary() {
+        let repository = MockPersonaRepository::new()
+            .with_persona(persona("mai", "Mai"))
+            .with_standby_persona(persona("yui", "Yui"));
+        let agents = ScriptedAgentFactory::new()
+            .with_agent("mai", ScriptedAgent::new().reply("Parser first."))
+            .with_agent("yui", ScriptedAgent::new().reply("Then the lexer."));
+        let manager = scripted_session(repository, agents);
+        let cli = Cli::try_parse_from([
+            "orcs",
+            "chat",
+            "--persona",
+            "mai",
+            "--persona",
+            "yui",
+            "--output",
+            "json",
+            "Where do we start?",
+        ])
+        .unwrap();
+        let Commands::Chat {
+            message,
+            personas,
+            output,
+        } = cli.command
+        else {
+            panic!("expected the chat subcommand");
+        };
+        let args = ChatArgs {
+            message,
+            personas,
+            output,
+        };
+
+        let mut out = Vec::new();
+        chat(&manager, &args, &mut out).await.unwrap();
+
+        let document: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let turns: Vec<(&str, &str)> = document["turns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| {
+                (
+                    t["author"].as_str().unwrap(),
+                    t["content"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            turns,
+            vec![("Mai", "Parser first."), ("Yui", "Then the lexer.")]
+        );
+        assert_eq!(document["errors"], serde_json::json!([]));
+        assert_eq!(document["summary"]["turn_count"], 2);
+        assert_eq!(document["summary"]["success"], true);
+        assert_eq!(
+            document["summary"]["participants"],
+            serde_json::json!(["mai", "yui"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lines_output_streams_failures_and_ends_with_a_summary() {
+        let repository = MockPersonaRepository::new().with_persona(persona("mai", "Mai"));
+        let agents = ScriptedAgentFactory::new().with_agent(
+            "mai",
+            ScriptedAgent::new().fail(AgentError::ExecutionFailed("backend down".to_string())),
+        );
+        let manager = scripted_session(repository, agents);
+        let args = ChatArgs {
+            message: "Status?".to_string(),
+            personas: Vec::new(),
+            output: OutputFormat::Lines,
+        };
+
+        let mut out = Vec::new();
+        let transcript = chat(&manager, &args, &mut out).await.unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let types: Vec<&str> = lines.iter().map(|l| l["type"].as_str().unwrap()).collect();
+        assert_eq!(types, vec!["error", "summary"]);
+        assert!(
+            lines[0]["message"]
+                .as_str()
+                .unwrap()
+                .contains("backend down")
+        );
+        assert_eq!(lines[1]["success"], false);
+        assert!(!transcript.summary.success);
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use orcs_application::{DigestPeriod, DigestSource, WorkspaceDigestService};
+use orcs_infrastructure::workspace_storage_service::FileSystemWorkspaceManager;
+use orcs_infrastructure::{AsyncDirSessionRepository, AsyncDirTaskRepository};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Generates the digest of a workspace and prints where it was saved.
+///
+/// Ctrl-C stops after the current session; running again resumes.
+pub fn run(workspace_id: &str, period: &str) -> Result<()> {
+    let period: DigestPeriod = period.parse()?;
+
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start async runtime")?;
+    let digest = runtime.block_on(async {
+        let sessions = AsyncDirSessionRepository::new(None)
+            .await
+            .context("Failed to open session repository")?;
+        let tasks = AsyncDirTaskRepository::new(None)
+            .await
+            .context("Failed to open task repository")?;
+        let workspaces = FileSystemWorkspaceManager::new(None)
+            .await
+            .context("Failed to open workspace storage")?;
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let on_interrupt = cancel_flag.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                println!("\n⏹️  Stopping after the current session...");
+                on_interrupt.store(true, Ordering::SeqCst);
+            }
+        });
+
+        println!("🗞️  Generating digest for workspace {}...", workspace_id);
+        WorkspaceDigestService::new(Arc::new(sessions), Arc::new(tasks), Arc::new(workspaces))
+            .generate_workspace_digest(workspace_id, period, Some(cancel_flag), |progress| {
+                let note = match progress.source {
+                    DigestSource::Reused => "existing summary",
+                    DigestSource::Generated => "summarized",
+                    DigestSource::Skipped => "no summary",
+                };
+                println!(
+                    "  [{}/{}] {} ({})",
+                    progress.processed, progress.total, progress.title, note
+                );
+            })
+            .await
+    })?;
+
+    println!(
+        "✅ {} active sessions, {} completed tasks (~{} tokens)",
+        digest.sessions.len(),
+        digest.completed_tasks.len(),
+        digest.tokens_used
+    );
+    println!("📄 Saved to {}", digest.markdown_path.display());
+    Ok(())
+}
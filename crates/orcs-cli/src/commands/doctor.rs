@@ -0,0 +1,121 @@
+use anyhow::{Context, Result};
+use orcs_application::{IntegrityReport, IntegrityService};
+use orcs_infrastructure::workspace_storage_service::FileSystemWorkspaceManager;
+use orcs_infrastructure::{AsyncDirSessionRepository, AsyncDirTaskRepository};
+use std::sync::Arc;
+
+/// Scans stored data and prints orphaned items. Nothing is deleted.
+pub fn run() -> Result<()> {
+    println!("🩺 Scanning ORCS data...");
+
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start async runtime")?;
+    let report = runtime.block_on(async {
+        let sessions = AsyncDirSessionRepository::new(None)
+            .await
+            .context("Failed to open session repository")?;
+        let tasks = AsyncDirTaskRepository::new(None)
+            .await
+            .context("Failed to open task repository")?;
+        let workspaces = Arc::new(
+            FileSystemWorkspaceManager::new(None)
+                .await
+                .context("Failed to open workspace storage")?,
+        );
+        let workspaces_root = workspaces.workspaces_root_path().to_path_buf();
+        IntegrityService::new(
+            Arc::new(sessions),
+            Arc::new(tasks),
+            workspaces,
+            workspaces_root,
+        )
+        .scan()
+        .await
+    })?;
+
+    print_report(&report);
+    Ok(())
+}
+
+fn print_report(report: &IntegrityReport) {
+    if report.is_clean() {
+        println!("✅ No orphaned data found");
+        return;
+    }
+
+    if !report.orphaned_sessions.is_empty() {
+        println!(
+            "\n📝 Orphaned sessions ({}):",
+            report.orphaned_sessions.len()
+        );
+        for session in &report.orphaned_sessions {
+            let note = if session.is_placeholder_workspace {
+                " [placeholder workspace, kept]"
+            } else {
+                ""
+            };
+            println!(
+                "  {} \"{}\" (workspace {}){}",
+                session.session_id, session.title, session.workspace_id, note
+            );
+        }
+    }
+
+    if !report.orphaned_tasks.is_empty() {
+        println!("\n🧾 Orphaned tasks ({}):", report.orphaned_tasks.len());
+        for task in &report.orphaned_tasks {
+            println!(
+                "  {} \"{}\" (session {})",
+                task.task_id, task.title, task.session_id
+            );
+        }
+    }
+
+    if !report.unreferenced_uploads.is_empty() {
+        println!(
+            "\n📎 Unreferenced uploads ({}):",
+            report.unreferenced_uploads.len()
+        );
+        for upload in &report.unreferenced_uploads {
+            println!(
+                "  {} ({})",
+                upload.path.display(),
+                format_bytes(upload.bytes)
+            );
+        }
+    }
+
+    if !report.stale_worktrees.is_empty() {
+        println!(
+            "\n🌿 Stale sandbox worktrees ({}):",
+            report.stale_worktrees.len()
+        );
+        for worktree in &report.stale_worktrees {
+            println!(
+                "  {} ({})",
+                worktree.path.display(),
+                format_bytes(worktree.bytes)
+            );
+        }
+    }
+
+    println!(
+        "\n⚠️  {} items found, {} reclaimable. Use the desktop app to clean selected items.",
+        report.total_items,
+        format_bytes(report.reclaimable_bytes)
+    );
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, NaiveDate, Utc};
+use orcs_application::{DateRange, ExportFilter, SessionExportService};
+use orcs_infrastructure::AsyncDirSessionRepository;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Options for `orcs export`.
+pub struct ExportArgs {
+    pub output: PathBuf,
+    pub workspace: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub include_system: bool,
+    pub include_archived: bool,
+    pub redact: bool,
+}
+
+pub fn run(args: ExportArgs) -> Result<()> {
+    let date_range = match (&args.since, &args.until) {
+        (None, None) => None,
+        (since, until) => Some(DateRange {
+            start: since.as_deref().map(|s| parse_date(s, false)).transpose()?,
+            end: until.as_deref().map(|s| parse_date(s, true)).transpose()?,
+        }),
+    };
+
+    let filter = ExportFilter {
+        workspace_id: args.workspace,
+        date_range,
+        include_system: args.include_system,
+        include_archived: args.include_archived,
+    };
+
+    println!("📦 Exporting sessions to {}...", args.output.display());
+
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start async runtime")?;
+    let summary = runtime.block_on(async {
+        let repository = AsyncDirSessionRepository::new(None)
+            .await
+            .context("Failed to open session repository")?;
+        SessionExportService::new(Arc::new(repository))
+            .with_redaction(args.redact)
+            .export_sessions_jsonl(&filter, &args.output)
+            .await
+    })?;
+
+    println!(
+        "✅ Exported {} messages from {} sessions",
+        summary.messages_exported, summary.sessions_exported
+    );
+
+    Ok(())
+}
+
+/// Parses an RFC3339 timestamp or a `YYYY-MM-DD` date.
+///
+/// Plain dates cover the whole day: start of day for `since`, end of day for `until`.
+fn parse_date(value: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| anyhow!("Invalid date '{}': use YYYY-MM-DD or RFC3339", value))?;
+    let time = if end_of_day {
+        date.and_hms_opt(23, 59, 59)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.expect("valid time").and_utc())
+}
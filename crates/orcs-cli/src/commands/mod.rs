@@ -1,5 +1,9 @@
 pub mod build;
+pub mod chat;
 pub mod dev;
+pub mod digest;
+pub mod doctor;
+pub mod export;
 pub mod schema;
 pub mod utils;
 pub mod version;
@@ -5,7 +5,7 @@ use orcs_core::schema::{
 };
 use orcs_core::session::{
     AppMode, AutoChatConfig, ConversationMessage, ConversationMode, ErrorSeverity, MessageMetadata,
-    MessageRole, Plan, SandboxState, StopCondition, SystemEventType,
+    MessageRole, Plan, SandboxState, SourceKind, SourceRef, StopCondition, SystemEventType,
 };
 use orcs_core::state::model::{AppState, OpenTab};
 use orcs_core::workspace::model::{
@@ -54,6 +54,8 @@ pub fn generate() -> Result<()> {
         ("MessageRole", MessageRole::to_ts()),
         ("SystemEventType", SystemEventType::to_ts()),
         ("ErrorSeverity", ErrorSeverity::to_ts()),
+        ("SourceKind", SourceKind::to_ts()),
+        ("SourceRef", SourceRef::to_ts()),
         ("MessageMetadata", MessageMetadata::to_ts()),
         ("ConversationMessage", ConversationMessage::to_ts()),
         // App Mode types
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 mod commands;
 
@@ -27,6 +28,52 @@ enum Commands {
         #[command(subcommand)]
         action: VersionAction,
     },
+    /// Export sessions as JSONL (one JSON object per message)
+    Export {
+        /// Destination file
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Only export sessions from this workspace ID
+        #[arg(long)]
+        workspace: Option<String>,
+        /// Only export messages on or after this date (YYYY-MM-DD or RFC3339)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only export messages on or before this date (YYYY-MM-DD or RFC3339)
+        #[arg(long)]
+        until: Option<String>,
+        /// Include system messages
+        #[arg(long)]
+        include_system: bool,
+        /// Include archived sessions
+        #[arg(long)]
+        include_archived: bool,
+        /// Redact API keys, tokens and email addresses
+        #[arg(long)]
+        redact: bool,
+    },
+    /// Send one message to a new session and print the participants' replies
+    Chat {
+        /// Message to send
+        message: String,
+        /// Persona ID to take part instead of the default participants (repeatable)
+        #[arg(long = "persona")]
+        personas: Vec<String>,
+        /// lines: one JSON object per turn as it arrives; json: one document at the end
+        #[arg(long, value_enum, default_value_t)]
+        output: commands::chat::OutputFormat,
+    },
+    /// Report orphaned sessions, tasks, uploads and sandbox worktrees
+    Doctor,
+    /// Summarize a workspace's sessions over the last full day or week
+    Digest {
+        /// Workspace ID
+        #[arg(long)]
+        workspace: String,
+        /// daily (yesterday) or weekly (last Monday-to-Monday week), in UTC
+        #[arg(long, default_value = "weekly")]
+        period: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -59,6 +106,34 @@ fn main() -> Result<()> {
             VersionAction::Bump { version } => commands::version::bump(&version)?,
             VersionAction::Show => commands::version::show()?,
         },
+        Commands::Export {
+            output,
+            workspace,
+            since,
+            until,
+            include_system,
+            include_archived,
+            redact,
+        } => commands::export::run(commands::export::ExportArgs {
+            output,
+            workspace,
+            since,
+            until,
+            include_system,
+            include_archived,
+            redact,
+        })?,
+        Commands::Chat {
+            message,
+            personas,
+            output,
+        } => commands::chat::run(commands::chat::ChatArgs {
+            message,
+            personas,
+            output,
+        })?,
+        Commands::Doctor => commands::doctor::run()?,
+        Commands::Digest { workspace, period } => commands::digest::run(&workspace, &period)?,
     }
 
     Ok(())
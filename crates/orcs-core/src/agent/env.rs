@@ -31,6 +31,7 @@ use std::path::{Path, PathBuf};
 /// let settings = EnvSettings {
 ///     additional_paths: vec!["/custom/bin".to_string()],
 ///     auto_detect_tool_managers: true,
+///     ..Default::default()
 /// };
 /// let enhanced_path = build_enhanced_path(&workspace, Some(&settings));
 /// assert!(!enhanced_path.is_empty());
@@ -287,6 +288,7 @@ mod tests {
         let settings = EnvSettings {
             additional_paths: vec!["/custom/tool/bin".to_string(), "/opt/myapp/bin".to_string()],
             auto_detect_tool_managers: false, // Disable auto-detect for test stability
+            ..Default::default()
         };
         let path = build_enhanced_path(&workspace, Some(&settings));
 
@@ -0,0 +1,19 @@
+//! Source of the current time, replaceable in tests.
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time (replaceable in tests).
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock backed by the system time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
@@ -2,9 +2,12 @@
 //!
 //! Contains domain models for various configuration structures.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use version_migrate::Queryable;
 
+use crate::persona::PersonaBackend;
 use crate::user::UserProfile;
 
 // Re-export from persona module for backward compatibility
@@ -126,7 +129,20 @@ pub struct KaibaConfig {
 ///     "/custom/tools/bin",
 ///     "/opt/my-cli/bin"
 /// ]
+/// https_proxy = "http://proxy.example.com:8080"
+/// environment_description = "Acme Assistant multi-agent workspace"
+/// max_auto_chat_iterations = 50
+/// max_participants = 8
+/// session_idle_timeout_secs = 1800
+/// cli_heartbeat_interval_secs = 10
+/// default_persona_backend = "claude_cli"
 /// ```
+///
+/// # Proxy Precedence
+///
+/// When `https_proxy` or `http_proxy` is set here, API backends (Claude/Gemini/OpenAI)
+/// use these values and ignore the system `HTTPS_PROXY`/`HTTP_PROXY` variables.
+/// When neither is set, the system environment variables are honored as usual.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnvSettings {
     /// Additional PATH directories to prepend to the enhanced PATH.
@@ -151,6 +167,72 @@ pub struct EnvSettings {
     /// Default: `true`
     #[serde(default = "default_auto_detect_tool_managers")]
     pub auto_detect_tool_managers: bool,
+
+    /// Proxy URL for HTTPS requests made by API backends.
+    ///
+    /// Takes precedence over the system `HTTPS_PROXY` variable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+
+    /// Proxy URL for plain HTTP requests made by API backends.
+    ///
+    /// Takes precedence over the system `HTTP_PROXY` variable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_proxy: Option<String>,
+
+    /// Environment description given to agents at the start of every dialogue.
+    ///
+    /// Lets a white-labeled build name its own product. Defaults to the ORCS
+    /// description when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment_description: Option<String>,
+
+    /// Hard cap on AutoChat `max_iterations`.
+    ///
+    /// Larger values requested for a session are clamped to this cap, so a typo
+    /// cannot start a practically endless (and costly) AutoChat run.
+    ///
+    /// Default: `50`
+    #[serde(default = "default_max_auto_chat_iterations")]
+    pub max_auto_chat_iterations: i32,
+
+    /// Maximum number of participants in a session.
+    ///
+    /// Each participant answers every broadcast round, so adding personas
+    /// beyond this cap is rejected to keep replies focused and costs bounded.
+    ///
+    /// Default: `8`
+    #[serde(default = "default_max_participants")]
+    pub max_participants: usize,
+
+    /// Seconds a loaded session may stay unused before it is saved and unloaded.
+    ///
+    /// Bounds memory when many sessions were opened; an unloaded session is
+    /// loaded again when next opened. The active session is never unloaded.
+    /// `0` keeps sessions loaded until the app exits.
+    ///
+    /// Default: `1800`
+    #[serde(default = "default_session_idle_timeout_secs")]
+    pub session_idle_timeout_secs: u64,
+
+    /// Seconds between progress heartbeats of a running CLI turn.
+    ///
+    /// CLI backends print nothing until their process exits, so the UI is
+    /// told the turn is still working at this interval. API backends stream
+    /// and never get heartbeats. `0` disables heartbeats.
+    ///
+    /// Default: `10`
+    #[serde(default = "default_cli_heartbeat_interval_secs")]
+    pub cli_heartbeat_interval_secs: u64,
+
+    /// Backend of personas created without one.
+    ///
+    /// Unknown backend identifiers are rejected when the config is loaded,
+    /// falling back to the default.
+    ///
+    /// Default: `claude_cli`
+    #[serde(default)]
+    pub default_persona_backend: PersonaBackend,
 }
 
 // ============================================================================
@@ -273,11 +355,47 @@ fn default_auto_detect_tool_managers() -> bool {
     true
 }
 
+/// Default hard cap on AutoChat iterations.
+pub const DEFAULT_MAX_AUTO_CHAT_ITERATIONS: i32 = 50;
+
+fn default_max_auto_chat_iterations() -> i32 {
+    DEFAULT_MAX_AUTO_CHAT_ITERATIONS
+}
+
+/// Default for [`EnvSettings::max_participants`].
+pub const DEFAULT_MAX_PARTICIPANTS: usize = 8;
+
+fn default_max_participants() -> usize {
+    DEFAULT_MAX_PARTICIPANTS
+}
+
+/// Default for [`EnvSettings::session_idle_timeout_secs`].
+pub const DEFAULT_SESSION_IDLE_TIMEOUT_SECS: u64 = 1800;
+
+fn default_session_idle_timeout_secs() -> u64 {
+    DEFAULT_SESSION_IDLE_TIMEOUT_SECS
+}
+
+/// Default for [`EnvSettings::cli_heartbeat_interval_secs`].
+pub const DEFAULT_CLI_HEARTBEAT_INTERVAL_SECS: u64 = 10;
+
+fn default_cli_heartbeat_interval_secs() -> u64 {
+    DEFAULT_CLI_HEARTBEAT_INTERVAL_SECS
+}
+
 impl Default for EnvSettings {
     fn default() -> Self {
         Self {
             additional_paths: Vec::new(),
             auto_detect_tool_managers: true,
+            https_proxy: None,
+            http_proxy: None,
+            environment_description: None,
+            max_auto_chat_iterations: DEFAULT_MAX_AUTO_CHAT_ITERATIONS,
+            max_participants: DEFAULT_MAX_PARTICIPANTS,
+            session_idle_timeout_secs: DEFAULT_SESSION_IDLE_TIMEOUT_SECS,
+            cli_heartbeat_interval_secs: DEFAULT_CLI_HEARTBEAT_INTERVAL_SECS,
+            default_persona_backend: PersonaBackend::default(),
         }
     }
 }
@@ -385,6 +503,68 @@ pub struct TerminalSettings {
     pub custom_app: Option<String>,
 }
 
+// ============================================================================
+// Shell policy configuration models
+// ============================================================================
+
+/// Shell execution policy for agent-triggered commands.
+///
+/// Commands matching any of `destructive_patterns` are not executed directly when
+/// requested by an agent. Instead they are queued as pending actions that the user
+/// must approve. User-typed commands are never gated.
+///
+/// # Example (config.toml)
+///
+/// ```toml
+/// [shell_policy]
+/// destructive_patterns = ['\brm\s', 'git\s+push\s+.*--force']
+/// pending_action_ttl_secs = 300
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellPolicy {
+    /// Regex patterns identifying destructive shell commands.
+    ///
+    /// Invalid patterns are ignored.
+    ///
+    /// Default: `rm`, forced git pushes, hard resets and common DB migration commands
+    #[serde(default = "default_destructive_patterns")]
+    pub destructive_patterns: Vec<String>,
+
+    /// Seconds a pending action waits for approval before it expires.
+    ///
+    /// Default: `300`
+    #[serde(default = "default_pending_action_ttl_secs")]
+    pub pending_action_ttl_secs: u64,
+}
+
+/// Returns the default destructive command patterns.
+pub fn default_destructive_patterns() -> Vec<String> {
+    [
+        r"(^|[\s;&|(])rm\s",
+        r"git\s+push\s+.*(--force|-f\b)",
+        r"git\s+reset\s+--hard",
+        r"git\s+clean\s+-[a-zA-Z]*f",
+        r"(?i)\bdrop\s+(table|database)\b",
+        r"\b(sqlx|diesel|sea-orm-cli|refinery|rails|rake|manage\.py|alembic|flyway|liquibase|prisma|knex|sequelize)\b[^;&|]*\b(migrate|migration|upgrade|downgrade)\b",
+    ]
+    .iter()
+    .map(|p| p.to_string())
+    .collect()
+}
+
+fn default_pending_action_ttl_secs() -> u64 {
+    300
+}
+
+impl Default for ShellPolicy {
+    fn default() -> Self {
+        Self {
+            destructive_patterns: default_destructive_patterns(),
+            pending_action_ttl_secs: default_pending_action_ttl_secs(),
+        }
+    }
+}
+
 // ============================================================================
 // Root configuration model (Domain layer)
 // ============================================================================
@@ -446,6 +626,17 @@ pub struct RootConfig {
     /// Terminal settings for workspace terminal launch.
     #[serde(default)]
     pub terminal_settings: TerminalSettings,
+    /// Shell execution policy for agent-triggered commands.
+    #[serde(default)]
+    pub shell_policy: ShellPolicy,
+    /// URL of a JSON persona index shared by a team.
+    /// When set, its personas are listed read-only alongside local ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_persona_url: Option<String>,
+    /// App-level default model per backend.
+    /// Used for personas that don't set their own model.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub default_models: HashMap<PersonaBackend, String>,
 }
 
 impl Queryable for RootConfig {
@@ -0,0 +1,24 @@
+//! Conversation scripts module.
+//!
+//! Conversation scripts are structured scaffolds for recurring meeting formats
+//! (retros, design reviews, ...). Unlike session templates, which only carry
+//! settings, a script drives the conversation itself: it posts framing System
+//! messages, runs targeted rounds and pauses for the user.
+//!
+//! # Module Structure
+//!
+//! - `model`: Script, step and run-progress models, plus the built-in scripts
+//! - `repository`: Repository trait for conversation script persistence
+//!
+//! # Usage
+//!
+//! ```ignore
+//! use orcs_core::conversation_script::{ConversationScript, ScriptStep, get_default_scripts};
+//! ```
+
+pub mod model;
+pub mod repository;
+
+// Re-export public API
+pub use model::{ConversationScript, ScriptRun, ScriptStep, get_default_scripts};
+pub use repository::ConversationScriptRepository;
@@ -0,0 +1,267 @@
+//! Conversation script models and built-in scripts.
+//!
+//! A conversation script scaffolds a recurring meeting format such as a weekly
+//! retro: it posts framing System messages ("Part 1: what went well"), runs a
+//! round per section with the chosen participants, and pauses for the user
+//! between sections.
+
+use crate::dialogue::PresetSource;
+use crate::error::{OrcsError, Result};
+use serde::{Deserialize, Serialize};
+use version_migrate::DeriveQueryable as Queryable;
+
+/// One step of a conversation script.
+///
+/// # Serialization Format
+///
+/// Internally tagged by `type` (`post_system_message`, `run_round`, `pause`),
+/// so steps read naturally in TOML:
+///
+/// ```toml
+/// [[steps]]
+/// type = "run_round"
+/// participants = ["Mai"]
+/// prompt = "What went well this week?"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScriptStep {
+    /// Posts a System message framing the next part of the conversation
+    PostSystemMessage { content: String },
+    /// Runs one round in which each listed participant answers `prompt` once
+    RunRound {
+        /// Persona IDs or names of the participants to answer (empty = all active participants)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        participants: Vec<String>,
+        prompt: String,
+    },
+    /// Stops the script until the user advances it
+    Pause {
+        /// Posted as a System message while the script waits
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+}
+
+/// A conversation script: an ordered list of steps run against a session.
+///
+/// # JSON Serialization Format
+///
+/// This domain model uses `#[serde(rename_all = "camelCase")]` for Tauri IPC communication.
+/// Scripts are stored in `~/.orcs/conversation_scripts/*.toml` via a versioned DTO.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Queryable)]
+#[serde(rename_all = "camelCase")]
+#[queryable(entity = "conversation_script")]
+pub struct ConversationScript {
+    /// Unique identifier (UUID format, `script-` prefix for system scripts)
+    pub id: String,
+
+    /// Display name of the script (e.g., "Weekly Retro")
+    pub name: String,
+
+    /// Visual icon/emoji representing this script
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    /// Description of the meeting format this script runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Steps executed in order
+    pub steps: Vec<ScriptStep>,
+
+    /// Source of the script (System or User)
+    #[serde(default)]
+    pub source: PresetSource,
+}
+
+impl ConversationScript {
+    /// Checks that the script can be run.
+    ///
+    /// # Errors
+    ///
+    /// Returns a config error if the script has no steps, or a step has an
+    /// empty message or prompt.
+    pub fn validate(&self) -> Result<()> {
+        if self.steps.is_empty() {
+            return Err(OrcsError::config(format!(
+                "Conversation script '{}' has no steps",
+                self.name
+            )));
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            let empty = match step {
+                ScriptStep::PostSystemMessage { content } => content.trim().is_empty(),
+                ScriptStep::RunRound { prompt, .. } => prompt.trim().is_empty(),
+                ScriptStep::Pause { .. } => false,
+            };
+            if empty {
+                return Err(OrcsError::config(format!(
+                    "Step {} of conversation script '{}' is empty",
+                    index + 1,
+                    self.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Progress of a conversation script running in a session.
+///
+/// Persisted with the session, so a paused or interrupted script resumes
+/// after a restart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptRun {
+    /// ID of the running script
+    pub script_id: String,
+    /// Name of the running script, for display
+    pub script_name: String,
+    /// Index of the next step to execute
+    pub next_step: usize,
+    /// Number of steps of the script
+    pub total_steps: usize,
+    /// Whether the script waits at a Pause step; false if it was interrupted
+    /// (cancelled, muted or failed) and resumes with the interrupted step
+    pub paused: bool,
+}
+
+impl ScriptRun {
+    /// Starts a run of `script` at its first step.
+    pub fn start(script: &ConversationScript) -> Self {
+        Self {
+            script_id: script.id.clone(),
+            script_name: script.name.clone(),
+            next_step: 0,
+            total_steps: script.steps.len(),
+            paused: false,
+        }
+    }
+}
+
+fn system_message(content: &str) -> ScriptStep {
+    ScriptStep::PostSystemMessage {
+        content: content.to_string(),
+    }
+}
+
+fn round(prompt: &str) -> ScriptStep {
+    ScriptStep::RunRound {
+        participants: vec![],
+        prompt: prompt.to_string(),
+    }
+}
+
+fn pause(message: &str) -> ScriptStep {
+    ScriptStep::Pause {
+        message: Some(message.to_string()),
+    }
+}
+
+/// Returns the system-defined conversation scripts.
+///
+/// - Weekly retro (went well / could improve / action items)
+/// - Design review (proposal / risks / decision)
+///
+/// Their rounds address all active participants, since system scripts cannot
+/// know the user's personas.
+pub fn get_default_scripts() -> Vec<ConversationScript> {
+    vec![
+        ConversationScript {
+            id: "script-retro".to_string(),
+            name: "Weekly Retro".to_string(),
+            icon: Some("🔁".to_string()),
+            description: Some(
+                "Went well, could improve, action items: a round per part with a pause in between"
+                    .to_string(),
+            ),
+            steps: vec![
+                system_message("Part 1: What went well"),
+                round("Share what went well since the last retro, in a few bullet points."),
+                pause("Add your own points on what went well, then continue the retro."),
+                system_message("Part 2: What could be improved"),
+                round("Share what could be improved, and why it mattered."),
+                pause("Add your own points on what could be improved, then continue the retro."),
+                system_message("Part 3: Action items"),
+                round(
+                    "Propose at most three concrete action items, each with an owner, \
+                     based on the discussion so far.",
+                ),
+            ],
+            source: PresetSource::System,
+        },
+        ConversationScript {
+            id: "script-design-review".to_string(),
+            name: "Design Review".to_string(),
+            icon: Some("📐".to_string()),
+            description: Some(
+                "Proposal, risks and alternatives, decision: review a design step by step"
+                    .to_string(),
+            ),
+            steps: vec![
+                system_message("Design review: describe the design under review"),
+                pause("Post the design (or a link to it), then continue the review."),
+                system_message("Part 1: Understanding the proposal"),
+                round("Summarize the proposal in your own words and ask clarifying questions."),
+                pause("Answer the open questions, then continue the review."),
+                system_message("Part 2: Risks and alternatives"),
+                round("Name the main risks of this design and any alternative worth considering."),
+                system_message("Part 3: Decision"),
+                round(
+                    "Give your recommendation (approve, approve with changes, or rework) \
+                     with a one-sentence rationale.",
+                ),
+            ],
+            source: PresetSource::System,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_scripts_are_valid_system_scripts() {
+        let scripts = get_default_scripts();
+        assert_eq!(scripts.len(), 2);
+        for script in &scripts {
+            assert_eq!(script.source, PresetSource::System);
+            assert!(script.id.starts_with("script-"));
+            script.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_scripts_and_steps() {
+        let mut script = ConversationScript {
+            id: "s".to_string(),
+            name: "Empty".to_string(),
+            icon: None,
+            description: None,
+            steps: vec![],
+            source: PresetSource::User,
+        };
+        assert!(script.validate().is_err());
+
+        script.steps = vec![system_message("Intro"), round("  ")];
+        assert!(script.validate().is_err());
+
+        script.steps = vec![system_message("Intro"), ScriptStep::Pause { message: None }];
+        assert!(script.validate().is_ok());
+    }
+
+    #[test]
+    fn test_steps_are_tagged_by_type() {
+        let step: ScriptStep =
+            serde_json::from_str(r#"{"type":"run_round","prompt":"Go"}"#).expect("deserialize");
+        assert_eq!(
+            step,
+            ScriptStep::RunRound {
+                participants: vec![],
+                prompt: "Go".to_string(),
+            }
+        );
+    }
+}
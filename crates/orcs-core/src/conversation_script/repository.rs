@@ -0,0 +1,36 @@
+//! Conversation script repository trait.
+//!
+//! Defines the interface for conversation script persistence operations.
+
+use super::model::ConversationScript;
+use crate::error::Result;
+
+/// An abstract repository for managing conversation script persistence.
+///
+/// Implementations merge the read-only system scripts from
+/// [`super::get_default_scripts`] with user-created scripts.
+#[async_trait::async_trait]
+pub trait ConversationScriptRepository: Send + Sync {
+    /// Finds a conversation script by its ID.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(ConversationScript))`: Script found
+    /// - `Ok(None)`: Script not found
+    /// - `Err(OrcsError)`: Error occurred during retrieval
+    async fn find_by_id(&self, script_id: &str) -> Result<Option<ConversationScript>>;
+
+    /// Saves a user conversation script.
+    ///
+    /// Implementations should return an error for System scripts and for
+    /// scripts failing [`ConversationScript::validate`].
+    async fn save(&self, script: &ConversationScript) -> Result<()>;
+
+    /// Deletes a user conversation script (succeeds if it didn't exist).
+    ///
+    /// Implementations should return an error for System scripts.
+    async fn delete(&self, script_id: &str) -> Result<()>;
+
+    /// Retrieves all conversation scripts, system scripts first.
+    async fn get_all(&self) -> Result<Vec<ConversationScript>>;
+}
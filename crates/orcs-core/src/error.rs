@@ -47,6 +47,14 @@ pub enum OrcsError {
     #[error("Task execution error: {0}")]
     Execution(String),
 
+    /// Mutation attempted on a session opened read-only
+    #[error("Session '{session_id}' is open read-only")]
+    ReadOnly { session_id: String },
+
+    /// A network-using backend or service refused to run (e.g. in offline mode)
+    #[error("{backend} is unavailable: {reason}")]
+    BackendUnavailable { backend: String, reason: String },
+
     /// Internal error (should not happen in normal operation)
     #[error("Internal error: {0}")]
     Internal(String),
@@ -91,6 +99,21 @@ impl OrcsError {
         Self::Internal(message.into())
     }
 
+    /// Creates a ReadOnly error
+    pub fn read_only(session_id: impl Into<String>) -> Self {
+        Self::ReadOnly {
+            session_id: session_id.into(),
+        }
+    }
+
+    /// Creates a BackendUnavailable error
+    pub fn backend_unavailable(backend: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::BackendUnavailable {
+            backend: backend.into(),
+            reason: reason.into(),
+        }
+    }
+
     /// Creates a Migration error
     pub fn migration(message: impl Into<String>) -> Self {
         Self::Migration(message.into())
@@ -115,6 +138,16 @@ impl OrcsError {
         matches!(self, Self::Serialization { .. })
     }
 
+    /// Check if this is a read-only session error
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, Self::ReadOnly { .. })
+    }
+
+    /// Check if this is a backend unavailable error
+    pub fn is_backend_unavailable(&self) -> bool {
+        matches!(self, Self::BackendUnavailable { .. })
+    }
+
     /// Check if this is a config error
     pub fn is_config(&self) -> bool {
         matches!(self, Self::Config(_))
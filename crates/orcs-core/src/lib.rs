@@ -1,8 +1,12 @@
 pub mod agent;
+pub mod clock;
 pub mod config;
+pub mod conversation_script;
 pub mod dialogue;
 pub mod error;
 pub mod memory;
+pub mod offline;
+pub mod pending_action;
 pub mod persona;
 pub mod quick_action;
 pub mod repository;
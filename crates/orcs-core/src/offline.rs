@@ -0,0 +1,71 @@
+//! Offline mode: an app-wide switch that blocks every network-using backend.
+//!
+//! While it is on, API persona backends refuse to run (personas with a local
+//! `fallback_backend` switch to it), web clipping and memory search are
+//! refused, memory sync pauses, and utility agents use their non-LLM paths.
+//! CLI backends keep working. The switch is shared by cloning, so turning it
+//! off resumes everything without a restart.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::{OrcsError, Result};
+
+/// Reason reported by everything refused while offline mode is on.
+pub const OFFLINE_REASON: &str = "offline mode is on";
+
+/// Shared handle to the offline mode switch. Clones observe the same state.
+#[derive(Debug, Clone, Default)]
+pub struct OfflineMode {
+    enabled: Arc<AtomicBool>,
+}
+
+impl OfflineMode {
+    /// Creates a switch that starts in the given state.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+        }
+    }
+
+    /// Returns whether offline mode is on.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Turns offline mode on or off, returning the previous state.
+    pub fn set(&self, enabled: bool) -> bool {
+        self.enabled.swap(enabled, Ordering::SeqCst)
+    }
+
+    /// Fails with [`OrcsError::BackendUnavailable`] for `backend` while offline mode is on.
+    pub fn ensure_online(&self, backend: &str) -> Result<()> {
+        if self.is_enabled() {
+            return Err(OrcsError::backend_unavailable(backend, OFFLINE_REASON));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_the_switch() {
+        let mode = OfflineMode::default();
+        let shared = mode.clone();
+        assert!(mode.ensure_online("Claude API").is_ok());
+
+        assert!(!shared.set(true));
+        let err = mode.ensure_online("Claude API").unwrap_err();
+        assert!(err.is_backend_unavailable());
+        assert_eq!(
+            err.to_string(),
+            "Claude API is unavailable: offline mode is on"
+        );
+
+        assert!(shared.set(false));
+        assert!(!mode.is_enabled());
+    }
+}
@@ -0,0 +1,146 @@
+//! Pattern-based detection of destructive shell commands.
+
+use regex::Regex;
+
+use crate::config::ShellPolicy;
+use crate::slash_command::{CommandType, SlashCommand};
+
+use super::CommandSource;
+
+/// Detects destructive shell commands using the regex list from [`ShellPolicy`].
+#[derive(Debug, Clone, Default)]
+pub struct DestructiveCommandDetector {
+    patterns: Vec<Regex>,
+}
+
+impl DestructiveCommandDetector {
+    /// Builds a detector from the given patterns, skipping invalid ones.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let patterns = patterns
+            .iter()
+            .filter_map(|p| Regex::new(p.as_ref()).ok())
+            .collect();
+        Self { patterns }
+    }
+
+    /// Builds a detector from a shell policy.
+    pub fn from_policy(policy: &ShellPolicy) -> Self {
+        Self::new(&policy.destructive_patterns)
+    }
+
+    /// Returns true if the command matches any destructive pattern.
+    pub fn is_destructive(&self, command: &str) -> bool {
+        self.patterns.iter().any(|re| re.is_match(command))
+    }
+
+    /// Decides whether an execution must wait for user confirmation.
+    ///
+    /// User-initiated executions always bypass the gate. For agent-initiated ones,
+    /// a slash command flagged with `requires_confirmation` is gated, and shell
+    /// commands (raw or from a shell slash command) are gated when the rendered
+    /// command matches a destructive pattern.
+    pub fn requires_confirmation(
+        &self,
+        source: CommandSource,
+        command: Option<&SlashCommand>,
+        rendered_command: &str,
+    ) -> bool {
+        if source == CommandSource::User {
+            return false;
+        }
+
+        match command {
+            Some(cmd) if cmd.requires_confirmation => true,
+            Some(cmd) if cmd.command_type != CommandType::Shell => false,
+            _ => self.is_destructive(rendered_command),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_detector() -> DestructiveCommandDetector {
+        DestructiveCommandDetector::from_policy(&ShellPolicy::default())
+    }
+
+    #[test]
+    fn test_default_patterns_detect_destructive_commands() {
+        let detector = default_detector();
+
+        assert!(detector.is_destructive("rm -rf target"));
+        assert!(detector.is_destructive("cd build && rm file.txt"));
+        assert!(detector.is_destructive("git push origin main --force"));
+        assert!(detector.is_destructive("git push -f origin main"));
+        assert!(detector.is_destructive("git reset --hard HEAD~1"));
+        assert!(detector.is_destructive("sqlx migrate run"));
+        assert!(detector.is_destructive("python manage.py migrate"));
+        assert!(detector.is_destructive("bin/rails db:migrate"));
+        assert!(detector.is_destructive("cd api && npx prisma migrate deploy"));
+        assert!(detector.is_destructive("alembic upgrade head"));
+        assert!(detector.is_destructive("psql -c 'DROP TABLE users'"));
+    }
+
+    #[test]
+    fn test_default_patterns_allow_safe_commands() {
+        let detector = default_detector();
+
+        assert!(!detector.is_destructive("ls -la"));
+        assert!(!detector.is_destructive("git status"));
+        assert!(!detector.is_destructive("git push origin main"));
+        assert!(!detector.is_destructive("cargo fmt"));
+        assert!(!detector.is_destructive("cat migration.md"));
+        assert!(!detector.is_destructive("grep -rn migrate src"));
+        assert!(!detector.is_destructive("ls migrations && cat README.md"));
+    }
+
+    #[test]
+    fn test_invalid_patterns_are_skipped() {
+        let detector = DestructiveCommandDetector::new(&["(unclosed", "shutdown"]);
+
+        assert!(detector.is_destructive("shutdown now"));
+        assert!(!detector.is_destructive("(unclosed"));
+    }
+
+    #[test]
+    fn test_user_source_bypasses_gate() {
+        let detector = default_detector();
+        let mut cmd = SlashCommand::new_prompt(
+            "deploy".to_string(),
+            "🚀".to_string(),
+            "Deploy".to_string(),
+            "Deploy now".to_string(),
+        );
+        cmd.requires_confirmation = true;
+
+        assert!(!detector.requires_confirmation(CommandSource::User, Some(&cmd), "Deploy now"));
+        assert!(!detector.requires_confirmation(CommandSource::User, None, "rm -rf /"));
+    }
+
+    #[test]
+    fn test_agent_source_is_gated() {
+        let detector = default_detector();
+        let mut prompt = SlashCommand::new_prompt(
+            "deploy".to_string(),
+            "🚀".to_string(),
+            "Deploy".to_string(),
+            "Deploy now".to_string(),
+        );
+        assert!(!detector.requires_confirmation(CommandSource::Agent, Some(&prompt), "rm -rf /"));
+
+        prompt.requires_confirmation = true;
+        assert!(detector.requires_confirmation(CommandSource::Agent, Some(&prompt), "Deploy now"));
+
+        let shell = SlashCommand::new_shell(
+            "clean".to_string(),
+            "🧹".to_string(),
+            "Clean".to_string(),
+            "rm -rf {args}".to_string(),
+            None,
+        );
+        assert!(detector.requires_confirmation(CommandSource::Agent, Some(&shell), "rm -rf dist"));
+        assert!(detector.requires_confirmation(CommandSource::Agent, None, "rm -rf dist"));
+        assert!(!detector.requires_confirmation(CommandSource::Agent, None, "ls"));
+    }
+}
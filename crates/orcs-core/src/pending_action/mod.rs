@@ -0,0 +1,12 @@
+//! Pending actions awaiting user confirmation.
+//!
+//! When an agent requests a destructive command (a slash command flagged with
+//! `requires_confirmation`, or a raw shell command matching the shell policy),
+//! the command is not executed directly. Instead a [`PendingAction`] is created
+//! and the user decides whether to approve or deny it.
+
+mod detector;
+mod model;
+
+pub use detector::DestructiveCommandDetector;
+pub use model::{CommandSource, PendingAction, PendingActionKind, PendingActionStatus};
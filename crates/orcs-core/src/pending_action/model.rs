@@ -0,0 +1,120 @@
+//! Pending action domain models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Who initiated a command execution.
+///
+/// Only agent-initiated executions are subject to confirmation gates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandSource {
+    /// Typed or clicked by the user.
+    #[default]
+    User,
+    /// Emitted by an agent in its response.
+    Agent,
+}
+
+/// Resolution state of a pending action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PendingActionStatus {
+    /// Approved by the user and executed.
+    Approved,
+    /// Denied by the user.
+    Denied,
+    /// Not resolved before `expires_at`.
+    Expired,
+}
+
+/// What runs when a pending action is approved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum PendingActionKind {
+    /// `rendered_command` runs as a shell command in the backend.
+    #[default]
+    Shell,
+    /// A prompt, action, task or pipeline slash command, re-dispatched by the UI.
+    SlashCommand,
+}
+
+/// An agent-triggered command waiting for user approval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingAction {
+    /// Unique identifier (UUID).
+    pub id: String,
+    /// How the command runs once approved.
+    #[serde(default)]
+    pub kind: PendingActionKind,
+    /// Session the command was requested in.
+    pub session_id: String,
+    /// Name of the agent (persona) that requested the command.
+    pub requested_by: String,
+    /// Fully expanded command text that will run on approval.
+    pub rendered_command: String,
+    /// Slash command name, if the request came from a slash command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command_name: Option<String>,
+    /// Slash command arguments, for [`PendingActionKind::SlashCommand`] actions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+    /// Working directory for the command, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    /// Creation timestamp (RFC3339).
+    pub created_at: String,
+    /// Expiry timestamp (RFC3339).
+    pub expires_at: String,
+}
+
+impl PendingAction {
+    /// Returns true if the action has expired at the given instant.
+    ///
+    /// An unparseable `expires_at` is treated as expired.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        match DateTime::parse_from_rfc3339(&self.expires_at) {
+            Ok(expires_at) => now >= expires_at,
+            Err(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn action(expires_at: DateTime<Utc>) -> PendingAction {
+        PendingAction {
+            id: "a1".to_string(),
+            kind: PendingActionKind::Shell,
+            session_id: "s1".to_string(),
+            requested_by: "Mai".to_string(),
+            rendered_command: "rm -rf build".to_string(),
+            command_name: None,
+            args: None,
+            working_dir: None,
+            created_at: Utc::now().to_rfc3339(),
+            expires_at: expires_at.to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_is_expired_at() {
+        let now = Utc::now();
+        let action = action(now + Duration::seconds(10));
+
+        assert!(!action.is_expired_at(now));
+        assert!(action.is_expired_at(now + Duration::seconds(10)));
+    }
+
+    #[test]
+    fn test_invalid_expiry_is_expired() {
+        let mut action = action(Utc::now());
+        action.expires_at = "not-a-date".to_string();
+
+        assert!(action.is_expired_at(Utc::now()));
+    }
+}
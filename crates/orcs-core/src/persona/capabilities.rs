@@ -0,0 +1,355 @@
+//! Capabilities a persona actually has in a session.
+//!
+//! The backend sets the upper bound. The persona's configuration and the
+//! session's runtime state can only take capabilities away: a CLI backend
+//! without a workspace has no files to work on, a Claude CLI persona can only
+//! use pre-approved tools (never the file-editing ones for reviewer-only
+//! personas), and web search needs both backend support and the session's
+//! permission.
+
+use super::model::{CapabilityFlags, Persona, PersonaBackend};
+use llm_toolkit::agent::Capability;
+
+/// Tools pre-approved for Claude CLI personas.
+///
+/// Claude CLI runs non-interactively, so tools that need approval and are not
+/// listed here are denied.
+pub const CLAUDE_CLI_ALLOWED_TOOLS: &[&str] = &["Edit", "Write"];
+
+/// Claude CLI tools that modify files, withheld from reviewer-only personas.
+pub const CLAUDE_CLI_WRITE_TOOLS: &[&str] = &["Edit", "Write"];
+
+/// Returns the tools pre-approved for a Claude CLI persona.
+///
+/// Reviewer-only personas never get [`CLAUDE_CLI_WRITE_TOOLS`], whatever
+/// else is allowed.
+pub fn claude_cli_allowed_tools(reviewer_only: bool) -> Vec<&'static str> {
+    CLAUDE_CLI_ALLOWED_TOOLS
+        .iter()
+        .copied()
+        .filter(|tool| !(reviewer_only && CLAUDE_CLI_WRITE_TOOLS.contains(tool)))
+        .collect()
+}
+
+/// Session state that affects what participants can do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeCaps {
+    /// Whether a workspace root is set for CLI backends to work in
+    pub workspace_available: bool,
+    /// Whether the session allows web search
+    pub web_search_enabled: bool,
+}
+
+/// Capabilities of a persona in a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    pub backend: PersonaBackend,
+    pub flags: CapabilityFlags,
+}
+
+/// Computes what `persona` can do given the session's runtime state.
+pub fn effective_capabilities(persona: &Persona, runtime: &RuntimeCaps) -> Capabilities {
+    let backend = &persona.backend;
+    let allowed_tools = allowed_tools(persona);
+    let tool_allowed = |tools: &[&str]| {
+        allowed_tools
+            .as_ref()
+            .is_none_or(|allowed| tools.iter().any(|t| allowed.contains(t)))
+    };
+
+    let backend_web_search = match backend {
+        PersonaBackend::GeminiApi => persona
+            .gemini_options
+            .as_ref()
+            .and_then(|options| options.google_search)
+            .unwrap_or(false),
+        _ => backend.has_web_search(),
+    };
+
+    Capabilities {
+        backend: backend.clone(),
+        flags: CapabilityFlags {
+            file_read: backend.has_direct_file_access() && runtime.workspace_available,
+            file_write: backend.can_edit_files()
+                && runtime.workspace_available
+                && tool_allowed(&["Edit", "Write"]),
+            command_execute: backend.can_execute_commands() && tool_allowed(&["Bash"]),
+            web_search: backend_web_search
+                && runtime.web_search_enabled
+                && tool_allowed(&["WebSearch"]),
+        },
+    }
+}
+
+/// Tools a persona's backend is restricted to, or `None` when it is not restricted.
+fn allowed_tools(persona: &Persona) -> Option<Vec<&'static str>> {
+    match persona.backend {
+        PersonaBackend::ClaudeCli => Some(claude_cli_allowed_tools(persona.reviewer_only)),
+        _ => None,
+    }
+}
+
+impl Capabilities {
+    /// Returns the capabilities as llm-toolkit Capability objects.
+    pub fn to_toolkit(&self) -> Vec<Capability> {
+        let flags = self.flags;
+        let mut capabilities = Vec::new();
+        if flags.file_read {
+            capabilities.push(
+                Capability::new("file:read").with_description("Read file contents from disk"),
+            );
+        }
+        if flags.file_write {
+            capabilities.push(
+                Capability::new("file:write").with_description("Write content to files on disk"),
+            );
+            capabilities
+                .push(Capability::new("file:edit").with_description("Edit existing files on disk"));
+        }
+        if flags.command_execute {
+            capabilities.push(
+                Capability::new("command:execute")
+                    .with_description("Execute shell commands and scripts"),
+            );
+            capabilities.push(
+                Capability::new("env:access").with_description("Access environment variables"),
+            );
+        }
+        if flags.web_search {
+            capabilities.push(Capability::new("web:search").with_description("Search the web"));
+        }
+        capabilities.push(
+            Capability::new("payload:read").with_description("Read input payload and messages"),
+        );
+        capabilities
+            .push(Capability::new("attachment:read").with_description("Read file attachments"));
+        if !flags.file_write {
+            capabilities
+                .push(Capability::new("analysis:code").with_description("Analyze and review code"));
+            capabilities.push(
+                Capability::new("suggestion:provide")
+                    .with_description("Provide suggestions and designs"),
+            );
+        }
+        capabilities.push(Capability::new("task:execute").with_description(
+            "Execute ORCS tasks: multi-step orchestration workflows with specialized agents",
+        ));
+        capabilities.push(Capability::new("slashCommand:execute").with_description(
+            "Execute ORCS slash commands: invoke built-in & user-defined operations",
+        ));
+        capabilities
+    }
+
+    /// Returns a markdown-formatted capabilities description for system prompts.
+    pub fn markdown(&self) -> String {
+        let flags = self.flags;
+        let local_backend = self.backend.has_direct_file_access();
+
+        let access_level = if flags.file_write {
+            "Direct local access"
+        } else if flags.file_read {
+            "Read-only local access"
+        } else if local_backend {
+            "Local CLI without a workspace"
+        } else {
+            "Remote API only"
+        };
+
+        let mut can = Vec::new();
+        let mut cannot = Vec::new();
+        match (flags.file_read, flags.file_write) {
+            (true, true) => can.push("Direct file system access (read, write, edit)"),
+            (true, false) => {
+                can.push("Read files in the workspace");
+                cannot.push("Edit files directly (suggest changes instead)");
+            }
+            _ => {
+                can.push("Read attached files");
+                cannot.push(if local_backend {
+                    "File system access (no workspace is open)"
+                } else {
+                    "Direct file system access"
+                });
+                cannot.push("Edit files directly (suggest changes instead)");
+            }
+        }
+        if flags.command_execute {
+            can.push("Execute shell commands");
+            can.push("Run local tools and scripts");
+            can.push("Access environment variables");
+        } else {
+            cannot.push("Execute local commands");
+        }
+        if flags.web_search {
+            can.push("Search the web");
+        } else {
+            cannot.push("Search the web");
+        }
+        if !flags.file_write {
+            can.push("Analyze code shared in the conversation");
+            can.push("Provide suggestions and designs");
+        }
+        can.push("Execute multi-step orchestration tasks");
+
+        let collaboration = if flags.file_write {
+            "For tasks requiring different capabilities, you can work with other agents using @mention."
+        } else {
+            "**Important**: For implementation tasks, delegate to agents with local access (e.g., @coder with CLI backend).\nFor file modifications, provide exact code suggestions that agents with file access can implement."
+        };
+
+        let mut out = format!(
+            "## Your Runtime Capabilities\n\n**Identity**: {} ({})\n**Access Level**: {}\n\n### What You CAN Do:\n",
+            self.backend.display_name(),
+            self.backend.access_type(),
+            access_level
+        );
+        for item in can {
+            out.push_str(&format!("✅ {}\n", item));
+        }
+        if !cannot.is_empty() {
+            out.push_str("\n### What You CANNOT Do:\n");
+            for item in cannot {
+                out.push_str(&format!("❌ {}\n", item));
+            }
+        }
+        out.push_str(&format!("\n### Collaboration:\n{}", collaboration));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persona::GeminiOptions;
+
+    fn persona(backend: PersonaBackend, google_search: Option<bool>) -> Persona {
+        Persona {
+            id: "p1".to_string(),
+            name: "Tester".to_string(),
+            role: "Engineer".to_string(),
+            background: String::new(),
+            communication_style: String::new(),
+            default_participant: false,
+            source: Default::default(),
+            backend,
+            model_name: None,
+            icon: None,
+            base_color: None,
+            gemini_options: google_search.map(|enabled| GeminiOptions {
+                thinking_level: None,
+                google_search: Some(enabled),
+            }),
+            kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
+        }
+    }
+
+    #[test]
+    fn test_claims_match_configuration() {
+        use PersonaBackend::*;
+
+        // (backend, google_search, workspace, session web search) => (read, write, command, web)
+        #[rustfmt::skip]
+        let cases = [
+            (ClaudeCli,  None,        true,  true,  (true,  true,  false, false)),
+            (ClaudeCli,  None,        false, true,  (false, false, false, false)),
+            (GeminiCli,  None,        true,  true,  (true,  true,  true,  true)),
+            (GeminiCli,  None,        true,  false, (true,  true,  true,  false)),
+            (GeminiCli,  None,        false, true,  (false, false, true,  true)),
+            (CodexCli,   None,        true,  true,  (true,  true,  true,  false)),
+            (GeminiApi,  None,        true,  true,  (false, false, false, false)),
+            (GeminiApi,  Some(false), true,  true,  (false, false, false, false)),
+            (GeminiApi,  Some(true),  true,  true,  (false, false, false, true)),
+            (GeminiApi,  Some(true),  true,  false, (false, false, false, false)),
+            (ClaudeApi,  None,        true,  true,  (false, false, false, false)),
+            (OpenAiApi,  None,        true,  true,  (false, false, false, false)),
+            (KaibaApi,   None,        true,  true,  (false, false, false, false)),
+        ];
+
+        for (backend, google_search, workspace_available, web_search_enabled, expected) in cases {
+            let runtime = RuntimeCaps {
+                workspace_available,
+                web_search_enabled,
+            };
+            let capabilities =
+                effective_capabilities(&persona(backend.clone(), google_search), &runtime);
+            let flags = capabilities.flags;
+            let case = format!("{:?} search={:?} {:?}", backend, google_search, runtime);
+            assert_eq!(
+                (
+                    flags.file_read,
+                    flags.file_write,
+                    flags.command_execute,
+                    flags.web_search
+                ),
+                expected,
+                "{}",
+                case
+            );
+
+            let markdown = capabilities.markdown();
+            assert_eq!(
+                markdown.contains("✅ Direct file system access"),
+                flags.file_write,
+                "{}",
+                case
+            );
+            assert_eq!(
+                markdown.contains("✅ Execute shell commands"),
+                flags.command_execute,
+                "{}",
+                case
+            );
+            assert_eq!(
+                markdown.contains("✅ Search the web"),
+                flags.web_search,
+                "{}",
+                case
+            );
+
+            let toolkit: Vec<String> = capabilities
+                .to_toolkit()
+                .into_iter()
+                .map(|c| c.name)
+                .collect();
+            assert_eq!(
+                toolkit.contains(&"file:write".to_string()),
+                flags.file_write,
+                "{}",
+                case
+            );
+            assert_eq!(
+                toolkit.contains(&"web:search".to_string()),
+                flags.web_search,
+                "{}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn test_reviewer_only_claude_cli_persona_cannot_write() {
+        let reviewer = Persona {
+            reviewer_only: true,
+            ..persona(PersonaBackend::ClaudeCli, None)
+        };
+        let runtime = RuntimeCaps {
+            workspace_available: true,
+            web_search_enabled: true,
+        };
+
+        assert!(
+            claude_cli_allowed_tools(true)
+                .iter()
+                .all(|tool| !CLAUDE_CLI_WRITE_TOOLS.contains(tool))
+        );
+        let flags = effective_capabilities(&reviewer, &runtime).flags;
+        assert!(flags.file_read);
+        assert!(!flags.file_write);
+    }
+}
@@ -6,7 +6,9 @@
 //! # Module Structure
 //!
 //! - `model`: Core persona domain models (`Persona`, `PersonaSource`, `PersonaBackend`)
+//! - `capabilities`: What a persona can do given its configuration and the session
 //! - `repository`: Repository trait for persona persistence
+//! - `suggestion`: Personas worth adding for a message's topic
 //! - `preset`: Default system personas
 //!
 //! # Usage
@@ -15,13 +17,22 @@
 //! use orcs_core::persona::{Persona, PersonaSource, PersonaRepository, get_default_presets};
 //! ```
 
+mod capabilities;
 mod model;
 mod preset;
 mod repository;
 pub mod request;
+mod suggestion;
 
 // Re-export public API
-pub use model::{GeminiOptions, KaibaOptions, Persona, PersonaBackend, PersonaSource};
-pub use preset::get_default_presets;
+pub use capabilities::{
+    CLAUDE_CLI_ALLOWED_TOOLS, CLAUDE_CLI_WRITE_TOOLS, Capabilities, RuntimeCaps,
+    claude_cli_allowed_tools, effective_capabilities,
+};
+pub use model::{
+    CapabilityFlags, GeminiOptions, KaibaOptions, Persona, PersonaBackend, PersonaSource,
+};
+pub use preset::{PresetSeeding, get_default_presets, seed_default_presets};
 pub use repository::PersonaRepository;
-pub use request::CreatePersonaRequest;
+pub use request::{CreatePersonaRequest, FieldError, PersonaOverrides, PersonaValidationError};
+pub use suggestion::{MAX_PARTICIPANT_SUGGESTIONS, PersonaMatch, suggest_participants};
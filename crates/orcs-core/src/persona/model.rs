@@ -3,11 +3,17 @@
 //! Represents AI personas that participate in conversations with users.
 //! Each persona has unique characteristics, roles, and communication styles.
 
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use llm_toolkit::models::{ClaudeModel, GeminiModel, OpenAIModel};
 use serde::{Deserialize, Serialize};
 use version_migrate::DeriveQueryable as Queryable;
 
+use crate::session::ContextMode;
+
 /// Supported LLM backends for personas.
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum PersonaBackend {
     /// Anthropic Claude Code CLI backend
@@ -67,6 +73,22 @@ impl PersonaBackend {
         }
     }
 
+    /// Parses a backend identifier string as returned by [`Self::as_str`].
+    ///
+    /// Returns `None` for unknown identifiers.
+    pub fn parse(id: &str) -> Option<Self> {
+        match id {
+            "claude_cli" => Some(PersonaBackend::ClaudeCli),
+            "claude_api" => Some(PersonaBackend::ClaudeApi),
+            "gemini_cli" => Some(PersonaBackend::GeminiCli),
+            "gemini_api" => Some(PersonaBackend::GeminiApi),
+            "open_ai_api" => Some(PersonaBackend::OpenAiApi),
+            "codex_cli" => Some(PersonaBackend::CodexCli),
+            "kaiba_api" => Some(PersonaBackend::KaibaApi),
+            _ => None,
+        }
+    }
+
     /// Returns the access type for this backend.
     pub fn access_type(&self) -> &'static str {
         match self {
@@ -80,6 +102,16 @@ impl PersonaBackend {
         }
     }
 
+    /// Returns whether this backend runs as a local CLI process.
+    ///
+    /// CLI backends return their output only once the process exits.
+    pub fn is_cli(&self) -> bool {
+        matches!(
+            self,
+            PersonaBackend::ClaudeCli | PersonaBackend::GeminiCli | PersonaBackend::CodexCli
+        )
+    }
+
     /// Returns whether this backend has direct file system access.
     pub fn has_direct_file_access(&self) -> bool {
         matches!(
@@ -104,92 +136,71 @@ impl PersonaBackend {
         )
     }
 
-    /// Returns the capabilities for this backend as llm-toolkit Capability objects.
-    pub fn capabilities(&self) -> Vec<llm_toolkit::agent::Capability> {
-        use llm_toolkit::agent::Capability;
-
-        if self.has_direct_file_access() {
-            // CLI backends: full local access
-            vec![
-                Capability::new("file:read").with_description("Read file contents from disk"),
-                Capability::new("file:write").with_description("Write content to files on disk"),
-                Capability::new("file:edit").with_description("Edit existing files on disk"),
-                Capability::new("command:execute")
-                    .with_description("Execute shell commands and scripts"),
-                Capability::new("env:access").with_description("Access environment variables"),
-                Capability::new("payload:read").with_description("Read input payload and messages"),
-                Capability::new("attachment:read").with_description("Read file attachments"),
-                Capability::new("task:execute")
-                    .with_description("Execute ORCS tasks: multi-step orchestration workflows with specialized agents"),
-                Capability::new("slashCommand:execute")
-                    .with_description("Execute ORCS slash commands: invoke built-in & user-defined operations"),
-            ]
-        } else {
-            // API backends: read-only, remote access
-            vec![
-                Capability::new("payload:read").with_description("Read input payload and messages"),
-                Capability::new("attachment:read").with_description("Read file attachments"),
-                Capability::new("analysis:code").with_description("Analyze and review code"),
-                Capability::new("suggestion:provide")
-                    .with_description("Provide suggestions and designs"),
-                Capability::new("task:execute")
-                    .with_description("Execute ORCS tasks: multi-step orchestration workflows with specialized agents"),
-                Capability::new("slashCommand:execute")
-                    .with_description("Execute ORCS slash commands: invoke built-in & user-defined operations"),
-            ]
+    /// Returns whether this backend can search the web on its own.
+    pub fn has_web_search(&self) -> bool {
+        matches!(self, PersonaBackend::ClaudeCli | PersonaBackend::GeminiCli)
+    }
+
+    /// Returns whether this backend runs a selectable model.
+    pub fn uses_model_name(&self) -> bool {
+        !matches!(self, PersonaBackend::KaibaApi)
+    }
+
+    /// Checks a model name against the model catalog of this backend.
+    pub fn validate_model_name(&self, model: &str) -> Result<(), String> {
+        let result = match self {
+            PersonaBackend::ClaudeCli | PersonaBackend::ClaudeApi => {
+                ClaudeModel::from_str(model).map(|_| ())
+            }
+            PersonaBackend::GeminiCli | PersonaBackend::GeminiApi => {
+                GeminiModel::from_str(model).map(|_| ())
+            }
+            PersonaBackend::OpenAiApi | PersonaBackend::CodexCli => {
+                OpenAIModel::from_str(model).map(|_| ())
+            }
+            PersonaBackend::KaibaApi => {
+                return Err(format!("{} does not use a model name", self.display_name()));
+            }
+        };
+        result.map_err(|e| format!("{}: {}", self.display_name(), e))
+    }
+
+    /// Returns the capability flags for this backend.
+    pub fn capability_flags(&self) -> CapabilityFlags {
+        CapabilityFlags {
+            file_read: self.has_direct_file_access(),
+            file_write: self.can_edit_files(),
+            command_execute: self.can_execute_commands(),
+            web_search: self.has_web_search(),
         }
     }
+}
 
-    /// Returns a markdown-formatted capabilities description for system prompts.
-    pub fn capabilities_markdown(&self) -> String {
-        let access_type = self.access_type();
-        let backend_name = self.display_name();
-
-        if self.has_direct_file_access() {
-            format!(
-                r#"## Your Runtime Capabilities
-
-**Identity**: {backend_name} ({access_type})
-**Access Level**: Direct local access
-
-### What You CAN Do:
-✅ Direct file system access (read, write, edit)
-✅ Execute shell commands
-✅ Run local tools and scripts
-✅ Access environment variables
-✅ Full development workflow
-✅ Execute multi-step orchestration tasks
-
-### Collaboration:
-For tasks requiring different capabilities, you can work with other agents using @mention."#,
-                backend_name = backend_name,
-                access_type = access_type
-            )
-        } else {
-            format!(
-                r#"## Your Runtime Capabilities
-
-**Identity**: {backend_name} ({access_type})
-**Access Level**: Remote API only
-
-### What You CAN Do:
-✅ Read file contents (via tool calls)
-✅ Search and analyze code
-✅ Provide suggestions and designs
-✅ Call available tools
-
-### What You CANNOT Do:
-❌ Direct file system access
-❌ Edit files directly (suggest changes instead)
-❌ Execute local commands
-❌ Access local environment variables
-
-### Collaboration:
-**Important**: For implementation tasks, delegate to agents with local access (e.g., @coder with CLI backend).
-For file modifications, provide exact code suggestions that CLI agents can implement."#,
-                backend_name = backend_name,
-                access_type = access_type
-            )
+/// Coarse capability flags of a backend or a group of participants.
+///
+/// Flags of several participants are combined with [`CapabilityFlags::union`],
+/// so a flag is set when at least one participant supports it.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityFlags {
+    /// Can read files from the local file system
+    pub file_read: bool,
+    /// Can write or edit files on the local file system
+    pub file_write: bool,
+    /// Can execute shell commands
+    pub command_execute: bool,
+    /// Can search the web
+    pub web_search: bool,
+}
+
+impl CapabilityFlags {
+    /// Returns the flags supported by either `self` or `other`.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            file_read: self.file_read || other.file_read,
+            file_write: self.file_write || other.file_write,
+            command_execute: self.command_execute || other.command_execute,
+            web_search: self.web_search || other.web_search,
         }
     }
 }
@@ -204,6 +215,8 @@ pub enum PersonaSource {
     User,
     /// Adhoc expert persona (temporary, session-specific)
     Adhoc,
+    /// Read-only persona fetched from a shared remote index
+    Remote,
 }
 
 /// Options specific to Gemini models (e.g., Gemini 3).
@@ -255,9 +268,16 @@ pub struct Persona {
     /// If None, uses the backend's default model
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model_name: Option<String>,
+    /// CLI backend used instead of an API `backend` while offline mode is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_backend: Option<PersonaBackend>,
     /// Visual icon/emoji representing this persona (e.g., "🎨", "🔧", "📊")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
+    /// Uploaded icon image, as a file name in the managed persona assets directory.
+    /// Takes precedence over `icon` when rendering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_asset: Option<String>,
     /// Base color for UI theming (e.g., "#FF5733", "#3357FF")
     /// Used for message background tinting and visual identification
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -268,6 +288,83 @@ pub struct Persona {
     /// Kaiba-specific options (Rei ID for persistent memory)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub kaiba_options: Option<KaibaOptions>,
+    /// Per-persona context mode; overrides the session's context mode when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_mode_override: Option<ContextMode>,
+    /// Whether this persona is archived (hidden from pickers, kept for old sessions)
+    #[serde(default)]
+    pub archived: bool,
+    /// Whether this persona only reviews; Claude CLI runs never get file-editing tools
+    #[serde(default)]
+    pub reviewer_only: bool,
+    /// Workspace this persona belongs to; `None` makes it global, available in
+    /// every workspace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+}
+
+/// Prefix marking an icon reference as an uploaded asset rather than an emoji.
+pub const ICON_ASSET_PREFIX: &str = "asset:";
+
+impl Persona {
+    /// Whether this persona is available in sessions of `workspace_id`: global
+    /// personas are available everywhere, workspace-scoped ones only in their
+    /// own workspace.
+    pub fn is_available_in(&self, workspace_id: Option<&str>) -> bool {
+        match &self.workspace_id {
+            None => true,
+            Some(own) => workspace_id == Some(own.as_str()),
+        }
+    }
+
+    /// Returns the icon to render: the uploaded asset as `asset:<file>` if any,
+    /// otherwise the emoji icon.
+    pub fn display_icon(&self) -> Option<String> {
+        self.icon_asset
+            .as_ref()
+            .map(|asset| format!("{ICON_ASSET_PREFIX}{asset}"))
+            .or_else(|| self.icon.clone())
+    }
+
+    /// Returns the context mode this persona runs with in a session.
+    pub fn effective_context_mode(&self, session_mode: ContextMode) -> ContextMode {
+        self.context_mode_override.unwrap_or(session_mode)
+    }
+
+    /// Returns whether this persona is explicitly overridden to Clean context.
+    pub fn has_clean_context_override(&self) -> bool {
+        self.context_mode_override == Some(ContextMode::Clean)
+    }
+
+    /// Returns the backend this persona runs with while offline mode is on: its
+    /// own backend if it is a CLI, else its CLI `fallback_backend`, if any.
+    pub fn offline_backend(&self) -> Option<PersonaBackend> {
+        if self.backend.is_cli() {
+            return Some(self.backend.clone());
+        }
+        self.fallback_backend
+            .clone()
+            .filter(|fallback| fallback.is_cli())
+    }
+
+    /// Returns the model this persona runs with.
+    ///
+    /// The persona's own model wins over the app-level default for its backend;
+    /// `None` means the backend library picks its default model.
+    pub fn resolved_model_name(
+        &self,
+        default_models: &HashMap<PersonaBackend, String>,
+    ) -> Option<String> {
+        if !self.backend.uses_model_name() {
+            return None;
+        }
+        self.model_name.clone().or_else(|| {
+            default_models
+                .get(&self.backend)
+                .filter(|model| !model.trim().is_empty())
+                .cloned()
+        })
+    }
 }
 
 #[cfg(test)]
@@ -321,4 +418,135 @@ mod tests {
             assert!(result.is_ok(), "Failed to deserialize variant key: {}", key);
         }
     }
+
+    #[test]
+    fn test_parse_round_trips_identifiers() {
+        for (key, _label) in PersonaBackend::all_variants() {
+            let backend = PersonaBackend::parse(&key).expect("known identifier");
+            assert_eq!(backend.as_str(), key);
+        }
+        assert_eq!(PersonaBackend::parse("claude"), None);
+    }
+
+    fn persona_with(backend: PersonaBackend, model_name: Option<&str>) -> Persona {
+        Persona {
+            id: "p1".to_string(),
+            name: "Tester".to_string(),
+            role: String::new(),
+            background: String::new(),
+            communication_style: String::new(),
+            default_participant: false,
+            source: PersonaSource::User,
+            backend,
+            model_name: model_name.map(str::to_string),
+            fallback_backend: None,
+            icon: None,
+            base_color: None,
+            gemini_options: None,
+            kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            reviewer_only: false,
+            archived: false,
+        }
+    }
+
+    #[test]
+    fn test_resolved_model_name_precedence() {
+        let cases = [
+            (PersonaBackend::ClaudeCli, "claude-persona", "claude-app"),
+            (PersonaBackend::ClaudeApi, "claude-persona", "claude-app"),
+            (PersonaBackend::GeminiCli, "gemini-persona", "gemini-app"),
+            (PersonaBackend::GeminiApi, "gemini-persona", "gemini-app"),
+            (PersonaBackend::OpenAiApi, "gpt-persona", "gpt-app"),
+            (PersonaBackend::CodexCli, "gpt-persona", "gpt-app"),
+        ];
+
+        for (backend, persona_model, app_model) in cases {
+            let defaults = HashMap::from([(backend.clone(), app_model.to_string())]);
+
+            // Persona model wins over the app default.
+            let persona = persona_with(backend.clone(), Some(persona_model));
+            assert_eq!(
+                persona.resolved_model_name(&defaults).as_deref(),
+                Some(persona_model),
+                "{backend:?}"
+            );
+
+            // App default applies when the persona has no model.
+            let persona = persona_with(backend.clone(), None);
+            assert_eq!(
+                persona.resolved_model_name(&defaults).as_deref(),
+                Some(app_model),
+                "{backend:?}"
+            );
+
+            // Library default (None) when neither is set.
+            assert_eq!(persona.resolved_model_name(&HashMap::new()), None);
+
+            // Defaults for other backends are ignored.
+            let other = HashMap::from([(PersonaBackend::KaibaApi, "x".to_string())]);
+            assert_eq!(persona.resolved_model_name(&other), None);
+        }
+
+        let kaiba = persona_with(PersonaBackend::KaibaApi, None);
+        let defaults = HashMap::from([(PersonaBackend::KaibaApi, "anything".to_string())]);
+        assert_eq!(kaiba.resolved_model_name(&defaults), None);
+    }
+
+    #[test]
+    fn test_offline_backend_prefers_cli_backends() {
+        let cli = persona_with(PersonaBackend::GeminiCli, None);
+        assert_eq!(cli.offline_backend(), Some(PersonaBackend::GeminiCli));
+
+        let mut api = persona_with(PersonaBackend::ClaudeApi, None);
+        assert_eq!(api.offline_backend(), None);
+
+        api.fallback_backend = Some(PersonaBackend::ClaudeCli);
+        assert_eq!(api.offline_backend(), Some(PersonaBackend::ClaudeCli));
+
+        // An API fallback does not help while offline
+        api.fallback_backend = Some(PersonaBackend::OpenAiApi);
+        assert_eq!(api.offline_backend(), None);
+    }
+
+    #[test]
+    fn test_validate_model_name_uses_backend_catalog() {
+        assert!(
+            PersonaBackend::ClaudeApi
+                .validate_model_name("sonnet")
+                .is_ok()
+        );
+        assert!(
+            PersonaBackend::ClaudeCli
+                .validate_model_name("gpt-4o")
+                .is_err()
+        );
+        assert!(
+            PersonaBackend::GeminiCli
+                .validate_model_name("gemini-2.5-flash")
+                .is_ok()
+        );
+        assert!(
+            PersonaBackend::GeminiApi
+                .validate_model_name("claude-x")
+                .is_err()
+        );
+        assert!(
+            PersonaBackend::OpenAiApi
+                .validate_model_name("gpt-4o")
+                .is_ok()
+        );
+        assert!(
+            PersonaBackend::CodexCli
+                .validate_model_name("gemini-pro")
+                .is_err()
+        );
+        assert!(
+            PersonaBackend::KaibaApi
+                .validate_model_name("anything")
+                .is_err()
+        );
+    }
 }
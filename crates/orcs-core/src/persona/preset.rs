@@ -3,8 +3,50 @@
 //! Provides system-defined default personas that are available to all users.
 
 use super::model::{Persona, PersonaSource};
+use super::repository::PersonaRepository;
+use crate::error::Result;
 use uuid::Uuid;
 
+/// Outcome of [`seed_default_presets`].
+#[derive(Debug, Clone)]
+pub enum PresetSeeding {
+    /// The repository already had personas; nothing was written.
+    AlreadyPresent,
+    /// The repository is empty but presets were seeded before, so the user
+    /// removed them deliberately; nothing was written.
+    PreviouslySeeded,
+    /// The built-in presets were written to the repository.
+    Seeded(Vec<Persona>),
+}
+
+/// Writes the built-in presets to `repository` when it has no personas.
+///
+/// A failed listing counts as empty. Seeding happens at most once per
+/// repository: the seeding marker is set afterwards, and also when personas
+/// already exist, so deleting every persona does not bring the presets back.
+pub async fn seed_default_presets(repository: &dyn PersonaRepository) -> Result<PresetSeeding> {
+    let has_personas = repository
+        .get_all_including_archived()
+        .await
+        .is_ok_and(|personas| !personas.is_empty());
+    let seeded_before = repository.presets_seeded().await?;
+
+    if has_personas {
+        if !seeded_before {
+            repository.mark_presets_seeded().await?;
+        }
+        return Ok(PresetSeeding::AlreadyPresent);
+    }
+    if seeded_before {
+        return Ok(PresetSeeding::PreviouslySeeded);
+    }
+
+    let presets = get_default_presets();
+    repository.save_all(&presets).await?;
+    repository.mark_presets_seeded().await?;
+    Ok(PresetSeeding::Seeded(presets))
+}
+
 /// Returns the official preset persona configurations for the application.
 ///
 /// These personas are system-defined and serve as the default AI agents:
@@ -26,6 +68,12 @@ pub fn get_default_presets() -> Vec<Persona> {
             base_color: Some("#FFB6C1".to_string()), // Light pink for UX
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         },
         Persona {
             id: Uuid::new_v4().to_string(),
@@ -41,6 +89,12 @@ pub fn get_default_presets() -> Vec<Persona> {
             base_color: Some("#ADD8E6".to_string()), // Light blue for Engineer
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         },
     ]
 }
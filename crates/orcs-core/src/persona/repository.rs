@@ -56,14 +56,27 @@ pub trait PersonaRepository: Send + Sync {
     /// - `Err(OrcsError)`: Error occurred during deletion
     async fn delete(&self, persona_id: &str) -> Result<()>;
 
-    /// Retrieves all personas from storage.
+    /// Retrieves all active (non-archived) personas from storage.
+    ///
+    /// Use this for participant pickers and default-participant selection.
     ///
     /// # Returns
     ///
-    /// - `Ok(Vec<Persona>)`: All stored personas
+    /// - `Ok(Vec<Persona>)`: All stored personas that are not archived
     /// - `Err(OrcsError)`: Error if retrieval fails
     async fn get_all(&self) -> Result<Vec<Persona>>;
 
+    /// Retrieves all personas from storage, including archived ones.
+    ///
+    /// Use this to resolve persona IDs referenced by existing sessions
+    /// (display names, participant enrichment, history rebuilds).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<Persona>)`: All stored personas
+    /// - `Err(OrcsError)`: Error if retrieval fails
+    async fn get_all_including_archived(&self) -> Result<Vec<Persona>>;
+
     /// Saves all provided personas to storage.
     ///
     /// # Arguments
@@ -76,4 +89,17 @@ pub trait PersonaRepository: Send + Sync {
     /// - `Ok(())`: Personas saved successfully
     /// - `Err(OrcsError)`: Error if save fails
     async fn save_all(&self, personas: &[Persona]) -> Result<()>;
+
+    /// Returns whether the built-in presets were seeded into this storage before.
+    ///
+    /// Storages without a seeding marker always return `false`.
+    async fn presets_seeded(&self) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Records that the built-in presets were seeded, so personas the user
+    /// deletes afterwards are not seeded again.
+    async fn mark_presets_seeded(&self) -> Result<()> {
+        Ok(())
+    }
 }
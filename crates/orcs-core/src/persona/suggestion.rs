@@ -0,0 +1,346 @@
+//! Participant suggestions from message content.
+//!
+//! Scores personas against a user message by the keywords their role and
+//! background share with it. No embeddings or model calls are involved, so
+//! this is cheap enough to run after every user turn.
+
+use std::collections::{BTreeSet, HashSet};
+
+use super::model::Persona;
+
+/// Most personas suggested after a single user turn.
+pub const MAX_PARTICIPANT_SUGGESTIONS: usize = 3;
+
+/// Shortest Latin-script word treated as a keyword.
+const MIN_KEYWORD_LEN: usize = 3;
+
+/// Words too common in messages or persona profiles to say anything about a topic.
+const STOPWORDS: &[&str] = &[
+    "about",
+    "all",
+    "also",
+    "and",
+    "any",
+    "are",
+    "but",
+    "can",
+    "could",
+    "does",
+    "for",
+    "from",
+    "has",
+    "have",
+    "help",
+    "how",
+    "into",
+    "its",
+    "just",
+    "like",
+    "make",
+    "need",
+    "not",
+    "our",
+    "please",
+    "should",
+    "than",
+    "that",
+    "the",
+    "their",
+    "them",
+    "then",
+    "there",
+    "they",
+    "this",
+    "use",
+    "using",
+    "want",
+    "was",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "who",
+    "why",
+    "will",
+    "with",
+    "would",
+    "you",
+    "your",
+    // Generic profile wording
+    "deep",
+    "experience",
+    "experienced",
+    "expert",
+    "expertise",
+    "focus",
+    "focused",
+    "knowledge",
+    "skilled",
+    "specialist",
+    "strong",
+    "work",
+    "working",
+    "year",
+];
+
+/// A persona whose profile matches a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonaMatch {
+    /// ID of the matching persona
+    pub persona_id: String,
+    /// Number of distinct keywords shared with the message
+    pub score: usize,
+    /// The shared keywords, in sorted order
+    pub matched_keywords: Vec<String>,
+}
+
+/// Suggests personas to add when no active participant covers `message`'s topic.
+///
+/// Returns nothing if any active participant's role or background shares a
+/// keyword with the message. Otherwise returns up to
+/// [`MAX_PARTICIPANT_SUGGESTIONS`] inactive personas that do, best match
+/// first. Archived personas and those in `dismissed_ids` are never suggested.
+pub fn suggest_participants(
+    message: &str,
+    personas: &[Persona],
+    active_ids: &[String],
+    dismissed_ids: &HashSet<String>,
+) -> Vec<PersonaMatch> {
+    let message_keywords = keywords(message);
+    if message_keywords.is_empty() {
+        return Vec::new();
+    }
+
+    let is_active = |persona: &Persona| active_ids.contains(&persona.id);
+    let covered = personas
+        .iter()
+        .filter(|persona| is_active(persona))
+        .any(|persona| match_persona(persona, &message_keywords).is_some());
+    if covered {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<PersonaMatch> = personas
+        .iter()
+        .filter(|persona| {
+            !is_active(persona) && !persona.archived && !dismissed_ids.contains(&persona.id)
+        })
+        .filter_map(|persona| match_persona(persona, &message_keywords))
+        .collect();
+    // Stable, so equal scores keep the repository's persona order
+    matches.sort_by_key(|m| std::cmp::Reverse(m.score));
+    matches.truncate(MAX_PARTICIPANT_SUGGESTIONS);
+    matches
+}
+
+fn match_persona(persona: &Persona, message_keywords: &BTreeSet<String>) -> Option<PersonaMatch> {
+    let profile = keywords(&format!("{}\n{}", persona.role, persona.background));
+    let matched_keywords: Vec<String> = profile.intersection(message_keywords).cloned().collect();
+    if matched_keywords.is_empty() {
+        return None;
+    }
+    Some(PersonaMatch {
+        persona_id: persona.id.clone(),
+        score: matched_keywords.len(),
+        matched_keywords,
+    })
+}
+
+/// Extracts the topic keywords of `text`.
+///
+/// Latin-script words are lowercased, stripped of a plural `s` and filtered
+/// against [`STOPWORDS`]. Text without spaces between words (Chinese,
+/// Japanese) is split into character bigrams of its Han and katakana runs;
+/// hiragana mostly carries grammar and is skipped.
+fn keywords(text: &str) -> BTreeSet<String> {
+    let mut keywords = BTreeSet::new();
+    let mut ideographic_run: Vec<char> = Vec::new();
+
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let mut latin = String::new();
+        for c in word.chars() {
+            if is_ideographic(c) {
+                ideographic_run.push(c);
+                continue;
+            }
+            insert_bigrams(&mut ideographic_run, &mut keywords);
+            if !is_hiragana(c) {
+                latin.extend(c.to_lowercase());
+            }
+        }
+        insert_bigrams(&mut ideographic_run, &mut keywords);
+
+        if latin.chars().count() < MIN_KEYWORD_LEN || latin.chars().all(char::is_numeric) {
+            continue;
+        }
+        let keyword = singular(&latin);
+        if !STOPWORDS.contains(&keyword) {
+            keywords.insert(keyword.to_string());
+        }
+    }
+    keywords
+}
+
+/// Adds the character bigrams of `run` to `keywords` and empties it.
+fn insert_bigrams(run: &mut Vec<char>, keywords: &mut BTreeSet<String>) {
+    for pair in run.windows(2) {
+        keywords.insert(pair.iter().collect());
+    }
+    run.clear();
+}
+
+/// Han characters and katakana, which carry the content of CJK text.
+fn is_ideographic(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x30A0..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF
+    )
+}
+
+fn is_hiragana(c: char) -> bool {
+    matches!(c as u32, 0x3040..=0x309F)
+}
+
+/// Strips a plural `s` so "databases" matches "database".
+fn singular(word: &str) -> &str {
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() >= MIN_KEYWORD_LEN && !stem.ends_with('s') => stem,
+        _ => word,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn persona(id: &str, role: &str, background: &str) -> Persona {
+        Persona {
+            id: id.to_string(),
+            name: id.to_string(),
+            role: role.to_string(),
+            background: background.to_string(),
+            communication_style: String::new(),
+            default_participant: false,
+            source: Default::default(),
+            backend: Default::default(),
+            model_name: None,
+            icon: None,
+            base_color: None,
+            gemini_options: None,
+            kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
+        }
+    }
+
+    fn fixture() -> Vec<Persona> {
+        let mut retired = persona(
+            "retired-dba",
+            "Database Administrator",
+            "Maintains legacy database servers.",
+        );
+        retired.archived = true;
+        vec![
+            persona(
+                "frontend",
+                "Frontend Engineer",
+                "Builds React components, CSS layouts and accessible UI.",
+            ),
+            persona(
+                "dba",
+                "Database Engineer",
+                "Designs PostgreSQL schemas, tunes slow queries and indexes.",
+            ),
+            persona(
+                "backend",
+                "Backend Engineer",
+                "Writes REST APIs and keeps the database layer consistent.",
+            ),
+            persona(
+                "devops",
+                "DevOps Engineer",
+                "Runs Kubernetes deployments and CI pipelines.",
+            ),
+            persona(
+                "ja-dba",
+                "データベース設計者",
+                "インデックス設計とクエリの最適化を担当します。",
+            ),
+            retired,
+        ]
+    }
+
+    fn ids(matches: &[PersonaMatch]) -> Vec<&str> {
+        matches.iter().map(|m| m.persona_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_suggests_matching_personas_best_first() {
+        let personas = fixture();
+        let matches = suggest_participants(
+            "Why are my PostgreSQL queries so slow? Should the database use more indexes?",
+            &personas,
+            &["frontend".to_string()],
+            &HashSet::new(),
+        );
+
+        assert_eq!(ids(&matches), vec!["dba", "backend"]);
+        // Both sides are normalized the same way, so "queries" matches as "querie"
+        assert_eq!(
+            matches[0].matched_keywords,
+            vec!["database", "indexe", "postgresql", "querie", "slow"]
+        );
+    }
+
+    #[test]
+    fn test_no_suggestions_when_an_active_participant_covers_the_topic() {
+        let personas = fixture();
+        let matches = suggest_participants(
+            "Can we add an index to the orders database table?",
+            &personas,
+            &["frontend".to_string(), "backend".to_string()],
+            &HashSet::new(),
+        );
+        assert!(matches.is_empty());
+
+        let unrelated = suggest_participants(
+            "Thanks, that is all for today.",
+            &personas,
+            &["frontend".to_string()],
+            &HashSet::new(),
+        );
+        assert!(unrelated.is_empty());
+    }
+
+    #[test]
+    fn test_skips_dismissed_and_archived_personas_and_caps_the_list() {
+        let personas = fixture();
+        let message = "The engineer on call saw the database deployment fail in CI.";
+        let active = ["ja-dba".to_string()];
+
+        let matches = suggest_participants(message, &personas, &active, &HashSet::new());
+        assert_eq!(matches.len(), MAX_PARTICIPANT_SUGGESTIONS);
+        assert!(!ids(&matches).contains(&"retired-dba"));
+
+        let dismissed = HashSet::from(["dba".to_string()]);
+        let matches = suggest_participants(message, &personas, &active, &dismissed);
+        assert!(!ids(&matches).contains(&"dba"));
+    }
+
+    #[test]
+    fn test_matches_japanese_profiles_by_bigrams() {
+        let personas = fixture();
+        let matches = suggest_participants(
+            "このデータベースのクエリが遅いので見てほしいです。",
+            &personas,
+            &["frontend".to_string()],
+            &HashSet::new(),
+        );
+        assert_eq!(ids(&matches), vec!["ja-dba"]);
+    }
+}
@@ -317,6 +317,20 @@ impl From<SessionType> for crate::session::Session {
             context_mode: crate::session::ContextMode::default(), // Default to Rich
             sandbox_state: None,                                  // Default to non-sandbox mode
             last_memory_sync_at: None,                            // Managed by SessionUseCase
+            is_generating: false, // Runtime state, not in SessionType
+            style_calibration_enabled: false, // Not in SessionType
+            response_splitting_enabled: true, // Not in SessionType
+            pinboard: None,       // Not in SessionType
+            pinboard_agent_edits_enabled: false, // Not in SessionType
+            apply_conversation_mode_in_clean: true, // Not in SessionType
+            participant_suggestions_enabled: false, // Not in SessionType
+            script_run: None,     // Not in SessionType
+            focused_participant_ids: None, // Not in SessionType
+            random_seed: None,    // Not in SessionType
+            clock_context_enabled: true, // Not in SessionType
+            plan_detection_enabled: false, // Not in SessionType
+            paste_attachment_enabled: true, // Not in SessionType
+            paste_attachment_threshold: 8_000, // Not in SessionType
         }
     }
 }
@@ -366,6 +380,12 @@ pub struct TaskType {
     pub error: Option<String>,
     /// Result summary text
     pub result: Option<String>,
+    /// Timestamp of the conversation message this task was spawned from
+    pub origin_message_timestamp: Option<String>,
+    /// Author of the originating message
+    pub origin_author: Option<String>,
+    /// Whether this was a dry run that changed nothing
+    pub dry_run: bool,
 }
 
 // Re-export TaskStatus from task module for TypeScript generation
@@ -0,0 +1,292 @@
+//! Per-persona response style calibration from user feedback.
+//!
+//! Feedback on a persona's answers is counted over its most recent turns. When
+//! one kind of negative feedback crosses the threshold, that persona gets a
+//! style hint on its following turns. The hint decays after a few turns without
+//! negative feedback.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// User feedback on a persona's answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackKind {
+    TooLong,
+    TooShort,
+    OffTopic,
+    Great,
+}
+
+impl FeedbackKind {
+    /// Instruction given to the persona once this feedback crosses the threshold.
+    ///
+    /// `None` for positive feedback, which never triggers a hint.
+    pub fn instruction(self) -> Option<&'static str> {
+        match self {
+            Self::TooLong => {
+                Some("Your recent answers were marked too long; aim for half the length.")
+            }
+            Self::TooShort => Some(
+                "Your recent answers were marked too short; give more detail and explain your reasoning.",
+            ),
+            Self::OffTopic => Some(
+                "Your recent answers were marked off-topic; stay focused on the user's latest request.",
+            ),
+            Self::Great => None,
+        }
+    }
+
+    fn is_negative(self) -> bool {
+        self.instruction().is_some()
+    }
+}
+
+/// Thresholds of the calibration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalibrationPolicy {
+    /// Number of recent turns feedback is counted over.
+    pub window_turns: usize,
+    /// Feedback of one kind within the window that triggers a hint.
+    pub threshold: usize,
+    /// Turns without negative feedback after which a hint is dropped.
+    pub decay_turns: usize,
+}
+
+impl Default for CalibrationPolicy {
+    fn default() -> Self {
+        Self {
+            window_turns: 10,
+            threshold: 3,
+            decay_turns: 3,
+        }
+    }
+}
+
+/// A change of a persona's style hint, recorded for auditing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum CalibrationEvent {
+    /// Feedback crossed the threshold; the hint applies from the next turn.
+    Triggered {
+        persona_id: String,
+        kind: FeedbackKind,
+    },
+    /// The hint was dropped after turns without negative feedback.
+    Decayed {
+        persona_id: String,
+        kind: FeedbackKind,
+    },
+}
+
+/// Feedback counted for one persona.
+#[derive(Debug, Clone, Default)]
+struct PersonaCalibration {
+    /// Feedback per recent turn, oldest first.
+    turns: VecDeque<Vec<FeedbackKind>>,
+    active_hint: Option<FeedbackKind>,
+    turns_since_negative: usize,
+}
+
+/// Calibration state of one persona, for display.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonaCalibrationState {
+    pub persona_id: String,
+    /// Feedback counts within the window.
+    pub feedback_counts: HashMap<FeedbackKind, usize>,
+    /// Feedback kind whose hint is currently applied.
+    pub active_hint: Option<FeedbackKind>,
+    /// Turns since the last negative feedback.
+    pub turns_since_negative: usize,
+}
+
+/// Style calibration of all personas in a session.
+#[derive(Debug, Clone, Default)]
+pub struct StyleCalibration {
+    policy: CalibrationPolicy,
+    personas: HashMap<String, PersonaCalibration>,
+}
+
+impl StyleCalibration {
+    pub fn new(policy: CalibrationPolicy) -> Self {
+        Self {
+            policy,
+            personas: HashMap::new(),
+        }
+    }
+
+    /// Records that `persona_id` answered a turn.
+    ///
+    /// Returns a [`CalibrationEvent::Decayed`] when the persona's hint expires.
+    pub fn record_turn(&mut self, persona_id: &str) -> Option<CalibrationEvent> {
+        let policy = self.policy;
+        let calibration = self.personas.entry(persona_id.to_string()).or_default();
+        calibration.turns.push_back(Vec::new());
+        while calibration.turns.len() > policy.window_turns {
+            calibration.turns.pop_front();
+        }
+
+        let kind = calibration.active_hint?;
+        calibration.turns_since_negative += 1;
+        if calibration.turns_since_negative < policy.decay_turns {
+            return None;
+        }
+        calibration.active_hint = None;
+        Some(CalibrationEvent::Decayed {
+            persona_id: persona_id.to_string(),
+            kind,
+        })
+    }
+
+    /// Records feedback on `persona_id`'s latest answer.
+    ///
+    /// Returns a [`CalibrationEvent::Triggered`] when the feedback crosses the
+    /// threshold and changes the persona's hint.
+    pub fn record_feedback(
+        &mut self,
+        persona_id: &str,
+        kind: FeedbackKind,
+    ) -> Option<CalibrationEvent> {
+        let policy = self.policy;
+        let calibration = self.personas.entry(persona_id.to_string()).or_default();
+        match calibration.turns.back_mut() {
+            Some(latest) => latest.push(kind),
+            None => calibration.turns.push_back(vec![kind]),
+        }
+        if !kind.is_negative() {
+            return None;
+        }
+
+        calibration.turns_since_negative = 0;
+        let count = calibration
+            .turns
+            .iter()
+            .flatten()
+            .filter(|k| **k == kind)
+            .count();
+        if count < policy.threshold || calibration.active_hint == Some(kind) {
+            return None;
+        }
+        calibration.active_hint = Some(kind);
+        Some(CalibrationEvent::Triggered {
+            persona_id: persona_id.to_string(),
+            kind,
+        })
+    }
+
+    /// Instruction to add to `persona_id`'s next payload, if any.
+    pub fn hint_for(&self, persona_id: &str) -> Option<&'static str> {
+        self.personas
+            .get(persona_id)?
+            .active_hint
+            .and_then(FeedbackKind::instruction)
+    }
+
+    /// Current state of every persona with recorded turns or feedback.
+    pub fn states(&self) -> Vec<PersonaCalibrationState> {
+        let mut states: Vec<PersonaCalibrationState> = self
+            .personas
+            .iter()
+            .map(|(persona_id, calibration)| {
+                let mut feedback_counts = HashMap::new();
+                for kind in calibration.turns.iter().flatten() {
+                    *feedback_counts.entry(*kind).or_insert(0) += 1;
+                }
+                PersonaCalibrationState {
+                    persona_id: persona_id.clone(),
+                    feedback_counts,
+                    active_hint: calibration.active_hint,
+                    turns_since_negative: calibration.turns_since_negative,
+                }
+            })
+            .collect();
+        states.sort_by(|a, b| a.persona_id.cmp(&b.persona_id));
+        states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer_with(calibration: &mut StyleCalibration, persona_id: &str, kind: FeedbackKind) {
+        calibration.record_turn(persona_id);
+        calibration.record_feedback(persona_id, kind);
+    }
+
+    #[test]
+    fn test_threshold_triggers_hint_for_that_persona_only() {
+        let mut calibration = StyleCalibration::default();
+        answer_with(&mut calibration, "mai", FeedbackKind::TooLong);
+        answer_with(&mut calibration, "ken", FeedbackKind::TooLong);
+        answer_with(&mut calibration, "mai", FeedbackKind::TooLong);
+        assert_eq!(calibration.hint_for("mai"), None);
+
+        calibration.record_turn("mai");
+        let event = calibration.record_feedback("mai", FeedbackKind::TooLong);
+        assert_eq!(
+            event,
+            Some(CalibrationEvent::Triggered {
+                persona_id: "mai".to_string(),
+                kind: FeedbackKind::TooLong,
+            })
+        );
+        assert!(calibration.hint_for("mai").unwrap().contains("too long"));
+        assert_eq!(calibration.hint_for("ken"), None);
+
+        // Further feedback of the same kind does not re-trigger
+        assert_eq!(
+            calibration.record_feedback("mai", FeedbackKind::TooLong),
+            None
+        );
+    }
+
+    #[test]
+    fn test_feedback_outside_window_is_not_counted() {
+        let mut calibration = StyleCalibration::default();
+        answer_with(&mut calibration, "mai", FeedbackKind::TooShort);
+        answer_with(&mut calibration, "mai", FeedbackKind::TooShort);
+        for _ in 0..10 {
+            calibration.record_turn("mai");
+        }
+        answer_with(&mut calibration, "mai", FeedbackKind::TooShort);
+        assert_eq!(calibration.hint_for("mai"), None);
+
+        // Positive feedback never triggers a hint
+        for _ in 0..5 {
+            answer_with(&mut calibration, "mai", FeedbackKind::Great);
+        }
+        assert_eq!(calibration.hint_for("mai"), None);
+    }
+
+    #[test]
+    fn test_hint_decays_after_turns_without_negative_feedback() {
+        let mut calibration = StyleCalibration::default();
+        for _ in 0..3 {
+            answer_with(&mut calibration, "mai", FeedbackKind::OffTopic);
+        }
+        assert!(calibration.hint_for("mai").is_some());
+
+        // Negative feedback restarts the decay countdown
+        calibration.record_turn("mai");
+        calibration.record_turn("mai");
+        calibration.record_feedback("mai", FeedbackKind::TooLong);
+        assert_eq!(calibration.record_turn("mai"), None);
+        assert_eq!(calibration.record_turn("mai"), None);
+        assert!(calibration.hint_for("mai").is_some());
+
+        assert_eq!(
+            calibration.record_turn("mai"),
+            Some(CalibrationEvent::Decayed {
+                persona_id: "mai".to_string(),
+                kind: FeedbackKind::OffTopic,
+            })
+        );
+        assert_eq!(calibration.hint_for("mai"), None);
+
+        let state = &calibration.states()[0];
+        assert_eq!(state.active_hint, None);
+        assert_eq!(state.feedback_counts.get(&FeedbackKind::OffTopic), Some(&3));
+    }
+}
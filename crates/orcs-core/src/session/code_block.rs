@@ -0,0 +1,89 @@
+//! Fenced code blocks in message content.
+
+/// A fenced code block found in a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Language from the opening fence's info string (e.g. `rust`), if any.
+    pub language: Option<String>,
+    /// The block's content, without the fences.
+    pub code: String,
+}
+
+/// Extracts the fenced code blocks of `content`, in order.
+///
+/// Blocks are fenced with three or more backticks; the closing fence must be at
+/// least as long as the opening one, so blocks can contain shorter fences. An
+/// unclosed block runs to the end of the content, as Markdown renders it.
+pub fn extract_code_blocks(content: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    // Opening fence length, language and lines of the block being read
+    let mut open: Option<(usize, Option<String>, Vec<&str>)> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let fence = trimmed.chars().take_while(|c| *c == '`').count();
+        match open.as_mut() {
+            Some((open_fence, _, _))
+                if fence >= *open_fence && trimmed[fence..].trim().is_empty() =>
+            {
+                let (_, language, lines) = open.take().unwrap();
+                blocks.push(CodeBlock {
+                    language,
+                    code: lines.join("\n"),
+                });
+            }
+            Some((_, _, lines)) => lines.push(line),
+            None if fence >= 3 => {
+                let info = trimmed[fence..].split_whitespace().next();
+                open = Some((fence, info.map(str::to_string), Vec::new()));
+            }
+            None => {}
+        }
+    }
+    if let Some((_, language, lines)) = open {
+        blocks.push(CodeBlock {
+            language,
+            code: lines.join("\n"),
+        });
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_blocks_in_order() {
+        let content =
+            "Change the parser:\n\n```rust\nfn parse() {}\n```\n\nThen run:\n```\ncargo test\n```";
+        assert_eq!(
+            extract_code_blocks(content),
+            vec![
+                CodeBlock {
+                    language: Some("rust".to_string()),
+                    code: "fn parse() {}".to_string(),
+                },
+                CodeBlock {
+                    language: None,
+                    code: "cargo test".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_longer_fences_contain_shorter_ones() {
+        let content = "````markdown\n```rust\nlet x = 1;\n```\n````";
+        let blocks = extract_code_blocks(content);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].code, "```rust\nlet x = 1;\n```");
+    }
+
+    #[test]
+    fn test_unclosed_block_runs_to_end() {
+        let blocks = extract_code_blocks("```toml\n[package]\nname = \"orcs\"");
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].code, "[package]\nname = \"orcs\"");
+    }
+}
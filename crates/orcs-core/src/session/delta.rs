@@ -0,0 +1,41 @@
+//! Changed sections of a session.
+//!
+//! Lets autosave write only what changed since the last save, e.g. append a
+//! new message instead of rewriting the whole session document.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Sections of a session that changed since it was last saved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionDelta {
+    /// Whether the session must be saved whole, e.g. because it was never
+    /// saved or an already saved message was edited
+    pub full: bool,
+    /// Whether fields other than messages changed (title, modes, participants, ...)
+    pub metadata: bool,
+    /// Whether the system messages changed
+    pub system_messages: bool,
+    /// Indices of the messages appended to each persona's history, keyed by persona ID
+    pub appended_messages: HashMap<String, Range<usize>>,
+}
+
+impl SessionDelta {
+    /// A delta requiring a full save.
+    pub fn full() -> Self {
+        Self {
+            full: true,
+            ..Self::default()
+        }
+    }
+
+    /// Returns whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        !self.full && !self.metadata && !self.system_messages && self.appended_messages.is_empty()
+    }
+
+    /// Returns whether messages were appended and nothing else changed.
+    pub fn is_append_only(&self) -> bool {
+        !self.full && !self.metadata && !self.system_messages && !self.appended_messages.is_empty()
+    }
+}
@@ -11,6 +11,9 @@ pub enum SessionEvent {
         content: String,
         #[serde(default)]
         attachments: Vec<String>,
+        /// Attach files the workspace's `.orcsignore` excludes anyway
+        #[serde(default)]
+        confirm_ignored: bool,
     },
     /// System-side message that should be persisted.
     SystemEvent {
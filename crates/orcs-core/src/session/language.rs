@@ -0,0 +1,162 @@
+//! Lightweight language detection for conversations.
+//!
+//! Guesses the primary language of a few messages from the scripts their
+//! letters are written in. It tells apart languages with distinct scripts
+//! (Japanese, Chinese, Korean, Russian, ...) and reports every Latin-script
+//! text as English, which is enough to pick a locale-appropriate prompt.
+
+use std::collections::HashMap;
+
+/// Number of recent user messages a conversation's language is detected from.
+pub const LANGUAGE_SAMPLE_MESSAGES: usize = 20;
+
+/// Fewest weighted letters needed before a guess is made.
+const MIN_SAMPLE_WEIGHT: usize = 8;
+
+/// Share of kana among CJK characters above which Han text is read as Japanese.
+const MIN_KANA_SHARE: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Script {
+    Latin,
+    Kana,
+    Han,
+    Hangul,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Thai,
+    Devanagari,
+}
+
+impl Script {
+    fn of(c: char) -> Option<Self> {
+        let script = match c as u32 {
+            0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Self::Kana,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Self::Han,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Self::Hangul,
+            0x0400..=0x04FF => Self::Cyrillic,
+            0x0370..=0x03FF => Self::Greek,
+            0x0600..=0x06FF => Self::Arabic,
+            0x0590..=0x05FF => Self::Hebrew,
+            0x0E00..=0x0E7F => Self::Thai,
+            0x0900..=0x097F => Self::Devanagari,
+            _ if c.is_ascii_alphabetic() => Self::Latin,
+            0x00C0..=0x024F if c.is_alphabetic() => Self::Latin,
+            _ => return None,
+        };
+        Some(script)
+    }
+
+    /// Weight of one character: a CJK or Hangul character carries about as
+    /// much text as several alphabetic letters.
+    fn weight(self) -> usize {
+        match self {
+            Self::Kana | Self::Han | Self::Hangul => 3,
+            _ => 1,
+        }
+    }
+}
+
+/// Detects the primary language of `texts` and returns its ISO 639-1 code.
+///
+/// Fenced code blocks and inline code are skipped, so pasted code does not
+/// outweigh the prose around it. Returns `None` when there is too little text
+/// to tell.
+pub fn detect_language<'a>(texts: impl IntoIterator<Item = &'a str>) -> Option<&'static str> {
+    let mut weights: HashMap<Script, usize> = HashMap::new();
+    for text in texts {
+        for c in prose(text).chars() {
+            if let Some(script) = Script::of(c) {
+                *weights.entry(script).or_default() += script.weight();
+            }
+        }
+    }
+    let weight = |script| weights.get(&script).copied().unwrap_or(0);
+
+    // Japanese mixes kana with Han characters, so both count towards it
+    let kana = weight(Script::Kana);
+    let cjk = kana + weight(Script::Han);
+    let mut totals: Vec<(&'static str, usize)> = vec![
+        ("en", weight(Script::Latin)),
+        ("ko", weight(Script::Hangul)),
+        ("ru", weight(Script::Cyrillic)),
+        ("el", weight(Script::Greek)),
+        ("ar", weight(Script::Arabic)),
+        ("he", weight(Script::Hebrew)),
+        ("th", weight(Script::Thai)),
+        ("hi", weight(Script::Devanagari)),
+    ];
+    let cjk_code = if kana as f64 >= cjk as f64 * MIN_KANA_SHARE {
+        "ja"
+    } else {
+        "zh"
+    };
+    totals.push((cjk_code, cjk));
+
+    let total: usize = totals.iter().map(|(_, weight)| weight).sum();
+    if total < MIN_SAMPLE_WEIGHT {
+        return None;
+    }
+    totals
+        .into_iter()
+        .max_by_key(|(_, weight)| *weight)
+        .map(|(code, _)| code)
+}
+
+/// Returns `text` without fenced code blocks and inline code spans.
+fn prose(text: &str) -> String {
+    let mut prose = String::with_capacity(text.len());
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        for (i, segment) in line.split('`').enumerate() {
+            if i % 2 == 0 {
+                prose.push_str(segment);
+            }
+        }
+        prose.push('\n');
+    }
+    prose
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_japanese_and_english_conversations() {
+        let japanese = [
+            "このAPIのエラー処理を見直したいです。",
+            "リトライの回数は設定ファイルで変えられるようにしてください。",
+            "`retry_count` を使っているところも確認してもらえますか？",
+            "```rust\nfn retry(count: usize) -> Result<(), Error> { todo!() }\n```\nこの関数です。",
+        ];
+        assert_eq!(detect_language(japanese), Some("ja"));
+
+        let english = [
+            "Can we revisit how the API handles errors?",
+            "The retry count should be configurable from the settings file.",
+            "Please also check where 日本語 labels are rendered.",
+        ];
+        assert_eq!(detect_language(english), Some("en"));
+    }
+
+    #[test]
+    fn test_han_without_kana_is_chinese() {
+        assert_eq!(detect_language(["我们需要重新设计错误处理。"]), Some("zh"));
+    }
+
+    #[test]
+    fn test_too_little_text_is_undetermined() {
+        assert_eq!(detect_language(["ok", "👍"]), None);
+        assert_eq!(detect_language([]), None);
+    }
+}
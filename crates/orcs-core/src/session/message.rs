@@ -3,6 +3,8 @@
 //! This module contains types for representing messages in a conversation,
 //! including roles and message content.
 
+use super::provenance::SourceRef;
+use super::usage::TurnStats;
 use schema_bridge::SchemaBridge;
 use serde::{Deserialize, Serialize};
 use version_migrate::DeriveQueryable as Queryable;
@@ -34,6 +36,12 @@ pub enum SystemEventType {
     WorkspaceSwitched,
     /// Generic system notification.
     Notification,
+    /// A persona's style hint from user feedback was applied or dropped.
+    StyleCalibrationChanged,
+    /// An agent appended to the session pinboard.
+    PinboardUpdated,
+    /// Focus mode narrowed the responding participants, or ended.
+    FocusChanged,
 }
 
 /// Severity level for error messages.
@@ -91,6 +99,46 @@ pub struct MessageMetadata {
     /// Debug information for LLM interactions (only present when debug mode is enabled).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub llm_debug_info: Option<LlmDebugInfo>,
+
+    /// Assistant output recovered after the app exited mid-turn.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub recovered_partial: bool,
+
+    /// Always kept in agent context, even for personas that receive no history.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+
+    /// Split out of another persona's reply that spoke for several personas.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub split_from_single_response: bool,
+
+    /// IDs of tasks spawned from this message (see `Task::origin_message_timestamp`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawned_task_ids: Vec<String>,
+
+    /// For agent turns reacting to another agent's turn in the same round: the
+    /// persona ID of the agent replied to. `None` for replies to the user or
+    /// the system.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_response_to: Option<String>,
+
+    /// For context messages agents may quote (shell output, search results,
+    /// web clips, attached files): the source this message provides.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceRef>,
+
+    /// For agent turns: the sources the turn copied passages from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<SourceRef>,
+
+    /// For user messages whose long paste was stored as an attachment: the
+    /// file holding the full text. The message content is then an excerpt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_content_path: Option<String>,
+
+    /// For agent turns: duration, token usage and estimated cost of the turn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_stats: Option<TurnStats>,
 }
 
 fn default_true() -> bool {
@@ -121,3 +169,43 @@ pub struct ConversationMessage {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub attachments: Vec<String>,
 }
+
+impl ConversationMessage {
+    /// Returns true if this message was created at `timestamp`.
+    ///
+    /// See [`timestamps_match`].
+    pub fn has_timestamp(&self, timestamp: &str) -> bool {
+        timestamps_match(&self.timestamp, timestamp)
+    }
+}
+
+/// A message of the merged conversation, with its resolved author.
+///
+/// Built by [`Session::conversation_turns`](super::Session::conversation_turns).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationTurn {
+    /// Key of the history the message belongs to (persona ID, user name, or `"system"`)
+    pub author_id: String,
+    /// Display name of the author
+    pub author: String,
+    /// The role of the message sender.
+    pub role: MessageRole,
+    /// The content of the message.
+    pub content: String,
+    /// Timestamp when the message was created (ISO 8601 format).
+    pub timestamp: String,
+    /// Attached files (file paths in workspace).
+    pub attachments: Vec<String>,
+    /// Additional metadata about the message.
+    pub metadata: MessageMetadata,
+}
+
+/// Returns true if two message timestamps denote the same message.
+///
+/// Timestamps are compared up to milliseconds (the first 23 characters), as
+/// the frontend sends ISO 8601 with a `Z` suffix while the backend stores
+/// RFC 3339 with microseconds and an offset.
+pub fn timestamps_match(a: &str, b: &str) -> bool {
+    a.get(..23).unwrap_or(a) == b.get(..23).unwrap_or(b)
+}
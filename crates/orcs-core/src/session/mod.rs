@@ -8,7 +8,15 @@
 //! - `model`: Core session domain model (`Session`)
 //! - `message`: Conversation message types (`MessageRole`, `ConversationMessage`)
 //! - `app_mode`: Session state types (`AppMode`, `Plan`)
+//! - `code_block`: Fenced code blocks in message content (`CodeBlock`)
+//! - `calibration`: Persona style calibration from user feedback (`StyleCalibration`)
 //! - `user_input`: User input types (`UserInput`)
+//! - `language`: Script-based language detection (`detect_language`)
+//! - `provenance`: Sources agents copy from and reuse detection (`SourceRef`)
+//! - `recovery`: Partial output of interrupted turns (`PartialTurn`)
+//! - `delta`: Sections changed since the last save (`SessionDelta`)
+//! - `untrusted`: Quarantine markers for third-party content (`wrap_untrusted`)
+//! - `usage`: Per-turn duration, tokens and cost (`TurnStats`, `CostBreakdown`)
 //! - `repository`: Repository trait for session persistence
 //!
 //! # Usage
@@ -20,22 +28,48 @@
 //! ```
 
 mod app_mode;
+mod calibration;
+mod code_block;
+mod delta;
 mod event;
 mod interaction_manager_trait;
+mod language;
 mod message;
 mod model;
+mod provenance;
+mod recovery;
 mod repository;
+mod untrusted;
+mod usage;
 mod user_input;
 
 // Re-export public API
 pub use app_mode::{AppMode, ConversationMode, Plan};
+pub use calibration::{
+    CalibrationEvent, CalibrationPolicy, FeedbackKind, PersonaCalibrationState, StyleCalibration,
+};
+pub use code_block::{CodeBlock, extract_code_blocks};
+pub use delta::SessionDelta;
 pub use event::{ModeratorAction, SessionEvent};
 pub use interaction_manager_trait::InteractionManagerTrait;
+pub use language::{LANGUAGE_SAMPLE_MESSAGES, detect_language};
 pub use message::{
-    ConversationMessage, ErrorSeverity, MessageMetadata, MessageRole, SystemEventType,
+    ConversationMessage, ConversationTurn, ErrorSeverity, LlmDebugInfo, MessageMetadata,
+    MessageRole, SystemEventType, timestamps_match,
 };
 pub use model::{
-    AutoChatConfig, ContextMode, PLACEHOLDER_WORKSPACE_ID, SandboxState, Session, StopCondition,
+    AutoChatConfig, ContextMode, DEFAULT_PASTE_ATTACHMENT_THRESHOLD, LEGACY_ERROR_HISTORY_KEY,
+    PLACEHOLDER_WORKSPACE_ID, SandboxState, Session, SessionSummary, StopCondition,
+};
+pub use provenance::{
+    MATCH_WINDOW_CHARS, MAX_COMPARED_CHARS_PER_TURN, MAX_REPLY_CHARS, MAX_SOURCE_CHARS,
+    MAX_SOURCES_PER_TURN, SEARCH_RESULTS_PREFIX, SourceKind, SourceRef, WEB_CLIP_PREFIX,
+    detect_reused_sources,
 };
+pub use recovery::{PartialChunk, PartialTurn};
 pub use repository::SessionRepository;
+pub use untrusted::{
+    UNTRUSTED_CONTENT_CLOSE, UNTRUSTED_CONTENT_GUIDELINE, UNTRUSTED_CONTENT_OPEN, wrap_untrusted,
+};
+pub use usage::{CostBreakdown, CostTotals, DailyCost, PersonaCost, TurnStats, UNKNOWN_DAY};
 pub use user_input::UserInput;
@@ -4,7 +4,9 @@
 //! a user session in the application's domain layer.
 
 use super::app_mode::{AppMode, ConversationMode};
-use super::message::ConversationMessage;
+use super::message::{ConversationMessage, ConversationTurn};
+use super::usage::CostBreakdown;
+use crate::conversation_script::ScriptRun;
 use llm_toolkit::agent::dialogue::{ExecutionModel, TalkStyle};
 use schema_bridge::SchemaBridge;
 use serde::{Deserialize, Serialize};
@@ -14,6 +16,15 @@ use std::collections::HashMap;
 /// This will be replaced with the actual default workspace ID during bootstrap.
 pub const PLACEHOLDER_WORKSPACE_ID: &str = "___workspace_placeholder___";
 
+/// `persona_histories` key under which older versions stored error messages.
+///
+/// Errors now live in `system_messages`; see [`Session::relocate_legacy_error_history`].
+pub const LEGACY_ERROR_HISTORY_KEY: &str = "Error";
+
+/// Length in characters above which a non-prose paste becomes an attachment
+/// unless the session sets its own threshold.
+pub const DEFAULT_PASTE_ATTACHMENT_THRESHOLD: usize = 8_000;
+
 /// Configuration for AutoChat mode.
 ///
 /// AutoChat enables automatic multi-round dialogue where agents continue
@@ -26,6 +37,15 @@ pub struct AutoChatConfig {
     pub stop_condition: StopCondition,
     /// Enable WebSearch during auto-chat
     pub web_search_enabled: bool,
+    /// Phrases that end AutoChat when any agent says them (case-insensitive substring)
+    #[serde(default)]
+    pub stop_phrases: Vec<String>,
+    /// Delay between iterations in milliseconds (500ms when unset)
+    #[serde(default)]
+    pub iteration_delay_ms: Option<u64>,
+    /// Back off after rate limits and shorten the wait by each iteration's duration
+    #[serde(default)]
+    pub adaptive_delay: bool,
 }
 
 impl Default for AutoChatConfig {
@@ -34,7 +54,36 @@ impl Default for AutoChatConfig {
             max_iterations: 5,
             stop_condition: StopCondition::IterationCount,
             web_search_enabled: true,
+            stop_phrases: Vec::new(),
+            iteration_delay_ms: None,
+            adaptive_delay: false,
+        }
+    }
+}
+
+impl AutoChatConfig {
+    /// Caps `max_iterations` at `limit`.
+    ///
+    /// Returns the requested value when it exceeded the limit and was clamped.
+    pub fn clamp_max_iterations(&mut self, limit: i32) -> Option<i32> {
+        if self.max_iterations <= limit {
+            return None;
         }
+        let requested = self.max_iterations;
+        self.max_iterations = limit;
+        Some(requested)
+    }
+
+    /// Returns the first configured stop phrase contained in `content`, if any.
+    ///
+    /// Matching is a case-insensitive substring match; blank phrases are ignored.
+    pub fn matching_stop_phrase(&self, content: &str) -> Option<&str> {
+        let content = content.to_lowercase();
+        self.stop_phrases
+            .iter()
+            .map(|phrase| phrase.trim())
+            .filter(|phrase| !phrase.is_empty())
+            .find(|phrase| content.contains(&phrase.to_lowercase()))
     }
 }
 
@@ -72,13 +121,18 @@ pub struct SandboxState {
 /// Controls the amount of system context provided to AI agents:
 /// - Rich: Full context with all system extensions (SlashCommands, TalkStyle, etc.)
 /// - Clean: Minimal context with Expertise only, no system extensions
+///
+/// The conversation-mode instruction (e.g. the Brief length limit) shapes the
+/// output rather than adding context, so it applies in both modes. Sessions can
+/// turn it off for Clean mode with `Session::apply_conversation_mode_in_clean`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, SchemaBridge)]
 #[serde(rename_all = "snake_case")]
 pub enum ContextMode {
     /// Full context: all system extensions enabled (default)
     #[default]
     Rich,
-    /// Clean context: expertise only, no system extensions
+    /// Clean context: expertise only, no system extensions (the conversation-mode
+    /// instruction still applies unless the session opts out)
     Clean,
 }
 
@@ -163,7 +217,8 @@ pub struct Session {
     /// Participant persona ID to name mapping for display
     #[serde(default)]
     pub participants: HashMap<String, String>,
-    /// Participant persona ID to icon mapping for display
+    /// Participant persona ID to icon mapping for display: an emoji, or
+    /// `asset:<file>` for an uploaded image (see `Persona::display_icon`)
     #[serde(default)]
     pub participant_icons: HashMap<String, String>,
     /// Participant persona ID to base color mapping for UI theming
@@ -206,8 +261,207 @@ pub struct Session {
     /// Used for differential sync - only messages after this timestamp are synced
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_memory_sync_at: Option<String>,
+    /// Whether a background dialogue is still generating responses for this session
+    #[serde(default)]
+    pub is_generating: bool,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    #[serde(default)]
+    pub participant_suggestions_enabled: bool,
+    /// Progress of the conversation script running in this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_run: Option<ScriptRun>,
+    /// Participants answering while focus mode is on (None: everyone).
+    /// The rest stay in the roster and the participants listing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused_participant_ids: Option<Vec<String>>,
+    /// Seed passed to backends that support one (OpenAI), for reproducible replies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u64>,
+    /// Whether Rich-mode turns tell agents the current time and how long ago
+    /// the user last wrote
+    #[serde(default = "default_clock_context_enabled")]
+    pub clock_context_enabled: bool,
+    /// Whether fenced `plan` blocks in agent replies are offered for confirmation
+    #[serde(default)]
+    pub plan_detection_enabled: bool,
+    /// Whether long non-prose user pastes are stored as a workspace file and
+    /// sent as an excerpt plus attachment
+    #[serde(default = "default_paste_attachment_enabled")]
+    pub paste_attachment_enabled: bool,
+    /// Length in characters above which a paste is turned into an attachment
+    #[serde(default = "default_paste_attachment_threshold")]
+    pub paste_attachment_threshold: usize,
+}
+
+impl Session {
+    /// Moves error messages stored under [`LEGACY_ERROR_HISTORY_KEY`] into
+    /// `system_messages`, keeping those in timestamp order.
+    ///
+    /// Old sessions clean themselves up when loaded; they are written in the
+    /// new layout on their next save. Returns `true` if anything was moved.
+    pub fn relocate_legacy_error_history(&mut self) -> bool {
+        let Some(errors) = self.persona_histories.remove(LEGACY_ERROR_HISTORY_KEY) else {
+            return false;
+        };
+        self.system_messages.extend(errors);
+        self.system_messages
+            .sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        self.active_participant_ids
+            .retain(|id| id != LEGACY_ERROR_HISTORY_KEY);
+        self.participants.remove(LEGACY_ERROR_HISTORY_KEY);
+        self.participant_icons.remove(LEGACY_ERROR_HISTORY_KEY);
+        self.participant_colors.remove(LEGACY_ERROR_HISTORY_KEY);
+        self.participant_backends.remove(LEGACY_ERROR_HISTORY_KEY);
+        self.participant_models.remove(LEGACY_ERROR_HISTORY_KEY);
+        true
+    }
+
+    /// Drops system messages that repeat the one right before them (same role,
+    /// content, metadata and attachments; timestamps may differ), such as a
+    /// burst of identical errors. Returns the number of messages dropped.
+    pub fn dedup_consecutive_system_messages(&mut self) -> usize {
+        let before = self.system_messages.len();
+        self.system_messages.dedup_by(|next, prev| {
+            next.role == prev.role
+                && next.content == prev.content
+                && next.metadata == prev.metadata
+                && next.attachments == prev.attachments
+        });
+        before - self.system_messages.len()
+    }
+
+    /// Merges the persona histories and system messages into one conversation
+    /// in chronological order.
+    ///
+    /// Authors are resolved through `participants`, falling back to the history
+    /// key; system messages are authored by `"System"`. Messages with equal
+    /// timestamps keep a stable order (by history key, then position).
+    pub fn conversation_turns(&self) -> Vec<ConversationTurn> {
+        let mut authors: Vec<&String> = self.persona_histories.keys().collect();
+        authors.sort();
+        let histories = authors.into_iter().flat_map(|author_id| {
+            let author = self
+                .participants
+                .get(author_id)
+                .unwrap_or(author_id)
+                .as_str();
+            self.persona_histories[author_id]
+                .iter()
+                .map(move |message| (author_id.as_str(), author, message))
+        });
+        let system = self
+            .system_messages
+            .iter()
+            .map(|message| ("system", "System", message));
+
+        let mut turns: Vec<(
+            Option<chrono::DateTime<chrono::FixedOffset>>,
+            ConversationTurn,
+        )> = histories
+            .chain(system)
+            .map(|(author_id, author, message)| {
+                (
+                    chrono::DateTime::parse_from_rfc3339(&message.timestamp).ok(),
+                    ConversationTurn {
+                        author_id: author_id.to_string(),
+                        author: author.to_string(),
+                        role: message.role.clone(),
+                        content: message.content.clone(),
+                        timestamp: message.timestamp.clone(),
+                        attachments: message.attachments.clone(),
+                        metadata: message.metadata.clone(),
+                    },
+                )
+            })
+            .collect();
+        // Unparseable timestamps sort first, like an empty timestamp would
+        turns.sort_by_key(|(time, _)| *time);
+        turns.into_iter().map(|(_, turn)| turn).collect()
+    }
+
+    /// Duration, token usage and estimated cost of the session's agent turns,
+    /// per persona and per day.
+    pub fn cost_breakdown(&self) -> CostBreakdown {
+        CostBreakdown::from_histories(&self.persona_histories, &self.participants)
+    }
+
+    /// Finds the message created at `timestamp` (see
+    /// [`ConversationMessage::has_timestamp`]), with the key of the history it
+    /// belongs to (the author, or `"system"` for system messages).
+    pub fn find_message(&self, timestamp: &str) -> Option<(&str, &ConversationMessage)> {
+        self.persona_histories
+            .iter()
+            .flat_map(|(author, messages)| {
+                messages
+                    .iter()
+                    .map(move |message| (author.as_str(), message))
+            })
+            .chain(
+                self.system_messages
+                    .iter()
+                    .map(|message| ("system", message)),
+            )
+            .find(|(_, message)| message.has_timestamp(timestamp))
+    }
+}
+
+/// Lightweight session metadata for listings that don't need message contents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    pub id: String,
+    pub title: String,
+    pub workspace_id: String,
+    /// Last update timestamp (ISO 8601 format)
+    pub updated_at: String,
+}
+
+impl From<&Session> for SessionSummary {
+    fn from(session: &Session) -> Self {
+        Self {
+            id: session.id.clone(),
+            title: session.title.clone(),
+            workspace_id: session.workspace_id.clone(),
+            updated_at: session.updated_at.clone(),
+        }
+    }
 }
 
 fn default_execution_strategy() -> ExecutionModel {
     ExecutionModel::Broadcast
 }
+
+fn default_response_splitting_enabled() -> bool {
+    true
+}
+
+fn default_apply_conversation_mode_in_clean() -> bool {
+    true
+}
+
+fn default_clock_context_enabled() -> bool {
+    true
+}
+
+fn default_paste_attachment_enabled() -> bool {
+    true
+}
+
+fn default_paste_attachment_threshold() -> usize {
+    DEFAULT_PASTE_ATTACHMENT_THRESHOLD
+}
@@ -0,0 +1,341 @@
+//! Provenance of content agents copy from context messages.
+//!
+//! Context messages agents may quote (shell output, search results, web clips,
+//! notes backed by attached files) are tagged with a [`SourceRef`] when they
+//! are created. After each agent turn, [`detect_reused_sources`] looks for
+//! passages the turn copied verbatim or near-verbatim from those sources, so
+//! the turn can record where its claims came from.
+//!
+//! Detection is deliberately conservative: a source counts as reused only if
+//! the turn shares a run of [`MATCH_WINDOW_CHARS`] normalized characters with
+//! it. Paraphrases are missed, but common phrases do not produce false
+//! matches. The work per turn is bounded by the `MAX_*` limits below.
+
+use schema_bridge::SchemaBridge;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Start of the System message `/search` posts its results in.
+pub const SEARCH_RESULTS_PREFIX: &str = "Search results for ";
+
+/// Start of the `context_info` message announcing a web clip.
+pub const WEB_CLIP_PREFIX: &str = "Web clip: ";
+
+/// Length of the normalized character run a turn must share with a source.
+pub const MATCH_WINDOW_CHARS: usize = 60;
+
+/// Most recent sources compared against a turn.
+pub const MAX_SOURCES_PER_TURN: usize = 20;
+
+/// Normalized characters of a single source that are compared.
+pub const MAX_SOURCE_CHARS: usize = 20_000;
+
+/// Normalized source characters compared per turn, across all sources.
+pub const MAX_COMPARED_CHARS_PER_TURN: usize = 100_000;
+
+/// Normalized characters of a turn that are searched for copied passages.
+pub const MAX_REPLY_CHARS: usize = 20_000;
+
+/// Longest label kept for a source, in characters.
+const MAX_LABEL_CHARS: usize = 80;
+
+/// Base of the rolling hash over character windows.
+const HASH_BASE: u64 = 1_000_003;
+
+/// What a source message carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    /// Output of a shell command.
+    ShellOutput,
+    /// Results of `/search`.
+    SearchResult,
+    /// A clipped web page.
+    WebClip,
+    /// A note whose content is an attached workspace file.
+    Attachment,
+    /// Other context info.
+    ContextInfo,
+}
+
+/// A source agents may copy from, identified by a stable ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceRef {
+    /// Stable identifier assigned when the source message was created
+    pub source_id: String,
+    /// What the source carries
+    pub kind: SourceKind,
+    /// Short human-readable description (command, page title, file name, ...)
+    pub label: String,
+}
+
+impl SourceRef {
+    /// Creates the source of a new System message, if agents may quote it.
+    ///
+    /// Shell output and `/search` results are sources whatever their message
+    /// type; other messages only if they are `context_info`. Returns `None` for
+    /// plain notifications.
+    pub fn for_system_message(
+        message_type: Option<&str>,
+        content: &str,
+        attachments: &[String],
+    ) -> Option<Self> {
+        let (kind, label) = if message_type == Some("shell_output") {
+            (SourceKind::ShellOutput, first_line(content))
+        } else if content.starts_with(SEARCH_RESULTS_PREFIX) {
+            (SourceKind::SearchResult, first_line(content))
+        } else if message_type != Some("context_info") {
+            return None;
+        } else if let Some(title) = content.strip_prefix(WEB_CLIP_PREFIX) {
+            (SourceKind::WebClip, first_line(title))
+        } else if let Some(path) = attachments.first() {
+            let name = std::path::Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            (SourceKind::Attachment, name)
+        } else {
+            (SourceKind::ContextInfo, first_line(content))
+        };
+
+        Some(Self {
+            source_id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            label,
+        })
+    }
+}
+
+/// First non-empty line of `text`, shortened to a label.
+fn first_line(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() > MAX_LABEL_CHARS {
+        let mut label: String = line.chars().take(MAX_LABEL_CHARS - 1).collect();
+        label.push('…');
+        label
+    } else {
+        line.to_string()
+    }
+}
+
+/// Lowercases `text` and reduces every run of non-alphanumeric characters
+/// (whitespace, punctuation, Markdown markup) to one space, keeping at most
+/// `limit` characters.
+fn normalize(text: &str, limit: usize) -> Vec<char> {
+    let mut normalized = Vec::new();
+    let mut separated = false;
+    for c in text.chars() {
+        if normalized.len() >= limit {
+            break;
+        }
+        if c.is_alphanumeric() {
+            if separated && !normalized.is_empty() {
+                normalized.push(' ');
+            }
+            separated = false;
+            normalized.extend(c.to_lowercase());
+        } else {
+            separated = true;
+        }
+    }
+    normalized.truncate(limit);
+    normalized
+}
+
+/// Rolling hashes of every `MATCH_WINDOW_CHARS`-long window of `chars`, with
+/// the window's start index.
+fn window_hashes(chars: &[char]) -> impl Iterator<Item = (u64, usize)> + '_ {
+    let leading_power =
+        (1..MATCH_WINDOW_CHARS).fold(1u64, |power, _| power.wrapping_mul(HASH_BASE));
+    let mut hash = chars
+        .iter()
+        .take(MATCH_WINDOW_CHARS)
+        .fold(0u64, |hash, &c| {
+            hash.wrapping_mul(HASH_BASE).wrapping_add(c as u64)
+        });
+    let windows = (chars.len() + 1).saturating_sub(MATCH_WINDOW_CHARS);
+    (0..windows).map(move |start| {
+        if start > 0 {
+            let outgoing = chars[start - 1] as u64;
+            let incoming = chars[start + MATCH_WINDOW_CHARS - 1] as u64;
+            hash = hash
+                .wrapping_sub(outgoing.wrapping_mul(leading_power))
+                .wrapping_mul(HASH_BASE)
+                .wrapping_add(incoming);
+        }
+        (hash, start)
+    })
+}
+
+/// Returns the sources `reply` copied from, in the order given.
+///
+/// `sources` pairs each source with its text and should be ordered newest
+/// first: only the first [`MAX_SOURCES_PER_TURN`] are compared, each up to
+/// [`MAX_SOURCE_CHARS`] and all together up to
+/// [`MAX_COMPARED_CHARS_PER_TURN`] normalized characters. A hash match is
+/// confirmed by comparing the characters, so hash collisions cannot produce
+/// false matches.
+pub fn detect_reused_sources<'a>(
+    reply: &str,
+    sources: impl IntoIterator<Item = (&'a SourceRef, &'a str)>,
+) -> Vec<SourceRef> {
+    let reply = normalize(reply, MAX_REPLY_CHARS);
+    if reply.len() < MATCH_WINDOW_CHARS {
+        return vec![];
+    }
+    let reply_windows: HashMap<u64, usize> = window_hashes(&reply).collect();
+
+    let mut budget = MAX_COMPARED_CHARS_PER_TURN;
+    let mut seen = HashSet::new();
+    let mut reused = Vec::new();
+    for (source, text) in sources.into_iter().take(MAX_SOURCES_PER_TURN) {
+        if budget < MATCH_WINDOW_CHARS {
+            break;
+        }
+        let text = normalize(text, MAX_SOURCE_CHARS.min(budget));
+        budget -= text.len();
+        if seen.contains(&source.source_id) {
+            continue;
+        }
+
+        let copied = window_hashes(&text).any(|(hash, start)| {
+            reply_windows.get(&hash).is_some_and(|&reply_start| {
+                reply[reply_start..reply_start + MATCH_WINDOW_CHARS]
+                    == text[start..start + MATCH_WINDOW_CHARS]
+            })
+        });
+        if copied {
+            seen.insert(source.source_id.clone());
+            reused.push(source.clone());
+        }
+    }
+    reused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNIPPET: &str = "The deployment pipeline retries failed uploads three times \
+                           before paging the on-call engineer";
+
+    fn source(id: &str) -> SourceRef {
+        SourceRef {
+            source_id: id.to_string(),
+            kind: SourceKind::ContextInfo,
+            label: id.to_string(),
+        }
+    }
+
+    /// Filler text without any long run in common with the snippet.
+    fn filler(words: usize) -> String {
+        (0..words)
+            .map(|i| format!("w{}", i))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_sources_are_classified_by_type_and_content() {
+        let shell = SourceRef::for_system_message(Some("shell_output"), "$ ls\nsrc", &[]).unwrap();
+        assert_eq!(shell.kind, SourceKind::ShellOutput);
+        assert_eq!(shell.label, "$ ls");
+
+        let search = SourceRef::for_system_message(
+            Some("info"),
+            "Search results for \"retry\"\n\n• a.rs",
+            &[],
+        )
+        .unwrap();
+        assert_eq!(search.kind, SourceKind::SearchResult);
+
+        let clip = SourceRef::for_system_message(
+            Some("context_info"),
+            "Web clip: Release notes\nURL: https://example.com",
+            &["/ws/uploaded/release-notes.md".to_string()],
+        )
+        .unwrap();
+        assert_eq!(clip.kind, SourceKind::WebClip);
+        assert_eq!(clip.label, "Release notes");
+
+        let note = SourceRef::for_system_message(
+            Some("context_info"),
+            "Decision record",
+            &["/ws/decisions/adr.md".to_string()],
+        )
+        .unwrap();
+        assert_eq!(note.kind, SourceKind::Attachment);
+        assert_eq!(note.label, "adr.md");
+
+        assert!(SourceRef::for_system_message(Some("info"), "Mode changed", &[]).is_none());
+        assert_ne!(shell.source_id, search.source_id);
+    }
+
+    #[test]
+    fn test_detects_planted_verbatim_snippet() {
+        let log = source("log");
+        let other = source("other");
+        let log_text = format!("{}\n\n{}.\n{}", filler(50), SNIPPET, filler(50));
+        let other_text = filler(200);
+        // Markdown emphasis and different casing still count as verbatim
+        let reply = format!(
+            "Looking at the log: **{}**. So we should be fine.",
+            SNIPPET.to_uppercase()
+        );
+
+        let reused = detect_reused_sources(
+            &reply,
+            [(&other, other_text.as_str()), (&log, log_text.as_str())],
+        );
+        assert_eq!(reused, vec![log]);
+    }
+
+    #[test]
+    fn test_short_overlaps_and_paraphrases_are_not_matches() {
+        let log = source("log");
+        let reply = "The deployment pipeline retries failed uploads, \
+                     and eventually someone on call gets paged.";
+        assert!(detect_reused_sources(reply, [(&log, SNIPPET)]).is_empty());
+        assert!(detect_reused_sources("The deployment pipeline", [(&log, SNIPPET)]).is_empty());
+    }
+
+    #[test]
+    fn test_comparison_work_is_capped() {
+        let reply = format!("As the output says: {}", SNIPPET);
+
+        // Text beyond the per-source cap is not compared
+        let huge = source("huge");
+        let huge_text = format!("{}{}", "x".repeat(MAX_SOURCE_CHARS), SNIPPET);
+        assert!(detect_reused_sources(&reply, [(&huge, huge_text.as_str())]).is_empty());
+
+        // Only the most recent sources are compared
+        let planted = source("planted");
+        let recent: Vec<SourceRef> = (0..MAX_SOURCES_PER_TURN)
+            .map(|i| source(&format!("recent-{}", i)))
+            .collect();
+        let mut sources: Vec<(&SourceRef, &str)> =
+            recent.iter().map(|s| (s, "unrelated")).collect();
+        sources.push((&planted, SNIPPET));
+        assert!(detect_reused_sources(&reply, sources).is_empty());
+
+        // Once the per-turn budget is spent, later sources are skipped
+        let big_text = "y".repeat(MAX_SOURCE_CHARS);
+        let big: Vec<SourceRef> = (0..MAX_COMPARED_CHARS_PER_TURN / MAX_SOURCE_CHARS)
+            .map(|i| source(&format!("big-{}", i)))
+            .collect();
+        let mut sources: Vec<(&SourceRef, &str)> =
+            big.iter().map(|s| (s, big_text.as_str())).collect();
+        sources.push((&planted, SNIPPET));
+        assert!(detect_reused_sources(&reply, sources.clone()).is_empty());
+
+        // The same snippet is found when within the limits
+        sources.truncate(1);
+        sources.push((&planted, SNIPPET));
+        assert_eq!(detect_reused_sources(&reply, sources), vec![planted]);
+    }
+}
@@ -0,0 +1,118 @@
+//! Recovery of assistant output streamed during an interrupted turn.
+//!
+//! While a turn streams, the shown content is mirrored into a [`PartialTurn`]
+//! that is written to a per-session recovery file. If the app dies before the
+//! turn is persisted, the next load of the session turns the partial turn into
+//! assistant messages flagged with `recovered_partial`.
+
+use super::message::{ConversationMessage, MessageMetadata, MessageRole, SystemEventType};
+use super::model::Session;
+use serde::{Deserialize, Serialize};
+
+/// A chunk of streamed assistant output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialChunk {
+    /// Display name of the participant that produced the chunk.
+    pub author: String,
+    pub content: String,
+    /// When the chunk arrived (RFC 3339).
+    pub timestamp: String,
+}
+
+/// Streamed output of an in-flight turn that has not been persisted yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialTurn {
+    pub session_id: String,
+    /// Chunks in arrival order.
+    pub chunks: Vec<PartialChunk>,
+}
+
+impl PartialTurn {
+    pub fn new(session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Appends a chunk stamped with the current time.
+    pub fn push(&mut self, author: impl Into<String>, content: impl Into<String>) {
+        self.chunks.push(PartialChunk {
+            author: author.into(),
+            content: content.into(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+
+    /// Appends the recovered output to `session`'s history.
+    ///
+    /// Consecutive chunks from the same author become one assistant message.
+    /// Messages already present in the author's history (the turn was saved
+    /// before its recovery file was removed) are skipped. A system note is
+    /// added when anything was recovered.
+    ///
+    /// Returns the number of recovered messages.
+    pub fn apply_to(&self, session: &mut Session) -> usize {
+        let mut groups: Vec<(&str, String, &str)> = Vec::new();
+        for chunk in &self.chunks {
+            match groups.last_mut() {
+                Some((author, content, timestamp)) if *author == chunk.author => {
+                    content.push_str(&chunk.content);
+                    *timestamp = &chunk.timestamp;
+                }
+                _ => groups.push((&chunk.author, chunk.content.clone(), &chunk.timestamp)),
+            }
+        }
+
+        let mut recovered = 0;
+        for (author, content, timestamp) in groups {
+            if content.trim().is_empty() {
+                continue;
+            }
+            // History is keyed by persona ID; chunks carry the display name.
+            let key = session
+                .participants
+                .iter()
+                .find(|(_, name)| name.as_str() == author)
+                .map(|(id, _)| id.clone())
+                .unwrap_or_else(|| author.to_string());
+            let history = session.persona_histories.entry(key).or_default();
+            if history
+                .iter()
+                .any(|m| m.role == MessageRole::Assistant && m.content == content)
+            {
+                continue;
+            }
+            history.push(ConversationMessage {
+                role: MessageRole::Assistant,
+                content,
+                timestamp: timestamp.to_string(),
+                metadata: MessageMetadata {
+                    include_in_dialogue: true,
+                    recovered_partial: true,
+                    ..Default::default()
+                },
+                attachments: vec![],
+            });
+            recovered += 1;
+        }
+
+        if recovered > 0 {
+            session.system_messages.push(ConversationMessage {
+                role: MessageRole::System,
+                content: "応答の生成中にアプリが終了したため、表示済みの内容を復元しました"
+                    .to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                metadata: MessageMetadata {
+                    system_event_type: Some(SystemEventType::Notification),
+                    include_in_dialogue: false,
+                    ..Default::default()
+                },
+                attachments: vec![],
+            });
+        }
+        recovered
+    }
+}
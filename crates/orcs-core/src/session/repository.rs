@@ -2,9 +2,12 @@
 //!
 //! Defines the interface for session persistence operations.
 
-use super::model::Session;
+use super::delta::SessionDelta;
+use super::model::{Session, SessionSummary};
 use crate::error::Result;
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::ops::Range;
 
 /// An abstract repository for managing session persistence.
 ///
@@ -45,6 +48,69 @@ pub trait SessionRepository: Send + Sync {
     /// - `Err(_)`: Error occurred during save
     async fn save(&self, session: &Session) -> Result<()>;
 
+    /// Saves only the sections of a session that `delta` marks as changed.
+    ///
+    /// Deltas that only append messages go through `append_messages`, and
+    /// deltas that leave the histories alone through `save_metadata`; anything
+    /// else is a full `save`. Backends without partial writes keep the
+    /// defaults of those methods, which save the whole session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The complete current state of the session
+    /// * `delta` - Sections changed since the session was last saved
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())`: Changes saved successfully (or nothing changed)
+    /// - `Err(_)`: Error occurred during save
+    async fn save_delta(&self, session: &Session, delta: &SessionDelta) -> Result<()> {
+        if delta.is_empty() {
+            return Ok(());
+        }
+        if delta.is_append_only() {
+            return self
+                .append_messages(session, &delta.appended_messages)
+                .await;
+        }
+        if !delta.full && delta.appended_messages.is_empty() {
+            return self.save_metadata(session).await;
+        }
+        self.save(session).await
+    }
+
+    /// Appends new messages to the end of stored persona histories.
+    ///
+    /// `appended` maps persona IDs to the indices of their new messages in
+    /// `session`, always a tail of the history. Delta-aware backends write only
+    /// those messages (and the session's `updated_at`), and should save the
+    /// whole session instead if a stored history is not exactly the part
+    /// before its new messages. The default implementation saves the whole
+    /// session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The complete current state of the session
+    /// * `appended` - Indices of the appended messages, keyed by persona ID
+    async fn append_messages(
+        &self,
+        session: &Session,
+        _appended: &HashMap<String, Range<usize>>,
+    ) -> Result<()> {
+        self.save(session).await
+    }
+
+    /// Rewrites every section of a stored session except the persona histories.
+    ///
+    /// The default implementation saves the whole session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The complete current state of the session
+    async fn save_metadata(&self, session: &Session) -> Result<()> {
+        self.save(session).await
+    }
+
     /// Deletes a session from storage.
     ///
     /// # Arguments
@@ -64,4 +130,67 @@ pub trait SessionRepository: Send + Sync {
     /// - `Ok(Vec<Session>)`: All stored sessions
     /// - `Err(_)`: Error occurred during listing
     async fn list_all(&self) -> Result<Vec<Session>>;
+
+    /// Lists the IDs of all stored sessions without loading their contents.
+    ///
+    /// Used for streaming operations (e.g., export) that load one session at a time.
+    /// The default implementation falls back to `list_all`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<String>)`: IDs of all stored sessions
+    /// - `Err(_)`: Error occurred during listing
+    async fn list_ids(&self) -> Result<Vec<String>> {
+        Ok(self.list_all().await?.into_iter().map(|s| s.id).collect())
+    }
+
+    /// Lists summaries of all stored sessions without their messages.
+    ///
+    /// Lets callers pick the sessions worth loading in full (e.g., by workspace
+    /// or `updated_at`). The default implementation falls back to `list_all`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<SessionSummary>)`: Summaries of all stored sessions
+    /// - `Err(_)`: Error occurred during listing
+    async fn list_summaries(&self) -> Result<Vec<SessionSummary>> {
+        Ok(self
+            .list_all()
+            .await?
+            .iter()
+            .map(SessionSummary::from)
+            .collect())
+    }
+
+    /// Counts the stored sessions of each workspace, keyed by workspace ID.
+    ///
+    /// Workspaces without sessions are absent. The default implementation
+    /// tallies `list_summaries`, so it reads only session headers wherever
+    /// summaries do.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(HashMap<String, usize>)`: Session count per workspace ID
+    /// - `Err(_)`: Error occurred during listing
+    async fn count_by_workspace(&self) -> Result<HashMap<String, usize>> {
+        let mut counts = HashMap::new();
+        for summary in self.list_summaries().await? {
+            *counts.entry(summary.workspace_id).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    /// Returns the size in bytes of a session's stored representation.
+    ///
+    /// Used to report space reclaimed by compaction. The default
+    /// implementation returns `Ok(None)` for storage without a meaningful size.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(bytes))`: Size of the stored session
+    /// - `Ok(None)`: Session not found or size unknown
+    /// - `Err(_)`: Error occurred while reading the size
+    async fn stored_size(&self, _session_id: &str) -> Result<Option<u64>> {
+        Ok(None)
+    }
 }
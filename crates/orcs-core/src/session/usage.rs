@@ -0,0 +1,276 @@
+//! Per-turn usage statistics and their aggregation.
+//!
+//! Assistant messages carry the [`TurnStats`] of the turn that produced them:
+//! how long it took and, for API backends, the tokens it used and what they
+//! are estimated to cost. CLI backends do not report usage and only record the
+//! duration. [`CostBreakdown`] sums these per persona and per day.
+
+use super::message::{ConversationMessage, MessageRole};
+use schema_bridge::SchemaBridge;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Day under which turns with an unparseable timestamp are counted.
+pub const UNKNOWN_DAY: &str = "unknown";
+
+/// Duration, token usage and estimated cost of one agent turn.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+pub struct TurnStats {
+    /// Time the agent took to answer, in milliseconds.
+    pub duration_ms: u64,
+    /// Prompt tokens reported by the backend (API backends only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u64>,
+    /// Completion tokens reported by the backend (API backends only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    /// Estimated cost in millionths of a US dollar, when the model's price is known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_cost_microusd: Option<u64>,
+}
+
+/// Summed [`TurnStats`] of several turns.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+pub struct CostTotals {
+    /// Number of turns with stats.
+    pub turns: u64,
+    pub duration_ms: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub estimated_cost_microusd: u64,
+}
+
+impl CostTotals {
+    /// Adds one turn; missing token counts and costs count as zero.
+    pub fn add(&mut self, stats: &TurnStats) {
+        self.turns += 1;
+        self.duration_ms += stats.duration_ms;
+        self.input_tokens += stats.input_tokens.unwrap_or(0);
+        self.output_tokens += stats.output_tokens.unwrap_or(0);
+        self.estimated_cost_microusd += stats.estimated_cost_microusd.unwrap_or(0);
+    }
+}
+
+/// Usage of one persona in a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonaCost {
+    pub persona_id: String,
+    /// Display name, falling back to the persona ID.
+    pub persona_name: String,
+    pub totals: CostTotals,
+}
+
+/// Usage of a session on one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyCost {
+    /// UTC date (`YYYY-MM-DD`), or [`UNKNOWN_DAY`].
+    pub date: String,
+    pub totals: CostTotals,
+}
+
+/// Usage of a session per persona and per day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SchemaBridge)]
+#[serde(rename_all = "camelCase")]
+pub struct CostBreakdown {
+    pub total: CostTotals,
+    /// Sorted by persona name.
+    pub by_persona: Vec<PersonaCost>,
+    /// Sorted by date.
+    pub by_day: Vec<DailyCost>,
+}
+
+impl CostBreakdown {
+    /// Sums the stats of the assistant messages in `persona_histories`, naming
+    /// personas through `participants`.
+    pub fn from_histories(
+        persona_histories: &HashMap<String, Vec<ConversationMessage>>,
+        participants: &HashMap<String, String>,
+    ) -> Self {
+        let mut breakdown = Self::default();
+        let mut days: BTreeMap<String, CostTotals> = BTreeMap::new();
+
+        for (persona_id, history) in persona_histories {
+            let mut totals = CostTotals::default();
+            for message in history {
+                let Some(stats) = message.metadata.turn_stats.as_ref() else {
+                    continue;
+                };
+                if !matches!(message.role, MessageRole::Assistant) {
+                    continue;
+                }
+                totals.add(stats);
+                breakdown.total.add(stats);
+                days.entry(day_of(&message.timestamp))
+                    .or_default()
+                    .add(stats);
+            }
+            if totals.turns > 0 {
+                breakdown.by_persona.push(PersonaCost {
+                    persona_id: persona_id.clone(),
+                    persona_name: participants
+                        .get(persona_id)
+                        .cloned()
+                        .unwrap_or_else(|| persona_id.clone()),
+                    totals,
+                });
+            }
+        }
+
+        breakdown.by_persona.sort_by(|a, b| {
+            (&a.persona_name, &a.persona_id).cmp(&(&b.persona_name, &b.persona_id))
+        });
+        breakdown.by_day = days
+            .into_iter()
+            .map(|(date, totals)| DailyCost { date, totals })
+            .collect();
+        breakdown
+    }
+}
+
+/// UTC date of an RFC 3339 timestamp.
+fn day_of(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|time| {
+            time.with_timezone(&chrono::Utc)
+                .format("%Y-%m-%d")
+                .to_string()
+        })
+        .unwrap_or_else(|_| UNKNOWN_DAY.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::MessageMetadata;
+
+    fn turn(role: MessageRole, timestamp: &str, stats: Option<TurnStats>) -> ConversationMessage {
+        ConversationMessage {
+            role,
+            content: "reply".to_string(),
+            timestamp: timestamp.to_string(),
+            metadata: MessageMetadata {
+                turn_stats: stats,
+                ..MessageMetadata::default()
+            },
+            attachments: vec![],
+        }
+    }
+
+    fn api_stats(duration_ms: u64, input: u64, output: u64, cost: u64) -> TurnStats {
+        TurnStats {
+            duration_ms,
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+            estimated_cost_microusd: Some(cost),
+        }
+    }
+
+    #[test]
+    fn test_breakdown_sums_per_persona_and_day() {
+        let cli_stats = TurnStats {
+            duration_ms: 4_000,
+            ..TurnStats::default()
+        };
+        let histories = HashMap::from([
+            (
+                "mai".to_string(),
+                vec![
+                    turn(MessageRole::User, "2026-03-01T09:00:00Z", None),
+                    turn(
+                        MessageRole::Assistant,
+                        "2026-03-01T09:00:05Z",
+                        Some(api_stats(1_200, 1_000, 200, 6_000)),
+                    ),
+                    // 23:30 in UTC-2 is the next day in UTC
+                    turn(
+                        MessageRole::Assistant,
+                        "2026-03-01T23:30:00-02:00",
+                        Some(api_stats(800, 500, 100, 3_000)),
+                    ),
+                ],
+            ),
+            (
+                "yui".to_string(),
+                vec![
+                    turn(
+                        MessageRole::Assistant,
+                        "2026-03-01T09:00:07Z",
+                        Some(cli_stats.clone()),
+                    ),
+                    turn(MessageRole::Assistant, "2026-03-02T10:00:00Z", None),
+                ],
+            ),
+            (
+                "ren".to_string(),
+                vec![turn(MessageRole::User, "2026-03-01T09:00:00Z", None)],
+            ),
+        ]);
+        let participants = HashMap::from([
+            ("mai".to_string(), "Mai".to_string()),
+            ("yui".to_string(), "Yui".to_string()),
+        ]);
+
+        let breakdown = CostBreakdown::from_histories(&histories, &participants);
+
+        assert_eq!(
+            breakdown.total,
+            CostTotals {
+                turns: 3,
+                duration_ms: 6_000,
+                input_tokens: 1_500,
+                output_tokens: 300,
+                estimated_cost_microusd: 9_000,
+            }
+        );
+        let names: Vec<&str> = breakdown
+            .by_persona
+            .iter()
+            .map(|p| p.persona_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Mai", "Yui"]);
+        assert_eq!(
+            breakdown.by_persona[0].totals.estimated_cost_microusd,
+            9_000
+        );
+        assert_eq!(breakdown.by_persona[1].totals.duration_ms, 4_000);
+        assert_eq!(breakdown.by_persona[1].totals.input_tokens, 0);
+
+        let days: Vec<(&str, u64, u64)> = breakdown
+            .by_day
+            .iter()
+            .map(|d| {
+                (
+                    d.date.as_str(),
+                    d.totals.turns,
+                    d.totals.estimated_cost_microusd,
+                )
+            })
+            .collect();
+        assert_eq!(
+            days,
+            vec![("2026-03-01", 2, 6_000), ("2026-03-02", 1, 3_000)]
+        );
+    }
+
+    #[test]
+    fn test_unparseable_timestamps_count_under_unknown_day() {
+        let histories = HashMap::from([(
+            "mai".to_string(),
+            vec![turn(
+                MessageRole::Assistant,
+                "yesterday",
+                Some(api_stats(100, 10, 5, 1)),
+            )],
+        )]);
+
+        let breakdown = CostBreakdown::from_histories(&histories, &HashMap::new());
+
+        assert_eq!(breakdown.by_persona[0].persona_name, "mai");
+        assert_eq!(breakdown.by_day[0].date, UNKNOWN_DAY);
+        assert_eq!(breakdown.by_day[0].totals.turns, 1);
+    }
+}
@@ -110,11 +110,29 @@ pub fn builtin_commands() -> &'static [BuiltinSlashCommand] {
                 "Set dialogue style for multi-agent collaboration",
                 Some("brainstorm / casual / decision_making / debate / problem_solving / review / planning / none"),
             ),
+            BuiltinSlashCommand::new(
+                "focus",
+                "/focus [name ...|off]",
+                "Let only the named participants respond until focus is cleared",
+                Some("Participant names; none or off clears the focus"),
+            ),
+            BuiltinSlashCommand::new(
+                "poll",
+                "/poll <question> | <option1> | <option2> [| ...]",
+                "Ask every participant to vote on one option and tally the result",
+                Some("Question followed by at least two options, separated by |"),
+            ),
+            BuiltinSlashCommand::new(
+                "decision",
+                "/decision",
+                "Extract an ADR-style decision record from this session into workspace storage",
+                None,
+            ),
             BuiltinSlashCommand::new(
                 "create-persona",
                 "/create-persona <json>",
                 "Create a new persona from JSON definition (UUID auto-generated)",
-                Some(r#"JSON with required fields: name, role, background (min 10 chars), communication_style (min 10 chars), backend (claude_cli/claude_api/gemini_cli/gemini_api/open_ai_api/codex_cli). Optional: model_name, default_participant (bool), icon, base_color. NOTE: ID is always auto-generated as UUID (not accepted in request)"#),
+                Some(r#"JSON with required fields: name (unique, max 50 chars, not System/User/Error), role (max 100 chars), background (10-4000 chars), communication_style (10-1000 chars), backend (claude_cli/claude_api/gemini_cli/gemini_api/open_ai_api/codex_cli). Optional: model_name (must suit the backend), default_participant (bool), icon (single emoji), base_color (hex like #3B82F6). NOTE: ID is always auto-generated as UUID (not accepted in request)"#),
             ),
             BuiltinSlashCommand::new(
                 "create-slash-command",
@@ -8,8 +8,15 @@ pub mod builtin;
 pub mod model;
 pub mod repository;
 pub mod request;
+pub mod result;
+pub mod template;
 
 pub use builtin::{BuiltinSlashCommand, builtin_commands, find_builtin_command};
-pub use model::{ActionConfig, CommandType, PipelineConfig, PipelineStep, SlashCommand};
+pub use model::{
+    ActionConfig, CommandType, PipelineConfig, PipelineStep, SlashCommand,
+    SlashCommandValidationError,
+};
 pub use repository::SlashCommandRepository;
 pub use request::CreateSlashCommandRequest;
+pub use result::{SlashCommandContentType, SlashCommandResult};
+pub use template::{CommandArgs, TemplateError, expand_named_placeholders, named_placeholders};
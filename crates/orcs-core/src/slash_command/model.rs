@@ -1,6 +1,7 @@
 //! Slash command domain models.
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 /// Type of slash command execution.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -83,6 +84,28 @@ pub struct ActionConfig {
     pub gemini_google_search: Option<bool>,
 }
 
+/// Reason a slash command definition is invalid.
+#[derive(Error, Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SlashCommandValidationError {
+    #[error("Command name is required")]
+    EmptyName,
+
+    /// Names may contain only letters, digits, `-` and `_`
+    #[error("Command name '{name}' contains invalid character '{invalid}'")]
+    InvalidName { name: String, invalid: char },
+
+    #[error("Description is required")]
+    EmptyDescription,
+
+    /// The field that carries the command's body is empty
+    #[error("{command_type:?} commands require a {field}")]
+    MissingContent {
+        command_type: CommandType,
+        field: &'static str,
+    },
+}
+
 /// A custom slash command definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlashCommand {
@@ -135,6 +158,12 @@ pub struct SlashCommand {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "sortOrder")]
     pub sort_order: Option<u32>,
+
+    /// Whether agent-triggered executions must be approved by the user first.
+    /// User-typed invocations are never gated.
+    #[serde(default)]
+    #[serde(rename = "requiresConfirmation")]
+    pub requires_confirmation: bool,
 }
 
 /// Default value for include_in_system_prompt (true).
@@ -160,6 +189,7 @@ impl SlashCommand {
             include_in_system_prompt: true,
             is_favorite: false,
             sort_order: None,
+            requires_confirmation: false,
         }
     }
 
@@ -198,6 +228,7 @@ impl SlashCommand {
             include_in_system_prompt: true,
             is_favorite: false,
             sort_order: None,
+            requires_confirmation: false,
         }
     }
 
@@ -238,6 +269,7 @@ impl SlashCommand {
             include_in_system_prompt: false, // Task commands excluded by default
             is_favorite: false,
             sort_order: None,
+            requires_confirmation: false,
         }
     }
 
@@ -278,6 +310,7 @@ impl SlashCommand {
             include_in_system_prompt: true,
             is_favorite: false,
             sort_order: None,
+            requires_confirmation: false,
         }
     }
 
@@ -317,6 +350,120 @@ impl SlashCommand {
             include_in_system_prompt: false, // Pipeline commands excluded by default
             is_favorite: false,
             sort_order: None,
+            requires_confirmation: false,
         }
     }
+
+    /// Checks that the definition can be saved and executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found: a missing or malformed name, a missing
+    /// description, or a missing body for the command's type (shell command,
+    /// prompt template, task description, or pipeline steps).
+    pub fn validate(&self) -> Result<(), SlashCommandValidationError> {
+        if self.name.trim().is_empty() {
+            return Err(SlashCommandValidationError::EmptyName);
+        }
+        if let Some(invalid) = self
+            .name
+            .chars()
+            .find(|c| !(c.is_alphanumeric() || *c == '-' || *c == '_'))
+        {
+            return Err(SlashCommandValidationError::InvalidName {
+                name: self.name.clone(),
+                invalid,
+            });
+        }
+        if self.description.trim().is_empty() {
+            return Err(SlashCommandValidationError::EmptyDescription);
+        }
+
+        let has_body = match self.command_type {
+            CommandType::Pipeline => self
+                .pipeline_config
+                .as_ref()
+                .is_some_and(|config| !config.steps.is_empty()),
+            _ => !self.content.trim().is_empty(),
+        };
+        if !has_body {
+            let field = match self.command_type {
+                CommandType::Shell => "shell command",
+                CommandType::Task => "task description",
+                CommandType::Pipeline => "pipeline step",
+                CommandType::Prompt | CommandType::Action => "prompt template",
+            };
+            return Err(SlashCommandValidationError::MissingContent {
+                command_type: self.command_type.clone(),
+                field,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rejects_name_with_space() {
+        let command = SlashCommand::new_prompt(
+            "my cmd".to_string(),
+            "⚡".to_string(),
+            "Test".to_string(),
+            "Review {args}".to_string(),
+        );
+
+        assert_eq!(
+            command.validate(),
+            Err(SlashCommandValidationError::InvalidName {
+                name: "my cmd".to_string(),
+                invalid: ' ',
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_command() {
+        let command = SlashCommand::new_shell(
+            "git-status".to_string(),
+            "⚡".to_string(),
+            "Show git status".to_string(),
+            "git status".to_string(),
+            None,
+        );
+
+        assert!(command.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_requires_content_for_type() {
+        let shell = SlashCommand::new_shell(
+            "status".to_string(),
+            "⚡".to_string(),
+            "Show status".to_string(),
+            "  ".to_string(),
+            None,
+        );
+        assert_eq!(
+            shell.validate(),
+            Err(SlashCommandValidationError::MissingContent {
+                command_type: CommandType::Shell,
+                field: "shell command",
+            })
+        );
+
+        let pipeline = SlashCommand::new_pipeline(
+            "chain".to_string(),
+            "⚡".to_string(),
+            "Run steps".to_string(),
+            PipelineConfig {
+                steps: vec![],
+                fail_on_error: true,
+                chain_output: true,
+            },
+        );
+        assert!(pipeline.validate().is_err());
+    }
 }
@@ -70,6 +70,11 @@ pub struct CreateSlashCommandRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "sortOrder")]
     pub sort_order: Option<u32>,
+
+    /// Whether agent-triggered executions must be approved by the user first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "requiresConfirmation")]
+    pub requires_confirmation: Option<bool>,
 }
 
 fn default_icon() -> String {
@@ -138,6 +143,7 @@ impl CreateSlashCommandRequest {
             include_in_system_prompt,
             is_favorite: self.is_favorite.unwrap_or(false),
             sort_order: self.sort_order,
+            requires_confirmation: self.requires_confirmation.unwrap_or(false),
         }
     }
 
@@ -157,6 +163,7 @@ impl CreateSlashCommandRequest {
             include_in_system_prompt: Some(cmd.include_in_system_prompt),
             is_favorite: Some(cmd.is_favorite),
             sort_order: cmd.sort_order,
+            requires_confirmation: Some(cmd.requires_confirmation),
         }
     }
 }
@@ -181,6 +188,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         assert!(req.validate().is_ok());
@@ -202,6 +210,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         assert!(req.validate().is_err());
@@ -223,6 +232,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         assert!(req.validate().is_err());
@@ -244,6 +254,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         assert!(req.validate().is_err());
@@ -265,6 +276,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         assert!(req.validate().is_err());
@@ -286,6 +298,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         assert!(req.validate().is_ok());
@@ -307,6 +320,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         let cmd = req.into_slash_command();
@@ -334,6 +348,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         let cmd = req.into_slash_command();
@@ -352,9 +367,11 @@ mod tests {
             args_description: None,
             task_blueprint: None,
             action_config: None,
+            pipeline_config: None,
             include_in_system_prompt: Some(true), // Explicitly override to true
             is_favorite: None,
             sort_order: None,
+            requires_confirmation: None,
         };
 
         let cmd = req.into_slash_command();
@@ -377,6 +394,7 @@ mod tests {
             include_in_system_prompt: None,
             is_favorite: Some(true),
             sort_order: Some(1),
+            requires_confirmation: None,
         };
 
         let cmd = req.into_slash_command();
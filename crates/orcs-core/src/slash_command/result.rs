@@ -0,0 +1,125 @@
+//! Structured results of slash command expansion and execution.
+
+use serde::{Deserialize, Serialize};
+
+/// What the `output` of a [`SlashCommandResult`] contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashCommandContentType {
+    /// An expanded prompt, ready to be sent as user input.
+    Prompt,
+    /// A rendered shell command line, not yet executed.
+    ShellCommand,
+    /// Output captured from an executed shell command.
+    ShellOutput,
+    /// An informational notice (e.g. the command is waiting for confirmation).
+    Notice,
+}
+
+/// Result of expanding or executing a slash command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommandResult {
+    /// The slash command name, or the raw command line when run without one.
+    pub command: String,
+    pub success: bool,
+    /// Expanded text or captured output; stderr when a shell command failed.
+    pub output: String,
+    pub content_type: SlashCommandContentType,
+    /// Directory the command runs (or ran) in, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+}
+
+impl SlashCommandResult {
+    /// Creates a successful result.
+    pub fn success(
+        command: impl Into<String>,
+        output: impl Into<String>,
+        content_type: SlashCommandContentType,
+    ) -> Self {
+        Self {
+            command: command.into(),
+            success: true,
+            output: output.into(),
+            content_type,
+            working_dir: None,
+        }
+    }
+
+    /// Creates a failed shell result carrying an error message.
+    pub fn failure(command: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            success: false,
+            output: error.into(),
+            content_type: SlashCommandContentType::ShellOutput,
+            working_dir: None,
+        }
+    }
+
+    /// Builds a result from a finished shell process.
+    ///
+    /// Successful runs carry stdout; failed runs carry stderr.
+    pub fn from_process_output(command: impl Into<String>, output: &std::process::Output) -> Self {
+        let success = output.status.success();
+        let stream = if success {
+            &output.stdout
+        } else {
+            &output.stderr
+        };
+        Self {
+            command: command.into(),
+            success,
+            output: String::from_utf8_lossy(stream).into_owned(),
+            content_type: SlashCommandContentType::ShellOutput,
+            working_dir: None,
+        }
+    }
+
+    /// Sets the working directory.
+    pub fn with_working_dir(mut self, working_dir: Option<String>) -> Self {
+        self.working_dir = working_dir;
+        self
+    }
+
+    /// Converts to the plain-text form used where only the output matters.
+    pub fn into_output(self) -> Result<String, String> {
+        if self.success {
+            Ok(self.output)
+        } else {
+            Err(format!("Command failed: {}", self.output))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_shell_command_result_has_success_and_output() {
+        let output = std::process::Command::new("sh")
+            .args(["-c", "echo hello"])
+            .output()
+            .unwrap();
+        let result = SlashCommandResult::from_process_output("greet", &output);
+        assert!(result.success);
+        assert_eq!(result.command, "greet");
+        assert_eq!(result.output, "hello\n");
+        assert_eq!(result.content_type, SlashCommandContentType::ShellOutput);
+
+        let output = std::process::Command::new("sh")
+            .args(["-c", "echo broken >&2; exit 3"])
+            .output()
+            .unwrap();
+        let result = SlashCommandResult::from_process_output("greet", &output);
+        assert!(!result.success);
+        assert_eq!(result.output, "broken\n");
+        assert_eq!(
+            result.into_output(),
+            Err("Command failed: broken\n".to_string())
+        );
+    }
+}
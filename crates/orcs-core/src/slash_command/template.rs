@@ -0,0 +1,179 @@
+//! Named placeholder expansion for slash command templates.
+//!
+//! Templates can reference named variables as `{{name}}`, filled from
+//! `--name value` pairs in the command arguments. Values containing spaces can
+//! be wrapped in double quotes (`--topic "error handling"`).
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+/// Arguments of a slash command invocation, split into named and positional parts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandArgs {
+    /// Values of `--key value` pairs; a trailing `--flag` without value is `"true"`
+    pub named: HashMap<String, String>,
+    /// Remaining arguments joined by single spaces
+    pub positional: String,
+}
+
+impl CommandArgs {
+    /// Parses `--key value` pairs out of `args`, keeping everything else positional.
+    pub fn parse(args: &str) -> Self {
+        let mut named = HashMap::new();
+        let mut positional = Vec::new();
+        let mut tokens = tokenize(args).into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token.strip_prefix("--").filter(|key| !key.is_empty()) {
+                Some(key) => {
+                    let value = tokens
+                        .next_if(|next| !next.starts_with("--"))
+                        .unwrap_or_else(|| "true".to_string());
+                    named.insert(key.to_string(), value);
+                }
+                None => positional.push(token),
+            }
+        }
+        Self {
+            named,
+            positional: positional.join(" "),
+        }
+    }
+}
+
+/// Error expanding a template with named placeholders.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// Placeholders without a matching `--name value` argument, in template order
+    #[error("Missing value for {}", .0.iter().map(|name| format!("--{}", name)).collect::<Vec<_>>().join(", "))]
+    MissingVariables(Vec<String>),
+}
+
+/// Returns the names of the `{{name}}` placeholders in `template`, in order of
+/// first appearance.
+pub fn named_placeholders(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if is_variable_name(name) && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    names
+}
+
+/// Replaces every `{{name}}` placeholder in `template` with its value from `args`.
+///
+/// # Errors
+///
+/// Returns [`TemplateError::MissingVariables`] listing every placeholder that
+/// has no value; nothing is substituted in that case.
+pub fn expand_named_placeholders(
+    template: &str,
+    args: &CommandArgs,
+) -> Result<String, TemplateError> {
+    let names = named_placeholders(template);
+    let missing: Vec<String> = names
+        .iter()
+        .filter(|name| !args.named.contains_key(*name))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        return Err(TemplateError::MissingVariables(missing));
+    }
+
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        let name = after[..end].trim();
+        match args.named.get(name).filter(|_| is_variable_name(name)) {
+            Some(value) => expanded.push_str(value),
+            None => expanded.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Splits on whitespace, keeping double-quoted sections together.
+fn tokenize(args: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    for c in args.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_two_named_variables() {
+        let args = CommandArgs::parse(r#"--topic "error handling" --count 3 in Rust"#);
+        assert_eq!(args.positional, "in Rust");
+
+        let expanded =
+            expand_named_placeholders("List {{count}} tips about {{ topic }}.", &args).unwrap();
+        assert_eq!(expanded, "List 3 tips about error handling.");
+    }
+
+    #[test]
+    fn test_missing_placeholder_is_an_error() {
+        let args = CommandArgs::parse("--topic parsing");
+
+        assert_eq!(
+            expand_named_placeholders("{{count}} tips about {{topic}}", &args),
+            Err(TemplateError::MissingVariables(vec!["count".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_flag_without_value() {
+        let args = CommandArgs::parse("--verbose --depth 2");
+
+        assert_eq!(args.named["verbose"], "true");
+        assert_eq!(args.named["depth"], "2");
+        assert!(args.positional.is_empty());
+    }
+}
@@ -69,6 +69,7 @@ pub struct OpenTab {
 /// * `active_session_id` - The ID of the currently active session.
 /// * `open_tabs` - List of currently open tabs.
 /// * `active_tab_id` - The ID of the currently active tab.
+/// * `offline_mode` - Whether network-using backends are blocked.
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, SchemaBridge, Default)]
 #[queryable(entity = "app_state")]
 #[serde(rename_all = "camelCase")]
@@ -90,6 +91,10 @@ pub struct AppState {
 
     /// ID of the currently active tab.
     pub active_tab_id: Option<String>,
+
+    /// Whether offline mode is on (see `orcs_core::offline`).
+    #[serde(default)]
+    pub offline_mode: bool,
 }
 
 impl AppState {
@@ -111,6 +116,7 @@ mod tests {
         assert!(state.active_session_id.is_none());
         assert!(state.open_tabs.is_empty());
         assert!(state.active_tab_id.is_none());
+        assert!(!state.offline_mode);
     }
 
     #[test]
@@ -29,6 +29,12 @@ pub trait StateRepository: Send + Sync {
 
     async fn clear_active_session(&self) -> Result<()>;
 
+    /// Returns whether offline mode is on.
+    async fn get_offline_mode(&self) -> bool;
+
+    /// Turns offline mode on or off and saves it.
+    async fn set_offline_mode(&self, enabled: bool) -> Result<()>;
+
     // Tab management methods
     async fn get_open_tabs(&self) -> Vec<OpenTab>;
 
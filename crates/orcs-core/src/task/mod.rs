@@ -8,6 +8,7 @@
 //! - `model`: Core task domain models (`TaskStatus`, `TaskContext`, etc.)
 //!   and domain messaging types
 //! - `repository`: Task repository trait for persistence
+//! - `report`: Markdown reports of task executions
 //!
 //! # Usage
 //!
@@ -18,12 +19,14 @@
 //! ```
 
 mod model;
+mod report;
 pub mod repository;
 
 // Re-export public API
 pub use model::{
     DomainMessage, ExecutionDetails, ExecutionMessage, SerializableOrchestrationResult, StepInfo,
-    StepStatus, Task, TaskContext, TaskManagerMessage, TaskStatus,
+    StepStatus, Task, TaskContext, TaskManagerMessage, TaskOrigin, TaskStatus,
 };
+pub use report::format_task_report;
 
 pub use repository::TaskRepository;
@@ -222,7 +222,7 @@ pub struct ExecutionDetails {
 /// # JSON Serialization Format
 ///
 /// This domain model uses `#[serde(rename_all = "camelCase")]` for Tauri IPC communication.
-/// **IMPORTANT**: The DTO layer (`TaskV1_1_0`) does NOT use camelCase and remains snake_case
+/// **IMPORTANT**: The DTO layer (`TaskV1_2_0`) does NOT use camelCase and remains snake_case
 /// for backward compatibility with existing saved task files (`~/.orcs/tasks/*.json`).
 ///
 /// - **Tauri IPC** (this struct): Serialized as camelCase for TypeScript frontend
@@ -270,4 +270,23 @@ pub struct Task {
     /// Journal log (execution trace from ParallelOrchestrator)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub journal_log: Option<String>,
+    /// Timestamp of the conversation message this task was spawned from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_message_timestamp: Option<String>,
+    /// Author (persona ID, user or "system") of the originating message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_author: Option<String>,
+    /// Whether this was a dry run: steps described what they would do instead of doing it
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Conversation message a task was spawned from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOrigin {
+    /// Timestamp of the originating message (ISO 8601 format)
+    pub message_timestamp: String,
+    /// Author (persona ID, user or "system") of the originating message
+    pub author: String,
 }
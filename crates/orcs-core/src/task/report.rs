@@ -0,0 +1,175 @@
+//! Markdown reports of task executions.
+//!
+//! Reports are rendered from the persisted [`Task`] so they can be shared in
+//! retrospectives without access to the app.
+
+use super::model::Task;
+use llm_toolkit::orchestrator::StrategyMap;
+use std::fmt::Write;
+
+/// Renders a Markdown report of a task execution.
+///
+/// Includes the task's status, request, strategy summary, steps with their
+/// statuses, context keys, result, and any errors.
+pub fn format_task_report(task: &Task) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# Task Report: {}\n", task.title);
+    let _ = writeln!(out, "- **Status:** {}", task.status.as_str());
+    if task.dry_run {
+        let _ = writeln!(
+            out,
+            "- **Dry run:** steps describe what they would do; nothing was changed"
+        );
+    }
+    let _ = writeln!(out, "- **Task ID:** `{}`", task.id);
+    let _ = writeln!(out, "- **Session ID:** `{}`", task.session_id);
+    let _ = writeln!(out, "- **Created:** {}", task.created_at);
+    if let Some(completed_at) = &task.completed_at {
+        let _ = writeln!(out, "- **Completed:** {}", completed_at);
+    }
+    let _ = writeln!(
+        out,
+        "- **Steps:** {} executed, {} skipped",
+        task.steps_executed, task.steps_skipped
+    );
+
+    let _ = writeln!(out, "\n## Request\n\n{}", task.description.trim());
+
+    if let Some(strategy) = &task.strategy {
+        let _ = writeln!(out, "\n## Strategy\n");
+        match serde_json::from_str::<StrategyMap>(strategy) {
+            Ok(map) => {
+                let _ = writeln!(out, "- **Goal:** {}", map.goal);
+                let _ = writeln!(
+                    out,
+                    "- **Planned steps:** {}",
+                    map.steps.len().max(map.elements.len())
+                );
+            }
+            Err(_) => {
+                let _ = writeln!(out, "_Strategy could not be parsed._");
+            }
+        }
+    }
+
+    let details = task.execution_details.as_ref();
+
+    let _ = writeln!(out, "\n## Steps\n");
+    match details.filter(|d| !d.steps.is_empty()) {
+        Some(details) => {
+            for (index, step) in details.steps.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "{}. **{}** — {} ({})",
+                    index + 1,
+                    step.id,
+                    step.status,
+                    step.agent
+                );
+                if !step.description.is_empty() {
+                    let _ = writeln!(out, "   {}", step.description);
+                }
+            }
+        }
+        None => {
+            let _ = writeln!(out, "_No step details recorded._");
+        }
+    }
+
+    let _ = writeln!(out, "\n## Context Keys\n");
+    match details.filter(|d| !d.context.is_empty()) {
+        Some(details) => {
+            let mut keys: Vec<&String> = details.context.keys().collect();
+            keys.sort();
+            for key in keys {
+                let _ = writeln!(out, "- `{}`", key);
+            }
+        }
+        None => {
+            let _ = writeln!(out, "{} key(s) generated.", task.context_keys);
+        }
+    }
+
+    if let Some(result) = &task.result {
+        let _ = writeln!(out, "\n## Result\n\n{}", result.trim());
+    }
+
+    let step_errors: Vec<(&str, &str)> = details
+        .map(|d| {
+            d.steps
+                .iter()
+                .filter_map(|s| s.error.as_deref().map(|e| (s.id.as_str(), e)))
+                .collect()
+        })
+        .unwrap_or_default();
+    if task.error.is_some() || !step_errors.is_empty() {
+        let _ = writeln!(out, "\n## Errors\n");
+        if let Some(error) = &task.error {
+            let _ = writeln!(out, "- **Task:** {}", error);
+        }
+        for (step_id, error) in step_errors {
+            let _ = writeln!(out, "- **Step `{}`:** {}", step_id, error);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{ExecutionDetails, StepInfo, StepStatus, TaskStatus};
+    use std::collections::HashMap;
+
+    fn step(id: &str, status: StepStatus, error: Option<&str>) -> StepInfo {
+        StepInfo {
+            id: id.to_string(),
+            description: format!("Run {}", id),
+            status,
+            agent: "ClaudeCodeAgent".to_string(),
+            output: None,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_report_lists_steps_with_statuses() {
+        let task = Task {
+            id: "task-1".to_string(),
+            session_id: "session-1".to_string(),
+            title: "Fix parser".to_string(),
+            description: "Fix the TOML parser".to_string(),
+            status: TaskStatus::Failed,
+            created_at: "2026-01-01T10:00:00Z".to_string(),
+            updated_at: "2026-01-01T10:05:00Z".to_string(),
+            completed_at: Some("2026-01-01T10:05:00Z".to_string()),
+            steps_executed: 2,
+            steps_skipped: 0,
+            context_keys: 1,
+            error: Some("Step test failed".to_string()),
+            result: None,
+            execution_details: Some(ExecutionDetails {
+                steps: vec![
+                    step("analyze", StepStatus::Completed, None),
+                    step("test", StepStatus::Failed, Some("3 tests failed")),
+                ],
+                context: HashMap::from([("analysis".to_string(), serde_json::json!("ok"))]),
+            }),
+            strategy: Some(r#"{"goal":"Fix the parser","steps":[]}"#.to_string()),
+            journal_log: None,
+            origin_message_timestamp: None,
+            origin_author: None,
+            dry_run: false,
+        };
+
+        let report = format_task_report(&task);
+        assert!(report.starts_with("# Task Report: Fix parser\n"));
+        assert!(report.contains("- **Status:** Failed\n"));
+        assert!(report.contains("1. **analyze** — Completed (ClaudeCodeAgent)\n"));
+        assert!(report.contains("2. **test** — Failed (ClaudeCodeAgent)\n"));
+        assert!(report.contains("- `analysis`\n"));
+        assert!(report.contains("- **Goal:** Fix the parser\n"));
+        assert!(report.contains("- **Step `test`:** 3 tests failed\n"));
+    }
+}
@@ -1,6 +1,6 @@
 //! UserProfile domain model.
 //!
-//! Represents user profile information including display name and background.
+//! Represents user profile information including display name, background and timezone.
 
 use serde::{Deserialize, Serialize};
 use version_migrate::DeriveQueryable as Queryable;
@@ -17,6 +17,10 @@ pub struct UserProfile {
     pub nickname: String,
     /// User's background or bio
     pub background: String,
+    /// UTC offset agents see the current time in, e.g. `+09:00`
+    /// (None: the OS timezone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
 }
 
 impl Default for UserProfile {
@@ -24,6 +28,7 @@ impl Default for UserProfile {
         Self {
             nickname: "You".to_string(),
             background: String::new(),
+            timezone: None,
         }
     }
 }
@@ -33,6 +33,15 @@ pub trait UserService: Send + Sync {
     /// A UserProfile containing nickname and background.
     fn get_user_profile(&self) -> super::model::UserProfile;
 
+    /// Looks up the profile of the user with the given ID.
+    ///
+    /// User IDs key user turns in session histories. Single-user services know
+    /// only the current user, whose ID is [`get_user_name`](Self::get_user_name),
+    /// so the default returns that user's profile for it and `None` otherwise.
+    fn get_user(&self, id: &str) -> Option<super::model::UserProfile> {
+        (id == self.get_user_name()).then(|| self.get_user_profile())
+    }
+
     /// Returns the current debug settings.
     ///
     /// # Returns
@@ -111,7 +120,14 @@ mod tests {
 
     #[test]
     fn test_default_user_service() {
-        let service = DefaultUserService::default();
+        let service = DefaultUserService;
         assert_eq!(service.get_user_name(), "user");
     }
+
+    #[test]
+    fn test_default_get_user_knows_only_the_current_user() {
+        let service = DefaultUserService;
+        assert_eq!(service.get_user("user").unwrap().nickname, "You");
+        assert!(service.get_user("someone-else").is_none());
+    }
 }
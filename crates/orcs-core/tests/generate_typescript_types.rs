@@ -6,8 +6,9 @@ use orcs_core::schema::{
     TaskStatus, TaskType,
 };
 use orcs_core::session::{
-    AppMode, AutoChatConfig, ContextMode, ConversationMessage, ConversationMode, ErrorSeverity,
-    MessageMetadata, MessageRole, Plan, SandboxState, StopCondition, SystemEventType,
+    AppMode, AutoChatConfig, ContextMode, ConversationMessage, ConversationMode, CostBreakdown,
+    CostTotals, DailyCost, ErrorSeverity, MessageMetadata, MessageRole, PersonaCost, Plan,
+    SandboxState, SourceKind, SourceRef, StopCondition, SystemEventType, TurnStats,
 };
 use orcs_core::state::model::{AppState, OpenTab};
 use orcs_core::workspace::{ProjectContext, TempFile, UploadedFile, Workspace, WorkspaceResources};
@@ -16,6 +17,7 @@ use std::fs;
 use std::path::Path;
 
 #[test]
+#[allow(clippy::vec_init_then_push)]
 fn generate_typescript_types() {
     let output_dir = Path::new("../../orcs-desktop/src/bindings");
     fs::create_dir_all(output_dir).expect("Failed to create bindings directory");
@@ -36,9 +38,18 @@ fn generate_typescript_types() {
     types.push(("MessageRole", MessageRole::to_ts()));
     types.push(("SystemEventType", SystemEventType::to_ts()));
     types.push(("ErrorSeverity", ErrorSeverity::to_ts()));
+    types.push(("SourceKind", SourceKind::to_ts()));
+    types.push(("SourceRef", SourceRef::to_ts()));
+    types.push(("TurnStats", TurnStats::to_ts()));
     types.push(("MessageMetadata", MessageMetadata::to_ts()));
     types.push(("ConversationMessage", ConversationMessage::to_ts()));
 
+    // Usage types
+    types.push(("CostTotals", CostTotals::to_ts()));
+    types.push(("PersonaCost", PersonaCost::to_ts()));
+    types.push(("DailyCost", DailyCost::to_ts()));
+    types.push(("CostBreakdown", CostBreakdown::to_ts()));
+
     // App Mode types
     types.push(("Plan", Plan::to_ts()));
     types.push(("AppMode", AppMode::to_ts()));
@@ -3,13 +3,16 @@ use chrono::Utc;
 use llm_toolkit::agent::impls::RetryAgent;
 use llm_toolkit::agent::impls::claude_code::{ClaudeCodeAgent, ClaudeCodeJsonAgent};
 use llm_toolkit::agent::{Agent, AgentError, AgentOutput, Payload};
-use llm_toolkit::orchestrator::{BlueprintWorkflow, ParallelOrchestrator};
-use orcs_application::UtilityAgentService;
+use llm_toolkit::orchestrator::{BlueprintWorkflow, ParallelOrchestrator, StrategyMap};
+use orcs_application::UtilityOps;
 use orcs_core::OrcsError;
 use orcs_core::agent::build_enhanced_path;
 use orcs_core::repository::TaskRepository;
-use orcs_core::task::{Task, TaskContext, TaskStatus};
+use orcs_core::task::{
+    ExecutionDetails, StepInfo, StepStatus, Task, TaskContext, TaskOrigin, TaskStatus,
+};
 use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
@@ -50,10 +53,95 @@ impl llm_toolkit::agent::DynamicAgent for DynamicAgentAdapter {
     }
 
     fn expertise(&self) -> &str {
-        *self.agent.expertise()
+        self.agent.expertise()
     }
 }
 
+/// Instruction given to the executor for every step of a dry run.
+const DRY_RUN_INSTRUCTION: &str = "DRY RUN: Do not create, modify or delete any files, and do not \
+     run commands that change anything. Instead, describe concretely what you would do for this \
+     step: the files you would touch, the changes you would make and the commands you would run. \
+     Phrase each action as \"Would ...\".";
+
+/// Extra Claude CLI arguments for the executor agent.
+///
+/// Dry runs use plan mode with every file-modifying tool disallowed, so the
+/// executor can read the workspace but not change it.
+fn executor_cli_args(dry_run: bool) -> Vec<String> {
+    if !dry_run {
+        return Vec::new();
+    }
+    vec![
+        "--permission-mode".to_string(),
+        "plan".to_string(),
+        "--disallowedTools".to_string(),
+        "Bash Edit MultiEdit Write NotebookEdit".to_string(),
+    ]
+}
+
+/// Executor agent for dry runs: describes what each step would do.
+struct DryRunAgent {
+    inner: Arc<dyn Agent<Output = String, Expertise = &'static str> + Send + Sync>,
+}
+
+#[async_trait]
+impl Agent for DryRunAgent {
+    type Output = String;
+    type Expertise = &'static str;
+
+    fn expertise(&self) -> &&'static str {
+        self.inner.expertise()
+    }
+
+    async fn execute(&self, intent: Payload) -> Result<String, AgentError> {
+        self.inner
+            .execute(intent.prepend_text(DRY_RUN_INSTRUCTION))
+            .await
+    }
+}
+
+/// Builds step details from a strategy and the context outputs of its steps.
+///
+/// Steps without an output in the context are reported as skipped.
+fn step_infos(strategy: &StrategyMap, context: &HashMap<String, JsonValue>) -> Vec<StepInfo> {
+    strategy
+        .steps
+        .iter()
+        .map(|step| {
+            let output_key = step
+                .output_key
+                .clone()
+                .unwrap_or_else(|| format!("{}_output", step.step_id));
+            let output = context.get(&output_key).cloned();
+            StepInfo {
+                id: step.step_id.clone(),
+                description: step.description.clone(),
+                status: if output.is_some() {
+                    StepStatus::Completed
+                } else {
+                    StepStatus::Skipped
+                },
+                agent: step.assigned_agent.clone(),
+                output,
+                error: None,
+            }
+        })
+        .collect()
+}
+
+/// A single run of a task.
+struct TaskRun {
+    task_id: String,
+    session_id: String,
+    message_content: String,
+    workspace_root: Option<std::path::PathBuf>,
+    thread_context: Option<String>,
+    origin: Option<TaskOrigin>,
+    dry_run: bool,
+    /// Strategy to execute instead of generating one from the request
+    strategy: Option<StrategyMap>,
+}
+
 /// Responsible for executing a single task.
 ///
 /// This struct implements task execution logic using ParallelOrchestrator.
@@ -61,7 +149,7 @@ pub struct TaskExecutor {
     agent: Arc<dyn Agent<Output = String, Expertise = &'static str> + Send + Sync>,
     task_repository: Option<Arc<dyn TaskRepository>>,
     event_sender: Option<mpsc::UnboundedSender<tracing_layer::OrchestratorEvent>>,
-    utility_service: Option<Arc<UtilityAgentService>>,
+    utility_service: Option<Arc<dyn UtilityOps>>,
 }
 
 impl Default for TaskExecutor {
@@ -108,8 +196,8 @@ impl TaskExecutor {
         self
     }
 
-    /// Sets the utility agent service for lightweight LLM operations.
-    pub fn with_utility_service(mut self, service: Arc<UtilityAgentService>) -> Self {
+    /// Sets the utility service for lightweight LLM operations (task titles).
+    pub fn with_utility_service(mut self, service: Arc<dyn UtilityOps>) -> Self {
         self.utility_service = Some(service);
         self
     }
@@ -144,6 +232,7 @@ impl TaskExecutor {
     /// * `session_id` - The session ID where this task is being executed
     /// * `message_content` - The message content to execute as a task
     /// * `workspace_root` - Optional workspace root path where the task should execute
+    /// * `dry_run` - Describe what each step would do instead of doing it
     ///
     /// # Returns
     ///
@@ -154,9 +243,16 @@ impl TaskExecutor {
         session_id: String,
         message_content: String,
         workspace_root: Option<std::path::PathBuf>,
+        dry_run: bool,
     ) -> Result<String, OrcsError> {
-        self.execute_from_message_with_context(session_id, message_content, workspace_root, None)
-            .await
+        self.execute_from_message_with_context(
+            session_id,
+            message_content,
+            workspace_root,
+            None,
+            dry_run,
+        )
+        .await
     }
 
     /// Executes a message content as a task with optional thread context.
@@ -167,6 +263,7 @@ impl TaskExecutor {
     /// * `message_content` - The message content to execute as a task
     /// * `workspace_root` - Optional workspace root path where the task should execute
     /// * `thread_context` - Optional thread context (summary, recent messages) for better task understanding
+    /// * `dry_run` - Describe what each step would do instead of doing it
     ///
     /// # Returns
     ///
@@ -178,8 +275,140 @@ impl TaskExecutor {
         message_content: String,
         workspace_root: Option<std::path::PathBuf>,
         thread_context: Option<String>,
+        dry_run: bool,
+    ) -> Result<String, OrcsError> {
+        self.execute_from_message_with_origin(
+            Uuid::new_v4().to_string(),
+            session_id,
+            message_content,
+            workspace_root,
+            thread_context,
+            None,
+            dry_run,
+        )
+        .await
+    }
+
+    /// Executes a message content as a task spawned from a conversation message.
+    ///
+    /// The caller picks the task ID up front, so it can link the task to the
+    /// originating message before execution starts.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the task to create (UUID format)
+    /// * `session_id` - The session ID where this task is being executed
+    /// * `message_content` - The message content to execute as a task
+    /// * `workspace_root` - Optional workspace root path where the task should execute
+    /// * `thread_context` - Optional thread context (summary, recent messages) for better task understanding
+    /// * `origin` - Optional conversation message this task was spawned from
+    /// * `dry_run` - Describe what each step would do instead of doing it
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` with the execution result summary
+    /// * `Err(OrcsError)` if an error occurs during execution
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute_from_message_with_origin(
+        &self,
+        task_id: String,
+        session_id: String,
+        message_content: String,
+        workspace_root: Option<std::path::PathBuf>,
+        thread_context: Option<String>,
+        origin: Option<TaskOrigin>,
+        dry_run: bool,
+    ) -> Result<String, OrcsError> {
+        self.run(TaskRun {
+            task_id,
+            session_id,
+            message_content,
+            workspace_root,
+            thread_context,
+            origin,
+            dry_run,
+            strategy: None,
+        })
+        .await
+    }
+
+    /// Re-executes a dry-run task for real with the strategy it planned.
+    ///
+    /// The real run is recorded as a new task linked to the same originating
+    /// message; the dry-run task is kept for reference.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_id` - The ID of the dry-run task to promote
+    /// * `workspace_root` - Optional workspace root path where the task should execute
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` with the execution result summary
+    /// * `Err(OrcsError)` if the task is not a completed dry run with a saved
+    ///   strategy, or if execution fails
+    pub async fn promote_dry_run(
+        &self,
+        task_id: &str,
+        workspace_root: Option<std::path::PathBuf>,
     ) -> Result<String, OrcsError> {
-        tracing::info!("TaskExecutor: Executing task from message with ParallelOrchestrator");
+        let repository = self.task_repository.as_ref().ok_or_else(|| {
+            OrcsError::Execution("No task repository to load the dry run from".to_string())
+        })?;
+        let task = repository
+            .find_by_id(task_id)
+            .await?
+            .ok_or_else(|| OrcsError::Execution(format!("Task not found: {}", task_id)))?;
+        if !task.dry_run || task.status != TaskStatus::Completed {
+            return Err(OrcsError::Execution(format!(
+                "Task {} is not a completed dry run",
+                task_id
+            )));
+        }
+        let strategy_json = task.strategy.as_deref().ok_or_else(|| {
+            OrcsError::Execution(format!("Dry run {} has no saved strategy", task_id))
+        })?;
+        let strategy: StrategyMap = serde_json::from_str(strategy_json).map_err(|e| {
+            OrcsError::Execution(format!("Invalid strategy of dry run {}: {}", task_id, e))
+        })?;
+
+        let origin = match (task.origin_message_timestamp, task.origin_author) {
+            (Some(message_timestamp), Some(author)) => Some(TaskOrigin {
+                message_timestamp,
+                author,
+            }),
+            _ => None,
+        };
+        self.run(TaskRun {
+            task_id: Uuid::new_v4().to_string(),
+            session_id: task.session_id,
+            // The description already includes the thread context of the dry run
+            message_content: task.description,
+            workspace_root,
+            thread_context: None,
+            origin,
+            dry_run: false,
+            strategy: Some(strategy),
+        })
+        .await
+    }
+
+    async fn run(&self, run: TaskRun) -> Result<String, OrcsError> {
+        let TaskRun {
+            task_id,
+            session_id,
+            message_content,
+            workspace_root,
+            thread_context,
+            origin,
+            dry_run,
+            strategy,
+        } = run;
+
+        tracing::info!(
+            "TaskExecutor: Executing task from message with ParallelOrchestrator (dry run: {})",
+            dry_run
+        );
         tracing::debug!(
             "Task content: {}",
             message_content.chars().take(200).collect::<String>()
@@ -213,14 +442,19 @@ impl TaskExecutor {
             Arc::new(
                 ClaudeCodeAgent::new()
                     .with_cwd(workspace.clone())
-                    .with_env("PATH", enhanced_path),
+                    .with_env("PATH", enhanced_path)
+                    .with_args(executor_cli_args(dry_run)),
             ) as Arc<dyn Agent<Output = String, Expertise = &'static str> + Send + Sync>
         } else {
             self.agent.clone()
         };
+        let agent = if dry_run {
+            Arc::new(DryRunAgent { inner: agent })
+                as Arc<dyn Agent<Output = String, Expertise = &'static str> + Send + Sync>
+        } else {
+            agent
+        };
 
-        // Generate task ID and timestamps
-        let task_id = Uuid::new_v4().to_string();
         let now = Utc::now().to_rfc3339();
 
         // Use fallback title immediately for fast UI display
@@ -249,6 +483,9 @@ impl TaskExecutor {
             execution_details: None,
             strategy: None,
             journal_log: None,
+            origin_message_timestamp: origin.as_ref().map(|o| o.message_timestamp.clone()),
+            origin_author: origin.map(|o| o.author),
+            dry_run,
         };
 
         // 🚀 STEP 1: Save immediately with Pending status (for instant UI display)
@@ -347,6 +584,9 @@ impl TaskExecutor {
             );
             ParallelOrchestrator::new(blueprint)
         };
+        if let Some(strategy) = strategy {
+            orchestrator.set_strategy_map(strategy);
+        }
 
         // Register our executor agent as a DynamicAgent (with workspace context if provided)
         let executor_agent = Arc::new(DynamicAgentAdapter::new(
@@ -373,11 +613,17 @@ impl TaskExecutor {
             task.status = TaskStatus::Completed;
             task.completed_at = Some(completed_at);
 
+            let headline = if dry_run {
+                "🔍 Dry run completed: nothing was changed. Review the steps, then promote it to run for real."
+            } else {
+                "✅ Task completed successfully!"
+            };
             let summary = format!(
-                "✅ Task completed successfully!\n\
+                "{}\n\
                  Steps executed: {}\n\
                  Steps skipped: {}\n\
                  Context keys: {}",
+                headline,
                 result.steps_executed,
                 result.steps_skipped,
                 result.context.keys().len()
@@ -393,9 +639,14 @@ impl TaskExecutor {
 
             task.result = Some(summary);
 
-            // Save execution details with context outputs
-            task.execution_details = Some(orcs_core::task::ExecutionDetails {
-                steps: vec![], // TODO: Extract step info from orchestrator
+            // Save execution details with context outputs; dry runs list each
+            // step's narrative so it can be reviewed before promoting
+            let steps = match orchestrator.strategy_map() {
+                Some(strategy) if dry_run => step_infos(strategy, &result.context),
+                _ => vec![], // TODO: Extract step info from orchestrator
+            };
+            task.execution_details = Some(ExecutionDetails {
+                steps,
                 context: result.context.clone(),
             });
 
@@ -435,7 +686,7 @@ impl TaskExecutor {
             task.completed_at = Some(completed_at);
 
             // Save execution details with context outputs (even on failure)
-            task.execution_details = Some(orcs_core::task::ExecutionDetails {
+            task.execution_details = Some(ExecutionDetails {
                 steps: vec![], // TODO: Extract step info from orchestrator
                 context: result.context.clone(),
             });
@@ -475,3 +726,196 @@ impl TaskExecutor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_toolkit::orchestrator::StrategyStep;
+    use orcs_infrastructure::AsyncDirTaskRepository;
+    use std::sync::Mutex;
+
+    /// Executor agent that records the prompts it receives.
+    #[derive(Default)]
+    struct RecordingAgent {
+        prompts: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Agent for RecordingAgent {
+        type Output = String;
+        type Expertise = &'static str;
+
+        fn expertise(&self) -> &&'static str {
+            &"Executes task steps"
+        }
+
+        async fn execute(&self, intent: Payload) -> Result<String, AgentError> {
+            let prompt = intent.to_text();
+            let reply = if prompt.contains("DRY RUN") {
+                "Would rename parser.rs to lexer.rs"
+            } else {
+                "Renamed parser.rs to lexer.rs"
+            };
+            self.prompts.lock().unwrap().push(prompt);
+            Ok(reply.to_string())
+        }
+    }
+
+    /// Utility service that records the task descriptions it titles.
+    #[derive(Default)]
+    struct MockUtility {
+        titled: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl UtilityOps for MockUtility {
+        async fn generate_task_title(&self, task_description: &str) -> anyhow::Result<String> {
+            self.titled
+                .lock()
+                .unwrap()
+                .push(task_description.to_string());
+            Ok("Rename parser module".to_string())
+        }
+
+        async fn summarize(&self, content: &str) -> anyhow::Result<String> {
+            Ok(content.to_string())
+        }
+
+        async fn classify(&self, _content: &str, labels: &[String]) -> anyhow::Result<String> {
+            Ok(labels[0].clone())
+        }
+    }
+
+    fn rename_strategy() -> StrategyMap {
+        let mut strategy = StrategyMap::new("Rename the parser module".to_string());
+        strategy.add_step(StrategyStep::new(
+            "rename".to_string(),
+            "Rename the parser module".to_string(),
+            "executor".to_string(),
+            "Rename src/parser.rs to src/lexer.rs".to_string(),
+            "Renamed module".to_string(),
+        ));
+        strategy
+    }
+
+    #[test]
+    fn test_dry_run_executor_gets_no_file_modifying_tools() {
+        assert!(executor_cli_args(false).is_empty());
+
+        let args = executor_cli_args(true);
+        for flag in [
+            "--dangerously-skip-permissions",
+            "--allowedTools",
+            "acceptEdits",
+            "bypassPermissions",
+        ] {
+            assert!(!args.iter().any(|arg| arg.contains(flag)), "{}", flag);
+        }
+        let mode = args
+            .iter()
+            .position(|arg| arg == "--permission-mode")
+            .unwrap();
+        assert_eq!(args[mode + 1], "plan");
+        let disallowed = args
+            .iter()
+            .position(|arg| arg == "--disallowedTools")
+            .map(|index| args[index + 1].split_whitespace().collect::<Vec<_>>())
+            .unwrap();
+        for tool in ["Bash", "Edit", "MultiEdit", "Write", "NotebookEdit"] {
+            assert!(disallowed.contains(&tool), "{}", tool);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_promote_dry_run_reuses_the_strategy() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repository = Arc::new(
+            AsyncDirTaskRepository::new(Some(temp_dir.path()))
+                .await
+                .unwrap(),
+        );
+        let agent = Arc::new(RecordingAgent::default());
+        let executor =
+            TaskExecutor::with_agent(agent.clone()).with_task_repository(repository.clone());
+
+        let dry_run_id = Uuid::new_v4().to_string();
+        executor
+            .run(TaskRun {
+                task_id: dry_run_id.clone(),
+                session_id: "session".to_string(),
+                message_content: "Rename the parser module".to_string(),
+                workspace_root: None,
+                thread_context: None,
+                origin: None,
+                dry_run: true,
+                strategy: Some(rename_strategy()),
+            })
+            .await
+            .unwrap();
+
+        let dry_run = repository.find_by_id(&dry_run_id).await.unwrap().unwrap();
+        assert!(dry_run.dry_run);
+        assert_eq!(dry_run.status, TaskStatus::Completed);
+        let steps = dry_run.execution_details.unwrap().steps;
+        assert_eq!(steps.len(), 1);
+        assert_eq!(
+            steps[0].output,
+            Some(JsonValue::String(
+                "Would rename parser.rs to lexer.rs".to_string()
+            ))
+        );
+        assert!(agent.prompts.lock().unwrap()[0].starts_with("DRY RUN"));
+
+        executor.promote_dry_run(&dry_run_id, None).await.unwrap();
+
+        let prompts = agent.prompts.lock().unwrap().clone();
+        assert_eq!(prompts.len(), 2);
+        assert!(!prompts[1].contains("DRY RUN"));
+        assert!(prompts[1].contains("Rename src/parser.rs to src/lexer.rs"));
+
+        let tasks = repository.list_by_session("session").await.unwrap();
+        let promoted = tasks.iter().find(|task| task.id != dry_run_id).unwrap();
+        assert!(!promoted.dry_run);
+        assert_eq!(promoted.status, TaskStatus::Completed);
+        assert_eq!(promoted.strategy, dry_run.strategy);
+
+        // A real run cannot be promoted again
+        assert!(executor.promote_dry_run(&promoted.id, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_task_title_comes_from_utility_service() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repository = Arc::new(
+            AsyncDirTaskRepository::new(Some(temp_dir.path()))
+                .await
+                .unwrap(),
+        );
+        let utility = Arc::new(MockUtility::default());
+        let executor = TaskExecutor::with_agent(Arc::new(RecordingAgent::default()))
+            .with_task_repository(repository.clone())
+            .with_utility_service(utility.clone());
+
+        let task_id = Uuid::new_v4().to_string();
+        executor
+            .run(TaskRun {
+                task_id: task_id.clone(),
+                session_id: "session".to_string(),
+                message_content: "Please rename src/parser.rs to src/lexer.rs".to_string(),
+                workspace_root: None,
+                thread_context: None,
+                origin: None,
+                dry_run: false,
+                strategy: Some(rename_strategy()),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *utility.titled.lock().unwrap(),
+            vec!["Please rename src/parser.rs to src/lexer.rs".to_string()]
+        );
+        let task = repository.find_by_id(&task_id).await.unwrap().unwrap();
+        assert_eq!(task.title, "Rename parser module");
+    }
+}
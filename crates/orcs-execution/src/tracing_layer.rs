@@ -206,6 +206,7 @@ impl OrchestratorEventBuilder {
             "context_keys".to_string(),
             serde_json::json!(task.context_keys),
         );
+        fields.insert("dry_run".to_string(), serde_json::json!(task.dry_run));
 
         // Optional fields
         if let Some(ref error) = task.error {
@@ -216,7 +217,7 @@ impl OrchestratorEventBuilder {
         }
 
         eprintln!("[EventBuilder] info_from_task called:");
-        eprintln!("  task_id: {}", &task.id);
+        eprintln!("  task_id: {}", task.id);
         eprintln!("  status: {}", task.status.as_str());
         eprintln!("  fields count: {}", fields.len());
         eprintln!("  fields keys: {:?}", fields.keys().collect::<Vec<_>>());
@@ -0,0 +1,114 @@
+//! Named checkpoints of the application state.
+//!
+//! Each snapshot is stored as a versioned app state file (`<name>.json`), so
+//! snapshots taken by an older version are migrated when they are restored.
+
+use crate::dto::{AppStateDTO, create_app_state_migrator};
+use crate::paths::{OrcsPaths, ServiceType};
+use orcs_core::error::{OrcsError, Result};
+use orcs_core::state::model::AppState;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use version_migrate::{FromDomain, Migrator};
+
+/// A stored app state snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStateSnapshotInfo {
+    pub name: String,
+    /// When the snapshot was written (RFC 3339).
+    pub created_at: String,
+}
+
+/// Directory of named app state snapshots.
+pub struct AppStateSnapshotStore {
+    dir: PathBuf,
+    migrator: Migrator,
+}
+
+impl AppStateSnapshotStore {
+    /// Creates a store under `base_dir`, or the default config directory when `None`.
+    pub async fn new(base_dir: Option<&Path>) -> Result<Self> {
+        let dir = OrcsPaths::new(base_dir)
+            .get_path(ServiceType::AppStateSnapshot)
+            .map_err(|e| OrcsError::config(e.to_string()))?
+            .into_path_buf();
+        tokio::fs::create_dir_all(&dir).await?;
+        Ok(Self {
+            dir,
+            migrator: create_app_state_migrator(),
+        })
+    }
+
+    /// Writes `snapshot` under `name`, replacing an existing snapshot of that name.
+    pub async fn save(&self, name: &str, snapshot: AppStateDTO) -> Result<AppStateSnapshotInfo> {
+        let path = self.path(name)?;
+        let content = self.migrator.save_flat(snapshot)?;
+        // Write then rename so a crash never leaves a truncated snapshot.
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, content).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        self.info(name, &path).await
+    }
+
+    /// Loads the snapshot stored under `name`.
+    pub async fn load(&self, name: &str) -> Result<AppStateDTO> {
+        let path = self.path(name)?;
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(OrcsError::not_found("AppStateSnapshot", name));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let state: AppState = self.migrator.load_flat("app_state", &content)?;
+        Ok(AppStateDTO::from_domain(state))
+    }
+
+    /// Lists the stored snapshots, newest first.
+    pub async fn list(&self) -> Result<Vec<AppStateSnapshotInfo>> {
+        let mut snapshots = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json")
+                && let Some(name) = path.file_stem().and_then(|stem| stem.to_str())
+            {
+                snapshots.push(self.info(name, &path).await?);
+            }
+        }
+        snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(snapshots)
+    }
+
+    /// Deletes the snapshot stored under `name`; a missing snapshot is not an error.
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path(name)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolves the file of a snapshot, rejecting names that are not plain file stems.
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(OrcsError::data_access(format!(
+                "Invalid snapshot name '{}': use letters, digits, '-' or '_'",
+                name
+            )));
+        }
+        Ok(self.dir.join(format!("{}.json", name)))
+    }
+
+    async fn info(&self, name: &str, path: &Path) -> Result<AppStateSnapshotInfo> {
+        let modified = tokio::fs::metadata(path).await?.modified()?;
+        Ok(AppStateSnapshotInfo {
+            name: name.to_string(),
+            created_at: chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339(),
+        })
+    }
+}
@@ -0,0 +1,199 @@
+//! AsyncDirStorage-based ConversationScriptRepository implementation
+//!
+//! Directory structure:
+//! ```text
+//! base_dir/
+//! └── conversation_scripts/
+//!     ├── <script-id-1>.toml
+//!     └── <script-id-2>.toml
+//! ```
+//!
+//! # System vs User Scripts
+//!
+//! System scripts are provided by `get_default_scripts()` and are merged
+//! with user-created scripts from storage. System scripts cannot be modified
+//! or deleted.
+
+use crate::OrcsPaths;
+use crate::dto::create_conversation_script_migrator;
+use crate::storage_repository::StorageRepository;
+use orcs_core::conversation_script::{
+    ConversationScript, ConversationScriptRepository, get_default_scripts,
+};
+use orcs_core::dialogue::PresetSource;
+use orcs_core::error::Result;
+use std::path::Path;
+use version_migrate::AsyncDirStorage;
+
+/// AsyncDirStorage-based conversation script repository.
+pub struct AsyncDirConversationScriptRepository {
+    storage: AsyncDirStorage,
+}
+
+impl StorageRepository for AsyncDirConversationScriptRepository {
+    const SERVICE_TYPE: crate::paths::ServiceType = crate::paths::ServiceType::ConversationScript;
+    const ENTITY_NAME: &'static str = "conversation_script";
+
+    fn storage(&self) -> &AsyncDirStorage {
+        &self.storage
+    }
+}
+
+impl AsyncDirConversationScriptRepository {
+    /// Creates an AsyncDirConversationScriptRepository instance at the default location.
+    pub async fn default() -> Result<Self> {
+        Self::new(None).await
+    }
+
+    /// Creates a new AsyncDirConversationScriptRepository with custom base directory (for testing).
+    ///
+    /// # Arguments
+    ///
+    /// * `base_dir` - Base directory for conversation scripts
+    pub async fn new(base_dir: Option<&Path>) -> Result<Self> {
+        let migrator = create_conversation_script_migrator();
+        let orcs_paths = OrcsPaths::new(base_dir);
+        let storage = orcs_paths
+            .create_async_dir_storage(Self::SERVICE_TYPE, migrator)
+            .await?;
+        Ok(Self { storage })
+    }
+
+    /// Helper to check if a script is a system script (cannot be modified/deleted).
+    fn is_system_script(script_id: &str) -> bool {
+        script_id.starts_with("script-")
+    }
+}
+
+#[async_trait::async_trait]
+impl ConversationScriptRepository for AsyncDirConversationScriptRepository {
+    async fn find_by_id(&self, script_id: &str) -> Result<Option<ConversationScript>> {
+        if let Some(system_script) = get_default_scripts()
+            .into_iter()
+            .find(|s| s.id == script_id)
+        {
+            return Ok(Some(system_script));
+        }
+
+        match self
+            .storage
+            .load::<ConversationScript>(Self::ENTITY_NAME, script_id)
+            .await
+        {
+            Ok(script) => Ok(Some(script)),
+            Err(e) => {
+                let orcs_err: orcs_core::OrcsError = e.into();
+                if orcs_err.is_not_found()
+                    || (orcs_err.is_io() && orcs_err.to_string().contains("File not found"))
+                {
+                    Ok(None)
+                } else {
+                    Err(orcs_err)
+                }
+            }
+        }
+    }
+
+    async fn save(&self, script: &ConversationScript) -> Result<()> {
+        if script.source == PresetSource::System || Self::is_system_script(&script.id) {
+            return Err(orcs_core::OrcsError::config(
+                "Cannot save system scripts. System scripts are read-only.",
+            ));
+        }
+        script.validate()?;
+
+        self.storage
+            .save(Self::ENTITY_NAME, &script.id, script)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, script_id: &str) -> Result<()> {
+        if Self::is_system_script(script_id) {
+            return Err(orcs_core::OrcsError::config(
+                "Cannot delete system scripts. System scripts are read-only.",
+            ));
+        }
+
+        self.storage.delete(script_id).await?;
+        Ok(())
+    }
+
+    async fn get_all(&self) -> Result<Vec<ConversationScript>> {
+        let mut all_scripts = get_default_scripts();
+        let user_scripts = self
+            .storage
+            .load_all::<ConversationScript>(Self::ENTITY_NAME)
+            .await?;
+        all_scripts.extend(user_scripts.into_iter().map(|(_, s)| s));
+        Ok(all_scripts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orcs_core::conversation_script::ScriptStep;
+    use tempfile::TempDir;
+
+    fn user_script(id: &str) -> ConversationScript {
+        ConversationScript {
+            id: id.to_string(),
+            name: "Standup".to_string(),
+            icon: None,
+            description: None,
+            steps: vec![
+                ScriptStep::PostSystemMessage {
+                    content: "Standup".to_string(),
+                },
+                ScriptStep::RunRound {
+                    participants: vec!["Mai".to_string()],
+                    prompt: "What are you working on?".to_string(),
+                },
+            ],
+            source: PresetSource::User,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_all_combines_system_and_user_scripts() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = AsyncDirConversationScriptRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let script = user_script(&uuid::Uuid::new_v4().to_string());
+        repo.save(&script).await.unwrap();
+
+        let all = repo.get_all().await.unwrap();
+        assert!(all.iter().any(|s| s.id == "script-retro"));
+        assert!(all.iter().any(|s| s.id == "script-design-review"));
+        assert!(all.contains(&script));
+        assert_eq!(repo.find_by_id(&script.id).await.unwrap(), Some(script));
+    }
+
+    #[tokio::test]
+    async fn test_system_scripts_are_read_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = AsyncDirConversationScriptRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        assert!(repo.save(&user_script("script-retro")).await.is_err());
+        assert!(repo.delete("script-retro").await.is_err());
+        assert!(repo.find_by_id("script-retro").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_script_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = AsyncDirConversationScriptRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let mut script = user_script("empty");
+        script.steps.clear();
+        assert!(repo.save(&script).await.is_err());
+        assert!(repo.find_by_id("empty").await.unwrap().is_none());
+    }
+}
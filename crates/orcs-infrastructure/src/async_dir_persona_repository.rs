@@ -15,12 +15,16 @@ use orcs_core::repository::PersonaRepository;
 use std::path::Path;
 use version_migrate::AsyncDirStorage;
 
+/// Marker file recording that the built-in presets were seeded.
+const PRESETS_SEEDED_MARKER: &str = ".presets_seeded";
+
 /// AsyncDirStorage-based persona repository.
 ///
 /// Directory structure:
 /// ```text
 /// base_dir/
 /// └── personas/
+///     ├── .presets_seeded
 ///     ├── <uuid-1>.toml
 ///     ├── <uuid-2>.toml
 ///     └── <uuid-3>.toml
@@ -94,6 +98,11 @@ impl PersonaRepository for AsyncDirPersonaRepository {
     }
 
     async fn get_all(&self) -> Result<Vec<Persona>> {
+        let personas = self.get_all_including_archived().await?;
+        Ok(personas.into_iter().filter(|p| !p.archived).collect())
+    }
+
+    async fn get_all_including_archived(&self) -> Result<Vec<Persona>> {
         let all_personas = self.storage.load_all::<Persona>(Self::ENTITY_NAME).await?;
 
         // Extract values from Vec<(String, Persona)>
@@ -112,14 +121,72 @@ impl PersonaRepository for AsyncDirPersonaRepository {
         }
         Ok(())
     }
+
+    async fn presets_seeded(&self) -> Result<bool> {
+        Ok(tokio::fs::try_exists(self.base_dir().join(PRESETS_SEEDED_MARKER)).await?)
+    }
+
+    async fn mark_presets_seeded(&self) -> Result<()> {
+        tokio::fs::write(self.base_dir().join(PRESETS_SEEDED_MARKER), b"").await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use orcs_core::persona::{PersonaBackend, PersonaSource};
+    use orcs_core::persona::{PersonaBackend, PersonaSource, PresetSeeding, seed_default_presets};
     use tempfile::TempDir;
 
+    #[tokio::test]
+    async fn test_presets_are_seeded_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = AsyncDirPersonaRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let PresetSeeding::Seeded(presets) = seed_default_presets(&repo).await.unwrap() else {
+            panic!("an empty repository should be seeded");
+        };
+        assert!(repo.presets_seeded().await.unwrap());
+        assert_eq!(repo.get_all().await.unwrap().len(), presets.len());
+
+        // Personas the user deleted stay deleted
+        for preset in &presets {
+            repo.delete(&preset.id).await.unwrap();
+        }
+        assert!(matches!(
+            seed_default_presets(&repo).await.unwrap(),
+            PresetSeeding::PreviouslySeeded
+        ));
+        assert!(repo.get_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_existing_personas_mark_presets_as_seeded() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = AsyncDirPersonaRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+        let mut persona = orcs_core::persona::get_default_presets().remove(0);
+        persona.source = PersonaSource::User;
+        repo.save(&persona).await.unwrap();
+
+        assert!(matches!(
+            seed_default_presets(&repo).await.unwrap(),
+            PresetSeeding::AlreadyPresent
+        ));
+        assert!(repo.presets_seeded().await.unwrap());
+        let ids: Vec<String> = repo
+            .get_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        assert_eq!(ids, vec![persona.id]);
+    }
+
     #[tokio::test]
     async fn test_save_and_load_personas() {
         let temp_dir = TempDir::new().unwrap();
@@ -141,10 +208,16 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         // Save
-        repo.save_all(&[persona.clone()]).await.unwrap();
+        repo.save_all(std::slice::from_ref(&persona)).await.unwrap();
 
         // Load
         let personas = repo.get_all().await.unwrap();
@@ -174,6 +247,12 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         let persona2 = Persona {
@@ -190,6 +269,12 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         // Save multiple
@@ -228,6 +313,12 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         let persona2 = Persona {
@@ -244,10 +335,20 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
-        repo.save_all(&[persona1.clone()]).await.unwrap();
-        repo.save_all(&[persona2.clone()]).await.unwrap();
+        repo.save_all(std::slice::from_ref(&persona1))
+            .await
+            .unwrap();
+        repo.save_all(std::slice::from_ref(&persona2))
+            .await
+            .unwrap();
 
         let personas = repo.get_all().await.unwrap();
         let names: Vec<String> = personas.iter().map(|p| p.name.clone()).collect();
@@ -288,6 +389,12 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         // Save persona
@@ -324,6 +431,12 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         // Save
@@ -357,6 +470,12 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         // Save original
@@ -396,6 +515,12 @@ mod tests {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         };
 
         // Save
@@ -411,6 +536,44 @@ mod tests {
         assert!(repo.find_by_id(&persona.id).await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn test_get_all_excludes_archived_personas() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = AsyncDirPersonaRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let persona = Persona {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: "Retired".to_string(),
+            role: "Tester".to_string(),
+            background: "Test background".to_string(),
+            communication_style: "Test style".to_string(),
+            default_participant: true,
+            source: PersonaSource::User,
+            backend: PersonaBackend::ClaudeCli,
+            model_name: None,
+            icon: None,
+            base_color: None,
+            gemini_options: None,
+            kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: true,
+        };
+        repo.save(&persona).await.unwrap();
+
+        assert!(repo.get_all().await.unwrap().is_empty());
+
+        let all = repo.get_all_including_archived().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert!(all[0].archived);
+        assert!(repo.find_by_id(&persona.id).await.unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn test_delete_non_existent_persona() {
         let temp_dir = TempDir::new().unwrap();
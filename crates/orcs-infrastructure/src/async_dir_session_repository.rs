@@ -12,10 +12,33 @@ use crate::storage_repository::StorageRepository;
 use async_trait::async_trait;
 use orcs_core::error::Result;
 use orcs_core::repository::SessionRepository;
-use orcs_core::session::Session;
+use orcs_core::session::{Session, SessionSummary};
+use serde::Deserialize;
 use std::path::Path;
 use version_migrate::AsyncDirStorage;
 
+/// Top-level session fields read without deserializing messages.
+///
+/// Every field is optional so older schemas fall back to a full load.
+#[derive(Deserialize)]
+struct SessionSummaryRecord {
+    id: Option<String>,
+    title: Option<String>,
+    workspace_id: Option<String>,
+    updated_at: Option<String>,
+}
+
+impl SessionSummaryRecord {
+    fn into_summary(self) -> Option<SessionSummary> {
+        Some(SessionSummary {
+            id: self.id?,
+            title: self.title?,
+            workspace_id: self.workspace_id?,
+            updated_at: self.updated_at?,
+        })
+    }
+}
+
 /// AsyncDirStorage-based session repository.
 ///
 /// Directory structure:
@@ -73,6 +96,13 @@ impl AsyncDirSessionRepository {
         Ok(Self { storage })
     }
 
+    /// Path of a session's file.
+    fn session_path(&self, session_id: &str) -> std::path::PathBuf {
+        self.storage
+            .base_path()
+            .join(format!("{}.toml", session_id))
+    }
+
     /// Fallback implementation that loads sessions individually, skipping corrupt files.
     async fn list_all_with_fallback(&self) -> Result<Vec<Session>> {
         use tokio::fs;
@@ -185,6 +215,48 @@ impl SessionRepository for AsyncDirSessionRepository {
         Ok(())
     }
 
+    async fn list_ids(&self) -> Result<Vec<String>> {
+        let mut ids = self.storage.list_ids().await?;
+        ids.sort();
+        Ok(ids)
+    }
+
+    async fn stored_size(&self, session_id: &str) -> Result<Option<u64>> {
+        match tokio::fs::metadata(self.session_path(session_id)).await {
+            Ok(metadata) => Ok(Some(metadata.len())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list_summaries(&self) -> Result<Vec<SessionSummary>> {
+        let mut summaries = Vec::new();
+        for id in self.list_ids().await? {
+            let path = self.session_path(&id);
+            let record = tokio::fs::read_to_string(&path)
+                .await
+                .ok()
+                .and_then(|content| toml::from_str::<SessionSummaryRecord>(&content).ok())
+                .and_then(SessionSummaryRecord::into_summary);
+
+            match record {
+                Some(summary) => summaries.push(summary),
+                // Older or unreadable layouts go through the migrator
+                None => match self.find_by_id(&id).await {
+                    Ok(Some(session)) => summaries.push(SessionSummary::from(&session)),
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!(
+                        "[AsyncDirSessionRepository] Skipping unreadable session {}: {:?}",
+                        id,
+                        e
+                    ),
+                },
+            }
+        }
+        summaries.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(summaries)
+    }
+
     async fn list_all(&self) -> Result<Vec<Session>> {
         // Try the fast path first using load_all
         match self.storage.load_all::<Session>(Self::ENTITY_NAME).await {
@@ -234,7 +306,10 @@ impl SessionRepository for AsyncDirSessionRepository {
 mod tests {
     use super::*;
     use llm_toolkit::agent::dialogue::ExecutionModel;
-    use orcs_core::session::{AppMode, ConversationMessage, MessageMetadata, MessageRole};
+    use orcs_core::session::{
+        AppMode, AutoChatConfig, ConversationMessage, MessageMetadata, MessageRole, StopCondition,
+        TurnStats,
+    };
     use std::collections::HashMap;
     use tempfile::TempDir;
 
@@ -287,6 +362,20 @@ mod tests {
             context_mode: Default::default(),
             sandbox_state: None,
             last_memory_sync_at: None,
+            is_generating: false,
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: 8_000,
         }
     }
 
@@ -313,6 +402,277 @@ mod tests {
         assert_eq!(loaded.current_persona_id, "mai");
     }
 
+    #[tokio::test]
+    async fn test_is_generating_is_not_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let mut session = create_test_session("generating");
+        session.is_generating = true;
+        repository.save(&session).await.unwrap();
+
+        let loaded = repository.find_by_id("generating").await.unwrap().unwrap();
+        assert!(!loaded.is_generating);
+        assert!(
+            !repository
+                .list_all()
+                .await
+                .unwrap()
+                .iter()
+                .any(|s| s.is_generating)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_legacy_error_history_is_relocated_on_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        // Old layout: errors kept under a pseudo persona key
+        let mut session = create_test_session("legacy-errors");
+        session.persona_histories.insert(
+            orcs_core::session::LEGACY_ERROR_HISTORY_KEY.to_string(),
+            vec![ConversationMessage {
+                role: MessageRole::System,
+                content: "Rate limited".to_string(),
+                timestamp: "2024-01-01T00:00:02Z".to_string(),
+                metadata: MessageMetadata {
+                    error_severity: Some(orcs_core::session::ErrorSeverity::Critical),
+                    include_in_dialogue: true,
+                    ..Default::default()
+                },
+                attachments: vec![],
+            }],
+        );
+        session.system_messages.push(ConversationMessage {
+            role: MessageRole::System,
+            content: "Mai joined".to_string(),
+            timestamp: "2024-01-01T00:00:03Z".to_string(),
+            metadata: MessageMetadata::default(),
+            attachments: vec![],
+        });
+        repository.save(&session).await.unwrap();
+
+        let loaded = repository
+            .find_by_id("legacy-errors")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            loaded.persona_histories.keys().collect::<Vec<_>>(),
+            vec!["mai"]
+        );
+        let contents: Vec<&str> = loaded
+            .system_messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(contents, vec!["Rate limited", "Mai joined"]);
+        assert!(loaded.system_messages[0].metadata.include_in_dialogue);
+    }
+
+    #[tokio::test]
+    async fn test_turn_stats_are_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let stats = TurnStats {
+            duration_ms: 1_250,
+            input_tokens: Some(1_000),
+            output_tokens: Some(200),
+            estimated_cost_microusd: Some(6_000),
+        };
+        let mut session = create_test_session("turn-stats");
+        session.persona_histories.get_mut("mai").unwrap()[1]
+            .metadata
+            .turn_stats = Some(stats.clone());
+        repository.save(&session).await.unwrap();
+
+        let loaded = repository.find_by_id("turn-stats").await.unwrap().unwrap();
+        let history = &loaded.persona_histories["mai"];
+        assert_eq!(history[1].metadata.turn_stats, Some(stats));
+        // Messages without stats, like those saved before stats existed, load without
+        assert_eq!(history[0].metadata.turn_stats, None);
+    }
+
+    #[tokio::test]
+    async fn test_auto_chat_config_is_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let config = AutoChatConfig {
+            stop_phrases: vec!["LGTM".to_string()],
+            iteration_delay_ms: Some(2000),
+            adaptive_delay: true,
+            ..AutoChatConfig::default()
+        };
+        let mut session = create_test_session("auto-chat");
+        session.auto_chat_config = Some(config.clone());
+        repository.save(&session).await.unwrap();
+
+        let loaded = repository.find_by_id("auto-chat").await.unwrap().unwrap();
+        assert_eq!(loaded.auto_chat_config, Some(config));
+    }
+
+    #[tokio::test]
+    async fn test_pinboard_is_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let mut session = create_test_session("pinboard");
+        session.pinboard = Some("Use UTC everywhere\n\nShip on Friday".to_string());
+        session.pinboard_agent_edits_enabled = true;
+        repository.save(&session).await.unwrap();
+
+        let loaded = repository.find_by_id("pinboard").await.unwrap().unwrap();
+        assert_eq!(loaded.pinboard, session.pinboard);
+        assert!(loaded.pinboard_agent_edits_enabled);
+
+        // Sessions saved before the pinboard existed load without one
+        let legacy: toml::Value = toml::from_str(
+            r#"
+version = "4.10.0"
+id = "legacy"
+title = "Legacy"
+created_at = "2024-01-01T00:00:00Z"
+updated_at = "2024-01-01T00:00:00Z"
+current_persona_id = "mai"
+workspace_id = "ws"
+
+[persona_histories]
+
+[app_mode]
+type = "Idle"
+"#,
+        )
+        .unwrap();
+        let legacy: Session = create_session_migrator()
+            .load_flat_from("session", legacy)
+            .unwrap();
+        assert_eq!(legacy.pinboard, None);
+        assert!(!legacy.pinboard_agent_edits_enabled);
+        assert!(legacy.apply_conversation_mode_in_clean);
+        assert!(!legacy.participant_suggestions_enabled);
+        assert_eq!(legacy.script_run, None);
+    }
+
+    #[tokio::test]
+    async fn test_clean_mode_conversation_override_is_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let mut session = create_test_session("clean-override");
+        assert!(session.apply_conversation_mode_in_clean);
+        session.apply_conversation_mode_in_clean = false;
+        repository.save(&session).await.unwrap();
+
+        let loaded = repository
+            .find_by_id("clean-override")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!loaded.apply_conversation_mode_in_clean);
+    }
+
+    #[tokio::test]
+    async fn test_script_run_is_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let mut session = create_test_session("script-run");
+        session.script_run = Some(orcs_core::conversation_script::ScriptRun {
+            script_id: "script-retro".to_string(),
+            script_name: "Weekly Retro".to_string(),
+            next_step: 3,
+            total_steps: 8,
+            paused: true,
+        });
+        repository.save(&session).await.unwrap();
+
+        let loaded = repository.find_by_id("script-run").await.unwrap().unwrap();
+        assert_eq!(loaded.script_run, session.script_run);
+    }
+
+    #[test]
+    fn test_v4_8_auto_chat_config_migrates_with_default_pacing() {
+        let toml_str = r#"
+version = "4.8.0"
+id = "legacy"
+title = "Legacy"
+created_at = "2024-01-01T00:00:00Z"
+updated_at = "2024-01-01T00:00:00Z"
+current_persona_id = "mai"
+workspace_id = "ws"
+
+[persona_histories]
+
+[app_mode]
+type = "Idle"
+
+[auto_chat_config]
+max_iterations = 3
+stop_condition = "iteration_count"
+web_search_enabled = true
+stop_phrases = ["LGTM"]
+"#;
+        let toml_value: toml::Value = toml::from_str(toml_str).unwrap();
+        let session: Session = create_session_migrator()
+            .load_flat_from("session", toml_value)
+            .unwrap();
+
+        let config = session.auto_chat_config.unwrap();
+        assert_eq!(config.stop_phrases, vec!["LGTM".to_string()]);
+        assert_eq!(config.iteration_delay_ms, None);
+        assert!(!config.adaptive_delay);
+    }
+
+    #[test]
+    fn test_legacy_auto_chat_config_migrates_without_stop_phrases() {
+        let toml_str = r#"
+version = "4.6.0"
+id = "legacy"
+title = "Legacy"
+created_at = "2024-01-01T00:00:00Z"
+updated_at = "2024-01-01T00:00:00Z"
+current_persona_id = "mai"
+workspace_id = "ws"
+
+[persona_histories]
+
+[app_mode]
+type = "Idle"
+
+[auto_chat_config]
+max_iterations = 3
+stop_condition = "user_interrupt"
+web_search_enabled = false
+"#;
+        let toml_value: toml::Value = toml::from_str(toml_str).unwrap();
+        let session: Session = create_session_migrator()
+            .load_flat_from("session", toml_value)
+            .unwrap();
+
+        let config = session.auto_chat_config.unwrap();
+        assert_eq!(config.max_iterations, 3);
+        assert_eq!(config.stop_condition, StopCondition::UserInterrupt);
+        assert!(!config.web_search_enabled);
+        assert!(config.stop_phrases.is_empty());
+    }
+
     #[tokio::test]
     async fn test_list_all() {
         let temp_dir = TempDir::new().unwrap();
@@ -340,6 +700,26 @@ mod tests {
         assert_eq!(sessions.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_list_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        repository
+            .save(&create_test_session("session-b"))
+            .await
+            .unwrap();
+        repository
+            .save(&create_test_session("session-a"))
+            .await
+            .unwrap();
+
+        let ids = repository.list_ids().await.unwrap();
+        assert_eq!(ids, vec!["session-a".to_string(), "session-b".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_delete() {
         let temp_dir = TempDir::new().unwrap();
@@ -382,4 +762,55 @@ mod tests {
         let result = repository.find_by_id("nonexistent-session").await.unwrap();
         assert!(result.is_none());
     }
+
+    #[tokio::test]
+    async fn test_list_summaries_reads_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        let mut older = create_test_session("session-older");
+        older.workspace_id = "ws-1".to_string();
+        let mut newer = create_test_session("session-newer");
+        newer.updated_at = "2024-02-01T00:00:00Z".to_string();
+        repository.save(&older).await.unwrap();
+        repository.save(&newer).await.unwrap();
+
+        // The metadata is read straight from the file, not via a full load
+        let content =
+            std::fs::read_to_string(repository.base_dir().join("session-older.toml")).unwrap();
+        let record: SessionSummaryRecord = toml::from_str(&content).unwrap();
+        assert_eq!(record.into_summary(), Some(SessionSummary::from(&older)));
+
+        let summaries = repository.list_summaries().await.unwrap();
+        assert_eq!(
+            summaries,
+            vec![SessionSummary::from(&newer), SessionSummary::from(&older)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_count_by_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = AsyncDirSessionRepository::new(Some(temp_dir.path()))
+            .await
+            .unwrap();
+
+        for (id, workspace_id) in [("s-1", "ws-1"), ("s-2", "ws-1"), ("s-3", "ws-2")] {
+            let mut session = create_test_session(id);
+            session.workspace_id = workspace_id.to_string();
+            repository.save(&session).await.unwrap();
+        }
+
+        let counts = repository.count_by_workspace().await.unwrap();
+        assert_eq!(
+            counts,
+            HashMap::from([("ws-1".to_string(), 2), ("ws-2".to_string(), 1)])
+        );
+
+        repository.delete("s-3").await.unwrap();
+        let counts = repository.count_by_workspace().await.unwrap();
+        assert_eq!(counts, HashMap::from([("ws-1".to_string(), 2)]));
+    }
 }
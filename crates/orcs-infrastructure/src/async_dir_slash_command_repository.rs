@@ -87,8 +87,9 @@ impl SlashCommandRepository for AsyncDirSlashCommandRepository {
         {
             Ok(command) => Ok(Some(command)),
             Err(e) => {
-                let orcs_err = e.into();
-                if orcs_core::OrcsError::is_not_found(&orcs_err) {
+                let orcs_err: orcs_core::OrcsError = e.into();
+                // The storage reports a missing entity as an IO error
+                if orcs_err.is_not_found_or_missing() {
                     Ok(None)
                 } else {
                     Err(orcs_err)
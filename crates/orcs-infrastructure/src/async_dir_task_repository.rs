@@ -134,6 +134,9 @@ mod tests {
             execution_details: None,
             strategy: None,
             journal_log: None,
+            origin_message_timestamp: None,
+            origin_author: None,
+            dry_run: false,
         }
     }
 
@@ -134,7 +134,7 @@ impl WorkspaceRepository for AsyncDirWorkspaceRepository {
             all_workspaces.into_iter().map(|(_id, ws)| ws).collect();
 
         // Sort by last_accessed (descending)
-        workspaces.sort_by(|a, b| b.last_accessed.cmp(&a.last_accessed));
+        workspaces.sort_by_key(|w| std::cmp::Reverse(w.last_accessed));
 
         Ok(workspaces)
     }
@@ -0,0 +1,233 @@
+//! PersonaRepository decorator that serves reads from an in-memory snapshot.
+//!
+//! A single turn resolves personas several times (speaker names, participant
+//! IDs, session snapshots), and every read of the file-based repository parses
+//! all persona TOMLs again. This decorator loads the full persona list once and
+//! answers reads from it until [`CachedPersonaRepository::invalidate`] is called.
+//! Writes through the decorator invalidate automatically; changes made behind
+//! its back (a remote refresh, edits to the persona files) need an explicit
+//! `invalidate()`.
+
+use orcs_core::error::Result;
+use orcs_core::persona::Persona;
+use orcs_core::repository::PersonaRepository;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Caching wrapper around any [`PersonaRepository`].
+pub struct CachedPersonaRepository {
+    inner: Arc<dyn PersonaRepository>,
+    /// All personas including archived ones, or `None` until the next load.
+    snapshot: RwLock<Option<Vec<Persona>>>,
+    /// Bumped on every invalidation, so a load that raced with a write is not cached.
+    generation: AtomicU64,
+}
+
+impl CachedPersonaRepository {
+    pub fn new(inner: Arc<dyn PersonaRepository>) -> Self {
+        Self {
+            inner,
+            snapshot: RwLock::new(None),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Drops the snapshot; the next read loads from the inner repository.
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        *self.snapshot.write().unwrap() = None;
+    }
+
+    /// Number of invalidations so far.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Returns the snapshot, loading it from the inner repository if needed.
+    async fn snapshot(&self) -> Result<Vec<Persona>> {
+        let cached = self.snapshot.read().unwrap().clone();
+        if let Some(personas) = cached {
+            return Ok(personas);
+        }
+
+        let generation = self.generation();
+        let personas = self.inner.get_all_including_archived().await?;
+        let mut snapshot = self.snapshot.write().unwrap();
+        // An invalidation during the load means `personas` may predate a write
+        if self.generation() == generation {
+            *snapshot = Some(personas.clone());
+        }
+        Ok(personas)
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonaRepository for CachedPersonaRepository {
+    async fn find_by_id(&self, persona_id: &str) -> Result<Option<Persona>> {
+        if let Some(persona) = self
+            .snapshot()
+            .await?
+            .into_iter()
+            .find(|p| p.id == persona_id)
+        {
+            return Ok(Some(persona));
+        }
+        // Some repositories resolve IDs they do not list (e.g. shadowed remote personas)
+        self.inner.find_by_id(persona_id).await
+    }
+
+    async fn save(&self, persona: &Persona) -> Result<()> {
+        let result = self.inner.save(persona).await;
+        self.invalidate();
+        result
+    }
+
+    async fn delete(&self, persona_id: &str) -> Result<()> {
+        let result = self.inner.delete(persona_id).await;
+        self.invalidate();
+        result
+    }
+
+    async fn get_all(&self) -> Result<Vec<Persona>> {
+        let personas = self.snapshot().await?;
+        Ok(personas.into_iter().filter(|p| !p.archived).collect())
+    }
+
+    async fn get_all_including_archived(&self) -> Result<Vec<Persona>> {
+        self.snapshot().await
+    }
+
+    async fn save_all(&self, personas: &[Persona]) -> Result<()> {
+        let result = self.inner.save_all(personas).await;
+        self.invalidate();
+        result
+    }
+
+    async fn presets_seeded(&self) -> Result<bool> {
+        self.inner.presets_seeded().await
+    }
+
+    async fn mark_presets_seeded(&self) -> Result<()> {
+        self.inner.mark_presets_seeded().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orcs_core::persona::{PersonaBackend, PersonaSource};
+    use std::sync::atomic::AtomicUsize;
+    use tokio::sync::Mutex;
+
+    /// In-memory repository counting how often the full list is read.
+    #[derive(Default)]
+    struct CountingRepository {
+        personas: Mutex<Vec<Persona>>,
+        reads: AtomicUsize,
+    }
+
+    impl CountingRepository {
+        fn reads(&self) -> usize {
+            self.reads.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PersonaRepository for CountingRepository {
+        async fn find_by_id(&self, persona_id: &str) -> Result<Option<Persona>> {
+            let personas = self.get_all_including_archived().await?;
+            Ok(personas.into_iter().find(|p| p.id == persona_id))
+        }
+
+        async fn save(&self, persona: &Persona) -> Result<()> {
+            let mut personas = self.personas.lock().await;
+            personas.retain(|p| p.id != persona.id);
+            personas.push(persona.clone());
+            Ok(())
+        }
+
+        async fn delete(&self, persona_id: &str) -> Result<()> {
+            self.personas.lock().await.retain(|p| p.id != persona_id);
+            Ok(())
+        }
+
+        async fn get_all(&self) -> Result<Vec<Persona>> {
+            let personas = self.get_all_including_archived().await?;
+            Ok(personas.into_iter().filter(|p| !p.archived).collect())
+        }
+
+        async fn get_all_including_archived(&self) -> Result<Vec<Persona>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(self.personas.lock().await.clone())
+        }
+
+        async fn save_all(&self, personas: &[Persona]) -> Result<()> {
+            for persona in personas {
+                self.save(persona).await?;
+            }
+            Ok(())
+        }
+    }
+
+    fn persona(id: &str, name: &str, archived: bool) -> Persona {
+        Persona {
+            id: id.to_string(),
+            name: name.to_string(),
+            role: "Engineer".to_string(),
+            background: "Background".to_string(),
+            communication_style: "Direct".to_string(),
+            default_participant: true,
+            source: PersonaSource::User,
+            backend: PersonaBackend::ClaudeCli,
+            model_name: None,
+            icon: None,
+            base_color: None,
+            gemini_options: None,
+            kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reads_are_served_from_one_load_until_invalidated() {
+        let inner = Arc::new(CountingRepository::default());
+        inner.save(&persona("mai", "Mai", false)).await.unwrap();
+        inner.save(&persona("old", "Old", true)).await.unwrap();
+        let cached = CachedPersonaRepository::new(inner.clone());
+
+        // The lookups of one broadcast turn
+        let participants = cached.get_all().await.unwrap();
+        assert_eq!(participants.len(), 1);
+        for _ in 0..3 {
+            let all = cached.get_all_including_archived().await.unwrap();
+            assert_eq!(all.len(), 2);
+        }
+        assert!(cached.find_by_id("old").await.unwrap().is_some());
+        assert_eq!(inner.reads(), 1);
+
+        // A write through the decorator is visible on the next read
+        cached.save(&persona("yui", "Yui", false)).await.unwrap();
+        assert_eq!(cached.get_all().await.unwrap().len(), 2);
+        assert_eq!(inner.reads(), 2);
+
+        // So is a change behind its back once invalidated
+        inner.delete("mai").await.unwrap();
+        assert_eq!(cached.get_all().await.unwrap().len(), 2);
+        cached.invalidate();
+        let names: Vec<String> = cached
+            .get_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["Yui"]);
+        assert_eq!(inner.reads(), 3);
+        assert_eq!(cached.generation(), 2);
+    }
+}
@@ -0,0 +1,396 @@
+//! PersonaRepository that layers local personas over a remote team source.
+//!
+//! Local personas always win: a remote persona is hidden when a local persona
+//! has the same name (case-insensitive) or id. Remote personas are read-only;
+//! writes of unchanged copies are ignored so callers can keep saving the full
+//! persona list, but any modification or deletion is refused.
+
+use crate::http_persona_repository::{HttpPersonaRepository, RemoteRefreshOutcome};
+use orcs_core::error::{OrcsError, Result};
+use orcs_core::persona::{Persona, PersonaSource};
+use orcs_core::repository::PersonaRepository;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Local persona storage with a read-only remote layer underneath.
+pub struct CompositePersonaRepository {
+    local: Arc<dyn PersonaRepository>,
+    remote: Arc<HttpPersonaRepository>,
+}
+
+impl CompositePersonaRepository {
+    pub fn new(local: Arc<dyn PersonaRepository>, remote: Arc<HttpPersonaRepository>) -> Self {
+        Self { local, remote }
+    }
+
+    /// Re-fetches the remote index.
+    pub async fn refresh_remote(&self) -> Result<RemoteRefreshOutcome> {
+        self.remote.refresh().await
+    }
+
+    /// Remote personas, or none when the remote source is unavailable.
+    async fn remote_personas(&self) -> Vec<Persona> {
+        self.remote
+            .get_all_including_archived()
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("[RemotePersonas] Remote personas unavailable: {}", e);
+                Vec::new()
+            })
+    }
+
+    /// Returns the remote copy of `persona_id` unless a local persona has that id.
+    async fn remote_only(&self, persona_id: &str) -> Result<Option<Persona>> {
+        if self.local.find_by_id(persona_id).await?.is_some() {
+            return Ok(None);
+        }
+        Ok(self
+            .remote_personas()
+            .await
+            .into_iter()
+            .find(|p| p.id == persona_id))
+    }
+
+    /// Decides whether `persona` may be written locally.
+    ///
+    /// Returns `Ok(false)` for an unchanged remote persona (nothing to write).
+    async fn check_writable(&self, persona: &Persona) -> Result<bool> {
+        match self.remote_only(&persona.id).await? {
+            Some(remote) if same_content(&remote, persona) => Ok(false),
+            Some(_) => Err(self.read_only(persona)),
+            None if persona.source == PersonaSource::Remote => Err(self.read_only(persona)),
+            None => Ok(true),
+        }
+    }
+
+    fn read_only(&self, persona: &Persona) -> OrcsError {
+        OrcsError::data_access(format!(
+            "Persona '{}' comes from the remote source {} and is read-only",
+            persona.name,
+            self.remote.url()
+        ))
+    }
+}
+
+fn same_content(a: &Persona, b: &Persona) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+#[async_trait::async_trait]
+impl PersonaRepository for CompositePersonaRepository {
+    async fn find_by_id(&self, persona_id: &str) -> Result<Option<Persona>> {
+        if let Some(persona) = self.local.find_by_id(persona_id).await? {
+            return Ok(Some(persona));
+        }
+        // Shadowed remote personas still resolve so existing sessions keep working.
+        Ok(self
+            .remote_personas()
+            .await
+            .into_iter()
+            .find(|p| p.id == persona_id))
+    }
+
+    async fn save(&self, persona: &Persona) -> Result<()> {
+        if self.check_writable(persona).await? {
+            self.local.save(persona).await?;
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, persona_id: &str) -> Result<()> {
+        if let Some(remote) = self.remote_only(persona_id).await? {
+            return Err(self.read_only(&remote));
+        }
+        self.local.delete(persona_id).await
+    }
+
+    async fn get_all(&self) -> Result<Vec<Persona>> {
+        let personas = self.get_all_including_archived().await?;
+        Ok(personas.into_iter().filter(|p| !p.archived).collect())
+    }
+
+    async fn get_all_including_archived(&self) -> Result<Vec<Persona>> {
+        let mut personas = self.local.get_all_including_archived().await?;
+        let local_ids: HashSet<String> = personas.iter().map(|p| p.id.clone()).collect();
+        let local_names: HashSet<String> = personas.iter().map(|p| p.name.to_lowercase()).collect();
+
+        personas.extend(self.remote_personas().await.into_iter().filter(|p| {
+            !local_ids.contains(&p.id) && !local_names.contains(&p.name.to_lowercase())
+        }));
+        Ok(personas)
+    }
+
+    async fn save_all(&self, personas: &[Persona]) -> Result<()> {
+        // Validate everything first so a refused remote edit doesn't leave a partial write.
+        let mut writable = Vec::with_capacity(personas.len());
+        for persona in personas {
+            if self.check_writable(persona).await? {
+                writable.push(persona.clone());
+            }
+        }
+        self.local.save_all(&writable).await
+    }
+
+    async fn presets_seeded(&self) -> Result<bool> {
+        self.local.presets_seeded().await
+    }
+
+    async fn mark_presets_seeded(&self) -> Result<()> {
+        self.local.mark_presets_seeded().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AsyncDirPersonaRepository;
+    use orcs_core::persona::PersonaBackend;
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const LOCAL_MAI_ID: &str = "1b4e28ba-2fa1-11d2-883f-0016d3cca427";
+    const REMOTE_MAI_ID: &str = "6fa459ea-ee8a-3ca4-894e-db77e160355e";
+    const REMOTE_KEN_ID: &str = "886313e1-3b8a-5372-9b90-0c9aee199e5d";
+    const INDEX_ETAG: &str = "\"v1\"";
+
+    fn persona(id: &str, name: &str, source: PersonaSource) -> Persona {
+        Persona {
+            id: id.to_string(),
+            name: name.to_string(),
+            role: "Engineer".to_string(),
+            background: "Background".to_string(),
+            communication_style: "Direct".to_string(),
+            default_participant: false,
+            source,
+            backend: PersonaBackend::ClaudeCli,
+            model_name: None,
+            icon: None,
+            base_color: None,
+            gemini_options: None,
+            kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
+        }
+    }
+
+    /// Serves the index with an ETag, answering 304 to a matching `If-None-Match`.
+    /// Returns the URL and a counter of full (200) responses.
+    async fn spawn_index_server(body: String) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/personas.json", listener.local_addr().unwrap());
+        let full_responses = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = full_responses.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut buf = vec![0u8; 8192];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                let response = if request.contains(&format!("if-none-match: {}", INDEX_ETAG)) {
+                    "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n".to_string()
+                } else {
+                    counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nETag: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        INDEX_ETAG,
+                        body.len(),
+                        body
+                    )
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        (url, full_responses)
+    }
+
+    fn remote_index() -> String {
+        serde_json::json!({
+            "personas": [
+                persona(REMOTE_MAI_ID, "Mai", PersonaSource::User),
+                persona(REMOTE_KEN_ID, "Ken", PersonaSource::User),
+            ]
+        })
+        .to_string()
+    }
+
+    /// Answers every request with 503. Returns the URL and a request counter.
+    async fn spawn_failing_server() -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/personas.json", listener.local_addr().unwrap());
+        let requests = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = requests.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut buf = vec![0u8; 8192];
+                let _ = stream.read(&mut buf).await;
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    )
+                    .await;
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        (url, requests)
+    }
+
+    /// Returns an address nothing is listening on.
+    async fn unreachable_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        format!("http://{}/personas.json", addr)
+    }
+
+    #[tokio::test]
+    async fn test_remote_fetch_uses_etag_and_falls_back_to_snapshot_offline() {
+        let cache_dir = TempDir::new().unwrap();
+        let (url, full_responses) = spawn_index_server(remote_index()).await;
+
+        let remote = HttpPersonaRepository::new(url.clone(), cache_dir.path());
+        let personas = remote.get_all().await.unwrap();
+        assert_eq!(personas.len(), 2);
+        assert!(personas.iter().all(|p| p.source == PersonaSource::Remote));
+
+        let outcome = remote.refresh().await.unwrap();
+        assert_eq!(
+            outcome.status,
+            crate::http_persona_repository::RemoteRefreshStatus::NotModified
+        );
+        assert_eq!(outcome.persona_count, 2);
+        assert_eq!(full_responses.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // A fresh instance whose server is gone serves the snapshot on disk.
+        let offline_url = unreachable_url().await;
+        std::fs::write(
+            cache_dir.path().join("snapshot.json"),
+            std::fs::read_to_string(cache_dir.path().join("snapshot.json"))
+                .unwrap()
+                .replace(&url, &offline_url),
+        )
+        .unwrap();
+        let offline = HttpPersonaRepository::new(offline_url, cache_dir.path());
+        let outcome = offline.refresh().await.unwrap();
+        assert_eq!(
+            outcome.status,
+            crate::http_persona_repository::RemoteRefreshStatus::Offline
+        );
+        let names: Vec<String> = offline
+            .get_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["Mai", "Ken"]);
+
+        // Without a snapshot, an unreachable source is an error.
+        let empty_cache = TempDir::new().unwrap();
+        let missing = HttpPersonaRepository::new(unreachable_url().await, empty_cache.path());
+        assert!(missing.refresh().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_remote_without_snapshot_backs_off_to_local_only() {
+        let local_dir = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let (url, requests) = spawn_failing_server().await;
+
+        let local = Arc::new(
+            AsyncDirPersonaRepository::new(Some(local_dir.path()))
+                .await
+                .unwrap(),
+        );
+        local
+            .save(&persona(LOCAL_MAI_ID, "mai", PersonaSource::User))
+            .await
+            .unwrap();
+        let repo = CompositePersonaRepository::new(
+            local,
+            Arc::new(HttpPersonaRepository::new(url, cache_dir.path())),
+        );
+
+        for _ in 0..3 {
+            let names: Vec<String> = repo
+                .get_all()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|p| p.name)
+                .collect();
+            assert_eq!(names, vec!["mai"]);
+        }
+        assert!(repo.find_by_id(REMOTE_KEN_ID).await.unwrap().is_none());
+        // Only the first lookup reached the server; the rest hit the backoff.
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // An explicit refresh still retries immediately.
+        assert!(repo.refresh_remote().await.is_err());
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_local_personas_shadow_remote_and_remote_is_read_only() {
+        let local_dir = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let (url, _) = spawn_index_server(remote_index()).await;
+
+        let local = Arc::new(
+            AsyncDirPersonaRepository::new(Some(local_dir.path()))
+                .await
+                .unwrap(),
+        );
+        local
+            .save(&persona(LOCAL_MAI_ID, "mai", PersonaSource::User))
+            .await
+            .unwrap();
+        let repo = CompositePersonaRepository::new(
+            local,
+            Arc::new(HttpPersonaRepository::new(url, cache_dir.path())),
+        );
+
+        let all = repo.get_all().await.unwrap();
+        let sources: Vec<(String, PersonaSource)> = all
+            .iter()
+            .map(|p| (p.name.clone(), p.source.clone()))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("mai".to_string(), PersonaSource::User),
+                ("Ken".to_string(), PersonaSource::Remote),
+            ]
+        );
+        // The shadowed remote persona still resolves by id.
+        assert!(repo.find_by_id(REMOTE_MAI_ID).await.unwrap().is_some());
+
+        // Saving the full list back is fine while remote personas are unchanged.
+        repo.save_all(&all).await.unwrap();
+
+        let mut ken = all.iter().find(|p| p.id == REMOTE_KEN_ID).unwrap().clone();
+        ken.role = "Manager".to_string();
+        assert!(repo.save(&ken).await.is_err());
+        assert!(repo.save_all(&[ken.clone()]).await.is_err());
+        assert!(repo.delete(REMOTE_KEN_ID).await.is_err());
+
+        let stored = repo.find_by_id(REMOTE_KEN_ID).await.unwrap().unwrap();
+        assert_eq!(stored.role, "Engineer");
+        assert_eq!(stored.source, PersonaSource::Remote);
+    }
+}
@@ -211,8 +211,43 @@ pub struct AppStateV1_6 {
     pub active_tab_id: Option<String>,
 }
 
+/// Application state configuration V1.7.0.
+///
+/// Added offline_mode to keep network-using backends blocked across restarts.
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.7.0")]
+#[serde(rename_all = "camelCase")]
+#[derive(Default)]
+pub struct AppStateV1_7 {
+    /// ID of the last selected workspace.
+    /// This is used to restore the workspace on application startup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_selected_workspace_id: Option<String>,
+
+    /// ID of the default system workspace (~/orcs).
+    /// None if not yet initialized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_workspace_id: Option<String>,
+
+    /// ID of the currently active session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_session_id: Option<String>,
+
+    /// List of currently open tabs (with UI state support).
+    #[serde(default)]
+    pub open_tabs: Vec<OpenTabDTOV1_6>,
+
+    /// ID of the currently active tab.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_tab_id: Option<String>,
+
+    /// Whether offline mode is on.
+    #[serde(default)]
+    pub offline_mode: bool,
+}
+
 /// Type alias for the latest AppState version.
-pub type AppStateDTO = AppStateV1_6;
+pub type AppStateDTO = AppStateV1_7;
 
 // ============================================================================
 // Migration implementations
@@ -311,6 +346,21 @@ impl version_migrate::MigratesTo<AppStateV1_6> for AppStateV1_5 {
     }
 }
 
+/// Migration from AppStateV1_6 to AppStateV1_7.
+/// Adds offline_mode field with default value (false).
+impl version_migrate::MigratesTo<AppStateV1_7> for AppStateV1_6 {
+    fn migrate(self) -> AppStateV1_7 {
+        AppStateV1_7 {
+            last_selected_workspace_id: self.last_selected_workspace_id,
+            default_workspace_id: self.default_workspace_id,
+            active_session_id: self.active_session_id,
+            open_tabs: self.open_tabs,
+            active_tab_id: self.active_tab_id,
+            offline_mode: false, // Default: online
+        }
+    }
+}
+
 // ============================================================================
 // Domain model conversions
 // ============================================================================
@@ -351,8 +401,8 @@ impl From<OpenTab> for OpenTabDTOV1_6 {
     }
 }
 
-/// Convert AppStateV1_6 DTO to domain model.
-impl IntoDomain<AppState> for AppStateV1_6 {
+/// Convert AppStateV1_7 DTO to domain model.
+impl IntoDomain<AppState> for AppStateV1_7 {
     fn into_domain(self) -> AppState {
         AppState {
             last_selected_workspace_id: self.last_selected_workspace_id,
@@ -360,19 +410,21 @@ impl IntoDomain<AppState> for AppStateV1_6 {
             active_session_id: self.active_session_id,
             open_tabs: self.open_tabs.into_iter().map(Into::into).collect(),
             active_tab_id: self.active_tab_id,
+            offline_mode: self.offline_mode,
         }
     }
 }
 
-/// Convert domain model to AppStateV1_6 DTO for persistence.
-impl version_migrate::FromDomain<AppState> for AppStateV1_6 {
+/// Convert domain model to AppStateV1_7 DTO for persistence.
+impl version_migrate::FromDomain<AppState> for AppStateV1_7 {
     fn from_domain(state: AppState) -> Self {
-        AppStateV1_6 {
+        AppStateV1_7 {
             last_selected_workspace_id: state.last_selected_workspace_id,
             default_workspace_id: state.default_workspace_id,
             active_session_id: state.active_session_id,
             open_tabs: state.open_tabs.into_iter().map(Into::into).collect(),
             active_tab_id: state.active_tab_id,
+            offline_mode: state.offline_mode,
         }
     }
 }
@@ -393,7 +445,8 @@ impl version_migrate::FromDomain<AppState> for AppStateV1_6 {
 /// - V1.3 → V1.4: Adds `open_tabs` and `active_tab_id` for tab management
 /// - V1.4 → V1.5: Migrates to camelCase serialization format
 /// - V1.5 → V1.6: Adds UI state fields to OpenTab (input, attached_file_paths, auto_mode, auto_chat_iteration, is_dirty)
-/// - V1.6 → AppState: Converts DTO to domain model
+/// - V1.6 → V1.7: Adds `offline_mode` (defaults to false)
+/// - V1.7 → AppState: Converts DTO to domain model
 ///
 /// # Example
 ///
@@ -410,6 +463,7 @@ pub fn create_app_state_migrator() -> version_migrate::Migrator {
         AppStateV1_4,
         AppStateV1_5,
         AppStateV1_6,
+        AppStateV1_7,
         AppState
     ], save = true)
     .expect("Failed to create app_state migrator")
@@ -7,14 +7,19 @@
 //! - V1.1.0: Added app_state field
 //! - V2.0.0: Simplified to only user_profile (personas/workspaces/app_state now managed separately)
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use version_migrate::{IntoDomain, MigratesTo, Versioned};
 
-use super::{AppStateDTO, UserProfileDTO, WorkspaceV1};
+use super::{AppStateDTO, PersonaBackendDTO, UserProfileDTO, WorkspaceV1};
 use orcs_core::config::{
-    ClaudeModelConfig, DebugSettings, EnvSettings, GeminiModelConfig, MemorySyncSettings,
-    ModelSettings, OpenAIModelConfig, RootConfig, TerminalSettings,
+    ClaudeModelConfig, DEFAULT_CLI_HEARTBEAT_INTERVAL_SECS, DEFAULT_MAX_AUTO_CHAT_ITERATIONS,
+    DEFAULT_MAX_PARTICIPANTS, DEFAULT_SESSION_IDLE_TIMEOUT_SECS, DebugSettings, EnvSettings,
+    GeminiModelConfig, MemorySyncSettings, ModelSettings, OpenAIModelConfig, RootConfig,
+    ShellPolicy, TerminalSettings, default_destructive_patterns,
 };
+use orcs_core::persona::PersonaBackend;
 
 // ============================================================================
 // ModelSettings DTOs
@@ -155,17 +160,63 @@ pub struct EnvSettingsDTO {
     pub additional_paths: Vec<String>,
     #[serde(default = "default_auto_detect_tool_managers")]
     pub auto_detect_tool_managers: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_proxy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment_description: Option<String>,
+    #[serde(default = "default_max_auto_chat_iterations")]
+    pub max_auto_chat_iterations: i32,
+    #[serde(default = "default_max_participants")]
+    pub max_participants: usize,
+    #[serde(default = "default_session_idle_timeout_secs")]
+    pub session_idle_timeout_secs: u64,
+    #[serde(default = "default_cli_heartbeat_interval_secs")]
+    pub cli_heartbeat_interval_secs: u64,
+    /// Backend identifier, validated in `into_domain` so a typo does not
+    /// discard the whole config
+    #[serde(default = "default_persona_backend")]
+    pub default_persona_backend: String,
 }
 
 fn default_auto_detect_tool_managers() -> bool {
     true
 }
 
+fn default_max_auto_chat_iterations() -> i32 {
+    DEFAULT_MAX_AUTO_CHAT_ITERATIONS
+}
+
+fn default_max_participants() -> usize {
+    DEFAULT_MAX_PARTICIPANTS
+}
+
+fn default_session_idle_timeout_secs() -> u64 {
+    DEFAULT_SESSION_IDLE_TIMEOUT_SECS
+}
+
+fn default_cli_heartbeat_interval_secs() -> u64 {
+    DEFAULT_CLI_HEARTBEAT_INTERVAL_SECS
+}
+
+fn default_persona_backend() -> String {
+    PersonaBackend::default().as_str().to_string()
+}
+
 impl Default for EnvSettingsDTO {
     fn default() -> Self {
         Self {
             additional_paths: Vec::new(),
             auto_detect_tool_managers: true,
+            https_proxy: None,
+            http_proxy: None,
+            environment_description: None,
+            max_auto_chat_iterations: DEFAULT_MAX_AUTO_CHAT_ITERATIONS,
+            max_participants: DEFAULT_MAX_PARTICIPANTS,
+            session_idle_timeout_secs: DEFAULT_SESSION_IDLE_TIMEOUT_SECS,
+            cli_heartbeat_interval_secs: DEFAULT_CLI_HEARTBEAT_INTERVAL_SECS,
+            default_persona_backend: default_persona_backend(),
         }
     }
 }
@@ -175,6 +226,22 @@ impl EnvSettingsDTO {
         EnvSettings {
             additional_paths: self.additional_paths,
             auto_detect_tool_managers: self.auto_detect_tool_managers,
+            https_proxy: self.https_proxy,
+            http_proxy: self.http_proxy,
+            environment_description: self.environment_description,
+            max_auto_chat_iterations: self.max_auto_chat_iterations,
+            max_participants: self.max_participants,
+            session_idle_timeout_secs: self.session_idle_timeout_secs,
+            cli_heartbeat_interval_secs: self.cli_heartbeat_interval_secs,
+            default_persona_backend: PersonaBackend::parse(&self.default_persona_backend)
+                .unwrap_or_else(|| {
+                    tracing::warn!(
+                        "[Config] Unknown default_persona_backend '{}', using {}",
+                        self.default_persona_backend,
+                        PersonaBackend::default().as_str()
+                    );
+                    PersonaBackend::default()
+                }),
         }
     }
 
@@ -182,6 +249,14 @@ impl EnvSettingsDTO {
         Self {
             additional_paths: settings.additional_paths,
             auto_detect_tool_managers: settings.auto_detect_tool_managers,
+            https_proxy: settings.https_proxy,
+            http_proxy: settings.http_proxy,
+            environment_description: settings.environment_description,
+            max_auto_chat_iterations: settings.max_auto_chat_iterations,
+            max_participants: settings.max_participants,
+            session_idle_timeout_secs: settings.session_idle_timeout_secs,
+            cli_heartbeat_interval_secs: settings.cli_heartbeat_interval_secs,
+            default_persona_backend: settings.default_persona_backend.as_str().to_string(),
         }
     }
 }
@@ -307,6 +382,50 @@ impl TerminalSettingsDTO {
     }
 }
 
+// ============================================================================
+// ShellPolicy DTOs
+// ============================================================================
+
+/// DTO for ShellPolicy.
+///
+/// Controls which agent-triggered shell commands require user confirmation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellPolicyDTO {
+    #[serde(default = "default_destructive_patterns")]
+    pub destructive_patterns: Vec<String>,
+    #[serde(default = "default_pending_action_ttl_secs")]
+    pub pending_action_ttl_secs: u64,
+}
+
+fn default_pending_action_ttl_secs() -> u64 {
+    300
+}
+
+impl Default for ShellPolicyDTO {
+    fn default() -> Self {
+        Self {
+            destructive_patterns: default_destructive_patterns(),
+            pending_action_ttl_secs: default_pending_action_ttl_secs(),
+        }
+    }
+}
+
+impl ShellPolicyDTO {
+    fn into_domain(self) -> ShellPolicy {
+        ShellPolicy {
+            destructive_patterns: self.destructive_patterns,
+            pending_action_ttl_secs: self.pending_action_ttl_secs,
+        }
+    }
+
+    fn from_domain(policy: ShellPolicy) -> Self {
+        Self {
+            destructive_patterns: policy.destructive_patterns,
+            pending_action_ttl_secs: policy.pending_action_ttl_secs,
+        }
+    }
+}
+
 // ============================================================================
 // ConfigRoot DTOs
 // ============================================================================
@@ -455,7 +574,7 @@ pub struct ConfigRootV2_4_0 {
     pub memory_sync_settings: MemorySyncSettingsDTO,
 }
 
-/// Root configuration structure V2.5.0 for the application config file (current).
+/// Root configuration structure V2.5.0 for the application config file.
 ///
 /// Added terminal_settings field for custom terminal application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
@@ -482,8 +601,107 @@ pub struct ConfigRootV2_5_0 {
     pub terminal_settings: TerminalSettingsDTO,
 }
 
+/// Root configuration structure V2.6.0 for the application config file.
+///
+/// Added shell_policy field for confirmation gates on agent-triggered commands.
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "2.6.0")]
+#[derive(Default)]
+pub struct ConfigRootV2_6_0 {
+    /// User profile configuration (name, background, etc.).
+    #[serde(default)]
+    pub user_profile: UserProfileDTO,
+    /// LLM model settings (non-sensitive configuration).
+    #[serde(default)]
+    pub model_settings: ModelSettingsDTO,
+    /// Environment PATH configuration for CLI tools.
+    #[serde(default)]
+    pub env_settings: EnvSettingsDTO,
+    /// Debug settings for LLM interactions.
+    #[serde(default)]
+    pub debug_settings: DebugSettingsDTO,
+    /// Memory synchronization settings for RAG integration.
+    #[serde(default)]
+    pub memory_sync_settings: MemorySyncSettingsDTO,
+    /// Terminal settings for workspace terminal launch.
+    #[serde(default)]
+    pub terminal_settings: TerminalSettingsDTO,
+    /// Shell execution policy for agent-triggered commands.
+    #[serde(default)]
+    pub shell_policy: ShellPolicyDTO,
+}
+
+/// Root configuration structure V2.7.0 for the application config file.
+///
+/// Added remote_persona_url field for a shared, read-only team persona source.
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "2.7.0")]
+#[derive(Default)]
+pub struct ConfigRootV2_7_0 {
+    /// User profile configuration (name, background, etc.).
+    #[serde(default)]
+    pub user_profile: UserProfileDTO,
+    /// LLM model settings (non-sensitive configuration).
+    #[serde(default)]
+    pub model_settings: ModelSettingsDTO,
+    /// Environment PATH configuration for CLI tools.
+    #[serde(default)]
+    pub env_settings: EnvSettingsDTO,
+    /// Debug settings for LLM interactions.
+    #[serde(default)]
+    pub debug_settings: DebugSettingsDTO,
+    /// Memory synchronization settings for RAG integration.
+    #[serde(default)]
+    pub memory_sync_settings: MemorySyncSettingsDTO,
+    /// Terminal settings for workspace terminal launch.
+    #[serde(default)]
+    pub terminal_settings: TerminalSettingsDTO,
+    /// Shell execution policy for agent-triggered commands.
+    #[serde(default)]
+    pub shell_policy: ShellPolicyDTO,
+    /// URL of a JSON persona index shared by a team.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_persona_url: Option<String>,
+}
+
+/// Root configuration structure V2.8.0 for the application config file (current).
+///
+/// Added default_models field for app-level per-backend default models.
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "2.8.0")]
+#[derive(Default)]
+pub struct ConfigRootV2_8_0 {
+    /// User profile configuration (name, background, etc.).
+    #[serde(default)]
+    pub user_profile: UserProfileDTO,
+    /// LLM model settings (non-sensitive configuration).
+    #[serde(default)]
+    pub model_settings: ModelSettingsDTO,
+    /// Environment PATH configuration for CLI tools.
+    #[serde(default)]
+    pub env_settings: EnvSettingsDTO,
+    /// Debug settings for LLM interactions.
+    #[serde(default)]
+    pub debug_settings: DebugSettingsDTO,
+    /// Memory synchronization settings for RAG integration.
+    #[serde(default)]
+    pub memory_sync_settings: MemorySyncSettingsDTO,
+    /// Terminal settings for workspace terminal launch.
+    #[serde(default)]
+    pub terminal_settings: TerminalSettingsDTO,
+    /// Shell execution policy for agent-triggered commands.
+    #[serde(default)]
+    pub shell_policy: ShellPolicyDTO,
+    /// URL of a JSON persona index shared by a team.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_persona_url: Option<String>,
+    /// Default model per backend for personas without their own model.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub default_models: HashMap<PersonaBackendDTO, String>,
+}
+
 /// Type alias for the latest ConfigRoot version.
-pub type ConfigRoot = ConfigRootV2_5_0;
+pub type ConfigRoot = ConfigRootV2_8_0;
 
 // ============================================================================
 // Default implementations
@@ -582,13 +800,64 @@ impl MigratesTo<ConfigRootV2_5_0> for ConfigRootV2_4_0 {
     }
 }
 
+/// Migration from ConfigRootV2_5_0 to ConfigRootV2_6_0.
+/// Adds shell_policy field with default values.
+impl MigratesTo<ConfigRootV2_6_0> for ConfigRootV2_5_0 {
+    fn migrate(self) -> ConfigRootV2_6_0 {
+        ConfigRootV2_6_0 {
+            user_profile: self.user_profile,
+            model_settings: self.model_settings,
+            env_settings: self.env_settings,
+            debug_settings: self.debug_settings,
+            memory_sync_settings: self.memory_sync_settings,
+            terminal_settings: self.terminal_settings,
+            shell_policy: ShellPolicyDTO::default(),
+        }
+    }
+}
+
+/// Migration from ConfigRootV2_6_0 to ConfigRootV2_7_0.
+/// Adds remote_persona_url field (unset).
+impl MigratesTo<ConfigRootV2_7_0> for ConfigRootV2_6_0 {
+    fn migrate(self) -> ConfigRootV2_7_0 {
+        ConfigRootV2_7_0 {
+            user_profile: self.user_profile,
+            model_settings: self.model_settings,
+            env_settings: self.env_settings,
+            debug_settings: self.debug_settings,
+            memory_sync_settings: self.memory_sync_settings,
+            terminal_settings: self.terminal_settings,
+            shell_policy: self.shell_policy,
+            remote_persona_url: None,
+        }
+    }
+}
+
+/// Migration from ConfigRootV2_7_0 to ConfigRootV2_8_0.
+/// Adds default_models field (empty).
+impl MigratesTo<ConfigRootV2_8_0> for ConfigRootV2_7_0 {
+    fn migrate(self) -> ConfigRootV2_8_0 {
+        ConfigRootV2_8_0 {
+            user_profile: self.user_profile,
+            model_settings: self.model_settings,
+            env_settings: self.env_settings,
+            debug_settings: self.debug_settings,
+            memory_sync_settings: self.memory_sync_settings,
+            terminal_settings: self.terminal_settings,
+            shell_policy: self.shell_policy,
+            remote_persona_url: self.remote_persona_url,
+            default_models: HashMap::new(),
+        }
+    }
+}
+
 // ============================================================================
 // Domain model conversions
 // ============================================================================
 
-/// IntoDomain implementation for ConfigRootV2_5_0.
+/// IntoDomain implementation for ConfigRootV2_8_0.
 /// Converts DTO to domain RootConfig.
-impl IntoDomain<RootConfig> for ConfigRootV2_5_0 {
+impl IntoDomain<RootConfig> for ConfigRootV2_8_0 {
     fn into_domain(self) -> RootConfig {
         RootConfig {
             user_profile: self.user_profile.into_domain(),
@@ -597,21 +866,35 @@ impl IntoDomain<RootConfig> for ConfigRootV2_5_0 {
             debug_settings: self.debug_settings.into_domain(),
             memory_sync_settings: self.memory_sync_settings.into_domain(),
             terminal_settings: self.terminal_settings.into_domain(),
+            shell_policy: self.shell_policy.into_domain(),
+            remote_persona_url: self.remote_persona_url,
+            default_models: self
+                .default_models
+                .into_iter()
+                .map(|(backend, model)| (backend.into(), model))
+                .collect(),
         }
     }
 }
 
-/// FromDomain implementation for ConfigRootV2_5_0.
+/// FromDomain implementation for ConfigRootV2_8_0.
 /// Converts domain RootConfig to DTO for persistence.
-impl version_migrate::FromDomain<RootConfig> for ConfigRootV2_5_0 {
+impl version_migrate::FromDomain<RootConfig> for ConfigRootV2_8_0 {
     fn from_domain(config: RootConfig) -> Self {
-        ConfigRootV2_5_0 {
+        ConfigRootV2_8_0 {
             user_profile: UserProfileDTO::from_domain(config.user_profile),
             model_settings: ModelSettingsDTO::from_domain(config.model_settings),
             env_settings: EnvSettingsDTO::from_domain(config.env_settings),
             debug_settings: DebugSettingsDTO::from_domain(config.debug_settings),
             memory_sync_settings: MemorySyncSettingsDTO::from_domain(config.memory_sync_settings),
             terminal_settings: TerminalSettingsDTO::from_domain(config.terminal_settings),
+            shell_policy: ShellPolicyDTO::from_domain(config.shell_policy),
+            remote_persona_url: config.remote_persona_url,
+            default_models: config
+                .default_models
+                .into_iter()
+                .map(|(backend, model)| (backend.into(), model))
+                .collect(),
         }
     }
 }
@@ -633,7 +916,10 @@ impl version_migrate::FromDomain<RootConfig> for ConfigRootV2_5_0 {
 /// - V2.2.0 → V2.3.0: Adds `debug_settings` field with default values (debug disabled)
 /// - V2.3.0 → V2.4.0: Adds `memory_sync_settings` field with default values (sync disabled)
 /// - V2.4.0 → V2.5.0: Adds `terminal_settings` field with default values
-/// - V2.5.0 → RootConfig: Converts DTO to domain model
+/// - V2.5.0 → V2.6.0: Adds `shell_policy` field with default values
+/// - V2.6.0 → V2.7.0: Adds `remote_persona_url` field (unset)
+/// - V2.7.0 → V2.8.0: Adds `default_models` field (empty)
+/// - V2.8.0 → RootConfig: Converts DTO to domain model
 ///
 /// # Example
 ///
@@ -651,6 +937,9 @@ pub fn create_config_root_migrator() -> version_migrate::Migrator {
         ConfigRootV2_3_0,
         ConfigRootV2_4_0,
         ConfigRootV2_5_0,
+        ConfigRootV2_6_0,
+        ConfigRootV2_7_0,
+        ConfigRootV2_8_0,
         RootConfig
     ], save = true)
     .expect("Failed to create config_root migrator")
@@ -0,0 +1,161 @@
+//! Conversation script DTOs and migrations
+
+use orcs_core::conversation_script::{ConversationScript, ScriptStep};
+use orcs_core::dialogue::PresetSource;
+use serde::{Deserialize, Serialize};
+use version_migrate::{FromDomain, IntoDomain, Versioned};
+
+/// Script step DTO V1.0.0, tagged by `type` like the domain model
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScriptStepV1_0_0 {
+    PostSystemMessage {
+        content: String,
+    },
+    RunRound {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        participants: Vec<String>,
+        prompt: String,
+    },
+    Pause {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+}
+
+impl From<ScriptStepV1_0_0> for ScriptStep {
+    fn from(step: ScriptStepV1_0_0) -> Self {
+        match step {
+            ScriptStepV1_0_0::PostSystemMessage { content } => {
+                ScriptStep::PostSystemMessage { content }
+            }
+            ScriptStepV1_0_0::RunRound {
+                participants,
+                prompt,
+            } => ScriptStep::RunRound {
+                participants,
+                prompt,
+            },
+            ScriptStepV1_0_0::Pause { message } => ScriptStep::Pause { message },
+        }
+    }
+}
+
+impl From<ScriptStep> for ScriptStepV1_0_0 {
+    fn from(step: ScriptStep) -> Self {
+        match step {
+            ScriptStep::PostSystemMessage { content } => {
+                ScriptStepV1_0_0::PostSystemMessage { content }
+            }
+            ScriptStep::RunRound {
+                participants,
+                prompt,
+            } => ScriptStepV1_0_0::RunRound {
+                participants,
+                prompt,
+            },
+            ScriptStep::Pause { message } => ScriptStepV1_0_0::Pause { message },
+        }
+    }
+}
+
+/// Conversation script DTO V1.0.0
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.0.0")]
+pub struct ConversationScriptV1_0_0 {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub steps: Vec<ScriptStepV1_0_0>,
+    #[serde(default)]
+    pub source: PresetSource,
+}
+
+/// Convert ConversationScriptV1_0_0 DTO to domain model
+impl IntoDomain<ConversationScript> for ConversationScriptV1_0_0 {
+    fn into_domain(self) -> ConversationScript {
+        ConversationScript {
+            id: self.id,
+            name: self.name,
+            icon: self.icon,
+            description: self.description,
+            steps: self.steps.into_iter().map(ScriptStep::from).collect(),
+            source: self.source,
+        }
+    }
+}
+
+/// Convert domain model to ConversationScriptV1_0_0 DTO for persistence
+impl FromDomain<ConversationScript> for ConversationScriptV1_0_0 {
+    fn from_domain(script: ConversationScript) -> Self {
+        ConversationScriptV1_0_0 {
+            id: script.id,
+            name: script.name,
+            icon: script.icon,
+            description: script.description,
+            steps: script
+                .steps
+                .into_iter()
+                .map(ScriptStepV1_0_0::from)
+                .collect(),
+            source: script.source,
+        }
+    }
+}
+
+// ============================================================================
+// Migrator factory
+// ============================================================================
+
+/// Creates a Migrator for ConversationScript entities.
+pub fn create_conversation_script_migrator() -> version_migrate::Migrator {
+    version_migrate::migrator!("conversation_script" => [ConversationScriptV1_0_0, ConversationScript], save = true)
+        .expect("Failed to create conversation_script migrator")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_read_from_toml() {
+        let dto: ConversationScriptV1_0_0 = toml::from_str(
+            r#"
+id = "standup"
+name = "Standup"
+
+[[steps]]
+type = "post_system_message"
+content = "Standup"
+
+[[steps]]
+type = "run_round"
+participants = ["Mai", "Yui"]
+prompt = "What are you working on?"
+
+[[steps]]
+type = "pause"
+"#,
+        )
+        .expect("deserialize");
+
+        let script = dto.into_domain();
+        assert_eq!(script.source, PresetSource::User);
+        assert_eq!(
+            script.steps,
+            vec![
+                ScriptStep::PostSystemMessage {
+                    content: "Standup".to_string(),
+                },
+                ScriptStep::RunRound {
+                    participants: vec!["Mai".to_string(), "Yui".to_string()],
+                    prompt: "What are you working on?".to_string(),
+                },
+                ScriptStep::Pause { message: None },
+            ]
+        );
+    }
+}
@@ -22,6 +22,7 @@
 
 mod app_state;
 mod config_root;
+mod conversation_script;
 mod dialogue_preset;
 mod persona;
 mod quick_action;
@@ -41,6 +42,9 @@ pub use config_root::{
     ConfigRoot, ConfigRootV1_0_0, ConfigRootV1_1_0, ConfigRootV2_0_0, create_config_root_migrator,
 };
 
+// Re-export conversation_script DTOs and migrator
+pub use conversation_script::{ConversationScriptV1_0_0, create_conversation_script_migrator};
+
 // Re-export dialogue_preset DTOs and migrator
 pub use dialogue_preset::{DialoguePresetV1_0_0, create_dialogue_preset_migrator};
 
@@ -72,7 +76,7 @@ pub use uploaded_file::{UploadedFileV1_0_0, UploadedFileV1_1_0, create_uploaded_
 
 // Re-export user_profile DTOs and migrator
 pub use user_profile::{
-    UserProfileDTO, UserProfileV1_0, UserProfileV1_1, create_user_profile_migrator,
+    UserProfileDTO, UserProfileV1_0, UserProfileV1_1, UserProfileV1_2, create_user_profile_migrator,
 };
 
 // Re-export workspace DTOs and migrators
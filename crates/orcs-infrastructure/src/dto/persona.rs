@@ -6,6 +6,8 @@ use version_migrate::{IntoDomain, MigratesTo, Versioned};
 
 use orcs_core::persona::{GeminiOptions, KaibaOptions, Persona, PersonaBackend, PersonaSource};
 
+use super::session::ContextModeDto;
+
 /// Represents the source of a persona.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum PersonaSourceDTO {
@@ -13,10 +15,11 @@ pub enum PersonaSourceDTO {
     #[default]
     User,
     Adhoc,
+    Remote,
 }
 
 /// Represents backend options for a persona.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
 pub enum PersonaBackendDTO {
@@ -265,6 +268,315 @@ pub struct PersonaConfigV1_6_0 {
     pub kaiba_options: Option<KaibaOptionsDTO>,
 }
 
+/// V1.7.0: Added archived flag (archive instead of hard delete)
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.7.0")]
+pub struct PersonaConfigV1_7_0 {
+    /// Unique persona identifier (UUID format).
+    pub id: String,
+    /// Display name of the persona.
+    pub name: String,
+    /// Role or title of the persona.
+    pub role: String,
+    /// Background description of the persona.
+    pub background: String,
+    /// Communication style of the persona.
+    pub communication_style: String,
+    /// Whether this persona is a default participant in new sessions.
+    #[serde(default)]
+    pub default_participant: bool,
+    /// Source of the persona (System or User).
+    #[serde(default)]
+    pub source: PersonaSourceDTO,
+    /// Backend to execute persona with (supports all 7 backends).
+    #[serde(default)]
+    pub backend: PersonaBackendDTO,
+    /// Model name for the backend (e.g., "claude-sonnet-4-5-20250929", "gemini-3-pro-preview")
+    /// If None, uses the backend's default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// Visual icon/emoji representing this persona (e.g., "🎨", "🔧", "📊")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Base color for UI theming (e.g., "#FF5733", "#3357FF")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_color: Option<String>,
+    /// Gemini-specific options (thinking level, Google Search)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini_options: Option<GeminiOptionsDTO>,
+    /// Kaiba-specific options (Rei ID for persistent memory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kaiba_options: Option<KaibaOptionsDTO>,
+    /// Whether this persona is archived (hidden from pickers, kept for old sessions).
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// V1.8.0: Added context_mode_override (per-persona Rich/Clean context)
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.8.0")]
+pub struct PersonaConfigV1_8_0 {
+    /// Unique persona identifier (UUID format).
+    pub id: String,
+    /// Display name of the persona.
+    pub name: String,
+    /// Role or title of the persona.
+    pub role: String,
+    /// Background description of the persona.
+    pub background: String,
+    /// Communication style of the persona.
+    pub communication_style: String,
+    /// Whether this persona is a default participant in new sessions.
+    #[serde(default)]
+    pub default_participant: bool,
+    /// Source of the persona (System or User).
+    #[serde(default)]
+    pub source: PersonaSourceDTO,
+    /// Backend to execute persona with (supports all 7 backends).
+    #[serde(default)]
+    pub backend: PersonaBackendDTO,
+    /// Model name for the backend (e.g., "claude-sonnet-4-5-20250929", "gemini-3-pro-preview")
+    /// If None, uses the backend's default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// Visual icon/emoji representing this persona (e.g., "🎨", "🔧", "📊")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Base color for UI theming (e.g., "#FF5733", "#3357FF")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_color: Option<String>,
+    /// Gemini-specific options (thinking level, Google Search)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini_options: Option<GeminiOptionsDTO>,
+    /// Kaiba-specific options (Rei ID for persistent memory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kaiba_options: Option<KaibaOptionsDTO>,
+    /// Whether this persona is archived (hidden from pickers, kept for old sessions).
+    #[serde(default)]
+    pub archived: bool,
+    /// Context mode override; when None the session's context mode applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_mode_override: Option<ContextModeDto>,
+}
+
+/// V1.9.0: Added reviewer_only (no file-editing tools for Claude CLI)
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.9.0")]
+pub struct PersonaConfigV1_9_0 {
+    /// Unique persona identifier (UUID format).
+    pub id: String,
+    /// Display name of the persona.
+    pub name: String,
+    /// Role or title of the persona.
+    pub role: String,
+    /// Background description of the persona.
+    pub background: String,
+    /// Communication style of the persona.
+    pub communication_style: String,
+    /// Whether this persona is a default participant in new sessions.
+    #[serde(default)]
+    pub default_participant: bool,
+    /// Source of the persona (System or User).
+    #[serde(default)]
+    pub source: PersonaSourceDTO,
+    /// Backend to execute persona with (supports all 7 backends).
+    #[serde(default)]
+    pub backend: PersonaBackendDTO,
+    /// Model name for the backend (e.g., "claude-sonnet-4-5-20250929", "gemini-3-pro-preview")
+    /// If None, uses the backend's default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// Visual icon/emoji representing this persona (e.g., "🎨", "🔧", "📊")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Base color for UI theming (e.g., "#FF5733", "#3357FF")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_color: Option<String>,
+    /// Gemini-specific options (thinking level, Google Search)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini_options: Option<GeminiOptionsDTO>,
+    /// Kaiba-specific options (Rei ID for persistent memory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kaiba_options: Option<KaibaOptionsDTO>,
+    /// Whether this persona is archived (hidden from pickers, kept for old sessions).
+    #[serde(default)]
+    pub archived: bool,
+    /// Context mode override; when None the session's context mode applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_mode_override: Option<ContextModeDto>,
+    /// Whether this persona only reviews and never gets file-editing tools.
+    #[serde(default)]
+    pub reviewer_only: bool,
+}
+
+/// V1.10.0: Added icon_asset (uploaded icon image)
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.10.0")]
+pub struct PersonaConfigV1_10_0 {
+    /// Unique persona identifier (UUID format).
+    pub id: String,
+    /// Display name of the persona.
+    pub name: String,
+    /// Role or title of the persona.
+    pub role: String,
+    /// Background description of the persona.
+    pub background: String,
+    /// Communication style of the persona.
+    pub communication_style: String,
+    /// Whether this persona is a default participant in new sessions.
+    #[serde(default)]
+    pub default_participant: bool,
+    /// Source of the persona (System or User).
+    #[serde(default)]
+    pub source: PersonaSourceDTO,
+    /// Backend to execute persona with (supports all 7 backends).
+    #[serde(default)]
+    pub backend: PersonaBackendDTO,
+    /// Model name for the backend (e.g., "claude-sonnet-4-5-20250929", "gemini-3-pro-preview")
+    /// If None, uses the backend's default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// Visual icon/emoji representing this persona (e.g., "🎨", "🔧", "📊")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Base color for UI theming (e.g., "#FF5733", "#3357FF")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_color: Option<String>,
+    /// Gemini-specific options (thinking level, Google Search)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini_options: Option<GeminiOptionsDTO>,
+    /// Kaiba-specific options (Rei ID for persistent memory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kaiba_options: Option<KaibaOptionsDTO>,
+    /// Whether this persona is archived (hidden from pickers, kept for old sessions).
+    #[serde(default)]
+    pub archived: bool,
+    /// Context mode override; when None the session's context mode applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_mode_override: Option<ContextModeDto>,
+    /// Whether this persona only reviews and never gets file-editing tools.
+    #[serde(default)]
+    pub reviewer_only: bool,
+    /// File name of the uploaded icon image in the persona assets directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_asset: Option<String>,
+}
+
+/// V1.11.0: Added fallback_backend (CLI backend used while offline)
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.11.0")]
+pub struct PersonaConfigV1_11_0 {
+    /// Unique persona identifier (UUID format).
+    pub id: String,
+    /// Display name of the persona.
+    pub name: String,
+    /// Role or title of the persona.
+    pub role: String,
+    /// Background description of the persona.
+    pub background: String,
+    /// Communication style of the persona.
+    pub communication_style: String,
+    /// Whether this persona is a default participant in new sessions.
+    #[serde(default)]
+    pub default_participant: bool,
+    /// Source of the persona (System or User).
+    #[serde(default)]
+    pub source: PersonaSourceDTO,
+    /// Backend to execute persona with (supports all 7 backends).
+    #[serde(default)]
+    pub backend: PersonaBackendDTO,
+    /// Model name for the backend (e.g., "claude-sonnet-4-5-20250929", "gemini-3-pro-preview")
+    /// If None, uses the backend's default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// CLI backend used instead of an API `backend` while offline mode is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_backend: Option<PersonaBackendDTO>,
+    /// Visual icon/emoji representing this persona (e.g., "🎨", "🔧", "📊")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Base color for UI theming (e.g., "#FF5733", "#3357FF")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_color: Option<String>,
+    /// Gemini-specific options (thinking level, Google Search)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini_options: Option<GeminiOptionsDTO>,
+    /// Kaiba-specific options (Rei ID for persistent memory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kaiba_options: Option<KaibaOptionsDTO>,
+    /// Whether this persona is archived (hidden from pickers, kept for old sessions).
+    #[serde(default)]
+    pub archived: bool,
+    /// Context mode override; when None the session's context mode applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_mode_override: Option<ContextModeDto>,
+    /// Whether this persona only reviews and never gets file-editing tools.
+    #[serde(default)]
+    pub reviewer_only: bool,
+    /// File name of the uploaded icon image in the persona assets directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_asset: Option<String>,
+}
+
+/// V1.12.0: Added workspace_id (workspace-scoped personas)
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.12.0")]
+pub struct PersonaConfigV1_12_0 {
+    /// Unique persona identifier (UUID format).
+    pub id: String,
+    /// Display name of the persona.
+    pub name: String,
+    /// Role or title of the persona.
+    pub role: String,
+    /// Background description of the persona.
+    pub background: String,
+    /// Communication style of the persona.
+    pub communication_style: String,
+    /// Whether this persona is a default participant in new sessions.
+    #[serde(default)]
+    pub default_participant: bool,
+    /// Source of the persona (System or User).
+    #[serde(default)]
+    pub source: PersonaSourceDTO,
+    /// Backend to execute persona with (supports all 7 backends).
+    #[serde(default)]
+    pub backend: PersonaBackendDTO,
+    /// Model name for the backend (e.g., "claude-sonnet-4-5-20250929", "gemini-3-pro-preview")
+    /// If None, uses the backend's default model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// CLI backend used instead of an API `backend` while offline mode is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_backend: Option<PersonaBackendDTO>,
+    /// Visual icon/emoji representing this persona (e.g., "🎨", "🔧", "📊")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Base color for UI theming (e.g., "#FF5733", "#3357FF")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_color: Option<String>,
+    /// Gemini-specific options (thinking level, Google Search)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gemini_options: Option<GeminiOptionsDTO>,
+    /// Kaiba-specific options (Rei ID for persistent memory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kaiba_options: Option<KaibaOptionsDTO>,
+    /// Whether this persona is archived (hidden from pickers, kept for old sessions).
+    #[serde(default)]
+    pub archived: bool,
+    /// Context mode override; when None the session's context mode applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_mode_override: Option<ContextModeDto>,
+    /// Whether this persona only reviews and never gets file-editing tools.
+    #[serde(default)]
+    pub reviewer_only: bool,
+    /// File name of the uploaded icon image in the persona assets directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_asset: Option<String>,
+    /// Workspace the persona is scoped to; None makes it global.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+}
+
 // ============================================================================
 // Migration implementations
 // ============================================================================
@@ -393,6 +705,153 @@ impl MigratesTo<PersonaConfigV1_6_0> for PersonaConfigV1_5_0 {
     }
 }
 
+/// Migration from PersonaConfigV1_6_0 to PersonaConfigV1_7_0.
+impl MigratesTo<PersonaConfigV1_7_0> for PersonaConfigV1_6_0 {
+    fn migrate(self) -> PersonaConfigV1_7_0 {
+        PersonaConfigV1_7_0 {
+            id: self.id,
+            name: self.name,
+            role: self.role,
+            background: self.background,
+            communication_style: self.communication_style,
+            default_participant: self.default_participant,
+            source: self.source,
+            backend: self.backend,
+            model_name: self.model_name,
+            icon: self.icon,
+            base_color: self.base_color,
+            gemini_options: self.gemini_options,
+            kaiba_options: self.kaiba_options,
+            archived: false, // V1_6_0 doesn't have archived field
+        }
+    }
+}
+
+/// Migration from PersonaConfigV1_7_0 to PersonaConfigV1_8_0.
+impl MigratesTo<PersonaConfigV1_8_0> for PersonaConfigV1_7_0 {
+    fn migrate(self) -> PersonaConfigV1_8_0 {
+        PersonaConfigV1_8_0 {
+            id: self.id,
+            name: self.name,
+            role: self.role,
+            background: self.background,
+            communication_style: self.communication_style,
+            default_participant: self.default_participant,
+            source: self.source,
+            backend: self.backend,
+            model_name: self.model_name,
+            icon: self.icon,
+            base_color: self.base_color,
+            gemini_options: self.gemini_options,
+            kaiba_options: self.kaiba_options,
+            archived: self.archived,
+            context_mode_override: None, // V1_7_0 follows the session's context mode
+        }
+    }
+}
+
+/// Migration from PersonaConfigV1_8_0 to PersonaConfigV1_9_0.
+impl MigratesTo<PersonaConfigV1_9_0> for PersonaConfigV1_8_0 {
+    fn migrate(self) -> PersonaConfigV1_9_0 {
+        PersonaConfigV1_9_0 {
+            id: self.id,
+            name: self.name,
+            role: self.role,
+            background: self.background,
+            communication_style: self.communication_style,
+            default_participant: self.default_participant,
+            source: self.source,
+            backend: self.backend,
+            model_name: self.model_name,
+            icon: self.icon,
+            base_color: self.base_color,
+            gemini_options: self.gemini_options,
+            kaiba_options: self.kaiba_options,
+            archived: self.archived,
+            context_mode_override: self.context_mode_override,
+            reviewer_only: false, // V1_8_0 personas may edit files
+        }
+    }
+}
+
+/// Migration from PersonaConfigV1_9_0 to PersonaConfigV1_10_0.
+impl MigratesTo<PersonaConfigV1_10_0> for PersonaConfigV1_9_0 {
+    fn migrate(self) -> PersonaConfigV1_10_0 {
+        PersonaConfigV1_10_0 {
+            id: self.id,
+            name: self.name,
+            role: self.role,
+            background: self.background,
+            communication_style: self.communication_style,
+            default_participant: self.default_participant,
+            source: self.source,
+            backend: self.backend,
+            model_name: self.model_name,
+            icon: self.icon,
+            base_color: self.base_color,
+            gemini_options: self.gemini_options,
+            kaiba_options: self.kaiba_options,
+            archived: self.archived,
+            context_mode_override: self.context_mode_override,
+            reviewer_only: self.reviewer_only,
+            icon_asset: None, // V1_9_0 personas only have emoji icons
+        }
+    }
+}
+
+/// Migration from PersonaConfigV1_10_0 to PersonaConfigV1_11_0.
+impl MigratesTo<PersonaConfigV1_11_0> for PersonaConfigV1_10_0 {
+    fn migrate(self) -> PersonaConfigV1_11_0 {
+        PersonaConfigV1_11_0 {
+            id: self.id,
+            name: self.name,
+            role: self.role,
+            background: self.background,
+            communication_style: self.communication_style,
+            default_participant: self.default_participant,
+            source: self.source,
+            backend: self.backend,
+            model_name: self.model_name,
+            fallback_backend: None, // V1_10_0 personas have no offline fallback
+            icon: self.icon,
+            base_color: self.base_color,
+            gemini_options: self.gemini_options,
+            kaiba_options: self.kaiba_options,
+            archived: self.archived,
+            context_mode_override: self.context_mode_override,
+            reviewer_only: self.reviewer_only,
+            icon_asset: self.icon_asset,
+        }
+    }
+}
+
+/// Migration from PersonaConfigV1_11_0 to PersonaConfigV1_12_0.
+impl MigratesTo<PersonaConfigV1_12_0> for PersonaConfigV1_11_0 {
+    fn migrate(self) -> PersonaConfigV1_12_0 {
+        PersonaConfigV1_12_0 {
+            id: self.id,
+            name: self.name,
+            role: self.role,
+            background: self.background,
+            communication_style: self.communication_style,
+            default_participant: self.default_participant,
+            source: self.source,
+            backend: self.backend,
+            model_name: self.model_name,
+            fallback_backend: self.fallback_backend,
+            icon: self.icon,
+            base_color: self.base_color,
+            gemini_options: self.gemini_options,
+            kaiba_options: self.kaiba_options,
+            archived: self.archived,
+            context_mode_override: self.context_mode_override,
+            reviewer_only: self.reviewer_only,
+            icon_asset: self.icon_asset,
+            workspace_id: None, // V1_11_0 personas are global
+        }
+    }
+}
+
 // ============================================================================
 // Domain model conversions
 // ============================================================================
@@ -440,6 +899,7 @@ impl From<PersonaSourceDTO> for PersonaSource {
             PersonaSourceDTO::System => PersonaSource::System,
             PersonaSourceDTO::User => PersonaSource::User,
             PersonaSourceDTO::Adhoc => PersonaSource::Adhoc,
+            PersonaSourceDTO::Remote => PersonaSource::Remote,
         }
     }
 }
@@ -451,6 +911,7 @@ impl From<PersonaSource> for PersonaSourceDTO {
             PersonaSource::System => PersonaSourceDTO::System,
             PersonaSource::User => PersonaSourceDTO::User,
             PersonaSource::Adhoc => PersonaSourceDTO::Adhoc,
+            PersonaSource::Remote => PersonaSourceDTO::Remote,
         }
     }
 }
@@ -483,14 +944,14 @@ impl From<PersonaBackend> for PersonaBackendDTO {
     }
 }
 
-/// Convert PersonaConfigV1_6_0 DTO to domain model.
-impl IntoDomain<Persona> for PersonaConfigV1_6_0 {
+/// Convert PersonaConfigV1_12_0 DTO to domain model.
+impl IntoDomain<Persona> for PersonaConfigV1_12_0 {
     fn into_domain(self) -> Persona {
         // Validate and fix ID if needed
         let id = if Uuid::parse_str(&self.id).is_ok() {
             self.id
         } else {
-            // Legacy data: V1.6.0 schema but non-UUID ID
+            // Legacy data: V1.12.0 schema but non-UUID ID
             generate_uuid_from_name(&self.name)
         };
 
@@ -504,18 +965,24 @@ impl IntoDomain<Persona> for PersonaConfigV1_6_0 {
             source: self.source.into(),
             backend: self.backend.into(),
             model_name: self.model_name,
+            fallback_backend: self.fallback_backend.map(Into::into),
             icon: self.icon,
             base_color: self.base_color,
             gemini_options: self.gemini_options.map(Into::into),
             kaiba_options: self.kaiba_options.map(Into::into),
+            archived: self.archived,
+            context_mode_override: self.context_mode_override.map(Into::into),
+            reviewer_only: self.reviewer_only,
+            icon_asset: self.icon_asset,
+            workspace_id: self.workspace_id,
         }
     }
 }
 
-/// Convert domain model to PersonaConfigV1_6_0 DTO for persistence.
-impl version_migrate::FromDomain<Persona> for PersonaConfigV1_6_0 {
+/// Convert domain model to PersonaConfigV1_12_0 DTO for persistence.
+impl version_migrate::FromDomain<Persona> for PersonaConfigV1_12_0 {
     fn from_domain(persona: Persona) -> Self {
-        PersonaConfigV1_6_0 {
+        PersonaConfigV1_12_0 {
             id: persona.id,
             name: persona.name,
             role: persona.role,
@@ -525,10 +992,16 @@ impl version_migrate::FromDomain<Persona> for PersonaConfigV1_6_0 {
             source: persona.source.into(),
             backend: persona.backend.into(),
             model_name: persona.model_name,
+            fallback_backend: persona.fallback_backend.map(Into::into),
             icon: persona.icon,
             base_color: persona.base_color,
             gemini_options: persona.gemini_options.map(Into::into),
             kaiba_options: persona.kaiba_options.map(Into::into),
+            archived: persona.archived,
+            context_mode_override: persona.context_mode_override.map(Into::into),
+            reviewer_only: persona.reviewer_only,
+            icon_asset: persona.icon_asset,
+            workspace_id: persona.workspace_id,
         }
     }
 }
@@ -539,7 +1012,7 @@ impl version_migrate::FromDomain<Persona> for PersonaConfigV1_6_0 {
 
 /// Creates and configures a Migrator instance for Persona entities.
 ///
-/// The migrator handles automatic schema migration from V1.0.0 to V1.6.0
+/// The migrator handles automatic schema migration from V1.0.0 to V1.12.0
 /// and conversion to the domain model.
 ///
 /// # Migration Path
@@ -550,7 +1023,13 @@ impl version_migrate::FromDomain<Persona> for PersonaConfigV1_6_0 {
 /// - V1.3.0 → V1.4.0: Adds `base_color` field (optional)
 /// - V1.4.0 → V1.5.0: Adds `gemini_options` field (optional)
 /// - V1.5.0 → V1.6.0: Adds `kaiba_options` field (optional)
-/// - V1.6.0 → Persona: Converts DTO to domain model (supports all 7 backends via enum expansion)
+/// - V1.6.0 → V1.7.0: Adds `archived` field (defaults to false)
+/// - V1.7.0 → V1.8.0: Adds `context_mode_override` field (optional)
+/// - V1.8.0 → V1.9.0: Adds `reviewer_only` field (defaults to false)
+/// - V1.9.0 → V1.10.0: Adds `icon_asset` field (optional)
+/// - V1.10.0 → V1.11.0: Adds `fallback_backend` field (optional)
+/// - V1.11.0 → V1.12.0: Adds `workspace_id` field (optional)
+/// - V1.12.0 → Persona: Converts DTO to domain model (supports all 7 backends via enum expansion)
 ///
 /// # Example
 ///
@@ -567,6 +1046,12 @@ pub fn create_persona_migrator() -> version_migrate::Migrator {
         PersonaConfigV1_4_0,
         PersonaConfigV1_5_0,
         PersonaConfigV1_6_0,
+        PersonaConfigV1_7_0,
+        PersonaConfigV1_8_0,
+        PersonaConfigV1_9_0,
+        PersonaConfigV1_10_0,
+        PersonaConfigV1_11_0,
+        PersonaConfigV1_12_0,
         Persona
     ], save = true)
     .expect("Failed to create persona migrator")
@@ -575,6 +1060,8 @@ pub fn create_persona_migrator() -> version_migrate::Migrator {
 #[cfg(test)]
 mod migrator_tests {
     use super::*;
+    use orcs_core::session::ContextMode;
+    use version_migrate::FromDomain;
 
     #[test]
     fn test_persona_migrator_creation() {
@@ -642,4 +1129,122 @@ backend = "gemini_cli"
         assert_eq!(persona.name, "Test");
         assert_eq!(persona.backend, PersonaBackend::GeminiCli);
     }
+
+    #[test]
+    fn test_persona_migration_v1_6_defaults_to_not_archived() {
+        let migrator = create_persona_migrator();
+
+        let toml_str = r#"
+version = "1.6.0"
+id = "8c3e2d7a-6a7b-4f3e-9d2a-1b2c3d4e5f60"
+name = "Test"
+role = "Tester"
+background = "Test background"
+communication_style = "Test style"
+default_participant = true
+source = "User"
+backend = "claude_cli"
+"#;
+        let toml_value: toml::Value = toml::from_str(toml_str).unwrap();
+
+        let persona: Persona = migrator.load_flat_from("persona", toml_value).unwrap();
+        assert!(!persona.archived);
+    }
+
+    #[test]
+    fn test_persona_context_mode_override_round_trip() {
+        let migrator = create_persona_migrator();
+
+        let toml_str = r#"
+version = "1.7.0"
+id = "8c3e2d7a-6a7b-4f3e-9d2a-1b2c3d4e5f60"
+name = "Test"
+role = "Tester"
+background = "Test background"
+communication_style = "Test style"
+"#;
+        let toml_value: toml::Value = toml::from_str(toml_str).unwrap();
+        let mut persona: Persona = migrator.load_flat_from("persona", toml_value).unwrap();
+        assert_eq!(persona.context_mode_override, None);
+
+        persona.context_mode_override = Some(ContextMode::Clean);
+        let dto = PersonaConfigV1_12_0::from_domain(persona);
+        assert_eq!(dto.context_mode_override, Some(ContextModeDto::Clean));
+        assert_eq!(
+            dto.into_domain().context_mode_override,
+            Some(ContextMode::Clean)
+        );
+    }
+
+    #[test]
+    fn test_persona_migration_v1_9_has_no_icon_asset() {
+        let migrator = create_persona_migrator();
+
+        let toml_str = r#"
+version = "1.9.0"
+id = "8c3e2d7a-6a7b-4f3e-9d2a-1b2c3d4e5f60"
+name = "Test"
+role = "Tester"
+background = "Test background"
+communication_style = "Test style"
+icon = "🔧"
+"#;
+        let toml_value: toml::Value = toml::from_str(toml_str).unwrap();
+        let mut persona: Persona = migrator.load_flat_from("persona", toml_value).unwrap();
+        assert_eq!(persona.icon_asset, None);
+        assert_eq!(persona.display_icon().as_deref(), Some("🔧"));
+
+        persona.icon_asset = Some("ab12.png".to_string());
+        let dto = PersonaConfigV1_12_0::from_domain(persona);
+        assert_eq!(dto.icon_asset.as_deref(), Some("ab12.png"));
+        assert_eq!(dto.into_domain().icon_asset.as_deref(), Some("ab12.png"));
+    }
+
+    #[test]
+    fn test_persona_fallback_backend_round_trip() {
+        let migrator = create_persona_migrator();
+
+        let toml_str = r#"
+version = "1.10.0"
+id = "8c3e2d7a-6a7b-4f3e-9d2a-1b2c3d4e5f60"
+name = "Test"
+role = "Tester"
+background = "Test background"
+communication_style = "Test style"
+backend = "claude_api"
+"#;
+        let toml_value: toml::Value = toml::from_str(toml_str).unwrap();
+        let mut persona: Persona = migrator.load_flat_from("persona", toml_value).unwrap();
+        assert_eq!(persona.fallback_backend, None);
+
+        persona.fallback_backend = Some(PersonaBackend::ClaudeCli);
+        let dto = PersonaConfigV1_12_0::from_domain(persona);
+        assert_eq!(dto.fallback_backend, Some(PersonaBackendDTO::ClaudeCli));
+        assert_eq!(
+            dto.into_domain().fallback_backend,
+            Some(PersonaBackend::ClaudeCli)
+        );
+    }
+
+    #[test]
+    fn test_persona_workspace_id_round_trip() {
+        let migrator = create_persona_migrator();
+
+        let toml_str = r#"
+version = "1.11.0"
+id = "8c3e2d7a-6a7b-4f3e-9d2a-1b2c3d4e5f60"
+name = "Test"
+role = "Tester"
+background = "Test background"
+communication_style = "Test style"
+"#;
+        let toml_value: toml::Value = toml::from_str(toml_str).unwrap();
+        let mut persona: Persona = migrator.load_flat_from("persona", toml_value).unwrap();
+        assert_eq!(persona.workspace_id, None);
+
+        persona.workspace_id = Some("ws-1".to_string());
+        let dto = PersonaConfigV1_12_0::from_domain(persona);
+        assert_eq!(dto.workspace_id.as_deref(), Some("ws-1"));
+        assert_eq!(dto.into_domain().workspace_id.as_deref(), Some("ws-1"));
+    }
 }
@@ -5,9 +5,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use version_migrate::{FromDomain, IntoDomain, MigratesTo, Versioned};
 
+use orcs_core::conversation_script::ScriptRun;
 use orcs_core::session::{
-    AppMode, AutoChatConfig, ContextMode, ConversationMessage, ConversationMode, MessageRole,
-    PLACEHOLDER_WORKSPACE_ID, SandboxState, Session,
+    AppMode, AutoChatConfig, ContextMode, ConversationMessage, ConversationMode,
+    DEFAULT_PASTE_ATTACHMENT_THRESHOLD, MessageRole, PLACEHOLDER_WORKSPACE_ID, SandboxState,
+    Session, StopCondition,
 };
 
 // ============================================================================
@@ -186,6 +188,178 @@ impl FromDomain<SandboxState> for SandboxStateV1_0_0 {
     }
 }
 
+// ============================================================================
+// ScriptRun DTOs
+// ============================================================================
+
+/// V1.0.0: Progress of the conversation script running in a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.0.0")]
+pub struct ScriptRunV1_0_0 {
+    pub script_id: String,
+    pub script_name: String,
+    pub next_step: usize,
+    pub total_steps: usize,
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// Convert DTO to domain model
+impl IntoDomain<ScriptRun> for ScriptRunV1_0_0 {
+    fn into_domain(self) -> ScriptRun {
+        ScriptRun {
+            script_id: self.script_id,
+            script_name: self.script_name,
+            next_step: self.next_step,
+            total_steps: self.total_steps,
+            paused: self.paused,
+        }
+    }
+}
+
+/// Convert domain model to DTO
+impl FromDomain<ScriptRun> for ScriptRunV1_0_0 {
+    fn from_domain(model: ScriptRun) -> Self {
+        ScriptRunV1_0_0 {
+            script_id: model.script_id,
+            script_name: model.script_name,
+            next_step: model.next_step,
+            total_steps: model.total_steps,
+            paused: model.paused,
+        }
+    }
+}
+
+// ============================================================================
+// AutoChatConfig DTOs
+// ============================================================================
+
+/// V1.0.0: Initial AutoChat config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.0.0")]
+pub struct AutoChatConfigV1_0_0 {
+    pub max_iterations: i32,
+    pub stop_condition: StopCondition,
+    pub web_search_enabled: bool,
+}
+
+/// V1.1.0: Added stop_phrases
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.1.0")]
+pub struct AutoChatConfigV1_1_0 {
+    pub max_iterations: i32,
+    pub stop_condition: StopCondition,
+    pub web_search_enabled: bool,
+    #[serde(default)]
+    pub stop_phrases: Vec<String>,
+}
+
+/// Migration from V1.0.0 to V1.1.0
+impl MigratesTo<AutoChatConfigV1_1_0> for AutoChatConfigV1_0_0 {
+    fn migrate(self) -> AutoChatConfigV1_1_0 {
+        AutoChatConfigV1_1_0 {
+            max_iterations: self.max_iterations,
+            stop_condition: self.stop_condition,
+            web_search_enabled: self.web_search_enabled,
+            stop_phrases: Vec::new(), // Old configs have no stop phrases
+        }
+    }
+}
+
+/// V1.2.0: Added iteration_delay_ms and adaptive_delay pacing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.2.0")]
+pub struct AutoChatConfigV1_2_0 {
+    pub max_iterations: i32,
+    pub stop_condition: StopCondition,
+    pub web_search_enabled: bool,
+    #[serde(default)]
+    pub stop_phrases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iteration_delay_ms: Option<u64>,
+    #[serde(default)]
+    pub adaptive_delay: bool,
+}
+
+/// Migration from V1.1.0 to V1.2.0
+impl MigratesTo<AutoChatConfigV1_2_0> for AutoChatConfigV1_1_0 {
+    fn migrate(self) -> AutoChatConfigV1_2_0 {
+        AutoChatConfigV1_2_0 {
+            max_iterations: self.max_iterations,
+            stop_condition: self.stop_condition,
+            web_search_enabled: self.web_search_enabled,
+            stop_phrases: self.stop_phrases,
+            iteration_delay_ms: None, // Old configs use the fixed default delay
+            adaptive_delay: false,
+        }
+    }
+}
+
+/// Convert DTO to domain model
+impl IntoDomain<AutoChatConfig> for AutoChatConfigV1_2_0 {
+    fn into_domain(self) -> AutoChatConfig {
+        AutoChatConfig {
+            max_iterations: self.max_iterations,
+            stop_condition: self.stop_condition,
+            web_search_enabled: self.web_search_enabled,
+            stop_phrases: self.stop_phrases,
+            iteration_delay_ms: self.iteration_delay_ms,
+            adaptive_delay: self.adaptive_delay,
+        }
+    }
+}
+
+/// Convert domain model to DTO
+impl FromDomain<AutoChatConfig> for AutoChatConfigV1_2_0 {
+    fn from_domain(model: AutoChatConfig) -> Self {
+        AutoChatConfigV1_2_0 {
+            max_iterations: model.max_iterations,
+            stop_condition: model.stop_condition,
+            web_search_enabled: model.web_search_enabled,
+            stop_phrases: model.stop_phrases,
+            iteration_delay_ms: model.iteration_delay_ms,
+            adaptive_delay: model.adaptive_delay,
+        }
+    }
+}
+
+/// Convert V1.1.0 to domain model
+impl IntoDomain<AutoChatConfig> for AutoChatConfigV1_1_0 {
+    fn into_domain(self) -> AutoChatConfig {
+        self.migrate().into_domain()
+    }
+}
+
+/// Convert domain model to V1.1.0 (lossy - drops pacing settings)
+impl FromDomain<AutoChatConfig> for AutoChatConfigV1_1_0 {
+    fn from_domain(model: AutoChatConfig) -> Self {
+        AutoChatConfigV1_1_0 {
+            max_iterations: model.max_iterations,
+            stop_condition: model.stop_condition,
+            web_search_enabled: model.web_search_enabled,
+            stop_phrases: model.stop_phrases,
+        }
+    }
+}
+
+/// Convert V1.0.0 to domain model
+impl IntoDomain<AutoChatConfig> for AutoChatConfigV1_0_0 {
+    fn into_domain(self) -> AutoChatConfig {
+        self.migrate().into_domain()
+    }
+}
+
+/// Convert domain model to V1.0.0 (lossy - drops stop_phrases)
+impl FromDomain<AutoChatConfig> for AutoChatConfigV1_0_0 {
+    fn from_domain(model: AutoChatConfig) -> Self {
+        AutoChatConfigV1_0_0 {
+            max_iterations: model.max_iterations,
+            stop_condition: model.stop_condition,
+            web_search_enabled: model.web_search_enabled,
+        }
+    }
+}
+
 // ============================================================================
 // Session DTOs
 // ============================================================================
@@ -816,9 +990,9 @@ pub struct SessionV3_3_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
 }
 
 /// V3.4.0: Added participant_backends and participant_models for API type display
@@ -880,9 +1054,9 @@ pub struct SessionV3_4_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
 }
 
 /// V4.0.0: Update execution_strategy V1_0_0 to V2_0_0
@@ -944,9 +1118,9 @@ pub struct SessionV4_0_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
 }
 
 /// V4.1.0: Fix participant_models to avoid TOML null serialization errors
@@ -1009,9 +1183,9 @@ pub struct SessionV4_1_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
 }
 
 /// V4.2.0: Added is_muted for memo mode
@@ -1146,9 +1320,9 @@ pub struct SessionV4_2_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
     /// Whether this session is muted (AI won't respond to messages)
     #[serde(default)]
     pub is_muted: bool,
@@ -1220,9 +1394,9 @@ pub struct SessionV4_3_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
     /// Whether this session is muted (AI won't respond to messages)
     #[serde(default)]
     pub is_muted: bool,
@@ -1291,9 +1465,9 @@ pub struct SessionV4_4_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
     /// Whether this session is muted (AI won't respond to messages)
     #[serde(default)]
     pub is_muted: bool,
@@ -1365,9 +1539,9 @@ pub struct SessionV4_5_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
     /// Whether this session is muted (AI won't respond to messages)
     #[serde(default)]
     pub is_muted: bool,
@@ -1439,9 +1613,9 @@ pub struct SessionV4_6_0 {
     /// Manual sort order (optional, for custom ordering within favorites)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<i32>,
-    /// AutoChat configuration (None means AutoChat is disabled)
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub auto_chat_config: Option<AutoChatConfig>,
+    pub auto_chat_config: Option<AutoChatConfigV1_0_0>,
     /// Whether this session is muted (AI won't respond to messages)
     #[serde(default)]
     pub is_muted: bool,
@@ -1457,65 +1631,1650 @@ pub struct SessionV4_6_0 {
     pub last_memory_sync_at: Option<String>,
 }
 
-fn default_execution_strategy() -> String {
-    "broadcast".to_string()
-}
-
-fn normalize_conversation_messages(messages: Vec<ConversationMessage>) -> Vec<ConversationMessage> {
-    messages
-        .into_iter()
-        .map(|mut message| {
-            if message.metadata.system_message_type.is_none() && message.role == MessageRole::System
-            {
-                message.metadata.system_message_type = Some("system".to_string());
-            }
-            message
-        })
-        .collect()
+/// Represents V4.7.0 of the session data schema.
+/// Added is_generating for background dialogue generation, and AutoChat
+/// config stop_phrases (AutoChatConfigV1_1_0).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.7.0")]
+pub struct SessionV4_7_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_1_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether a background dialogue is still generating responses
+    #[serde(default)]
+    pub is_generating: bool,
 }
 
-// ============================================================================
-// Migration implementations
-// ============================================================================
-
-/// Migration from SessionV1_0_0 to SessionV1_1_0.
-/// Changes: 'name' → 'title'
-impl MigratesTo<SessionV1_1_0> for SessionV1_0_0 {
-    fn migrate(self) -> SessionV1_1_0 {
-        SessionV1_1_0 {
-            id: self.id,
-            title: self.name, // name → title
-            created_at: self.created_at,
-            updated_at: self.updated_at,
-            current_persona_id: self.current_persona_id,
-            persona_histories: self.persona_histories,
-            app_mode: self.app_mode,
-        }
-    }
+/// Represents V4.8.0 of the session data schema.
+/// Added style_calibration_enabled for persona style calibration from user feedback.
+/// Dropped is_generating: generation state is runtime-only, so a crash
+/// mid-generation cannot leave the session stuck.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.8.0")]
+pub struct SessionV4_8_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_1_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
 }
 
-/// Migration from SessionV1_1_0 to SessionV2_0_0.
-/// Added workspace_id field (defaults to None for existing sessions).
-impl MigratesTo<SessionV2_0_0> for SessionV1_1_0 {
-    fn migrate(self) -> SessionV2_0_0 {
-        SessionV2_0_0 {
-            id: self.id,
-            title: self.title,
-            created_at: self.created_at,
-            updated_at: self.updated_at,
-            current_persona_id: self.current_persona_id,
-            persona_histories: self.persona_histories,
+/// Represents V4.9.0 of the session data schema.
+/// Added AutoChat pacing (iteration_delay_ms, adaptive_delay) via AutoChatConfigV1_2_0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.9.0")]
+pub struct SessionV4_9_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+}
+
+/// Represents V4.10.0 of the session data schema.
+/// Added response_splitting_enabled for splitting replies that speak for several personas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.10.0")]
+pub struct SessionV4_10_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+}
+
+/// Represents V4.11.0 of the session data schema.
+/// Added the pinboard notes document and whether agents may append to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.11.0")]
+pub struct SessionV4_11_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+}
+
+/// Represents V4.12.0 of the session data schema.
+/// Added whether conversation-mode instructions apply in Clean context mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.12.0")]
+pub struct SessionV4_12_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+}
+
+/// Represents V4.13.0 of the session data schema.
+/// Added the opt-in for participant suggestions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.13.0")]
+pub struct SessionV4_13_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    #[serde(default)]
+    pub participant_suggestions_enabled: bool,
+}
+
+/// Represents V4.14.0 of the session data schema.
+/// Added the progress of a running conversation script.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.14.0")]
+pub struct SessionV4_14_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    #[serde(default)]
+    pub participant_suggestions_enabled: bool,
+    /// Progress of the conversation script running in this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_run: Option<ScriptRunV1_0_0>,
+}
+
+/// Represents V4.15.0 of the session data schema.
+/// Added the focused participant subset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.15.0")]
+pub struct SessionV4_15_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    #[serde(default)]
+    pub participant_suggestions_enabled: bool,
+    /// Progress of the conversation script running in this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_run: Option<ScriptRunV1_0_0>,
+    /// Participants answering while focus mode is on (None: everyone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused_participant_ids: Option<Vec<String>>,
+}
+
+/// Represents V4.16.0 of the session data schema.
+/// Added the per-session random seed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.16.0")]
+pub struct SessionV4_16_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    #[serde(default)]
+    pub participant_suggestions_enabled: bool,
+    /// Progress of the conversation script running in this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_run: Option<ScriptRunV1_0_0>,
+    /// Participants answering while focus mode is on (None: everyone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused_participant_ids: Option<Vec<String>>,
+    /// Seed passed to backends that support one (None: unseeded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u64>,
+}
+
+/// Represents V4.17.0 of the session data schema.
+/// Added the clock context toggle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.17.0")]
+pub struct SessionV4_17_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    #[serde(default)]
+    pub participant_suggestions_enabled: bool,
+    /// Progress of the conversation script running in this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_run: Option<ScriptRunV1_0_0>,
+    /// Participants answering while focus mode is on (None: everyone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused_participant_ids: Option<Vec<String>>,
+    /// Seed passed to backends that support one (None: unseeded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u64>,
+    /// Whether Rich-mode turns include the current time and user message gaps
+    #[serde(default = "default_clock_context_enabled")]
+    pub clock_context_enabled: bool,
+}
+
+/// Represents V4.18.0 of the session data schema.
+/// Added the plan detection toggle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.18.0")]
+pub struct SessionV4_18_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    #[serde(default)]
+    pub participant_suggestions_enabled: bool,
+    /// Progress of the conversation script running in this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_run: Option<ScriptRunV1_0_0>,
+    /// Participants answering while focus mode is on (None: everyone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused_participant_ids: Option<Vec<String>>,
+    /// Seed passed to backends that support one (None: unseeded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u64>,
+    /// Whether Rich-mode turns include the current time and user message gaps
+    #[serde(default = "default_clock_context_enabled")]
+    pub clock_context_enabled: bool,
+    /// Whether fenced `plan` blocks in agent replies are offered for confirmation
+    #[serde(default)]
+    pub plan_detection_enabled: bool,
+}
+
+/// Represents V4.19.0 of the session data schema.
+/// Added the paste-to-attachment toggle and threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Versioned)]
+#[versioned(version = "4.19.0")]
+pub struct SessionV4_19_0 {
+    /// Unique session identifier
+    pub id: String,
+    /// Human-readable session title
+    pub title: String,
+    /// Timestamp when the session was created (ISO 8601 format)
+    pub created_at: String,
+    /// Timestamp when the session was last updated (ISO 8601 format)
+    pub updated_at: String,
+    /// The currently active persona ID
+    pub current_persona_id: String,
+    /// Conversation history for each persona
+    pub persona_histories: HashMap<String, Vec<ConversationMessage>>,
+    /// Current application mode
+    pub app_mode: AppMode,
+    /// Workspace ID - all sessions must be associated with a workspace
+    pub workspace_id: String,
+    /// Active participant persona IDs
+    #[serde(default)]
+    pub active_participant_ids: Vec<String>,
+    /// Execution strategy (now using ExecutionModel enum)
+    #[serde(default = "default_execution_strategy_v2_0_0")]
+    pub execution_strategy: ExecutionStrategyV2_0_0,
+    /// System messages (join/leave notifications, etc.)
+    #[serde(default)]
+    pub system_messages: Vec<ConversationMessage>,
+    /// Participant persona ID to name mapping for display
+    #[serde(default)]
+    pub participants: HashMap<String, String>,
+    /// Participant persona ID to icon mapping for display
+    #[serde(default)]
+    pub participant_icons: HashMap<String, String>,
+    /// Participant persona ID to base color mapping for UI theming
+    #[serde(default)]
+    pub participant_colors: HashMap<String, String>,
+    /// Participant persona ID to backend mapping (e.g., "claude_api", "gemini_cli")
+    #[serde(default)]
+    pub participant_backends: HashMap<String, String>,
+    /// Participant persona ID to model name mapping (e.g., "claude-sonnet-4-5-20250929")
+    #[serde(default)]
+    pub participant_models: HashMap<String, String>,
+    /// Conversation mode (controls verbosity and style)
+    #[serde(default)]
+    pub conversation_mode: ConversationMode,
+    /// Talk style for dialogue context (Brainstorm, Debate, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub talk_style: Option<TalkStyle>,
+    /// Whether this session is marked as favorite (pinned to top)
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// Whether this session is archived (hidden by default)
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Manual sort order (optional, for custom ordering within favorites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+    /// AutoChat configuration with versioned DTO (None means AutoChat is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_chat_config: Option<AutoChatConfigV1_2_0>,
+    /// Whether this session is muted (AI won't respond to messages)
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Context mode for AI interactions (Rich = full context, Clean = expertise only)
+    #[serde(default)]
+    pub context_mode: ContextModeDto,
+    /// Sandbox state with versioned DTO (None = normal mode, Some = sandbox mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_state: Option<SandboxStateV1_1_0>,
+    /// Timestamp of the last successful memory sync (ISO 8601 format)
+    /// Used for differential sync - only messages after this timestamp are synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_memory_sync_at: Option<String>,
+    /// Whether personas adapt their style to user feedback in this session
+    #[serde(default)]
+    pub style_calibration_enabled: bool,
+    /// Whether a reply speaking for several personas is split into one turn per persona
+    #[serde(default = "default_response_splitting_enabled")]
+    pub response_splitting_enabled: bool,
+    /// Shared notes document for this session, editable by the user and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinboard: Option<String>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    #[serde(default)]
+    pub pinboard_agent_edits_enabled: bool,
+    /// Whether conversation-mode instructions also apply in Clean context mode
+    #[serde(default = "default_apply_conversation_mode_in_clean")]
+    pub apply_conversation_mode_in_clean: bool,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    #[serde(default)]
+    pub participant_suggestions_enabled: bool,
+    /// Progress of the conversation script running in this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_run: Option<ScriptRunV1_0_0>,
+    /// Participants answering while focus mode is on (None: everyone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused_participant_ids: Option<Vec<String>>,
+    /// Seed passed to backends that support one (None: unseeded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u64>,
+    /// Whether Rich-mode turns include the current time and user message gaps
+    #[serde(default = "default_clock_context_enabled")]
+    pub clock_context_enabled: bool,
+    /// Whether fenced `plan` blocks in agent replies are offered for confirmation
+    #[serde(default)]
+    pub plan_detection_enabled: bool,
+    /// Whether long non-prose user pastes are turned into attachments
+    #[serde(default = "default_paste_attachment_enabled")]
+    pub paste_attachment_enabled: bool,
+    /// Length in characters above which a paste is turned into an attachment
+    #[serde(default = "default_paste_attachment_threshold")]
+    pub paste_attachment_threshold: usize,
+}
+
+fn default_execution_strategy() -> String {
+    "broadcast".to_string()
+}
+
+fn default_response_splitting_enabled() -> bool {
+    true
+}
+
+fn default_apply_conversation_mode_in_clean() -> bool {
+    true
+}
+
+fn default_clock_context_enabled() -> bool {
+    true
+}
+
+fn default_paste_attachment_enabled() -> bool {
+    true
+}
+
+fn default_paste_attachment_threshold() -> usize {
+    DEFAULT_PASTE_ATTACHMENT_THRESHOLD
+}
+
+fn normalize_conversation_messages(messages: Vec<ConversationMessage>) -> Vec<ConversationMessage> {
+    messages
+        .into_iter()
+        .map(|mut message| {
+            if message.metadata.system_message_type.is_none() && message.role == MessageRole::System
+            {
+                message.metadata.system_message_type = Some("system".to_string());
+            }
+            message
+        })
+        .collect()
+}
+
+// ============================================================================
+// Migration implementations
+// ============================================================================
+
+/// Migration from SessionV1_0_0 to SessionV1_1_0.
+/// Changes: 'name' → 'title'
+impl MigratesTo<SessionV1_1_0> for SessionV1_0_0 {
+    fn migrate(self) -> SessionV1_1_0 {
+        SessionV1_1_0 {
+            id: self.id,
+            title: self.name, // name → title
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+        }
+    }
+}
+
+/// Migration from SessionV1_1_0 to SessionV2_0_0.
+/// Added workspace_id field (defaults to None for existing sessions).
+impl MigratesTo<SessionV2_0_0> for SessionV1_1_0 {
+    fn migrate(self) -> SessionV2_0_0 {
+        SessionV2_0_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: None, // Existing sessions have no workspace association
+        }
+    }
+}
+
+/// Migration from SessionV2_0_0 to SessionV2_1_0.
+/// Added active_participant_ids and execution_strategy fields.
+impl MigratesTo<SessionV2_1_0> for SessionV2_0_0 {
+    fn migrate(self) -> SessionV2_1_0 {
+        SessionV2_1_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: Vec::new(), // No active participants in old sessions
+            execution_strategy: default_execution_strategy(), // Default to broadcast
+        }
+    }
+}
+
+/// Migration from SessionV2_1_0 to SessionV2_2_0.
+/// Added system_messages field for system notifications.
+impl MigratesTo<SessionV2_2_0> for SessionV2_1_0 {
+    fn migrate(self) -> SessionV2_2_0 {
+        SessionV2_2_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: Vec::new(), // No system messages in old sessions
+        }
+    }
+}
+
+/// Migration from SessionV2_2_0 to SessionV2_3_0.
+/// Added participants field for persona ID to name mapping.
+impl MigratesTo<SessionV2_3_0> for SessionV2_2_0 {
+    fn migrate(self) -> SessionV2_3_0 {
+        SessionV2_3_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: HashMap::new(), // Will be populated on save
+        }
+    }
+}
+
+/// Migration from SessionV2_3_0 to SessionV2_4_0.
+/// Added conversation_mode field for controlling multi-agent dialogue verbosity.
+impl MigratesTo<SessionV2_4_0> for SessionV2_3_0 {
+    fn migrate(self) -> SessionV2_4_0 {
+        SessionV2_4_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            conversation_mode: ConversationMode::default(), // Default to Normal mode
+        }
+    }
+}
+
+/// Migration from SessionV2_4_0 to SessionV2_5_0.
+/// Added talk_style field for dialogue context.
+impl MigratesTo<SessionV2_5_0> for SessionV2_4_0 {
+    fn migrate(self) -> SessionV2_5_0 {
+        SessionV2_5_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            conversation_mode: self.conversation_mode,
+            talk_style: None, // Default to no talk style set
+        }
+    }
+}
+
+/// Migration from SessionV2_5_0 to SessionV2_6_0.
+/// Normalizes conversation metadata for UI reconstruction.
+impl MigratesTo<SessionV2_6_0> for SessionV2_5_0 {
+    fn migrate(self) -> SessionV2_6_0 {
+        SessionV2_6_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self
+                .persona_histories
+                .into_iter()
+                .map(|(persona_id, messages)| {
+                    (persona_id, normalize_conversation_messages(messages))
+                })
+                .collect(),
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: normalize_conversation_messages(self.system_messages),
+            participants: self.participants,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+        }
+    }
+}
+
+/// Migration from SessionV2_6_0 to SessionV2_7_0.
+/// Changes execution_strategy from String to ExecutionStrategyV2_0_0 DTO.
+impl MigratesTo<SessionV2_7_0> for SessionV2_6_0 {
+    fn migrate(self) -> SessionV2_7_0 {
+        SessionV2_7_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy.into(),
+            system_messages: self.system_messages,
+            participants: self.participants,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+        }
+    }
+}
+
+/// Migration from SessionV2_7_0 to SessionV2_8_0.
+impl MigratesTo<SessionV2_8_0> for SessionV2_7_0 {
+    fn migrate(self) -> SessionV2_8_0 {
+        SessionV2_8_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: HashMap::new(), // V2_7_0 doesn't have icon field
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+        }
+    }
+}
+
+/// Migration from SessionV2_8_0 to SessionV2_9_0.
+/// Added participant_colors for UI theming
+impl MigratesTo<SessionV2_9_0> for SessionV2_8_0 {
+    fn migrate(self) -> SessionV2_9_0 {
+        SessionV2_9_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: HashMap::new(), // V2_8_0 doesn't have color field
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+        }
+    }
+}
+
+/// Migration from V2.9.0 to V3.0.0
+/// Makes workspace_id required by setting placeholder if None
+impl MigratesTo<SessionV3_0_0> for SessionV2_9_0 {
+    fn migrate(self) -> SessionV3_0_0 {
+        SessionV3_0_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self
+                .workspace_id
+                .unwrap_or_else(|| PLACEHOLDER_WORKSPACE_ID.to_string()),
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+        }
+    }
+}
+
+/// Migration from V3.0.0 to V3.1.0
+/// Adds is_favorite and is_archived fields (default to false)
+impl MigratesTo<SessionV3_1_0> for SessionV3_0_0 {
+    fn migrate(self) -> SessionV3_1_0 {
+        SessionV3_1_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+            is_favorite: false, // Existing sessions are not favorited by default
+            is_archived: false, // Existing sessions are not archived by default
+        }
+    }
+}
+
+/// Migration from V3.1.0 to V3.2.0
+impl MigratesTo<SessionV3_2_0> for SessionV3_1_0 {
+    fn migrate(self) -> SessionV3_2_0 {
+        SessionV3_2_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: None, // Existing sessions have no manual sort order by default
+        }
+    }
+}
+
+/// Migration from V3.2.0 to V3.3.0
+/// Adds auto_chat_config field (default to None)
+impl MigratesTo<SessionV3_3_0> for SessionV3_2_0 {
+    fn migrate(self) -> SessionV3_3_0 {
+        SessionV3_3_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
             app_mode: self.app_mode,
-            workspace_id: None, // Existing sessions have no workspace association
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: None, // Existing sessions have AutoChat disabled by default
         }
     }
 }
 
-/// Migration from SessionV2_0_0 to SessionV2_1_0.
-/// Added active_participant_ids and execution_strategy fields.
-impl MigratesTo<SessionV2_1_0> for SessionV2_0_0 {
-    fn migrate(self) -> SessionV2_1_0 {
-        SessionV2_1_0 {
+/// Migration from SessionV3_3_0 to SessionV3_4_0.
+impl MigratesTo<SessionV3_4_0> for SessionV3_3_0 {
+    fn migrate(self) -> SessionV3_4_0 {
+        SessionV3_4_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1524,17 +3283,28 @@ impl MigratesTo<SessionV2_1_0> for SessionV2_0_0 {
             persona_histories: self.persona_histories,
             app_mode: self.app_mode,
             workspace_id: self.workspace_id,
-            active_participant_ids: Vec::new(), // No active participants in old sessions
-            execution_strategy: default_execution_strategy(), // Default to broadcast
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: HashMap::new(), // Will be populated on next participant add/remove
+            participant_models: HashMap::new(), // Will be populated on next participant add/remove
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
         }
     }
 }
 
-/// Migration from SessionV2_1_0 to SessionV2_2_0.
-/// Added system_messages field for system notifications.
-impl MigratesTo<SessionV2_2_0> for SessionV2_1_0 {
-    fn migrate(self) -> SessionV2_2_0 {
-        SessionV2_2_0 {
+/// Migration from SessionV3_4_0 to SessionV4_0_0.
+impl MigratesTo<SessionV4_0_0> for SessionV3_4_0 {
+    fn migrate(self) -> SessionV4_0_0 {
+        SessionV4_0_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1544,17 +3314,28 @@ impl MigratesTo<SessionV2_2_0> for SessionV2_1_0 {
             app_mode: self.app_mode,
             workspace_id: self.workspace_id,
             active_participant_ids: self.active_participant_ids,
-            execution_strategy: self.execution_strategy,
-            system_messages: Vec::new(), // No system messages in old sessions
+            execution_strategy: self.execution_strategy.migrate(),
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
         }
     }
 }
 
-/// Migration from SessionV2_2_0 to SessionV2_3_0.
-/// Added participants field for persona ID to name mapping.
-impl MigratesTo<SessionV2_3_0> for SessionV2_2_0 {
-    fn migrate(self) -> SessionV2_3_0 {
-        SessionV2_3_0 {
+/// Migration from SessionV4_0_0 to SessionV4_1_0.
+/// Filters out None values from participant_models to avoid TOML null errors.
+impl MigratesTo<SessionV4_1_0> for SessionV4_0_0 {
+    fn migrate(self) -> SessionV4_1_0 {
+        SessionV4_1_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1566,16 +3347,31 @@ impl MigratesTo<SessionV2_3_0> for SessionV2_2_0 {
             active_participant_ids: self.active_participant_ids,
             execution_strategy: self.execution_strategy,
             system_messages: self.system_messages,
-            participants: HashMap::new(), // Will be populated on save
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            // Filter out None values to avoid TOML null serialization errors
+            participant_models: self
+                .participant_models
+                .into_iter()
+                .filter_map(|(k, v)| v.map(|val| (k, val)))
+                .collect(),
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
         }
     }
 }
 
-/// Migration from SessionV2_3_0 to SessionV2_4_0.
-/// Added conversation_mode field for controlling multi-agent dialogue verbosity.
-impl MigratesTo<SessionV2_4_0> for SessionV2_3_0 {
-    fn migrate(self) -> SessionV2_4_0 {
-        SessionV2_4_0 {
+/// Migration from SessionV4_1_0 to SessionV4_2_0.
+/// Adds is_muted field for memo mode (default: false)
+impl MigratesTo<SessionV4_2_0> for SessionV4_1_0 {
+    fn migrate(self) -> SessionV4_2_0 {
+        SessionV4_2_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1588,16 +3384,26 @@ impl MigratesTo<SessionV2_4_0> for SessionV2_3_0 {
             execution_strategy: self.execution_strategy,
             system_messages: self.system_messages,
             participants: self.participants,
-            conversation_mode: ConversationMode::default(), // Default to Normal mode
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
+            is_muted: false, // Default to unmuted
         }
     }
 }
 
-/// Migration from SessionV2_4_0 to SessionV2_5_0.
-/// Added talk_style field for dialogue context.
-impl MigratesTo<SessionV2_5_0> for SessionV2_4_0 {
-    fn migrate(self) -> SessionV2_5_0 {
-        SessionV2_5_0 {
+/// Migration from SessionV4_2_0 to SessionV4_3_0.
+/// Adds context_mode field for AI context injection control (default: Rich)
+impl MigratesTo<SessionV4_3_0> for SessionV4_2_0 {
+    fn migrate(self) -> SessionV4_3_0 {
+        SessionV4_3_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1610,46 +3416,61 @@ impl MigratesTo<SessionV2_5_0> for SessionV2_4_0 {
             execution_strategy: self.execution_strategy,
             system_messages: self.system_messages,
             participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
-            talk_style: None, // Default to no talk style set
+            talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: ContextModeDto::default(), // Default to Rich
         }
     }
 }
 
-/// Migration from SessionV2_5_0 to SessionV2_6_0.
-/// Normalizes conversation metadata for UI reconstruction.
-impl MigratesTo<SessionV2_6_0> for SessionV2_5_0 {
-    fn migrate(self) -> SessionV2_6_0 {
-        SessionV2_6_0 {
+/// Migration from SessionV4_3_0 to SessionV4_4_0.
+/// Adds sandbox_state field for git worktree-based isolated development.
+impl MigratesTo<SessionV4_4_0> for SessionV4_3_0 {
+    fn migrate(self) -> SessionV4_4_0 {
+        SessionV4_4_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
             updated_at: self.updated_at,
             current_persona_id: self.current_persona_id,
-            persona_histories: self
-                .persona_histories
-                .into_iter()
-                .map(|(persona_id, messages)| {
-                    (persona_id, normalize_conversation_messages(messages))
-                })
-                .collect(),
+            persona_histories: self.persona_histories,
             app_mode: self.app_mode,
             workspace_id: self.workspace_id,
             active_participant_ids: self.active_participant_ids,
             execution_strategy: self.execution_strategy,
-            system_messages: normalize_conversation_messages(self.system_messages),
+            system_messages: self.system_messages,
             participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: None, // Existing sessions are not in sandbox mode
         }
     }
 }
 
-/// Migration from SessionV2_6_0 to SessionV2_7_0.
-/// Changes execution_strategy from String to ExecutionStrategyV2_0_0 DTO.
-impl MigratesTo<SessionV2_7_0> for SessionV2_6_0 {
-    fn migrate(self) -> SessionV2_7_0 {
-        SessionV2_7_0 {
+/// Migration from SessionV4_4_0 to SessionV4_5_0.
+/// Updates sandbox_state to use versioned SandboxStateV1_1_0.
+impl MigratesTo<SessionV4_5_0> for SessionV4_4_0 {
+    fn migrate(self) -> SessionV4_5_0 {
+        SessionV4_5_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1659,19 +3480,35 @@ impl MigratesTo<SessionV2_7_0> for SessionV2_6_0 {
             app_mode: self.app_mode,
             workspace_id: self.workspace_id,
             active_participant_ids: self.active_participant_ids,
-            execution_strategy: self.execution_strategy.into(),
+            execution_strategy: self.execution_strategy,
             system_messages: self.system_messages,
             participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            // Convert SandboxState to SandboxStateV1_0_0, then migrate to V1_1_0
+            sandbox_state: self.sandbox_state.map(|state| {
+                let v1_0_0: SandboxStateV1_0_0 = FromDomain::from_domain(state);
+                v1_0_0.migrate()
+            }),
         }
     }
 }
 
-/// Migration from SessionV2_7_0 to SessionV2_8_0.
-impl MigratesTo<SessionV2_8_0> for SessionV2_7_0 {
-    fn migrate(self) -> SessionV2_8_0 {
-        SessionV2_8_0 {
+/// Migration from SessionV4_5_0 to SessionV4_6_0.
+/// Adds last_memory_sync_at for differential memory sync with Kaiba RAG.
+impl MigratesTo<SessionV4_6_0> for SessionV4_5_0 {
+    fn migrate(self) -> SessionV4_6_0 {
+        SessionV4_6_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1684,18 +3521,65 @@ impl MigratesTo<SessionV2_8_0> for SessionV2_7_0 {
             execution_strategy: self.execution_strategy,
             system_messages: self.system_messages,
             participants: self.participants,
-            participant_icons: HashMap::new(), // V2_7_0 doesn't have icon field
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: None, // Default: no sync history
         }
     }
 }
 
-/// Migration from SessionV2_8_0 to SessionV2_9_0.
-/// Added participant_colors for UI theming
-impl MigratesTo<SessionV2_9_0> for SessionV2_8_0 {
-    fn migrate(self) -> SessionV2_9_0 {
-        SessionV2_9_0 {
+/// Migration from SessionV4_6_0 to SessionV4_7_0.
+/// Adds is_generating for background dialogue generation.
+impl MigratesTo<SessionV4_7_0> for SessionV4_6_0 {
+    fn migrate(self) -> SessionV4_7_0 {
+        SessionV4_7_0 {
+            id: self.id,
+            title: self.title,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            current_persona_id: self.current_persona_id,
+            persona_histories: self.persona_histories,
+            app_mode: self.app_mode,
+            workspace_id: self.workspace_id,
+            active_participant_ids: self.active_participant_ids,
+            execution_strategy: self.execution_strategy,
+            system_messages: self.system_messages,
+            participants: self.participants,
+            participant_icons: self.participant_icons,
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
+            conversation_mode: self.conversation_mode,
+            talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config.map(MigratesTo::migrate),
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            is_generating: false, // Default: not generating
+        }
+    }
+}
+
+/// Migration from SessionV4_7_0 to SessionV4_8_0.
+/// Adds style_calibration_enabled for persona style calibration.
+impl MigratesTo<SessionV4_8_0> for SessionV4_7_0 {
+    fn migrate(self) -> SessionV4_8_0 {
+        SessionV4_8_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1709,18 +3593,29 @@ impl MigratesTo<SessionV2_9_0> for SessionV2_8_0 {
             system_messages: self.system_messages,
             participants: self.participants,
             participant_icons: self.participant_icons,
-            participant_colors: HashMap::new(), // V2_8_0 doesn't have color field
+            participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: false, // Default: calibration is opt-in
         }
     }
 }
 
-/// Migration from V2.9.0 to V3.0.0
-/// Makes workspace_id required by setting placeholder if None
-impl MigratesTo<SessionV3_0_0> for SessionV2_9_0 {
-    fn migrate(self) -> SessionV3_0_0 {
-        SessionV3_0_0 {
+/// Migration from SessionV4_8_0 to SessionV4_9_0.
+/// Migrates auto_chat_config to AutoChatConfigV1_2_0 (default pacing).
+impl MigratesTo<SessionV4_9_0> for SessionV4_8_0 {
+    fn migrate(self) -> SessionV4_9_0 {
+        SessionV4_9_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1728,26 +3623,35 @@ impl MigratesTo<SessionV3_0_0> for SessionV2_9_0 {
             current_persona_id: self.current_persona_id,
             persona_histories: self.persona_histories,
             app_mode: self.app_mode,
-            workspace_id: self
-                .workspace_id
-                .unwrap_or_else(|| PLACEHOLDER_WORKSPACE_ID.to_string()),
+            workspace_id: self.workspace_id,
             active_participant_ids: self.active_participant_ids,
             execution_strategy: self.execution_strategy,
             system_messages: self.system_messages,
             participants: self.participants,
             participant_icons: self.participant_icons,
             participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config.map(MigratesTo::migrate),
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
         }
     }
 }
 
-/// Migration from V3.0.0 to V3.1.0
-/// Adds is_favorite and is_archived fields (default to false)
-impl MigratesTo<SessionV3_1_0> for SessionV3_0_0 {
-    fn migrate(self) -> SessionV3_1_0 {
-        SessionV3_1_0 {
+/// Migration from SessionV4_9_0 to SessionV4_10_0.
+/// Adds response_splitting_enabled (enabled by default).
+impl MigratesTo<SessionV4_10_0> for SessionV4_9_0 {
+    fn migrate(self) -> SessionV4_10_0 {
+        SessionV4_10_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1762,18 +3666,29 @@ impl MigratesTo<SessionV3_1_0> for SessionV3_0_0 {
             participants: self.participants,
             participant_icons: self.participant_icons,
             participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
-            is_favorite: false, // Existing sessions are not favorited by default
-            is_archived: false, // Existing sessions are not archived by default
+            is_favorite: self.is_favorite,
+            is_archived: self.is_archived,
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: true,
         }
     }
 }
 
-/// Migration from V3.1.0 to V3.2.0
-impl MigratesTo<SessionV3_2_0> for SessionV3_1_0 {
-    fn migrate(self) -> SessionV3_2_0 {
-        SessionV3_2_0 {
+/// Migration from SessionV4_10_0 to SessionV4_11_0.
+/// Adds an empty pinboard with agent edits disabled.
+impl MigratesTo<SessionV4_11_0> for SessionV4_10_0 {
+    fn migrate(self) -> SessionV4_11_0 {
+        SessionV4_11_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1788,20 +3703,31 @@ impl MigratesTo<SessionV3_2_0> for SessionV3_1_0 {
             participants: self.participants,
             participant_icons: self.participant_icons,
             participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
             is_favorite: self.is_favorite,
             is_archived: self.is_archived,
-            sort_order: None, // Existing sessions have no manual sort order by default
+            sort_order: self.sort_order,
+            auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
         }
     }
 }
 
-/// Migration from V3.2.0 to V3.3.0
-/// Adds auto_chat_config field (default to None)
-impl MigratesTo<SessionV3_3_0> for SessionV3_2_0 {
-    fn migrate(self) -> SessionV3_3_0 {
-        SessionV3_3_0 {
+/// Migration from SessionV4_11_0 to SessionV4_12_0.
+/// Keeps conversation-mode instructions on in Clean context mode (the new default).
+impl MigratesTo<SessionV4_12_0> for SessionV4_11_0 {
+    fn migrate(self) -> SessionV4_12_0 {
+        SessionV4_12_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1816,20 +3742,32 @@ impl MigratesTo<SessionV3_3_0> for SessionV3_2_0 {
             participants: self.participants,
             participant_icons: self.participant_icons,
             participant_colors: self.participant_colors,
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
             is_favorite: self.is_favorite,
             is_archived: self.is_archived,
             sort_order: self.sort_order,
-            auto_chat_config: None, // Existing sessions have AutoChat disabled by default
+            auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: true,
         }
     }
 }
 
-/// Migration from SessionV3_3_0 to SessionV3_4_0.
-impl MigratesTo<SessionV3_4_0> for SessionV3_3_0 {
-    fn migrate(self) -> SessionV3_4_0 {
-        SessionV3_4_0 {
+/// Migration from SessionV4_12_0 to SessionV4_13_0.
+/// Leaves participant suggestions off; they are opt-in.
+impl MigratesTo<SessionV4_13_0> for SessionV4_12_0 {
+    fn migrate(self) -> SessionV4_13_0 {
+        SessionV4_13_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1844,22 +3782,33 @@ impl MigratesTo<SessionV3_4_0> for SessionV3_3_0 {
             participants: self.participants,
             participant_icons: self.participant_icons,
             participant_colors: self.participant_colors,
-            participant_backends: HashMap::new(), // Will be populated on next participant add/remove
-            participant_models: HashMap::new(), // Will be populated on next participant add/remove
+            participant_backends: self.participant_backends,
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
             is_favorite: self.is_favorite,
             is_archived: self.is_archived,
             sort_order: self.sort_order,
             auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: self.apply_conversation_mode_in_clean,
+            participant_suggestions_enabled: false,
         }
     }
 }
 
-/// Migration from SessionV3_4_0 to SessionV4_0_0.
-impl MigratesTo<SessionV4_0_0> for SessionV3_4_0 {
-    fn migrate(self) -> SessionV4_0_0 {
-        SessionV4_0_0 {
+/// Migration from SessionV4_13_0 to SessionV4_14_0.
+/// No conversation script is running in older sessions.
+impl MigratesTo<SessionV4_14_0> for SessionV4_13_0 {
+    fn migrate(self) -> SessionV4_14_0 {
+        SessionV4_14_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1869,7 +3818,7 @@ impl MigratesTo<SessionV4_0_0> for SessionV3_4_0 {
             app_mode: self.app_mode,
             workspace_id: self.workspace_id,
             active_participant_ids: self.active_participant_ids,
-            execution_strategy: self.execution_strategy.migrate(),
+            execution_strategy: self.execution_strategy,
             system_messages: self.system_messages,
             participants: self.participants,
             participant_icons: self.participant_icons,
@@ -1882,15 +3831,26 @@ impl MigratesTo<SessionV4_0_0> for SessionV3_4_0 {
             is_archived: self.is_archived,
             sort_order: self.sort_order,
             auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: self.apply_conversation_mode_in_clean,
+            participant_suggestions_enabled: self.participant_suggestions_enabled,
+            script_run: None,
         }
     }
 }
 
-/// Migration from SessionV4_0_0 to SessionV4_1_0.
-/// Filters out None values from participant_models to avoid TOML null errors.
-impl MigratesTo<SessionV4_1_0> for SessionV4_0_0 {
-    fn migrate(self) -> SessionV4_1_0 {
-        SessionV4_1_0 {
+/// Migration from SessionV4_14_0 to SessionV4_15_0.
+/// Focus mode is off in older sessions.
+impl MigratesTo<SessionV4_15_0> for SessionV4_14_0 {
+    fn migrate(self) -> SessionV4_15_0 {
+        SessionV4_15_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1906,27 +3866,34 @@ impl MigratesTo<SessionV4_1_0> for SessionV4_0_0 {
             participant_icons: self.participant_icons,
             participant_colors: self.participant_colors,
             participant_backends: self.participant_backends,
-            // Filter out None values to avoid TOML null serialization errors
-            participant_models: self
-                .participant_models
-                .into_iter()
-                .filter_map(|(k, v)| v.map(|val| (k, val)))
-                .collect(),
+            participant_models: self.participant_models,
             conversation_mode: self.conversation_mode,
             talk_style: self.talk_style,
             is_favorite: self.is_favorite,
             is_archived: self.is_archived,
             sort_order: self.sort_order,
             auto_chat_config: self.auto_chat_config,
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: self.apply_conversation_mode_in_clean,
+            participant_suggestions_enabled: self.participant_suggestions_enabled,
+            script_run: self.script_run,
+            focused_participant_ids: None,
         }
     }
 }
 
-/// Migration from SessionV4_1_0 to SessionV4_2_0.
-/// Adds is_muted field for memo mode (default: false)
-impl MigratesTo<SessionV4_2_0> for SessionV4_1_0 {
-    fn migrate(self) -> SessionV4_2_0 {
-        SessionV4_2_0 {
+/// Migration from SessionV4_15_0 to SessionV4_16_0.
+/// Older sessions are unseeded.
+impl MigratesTo<SessionV4_16_0> for SessionV4_15_0 {
+    fn migrate(self) -> SessionV4_16_0 {
+        SessionV4_16_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1949,16 +3916,28 @@ impl MigratesTo<SessionV4_2_0> for SessionV4_1_0 {
             is_archived: self.is_archived,
             sort_order: self.sort_order,
             auto_chat_config: self.auto_chat_config,
-            is_muted: false, // Default to unmuted
+            is_muted: self.is_muted,
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: self.apply_conversation_mode_in_clean,
+            participant_suggestions_enabled: self.participant_suggestions_enabled,
+            script_run: self.script_run,
+            focused_participant_ids: self.focused_participant_ids,
+            random_seed: None,
         }
     }
 }
 
-/// Migration from SessionV4_2_0 to SessionV4_3_0.
-/// Adds context_mode field for AI context injection control (default: Rich)
-impl MigratesTo<SessionV4_3_0> for SessionV4_2_0 {
-    fn migrate(self) -> SessionV4_3_0 {
-        SessionV4_3_0 {
+/// Migration from SessionV4_16_0 to SessionV4_17_0.
+/// Older sessions get the clock context, like new ones.
+impl MigratesTo<SessionV4_17_0> for SessionV4_16_0 {
+    fn migrate(self) -> SessionV4_17_0 {
+        SessionV4_17_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -1982,16 +3961,28 @@ impl MigratesTo<SessionV4_3_0> for SessionV4_2_0 {
             sort_order: self.sort_order,
             auto_chat_config: self.auto_chat_config,
             is_muted: self.is_muted,
-            context_mode: ContextModeDto::default(), // Default to Rich
+            context_mode: self.context_mode,
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: self.apply_conversation_mode_in_clean,
+            participant_suggestions_enabled: self.participant_suggestions_enabled,
+            script_run: self.script_run,
+            focused_participant_ids: self.focused_participant_ids,
+            random_seed: self.random_seed,
+            clock_context_enabled: true,
         }
     }
 }
 
-/// Migration from SessionV4_3_0 to SessionV4_4_0.
-/// Adds sandbox_state field for git worktree-based isolated development.
-impl MigratesTo<SessionV4_4_0> for SessionV4_3_0 {
-    fn migrate(self) -> SessionV4_4_0 {
-        SessionV4_4_0 {
+/// Migration from SessionV4_17_0 to SessionV4_18_0.
+/// Plan detection stays off for older sessions.
+impl MigratesTo<SessionV4_18_0> for SessionV4_17_0 {
+    fn migrate(self) -> SessionV4_18_0 {
+        SessionV4_18_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -2016,16 +4007,28 @@ impl MigratesTo<SessionV4_4_0> for SessionV4_3_0 {
             auto_chat_config: self.auto_chat_config,
             is_muted: self.is_muted,
             context_mode: self.context_mode,
-            sandbox_state: None, // Existing sessions are not in sandbox mode
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: self.apply_conversation_mode_in_clean,
+            participant_suggestions_enabled: self.participant_suggestions_enabled,
+            script_run: self.script_run,
+            focused_participant_ids: self.focused_participant_ids,
+            random_seed: self.random_seed,
+            clock_context_enabled: self.clock_context_enabled,
+            plan_detection_enabled: false,
         }
     }
 }
 
-/// Migration from SessionV4_4_0 to SessionV4_5_0.
-/// Updates sandbox_state to use versioned SandboxStateV1_1_0.
-impl MigratesTo<SessionV4_5_0> for SessionV4_4_0 {
-    fn migrate(self) -> SessionV4_5_0 {
-        SessionV4_5_0 {
+/// Migration from SessionV4_18_0 to SessionV4_19_0.
+/// Turns paste-to-attachment on with the default threshold.
+impl MigratesTo<SessionV4_19_0> for SessionV4_18_0 {
+    fn migrate(self) -> SessionV4_19_0 {
+        SessionV4_19_0 {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -2050,20 +4053,33 @@ impl MigratesTo<SessionV4_5_0> for SessionV4_4_0 {
             auto_chat_config: self.auto_chat_config,
             is_muted: self.is_muted,
             context_mode: self.context_mode,
-            // Convert SandboxState to SandboxStateV1_0_0, then migrate to V1_1_0
-            sandbox_state: self.sandbox_state.map(|state| {
-                let v1_0_0: SandboxStateV1_0_0 = FromDomain::from_domain(state);
-                v1_0_0.migrate()
-            }),
+            sandbox_state: self.sandbox_state,
+            last_memory_sync_at: self.last_memory_sync_at,
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: self.apply_conversation_mode_in_clean,
+            participant_suggestions_enabled: self.participant_suggestions_enabled,
+            script_run: self.script_run,
+            focused_participant_ids: self.focused_participant_ids,
+            random_seed: self.random_seed,
+            clock_context_enabled: self.clock_context_enabled,
+            plan_detection_enabled: self.plan_detection_enabled,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: DEFAULT_PASTE_ATTACHMENT_THRESHOLD,
         }
     }
 }
 
-/// Migration from SessionV4_5_0 to SessionV4_6_0.
-/// Adds last_memory_sync_at for differential memory sync with Kaiba RAG.
-impl MigratesTo<SessionV4_6_0> for SessionV4_5_0 {
-    fn migrate(self) -> SessionV4_6_0 {
-        SessionV4_6_0 {
+// ============================================================================
+// Domain model conversions
+// ============================================================================
+
+/// Convert SessionV4_19_0 DTO to domain model.
+impl IntoDomain<Session> for SessionV4_19_0 {
+    fn into_domain(self) -> Session {
+        let mut session = Session {
             id: self.id,
             title: self.title,
             created_at: self.created_at,
@@ -2073,32 +4089,146 @@ impl MigratesTo<SessionV4_6_0> for SessionV4_5_0 {
             app_mode: self.app_mode,
             workspace_id: self.workspace_id,
             active_participant_ids: self.active_participant_ids,
-            execution_strategy: self.execution_strategy,
+            execution_strategy: self.execution_strategy.into_domain(), // DTO → Domain
             system_messages: self.system_messages,
             participants: self.participants,
             participant_icons: self.participant_icons,
             participant_colors: self.participant_colors,
             participant_backends: self.participant_backends,
-            participant_models: self.participant_models,
-            conversation_mode: self.conversation_mode,
+            // Convert HashMap<String, String> to HashMap<String, Option<String>>
+            participant_models: self
+                .participant_models
+                .into_iter()
+                .map(|(k, v)| (k, Some(v)))
+                .collect(),
+            conversation_mode: self.conversation_mode, // DTO → Domain
             talk_style: self.talk_style,
             is_favorite: self.is_favorite,
             is_archived: self.is_archived,
             sort_order: self.sort_order,
-            auto_chat_config: self.auto_chat_config,
+            auto_chat_config: self.auto_chat_config.map(|c| c.into_domain()), // DTO → Domain
             is_muted: self.is_muted,
-            context_mode: self.context_mode,
-            sandbox_state: self.sandbox_state,
-            last_memory_sync_at: None, // Default: no sync history
-        }
+            context_mode: self.context_mode.into(), // DTO → Domain
+            sandbox_state: self.sandbox_state.map(|s| s.into_domain()), // DTO → Domain
+            last_memory_sync_at: self.last_memory_sync_at,
+            is_generating: false, // Runtime-only, not persisted
+            style_calibration_enabled: self.style_calibration_enabled,
+            response_splitting_enabled: self.response_splitting_enabled,
+            pinboard: self.pinboard,
+            pinboard_agent_edits_enabled: self.pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean: self.apply_conversation_mode_in_clean,
+            participant_suggestions_enabled: self.participant_suggestions_enabled,
+            script_run: self.script_run.map(|r| r.into_domain()), // DTO → Domain
+            focused_participant_ids: self.focused_participant_ids,
+            random_seed: self.random_seed,
+            clock_context_enabled: self.clock_context_enabled,
+            plan_detection_enabled: self.plan_detection_enabled,
+            paste_attachment_enabled: self.paste_attachment_enabled,
+            paste_attachment_threshold: self.paste_attachment_threshold,
+        };
+        // Sessions saved before errors moved to system_messages
+        session.relocate_legacy_error_history();
+        session
     }
 }
 
-// ============================================================================
-// Domain model conversions
-// ============================================================================
+/// Convert domain model to SessionV4_19_0 DTO for persistence.
+impl FromDomain<Session> for SessionV4_19_0 {
+    fn from_domain(session: Session) -> Self {
+        let Session {
+            id,
+            title,
+            created_at,
+            updated_at,
+            current_persona_id,
+            persona_histories,
+            app_mode,
+            workspace_id,
+            active_participant_ids,
+            execution_strategy,
+            system_messages,
+            participants,
+            participant_icons,
+            participant_colors,
+            participant_backends,
+            participant_models,
+            conversation_mode,
+            talk_style,
+            is_favorite,
+            is_archived,
+            sort_order,
+            auto_chat_config,
+            is_muted,
+            context_mode,
+            sandbox_state,
+            last_memory_sync_at,
+            is_generating: _,
+            style_calibration_enabled,
+            response_splitting_enabled,
+            pinboard,
+            pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean,
+            participant_suggestions_enabled,
+            script_run,
+            focused_participant_ids,
+            random_seed,
+            clock_context_enabled,
+            plan_detection_enabled,
+            paste_attachment_enabled,
+            paste_attachment_threshold,
+        } = session;
+
+        // Convert HashMap<String, Option<String>> to HashMap<String, String>
+        let participant_models: HashMap<String, String> = participant_models
+            .into_iter()
+            .filter_map(|(k, v)| v.map(|model| (k, model)))
+            .collect();
+
+        SessionV4_19_0 {
+            id,
+            title,
+            created_at,
+            updated_at,
+            current_persona_id,
+            persona_histories,
+            app_mode,
+            workspace_id,
+            active_participant_ids,
+            execution_strategy: ExecutionStrategyV2_0_0::from_domain(execution_strategy), // Domain → DTO
+            system_messages,
+            participants,
+            participant_icons,
+            participant_colors,
+            participant_backends,
+            participant_models,
+            conversation_mode, // Domain → DTO
+            talk_style,
+            is_favorite,
+            is_archived,
+            sort_order,
+            auto_chat_config: auto_chat_config.map(AutoChatConfigV1_2_0::from_domain), // Domain → DTO
+            is_muted,
+            context_mode: context_mode.into(), // Domain → DTO
+            sandbox_state: sandbox_state.map(SandboxStateV1_1_0::from_domain), // Domain → DTO
+            last_memory_sync_at,
+            style_calibration_enabled,
+            response_splitting_enabled,
+            pinboard,
+            pinboard_agent_edits_enabled,
+            apply_conversation_mode_in_clean,
+            participant_suggestions_enabled,
+            script_run: script_run.map(ScriptRunV1_0_0::from_domain), // Domain → DTO
+            focused_participant_ids,
+            random_seed,
+            clock_context_enabled,
+            plan_detection_enabled,
+            paste_attachment_enabled,
+            paste_attachment_threshold,
+        }
+    }
+}
 
-/// Convert SessionV4_6_0 DTO to domain model.
+/// Convert SessionV4_6_0 DTO to domain model (deprecated, use V4_7_0).
 impl IntoDomain<Session> for SessionV4_6_0 {
     fn into_domain(self) -> Session {
         Session {
@@ -2128,16 +4258,30 @@ impl IntoDomain<Session> for SessionV4_6_0 {
             is_favorite: self.is_favorite,
             is_archived: self.is_archived,
             sort_order: self.sort_order,
-            auto_chat_config: self.auto_chat_config,
+            auto_chat_config: self.auto_chat_config.map(|c| c.into_domain()), // DTO → Domain
             is_muted: self.is_muted,
             context_mode: self.context_mode.into(), // DTO → Domain
             sandbox_state: self.sandbox_state.map(|s| s.into_domain()), // DTO → Domain
             last_memory_sync_at: self.last_memory_sync_at,
+            is_generating: false, // V4_6_0 doesn't have is_generating
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: DEFAULT_PASTE_ATTACHMENT_THRESHOLD,
         }
     }
 }
 
-/// Convert domain model to SessionV4_6_0 DTO for persistence.
+/// Convert domain model to SessionV4_6_0 DTO for persistence (deprecated, use V4_7_0).
 impl FromDomain<Session> for SessionV4_6_0 {
     fn from_domain(session: Session) -> Self {
         let Session {
@@ -2167,6 +4311,20 @@ impl FromDomain<Session> for SessionV4_6_0 {
             context_mode,
             sandbox_state,
             last_memory_sync_at,
+            is_generating: _, // V4_6_0 doesn't persist is_generating
+            style_calibration_enabled: _,
+            response_splitting_enabled: _,
+            pinboard: _,
+            pinboard_agent_edits_enabled: _,
+            apply_conversation_mode_in_clean: _,
+            participant_suggestions_enabled: _,
+            script_run: _,
+            focused_participant_ids: _,
+            random_seed: _,
+            clock_context_enabled: _,
+            plan_detection_enabled: _,
+            paste_attachment_enabled: _,
+            paste_attachment_threshold: _,
         } = session;
 
         // Convert HashMap<String, Option<String>> to HashMap<String, String>
@@ -2197,7 +4355,7 @@ impl FromDomain<Session> for SessionV4_6_0 {
             is_favorite,
             is_archived,
             sort_order,
-            auto_chat_config,
+            auto_chat_config: auto_chat_config.map(AutoChatConfigV1_0_0::from_domain), // Domain → DTO
             is_muted,
             context_mode: context_mode.into(), // Domain → DTO
             sandbox_state: sandbox_state.map(SandboxStateV1_1_0::from_domain), // Domain → DTO
@@ -2236,11 +4394,25 @@ impl IntoDomain<Session> for SessionV4_4_0 {
             is_favorite: self.is_favorite,
             is_archived: self.is_archived,
             sort_order: self.sort_order,
-            auto_chat_config: self.auto_chat_config,
+            auto_chat_config: self.auto_chat_config.map(|c| c.into_domain()), // DTO → Domain
             is_muted: self.is_muted,
             context_mode: self.context_mode.into(), // DTO → Domain
             sandbox_state: self.sandbox_state,      // Direct mapping
             last_memory_sync_at: None,              // V4_4_0 doesn't have last_memory_sync_at
+            is_generating: false,                   // V4_4_0 doesn't have is_generating
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: DEFAULT_PASTE_ATTACHMENT_THRESHOLD,
         }
     }
 }
@@ -2275,11 +4447,25 @@ impl IntoDomain<Session> for SessionV4_3_0 {
             is_favorite: self.is_favorite,
             is_archived: self.is_archived,
             sort_order: self.sort_order,
-            auto_chat_config: self.auto_chat_config,
+            auto_chat_config: self.auto_chat_config.map(|c| c.into_domain()), // DTO → Domain
             is_muted: self.is_muted,
             context_mode: self.context_mode.into(), // DTO → Domain
             sandbox_state: None,                    // V4_3_0 doesn't have sandbox_state
             last_memory_sync_at: None,              // V4_3_0 doesn't have last_memory_sync_at
+            is_generating: false,                   // V4_3_0 doesn't have is_generating
+            style_calibration_enabled: false,
+            response_splitting_enabled: true,
+            pinboard: None,
+            pinboard_agent_edits_enabled: false,
+            apply_conversation_mode_in_clean: true,
+            participant_suggestions_enabled: false,
+            script_run: None,
+            focused_participant_ids: None,
+            random_seed: None,
+            clock_context_enabled: true,
+            plan_detection_enabled: false,
+            paste_attachment_enabled: true,
+            paste_attachment_threshold: DEFAULT_PASTE_ATTACHMENT_THRESHOLD,
         }
     }
 }
@@ -2314,6 +4500,20 @@ impl version_migrate::FromDomain<Session> for SessionV4_3_0 {
             context_mode,
             sandbox_state: _,       // V4_3_0 doesn't persist sandbox_state
             last_memory_sync_at: _, // V4_3_0 doesn't persist last_memory_sync_at
+            is_generating: _,       // V4_3_0 doesn't persist is_generating
+            style_calibration_enabled: _,
+            response_splitting_enabled: _,
+            pinboard: _,
+            pinboard_agent_edits_enabled: _,
+            apply_conversation_mode_in_clean: _,
+            participant_suggestions_enabled: _,
+            script_run: _,
+            focused_participant_ids: _,
+            random_seed: _,
+            clock_context_enabled: _,
+            plan_detection_enabled: _,
+            paste_attachment_enabled: _,
+            paste_attachment_threshold: _,
         } = session;
 
         SessionV4_3_0 {
@@ -2342,7 +4542,7 @@ impl version_migrate::FromDomain<Session> for SessionV4_3_0 {
             is_favorite,
             is_archived,
             sort_order,
-            auto_chat_config,
+            auto_chat_config: auto_chat_config.map(AutoChatConfigV1_0_0::from_domain), // Domain → DTO
             is_muted,
             context_mode: context_mode.into(), // Domain → DTO
         }
@@ -2379,6 +4579,20 @@ impl version_migrate::FromDomain<Session> for SessionV4_4_0 {
             context_mode,
             sandbox_state,
             last_memory_sync_at: _, // V4_4_0 doesn't persist last_memory_sync_at
+            is_generating: _,       // V4_4_0 doesn't persist is_generating
+            style_calibration_enabled: _,
+            response_splitting_enabled: _,
+            pinboard: _,
+            pinboard_agent_edits_enabled: _,
+            apply_conversation_mode_in_clean: _,
+            participant_suggestions_enabled: _,
+            script_run: _,
+            focused_participant_ids: _,
+            random_seed: _,
+            clock_context_enabled: _,
+            plan_detection_enabled: _,
+            paste_attachment_enabled: _,
+            paste_attachment_threshold: _,
         } = session;
 
         // Convert HashMap<String, Option<String>> to HashMap<String, String>
@@ -2409,7 +4623,7 @@ impl version_migrate::FromDomain<Session> for SessionV4_4_0 {
             is_favorite,
             is_archived,
             sort_order,
-            auto_chat_config,
+            auto_chat_config: auto_chat_config.map(AutoChatConfigV1_0_0::from_domain), // Domain → DTO
             is_muted,
             context_mode: context_mode.into(), // Domain → DTO
             sandbox_state,                     // Direct mapping
@@ -2424,12 +4638,12 @@ impl version_migrate::FromDomain<Session> for SessionV4_4_0 {
 /// Creates and configures a Migrator instance for Session entities.
 ///
 /// Uses the `migrator!` macro for simplified migration path definition.
-/// The migrator handles automatic schema migration from V1.0.0 to V4.6.0
+/// The migrator handles automatic schema migration from V1.0.0 to V4.19.0
 /// and conversion to the domain model with save support.
 ///
 /// # Migration Path
 ///
-/// V1.0.0 → V1.1.0 → V2.0.0 → ... → V4.5.0 → V4.6.0 → Session
+/// V1.0.0 → V1.1.0 → V2.0.0 → ... → V4.18.0 → V4.19.0 → Session
 ///
 /// See individual DTO version structs for detailed migration documentation.
 ///
@@ -2465,6 +4679,19 @@ pub fn create_session_migrator() -> version_migrate::Migrator {
         SessionV4_4_0,
         SessionV4_5_0,
         SessionV4_6_0,
+        SessionV4_7_0,
+        SessionV4_8_0,
+        SessionV4_9_0,
+        SessionV4_10_0,
+        SessionV4_11_0,
+        SessionV4_12_0,
+        SessionV4_13_0,
+        SessionV4_14_0,
+        SessionV4_15_0,
+        SessionV4_16_0,
+        SessionV4_17_0,
+        SessionV4_18_0,
+        SessionV4_19_0,
         Session
     ], save = true)
     .expect("Failed to create session migrator")
@@ -285,8 +285,63 @@ pub struct SlashCommandV1_6 {
     pub sort_order: Option<u32>,
 }
 
-/// Convert SlashCommandV1_6 DTO to domain model
-impl IntoDomain<SlashCommand> for SlashCommandV1_6 {
+/// Migration from SlashCommandV1_6 to SlashCommandV1_7.
+/// Adds requires_confirmation field (defaults to false for existing commands).
+impl MigratesTo<SlashCommandV1_7> for SlashCommandV1_6 {
+    fn migrate(self) -> SlashCommandV1_7 {
+        SlashCommandV1_7 {
+            name: self.name,
+            icon: self.icon,
+            description: self.description,
+            command_type: self.command_type,
+            content: self.content,
+            working_dir: self.working_dir,
+            args_description: self.args_description,
+            task_blueprint: self.task_blueprint,
+            action_config: self.action_config,
+            pipeline_config: self.pipeline_config,
+            include_in_system_prompt: self.include_in_system_prompt,
+            is_favorite: self.is_favorite,
+            sort_order: self.sort_order,
+            requires_confirmation: false,
+        }
+    }
+}
+
+/// Slash command DTO V1.7.0 (adds requires_confirmation)
+/// Gates agent-triggered executions behind user approval.
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.7.0")]
+pub struct SlashCommandV1_7 {
+    pub name: String,
+    pub icon: String,
+    pub description: String,
+    #[serde(rename = "type")]
+    pub command_type: CommandType,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_blueprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_config: Option<ActionConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline_config: Option<PipelineConfig>,
+    #[serde(default = "default_include_in_system_prompt")]
+    pub include_in_system_prompt: bool,
+    #[serde(default)]
+    pub is_favorite: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<u32>,
+    /// Whether agent-triggered executions require user approval.
+    #[serde(default)]
+    pub requires_confirmation: bool,
+}
+
+/// Convert SlashCommandV1_7 DTO to domain model
+impl IntoDomain<SlashCommand> for SlashCommandV1_7 {
     fn into_domain(self) -> SlashCommand {
         SlashCommand {
             name: self.name,
@@ -302,14 +357,15 @@ impl IntoDomain<SlashCommand> for SlashCommandV1_6 {
             include_in_system_prompt: self.include_in_system_prompt,
             is_favorite: self.is_favorite,
             sort_order: self.sort_order,
+            requires_confirmation: self.requires_confirmation,
         }
     }
 }
 
-/// Convert domain model to SlashCommandV1_6 DTO for persistence
-impl From<&SlashCommand> for SlashCommandV1_6 {
+/// Convert domain model to SlashCommandV1_7 DTO for persistence
+impl From<&SlashCommand> for SlashCommandV1_7 {
     fn from(cmd: &SlashCommand) -> Self {
-        SlashCommandV1_6 {
+        SlashCommandV1_7 {
             name: cmd.name.clone(),
             icon: cmd.icon.clone(),
             description: cmd.description.clone(),
@@ -323,14 +379,15 @@ impl From<&SlashCommand> for SlashCommandV1_6 {
             include_in_system_prompt: cmd.include_in_system_prompt,
             is_favorite: cmd.is_favorite,
             sort_order: cmd.sort_order,
+            requires_confirmation: cmd.requires_confirmation,
         }
     }
 }
 
-/// Convert domain model to SlashCommandV1_6 DTO (for version-migrate save support)
-impl FromDomain<SlashCommand> for SlashCommandV1_6 {
+/// Convert domain model to SlashCommandV1_7 DTO (for version-migrate save support)
+impl FromDomain<SlashCommand> for SlashCommandV1_7 {
     fn from_domain(cmd: SlashCommand) -> Self {
-        SlashCommandV1_6 {
+        SlashCommandV1_7 {
             name: cmd.name,
             icon: cmd.icon,
             description: cmd.description,
@@ -344,6 +401,7 @@ impl FromDomain<SlashCommand> for SlashCommandV1_6 {
             include_in_system_prompt: cmd.include_in_system_prompt,
             is_favorite: cmd.is_favorite,
             sort_order: cmd.sort_order,
+            requires_confirmation: cmd.requires_confirmation,
         }
     }
 }
@@ -362,6 +420,7 @@ pub fn create_slash_command_migrator() -> version_migrate::Migrator {
         SlashCommandV1_4,
         SlashCommandV1_5,
         SlashCommandV1_6,
+        SlashCommandV1_7,
         SlashCommand
     ], save = true)
     .expect("Failed to create slash_command migrator")
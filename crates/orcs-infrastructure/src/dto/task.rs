@@ -229,6 +229,113 @@ pub struct TaskV1_1_0 {
     pub journal_log: Option<String>,
 }
 
+/// V1.2.0: Added the originating conversation message (timestamp and author).
+///
+/// Like `TaskV1_1_0`, this DTO stays **snake_case** for disk persistence.
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.2.0")]
+pub struct TaskV1_2_0 {
+    /// Unique task identifier (UUID format).
+    pub id: String,
+    /// Session ID where this task was executed.
+    pub session_id: String,
+    /// Task title.
+    pub title: String,
+    /// Full task description/request.
+    pub description: String,
+    /// Current task status.
+    pub status: TaskStatusDTO,
+    /// Timestamp when task was created (ISO 8601 format).
+    pub created_at: String,
+    /// Timestamp when task was last updated (ISO 8601 format).
+    pub updated_at: String,
+    /// Timestamp when task completed (ISO 8601 format).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+    /// Number of steps executed.
+    pub steps_executed: i32,
+    /// Number of steps skipped.
+    pub steps_skipped: i32,
+    /// Number of context keys generated.
+    pub context_keys: i32,
+    /// Error message if task failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Result summary text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Detailed execution information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_details: Option<ExecutionDetailsDTO>,
+    /// Execution strategy (JSON string from ParallelOrchestrator).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    /// Journal log (execution trace from ParallelOrchestrator).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal_log: Option<String>,
+    /// Timestamp of the conversation message this task was spawned from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_message_timestamp: Option<String>,
+    /// Author of the originating message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_author: Option<String>,
+}
+
+/// V1.3.0: Added the dry-run flag.
+///
+/// Like `TaskV1_1_0`, this DTO stays **snake_case** for disk persistence.
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.3.0")]
+pub struct TaskV1_3_0 {
+    /// Unique task identifier (UUID format).
+    pub id: String,
+    /// Session ID where this task was executed.
+    pub session_id: String,
+    /// Task title.
+    pub title: String,
+    /// Full task description/request.
+    pub description: String,
+    /// Current task status.
+    pub status: TaskStatusDTO,
+    /// Timestamp when task was created (ISO 8601 format).
+    pub created_at: String,
+    /// Timestamp when task was last updated (ISO 8601 format).
+    pub updated_at: String,
+    /// Timestamp when task completed (ISO 8601 format).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+    /// Number of steps executed.
+    pub steps_executed: i32,
+    /// Number of steps skipped.
+    pub steps_skipped: i32,
+    /// Number of context keys generated.
+    pub context_keys: i32,
+    /// Error message if task failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Result summary text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Detailed execution information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_details: Option<ExecutionDetailsDTO>,
+    /// Execution strategy (JSON string from ParallelOrchestrator).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    /// Journal log (execution trace from ParallelOrchestrator).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal_log: Option<String>,
+    /// Timestamp of the conversation message this task was spawned from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_message_timestamp: Option<String>,
+    /// Author of the originating message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_author: Option<String>,
+    /// Whether this was a dry run.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 // ============================================================================
 // Schema Migrations
 // ============================================================================
@@ -258,6 +365,61 @@ impl MigratesTo<TaskV1_1_0> for TaskV1_0_0 {
     }
 }
 
+/// Migration from TaskV1_1_0 to TaskV1_2_0.
+/// Added origin message fields (defaults to None for existing tasks).
+impl MigratesTo<TaskV1_2_0> for TaskV1_1_0 {
+    fn migrate(self) -> TaskV1_2_0 {
+        TaskV1_2_0 {
+            id: self.id,
+            session_id: self.session_id,
+            title: self.title,
+            description: self.description,
+            status: self.status,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            completed_at: self.completed_at,
+            steps_executed: self.steps_executed,
+            steps_skipped: self.steps_skipped,
+            context_keys: self.context_keys,
+            error: self.error,
+            result: self.result,
+            execution_details: self.execution_details,
+            strategy: self.strategy,
+            journal_log: self.journal_log,
+            origin_message_timestamp: None,
+            origin_author: None,
+        }
+    }
+}
+
+/// Migration from TaskV1_2_0 to TaskV1_3_0.
+/// Added the dry-run flag (existing tasks ran for real).
+impl MigratesTo<TaskV1_3_0> for TaskV1_2_0 {
+    fn migrate(self) -> TaskV1_3_0 {
+        TaskV1_3_0 {
+            id: self.id,
+            session_id: self.session_id,
+            title: self.title,
+            description: self.description,
+            status: self.status,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            completed_at: self.completed_at,
+            steps_executed: self.steps_executed,
+            steps_skipped: self.steps_skipped,
+            context_keys: self.context_keys,
+            error: self.error,
+            result: self.result,
+            execution_details: self.execution_details,
+            strategy: self.strategy,
+            journal_log: self.journal_log,
+            origin_message_timestamp: self.origin_message_timestamp,
+            origin_author: self.origin_author,
+            dry_run: false,
+        }
+    }
+}
+
 // ============================================================================
 // Domain model conversions
 // ============================================================================
@@ -268,8 +430,8 @@ fn generate_uuid_from_task(title: &str, timestamp: &str) -> String {
     Uuid::new_v5(&Uuid::NAMESPACE_OID, combined.as_bytes()).to_string()
 }
 
-/// Convert TaskV1_1_0 DTO to domain model.
-impl IntoDomain<Task> for TaskV1_1_0 {
+/// Convert TaskV1_3_0 DTO to domain model.
+impl IntoDomain<Task> for TaskV1_3_0 {
     fn into_domain(self) -> Task {
         // Validate and fix ID if needed
         let id = if Uuid::parse_str(&self.id).is_ok() {
@@ -296,14 +458,17 @@ impl IntoDomain<Task> for TaskV1_1_0 {
             execution_details: self.execution_details.map(Into::into),
             strategy: self.strategy,
             journal_log: self.journal_log,
+            origin_message_timestamp: self.origin_message_timestamp,
+            origin_author: self.origin_author,
+            dry_run: self.dry_run,
         }
     }
 }
 
-/// Convert domain model to TaskV1_1_0 DTO for persistence.
-impl version_migrate::FromDomain<Task> for TaskV1_1_0 {
+/// Convert domain model to TaskV1_3_0 DTO for persistence.
+impl version_migrate::FromDomain<Task> for TaskV1_3_0 {
     fn from_domain(task: Task) -> Self {
-        TaskV1_1_0 {
+        TaskV1_3_0 {
             id: task.id,
             session_id: task.session_id,
             title: task.title,
@@ -320,6 +485,9 @@ impl version_migrate::FromDomain<Task> for TaskV1_1_0 {
             execution_details: task.execution_details.map(Into::into),
             strategy: task.strategy,
             journal_log: task.journal_log,
+            origin_message_timestamp: task.origin_message_timestamp,
+            origin_author: task.origin_author,
+            dry_run: task.dry_run,
         }
     }
 }
@@ -335,7 +503,9 @@ impl version_migrate::FromDomain<Task> for TaskV1_1_0 {
 /// # Migration Path
 ///
 /// - V1.0.0 → V1.1.0: Adds strategy and journal_log fields
-/// - V1.1.0 → Task: Converts DTO to domain model
+/// - V1.1.0 → V1.2.0: Adds origin message timestamp and author
+/// - V1.2.0 → V1.3.0: Adds the dry-run flag
+/// - V1.3.0 → Task: Converts DTO to domain model
 ///
 /// # Example
 ///
@@ -347,6 +517,8 @@ pub fn create_task_migrator() -> version_migrate::Migrator {
     version_migrate::migrator!("task" => [
         TaskV1_0_0,
         TaskV1_1_0,
+        TaskV1_2_0,
+        TaskV1_3_0,
         Task
     ], save = true)
     .expect("Failed to create task migrator")
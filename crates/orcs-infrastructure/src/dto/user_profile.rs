@@ -25,14 +25,31 @@ pub struct UserProfileV1_1 {
     pub background: String,
 }
 
+/// User profile configuration V1.2.0 (added timezone field).
+#[derive(Debug, Clone, Serialize, Deserialize, Versioned)]
+#[versioned(version = "1.2.0")]
+pub struct UserProfileV1_2 {
+    /// User's display nickname.
+    pub nickname: String,
+
+    /// User's background or bio.
+    #[serde(default)]
+    pub background: String,
+
+    /// UTC offset agents see the current time in (None: the OS timezone).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
 /// Type alias for the latest UserProfile version.
-pub type UserProfileDTO = UserProfileV1_1;
+pub type UserProfileDTO = UserProfileV1_2;
 
-impl Default for UserProfileV1_1 {
+impl Default for UserProfileV1_2 {
     fn default() -> Self {
         Self {
             nickname: "You".to_string(),
             background: String::new(),
+            timezone: None,
         }
     }
 }
@@ -51,26 +68,39 @@ impl MigratesTo<UserProfileV1_1> for UserProfileV1_0 {
     }
 }
 
+/// Migration from UserProfileV1_1 to UserProfileV1_2.
+impl MigratesTo<UserProfileV1_2> for UserProfileV1_1 {
+    fn migrate(self) -> UserProfileV1_2 {
+        UserProfileV1_2 {
+            nickname: self.nickname,
+            background: self.background,
+            timezone: None,
+        }
+    }
+}
+
 // ============================================================================
 // Domain model conversions
 // ============================================================================
 
-/// Convert UserProfileV1_1 DTO to domain model.
-impl IntoDomain<UserProfile> for UserProfileV1_1 {
+/// Convert UserProfileV1_2 DTO to domain model.
+impl IntoDomain<UserProfile> for UserProfileV1_2 {
     fn into_domain(self) -> UserProfile {
         UserProfile {
             nickname: self.nickname,
             background: self.background,
+            timezone: self.timezone,
         }
     }
 }
 
-/// Convert domain model to UserProfileV1_1 DTO for persistence.
-impl version_migrate::FromDomain<UserProfile> for UserProfileV1_1 {
+/// Convert domain model to UserProfileV1_2 DTO for persistence.
+impl version_migrate::FromDomain<UserProfile> for UserProfileV1_2 {
     fn from_domain(profile: UserProfile) -> Self {
-        UserProfileV1_1 {
+        UserProfileV1_2 {
             nickname: profile.nickname,
             background: profile.background,
+            timezone: profile.timezone,
         }
     }
 }
@@ -81,13 +111,14 @@ impl version_migrate::FromDomain<UserProfile> for UserProfileV1_1 {
 
 /// Creates and configures a Migrator instance for UserProfile entities.
 ///
-/// The migrator handles automatic schema migration from V1.0 to V1.1
+/// The migrator handles automatic schema migration from V1.0 to V1.2
 /// and conversion to the domain model.
 ///
 /// # Migration Path
 ///
 /// - V1.0 → V1.1: Adds `background` field with default empty string
-/// - V1.1 → UserProfile: Converts DTO to domain model
+/// - V1.1 → V1.2: Adds `timezone` field, unset (the OS timezone)
+/// - V1.2 → UserProfile: Converts DTO to domain model
 ///
 /// # Example
 ///
@@ -99,6 +130,7 @@ pub fn create_user_profile_migrator() -> version_migrate::Migrator {
     version_migrate::migrator!("user_profile" => [
         UserProfileV1_0,
         UserProfileV1_1,
+        UserProfileV1_2,
         UserProfile
     ], save = true)
     .expect("Failed to create user_profile migrator")
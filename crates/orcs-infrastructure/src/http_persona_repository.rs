@@ -0,0 +1,314 @@
+//! Read-only PersonaRepository backed by a remote JSON persona index.
+//!
+//! Teams can publish a shared set of personas at a URL. The index is either a
+//! JSON array of personas or an object with a `personas` array, using the same
+//! field names as the persona TOML files.
+//!
+//! Fetches use the server's `ETag` so unchanged indexes are not re-downloaded,
+//! and every successful fetch is written to a snapshot on disk. When the server
+//! cannot be reached, the last snapshot is served instead. Without a snapshot,
+//! the failure is remembered and lookups fail fast until a growing backoff
+//! elapses, so an offline source does not stall every persona lookup.
+
+use crate::paths::{OrcsPaths, ServiceType};
+use orcs_core::error::{OrcsError, Result};
+use orcs_core::persona::{Persona, PersonaSource};
+use orcs_core::repository::PersonaRepository;
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+/// Wait before retrying after the first failed fetch without a snapshot.
+const RETRY_BACKOFF_INITIAL: Duration = Duration::from_secs(30);
+/// Upper bound for the doubling retry backoff.
+const RETRY_BACKOFF_MAX: Duration = Duration::from_secs(600);
+
+/// Accepted shapes of the remote index.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RemotePersonaIndex {
+    Wrapped { personas: Vec<Persona> },
+    List(Vec<Persona>),
+}
+
+impl RemotePersonaIndex {
+    fn into_personas(self) -> Vec<Persona> {
+        match self {
+            Self::Wrapped { personas } | Self::List(personas) => personas,
+        }
+    }
+}
+
+/// Last-fetched copy of the remote index, persisted for offline use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RemotePersonaSnapshot {
+    url: String,
+    etag: Option<String>,
+    fetched_at: String,
+    personas: Vec<Persona>,
+}
+
+/// A failed fetch while no snapshot was available.
+#[derive(Debug, Clone)]
+struct FetchFailure {
+    message: String,
+    failed_at: Instant,
+    backoff: Duration,
+}
+
+/// How a refresh of the remote index went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteRefreshStatus {
+    /// A new index was downloaded.
+    Updated,
+    /// The server reported the cached index is current.
+    NotModified,
+    /// The server was unreachable; the on-disk snapshot is in use.
+    Offline,
+}
+
+/// Result of [`HttpPersonaRepository::refresh`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteRefreshOutcome {
+    pub url: String,
+    pub status: RemoteRefreshStatus,
+    pub persona_count: usize,
+    /// When the personas in use were fetched (RFC 3339).
+    pub fetched_at: Option<String>,
+}
+
+/// Read-only persona repository that serves personas from a remote JSON index.
+///
+/// All personas are marked [`PersonaSource::Remote`]; write operations fail.
+pub struct HttpPersonaRepository {
+    url: String,
+    snapshot_path: PathBuf,
+    client: reqwest::Client,
+    snapshot: RwLock<Option<RemotePersonaSnapshot>>,
+    last_failure: RwLock<Option<FetchFailure>>,
+}
+
+impl HttpPersonaRepository {
+    /// Creates a repository for `url`, caching snapshots in the default location.
+    pub fn with_default_cache(url: impl Into<String>) -> Result<Self> {
+        let cache_dir = OrcsPaths::new(None)
+            .get_path(ServiceType::RemotePersonaCache)
+            .map_err(|e| OrcsError::config(e.to_string()))?
+            .into_path_buf();
+        Ok(Self::new(url, &cache_dir))
+    }
+
+    /// Creates a repository for `url` that keeps its snapshot under `cache_dir`.
+    pub fn new(url: impl Into<String>, cache_dir: &Path) -> Self {
+        Self {
+            url: url.into(),
+            snapshot_path: cache_dir.join(SNAPSHOT_FILE),
+            client: reqwest::Client::new(),
+            snapshot: RwLock::new(None),
+            last_failure: RwLock::new(None),
+        }
+    }
+
+    /// URL of the remote index.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Re-fetches the remote index, falling back to the on-disk snapshot when offline.
+    ///
+    /// Fails only when the server is unreachable and no snapshot exists. An
+    /// explicit refresh always contacts the server, even during a retry backoff.
+    pub async fn refresh(&self) -> Result<RemoteRefreshOutcome> {
+        let mut cached = self.cached();
+        if cached.is_none() {
+            cached = self.load_snapshot().await;
+        }
+        let etag = cached.as_ref().and_then(|s| s.etag.clone());
+
+        let status = match self.fetch(etag.as_deref()).await {
+            Ok(Some((etag, personas))) => {
+                let snapshot = RemotePersonaSnapshot {
+                    url: self.url.clone(),
+                    etag,
+                    fetched_at: chrono::Utc::now().to_rfc3339(),
+                    personas,
+                };
+                if let Err(e) = self.write_snapshot(&snapshot).await {
+                    tracing::warn!("[RemotePersonas] Failed to write snapshot: {}", e);
+                }
+                cached = Some(snapshot);
+                RemoteRefreshStatus::Updated
+            }
+            Ok(None) => RemoteRefreshStatus::NotModified,
+            Err(e) => {
+                if cached.is_none() {
+                    self.record_failure(&e);
+                    return Err(e);
+                }
+                tracing::warn!("[RemotePersonas] {}; using last snapshot", e);
+                RemoteRefreshStatus::Offline
+            }
+        };
+        *self.last_failure.write().unwrap() = None;
+
+        let outcome = RemoteRefreshOutcome {
+            url: self.url.clone(),
+            status,
+            persona_count: cached.as_ref().map_or(0, |s| s.personas.len()),
+            fetched_at: cached.as_ref().map(|s| s.fetched_at.clone()),
+        };
+        *self.snapshot.write().unwrap() = cached;
+        Ok(outcome)
+    }
+
+    /// Returns the personas in use, fetching the index on first access.
+    ///
+    /// While a previous fetch without snapshot is still backing off, fails
+    /// immediately with that error instead of contacting the server again.
+    async fn personas(&self) -> Result<Vec<Persona>> {
+        if let Some(snapshot) = self.cached() {
+            return Ok(snapshot.personas);
+        }
+        if let Some(failure) = self.last_failure.read().unwrap().as_ref() {
+            let remaining = failure.backoff.saturating_sub(failure.failed_at.elapsed());
+            if !remaining.is_zero() {
+                return Err(OrcsError::io(format!(
+                    "{} (retrying in {}s)",
+                    failure.message,
+                    remaining.as_secs()
+                )));
+            }
+        }
+        self.refresh().await?;
+        Ok(self.cached().map(|s| s.personas).unwrap_or_default())
+    }
+
+    /// Remembers a failed fetch, doubling the backoff of the previous failure.
+    fn record_failure(&self, error: &OrcsError) {
+        let mut last_failure = self.last_failure.write().unwrap();
+        let backoff = last_failure.as_ref().map_or(RETRY_BACKOFF_INITIAL, |f| {
+            (f.backoff * 2).min(RETRY_BACKOFF_MAX)
+        });
+        *last_failure = Some(FetchFailure {
+            message: error.to_string(),
+            failed_at: Instant::now(),
+            backoff,
+        });
+    }
+
+    fn cached(&self) -> Option<RemotePersonaSnapshot> {
+        self.snapshot.read().unwrap().clone()
+    }
+
+    /// Fetches the index; `Ok(None)` means the server answered 304 Not Modified.
+    async fn fetch(&self, etag: Option<&str>) -> Result<Option<(Option<String>, Vec<Persona>)>> {
+        let fetch_error =
+            |e: reqwest::Error| OrcsError::io(format!("Failed to fetch {}: {}", self.url, e));
+
+        let mut request = self.client.get(&self.url).timeout(FETCH_TIMEOUT);
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await.map_err(fetch_error)?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        let response = response.error_for_status().map_err(fetch_error)?;
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body = response.bytes().await.map_err(fetch_error)?;
+        let index: RemotePersonaIndex =
+            serde_json::from_slice(&body).map_err(|e| OrcsError::Serialization {
+                format: "JSON".to_string(),
+                message: format!("Invalid persona index at {}: {}", self.url, e),
+            })?;
+
+        let personas = index
+            .into_personas()
+            .into_iter()
+            .map(|mut persona| {
+                persona.source = PersonaSource::Remote;
+                persona
+            })
+            .collect();
+        Ok(Some((etag, personas)))
+    }
+
+    /// Loads the on-disk snapshot if it was taken from the same URL.
+    async fn load_snapshot(&self) -> Option<RemotePersonaSnapshot> {
+        let content = tokio::fs::read(&self.snapshot_path).await.ok()?;
+        match serde_json::from_slice::<RemotePersonaSnapshot>(&content) {
+            Ok(snapshot) if snapshot.url == self.url => Some(snapshot),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!("[RemotePersonas] Ignoring unreadable snapshot: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn write_snapshot(&self, snapshot: &RemotePersonaSnapshot) -> Result<()> {
+        if let Some(dir) = self.snapshot_path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let content = serde_json::to_vec_pretty(snapshot)?;
+        // Write then rename so a crash never leaves a truncated snapshot.
+        let tmp_path = self.snapshot_path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, content).await?;
+        tokio::fs::rename(&tmp_path, &self.snapshot_path).await?;
+        Ok(())
+    }
+
+    fn read_only(&self, persona_id: &str) -> OrcsError {
+        OrcsError::data_access(format!(
+            "Persona '{}' comes from the remote source {} and is read-only",
+            persona_id, self.url
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonaRepository for HttpPersonaRepository {
+    async fn find_by_id(&self, persona_id: &str) -> Result<Option<Persona>> {
+        Ok(self
+            .personas()
+            .await?
+            .into_iter()
+            .find(|p| p.id == persona_id))
+    }
+
+    async fn save(&self, persona: &Persona) -> Result<()> {
+        Err(self.read_only(&persona.id))
+    }
+
+    async fn delete(&self, persona_id: &str) -> Result<()> {
+        Err(self.read_only(persona_id))
+    }
+
+    async fn get_all(&self) -> Result<Vec<Persona>> {
+        let personas = self.get_all_including_archived().await?;
+        Ok(personas.into_iter().filter(|p| !p.archived).collect())
+    }
+
+    async fn get_all_including_archived(&self) -> Result<Vec<Persona>> {
+        self.personas().await
+    }
+
+    async fn save_all(&self, personas: &[Persona]) -> Result<()> {
+        match personas.first() {
+            Some(persona) => Err(self.read_only(&persona.id)),
+            None => Ok(()),
+        }
+    }
+}
@@ -24,31 +24,55 @@
 //!
 //! See [`paths`] module for detailed documentation on the path management system.
 
+pub mod app_state_snapshot_store;
+pub mod async_dir_conversation_script_repository;
 pub mod async_dir_dialogue_preset_repository;
 pub mod async_dir_persona_repository;
 pub mod async_dir_session_repository;
 pub mod async_dir_slash_command_repository;
 pub mod async_dir_task_repository;
 pub mod async_dir_workspace_repository;
+pub mod cached_persona_repository;
+pub mod composite_persona_repository;
 pub mod config_service;
 pub mod dto;
+pub mod http_persona_repository;
 pub mod paths;
+pub mod persona_icon_store;
 pub mod quick_action_repository;
 pub mod search;
 pub mod secret_service;
+pub mod session_validation;
 pub mod state_repository;
 pub mod storage_repository;
+pub mod stream_recovery;
 pub mod user_service;
+pub mod web_clip_service;
+pub mod wire_log;
+pub mod workspace_ignore;
 pub mod workspace_storage_service;
 
+pub use crate::app_state_snapshot_store::{AppStateSnapshotInfo, AppStateSnapshotStore};
+pub use crate::async_dir_conversation_script_repository::AsyncDirConversationScriptRepository;
 pub use crate::async_dir_dialogue_preset_repository::AsyncDirDialoguePresetRepository;
 pub use crate::async_dir_persona_repository::AsyncDirPersonaRepository;
 pub use crate::async_dir_session_repository::AsyncDirSessionRepository;
 pub use crate::async_dir_slash_command_repository::AsyncDirSlashCommandRepository;
 pub use crate::async_dir_task_repository::AsyncDirTaskRepository;
 pub use crate::async_dir_workspace_repository::AsyncDirWorkspaceRepository;
+pub use crate::cached_persona_repository::CachedPersonaRepository;
+pub use crate::composite_persona_repository::CompositePersonaRepository;
 pub use crate::config_service::ConfigService;
+pub use crate::http_persona_repository::{
+    HttpPersonaRepository, RemoteRefreshOutcome, RemoteRefreshStatus,
+};
 pub use crate::paths::{OrcsPaths, PathType, ServiceType};
+pub use crate::persona_icon_store::PersonaIconStore;
 pub use crate::quick_action_repository::FileQuickActionRepository;
 pub use crate::secret_service::SecretServiceImpl;
+pub use crate::session_validation::{ValidationReport, validate_all_sessions};
 pub use crate::state_repository::AppStateService;
+pub use crate::stream_recovery::{FlushPolicy, PartialTurnStore, StreamRecoveryWriter};
+pub use crate::web_clip_service::{WebClip, WebClipService};
+pub use crate::wire_log::{MAX_WIRE_LOG_TTL_HOURS, WireLog, WireLogEntry, WireTap};
+pub use crate::workspace_ignore::{EffectiveIgnoreRules, WorkspaceIgnore};
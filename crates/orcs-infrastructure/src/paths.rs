@@ -142,6 +142,8 @@ impl PathType {
 pub enum ServiceType {
     /// Application state service (state.toml)
     AppState,
+    /// Named application state checkpoints (app_state_snapshots/)
+    AppStateSnapshot,
     /// Configuration service (config.toml)
     Config,
     /// Secret configuration file (secret.json)
@@ -156,10 +158,18 @@ pub enum ServiceType {
     Task,
     /// Persona service (personas/)
     Persona,
+    /// Uploaded persona icon images (personas/assets/)
+    PersonaAsset,
     /// Dialogue preset service (dialogue_presets/)
     DialoguePreset,
+    /// Conversation script service (conversation_scripts/)
+    ConversationScript,
     /// Slash command service (slash_commands/)
     SlashCommand,
+    /// Last-fetched remote persona index (remote_personas/)
+    RemotePersonaCache,
+    /// Partial output of interrupted turns (stream_recovery/)
+    StreamRecovery,
     /// Logs directory (logs/)
     Logs,
 }
@@ -335,12 +345,27 @@ impl OrcsPaths {
             }
             ServiceType::Task => Ok(PathType::Dir(self.data_dir()?.join("tasks"))),
             ServiceType::Persona => Ok(PathType::Dir(self.data_dir()?.join("personas"))),
+            ServiceType::PersonaAsset => Ok(PathType::Dir(
+                self.data_dir()?.join("personas").join("assets"),
+            )),
             ServiceType::DialoguePreset => {
                 Ok(PathType::Dir(self.data_dir()?.join("dialogue_presets")))
             }
+            ServiceType::ConversationScript => {
+                Ok(PathType::Dir(self.data_dir()?.join("conversation_scripts")))
+            }
             ServiceType::SlashCommand => {
                 Ok(PathType::Dir(self.config_dir()?.join("slash_commands")))
             }
+            ServiceType::AppStateSnapshot => Ok(PathType::Dir(
+                self.config_dir()?.join("app_state_snapshots"),
+            )),
+            ServiceType::RemotePersonaCache => {
+                Ok(PathType::Dir(self.data_dir()?.join("remote_personas")))
+            }
+            ServiceType::StreamRecovery => {
+                Ok(PathType::Dir(self.data_dir()?.join("stream_recovery")))
+            }
             ServiceType::Logs => Ok(PathType::Dir(self.config_dir()?.join("logs"))),
         }
     }
@@ -0,0 +1,253 @@
+//! Uploaded persona icon images.
+//!
+//! Icons are normalized to PNG no larger than [`MAX_ICON_DIMENSION`] and stored
+//! content-addressed (`<sha256>.png`), so personas sharing an image share a file.
+//! Personas reference an icon by file name; deleting or archiving a persona
+//! leaves its file in place until [`PersonaIconStore::sweep_unreferenced`] runs.
+
+use crate::paths::{OrcsPaths, ServiceType};
+use image::{ImageFormat, imageops::FilterType};
+use orcs_core::error::{OrcsError, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Largest width or height of a stored icon, in pixels.
+pub const MAX_ICON_DIMENSION: u32 = 128;
+
+/// MIME types accepted for upload.
+pub const ACCEPTED_ICON_MIME_TYPES: [&str; 3] = ["image/png", "image/jpeg", "image/webp"];
+
+/// Content-addressed icon files (`<sha256>.png`) in the persona assets directory.
+pub struct PersonaIconStore {
+    dir: PathBuf,
+}
+
+impl PersonaIconStore {
+    /// Creates a store under `base_dir`, or the default data directory when `None`.
+    pub async fn new(base_dir: Option<&Path>) -> Result<Self> {
+        let dir = OrcsPaths::new(base_dir)
+            .get_path(ServiceType::PersonaAsset)
+            .map_err(|e| OrcsError::config(e.to_string()))?
+            .into_path_buf();
+        tokio::fs::create_dir_all(&dir).await?;
+        Ok(Self { dir })
+    }
+
+    /// Validates, downscales and stores an uploaded icon, returning its asset name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mime` is not PNG, JPEG or WebP, if the bytes are not
+    /// an image of that type, or if the file cannot be written.
+    pub async fn store(&self, bytes: &[u8], mime: &str) -> Result<String> {
+        let png = normalize_icon(bytes, mime)?;
+        let asset = format!("{}.png", hex_digest(&png));
+        let path = self.dir.join(&asset);
+        if !tokio::fs::try_exists(&path).await? {
+            // Write then rename so a crash never leaves a truncated file.
+            let tmp_path = path.with_extension("png.tmp");
+            tokio::fs::write(&tmp_path, png).await?;
+            tokio::fs::rename(&tmp_path, &path).await?;
+        }
+        Ok(asset)
+    }
+
+    /// Returns the PNG bytes of an asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns a NotFound error if the asset does not exist or is not a valid asset name.
+    pub async fn load(&self, asset: &str) -> Result<Vec<u8>> {
+        let path = self
+            .path(asset)
+            .ok_or_else(|| OrcsError::not_found("persona icon", asset))?;
+        match tokio::fs::read(path).await {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(OrcsError::not_found("persona icon", asset))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the file path of an asset, or `None` for names that are not asset names.
+    pub fn path(&self, asset: &str) -> Option<PathBuf> {
+        is_asset_name(asset).then(|| self.dir.join(asset))
+    }
+
+    /// Deletes stored icons not in `referenced`, returning the removed asset names.
+    pub async fn sweep_unreferenced(&self, referenced: &HashSet<String>) -> Result<Vec<String>> {
+        let mut removed = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if is_asset_name(&name) && !referenced.contains(&name) {
+                tokio::fs::remove_file(entry.path()).await?;
+                removed.push(name);
+            }
+        }
+        removed.sort();
+        Ok(removed)
+    }
+}
+
+/// Decodes an upload of the declared type and re-encodes it as a capped-size PNG.
+fn normalize_icon(bytes: &[u8], mime: &str) -> Result<Vec<u8>> {
+    let declared = match mime {
+        "image/png" => ImageFormat::Png,
+        "image/jpeg" => ImageFormat::Jpeg,
+        "image/webp" => ImageFormat::WebP,
+        _ => {
+            return Err(OrcsError::io(format!(
+                "Unsupported icon type '{}'; expected one of {}",
+                mime,
+                ACCEPTED_ICON_MIME_TYPES.join(", ")
+            )));
+        }
+    };
+    let actual = image::guess_format(bytes)
+        .map_err(|_| OrcsError::io("Icon data is not a recognized image"))?;
+    if actual != declared {
+        return Err(OrcsError::io(format!(
+            "Icon data does not match its declared type '{}'",
+            mime
+        )));
+    }
+
+    let mut icon = image::load_from_memory_with_format(bytes, declared)
+        .map_err(|e| OrcsError::io(format!("Failed to decode icon: {}", e)))?;
+    if icon.width() > MAX_ICON_DIMENSION || icon.height() > MAX_ICON_DIMENSION {
+        icon = icon.resize(MAX_ICON_DIMENSION, MAX_ICON_DIMENSION, FilterType::Lanczos3);
+    }
+
+    let mut png = Vec::new();
+    icon.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| OrcsError::io(format!("Failed to encode icon: {}", e)))?;
+    Ok(png)
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Asset names are `<64 hex chars>.png`, which also rules out path traversal.
+fn is_asset_name(name: &str) -> bool {
+    name.strip_suffix(".png")
+        .is_some_and(|digest| digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, RgbaImage};
+    use tempfile::TempDir;
+
+    fn encoded(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+            width,
+            height,
+            image::Rgba([200, 40, 40, 255]),
+        ));
+        let image = match format {
+            // JPEG has no alpha channel
+            ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+            _ => image,
+        };
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    #[tokio::test]
+    async fn test_store_rejects_unsupported_and_mismatched_types() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PersonaIconStore::new(Some(temp_dir.path())).await.unwrap();
+        let png = encoded(16, 16, ImageFormat::Png);
+
+        let gif = store.store(&png, "image/gif").await.unwrap_err();
+        assert!(gif.to_string().contains("Unsupported icon type"));
+
+        let mismatched = store.store(&png, "image/jpeg").await.unwrap_err();
+        assert!(mismatched.to_string().contains("does not match"));
+
+        let garbage = store.store(b"not an image", "image/png").await.unwrap_err();
+        assert!(garbage.to_string().contains("not a recognized image"));
+    }
+
+    #[tokio::test]
+    async fn test_store_downscales_large_icons_to_png() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PersonaIconStore::new(Some(temp_dir.path())).await.unwrap();
+
+        let asset = store
+            .store(&encoded(512, 256, ImageFormat::Jpeg), "image/jpeg")
+            .await
+            .unwrap();
+        let icon = image::load_from_memory(&store.load(&asset).await.unwrap()).unwrap();
+        assert_eq!((icon.width(), icon.height()), (128, 64));
+
+        let small = store
+            .store(&encoded(32, 48, ImageFormat::WebP), "image/webp")
+            .await
+            .unwrap();
+        let icon = image::load_from_memory_with_format(
+            &store.load(&small).await.unwrap(),
+            ImageFormat::Png,
+        )
+        .unwrap();
+        assert_eq!((icon.width(), icon.height()), (32, 48));
+    }
+
+    #[tokio::test]
+    async fn test_identical_uploads_share_one_asset() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PersonaIconStore::new(Some(temp_dir.path())).await.unwrap();
+        let png = encoded(16, 16, ImageFormat::Png);
+
+        let first = store.store(&png, "image/png").await.unwrap();
+        let second = store.store(&png, "image/png").await.unwrap();
+        assert_eq!(first, second);
+        assert!(is_asset_name(&first));
+    }
+
+    #[tokio::test]
+    async fn test_load_rejects_non_asset_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PersonaIconStore::new(Some(temp_dir.path())).await.unwrap();
+
+        let error = store.load("../personas.toml").await.unwrap_err();
+        assert!(error.is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_sweep_removes_only_unreferenced_assets() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PersonaIconStore::new(Some(temp_dir.path())).await.unwrap();
+        let kept = store
+            .store(&encoded(16, 16, ImageFormat::Png), "image/png")
+            .await
+            .unwrap();
+        let orphan = store
+            .store(&encoded(20, 20, ImageFormat::Png), "image/png")
+            .await
+            .unwrap();
+
+        let removed = store
+            .sweep_unreferenced(&HashSet::from([kept.clone()]))
+            .await
+            .unwrap();
+
+        assert_eq!(removed, vec![orphan.clone()]);
+        assert!(store.load(&kept).await.is_ok());
+        assert!(store.load(&orphan).await.unwrap_err().is_not_found());
+    }
+}
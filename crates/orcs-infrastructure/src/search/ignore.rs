@@ -0,0 +1,237 @@
+//! `.orcsignore` support for search.
+//!
+//! A workspace can list paths to leave out of search in a `.orcsignore` file at
+//! its root, using gitignore syntax: blank lines and `#` comments are skipped,
+//! `!pattern` re-includes, a trailing `/` matches directories only, and a
+//! pattern containing a `/` is anchored to the root. Matching is done by the
+//! `ignore` crate's gitignore matcher, the one ripgrep uses.
+//!
+//! [`BUILTIN_EXCLUDES`] apply before the file's own patterns, so a workspace can
+//! re-include them with e.g. `!target/`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::workspace_ignore::WorkspaceIgnore;
+
+/// Name of the ignore file read from the root of each searched path.
+pub const IGNORE_FILE_NAME: &str = ".orcsignore";
+
+/// Directories excluded from search unless the ignore file re-includes them.
+pub const BUILTIN_EXCLUDES: &[&str] = &[".git", "node_modules", "target"];
+
+/// Ignore patterns of one search root.
+#[derive(Debug, Clone)]
+pub struct IgnoreRules {
+    /// Lines the rules were parsed from, built-in excludes first
+    patterns: Vec<String>,
+    matcher: Gitignore,
+    /// The root's ignore file, when it exists
+    ignore_file: Option<PathBuf>,
+}
+
+impl IgnoreRules {
+    /// Parses ignore-file content; the built-in excludes come first.
+    ///
+    /// Lines that are not valid patterns are skipped.
+    pub fn parse(content: &str) -> Self {
+        let mut builder = GitignoreBuilder::new("");
+        let mut patterns = Vec::new();
+        let builtin = BUILTIN_EXCLUDES.iter().map(|name| format!("{}/", name));
+        for line in builtin.chain(content.lines().map(str::to_string)) {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match builder.add_line(None, line) {
+                Ok(_) => patterns.push(line.to_string()),
+                Err(e) => tracing::warn!("Skipping invalid ignore pattern {:?}: {}", line, e),
+            }
+        }
+        let matcher = builder.build().unwrap_or_else(|e| {
+            tracing::warn!("Failed to build ignore rules: {}", e);
+            Gitignore::empty()
+        });
+        Self {
+            patterns,
+            matcher,
+            ignore_file: None,
+        }
+    }
+
+    /// Loads the rules of `root`, reading its `.orcsignore` when present.
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(IGNORE_FILE_NAME);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Self {
+                ignore_file: Some(path),
+                ..Self::parse(&content)
+            },
+            Err(_) => Self::parse(""),
+        }
+    }
+
+    /// Returns the rules in the order they apply, built-in excludes first.
+    pub fn patterns(&self) -> Vec<&str> {
+        self.patterns.iter().map(String::as_str).collect()
+    }
+
+    /// Returns the root's ignore file, or `None` when it has none.
+    pub fn ignore_file(&self) -> Option<&Path> {
+        self.ignore_file.as_deref()
+    }
+
+    /// Returns the built-in excludes that the ignore file does not re-include.
+    pub fn active_builtin_excludes(&self) -> Vec<&'static str> {
+        BUILTIN_EXCLUDES
+            .iter()
+            .copied()
+            .filter(|name| self.matcher.matched(name, true).is_ignore())
+            .collect()
+    }
+
+    /// Whether the file at `relative` (from the root) is excluded.
+    ///
+    /// As in git, a file inside an excluded directory stays excluded even if a
+    /// later pattern matches the file itself.
+    pub fn is_ignored(&self, relative: &Path) -> bool {
+        let mut prefix = PathBuf::new();
+        let mut components = relative.components().peekable();
+        while let Some(component) = components.next() {
+            prefix.push(component);
+            let is_dir = components.peek().is_some();
+            if self.matcher.matched(&prefix, is_dir).is_ignore() {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Ignore rules of every searched path.
+#[derive(Debug, Clone)]
+pub struct SearchIgnore {
+    roots: Vec<(PathBuf, Arc<IgnoreRules>)>,
+}
+
+impl SearchIgnore {
+    /// Loads the rules of each search path.
+    pub fn load(search_paths: &[PathBuf]) -> Self {
+        Self {
+            roots: search_paths
+                .iter()
+                .map(|root| (root.clone(), Arc::new(IgnoreRules::load(root))))
+                .collect(),
+        }
+    }
+
+    /// Takes the rules of each search path from `cache`.
+    pub fn cached(search_paths: &[PathBuf], cache: &WorkspaceIgnore) -> Self {
+        Self {
+            roots: search_paths
+                .iter()
+                .map(|root| (root.clone(), cache.rules(root)))
+                .collect(),
+        }
+    }
+
+    /// Each search path with its rules.
+    pub fn roots(&self) -> impl Iterator<Item = (&Path, &IgnoreRules)> {
+        self.roots
+            .iter()
+            .map(|(root, rules)| (root.as_path(), rules.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns_follow_gitignore_rules() {
+        let rules = IgnoreRules::parse(
+            "# generated\n\
+             dist/\n\
+             *.log\n\
+             /docs/drafts\n\
+             !keep.log\n",
+        );
+
+        assert!(rules.is_ignored(Path::new("dist/app.js")));
+        assert!(rules.is_ignored(Path::new("web/dist/app.js")));
+        assert!(!rules.is_ignored(Path::new("dist")));
+        assert!(rules.is_ignored(Path::new("logs/server.log")));
+        assert!(!rules.is_ignored(Path::new("logs/keep.log")));
+        assert!(rules.is_ignored(Path::new("docs/drafts/idea.md")));
+        assert!(!rules.is_ignored(Path::new("web/docs/drafts/idea.md")));
+        assert!(!rules.is_ignored(Path::new("src/main.rs")));
+    }
+
+    #[test]
+    fn test_files_in_excluded_directories_stay_excluded() {
+        let rules = IgnoreRules::parse("build/\n!build/keep.txt\n/*.tmp\n!/cache.tmp\n");
+
+        // A directory's exclusion cannot be undone for a file inside it
+        assert!(rules.is_ignored(Path::new("build/keep.txt")));
+        assert!(rules.is_ignored(Path::new("scratch.tmp")));
+        assert!(!rules.is_ignored(Path::new("cache.tmp")));
+        assert!(!rules.is_ignored(Path::new("src/scratch.tmp")));
+    }
+
+    #[test]
+    fn test_patterns_lists_the_rules_in_order() {
+        let rules = IgnoreRules::parse("# secrets\n/secrets/.env\n\ndata/\n");
+        assert_eq!(
+            rules.patterns(),
+            vec![
+                ".git/",
+                "node_modules/",
+                "target/",
+                "/secrets/.env",
+                "data/"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_builtin_excludes_can_be_reincluded() {
+        let rules = IgnoreRules::parse("");
+        assert!(rules.is_ignored(Path::new("node_modules/react/index.js")));
+        assert!(rules.is_ignored(Path::new("crates/core/target/debug/build.log")));
+        assert_eq!(
+            rules.active_builtin_excludes(),
+            vec![".git", "node_modules", "target"]
+        );
+
+        let rules = IgnoreRules::parse("!target/\n");
+        assert!(!rules.is_ignored(Path::new("target/report.txt")));
+        assert_eq!(
+            rules.active_builtin_excludes(),
+            vec![".git", "node_modules"]
+        );
+    }
+
+    #[test]
+    fn test_search_ignore_uses_the_ignore_file_of_each_root() {
+        let workspace = tempfile::TempDir::new().unwrap();
+        let sessions = tempfile::TempDir::new().unwrap();
+        std::fs::write(workspace.path().join(IGNORE_FILE_NAME), "generated/\n").unwrap();
+        let ignore = SearchIgnore::load(&[
+            workspace.path().to_path_buf(),
+            sessions.path().to_path_buf(),
+        ]);
+
+        let roots: Vec<(&Path, &IgnoreRules)> = ignore.roots().collect();
+        assert_eq!(roots[0].0, workspace.path());
+        assert_eq!(
+            roots[0].1.ignore_file(),
+            Some(workspace.path().join(IGNORE_FILE_NAME).as_path())
+        );
+        assert!(roots[0].1.is_ignored(Path::new("generated/needle.rs")));
+        assert_eq!(roots[1].1.ignore_file(), None);
+        assert!(!roots[1].1.is_ignored(Path::new("generated/s1.toml")));
+        assert!(roots[1].1.is_ignored(Path::new("node_modules/x.js")));
+    }
+}
@@ -1,5 +1,7 @@
 //! Search service implementations.
 
+pub mod ignore;
 pub mod ripgrep_search;
 
+pub use ignore::{IgnoreRules, SearchIgnore};
 pub use ripgrep_search::RipgrepSearchService;
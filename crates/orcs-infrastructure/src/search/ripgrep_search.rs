@@ -1,38 +1,48 @@
 //! Ripgrep-based search implementation.
 
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 
 use orcs_core::agent::build_enhanced_path;
 use orcs_core::error::{OrcsError, Result};
 use orcs_core::search::model::SearchResultItem;
 use orcs_core::search::{SearchFilters, SearchOptions, SearchResult, SearchService};
 
+use super::ignore::{IgnoreRules, SearchIgnore};
+use crate::workspace_ignore::WorkspaceIgnore;
+
 /// Search service implementation using ripgrep.
-pub struct RipgrepSearchService;
+///
+/// Each searched path is walked by its own ripgrep run, which skips what the
+/// path's `.orcsignore` file and the built-in excludes leave out.
+pub struct RipgrepSearchService {
+    ignore: Arc<WorkspaceIgnore>,
+}
 
 impl RipgrepSearchService {
     pub fn new() -> Self {
-        Self
+        Self {
+            ignore: Arc::new(WorkspaceIgnore::new()),
+        }
+    }
+
+    /// Reads ignore rules through the app-wide `ignore` cache.
+    pub fn with_workspace_ignore(mut self, ignore: Arc<WorkspaceIgnore>) -> Self {
+        self.ignore = ignore;
+        self
     }
 
-    /// Searches for files by filename matching the query.
+    /// Searches for files under `root` by filename matching the query.
     fn search_by_filename(
         &self,
         query: &str,
-        search_paths: &[PathBuf],
+        root: &Path,
+        rules: &IgnoreRules,
         filters: &Option<SearchFilters>,
     ) -> Result<Vec<SearchResultItem>> {
-        if search_paths.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let mut cmd = Command::new("rg");
-
-        // Set enhanced PATH
-        let enhanced_path = build_enhanced_path(&search_paths[0], None);
-        cmd.env("PATH", enhanced_path);
+        let mut cmd = ripgrep_command(root, rules);
 
         // List files only
         cmd.arg("--files");
@@ -52,10 +62,7 @@ impl RipgrepSearchService {
             }
         }
 
-        // Add all search paths
-        for path in search_paths {
-            cmd.arg(path);
-        }
+        cmd.arg(root);
 
         tracing::debug!("Executing ripgrep --files command: {:?}", cmd);
 
@@ -93,23 +100,15 @@ impl RipgrepSearchService {
         Ok(items)
     }
 
-    /// Executes ripgrep command with the given parameters.
+    /// Searches the file contents under `root` with ripgrep.
     fn execute_ripgrep(
         &self,
         query: &str,
-        search_paths: &[PathBuf],
+        root: &Path,
+        rules: &IgnoreRules,
         filters: &Option<SearchFilters>,
     ) -> Result<Vec<SearchResultItem>> {
-        if search_paths.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let mut cmd = Command::new("rg");
-
-        // Set enhanced PATH to find ripgrep in system and workspace-specific locations
-        // Use first path for PATH enhancement
-        let enhanced_path = build_enhanced_path(&search_paths[0], None);
-        cmd.env("PATH", enhanced_path);
+        let mut cmd = ripgrep_command(root, rules);
 
         // Basic flags
         cmd.arg("--line-number"); // Show line numbers
@@ -148,11 +147,7 @@ impl RipgrepSearchService {
 
         // Add query
         cmd.arg(query);
-
-        // Add all search paths
-        for path in search_paths {
-            cmd.arg(path);
-        }
+        cmd.arg(root);
 
         tracing::debug!("Executing ripgrep command: {:?}", cmd);
 
@@ -198,6 +193,27 @@ impl RipgrepSearchService {
     }
 }
 
+/// Builds a ripgrep command that walks `root` without the paths `rules` exclude.
+///
+/// ripgrep matches `--ignore-file` patterns relative to its working directory,
+/// so the command runs in `root`. The built-in excludes are passed as globs so
+/// large dependency and build directories are not read at all.
+fn ripgrep_command(root: &Path, rules: &IgnoreRules) -> Command {
+    let mut cmd = Command::new("rg");
+
+    // Set enhanced PATH to find ripgrep in system and workspace-specific locations
+    cmd.env("PATH", build_enhanced_path(root, None));
+    cmd.current_dir(root);
+
+    if let Some(ignore_file) = rules.ignore_file() {
+        cmd.arg("--ignore-file").arg(ignore_file);
+    }
+    for name in rules.active_builtin_excludes() {
+        cmd.arg("--glob").arg(format!("!{}/", name));
+    }
+    cmd
+}
+
 impl Default for RipgrepSearchService {
     fn default() -> Self {
         Self::new()
@@ -219,8 +235,13 @@ impl SearchService for RipgrepSearchService {
         }
 
         // Search both file contents and filenames
-        let content_items = self.execute_ripgrep(query, &search_paths, &filters)?;
-        let filename_items = self.search_by_filename(query, &search_paths, &filters)?;
+        let ignore = SearchIgnore::cached(&search_paths, &self.ignore);
+        let mut filename_items = Vec::new();
+        let mut content_items = Vec::new();
+        for (root, rules) in ignore.roots() {
+            filename_items.extend(self.search_by_filename(query, root, rules, &filters)?);
+            content_items.extend(self.execute_ripgrep(query, root, rules, &filters)?);
+        }
 
         // Merge results (filename matches first, then content matches)
         let mut all_items = filename_items;
@@ -229,3 +250,73 @@ impl SearchService for RipgrepSearchService {
         Ok(SearchResult::new(query.to_string(), options, all_items))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    #[ignore = "requires ripgrep on PATH"]
+    async fn test_orcsignore_excludes_directory_from_results() {
+        let workspace = TempDir::new().unwrap();
+        let root = workspace.path();
+        for dir in ["src", "generated", "node_modules/pkg"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        std::fs::write(root.join("src/lib.rs"), "// needle\n").unwrap();
+        std::fs::write(root.join("generated/needle.rs"), "// needle\n").unwrap();
+        std::fs::write(root.join("node_modules/pkg/index.js"), "// needle\n").unwrap();
+        std::fs::write(root.join(".orcsignore"), "# build output\ngenerated/\n").unwrap();
+
+        let result = RipgrepSearchService::new()
+            .search(
+                "needle",
+                SearchOptions::default(),
+                vec![root.to_path_buf()],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let paths: Vec<&str> = result.items.iter().map(|item| item.path.as_str()).collect();
+        assert!(!paths.is_empty());
+        assert!(
+            paths.iter().all(|path| path.ends_with("src/lib.rs")),
+            "{:?}",
+            paths
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires ripgrep on PATH"]
+    async fn test_orcsignore_edits_apply_to_the_next_search() {
+        let workspace = TempDir::new().unwrap();
+        let root = workspace.path();
+        std::fs::create_dir_all(root.join("data")).unwrap();
+        std::fs::write(root.join("data/dump.csv"), "id,needle\n").unwrap();
+        std::fs::write(root.join("notes.md"), "needle\n").unwrap();
+        let service =
+            RipgrepSearchService::new().with_workspace_ignore(Arc::new(WorkspaceIgnore::new()));
+        let search = || async {
+            let result = service
+                .search(
+                    "needle",
+                    SearchOptions::default(),
+                    vec![root.to_path_buf()],
+                    None,
+                )
+                .await
+                .unwrap();
+            result
+                .items
+                .iter()
+                .any(|item| item.path.ends_with("data/dump.csv"))
+        };
+
+        std::fs::write(root.join(".orcsignore"), "# nothing yet\n").unwrap();
+        assert!(search().await);
+        std::fs::write(root.join(".orcsignore"), "data/\n").unwrap();
+        assert!(!search().await);
+    }
+}
@@ -0,0 +1,173 @@
+//! Validation of persisted sessions against the current schema.
+//!
+//! Loads every session file through the session migrator, exactly as the
+//! repository would, and reports per file whether it migrates cleanly. Files
+//! are only read: a failing session is reported, never repaired or rewritten.
+
+use crate::dto::create_session_migrator;
+use orcs_core::session::Session;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Outcome of loading one session file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    /// Path of the session file
+    pub file: PathBuf,
+    /// Schema version recorded in the file, if it could be read
+    pub detected_version: Option<String>,
+    /// Whether the file loaded and migrated to the current schema
+    pub success: bool,
+    /// Why loading failed (parse or migration error)
+    pub error: Option<String>,
+}
+
+impl ValidationReport {
+    fn failed(file: PathBuf, detected_version: Option<String>, error: String) -> Self {
+        Self {
+            file,
+            detected_version,
+            success: false,
+            error: Some(error),
+        }
+    }
+}
+
+/// Validates every `*.toml` session file in `dir`.
+///
+/// Reports are sorted by file path. A missing directory yields no reports.
+pub async fn validate_all_sessions(dir: &Path) -> Vec<ValidationReport> {
+    let mut files = Vec::new();
+    match tokio::fs::read_dir(dir).await {
+        Ok(mut entries) => {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) == Some("toml") {
+                    files.push(path);
+                }
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return vec![],
+        Err(e) => {
+            return vec![ValidationReport::failed(
+                dir.to_path_buf(),
+                None,
+                format!("Failed to read sessions directory: {}", e),
+            )];
+        }
+    }
+    files.sort();
+
+    let migrator = create_session_migrator();
+    let mut reports = Vec::with_capacity(files.len());
+    for file in files {
+        let content = match tokio::fs::read_to_string(&file).await {
+            Ok(content) => content,
+            Err(e) => {
+                reports.push(ValidationReport::failed(
+                    file,
+                    None,
+                    format!("Failed to read file: {}", e),
+                ));
+                continue;
+            }
+        };
+
+        let value = match toml::from_str::<toml::Value>(&content) {
+            Ok(value) => value,
+            Err(e) => {
+                reports.push(ValidationReport::failed(
+                    file,
+                    None,
+                    format!("Invalid TOML: {}", e),
+                ));
+                continue;
+            }
+        };
+        let detected_version = value
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        let loaded: Result<Session, _> = migrator.load_flat_from("session", value);
+        match loaded {
+            Ok(_) => reports.push(ValidationReport {
+                file,
+                detected_version,
+                success: true,
+                error: None,
+            }),
+            Err(e) => reports.push(ValidationReport::failed(
+                file,
+                detected_version,
+                format!("Migration failed: {}", e),
+            )),
+        }
+    }
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_reports_valid_and_corrupt_sessions_without_modifying_them() {
+        let temp_dir = TempDir::new().unwrap();
+        let valid = r#"
+version = "4.10.0"
+id = "valid"
+title = "Valid"
+created_at = "2024-01-01T00:00:00Z"
+updated_at = "2024-01-01T00:00:00Z"
+current_persona_id = "mai"
+workspace_id = "ws"
+
+[persona_histories]
+
+[app_mode]
+type = "Idle"
+"#;
+        let corrupt = "version = \"4.10.0\"\nid = \"corrupt\"\ntitle = 42\n";
+        std::fs::write(temp_dir.path().join("valid.toml"), valid).unwrap();
+        std::fs::write(temp_dir.path().join("corrupt.toml"), corrupt).unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "not a session").unwrap();
+
+        let reports = validate_all_sessions(temp_dir.path()).await;
+
+        assert_eq!(reports.len(), 2);
+        let corrupt_report = &reports[0];
+        assert_eq!(corrupt_report.file, temp_dir.path().join("corrupt.toml"));
+        assert_eq!(corrupt_report.detected_version.as_deref(), Some("4.10.0"));
+        assert!(!corrupt_report.success);
+        assert!(corrupt_report.error.is_some());
+
+        let valid_report = &reports[1];
+        assert_eq!(valid_report.file, temp_dir.path().join("valid.toml"));
+        assert_eq!(valid_report.detected_version.as_deref(), Some("4.10.0"));
+        assert!(valid_report.success);
+        assert_eq!(valid_report.error, None);
+
+        // Nothing was migrated in place
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("valid.toml")).unwrap(),
+            valid
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("corrupt.toml")).unwrap(),
+            corrupt
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_directory_has_no_reports() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(
+            validate_all_sessions(&temp_dir.path().join("missing"))
+                .await
+                .is_empty()
+        );
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides a service for managing application-level state that persists
 //! across sessions, such as the last selected workspace ID.
 
-use crate::dto::create_app_state_migrator;
+use crate::dto::{AppStateDTO, create_app_state_migrator};
 use crate::paths::{OrcsPaths, ServiceType};
 use orcs_core::error::{OrcsError, Result};
 use orcs_core::state::model::{AppState, OpenTab};
@@ -11,7 +11,9 @@ use orcs_core::state::repository::StateRepository;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
-use version_migrate::{FileStorage, FileStorageStrategy, FormatStrategy, LoadBehavior};
+use version_migrate::{
+    FileStorage, FileStorageStrategy, FormatStrategy, FromDomain, IntoDomain, LoadBehavior,
+};
 
 /// Service for managing application state.
 ///
@@ -99,6 +101,21 @@ impl StateRepositoryImpl {
             storage,
         })
     }
+
+    /// Returns a copy of the current app state, e.g. to keep as a named checkpoint.
+    pub async fn snapshot(&self) -> AppStateDTO {
+        AppStateDTO::from_domain(self.state.lock().await.clone())
+    }
+
+    /// Replaces the current app state with `snapshot` and saves it.
+    ///
+    /// Offline mode is a setting rather than part of the layout a snapshot
+    /// captures, so it keeps its current value.
+    pub async fn restore(&self, snapshot: AppStateDTO) -> Result<()> {
+        let mut state = snapshot.into_domain();
+        state.offline_mode = self.state.lock().await.offline_mode;
+        self.save_state(state).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -189,6 +206,18 @@ impl StateRepository for StateRepositoryImpl {
         self.save_state(cloned_state).await
     }
 
+    /// Gets whether offline mode is on.
+    async fn get_offline_mode(&self) -> bool {
+        self.state.lock().await.offline_mode
+    }
+
+    /// Sets offline mode.
+    async fn set_offline_mode(&self, enabled: bool) -> Result<()> {
+        let mut state = self.state.lock().await.clone();
+        state.offline_mode = enabled;
+        self.save_state(state).await
+    }
+
     async fn get_state(&self) -> Result<AppState> {
         Ok(self.state.lock().await.clone())
     }
@@ -441,4 +470,61 @@ mod tests {
         let workspace_id = service.get_last_selected_workspace().await;
         assert!(workspace_id.is_none());
     }
+
+    #[tokio::test]
+    async fn test_offline_mode_is_persisted() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let base = temp_dir.path().join("state");
+        let service = AppStateService::with_base_dir(Some(&base)).await.unwrap();
+        assert!(!service.get_offline_mode().await);
+
+        service.set_offline_mode(true).await.unwrap();
+
+        // `base` exists now, so the reload points at the written file
+        let state_file = base.join("config").join("app_state.json");
+        let reloaded = AppStateService::with_base_dir(Some(&state_file))
+            .await
+            .unwrap();
+        assert!(reloaded.get_offline_mode().await);
+    }
+
+    #[tokio::test]
+    async fn test_restore_snapshot_recovers_state() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let base = temp_dir.path().join("orcs");
+        let service = AppStateService::with_base_dir(Some(&base)).await.unwrap();
+        let store = crate::AppStateSnapshotStore::new(Some(&base))
+            .await
+            .unwrap();
+
+        service
+            .set_last_selected_workspace("ws-1".to_string())
+            .await
+            .unwrap();
+        let tab_id = service
+            .open_tab("session-1".to_string(), "ws-1".to_string())
+            .await
+            .unwrap();
+        store
+            .save("before-cleanup", service.snapshot().await)
+            .await
+            .unwrap();
+
+        service.close_tab(tab_id.clone()).await.unwrap();
+        service
+            .set_last_selected_workspace("ws-2".to_string())
+            .await
+            .unwrap();
+
+        let snapshot = store.load("before-cleanup").await.unwrap();
+        service.restore(snapshot).await.unwrap();
+
+        let state = service.get_state().await.unwrap();
+        assert_eq!(state.last_selected_workspace_id.as_deref(), Some("ws-1"));
+        assert_eq!(state.active_tab_id.as_deref(), Some(tab_id.as_str()));
+        assert_eq!(state.open_tabs.len(), 1);
+        assert_eq!(state.open_tabs[0].session_id, "session-1");
+
+        assert_eq!(store.list().await.unwrap()[0].name, "before-cleanup");
+    }
 }
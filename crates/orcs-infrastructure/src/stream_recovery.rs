@@ -0,0 +1,184 @@
+//! Write-ahead buffer for streamed assistant output.
+//!
+//! History is only persisted once a turn completes, so output that was already
+//! shown is lost if the app dies mid-turn. [`StreamRecoveryWriter`] mirrors
+//! streamed chunks into a per-session recovery file from a background task, and
+//! [`PartialTurnStore`] hands that file back on the next session load.
+
+use crate::paths::{OrcsPaths, ServiceType};
+use orcs_core::error::{OrcsError, Result};
+use orcs_core::session::PartialTurn;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Per-session recovery files (`<session_id>.json`).
+pub struct PartialTurnStore {
+    dir: PathBuf,
+}
+
+impl PartialTurnStore {
+    /// Creates a store under `base_dir`, or the default data directory when `None`.
+    pub async fn new(base_dir: Option<&Path>) -> Result<Self> {
+        let dir = OrcsPaths::new(base_dir)
+            .get_path(ServiceType::StreamRecovery)
+            .map_err(|e| OrcsError::config(e.to_string()))?
+            .into_path_buf();
+        tokio::fs::create_dir_all(&dir).await?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, session_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", session_id))
+    }
+
+    /// Writes the recovery file for the turn's session, replacing any previous one.
+    pub async fn save(&self, turn: &PartialTurn) -> Result<()> {
+        let path = self.path(&turn.session_id);
+        let content = serde_json::to_vec(turn)?;
+        // Write then rename so a crash never leaves a truncated file.
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, content).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    /// Loads the recovery file of a session, if any.
+    pub async fn load(&self, session_id: &str) -> Result<Option<PartialTurn>> {
+        match tokio::fs::read(self.path(session_id)).await {
+            Ok(content) => Ok(Some(serde_json::from_slice(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Deletes the recovery file of a session; a missing file is not an error.
+    pub async fn remove(&self, session_id: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path(session_id)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// When buffered chunks are flushed to the recovery file.
+#[derive(Debug, Clone, Copy)]
+pub struct FlushPolicy {
+    /// Flush once this many chunks are unflushed.
+    pub max_chunks: usize,
+    /// Flush pending chunks at least this often.
+    pub max_interval: Duration,
+}
+
+impl Default for FlushPolicy {
+    fn default() -> Self {
+        Self {
+            max_chunks: 8,
+            max_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+enum RecoveryCommand {
+    Chunk {
+        session_id: String,
+        author: String,
+        content: String,
+    },
+    Complete {
+        session_id: String,
+    },
+}
+
+/// Buffered turn of one session.
+struct TurnBuffer {
+    turn: PartialTurn,
+    unflushed: usize,
+}
+
+/// Feeds streamed chunks to a background task that maintains recovery files.
+///
+/// Sending never blocks or touches the disk. Cloning shares the same task.
+#[derive(Clone)]
+pub struct StreamRecoveryWriter {
+    tx: mpsc::UnboundedSender<RecoveryCommand>,
+}
+
+impl StreamRecoveryWriter {
+    /// Spawns the background flush task. Must be called within a Tokio runtime.
+    pub fn spawn(store: Arc<PartialTurnStore>, policy: FlushPolicy) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run_writer(store, policy, rx));
+        Self { tx }
+    }
+
+    /// Records a chunk of output shown for `session_id`'s in-flight turn.
+    pub fn record_chunk(&self, session_id: &str, author: &str, content: &str) {
+        let _ = self.tx.send(RecoveryCommand::Chunk {
+            session_id: session_id.to_string(),
+            author: author.to_string(),
+            content: content.to_string(),
+        });
+    }
+
+    /// Marks the turn as persisted: drops its buffer and recovery file.
+    pub fn complete(&self, session_id: &str) {
+        let _ = self.tx.send(RecoveryCommand::Complete {
+            session_id: session_id.to_string(),
+        });
+    }
+}
+
+async fn run_writer(
+    store: Arc<PartialTurnStore>,
+    policy: FlushPolicy,
+    mut rx: mpsc::UnboundedReceiver<RecoveryCommand>,
+) {
+    let mut buffers: HashMap<String, TurnBuffer> = HashMap::new();
+    let mut ticker = tokio::time::interval(policy.max_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            command = rx.recv() => match command {
+                Some(RecoveryCommand::Chunk { session_id, author, content }) => {
+                    let buffer = buffers.entry(session_id.clone()).or_insert_with(|| TurnBuffer {
+                        turn: PartialTurn::new(session_id),
+                        unflushed: 0,
+                    });
+                    buffer.turn.push(author, content);
+                    buffer.unflushed += 1;
+                    if buffer.unflushed >= policy.max_chunks {
+                        flush(&store, buffer).await;
+                    }
+                }
+                Some(RecoveryCommand::Complete { session_id }) => {
+                    buffers.remove(&session_id);
+                    if let Err(e) = store.remove(&session_id).await {
+                        tracing::warn!("[StreamRecovery] Failed to remove recovery file: {}", e);
+                    }
+                }
+                None => break,
+            },
+            _ = ticker.tick() => {
+                for buffer in buffers.values_mut().filter(|b| b.unflushed > 0) {
+                    flush(&store, buffer).await;
+                }
+            }
+        }
+    }
+
+    // All writers dropped: keep whatever is still pending.
+    for buffer in buffers.values_mut().filter(|b| b.unflushed > 0) {
+        flush(&store, buffer).await;
+    }
+}
+
+async fn flush(store: &PartialTurnStore, buffer: &mut TurnBuffer) {
+    match store.save(&buffer.turn).await {
+        Ok(()) => buffer.unflushed = 0,
+        Err(e) => tracing::warn!("[StreamRecovery] Failed to write recovery file: {}", e),
+    }
+}
@@ -166,44 +166,6 @@ impl UserService for ConfigBasedUserService {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_config_based_user_service() {
-        let service = ConfigBasedUserService::new();
-        let nickname = service.get_user_name();
-
-        // Should return either the configured nickname or default "You"
-        assert!(!nickname.is_empty());
-    }
-
-    #[test]
-    fn test_nickname_is_cached() {
-        let service = ConfigBasedUserService::new();
-
-        // First call loads from config
-        let first = service.get_user_name();
-
-        // Second call should use cache
-        let second = service.get_user_name();
-
-        assert_eq!(first, second);
-    }
-
-    #[test]
-    fn test_load_root_config() {
-        let result = load_root_config();
-
-        // Should either load successfully or create default
-        assert!(result.is_ok());
-
-        let config = result.unwrap();
-        assert!(!config.user_profile.nickname.is_empty());
-    }
-}
-
 /// Loads the complete RootConfig from config.toml.
 ///
 /// This function is useful for accessing configuration settings like EnvSettings,
@@ -304,3 +266,72 @@ pub fn save_root_config(config: RootConfig) -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_based_user_service() {
+        let service = ConfigBasedUserService::new();
+        let nickname = service.get_user_name();
+
+        // Should return either the configured nickname or default "You"
+        assert!(!nickname.is_empty());
+    }
+
+    #[test]
+    fn test_nickname_is_cached() {
+        let service = ConfigBasedUserService::new();
+
+        // First call loads from config
+        let first = service.get_user_name();
+
+        // Second call should use cache
+        let second = service.get_user_name();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_load_root_config() {
+        let result = load_root_config();
+
+        // Should either load successfully or create default
+        assert!(result.is_ok());
+
+        let config = result.unwrap();
+        assert!(!config.user_profile.nickname.is_empty());
+    }
+
+    #[test]
+    fn test_default_models_survive_toml_roundtrip() {
+        use orcs_core::persona::PersonaBackend;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let strategy = || {
+            FileStorageStrategy::new()
+                .with_format(FormatStrategy::Toml)
+                .with_load_behavior(LoadBehavior::CreateIfMissing)
+        };
+
+        let mut config = RootConfig::default();
+        config
+            .default_models
+            .insert(PersonaBackend::OpenAiApi, "gpt-4o".to_string());
+        config
+            .default_models
+            .insert(PersonaBackend::GeminiCli, "gemini-2.5-flash".to_string());
+
+        let mut storage =
+            FileStorage::new(path.clone(), create_config_root_migrator(), strategy()).unwrap();
+        storage
+            .update_and_save("config_root", vec![config.clone()])
+            .unwrap();
+
+        let storage = FileStorage::new(path, create_config_root_migrator(), strategy()).unwrap();
+        let loaded: Vec<RootConfig> = storage.query("config_root").unwrap();
+        assert_eq!(loaded[0].default_models, config.default_models);
+    }
+}
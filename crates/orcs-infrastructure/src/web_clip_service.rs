@@ -0,0 +1,576 @@
+//! Fetches web pages and extracts their readable text for use as context.
+//!
+//! Downloads are capped in size and time. Readable text is extracted with a
+//! simple heuristic: scripts, styles, navigation, headers, footers and forms
+//! are dropped, `<article>` or `<main>` is preferred over the whole body, and
+//! block elements become paragraphs.
+//!
+//! Pages that answer with an error status (blocked or paywalled pages usually
+//! do) still yield whatever text they returned, with a warning attached.
+
+use orcs_core::error::{OrcsError, Result};
+use orcs_core::offline::OfflineMode;
+use reqwest::header::{CONTENT_TYPE, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Largest response body read from a page.
+pub const DEFAULT_MAX_BYTES: usize = 2 * 1024 * 1024;
+/// Timeout for the whole request, including the body.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
+/// User agent sent with every request.
+pub const DEFAULT_USER_AGENT: &str = concat!("orcs-web-clipper/", env!("CARGO_PKG_VERSION"));
+
+/// Content types accepted as HTML.
+const HTML_CONTENT_TYPES: &[&str] = &["text/html", "application/xhtml+xml"];
+/// Elements whose content is never part of the readable text.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "nav", "header", "footer", "aside",
+    "form",
+];
+/// Elements that start a new paragraph.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p",
+    "div",
+    "br",
+    "li",
+    "ul",
+    "ol",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "tr",
+    "table",
+    "section",
+    "article",
+    "main",
+    "blockquote",
+    "pre",
+    "figcaption",
+    "dt",
+    "dd",
+    "hr",
+];
+/// Longest lead paragraph used as a fallback summary, in characters.
+const LEAD_PARAGRAPH_MAX_CHARS: usize = 400;
+
+/// Readable text extracted from a web page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebClip {
+    pub url: String,
+    pub title: String,
+    /// Paragraphs separated by blank lines
+    pub text: String,
+    /// Problems that made the text incomplete (error status, size cap)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl WebClip {
+    /// Returns the first paragraph that is not just the title, shortened if long.
+    ///
+    /// Used as the summary when no LLM summary is available.
+    pub fn lead_paragraph(&self) -> String {
+        let paragraph = self
+            .text
+            .split("\n\n")
+            .find(|p| p.trim() != self.title.trim())
+            .unwrap_or_default()
+            .trim();
+        if paragraph.chars().count() <= LEAD_PARAGRAPH_MAX_CHARS {
+            return paragraph.to_string();
+        }
+        let shortened: String = paragraph.chars().take(LEAD_PARAGRAPH_MAX_CHARS).collect();
+        format!("{}…", shortened.trim_end())
+    }
+
+    /// File name for storing the clip, derived from the title.
+    pub fn file_name(&self) -> String {
+        let mut slug = String::new();
+        for c in self.title.chars().flat_map(char::to_lowercase) {
+            if c.is_alphanumeric() {
+                slug.push(c);
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+            if slug.chars().count() >= 60 {
+                break;
+            }
+        }
+        let slug = slug.trim_end_matches('-');
+        if slug.is_empty() {
+            "web-clip.md".to_string()
+        } else {
+            format!("{}.md", slug)
+        }
+    }
+
+    /// Markdown document with the title, source URL and the extracted text.
+    pub fn to_markdown(&self) -> String {
+        let mut doc = format!("# {}\n\nSource: {}\n", self.title, self.url);
+        for warning in &self.warnings {
+            doc.push_str(&format!("> Warning: {}\n", warning));
+        }
+        doc.push('\n');
+        doc.push_str(&self.text);
+        doc.push('\n');
+        doc
+    }
+}
+
+/// Fetches web pages and extracts their readable text.
+pub struct WebClipService {
+    client: reqwest::Client,
+    max_bytes: usize,
+    timeout: Duration,
+    user_agent: String,
+    offline_mode: OfflineMode,
+}
+
+impl WebClipService {
+    /// Creates a service with the default size cap, timeout and user agent.
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            max_bytes: DEFAULT_MAX_BYTES,
+            timeout: DEFAULT_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            offline_mode: OfflineMode::default(),
+        }
+    }
+
+    /// Sets the largest response body that is read; the rest is dropped.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Sets the timeout for the whole request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the user agent sent with every request.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Refuses to fetch pages while `offline_mode` is on.
+    pub fn with_offline_mode(mut self, offline_mode: OfflineMode) -> Self {
+        self.offline_mode = offline_mode;
+        self
+    }
+
+    /// Fetches `url` and extracts its title and readable text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if offline mode is on, the URL is not http(s), the
+    /// request fails, the page is not HTML, or an error status came without
+    /// any readable text.
+    pub async fn clip(&self, url: &str) -> Result<WebClip> {
+        self.offline_mode.ensure_online("Web clipping")?;
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|e| OrcsError::data_access(format!("Invalid URL '{}': {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(OrcsError::data_access(format!(
+                "Only http(s) pages can be clipped: {}",
+                url
+            )));
+        }
+        let fetch_error =
+            |e: reqwest::Error| OrcsError::io(format!("Failed to fetch {}: {}", url, e));
+
+        let mut response = self
+            .client
+            .get(parsed)
+            .timeout(self.timeout)
+            .header(USER_AGENT, &self.user_agent)
+            .send()
+            .await
+            .map_err(fetch_error)?;
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| {
+                v.split(';')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_lowercase()
+            });
+        if let Some(content_type) = &content_type
+            && !HTML_CONTENT_TYPES.contains(&content_type.as_str())
+        {
+            return Err(OrcsError::data_access(format!(
+                "{} is {}, not an HTML page; only web pages can be clipped",
+                url, content_type
+            )));
+        }
+
+        let mut warnings = Vec::new();
+        let status = response.status();
+        if !status.is_success() {
+            warnings.push(format!(
+                "The page answered {}; it may be blocked or paywalled, so only the text it returned was clipped",
+                status
+            ));
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(fetch_error)? {
+            let room = self.max_bytes - body.len();
+            if chunk.len() > room {
+                body.extend_from_slice(&chunk[..room]);
+                warnings.push(format!(
+                    "The page is larger than {} bytes; only the beginning was clipped",
+                    self.max_bytes
+                ));
+                break;
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        let html = String::from_utf8_lossy(&body);
+        let text = extract_readable_text(&html);
+        if text.is_empty() && !status.is_success() {
+            return Err(OrcsError::io(format!(
+                "Failed to fetch {}: {} with no readable text",
+                url, status
+            )));
+        }
+        let title = extract_title(&html).unwrap_or_else(|| url.to_string());
+
+        Ok(WebClip {
+            url: url.to_string(),
+            title,
+            text,
+            warnings,
+        })
+    }
+}
+
+impl Default for WebClipService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the `<title>`, or the first `<h1>` if there is none.
+fn extract_title(html: &str) -> Option<String> {
+    ["title", "h1"].iter().find_map(|tag| {
+        let inner = element_inner(html, tag)?;
+        let title = collapse_whitespace(&decode_entities(&strip_tags(inner)));
+        (!title.is_empty()).then_some(title)
+    })
+}
+
+/// Extracts paragraphs of readable text from an HTML document.
+fn extract_readable_text(html: &str) -> String {
+    let mut cleaned = remove_between(html, "<!--", "-->");
+    for tag in SKIPPED_ELEMENTS {
+        cleaned = remove_element(&cleaned, tag);
+    }
+    let content = ["article", "main", "body"]
+        .iter()
+        .find_map(|tag| element_inner(&cleaned, tag))
+        .unwrap_or(&cleaned);
+
+    let mut text = String::new();
+    let mut rest = content;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        if BLOCK_ELEMENTS.contains(&tag_name(tag).as_str()) {
+            text.push('\n');
+        } else {
+            text.push(' ');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    decode_entities(&text)
+        .lines()
+        .map(collapse_whitespace)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Lowercased element name of a tag body such as `div class="x"` or `/p`.
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Returns the content between the first `<tag ...>` and its closing tag.
+fn element_inner<'a>(html: &'a str, tag: &str) -> Option<&'a str> {
+    let lower = html.to_ascii_lowercase();
+    let open = find_open_tag(&lower, tag, 0)?;
+    let content_start = open + lower[open..].find('>')? + 1;
+    let close = lower[content_start..]
+        .find(&format!("</{}", tag))
+        .map_or(html.len(), |i| content_start + i);
+    Some(&html[content_start..close])
+}
+
+/// Removes every `<tag ...>...</tag>` element, including unclosed trailing ones.
+fn remove_element(html: &str, tag: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let close_tag = format!("</{}", tag);
+    let mut result = String::with_capacity(html.len());
+    let mut pos = 0;
+    while let Some(open) = find_open_tag(&lower, tag, pos) {
+        result.push_str(&html[pos..open]);
+        pos = match lower[open..].find(&close_tag) {
+            Some(i) => {
+                let close = open + i;
+                lower[close..]
+                    .find('>')
+                    .map_or(html.len(), |j| close + j + 1)
+            }
+            None => html.len(),
+        };
+    }
+    result.push_str(&html[pos..]);
+    result
+}
+
+/// Finds `<tag` followed by a delimiter (so `<p` does not match `<pre`).
+fn find_open_tag(lower: &str, tag: &str, from: usize) -> Option<usize> {
+    let needle = format!("<{}", tag);
+    let mut pos = from;
+    while let Some(i) = lower[pos..].find(&needle) {
+        let at = pos + i;
+        let next = lower[at + needle.len()..].chars().next();
+        if matches!(next, Some(c) if c == '>' || c == '/' || c.is_whitespace()) {
+            return Some(at);
+        }
+        pos = at + needle.len();
+    }
+    None
+}
+
+fn remove_between(text: &str, open: &str, close: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(open) {
+        result.push_str(&rest[..start]);
+        rest = match rest[start..].find(close) {
+            Some(end) => &rest[start + end + close.len()..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+    result
+}
+
+fn strip_tags(html: &str) -> String {
+    remove_between(html, "<", ">")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decodes the common named entities and numeric character references.
+fn decode_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let decoded = after.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &after[..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" | "#39" => Some('\''),
+                "nbsp" => Some(' '),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                "hellip" => Some('…'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const ARTICLE_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+  <title>Error Handling in Rust &amp; Beyond</title>
+  <style>body { color: red; }</style>
+  <script>var tracking = "do not include";</script>
+</head>
+<body>
+  <header><a href="/">Home</a> | <a href="/blog">Blog</a></header>
+  <nav><ul><li>Menu item</li></ul></nav>
+  <article>
+    <h1>Error Handling in Rust &amp; Beyond</h1>
+    <p>Rust uses <code>Result</code> to make failures explicit.</p>
+    <!-- comment that should vanish -->
+    <p>The <em>?</em> operator propagates errors &mdash; without ceremony.</p>
+    <script>alert("inline");</script>
+  </article>
+  <footer>Copyright 2026</footer>
+</body>
+</html>"#;
+
+    /// Answers every request with `status`, `content_type` and `body`.
+    /// Returns the URL and the last request received.
+    async fn spawn_page_server(
+        status: &'static str,
+        content_type: &'static str,
+        body: String,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/article", listener.local_addr().unwrap());
+        let last_request = std::sync::Arc::new(std::sync::Mutex::new(String::new()));
+        let recorded = last_request.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut buf = vec![0u8; 8192];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                *recorded.lock().unwrap() = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    content_type,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        (url, last_request)
+    }
+
+    #[tokio::test]
+    async fn test_clip_extracts_readable_article_text() {
+        let (url, last_request) = spawn_page_server(
+            "200 OK",
+            "text/html; charset=utf-8",
+            ARTICLE_HTML.to_string(),
+        )
+        .await;
+
+        let clip = WebClipService::new()
+            .with_user_agent("clip-test/1.0")
+            .clip(&url)
+            .await
+            .unwrap();
+
+        assert_eq!(clip.title, "Error Handling in Rust & Beyond");
+        assert_eq!(
+            clip.text,
+            "Error Handling in Rust & Beyond\n\n\
+             Rust uses Result to make failures explicit.\n\n\
+             The ? operator propagates errors — without ceremony."
+        );
+        assert!(clip.warnings.is_empty());
+        assert_eq!(
+            clip.lead_paragraph(),
+            "Rust uses Result to make failures explicit."
+        );
+        assert_eq!(clip.file_name(), "error-handling-in-rust-beyond.md");
+        assert!(
+            last_request
+                .lock()
+                .unwrap()
+                .contains("user-agent: clip-test/1.0")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clip_rejects_non_html_content() {
+        let (url, _) = spawn_page_server("200 OK", "application/pdf", "%PDF-1.7".to_string()).await;
+
+        let error = WebClipService::new().clip(&url).await.unwrap_err();
+        assert!(error.to_string().contains("application/pdf"));
+        assert!(error.to_string().contains("not an HTML page"));
+    }
+
+    #[tokio::test]
+    async fn test_clip_is_refused_while_offline() {
+        let (url, last_request) =
+            spawn_page_server("200 OK", "text/html", "<p>Hi</p>".to_string()).await;
+        let offline_mode = OfflineMode::new(true);
+        let service = WebClipService::new().with_offline_mode(offline_mode.clone());
+
+        let error = service.clip(&url).await.unwrap_err();
+        assert!(error.is_backend_unavailable());
+        assert!(last_request.lock().unwrap().is_empty());
+
+        offline_mode.set(false);
+        assert!(service.clip(&url).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_clip_keeps_partial_text_of_blocked_page() {
+        let paywall = "<html><head><title>Members only</title></head><body>\
+            <main><p>The first paragraph is free to read.</p>\
+            <div class=\"paywall\">Subscribe to continue.</div></main></body></html>";
+        let (url, _) = spawn_page_server("403 Forbidden", "text/html", paywall.to_string()).await;
+
+        let clip = WebClipService::new()
+            .with_max_bytes(paywall.find("Subscribe").unwrap())
+            .clip(&url)
+            .await
+            .unwrap();
+
+        assert_eq!(clip.title, "Members only");
+        assert_eq!(clip.text, "The first paragraph is free to read.");
+        assert_eq!(clip.warnings.len(), 2);
+        assert!(clip.warnings[0].contains("403"));
+        assert!(clip.warnings[1].contains("only the beginning"));
+    }
+}
@@ -0,0 +1,147 @@
+//! Shared `.orcsignore` rules of workspace roots.
+//!
+//! Paths a workspace lists in its `.orcsignore` (see [`crate::search::ignore`]
+//! for the syntax) never reach agents: search leaves them out, the file list
+//! hides them and attaching one needs the user's confirmation. Unlike
+//! `.gitignore`, the file can exclude committed paths such as large data
+//! directories or local secrets.
+//!
+//! Rules are parsed once per root and reloaded when the ignore file changes,
+//! so edits apply without a restart.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use crate::search::ignore::{IGNORE_FILE_NAME, IgnoreRules};
+
+/// Modification time and length of an ignore file; `None` when it is missing.
+type FileStamp = Option<(Option<SystemTime>, u64)>;
+
+struct CachedRules {
+    stamp: FileStamp,
+    rules: Arc<IgnoreRules>,
+}
+
+/// The rules a workspace root applies, for debugging ignore files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveIgnoreRules {
+    /// Path of the root's ignore file
+    pub ignore_file: PathBuf,
+    /// Whether the ignore file exists (only the built-in excludes apply otherwise)
+    pub ignore_file_exists: bool,
+    /// Rules in the order they apply; the last matching rule decides
+    pub patterns: Vec<String>,
+}
+
+/// Cache of the ignore rules of every workspace root seen so far.
+#[derive(Default)]
+pub struct WorkspaceIgnore {
+    cache: RwLock<HashMap<PathBuf, CachedRules>>,
+}
+
+impl WorkspaceIgnore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the rules of `root`, reloading them if its ignore file changed.
+    pub fn rules(&self, root: &Path) -> Arc<IgnoreRules> {
+        let stamp = file_stamp(&root.join(IGNORE_FILE_NAME));
+        if let Some(cached) = self.cache.read().unwrap().get(root)
+            && cached.stamp == stamp
+        {
+            return cached.rules.clone();
+        }
+
+        let rules = Arc::new(IgnoreRules::load(root));
+        self.cache.write().unwrap().insert(
+            root.to_path_buf(),
+            CachedRules {
+                stamp,
+                rules: rules.clone(),
+            },
+        );
+        rules
+    }
+
+    /// Whether `path` (absolute, or relative to `root`) is excluded by the
+    /// rules of `root`. Paths outside `root` are never excluded.
+    pub fn is_ignored(&self, root: &Path, path: &Path) -> bool {
+        let relative = if path.is_absolute() {
+            match path.strip_prefix(root) {
+                Ok(relative) => relative,
+                Err(_) => return false,
+            }
+        } else {
+            path
+        };
+        self.rules(root).is_ignored(relative)
+    }
+
+    /// Returns the given paths that the rules of `root` exclude.
+    pub fn ignored_paths<'a>(&self, root: &Path, paths: &'a [String]) -> Vec<&'a str> {
+        paths
+            .iter()
+            .map(String::as_str)
+            .filter(|path| self.is_ignored(root, Path::new(path)))
+            .collect()
+    }
+
+    /// Describes the rules `root` currently applies.
+    pub fn effective_rules(&self, root: &Path) -> EffectiveIgnoreRules {
+        let ignore_file = root.join(IGNORE_FILE_NAME);
+        EffectiveIgnoreRules {
+            ignore_file_exists: ignore_file.is_file(),
+            ignore_file,
+            patterns: self
+                .rules(root)
+                .patterns()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rules_are_reloaded_when_the_ignore_file_changes() {
+        let workspace = TempDir::new().unwrap();
+        let root = workspace.path();
+        let ignore = WorkspaceIgnore::new();
+
+        assert!(!ignore.is_ignored(root, Path::new("data/dump.csv")));
+        assert!(!ignore.effective_rules(root).ignore_file_exists);
+
+        std::fs::write(root.join(IGNORE_FILE_NAME), "data/\nsecrets/.env\n").unwrap();
+        assert!(ignore.is_ignored(root, &root.join("data/dump.csv")));
+        assert!(ignore.is_ignored(root, Path::new("secrets/.env")));
+        assert!(!ignore.is_ignored(root, Path::new("src/main.rs")));
+        assert!(!ignore.is_ignored(root, Path::new("/elsewhere/data/dump.csv")));
+
+        let paths = vec![
+            root.join("secrets/.env").to_string_lossy().into_owned(),
+            root.join("README.md").to_string_lossy().into_owned(),
+        ];
+        assert_eq!(ignore.ignored_paths(root, &paths), vec![paths[0].as_str()]);
+
+        std::fs::write(root.join(IGNORE_FILE_NAME), "secrets/.env\n").unwrap();
+        assert!(!ignore.is_ignored(root, Path::new("data/dump.csv")));
+        let effective = ignore.effective_rules(root);
+        assert!(effective.ignore_file_exists);
+        assert_eq!(effective.patterns.last().unwrap(), "secrets/.env");
+    }
+}
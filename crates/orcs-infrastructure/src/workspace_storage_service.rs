@@ -119,7 +119,7 @@ impl FileSystemWorkspaceManager {
         })?;
 
         // Initialize AsyncDirWorkspaceRepository
-        let workspace_repository = Arc::new(AsyncDirWorkspaceRepository::default().await?);
+        let workspace_repository = Arc::new(AsyncDirWorkspaceRepository::new(root_path).await?);
 
         Ok(Self {
             root_path: path.clone(),
@@ -1238,8 +1238,8 @@ mod tests {
             .unwrap();
 
         // Verify the temp file metadata
-        assert!(temp_file.id.len() > 0);
-        assert_eq!(temp_file.auto_delete, true);
+        assert!(!temp_file.id.is_empty());
+        assert!(temp_file.auto_delete);
         assert!(temp_file.created_at > 0);
 
         // Verify the file was created at the correct location
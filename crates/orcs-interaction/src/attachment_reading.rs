@@ -0,0 +1,158 @@
+//! Reading of attached files before a turn.
+//!
+//! Attached files are read into memory before the dialogue lock is taken, a
+//! few at a time, so that large attachments do not block other operations on
+//! the session while a turn is being prepared. Agents then receive the same
+//! bytes, file name and MIME type they would have loaded from the path.
+
+use async_trait::async_trait;
+use llm_toolkit::attachment::Attachment;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// Most attached files read at the same time.
+pub const MAX_CONCURRENT_READS: usize = 4;
+
+/// Source of attached file contents (replaceable in tests).
+#[async_trait]
+pub trait AttachmentReader: Send + Sync {
+    /// Reads the whole file at `path`.
+    async fn read(&self, path: &str) -> std::io::Result<Vec<u8>>;
+}
+
+/// Reader backed by the file system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FsAttachmentReader;
+
+#[async_trait]
+impl AttachmentReader for FsAttachmentReader {
+    async fn read(&self, path: &str) -> std::io::Result<Vec<u8>> {
+        tokio::fs::read(path).await
+    }
+}
+
+/// Reads the files at `paths` with `reader`, at most [`MAX_CONCURRENT_READS`]
+/// at a time, and returns their attachments in the order of `paths`.
+///
+/// A file that cannot be read is attached by path, so the agent reports the
+/// failure as it would have without reading ahead.
+pub async fn read_attachments(
+    reader: Arc<dyn AttachmentReader>,
+    paths: Vec<String>,
+) -> Vec<Attachment> {
+    let mut attachments: Vec<Option<Attachment>> = paths.iter().map(|_| None).collect();
+    let mut pending = paths.iter().cloned().enumerate();
+    let mut reads = JoinSet::new();
+
+    loop {
+        while reads.len() < MAX_CONCURRENT_READS {
+            let Some((index, path)) = pending.next() else {
+                break;
+            };
+            let reader = Arc::clone(&reader);
+            reads.spawn(async move {
+                let bytes = reader.read(&path).await;
+                (index, path, bytes)
+            });
+        }
+        let Some(joined) = reads.join_next().await else {
+            break;
+        };
+        match joined {
+            Ok((index, path, Ok(bytes))) => {
+                tracing::info!("[InteractionManager] Attaching file: {}", path);
+                let local = Attachment::local(&path);
+                attachments[index] = Some(Attachment::in_memory_with_meta(
+                    bytes,
+                    local.file_name(),
+                    local.mime_type(),
+                ));
+            }
+            Ok((index, path, Err(e))) => {
+                tracing::warn!(
+                    "[InteractionManager] Failed to read attachment {}: {}",
+                    path,
+                    e
+                );
+                attachments[index] = Some(Attachment::local(path));
+            }
+            Err(e) => tracing::warn!("[InteractionManager] Attachment read panicked: {}", e),
+        }
+    }
+
+    // Reads that panicked leave their slot empty
+    attachments
+        .into_iter()
+        .zip(paths)
+        .map(|(attachment, path)| attachment.unwrap_or_else(|| Attachment::local(path)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Reader that answers slowly and records how many reads overlap.
+    #[derive(Default)]
+    struct SlowReader {
+        in_flight: Mutex<(usize, usize)>,
+    }
+
+    #[async_trait]
+    impl AttachmentReader for SlowReader {
+        async fn read(&self, path: &str) -> std::io::Result<Vec<u8>> {
+            {
+                let mut counts = self.in_flight.lock().unwrap();
+                counts.0 += 1;
+                counts.1 = counts.1.max(counts.0);
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.lock().unwrap().0 -= 1;
+            Ok(path.as_bytes().to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_attachments_is_bounded_and_keeps_order() {
+        let reader = Arc::new(SlowReader::default());
+        let paths: Vec<String> = (0..10).map(|n| format!("/notes/{}.txt", n)).collect();
+
+        let attachments = read_attachments(reader.clone(), paths.clone()).await;
+
+        assert_eq!(reader.in_flight.lock().unwrap().1, MAX_CONCURRENT_READS);
+        assert_eq!(attachments.len(), paths.len());
+        for (attachment, path) in attachments.iter().zip(&paths) {
+            assert_eq!(attachment.load_bytes().await.unwrap(), path.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_attachments_match_attachments_by_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let notes = dir.path().join("notes.md");
+        let image = dir.path().join("diagram.png");
+        std::fs::write(&notes, "# Plan\n- parser first\n").unwrap();
+        std::fs::write(&image, [0x89, b'P', b'N', b'G']).unwrap();
+        let missing = dir.path().join("missing.txt");
+        let paths: Vec<String> = [&notes, &image, &missing]
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+
+        let attachments = read_attachments(Arc::new(FsAttachmentReader), paths.clone()).await;
+
+        for (attachment, path) in attachments.iter().zip(&paths[..2]) {
+            let local = Attachment::local(path);
+            assert_eq!(attachment.file_name(), local.file_name());
+            assert_eq!(attachment.mime_type(), local.mime_type());
+            assert_eq!(
+                attachment.load_bytes().await.unwrap(),
+                local.load_bytes().await.unwrap()
+            );
+        }
+        // Unreadable files are left for the agent to report
+        assert!(matches!(&attachments[2], Attachment::Local(path) if *path == missing));
+    }
+}
@@ -0,0 +1,202 @@
+//! Pacing between AutoChat iterations.
+//!
+//! Iterations are spaced by a fixed delay unless adaptive pacing is enabled.
+//! Adaptive spacing doubles after an iteration that hit a rate limit and halves
+//! back toward the configured delay after clean ones; time the previous
+//! iteration already took counts toward the wait.
+
+use llm_toolkit::agent::AgentError;
+use orcs_core::session::AutoChatConfig;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Delay between iterations when none is configured.
+pub const DEFAULT_ITERATION_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound of the adaptive spacing.
+pub const MAX_ADAPTIVE_DELAY: Duration = Duration::from_secs(60);
+
+/// Smallest spacing after a rate limit.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How often the cancel flag is checked while waiting.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Kind of a failed agent turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// The backend rejected the request for exceeding its rate limit (HTTP 429).
+    RateLimited,
+    Other,
+}
+
+impl FailureKind {
+    /// Classifies an agent error.
+    pub fn of(error: &AgentError) -> Self {
+        let status_code = match error {
+            AgentError::ProcessError { status_code, .. }
+            | AgentError::ProcessErrorRich { status_code, .. } => *status_code,
+            _ => None,
+        };
+        if status_code == Some(429) {
+            return Self::RateLimited;
+        }
+
+        // CLI backends only report the provider's message
+        let message = error.to_string().to_lowercase();
+        if message.contains("rate limit") || message.contains("too many requests") {
+            Self::RateLimited
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Computes the delay before each AutoChat iteration.
+#[derive(Debug, Clone)]
+pub struct AutoChatPacer {
+    base: Duration,
+    adaptive: bool,
+    /// Current target time between iteration starts (adaptive mode only).
+    spacing: Duration,
+}
+
+impl AutoChatPacer {
+    pub fn new(config: &AutoChatConfig) -> Self {
+        let base = config
+            .iteration_delay_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_ITERATION_DELAY);
+        Self {
+            base,
+            adaptive: config.adaptive_delay,
+            spacing: base,
+        }
+    }
+
+    /// Returns the delay before the next iteration, given how long the previous
+    /// one took and the failures observed during it.
+    pub fn next_delay(&mut self, elapsed: Duration, failures: &[FailureKind]) -> Duration {
+        if !self.adaptive {
+            return self.base;
+        }
+
+        if failures.contains(&FailureKind::RateLimited) {
+            let cap = MAX_ADAPTIVE_DELAY.max(self.base);
+            self.spacing = (self.spacing * 2).clamp(MIN_BACKOFF, cap);
+        } else {
+            self.spacing = (self.spacing / 2).max(self.base);
+        }
+        self.spacing.saturating_sub(elapsed)
+    }
+}
+
+/// Sleeps for `delay`, waking early once `cancel_flag` is set.
+///
+/// Returns `true` when the wait was cancelled.
+pub async fn sleep_unless_cancelled(delay: Duration, cancel_flag: Option<&AtomicBool>) -> bool {
+    let Some(flag) = cancel_flag else {
+        tokio::time::sleep(delay).await;
+        return false;
+    };
+
+    let sleep = tokio::time::sleep(delay);
+    tokio::pin!(sleep);
+    let mut poll = tokio::time::interval(CANCEL_POLL_INTERVAL);
+    loop {
+        if flag.load(Ordering::SeqCst) {
+            return true;
+        }
+        tokio::select! {
+            _ = &mut sleep => return false,
+            _ = poll.tick() => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn adaptive_config(delay_ms: u64) -> AutoChatConfig {
+        AutoChatConfig {
+            iteration_delay_ms: Some(delay_ms),
+            adaptive_delay: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fixed_delay_ignores_failures() {
+        let mut pacer = AutoChatPacer::new(&AutoChatConfig::default());
+        assert_eq!(
+            pacer.next_delay(Duration::ZERO, &[FailureKind::RateLimited]),
+            DEFAULT_ITERATION_DELAY
+        );
+
+        let mut pacer = AutoChatPacer::new(&AutoChatConfig {
+            iteration_delay_ms: Some(0),
+            ..Default::default()
+        });
+        assert_eq!(pacer.next_delay(Duration::ZERO, &[]), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_adaptive_delay_backs_off_to_cap_and_recovers() {
+        let mut pacer = AutoChatPacer::new(&adaptive_config(200));
+        let rate_limited = [FailureKind::RateLimited];
+
+        let delays: Vec<u64> = (0..8)
+            .map(|_| pacer.next_delay(Duration::ZERO, &rate_limited).as_secs())
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+
+        // Other failures do not back off; the iteration's duration counts toward the wait
+        assert_eq!(
+            pacer.next_delay(Duration::from_secs(10), &[FailureKind::Other]),
+            Duration::from_secs(20)
+        );
+        for _ in 0..8 {
+            pacer.next_delay(Duration::ZERO, &[]);
+        }
+        assert_eq!(
+            pacer.next_delay(Duration::ZERO, &[]),
+            Duration::from_millis(200)
+        );
+    }
+
+    #[test]
+    fn test_classifies_rate_limits() {
+        assert_eq!(
+            FailureKind::of(&AgentError::process_error(429, "slow down", true)),
+            FailureKind::RateLimited
+        );
+        assert_eq!(
+            FailureKind::of(&AgentError::ExecutionFailed(
+                "API Error: Rate limit reached".to_string()
+            )),
+            FailureKind::RateLimited
+        );
+        assert_eq!(
+            FailureKind::of(&AgentError::process_error(500, "boom", true)),
+            FailureKind::Other
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancel_interrupts_long_delay() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let setter = flag.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            setter.store(true, Ordering::SeqCst);
+        });
+
+        let start = tokio::time::Instant::now();
+        assert!(sleep_unless_cancelled(MAX_ADAPTIVE_DELAY, Some(&flag)).await);
+        assert!(start.elapsed() < Duration::from_secs(3));
+
+        assert!(!sleep_unless_cancelled(Duration::from_secs(1), None).await);
+    }
+}
@@ -0,0 +1,676 @@
+//! Agent that runs a persona's turns on its configured backend.
+//!
+//! [`PersonaBackendAgent`] builds the CLI agent or API client of the persona's
+//! backend for every turn. API clients can be built ahead of the first turn by
+//! [`warm_up_backend`], which keeps them in the session's [`WarmClients`].
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use llm_toolkit::agent::impls::{ClaudeCodeAgent, CodexAgent, GeminiAgent};
+use llm_toolkit::agent::{Agent, AgentError, Payload};
+use orcs_core::agent::build_enhanced_path;
+use orcs_core::config::EnvSettings;
+use orcs_core::offline::OfflineMode;
+use orcs_core::persona::{CLAUDE_CLI_WRITE_TOOLS, PersonaBackend, claude_cli_allowed_tools};
+use orcs_core::session::LlmDebugInfo;
+use orcs_core::user::UserService;
+use orcs_infrastructure::{WireLog, WireTap};
+use tokio::sync::RwLock;
+
+use crate::http_proxy::build_http_client;
+use crate::turn_stats::{UsageCapture, UsageSink};
+use crate::{ClaudeApiAgent, GeminiApiAgent, KaibaApiAgent, OpenAIApiAgent};
+
+/// Upper bound of each captured prompt and raw output, in bytes.
+const MAX_RAW_OUTPUT_BYTES: usize = 64 * 1024;
+
+/// Truncates captured text to [`MAX_RAW_OUTPUT_BYTES`], noting how much was dropped.
+fn truncate_raw_output(mut text: String) -> String {
+    if text.len() <= MAX_RAW_OUTPUT_BYTES {
+        return text;
+    }
+    let mut end = MAX_RAW_OUTPUT_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let dropped = text.len() - end;
+    text.truncate(end);
+    text.push_str(&format!("\n... [{} bytes truncated]", dropped));
+    text
+}
+
+/// Collects a persona's raw CLI output for the turn's `llm_debug_info`.
+///
+/// Capture is gated by `DebugSettings::enable_llm_debug`, checked on every run
+/// so toggling debug mode applies without rebuilding the dialogue.
+#[derive(Clone)]
+pub(crate) struct RawOutputCapture {
+    pub(crate) persona_id: String,
+    pub(crate) user_service: Arc<dyn UserService>,
+    /// Latest capture per persona ID, taken when the turn is added to history
+    pub(crate) outputs: Arc<RwLock<HashMap<String, LlmDebugInfo>>>,
+}
+
+impl std::fmt::Debug for RawOutputCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawOutputCapture")
+            .field("persona_id", &self.persona_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RawOutputCapture {
+    fn is_enabled(&self) -> bool {
+        self.user_service.get_debug_settings().enable_llm_debug
+    }
+
+    async fn record(&self, info: LlmDebugInfo) {
+        self.outputs
+            .write()
+            .await
+            .insert(self.persona_id.clone(), info);
+    }
+}
+
+/// Session and persona a persona agent's backend exchanges are wire-logged under.
+#[derive(Clone, Debug)]
+pub(crate) struct WireLogTarget {
+    pub(crate) log: WireLog,
+    pub(crate) session_id: String,
+    pub(crate) persona_id: String,
+}
+
+impl WireLogTarget {
+    /// Starts a turn's tap, or `None` while the session's wire log is off.
+    fn tap(&self, backend: &PersonaBackend) -> Option<WireTap> {
+        self.log
+            .tap(&self.session_id, &self.persona_id, backend.as_str())
+    }
+}
+
+/// Debug captures of a persona agent's backend exchanges.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BackendCaptures {
+    pub(crate) raw_output: Option<RawOutputCapture>,
+    pub(crate) wire_log: Option<WireLogTarget>,
+    pub(crate) usage: Option<UsageCapture>,
+}
+
+/// Agent wrapper that delegates to the configured backend.
+#[derive(Clone, Debug)]
+pub(crate) struct PersonaBackendAgent {
+    backend: PersonaBackend,
+    model_name: Option<String>,
+    gemini_options: Option<orcs_core::persona::GeminiOptions>,
+    kaiba_options: Option<orcs_core::persona::KaibaOptions>,
+    workspace_root: Arc<RwLock<Option<PathBuf>>>,
+    env_settings: Arc<RwLock<EnvSettings>>,
+    captures: BackendCaptures,
+    /// Withholds file-editing tools from Claude CLI runs
+    reviewer_only: bool,
+    /// Refuses API backends while offline mode is on
+    offline_mode: OfflineMode,
+    /// Session seed, read on every run so changes apply without a rebuild
+    random_seed: Arc<RwLock<Option<u64>>>,
+    /// Clients built by the session's warmup
+    warm_clients: WarmClients,
+}
+
+impl PersonaBackendAgent {
+    pub(crate) fn new(
+        backend: PersonaBackend,
+        model_name: Option<String>,
+        gemini_options: Option<orcs_core::persona::GeminiOptions>,
+        kaiba_options: Option<orcs_core::persona::KaibaOptions>,
+        workspace_root: Arc<RwLock<Option<PathBuf>>>,
+        env_settings: Arc<RwLock<EnvSettings>>,
+        captures: BackendCaptures,
+    ) -> Self {
+        Self {
+            backend,
+            model_name,
+            gemini_options,
+            kaiba_options,
+            workspace_root,
+            env_settings,
+            captures,
+            reviewer_only: false,
+            offline_mode: OfflineMode::default(),
+            random_seed: Arc::new(RwLock::new(None)),
+            warm_clients: WarmClients::default(),
+        }
+    }
+
+    /// Withholds file-editing tools from Claude CLI runs when `reviewer_only` is set.
+    pub(crate) fn with_reviewer_only(mut self, reviewer_only: bool) -> Self {
+        self.reviewer_only = reviewer_only;
+        self
+    }
+
+    /// Refuses to run API backends while `offline_mode` is on.
+    pub(crate) fn with_offline_mode(mut self, offline_mode: OfflineMode) -> Self {
+        self.offline_mode = offline_mode;
+        self
+    }
+
+    /// Shares the session's seed with this agent.
+    pub(crate) fn with_random_seed(mut self, random_seed: Arc<RwLock<Option<u64>>>) -> Self {
+        self.random_seed = random_seed;
+        self
+    }
+
+    /// Reuses the clients built by the session's warmup.
+    pub(crate) fn with_warm_clients(mut self, warm_clients: WarmClients) -> Self {
+        self.warm_clients = warm_clients;
+        self
+    }
+
+    /// Takes the warmed-up client of this agent's backend, or builds a new one.
+    async fn api_client(&self) -> Result<Option<ApiClient>, AgentError> {
+        if let Some(client) = self.warm_clients.take(&self.backend) {
+            tracing::debug!(
+                "[PersonaBackendAgent] Reusing warmed-up {:?} client",
+                self.backend
+            );
+            return Ok(Some(client));
+        }
+        ApiClient::build(&self.backend, &*self.env_settings.read().await).await
+    }
+
+    /// Returns the session seed if this agent's backend accepts one.
+    ///
+    /// Only the OpenAI API takes a seed; other backends ignore it.
+    async fn backend_seed(&self) -> Option<u64> {
+        let seed = (*self.random_seed.read().await)?;
+        if matches!(self.backend, PersonaBackend::OpenAiApi) {
+            return Some(seed);
+        }
+        tracing::debug!(
+            "[PersonaBackendAgent] {:?} does not support a seed; ignoring {}",
+            self.backend,
+            seed
+        );
+        None
+    }
+
+    /// Tool arguments for Claude CLI runs.
+    ///
+    /// Pre-approves the allowed tools to avoid constant approval prompts. For
+    /// reviewer-only personas the write tools are also denied explicitly, so
+    /// permissions granted in the user's own Claude settings cannot re-enable them.
+    fn claude_cli_tool_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        let allowed = claude_cli_allowed_tools(self.reviewer_only);
+        if !allowed.is_empty() {
+            args.push("--allowed-tools".to_string());
+            args.push(allowed.join(","));
+        }
+        if self.reviewer_only {
+            args.push("--disallowed-tools".to_string());
+            args.push(CLAUDE_CLI_WRITE_TOOLS.join(","));
+        }
+        args
+    }
+
+    /// Runs a CLI agent, keeping its unprocessed output while LLM debug mode is
+    /// on and logging the exchange to `wire_tap`.
+    ///
+    /// Successful runs keep the CLI's stdout; failed runs keep the error,
+    /// which carries the CLI's stderr.
+    async fn execute_cli<A>(
+        &self,
+        agent: A,
+        payload: Payload,
+        wire_tap: Option<WireTap>,
+    ) -> Result<String, AgentError>
+    where
+        A: Agent<Output = String>,
+    {
+        let capture = self.captures.raw_output.as_ref().filter(|c| c.is_enabled());
+        if capture.is_none() && wire_tap.is_none() {
+            return agent.execute(payload).await;
+        }
+
+        let prompt = payload.to_text();
+        let result = agent.execute(payload).await;
+        let raw_response = match &result {
+            Ok(stdout) => stdout.clone(),
+            Err(e) => e.to_string(),
+        };
+        if let Some(tap) = &wire_tap {
+            let outcome = match &result {
+                Ok(_) => Ok(raw_response.as_str()),
+                Err(_) => Err(raw_response.as_str()),
+            };
+            tap.record(self.backend.as_str(), &prompt, outcome);
+        }
+        if let Some(capture) = capture {
+            capture
+                .record(LlmDebugInfo {
+                    prompt: truncate_raw_output(prompt),
+                    raw_response: truncate_raw_output(raw_response),
+                    model: self.model_name.clone(),
+                })
+                .await;
+        }
+        result
+    }
+
+    /// Executes the agent with optional workspace context.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The input payload for the agent
+    /// * `workspace_root` - Optional workspace root path (logged but not used for directory changes)
+    /// * `wire_tap` - Logs the backend exchange while the session's wire log is on
+    ///
+    /// # Returns
+    ///
+    /// Returns the agent's output string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent execution fails, or if the backend is an
+    /// API backend while offline mode is on
+    async fn execute_with_workspace(
+        &self,
+        payload: Payload,
+        workspace_root: Option<PathBuf>,
+        wire_tap: Option<WireTap>,
+    ) -> Result<String, AgentError> {
+        // Log the intention but do not change the directory
+        tracing::info!(
+            "[PersonaBackendAgent] Executing with workspace context: {:?} for backend: {:?}",
+            workspace_root,
+            self.backend
+        );
+
+        if !self.backend.is_cli() {
+            self.offline_mode
+                .ensure_online(self.backend.display_name())
+                .map_err(|e| AgentError::ExecutionFailed(e.to_string()))?;
+        }
+
+        let seed = self.backend_seed().await;
+        let usage_sink = UsageSink::default();
+
+        let result = match self.backend {
+            PersonaBackend::ClaudeCli => {
+                let mut agent = ClaudeCodeAgent::new().with_args(self.claude_cli_tool_args());
+
+                // Set workspace root and enhanced PATH if provided
+                if let Some(workspace) = workspace_root {
+                    let env_settings = self.env_settings.read().await;
+                    let enhanced_path = build_enhanced_path(&workspace, Some(&*env_settings));
+                    agent = agent.with_cwd(workspace).with_env("PATH", enhanced_path);
+                }
+                // Apply model if specified
+                if let Some(ref model_str) = self.model_name {
+                    tracing::info!("[PersonaBackendAgent] Using Claude model: {}", model_str);
+                    agent = agent.with_model_str(model_str);
+                }
+                self.execute_cli(agent, payload, wire_tap).await
+            }
+            PersonaBackend::ClaudeApi => {
+                let Some(ApiClient::Claude(agent)) = self.api_client().await? else {
+                    unreachable!("the Claude API backend builds a Claude client");
+                };
+                let mut agent = agent.with_usage_sink(usage_sink.clone());
+                if let Some(tap) = wire_tap {
+                    agent = agent.with_wire_tap(tap);
+                }
+                // Override model if specified
+                if let Some(ref model_str) = self.model_name {
+                    tracing::info!("[PersonaBackendAgent] Using Claude model: {}", model_str);
+                    agent = agent.with_model(model_str);
+                }
+                agent.execute(payload).await
+            }
+            PersonaBackend::GeminiCli => {
+                let mut agent = GeminiAgent::new();
+                // Set workspace root and enhanced PATH if provided
+                if let Some(workspace) = workspace_root {
+                    let env_settings = self.env_settings.read().await;
+                    let enhanced_path = build_enhanced_path(&workspace, Some(&*env_settings));
+                    agent = agent.with_cwd(workspace).with_env("PATH", enhanced_path);
+                }
+                // Apply model if specified
+                if let Some(ref model_str) = self.model_name {
+                    tracing::info!("[PersonaBackendAgent] Using Gemini model: {}", model_str);
+                    agent = agent.with_model_str(model_str);
+                }
+                self.execute_cli(agent, payload, wire_tap).await
+            }
+            PersonaBackend::GeminiApi => {
+                let Some(ApiClient::Gemini(agent)) = self.api_client().await? else {
+                    unreachable!("the Gemini API backend builds a Gemini client");
+                };
+                let mut agent = agent.with_usage_sink(usage_sink.clone());
+                if let Some(tap) = wire_tap {
+                    agent = agent.with_wire_tap(tap);
+                }
+                // Override model if specified
+                if let Some(ref model_str) = self.model_name {
+                    tracing::info!("[PersonaBackendAgent] Using Gemini model: {}", model_str);
+                    agent = agent.with_model(model_str);
+                }
+                // Apply Gemini options if specified
+                if let Some(ref options) = self.gemini_options {
+                    if let Some(ref thinking_level) = options.thinking_level {
+                        tracing::info!(
+                            "[PersonaBackendAgent] Setting Gemini thinking level: {}",
+                            thinking_level
+                        );
+                        agent = agent.with_thinking_level(thinking_level);
+                    }
+                    if let Some(google_search) = options.google_search {
+                        tracing::info!(
+                            "[PersonaBackendAgent] Setting Gemini Google Search: {}",
+                            google_search
+                        );
+                        agent = agent.with_google_search(google_search);
+                    }
+                }
+                agent.execute(payload).await
+            }
+            PersonaBackend::OpenAiApi => {
+                let Some(ApiClient::OpenAi(agent)) = self.api_client().await? else {
+                    unreachable!("the OpenAI API backend builds an OpenAI client");
+                };
+                let mut agent = agent.with_usage_sink(usage_sink.clone());
+                if let Some(tap) = wire_tap {
+                    agent = agent.with_wire_tap(tap);
+                }
+                // Override model if specified
+                if let Some(ref model_str) = self.model_name {
+                    tracing::info!("[PersonaBackendAgent] Using OpenAI model: {}", model_str);
+                    agent = agent.with_model(model_str);
+                }
+                if let Some(seed) = seed {
+                    tracing::info!("[PersonaBackendAgent] Using OpenAI seed: {}", seed);
+                    agent = agent.with_seed(seed);
+                }
+                agent.execute(payload).await
+            }
+            PersonaBackend::CodexCli => {
+                let mut agent = CodexAgent::new();
+                // Set workspace root and enhanced PATH if provided
+                if let Some(workspace) = workspace_root {
+                    let env_settings = self.env_settings.read().await;
+                    let enhanced_path = build_enhanced_path(&workspace, Some(&*env_settings));
+                    agent = agent.with_cwd(workspace).with_env("PATH", enhanced_path);
+                }
+                // Apply model if specified
+                if let Some(ref model_str) = self.model_name {
+                    tracing::info!("[PersonaBackendAgent] Using Codex model: {}", model_str);
+                    agent = agent.with_model_str(model_str);
+                }
+                self.execute_cli(agent, payload, wire_tap).await
+            }
+            PersonaBackend::KaibaApi => {
+                let Some(ApiClient::Kaiba(mut agent)) = self.api_client().await? else {
+                    unreachable!("the Kaiba API backend builds a Kaiba client");
+                };
+                if let Some(tap) = wire_tap {
+                    agent = agent.with_wire_tap(tap);
+                }
+                // Override Rei ID if specified in kaiba_options
+                if let Some(ref kaiba_opts) = self.kaiba_options
+                    && let Some(ref rei_id) = kaiba_opts.rei_id
+                {
+                    tracing::info!("[PersonaBackendAgent] Using Kaiba Rei ID: {}", rei_id);
+                    agent = agent.with_rei_id(rei_id);
+                }
+                agent.execute(payload).await
+            }
+        };
+
+        if let Some(capture) = &self.captures.usage
+            && let Some(usage) = usage_sink.take()
+        {
+            capture.record(usage).await;
+        }
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl Agent for PersonaBackendAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        // Using lazy_static pattern for static storage with dynamic dispatch
+        use std::sync::OnceLock;
+        static EXPERTISE_CLAUDE_CLI: OnceLock<String> = OnceLock::new();
+        static EXPERTISE_CLAUDE_API: OnceLock<String> = OnceLock::new();
+        static EXPERTISE_GEMINI_CLI: OnceLock<String> = OnceLock::new();
+        static EXPERTISE_GEMINI_API: OnceLock<String> = OnceLock::new();
+        static EXPERTISE_OPENAI_API: OnceLock<String> = OnceLock::new();
+        static EXPERTISE_CODEX_CLI: OnceLock<String> = OnceLock::new();
+        static EXPERTISE_KAIBA_API: OnceLock<String> = OnceLock::new();
+
+        match self.backend {
+            PersonaBackend::ClaudeCli => {
+                EXPERTISE_CLAUDE_CLI.get_or_init(|| "Claude CLI persona agent".to_string())
+            }
+            PersonaBackend::ClaudeApi => {
+                EXPERTISE_CLAUDE_API.get_or_init(|| "Claude API persona agent".to_string())
+            }
+            PersonaBackend::GeminiCli => {
+                EXPERTISE_GEMINI_CLI.get_or_init(|| "Gemini CLI persona agent".to_string())
+            }
+            PersonaBackend::GeminiApi => {
+                EXPERTISE_GEMINI_API.get_or_init(|| "Gemini API persona agent".to_string())
+            }
+            PersonaBackend::OpenAiApi => {
+                EXPERTISE_OPENAI_API.get_or_init(|| "OpenAI API persona agent".to_string())
+            }
+            PersonaBackend::CodexCli => {
+                EXPERTISE_CODEX_CLI.get_or_init(|| "Codex CLI persona agent".to_string())
+            }
+            PersonaBackend::KaibaApi => EXPERTISE_KAIBA_API
+                .get_or_init(|| "Kaiba API persona agent (with persistent memory)".to_string()),
+        }
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<Self::Output, AgentError> {
+        // Read workspace_root from shared state
+        let workspace_root = self.workspace_root.read().await.clone();
+        tracing::info!(
+            "[PersonaBackendAgent::execute] Read workspace_root from Arc: {:?}",
+            workspace_root
+        );
+        let wire_tap = self
+            .captures
+            .wire_log
+            .as_ref()
+            .and_then(|target| target.tap(&self.backend));
+        self.execute_with_workspace(payload, workspace_root, wire_tap)
+            .await
+    }
+}
+
+/// Client of an API backend with its credentials loaded and its HTTP client set up.
+#[derive(Clone)]
+enum ApiClient {
+    Claude(ClaudeApiAgent),
+    Gemini(GeminiApiAgent),
+    OpenAi(OpenAIApiAgent),
+    Kaiba(KaibaApiAgent),
+}
+
+impl ApiClient {
+    /// Builds the client of `backend`, or returns `None` for CLI backends.
+    async fn build(
+        backend: &PersonaBackend,
+        env_settings: &EnvSettings,
+    ) -> Result<Option<Self>, AgentError> {
+        Ok(Some(match backend {
+            PersonaBackend::ClaudeApi => Self::Claude(
+                ClaudeApiAgent::try_from_env()
+                    .await?
+                    .with_http_client(build_http_client(env_settings)?),
+            ),
+            PersonaBackend::GeminiApi => Self::Gemini(
+                GeminiApiAgent::try_from_env()
+                    .await?
+                    .with_http_client(build_http_client(env_settings)?),
+            ),
+            PersonaBackend::OpenAiApi => Self::OpenAi(
+                OpenAIApiAgent::try_from_env()
+                    .await?
+                    .with_http_client(build_http_client(env_settings)?),
+            ),
+            PersonaBackend::KaibaApi => Self::Kaiba(KaibaApiAgent::try_from_env().await?),
+            PersonaBackend::ClaudeCli | PersonaBackend::GeminiCli | PersonaBackend::CodexCli => {
+                return Ok(None);
+            }
+        }))
+    }
+}
+
+/// API clients built ahead of the first turn, by backend.
+///
+/// The first turn on a backend takes its client; later turns build their own,
+/// so they pick up changed credentials and proxy settings.
+#[derive(Clone, Default)]
+pub(crate) struct WarmClients(Arc<std::sync::Mutex<HashMap<PersonaBackend, ApiClient>>>);
+
+impl std::fmt::Debug for WarmClients {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let backends: Vec<PersonaBackend> = self.0.lock().unwrap().keys().cloned().collect();
+        f.debug_tuple("WarmClients").field(&backends).finish()
+    }
+}
+
+impl WarmClients {
+    fn insert(&self, backend: PersonaBackend, client: ApiClient) {
+        self.0.lock().unwrap().insert(backend, client);
+    }
+
+    fn take(&self, backend: &PersonaBackend) -> Option<ApiClient> {
+        self.0.lock().unwrap().remove(backend)
+    }
+}
+
+/// Constructs the client of an API backend once, loading its credentials and
+/// setting up its HTTP client, and keeps it for the backend's first turn. CLI
+/// backends have nothing to construct.
+pub(crate) async fn warm_up_backend(
+    backend: &PersonaBackend,
+    env_settings: &EnvSettings,
+    clients: &WarmClients,
+) -> Result<(), AgentError> {
+    if let Some(client) = ApiClient::build(backend, env_settings).await? {
+        clients.insert(backend.clone(), client);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_toolkit::agent::dialogue::Speaker;
+
+    fn backend_agent(backend: PersonaBackend) -> PersonaBackendAgent {
+        PersonaBackendAgent::new(
+            backend,
+            None,
+            None,
+            None,
+            Arc::new(RwLock::new(None)),
+            Arc::new(RwLock::new(EnvSettings::default())),
+            BackendCaptures::default(),
+        )
+    }
+
+    #[test]
+    fn test_raw_output_is_truncated() {
+        let bounded = truncate_raw_output("x".repeat(MAX_RAW_OUTPUT_BYTES + 10));
+        assert!(bounded.starts_with(&"x".repeat(MAX_RAW_OUTPUT_BYTES)));
+        assert!(bounded.ends_with("[10 bytes truncated]"));
+    }
+
+    #[test]
+    fn test_reviewer_only_persona_passes_no_write_tools() {
+        let agent = |reviewer_only: bool| {
+            backend_agent(PersonaBackend::ClaudeCli).with_reviewer_only(reviewer_only)
+        };
+        let allowed_tools = |args: &[String]| -> Vec<String> {
+            args.iter()
+                .position(|arg| arg == "--allowed-tools")
+                .map(|i| args[i + 1].split(',').map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+
+        let editor_args = agent(false).claude_cli_tool_args();
+        assert!(allowed_tools(&editor_args).contains(&"Write".to_string()));
+
+        let reviewer_args = agent(true).claude_cli_tool_args();
+        for tool in CLAUDE_CLI_WRITE_TOOLS {
+            assert!(!allowed_tools(&reviewer_args).contains(&tool.to_string()));
+        }
+        assert_eq!(
+            reviewer_args[reviewer_args.len() - 2..],
+            ["--disallowed-tools".to_string(), "Edit,Write".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode_refuses_api_backends() {
+        let agent =
+            backend_agent(PersonaBackend::ClaudeApi).with_offline_mode(OfflineMode::new(true));
+
+        let err = agent
+            .execute_with_workspace(
+                Payload::new().with_message(Speaker::System, "Hello"),
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Claude API is unavailable: offline mode is on")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_seed_is_applied_to_openai_agents() {
+        let random_seed = Arc::new(RwLock::new(Some(42)));
+        let agent =
+            |backend: PersonaBackend| backend_agent(backend).with_random_seed(random_seed.clone());
+
+        assert_eq!(
+            agent(PersonaBackend::OpenAiApi).backend_seed().await,
+            Some(42)
+        );
+        assert_eq!(agent(PersonaBackend::ClaudeApi).backend_seed().await, None);
+
+        // Clearing the seed reaches agents that were already built
+        let openai = agent(PersonaBackend::OpenAiApi);
+        *random_seed.write().await = None;
+        assert_eq!(openai.backend_seed().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_first_turn_takes_the_warmed_up_client() {
+        let warm_clients = WarmClients::default();
+        warm_clients.insert(
+            PersonaBackend::OpenAiApi,
+            ApiClient::OpenAi(OpenAIApiAgent::new("warm-key", "gpt-5")),
+        );
+        let agent =
+            backend_agent(PersonaBackend::OpenAiApi).with_warm_clients(warm_clients.clone());
+
+        assert!(matches!(
+            agent.api_client().await,
+            Ok(Some(ApiClient::OpenAi(_)))
+        ));
+        // Later turns build their own client
+        assert!(warm_clients.take(&PersonaBackend::OpenAiApi).is_none());
+    }
+}
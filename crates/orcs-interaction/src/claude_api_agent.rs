@@ -3,19 +3,20 @@
 //! This agent calls the Claude REST API directly without CLI dependency.
 //! Configuration priority: secret.json > environment variables
 
+use crate::turn_stats::{TokenUsage, UsageSink};
 use async_trait::async_trait;
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use llm_toolkit::agent::{Agent, AgentError, Payload};
 use llm_toolkit::attachment::Attachment;
 use orcs_core::secret::SecretService;
-use orcs_infrastructure::SecretServiceImpl;
+use orcs_infrastructure::{SecretServiceImpl, WireTap};
 use reqwest::{Client, StatusCode, header::HeaderValue};
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::time::Duration;
 
-const DEFAULT_CLAUDE_MODEL: &str = "claude-sonnet-4-6";
+pub(crate) const DEFAULT_CLAUDE_MODEL: &str = "claude-sonnet-4-6";
 const BASE_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 
@@ -27,6 +28,8 @@ pub struct ClaudeApiAgent {
     model: String,
     system: Option<String>,
     max_tokens: u32,
+    wire_tap: Option<WireTap>,
+    usage_sink: Option<UsageSink>,
 }
 
 impl ClaudeApiAgent {
@@ -38,6 +41,8 @@ impl ClaudeApiAgent {
             model: model.into(),
             system: None,
             max_tokens: 4096,
+            wire_tap: None,
+            usage_sink: None,
         }
     }
 
@@ -70,6 +75,12 @@ impl ClaudeApiAgent {
         Ok(Self::new(api_key, model))
     }
 
+    /// Replaces the HTTP client (e.g. one configured with proxy settings).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Overrides the model after construction.
     pub fn with_model(mut self, model: impl Into<String>) -> Self {
         self.model = model.into();
@@ -88,6 +99,34 @@ impl ClaudeApiAgent {
         self
     }
 
+    /// Records each request and its raw response to the session's wire log.
+    pub fn with_wire_tap(mut self, tap: WireTap) -> Self {
+        self.wire_tap = Some(tap.with_secret(self.api_key.clone()));
+        self
+    }
+
+    /// Reports the token usage of each response to `sink`.
+    pub fn with_usage_sink(mut self, sink: UsageSink) -> Self {
+        self.usage_sink = Some(sink);
+        self
+    }
+
+    fn record_usage(&self, input_tokens: u64, output_tokens: u64) {
+        if let Some(sink) = &self.usage_sink {
+            sink.record(TokenUsage {
+                model: self.model.clone(),
+                input_tokens,
+                output_tokens,
+            });
+        }
+    }
+
+    fn record_exchange(&self, body: &CreateMessageRequest, outcome: Result<&str, &str>) {
+        if let Some(tap) = &self.wire_tap {
+            tap.record_json(BASE_URL, body, outcome);
+        }
+    }
+
     async fn build_content(&self, payload: &Payload) -> Result<Vec<ContentBlock>, AgentError> {
         let mut content_blocks = Vec::new();
 
@@ -156,7 +195,8 @@ impl ClaudeApiAgent {
                 message: format!("Claude API request failed: {err}"),
                 is_retryable: err.is_connect() || err.is_timeout(),
                 retry_after: None,
-            })?;
+            })
+            .inspect_err(|err| self.record_exchange(body, Err(&err.to_string())))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -165,13 +205,21 @@ impl ClaudeApiAgent {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Failed to read Claude error body".to_string());
+            self.record_exchange(body, Err(&body_text));
             return Err(map_http_error(status, body_text, retry_after));
         }
 
-        let parsed: CreateMessageResponse = response
-            .json()
+        let response_text = response
+            .text()
             .await
+            .map_err(|err| AgentError::Other(format!("Failed to read Claude response: {err}")))
+            .inspect_err(|err| self.record_exchange(body, Err(&err.to_string())))?;
+        self.record_exchange(body, Ok(&response_text));
+        let parsed: CreateMessageResponse = serde_json::from_str(&response_text)
             .map_err(|err| AgentError::Other(format!("Failed to parse Claude response: {err}")))?;
+        if let Some(usage) = &parsed.usage {
+            self.record_usage(usage.input_tokens, usage.output_tokens);
+        }
 
         extract_text_response(parsed)
     }
@@ -263,6 +311,13 @@ struct ImageSource {
 #[derive(Deserialize)]
 struct CreateMessageResponse {
     content: Vec<ContentBlockResponse>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct Usage {
+    input_tokens: u64,
+    output_tokens: u64,
 }
 
 #[derive(Deserialize)]
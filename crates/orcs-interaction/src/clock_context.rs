@@ -0,0 +1,204 @@
+//! The clock context: the current time as agents see it.
+//!
+//! Agents know no date past their training data, so in Rich context mode each
+//! turn carries a one-line block with the current time in the user's timezone,
+//! the session's age and the time since the previous user message. The block
+//! is built per turn and never stored in the session history.
+//!
+//! The dialogue does not pass the round's payload contexts on to its agents,
+//! so each persona's agent is wrapped in a [`ClockContextAgent`] that adds the
+//! block of the round currently running.
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use llm_toolkit::agent::{Agent, AgentError, Payload};
+
+/// Parses a UTC offset such as `+09:00`, `-0530`, `+9`, `UTC+09:00` or `UTC`.
+///
+/// Returns `None` for anything else, including IANA zone names.
+pub fn parse_utc_offset(timezone: &str) -> Option<FixedOffset> {
+    let trimmed = timezone.trim();
+    let offset = trimmed
+        .strip_prefix("UTC")
+        .or_else(|| trimmed.strip_prefix("GMT"))
+        .unwrap_or(trimmed);
+    if offset.is_empty() {
+        return (trimmed != offset)
+            .then(|| FixedOffset::east_opt(0))
+            .flatten();
+    }
+
+    let (sign, digits) = match offset.as_bytes()[0] {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    if !digits.is_ascii() {
+        return None;
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if digits.len() > 2 => digits.split_at(digits.len() - 2),
+        None => (digits, "0"),
+    };
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60) as i32)
+}
+
+/// Renders the clock context block.
+///
+/// `timezone` is the user's UTC offset (see [`parse_utc_offset`]); when it is
+/// unset or invalid, the OS timezone is used.
+///
+/// # Example output
+///
+/// `Now: 2025-03-14 09:30 UTC+09:00; session started 3d ago; last user message 2h ago`
+pub fn format_context(
+    now: DateTime<Utc>,
+    timezone: Option<&str>,
+    session_started: Option<DateTime<Utc>>,
+    last_user_message: Option<DateTime<Utc>>,
+) -> String {
+    let offset = timezone
+        .and_then(parse_utc_offset)
+        .unwrap_or_else(|| *now.with_timezone(&Local).offset());
+    let mut parts = vec![format!(
+        "Now: {} UTC{}",
+        now.with_timezone(&offset).format("%Y-%m-%d %H:%M"),
+        offset
+    )];
+    if let Some(started) = session_started {
+        parts.push(format!("session started {}", format_elapsed(now, started)));
+    }
+    if let Some(last) = last_user_message {
+        parts.push(format!("last user message {}", format_elapsed(now, last)));
+    }
+    parts.join("; ")
+}
+
+/// Formats the time from `since` to `now` in its largest whole unit ("2h ago").
+fn format_elapsed(now: DateTime<Utc>, since: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(since);
+    if elapsed.num_days() > 0 {
+        format!("{}d ago", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{}h ago", elapsed.num_hours())
+    } else if elapsed.num_minutes() > 0 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else {
+        "just now".to_string()
+    }
+}
+
+/// The clock context block of the dialogue round currently running.
+#[derive(Clone, Default)]
+pub(crate) struct ClockContextSlot(Arc<Mutex<Option<String>>>);
+
+impl ClockContextSlot {
+    /// Hands `block` to persona agents until the returned round is dropped.
+    pub(crate) fn begin_round(&self, block: Option<String>) -> ClockContextRound {
+        *self.0.lock().unwrap() = block;
+        ClockContextRound(self.clone())
+    }
+
+    fn current(&self) -> Option<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Clears the slot's block when dropped.
+pub(crate) struct ClockContextRound(ClockContextSlot);
+
+impl Drop for ClockContextRound {
+    fn drop(&mut self) {
+        *self.0.0.lock().unwrap() = None;
+    }
+}
+
+/// Adds the clock context block of the current round to a persona's prompt.
+pub(crate) struct ClockContextAgent {
+    pub(crate) inner: Box<dyn Agent<Output = String, Expertise = String>>,
+    pub(crate) slot: ClockContextSlot,
+}
+
+#[async_trait::async_trait]
+impl Agent for ClockContextAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        self.inner.expertise()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn capabilities(&self) -> Option<Vec<llm_toolkit::agent::Capability>> {
+        self.inner.capabilities()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        let payload = match self.slot.current() {
+            Some(block) => payload.with_context(block),
+            None => payload,
+        };
+        self.inner.execute(payload).await
+    }
+
+    async fn is_available(&self) -> Result<(), AgentError> {
+        self.inner.is_available().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_parse_utc_offset() {
+        let hours = |h: i32| FixedOffset::east_opt(h * 3600);
+        assert_eq!(parse_utc_offset("+09:00"), hours(9));
+        assert_eq!(parse_utc_offset("UTC+9"), hours(9));
+        assert_eq!(parse_utc_offset("-0500"), hours(-5));
+        assert_eq!(parse_utc_offset("UTC"), hours(0));
+        assert_eq!(
+            parse_utc_offset("+05:30"),
+            FixedOffset::east_opt(5 * 3600 + 30 * 60)
+        );
+        assert_eq!(parse_utc_offset("Asia/Tokyo"), None);
+        assert_eq!(parse_utc_offset("+25:00"), None);
+        assert_eq!(parse_utc_offset(""), None);
+    }
+
+    #[test]
+    fn test_format_context_uses_the_user_timezone() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 14, 0, 30, 0).unwrap();
+        let context = format_context(
+            now,
+            Some("+09:00"),
+            Some(now - Duration::days(3) - Duration::hours(5)),
+            Some(now - Duration::minutes(130)),
+        );
+        assert_eq!(
+            context,
+            "Now: 2025-03-14 09:30 UTC+09:00; session started 3d ago; last user message 2h ago"
+        );
+
+        let context = format_context(now, Some("UTC"), None, Some(now));
+        assert_eq!(
+            context,
+            "Now: 2025-03-14 00:30 UTC+00:00; last user message just now"
+        );
+    }
+}
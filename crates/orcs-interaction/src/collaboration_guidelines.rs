@@ -0,0 +1,177 @@
+//! Collaboration guidelines per execution strategy.
+//!
+//! The guidelines open the dialogue context of every agent and tell agents how
+//! to coordinate. Coordination differs by strategy: in a Sequential session
+//! agents build on the previous speaker, in a Broadcast one they answer side by
+//! side, and under a Moderator one agent leads. Each strategy has a default
+//! template, which a workspace can replace by placing a Markdown file named
+//! after the strategy in [`OVERRIDE_DIR_NAME`] at its root:
+//!
+//! ```text
+//! <workspace root>/.orcs-guidelines/sequential.md
+//! ```
+//!
+//! An override replaces the template of its strategy only. The guideline on
+//! quarantined content is always appended, whatever the template.
+
+use llm_toolkit::agent::dialogue::ExecutionModel;
+use orcs_core::session::UNTRUSTED_CONTENT_GUIDELINE;
+use std::path::Path;
+
+/// Directory at the workspace root holding per-strategy guideline overrides.
+pub const OVERRIDE_DIR_NAME: &str = ".orcs-guidelines";
+
+const BROADCAST: &str = "【協調ガイドライン】\n\
+     - 複数の AI ペルソナが協力してユーザーをサポートします\n\
+     - 全員が同時に回答します。他の参加者の意見を尊重し、重複を避けて新しい視点を提供してください\n\
+     - ユーザーのワークスペース環境で実行されています\n\
+     - 建設的で協調的なコミュニケーションを心がけてください";
+
+const SEQUENTIAL: &str = "【協調ガイドライン】\n\
+     - 複数の AI ペルソナが順番に発言してユーザーをサポートします\n\
+     - 直前の発言者の内容を踏まえて発展させ、同じ内容を繰り返さないでください\n\
+     - 前の発言に誤りや抜けがあれば指摘し、補ってください\n\
+     - ユーザーのワークスペース環境で実行されています";
+
+const MENTIONED: &str = "【協調ガイドライン】\n\
+     - 複数の AI ペルソナが参加していますが、メンションされた参加者だけが回答します\n\
+     - 自分に向けられた質問に集中し、他の参加者の担当には立ち入らないでください\n\
+     - 他の参加者の意見が必要なら @名前 でメンションしてください\n\
+     - ユーザーのワークスペース環境で実行されています";
+
+const ORDERED_SEQUENTIAL: &str = "【協調ガイドライン】\n\
+     - 複数の AI ペルソナが決められた順番で発言してユーザーをサポートします\n\
+     - 自分の役割に沿って、直前の発言者の内容を踏まえて発展させてください\n\
+     - 同じ内容を繰り返さず、次の発言者に引き継げる形でまとめてください\n\
+     - ユーザーのワークスペース環境で実行されています";
+
+const ORDERED_BROADCAST: &str = "【協調ガイドライン】\n\
+     - 複数の AI ペルソナが決められた順番で同時に回答します\n\
+     - 他の参加者の回答は見えない前提で、自分の専門に沿った独立した意見を述べてください\n\
+     - ユーザーのワークスペース環境で実行されています\n\
+     - 建設的で協調的なコミュニケーションを心がけてください";
+
+const MODERATOR: &str = "【協調ガイドライン】\n\
+     - モデレーターが議論を進行し、発言する参加者を選びます\n\
+     - モデレーターは論点を整理し、次に意見を求める相手と理由を明示してください\n\
+     - 他の参加者は指名された論点に簡潔に答え、議論を広げすぎないでください\n\
+     - ユーザーのワークスペース環境で実行されています";
+
+/// Name of `strategy` in override file names (e.g. `ordered_sequential`).
+pub fn strategy_key(strategy: &ExecutionModel) -> &'static str {
+    match strategy {
+        ExecutionModel::Broadcast => "broadcast",
+        ExecutionModel::Sequential => "sequential",
+        ExecutionModel::Mentioned { .. } => "mentioned",
+        ExecutionModel::OrderedSequential(_) => "ordered_sequential",
+        ExecutionModel::OrderedBroadcast(_) => "ordered_broadcast",
+        ExecutionModel::Moderator => "moderator",
+    }
+}
+
+/// Default guideline template for `strategy`.
+pub fn default_template(strategy: &ExecutionModel) -> &'static str {
+    match strategy {
+        ExecutionModel::Broadcast => BROADCAST,
+        ExecutionModel::Sequential => SEQUENTIAL,
+        ExecutionModel::Mentioned { .. } => MENTIONED,
+        ExecutionModel::OrderedSequential(_) => ORDERED_SEQUENTIAL,
+        ExecutionModel::OrderedBroadcast(_) => ORDERED_BROADCAST,
+        ExecutionModel::Moderator => MODERATOR,
+    }
+}
+
+/// Reads the workspace's override of the template for `strategy`.
+///
+/// Returns `None` when the file is missing, unreadable or blank.
+pub async fn workspace_template(
+    workspace_root: &Path,
+    strategy: &ExecutionModel,
+) -> Option<String> {
+    let path = workspace_root
+        .join(OVERRIDE_DIR_NAME)
+        .join(format!("{}.md", strategy_key(strategy)));
+    let template = tokio::fs::read_to_string(&path).await.ok()?;
+    let template = template.trim();
+    (!template.is_empty()).then(|| template.to_string())
+}
+
+/// Guidelines agents get under `strategy`: the workspace's template when it
+/// has one, else the default, followed by the quarantine guideline.
+pub async fn effective(strategy: &ExecutionModel, workspace_root: Option<&Path>) -> String {
+    let template = match workspace_root {
+        Some(root) => workspace_template(root, strategy).await,
+        None => None,
+    };
+    format!(
+        "{}\n{}",
+        template.as_deref().unwrap_or(default_template(strategy)),
+        UNTRUSTED_CONTENT_GUIDELINE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_toolkit::agent::dialogue::{BroadcastOrder, SequentialOrder};
+
+    fn strategies() -> Vec<ExecutionModel> {
+        vec![
+            ExecutionModel::Broadcast,
+            ExecutionModel::Sequential,
+            ExecutionModel::Mentioned {
+                strategy: Default::default(),
+            },
+            ExecutionModel::OrderedSequential(SequentialOrder::AsAdded),
+            ExecutionModel::OrderedBroadcast(BroadcastOrder::ParticipantOrder),
+            ExecutionModel::Moderator,
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_each_strategy_selects_its_template() {
+        let expected = [
+            BROADCAST,
+            SEQUENTIAL,
+            MENTIONED,
+            ORDERED_SEQUENTIAL,
+            ORDERED_BROADCAST,
+            MODERATOR,
+        ];
+        for (strategy, template) in strategies().iter().zip(expected) {
+            let guidelines = effective(strategy, None).await;
+            assert!(guidelines.starts_with(template), "{:?}", strategy);
+            assert!(guidelines.ends_with(UNTRUSTED_CONTENT_GUIDELINE));
+        }
+        assert!(SEQUENTIAL.contains("直前の発言者"));
+        assert!(MODERATOR.contains("モデレーター"));
+    }
+
+    #[tokio::test]
+    async fn test_workspace_override_applies_to_its_strategy_only() {
+        let root = tempfile::TempDir::new().unwrap();
+        let dir = root.path().join(OVERRIDE_DIR_NAME);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("sequential.md"),
+            "\n- Review the previous diff first\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("moderator.md"), "  \n").unwrap();
+
+        let sequential = effective(&ExecutionModel::Sequential, Some(root.path())).await;
+        assert_eq!(
+            sequential,
+            format!(
+                "- Review the previous diff first\n{}",
+                UNTRUSTED_CONTENT_GUIDELINE
+            )
+        );
+
+        // Other strategies, and blank overrides, keep their defaults
+        let broadcast = effective(&ExecutionModel::Broadcast, Some(root.path())).await;
+        assert!(broadcast.starts_with(BROADCAST));
+        let moderator = effective(&ExecutionModel::Moderator, Some(root.path())).await;
+        assert!(moderator.starts_with(MODERATOR));
+    }
+}
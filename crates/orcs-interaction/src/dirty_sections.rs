@@ -0,0 +1,123 @@
+//! Tracks which sections of a session changed since it was last saved.
+//!
+//! Appended messages are found by comparing history lengths with the last
+//! save, metadata and system messages by comparing with a snapshot of them.
+//! Edits of already saved history messages cannot be appended and must be
+//! reported through [`DirtySections::edited`]; they force a full save.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use orcs_core::session::{ConversationMessage, Session, SessionDelta};
+
+/// State of the session as of its last save.
+struct SavedState {
+    history_lengths: HashMap<String, usize>,
+    system_messages: Vec<ConversationMessage>,
+    /// The session without messages and `updated_at`
+    metadata: Session,
+}
+
+impl SavedState {
+    fn of(session: &Session) -> Self {
+        Self {
+            history_lengths: session
+                .persona_histories
+                .iter()
+                .map(|(persona_id, history)| (persona_id.clone(), history.len()))
+                .collect(),
+            system_messages: session.system_messages.clone(),
+            metadata: metadata_of(session),
+        }
+    }
+
+    fn is_saved(&self, persona_id: &str, index: usize) -> bool {
+        index < self.history_lengths.get(persona_id).copied().unwrap_or(0)
+    }
+}
+
+fn metadata_of(session: &Session) -> Session {
+    Session {
+        persona_histories: HashMap::new(),
+        system_messages: Vec::new(),
+        updated_at: String::new(),
+        ..session.clone()
+    }
+}
+
+/// Dirty-section tracking of one session.
+#[derive(Default)]
+pub(crate) struct DirtySections {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// None until the first save; everything is dirty then
+    saved: Option<SavedState>,
+    /// Whether an already saved history message was edited
+    histories_edited: bool,
+}
+
+impl DirtySections {
+    /// Records an edit of the message at `index` of `persona_id`'s history.
+    ///
+    /// Messages appended since the last save are written with their edits, so
+    /// only edits of saved messages mark the histories dirty.
+    pub(crate) fn edited(&self, persona_id: &str, index: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner
+            .saved
+            .as_ref()
+            .is_some_and(|saved| saved.is_saved(persona_id, index))
+        {
+            inner.histories_edited = true;
+        }
+    }
+
+    /// Forgets the last save, so the next save is a full one.
+    pub(crate) fn reset(&self) {
+        *self.inner.lock().unwrap() = Inner::default();
+    }
+
+    /// Returns the sections of `session` changed since the last save and
+    /// records `session` as saved.
+    pub(crate) fn take(&self, session: &Session) -> SessionDelta {
+        let mut inner = self.inner.lock().unwrap();
+        let delta = match &inner.saved {
+            Some(saved) if !inner.histories_edited => delta_since(saved, session),
+            _ => SessionDelta::full(),
+        };
+        inner.saved = Some(SavedState::of(session));
+        inner.histories_edited = false;
+        delta
+    }
+}
+
+fn delta_since(saved: &SavedState, session: &Session) -> SessionDelta {
+    // A history that disappeared or shrank cannot be expressed as appends
+    let histories_kept = saved.history_lengths.iter().all(|(persona_id, &len)| {
+        len == 0
+            || session
+                .persona_histories
+                .get(persona_id)
+                .is_some_and(|history| history.len() >= len)
+    });
+    if !histories_kept {
+        return SessionDelta::full();
+    }
+
+    SessionDelta {
+        full: false,
+        metadata: saved.metadata != metadata_of(session),
+        system_messages: saved.system_messages != session.system_messages,
+        appended_messages: session
+            .persona_histories
+            .iter()
+            .filter_map(|(persona_id, history)| {
+                let saved_len = saved.history_lengths.get(persona_id).copied().unwrap_or(0);
+                (history.len() > saved_len).then(|| (persona_id.clone(), saved_len..history.len()))
+            })
+            .collect(),
+    }
+}
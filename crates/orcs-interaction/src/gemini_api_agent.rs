@@ -35,18 +35,19 @@
 //! # }
 //! ```
 
+use crate::turn_stats::{TokenUsage, UsageSink};
 use async_trait::async_trait;
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use llm_toolkit::agent::{Agent, AgentError, Payload};
 use llm_toolkit::attachment::Attachment;
 use orcs_core::secret::SecretService;
-use orcs_infrastructure::SecretServiceImpl;
+use orcs_infrastructure::{SecretServiceImpl, WireTap};
 use reqwest::{Client, StatusCode, header::HeaderValue};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-const DEFAULT_GEMINI_MODEL: &str = "gemini-2.5-flash";
+pub(crate) const DEFAULT_GEMINI_MODEL: &str = "gemini-2.5-flash";
 const GEMINI_3_PRO_MODEL: &str = "gemini-3.1-pro-preview";
 const BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
 
@@ -59,6 +60,8 @@ pub struct GeminiApiAgent {
     system_instruction: Option<String>,
     thinking_level: Option<String>,
     enable_google_search: bool,
+    wire_tap: Option<WireTap>,
+    usage_sink: Option<UsageSink>,
 }
 
 impl GeminiApiAgent {
@@ -71,6 +74,8 @@ impl GeminiApiAgent {
             system_instruction: None,
             thinking_level: None,
             enable_google_search: false,
+            wire_tap: None,
+            usage_sink: None,
         }
     }
 
@@ -128,6 +133,12 @@ impl GeminiApiAgent {
         Ok(agent)
     }
 
+    /// Replaces the HTTP client (e.g. one configured with proxy settings).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Overrides the model after construction.
     pub fn with_model(mut self, model: impl Into<String>) -> Self {
         self.model = model.into();
@@ -153,6 +164,35 @@ impl GeminiApiAgent {
         self
     }
 
+    /// Records each request and its raw response to the session's wire log.
+    pub fn with_wire_tap(mut self, tap: WireTap) -> Self {
+        self.wire_tap = Some(tap.with_secret(self.api_key.clone()));
+        self
+    }
+
+    /// Reports the token usage of each response to `sink`.
+    pub fn with_usage_sink(mut self, sink: UsageSink) -> Self {
+        self.usage_sink = Some(sink);
+        self
+    }
+
+    fn record_usage(&self, input_tokens: u64, output_tokens: u64) {
+        if let Some(sink) = &self.usage_sink {
+            sink.record(TokenUsage {
+                model: self.model.clone(),
+                input_tokens,
+                output_tokens,
+            });
+        }
+    }
+
+    fn record_exchange(&self, body: &GenerateContentRequest, outcome: Result<&str, &str>) {
+        if let Some(tap) = &self.wire_tap {
+            let endpoint = format!("{}/{}:generateContent", BASE_URL, self.model);
+            tap.record_json(&endpoint, body, outcome);
+        }
+    }
+
     async fn build_parts(&self, payload: &Payload) -> Result<Vec<Part>, AgentError> {
         let mut parts = Vec::new();
         let text = payload.to_text();
@@ -215,7 +255,8 @@ impl GeminiApiAgent {
                 message: format!("Gemini API request failed: {err}"),
                 is_retryable: err.is_connect() || err.is_timeout(),
                 retry_after: None,
-            })?;
+            })
+            .inspect_err(|err| self.record_exchange(body, Err(&err.to_string())))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -224,13 +265,17 @@ impl GeminiApiAgent {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Failed to read Gemini error body".to_string());
+            self.record_exchange(body, Err(&body_text));
             return Err(map_http_error(status, body_text, retry_after));
         }
 
         // Read response body as text first to preserve it for error reporting
-        let body_text = response.text().await.map_err(|err| {
-            AgentError::Other(format!("Failed to read Gemini response body: {err}"))
-        })?;
+        let body_text = response
+            .text()
+            .await
+            .map_err(|err| AgentError::Other(format!("Failed to read Gemini response body: {err}")))
+            .inspect_err(|err| self.record_exchange(body, Err(&err.to_string())))?;
+        self.record_exchange(body, Ok(&body_text));
 
         // Parse JSON from text
         let parsed: GenerateContentResponse = serde_json::from_str(&body_text).map_err(|err| {
@@ -248,6 +293,9 @@ impl GeminiApiAgent {
                 "Failed to parse Gemini response: {err}\n\nResponse body:\n{truncated_body}"
             ))
         })?;
+        if let Some(usage) = &parsed.usage_metadata {
+            self.record_usage(usage.prompt_token_count, usage.candidates_token_count);
+        }
 
         extract_text_response(parsed)
     }
@@ -359,8 +407,19 @@ struct InlineDataPayload {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct GenerateContentResponse {
     candidates: Option<Vec<Candidate>>,
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
 }
 
 #[derive(Deserialize)]
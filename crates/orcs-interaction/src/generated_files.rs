@@ -0,0 +1,77 @@
+//! Files agents report as created or modified during their turn.
+//!
+//! Agents working in the workspace list the files they wrote as
+//! `<GeneratedFile>path</GeneratedFile>` blocks in their replies. The paths
+//! are recorded as attachments of the assistant message so the UI can show
+//! and open them.
+
+use std::path::Path;
+
+const OPEN_TAG: &str = "<GeneratedFile>";
+const CLOSE_TAG: &str = "</GeneratedFile>";
+
+/// Instruction telling agents how to report the files they wrote.
+pub const REPORT_INSTRUCTION: &str = "【生成ファイル】\n\
+     ワークスペースにファイルを作成・変更した場合は、返信の最後にそのパスを \
+     1 ファイルずつ <GeneratedFile>path/to/file</GeneratedFile> の形式で記載してください。";
+
+/// Extracts the file paths reported in an agent reply.
+///
+/// Relative paths are resolved against `workspace_root` when one is set.
+/// Paths are returned in reply order without duplicates; empty blocks and a
+/// trailing unclosed block are ignored.
+pub fn extract_reported_files(content: &str, workspace_root: Option<&Path>) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find(OPEN_TAG) {
+        let after_open = &rest[start + OPEN_TAG.len()..];
+        let Some(end) = after_open.find(CLOSE_TAG) else {
+            break;
+        };
+        let reported = after_open[..end].trim().trim_matches('`');
+        if !reported.is_empty() {
+            let path = match workspace_root {
+                Some(root) if Path::new(reported).is_relative() => {
+                    root.join(reported).to_string_lossy().into_owned()
+                }
+                _ => reported.to_string(),
+            };
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        rest = &after_open[end + CLOSE_TAG.len()..];
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_reported_files_dedups_and_skips_empty_blocks() {
+        let content = "Done.\n<GeneratedFile> src/main.rs </GeneratedFile>\n\
+                       <GeneratedFile></GeneratedFile><GeneratedFile>`docs/plan.md`</GeneratedFile>\n\
+                       <GeneratedFile>src/main.rs</GeneratedFile><GeneratedFile>never closed";
+        assert_eq!(
+            extract_reported_files(content, None),
+            vec!["src/main.rs".to_string(), "docs/plan.md".to_string()]
+        );
+        assert!(extract_reported_files("no files", None).is_empty());
+    }
+
+    #[test]
+    fn test_extract_reported_files_resolves_relative_paths_in_workspace() {
+        let root = Path::new("/work/project");
+        let content = "<GeneratedFile>out/report.csv</GeneratedFile>\
+                       <GeneratedFile>/tmp/absolute.txt</GeneratedFile>";
+        assert_eq!(
+            extract_reported_files(content, Some(root)),
+            vec![
+                root.join("out/report.csv").to_string_lossy().into_owned(),
+                "/tmp/absolute.txt".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Heartbeats for long-running CLI turns.
+//!
+//! CLI backends print nothing useful until their process exits, so a slow run
+//! looks like a hang. While a CLI-backed persona's turn is in flight, the
+//! dialogue round emits a [`Heartbeat`] every configured interval (see
+//! `EnvSettings::cli_heartbeat_interval_secs`) with the elapsed time and the
+//! latest output line reported to the session's [`OutputActivity`].
+//! Heartbeats are streamed only, never persisted.
+
+use std::collections::HashMap;
+use std::process::{Output, Stdio};
+use std::sync::{Arc, Mutex};
+
+use orcs_infrastructure::wire_log::scrub;
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+
+/// Maximum number of characters of an output line carried by a heartbeat.
+pub const MAX_OUTPUT_LINE_CHARS: usize = 200;
+
+/// Progress of a turn that is still in flight.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Heartbeat {
+    /// Whole seconds since the persona started its turn
+    pub elapsed_secs: u64,
+    /// Most recent non-empty output line of the run, with secrets scrubbed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_output_line: Option<String>,
+}
+
+/// Latest output line of each persona's running CLI process.
+///
+/// Agents that spawn their own CLI processes report output here, e.g. through
+/// [`run_reporting_output`], so heartbeats can hint at what the tool is doing.
+#[derive(Clone, Default)]
+pub struct OutputActivity {
+    lines: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl OutputActivity {
+    /// Records output of `author`'s run, keeping its last non-empty line.
+    ///
+    /// The line is scrubbed of credentials and capped at [`MAX_OUTPUT_LINE_CHARS`].
+    pub fn record(&self, author: &str, output: &str) {
+        let Some(line) = output.lines().map(str::trim).rfind(|line| !line.is_empty()) else {
+            return;
+        };
+        let line: String = scrub(line, &[])
+            .chars()
+            .take(MAX_OUTPUT_LINE_CHARS)
+            .collect();
+        self.lines.lock().unwrap().insert(author.to_string(), line);
+    }
+
+    /// Returns the last recorded output line of `author`.
+    pub fn last_line(&self, author: &str) -> Option<String> {
+        self.lines.lock().unwrap().get(author).cloned()
+    }
+
+    /// Forgets the output of `author`'s finished run.
+    pub(crate) fn clear(&self, author: &str) {
+        self.lines.lock().unwrap().remove(author);
+    }
+}
+
+/// Runs `command` to completion, reporting each stdout and stderr line to
+/// `activity` as `author`'s output as soon as it is printed.
+///
+/// # Errors
+///
+/// Returns an error if the process cannot be spawned or its output cannot be read.
+pub async fn run_reporting_output(
+    command: &mut Command,
+    activity: &OutputActivity,
+    author: &str,
+) -> std::io::Result<Output> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+
+    let (stdout, stderr) = tokio::try_join!(
+        collect_lines(stdout, activity, author),
+        collect_lines(stderr, activity, author)
+    )?;
+    let status = child.wait().await?;
+    Ok(Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+async fn collect_lines<R: AsyncRead + Unpin>(
+    reader: R,
+    activity: &OutputActivity,
+    author: &str,
+) -> std::io::Result<Vec<u8>> {
+    let mut collected = Vec::new();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        activity.record(author, &line);
+        collected.extend_from_slice(line.as_bytes());
+        collected.push(b'\n');
+    }
+    Ok(collected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_keeps_last_non_empty_line_scrubbed() {
+        let activity = OutputActivity::default();
+        activity.record(
+            "Mai",
+            "Reading files\nCalling api with sk-abcdefghijklmnopqrstuv\n\n",
+        );
+        assert_eq!(
+            activity.last_line("Mai").as_deref(),
+            Some("Calling api with [REDACTED]")
+        );
+
+        // Blank output keeps the previous hint
+        activity.record("Mai", "   \n");
+        assert!(
+            activity
+                .last_line("Mai")
+                .unwrap()
+                .starts_with("Calling api")
+        );
+
+        activity.record("Mai", &"x".repeat(MAX_OUTPUT_LINE_CHARS + 50));
+        assert_eq!(
+            activity.last_line("Mai").unwrap().chars().count(),
+            MAX_OUTPUT_LINE_CHARS
+        );
+
+        activity.clear("Mai");
+        assert_eq!(activity.last_line("Mai"), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_reporting_output_collects_both_streams() {
+        let activity = OutputActivity::default();
+        let output = run_reporting_output(
+            Command::new("sh").args(["-c", "echo out; echo err >&2"]),
+            &activity,
+            "Mai",
+        )
+        .await
+        .unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+        assert!(activity.last_line("Mai").is_some());
+    }
+}
@@ -0,0 +1,134 @@
+//! HTTP client construction with proxy settings for API backends.
+//!
+//! Proxy precedence:
+//! 1. `EnvSettings.https_proxy` / `EnvSettings.http_proxy` (config.toml)
+//! 2. System `HTTPS_PROXY` / `HTTP_PROXY` environment variables
+//!
+//! When any proxy is configured in settings, reqwest's automatic system proxy
+//! detection is disabled, so settings fully replace the system environment.
+
+use llm_toolkit::agent::AgentError;
+use orcs_core::config::EnvSettings;
+use reqwest::{Client, Proxy};
+
+/// Proxy URLs resolved from settings (blank values are ignored).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyUrls {
+    pub https: Option<String>,
+    pub http: Option<String>,
+}
+
+impl ProxyUrls {
+    /// Reads proxy URLs from env settings.
+    pub fn from_settings(env_settings: &EnvSettings) -> Self {
+        let normalize = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        Self {
+            https: normalize(&env_settings.https_proxy),
+            http: normalize(&env_settings.http_proxy),
+        }
+    }
+
+    /// Returns true if no proxy is configured in settings.
+    pub fn is_empty(&self) -> bool {
+        self.https.is_none() && self.http.is_none()
+    }
+}
+
+/// Builds an HTTP client for API backends honoring proxy settings.
+///
+/// Falls back to a default client (which reads the system proxy variables)
+/// when settings define no proxy.
+///
+/// # Errors
+///
+/// Returns an error if a configured proxy URL is invalid.
+pub fn build_http_client(env_settings: &EnvSettings) -> Result<Client, AgentError> {
+    let proxies = ProxyUrls::from_settings(env_settings);
+    if proxies.is_empty() {
+        return Ok(Client::new());
+    }
+
+    let invalid = |e: reqwest::Error| {
+        AgentError::ExecutionFailed(format!("Invalid proxy URL in env_settings: {}", e))
+    };
+
+    let mut builder = Client::builder();
+    if let Some(url) = &proxies.https {
+        tracing::info!("[HttpProxy] Using HTTPS proxy from settings: {}", url);
+        builder = builder.proxy(Proxy::https(url).map_err(invalid)?);
+    }
+    if let Some(url) = &proxies.http {
+        tracing::info!("[HttpProxy] Using HTTP proxy from settings: {}", url);
+        builder = builder.proxy(Proxy::http(url).map_err(invalid)?);
+    }
+
+    builder
+        .build()
+        .map_err(|e| AgentError::ExecutionFailed(format!("Failed to build HTTP client: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_proxy_urls_from_settings() {
+        let settings = EnvSettings {
+            https_proxy: Some(" http://proxy.local:8080 ".to_string()),
+            http_proxy: Some("".to_string()),
+            ..Default::default()
+        };
+
+        let proxies = ProxyUrls::from_settings(&settings);
+
+        assert_eq!(proxies.https.as_deref(), Some("http://proxy.local:8080"));
+        assert_eq!(proxies.http, None);
+        assert!(ProxyUrls::from_settings(&EnvSettings::default()).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_proxy_url_is_error() {
+        let settings = EnvSettings {
+            https_proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+
+        assert!(build_http_client(&settings).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_https_requests_are_routed_through_configured_proxy() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_url = format!("http://{}", listener.local_addr().unwrap());
+        let settings = EnvSettings {
+            https_proxy: Some(proxy_url),
+            ..Default::default()
+        };
+        let client = build_http_client(&settings).unwrap();
+
+        let request = tokio::spawn(async move {
+            let _ = client
+                .get("https://api.anthropic.com/v1/messages")
+                .send()
+                .await;
+        });
+
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 1024];
+        let n = socket.read(&mut buf).await.unwrap();
+        let received = String::from_utf8_lossy(&buf[..n]);
+
+        assert!(received.starts_with("CONNECT api.anthropic.com:443"));
+        drop(socket);
+        request.abort();
+    }
+}
@@ -10,7 +10,7 @@
 use async_trait::async_trait;
 use llm_toolkit::agent::{Agent, AgentError, Payload};
 use orcs_core::secret::SecretService;
-use orcs_infrastructure::SecretServiceImpl;
+use orcs_infrastructure::{SecretServiceImpl, WireTap};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::env;
@@ -18,6 +18,7 @@ use std::time::Duration;
 
 const DEFAULT_KAIBA_URL: &str = "https://kaiba.shuttleapp.rs";
 const DEFAULT_CLAUDE_MODEL: &str = "claude-sonnet-4-6"; // Kaiba generates prompts, Claude executes
+const CLAUDE_MESSAGES_URL: &str = "https://api.anthropic.com/v1/messages";
 
 /// Agent implementation that talks to the Kaiba API to retrieve Rei prompts.
 #[derive(Clone)]
@@ -30,6 +31,7 @@ pub struct KaibaApiAgent {
     anthropic_api_key: String,
     model: String,
     max_tokens: u32,
+    wire_tap: Option<WireTap>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -91,6 +93,7 @@ impl KaibaApiAgent {
             anthropic_api_key: anthropic_api_key.into(),
             model: model.into(),
             max_tokens: 4096,
+            wire_tap: None,
         }
     }
 
@@ -186,6 +189,22 @@ impl KaibaApiAgent {
         self
     }
 
+    /// Records each Claude request and its raw response to the session's wire log.
+    pub fn with_wire_tap(mut self, tap: WireTap) -> Self {
+        let mut tap = tap.with_secret(self.anthropic_api_key.clone());
+        if let Some(api_key) = &self.kaiba_api_key {
+            tap = tap.with_secret(api_key.clone());
+        }
+        self.wire_tap = Some(tap);
+        self
+    }
+
+    fn record_exchange(&self, body: &ClaudeRequest, outcome: Result<&str, &str>) {
+        if let Some(tap) = &self.wire_tap {
+            tap.record_json(CLAUDE_MESSAGES_URL, body, outcome);
+        }
+    }
+
     /// Fetches the Rei prompt from Kaiba service.
     async fn fetch_rei_prompt(&self, context: &str) -> Result<KaibaPromptResponse, AgentError> {
         let url = format!("{}/kaiba/rei/{}/prompt", self.kaiba_url, self.rei_id);
@@ -239,7 +258,7 @@ impl KaibaApiAgent {
 
         let response = self
             .client
-            .post("https://api.anthropic.com/v1/messages")
+            .post(CLAUDE_MESSAGES_URL)
             .header("x-api-key", &self.anthropic_api_key)
             .header("anthropic-version", "2023-06-01")
             .header("content-type", "application/json")
@@ -247,9 +266,8 @@ impl KaibaApiAgent {
             .timeout(Duration::from_secs(120))
             .send()
             .await
-            .map_err(|e| {
-                AgentError::ExecutionFailed(format!("Claude API request failed: {}", e))
-            })?;
+            .map_err(|e| AgentError::ExecutionFailed(format!("Claude API request failed: {}", e)))
+            .inspect_err(|e| self.record_exchange(&request_body, Err(&e.to_string())))?;
 
         let status = response.status();
         if !status.is_success() {
@@ -257,15 +275,25 @@ impl KaibaApiAgent {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
+            self.record_exchange(&request_body, Err(&error_text));
             return Err(AgentError::ExecutionFailed(format!(
                 "Claude API error ({}): {}",
                 status, error_text
             )));
         }
 
-        let claude_response: ClaudeResponse = response.json().await.map_err(|e| {
-            AgentError::ExecutionFailed(format!("Failed to parse Claude response: {}", e))
-        })?;
+        let response_text = response
+            .text()
+            .await
+            .map_err(|e| {
+                AgentError::ExecutionFailed(format!("Failed to read Claude response: {}", e))
+            })
+            .inspect_err(|e| self.record_exchange(&request_body, Err(&e.to_string())))?;
+        self.record_exchange(&request_body, Ok(&response_text));
+        let claude_response: ClaudeResponse =
+            serde_json::from_str(&response_text).map_err(|e| {
+                AgentError::ExecutionFailed(format!("Failed to parse Claude response: {}", e))
+            })?;
 
         // Extract text from response
         Ok(claude_response
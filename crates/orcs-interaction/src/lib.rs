@@ -1,51 +1,121 @@
+pub mod attachment_reading;
+pub mod auto_chat_pacing;
+mod backend_agent;
 pub mod claude_api_agent;
+pub mod clock_context;
+pub mod collaboration_guidelines;
+mod dirty_sections;
 pub mod gemini_api_agent;
+pub mod generated_files;
+pub mod heartbeat;
+pub mod http_proxy;
 pub mod kaiba_api_agent;
 pub mod kaiba_memory_sync;
 pub mod local_agents;
+pub mod mentions;
 pub mod openai_api_agent;
+mod participant_digest;
+pub mod paste_attachment;
+pub mod pinboard;
+pub mod plan_block;
+pub mod poll;
+pub mod response_split;
 pub mod supported_models;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+mod thinking;
+pub mod turn_stats;
 
 // Re-export API agents for external use
+use crate::attachment_reading::{AttachmentReader, FsAttachmentReader};
+use crate::auto_chat_pacing::{AutoChatPacer, FailureKind};
+use crate::backend_agent::{
+    BackendCaptures, PersonaBackendAgent, RawOutputCapture, WarmClients, WireLogTarget,
+    warm_up_backend,
+};
 pub use crate::claude_api_agent::ClaudeApiAgent;
+use crate::clock_context::{ClockContextAgent, ClockContextSlot};
+use crate::dirty_sections::DirtySections;
 pub use crate::gemini_api_agent::GeminiApiAgent;
+use crate::heartbeat::{Heartbeat, OutputActivity};
 pub use crate::kaiba_api_agent::KaibaApiAgent;
 pub use crate::kaiba_memory_sync::KaibaMemorySyncService;
 pub use crate::openai_api_agent::OpenAIApiAgent;
+use crate::participant_digest::{ParticipantActivity, ParticipantDigestAgent};
+pub use crate::poll::{PollOptionCount, PollResult, PollVote};
+use crate::thinking::{ThinkingReportAgent, ThinkingSignal};
+use crate::turn_stats::{TurnStatsAgent, TurnStatsLog, UsageCapture};
+use llm_toolkit::ToPrompt;
 use llm_toolkit::agent::dialogue::{
-    Dialogue, DialogueTurn, ExecutionModel, ReactionStrategy, Speaker, TalkStyle,
+    BroadcastOrder, Dialogue, DialogueTurn, ExecutionModel, ReactionStrategy, Speaker, TalkStyle,
+    format_messages_to_prompt,
 };
-use llm_toolkit::agent::impls::{ClaudeCodeAgent, CodexAgent, GeminiAgent};
 use llm_toolkit::agent::persona::Persona as LlmPersona;
-use llm_toolkit::agent::{Agent, AgentError, Payload};
-use llm_toolkit::attachment::Attachment;
-use orcs_core::agent::build_enhanced_path;
+use llm_toolkit::agent::{Agent, AgentError, Payload, PayloadContent};
+use orcs_core::clock::{Clock, SystemClock};
 use orcs_core::config::EnvSettings;
-use orcs_core::persona::{Persona as PersonaDomain, PersonaBackend};
+use orcs_core::conversation_script::ScriptRun;
+use orcs_core::error::OrcsError;
+use orcs_core::offline::{OFFLINE_REASON, OfflineMode};
+use orcs_core::persona::{
+    CapabilityFlags, Persona as PersonaDomain, PersonaBackend, PresetSeeding, RuntimeCaps,
+    effective_capabilities, seed_default_presets, suggest_participants,
+};
 use orcs_core::repository::PersonaRepository;
 use orcs_core::session::{
-    AppMode, AutoChatConfig, ContextMode, ConversationMessage, ConversationMode, ErrorSeverity,
-    MessageMetadata, MessageRole, Plan, Session, SystemEventType,
+    AppMode, AutoChatConfig, CalibrationEvent, ContextMode, ConversationMessage, ConversationMode,
+    DEFAULT_PASTE_ATTACHMENT_THRESHOLD, ErrorSeverity, FeedbackKind, LlmDebugInfo,
+    MAX_SOURCES_PER_TURN, MessageMetadata, MessageRole, PersonaCalibrationState, Plan, Session,
+    SessionDelta, SourceRef, StyleCalibration, SystemEventType, TurnStats, detect_reused_sources,
+    wrap_untrusted,
 };
+use orcs_core::task::TaskOrigin;
 use orcs_core::user::UserService;
+use orcs_core::workspace::manager::WorkspaceStorageService;
+use orcs_infrastructure::{StreamRecoveryWriter, WireLog};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use tokio::sync::{Mutex, RwLock};
 
+/// Dialogue environment used when `EnvSettings::environment_description` is unset.
+pub const DEFAULT_DIALOGUE_ENVIRONMENT: &str =
+    "ORCS (Orchestrated Reasoning & Collaboration System) マルチエージェント対話アプリケーション";
+
+/// System message type of the note marking a participant handoff.
+pub const PARTICIPANT_HANDOFF_MESSAGE_TYPE: &str = "participant_handoff";
+
+/// Instruction for the final turn of a participant that is leaving.
+const PARTICIPANT_HANDOFF_INSTRUCTION: &str = "You are leaving this conversation. Write a handoff note for the remaining participants covering your key findings, open items, and recommendations. Keep it under 300 words.";
+
+/// Number of recent turns within which a persona keeps its full participant entry.
+pub const DEFAULT_PARTICIPANT_ACTIVITY_WINDOW: usize = 15;
+
+/// Reply to user input when there is no persona that could respond.
+pub const NO_PERSONAS_MESSAGE: &str =
+    "No personas are configured, so nobody can reply. Add a persona in the Personas tab.";
+
+/// Reply to user input when personas exist but none takes part in the session.
+pub const NO_PARTICIPANTS_MESSAGE: &str =
+    "No participants active — add a persona to get responses.";
+
 /// Converts a Persona domain model to llm-toolkit Persona.
 ///
-/// Automatically injects runtime capabilities based on the backend type
-/// into the communication_style to help the AI understand what it can and cannot do.
-fn domain_to_llm_persona(persona: &PersonaDomain) -> LlmPersona {
+/// Injects the persona's effective capabilities, given its configuration and the
+/// session's `runtime` state, into the communication_style to help the AI
+/// understand what it can and cannot do.
+fn domain_to_llm_persona(persona: &PersonaDomain, runtime: &RuntimeCaps) -> LlmPersona {
     use llm_toolkit::agent::persona::VisualIdentity;
 
+    let effective = effective_capabilities(persona, runtime);
+
     // Inject runtime capabilities into communication style
     let enhanced_communication_style = format!(
         "{}\n\n{}",
         persona.communication_style,
-        persona.backend.capabilities_markdown()
+        effective.markdown()
     );
 
     // Create visual identity if icon is present
@@ -58,8 +128,7 @@ fn domain_to_llm_persona(persona: &PersonaDomain) -> LlmPersona {
         identity
     });
 
-    // Get capabilities from backend
-    let capabilities = Some(persona.backend.capabilities());
+    let capabilities = Some(effective.to_toolkit());
 
     LlmPersona {
         name: persona.name.clone(),
@@ -120,13 +189,39 @@ pub enum StreamingDialogueTurnKind {
         author: String,
         /// The content of this chunk
         content: String,
+        /// Duration, token usage and estimated cost of the agent turn
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        turn_stats: Option<TurnStats>,
     },
     /// Stream completion marker (no more chunks)
     Final,
+    /// A persona started or finished generating its turn
+    Thinking {
+        /// The persona generating the turn
+        author: String,
+        /// True when the persona starts generating, false once its turn is done
+        active: bool,
+    },
+    /// A CLI-backed persona's turn is still running
+    Working {
+        /// The persona generating the turn
+        author: String,
+        /// Whole seconds since the persona started its turn
+        elapsed_secs: u64,
+        /// Most recent non-empty output line of the run, with secrets scrubbed
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        last_output_line: Option<String>,
+    },
     /// Error occurred during streaming
     Error {
         /// Error message to display
         message: String,
+        /// Whether retrying is likely to succeed (rate limits, transient backend errors)
+        #[serde(default)]
+        retryable: bool,
+        /// Wire log entry of the failed backend exchange, if the session's wire log is on
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wire_log_entry_id: Option<String>,
     },
     /// AutoChat iteration progress update
     AutoChatProgress {
@@ -134,250 +229,115 @@ pub enum StreamingDialogueTurnKind {
         current_iteration: i32,
         /// Maximum iterations configured
         max_iterations: i32,
+        /// Delay before the next iteration starts, in milliseconds
+        delay_ms: u64,
     },
     /// AutoChat completion notification
     AutoChatComplete {
         /// Total iterations completed
         total_iterations: i32,
     },
+    /// An agent-triggered command is waiting for user confirmation
+    PendingAction {
+        /// The queued action
+        action: orcs_core::pending_action::PendingAction,
+    },
+    /// A pending action was approved, denied or expired
+    PendingActionResolved {
+        /// ID of the resolved action
+        action_id: String,
+        /// How the action was resolved
+        status: orcs_core::pending_action::PendingActionStatus,
+    },
+    /// Personas that match the user's topic better than the active participants
+    ParticipantSuggestion {
+        /// Suggested personas, best match first
+        suggestions: Vec<ParticipantSuggestion>,
+    },
 }
 
-/// Agent wrapper that delegates to the configured backend.
-#[derive(Clone, Debug)]
-struct PersonaBackendAgent {
-    backend: PersonaBackend,
-    model_name: Option<String>,
-    gemini_options: Option<orcs_core::persona::GeminiOptions>,
-    kaiba_options: Option<orcs_core::persona::KaibaOptions>,
-    workspace_root: Arc<RwLock<Option<PathBuf>>>,
-    env_settings: Arc<RwLock<EnvSettings>>,
+/// A persona suggested as a participant for the user's latest message.
+///
+/// Suggestions are never acted on automatically; the user adds the persona
+/// by sending `add_participant` back to the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantSuggestion {
+    /// ID of the suggested persona
+    pub persona_id: String,
+    /// Display name of the persona
+    pub name: String,
+    /// Role of the persona
+    pub role: String,
+    /// Icon of the persona, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Keywords the message shares with the persona's profile
+    pub matched_keywords: Vec<String>,
+    /// Arguments for the `add_participant` command that adds this persona
+    pub add_participant: AddParticipantArgs,
 }
 
-impl PersonaBackendAgent {
-    fn new(
-        backend: PersonaBackend,
-        model_name: Option<String>,
-        gemini_options: Option<orcs_core::persona::GeminiOptions>,
-        kaiba_options: Option<orcs_core::persona::KaibaOptions>,
-        workspace_root: Arc<RwLock<Option<PathBuf>>>,
-        env_settings: Arc<RwLock<EnvSettings>>,
-    ) -> Self {
-        Self {
-            backend,
-            model_name,
-            gemini_options,
-            kaiba_options,
-            workspace_root,
-            env_settings,
-        }
-    }
-
-    /// Executes the agent with optional workspace context.
-    ///
-    /// # Arguments
-    ///
-    /// * `payload` - The input payload for the agent
-    /// * `workspace_root` - Optional workspace root path (logged but not used for directory changes)
-    ///
-    /// # Returns
-    ///
-    /// Returns the agent's output string.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the agent execution fails
-    async fn execute_with_workspace(
-        &self,
-        payload: Payload,
-        workspace_root: Option<PathBuf>,
-    ) -> Result<String, AgentError> {
-        // Log the intention but do not change the directory
-        tracing::info!(
-            "[PersonaBackendAgent] Executing with workspace context: {:?} for backend: {:?}",
-            workspace_root,
-            self.backend
-        );
-
-        match self.backend {
-            PersonaBackend::ClaudeCli => {
-                let mut agent = ClaudeCodeAgent::new()
-                    // Pre-approve Edit and Write tools to avoid constant approval prompts
-                    .with_args(vec![
-                        "--allowed-tools".to_string(),
-                        "Edit,Write".to_string(),
-                    ]);
-
-                // Set workspace root and enhanced PATH if provided
-                if let Some(workspace) = workspace_root {
-                    let env_settings = self.env_settings.read().await;
-                    let enhanced_path = build_enhanced_path(&workspace, Some(&*env_settings));
-                    agent = agent.with_cwd(workspace).with_env("PATH", enhanced_path);
-                }
-                // Apply model if specified
-                if let Some(ref model_str) = self.model_name {
-                    tracing::info!("[PersonaBackendAgent] Using Claude model: {}", model_str);
-                    agent = agent.with_model_str(model_str);
-                }
-                agent.execute(payload).await
-            }
-            PersonaBackend::ClaudeApi => {
-                let mut agent = ClaudeApiAgent::try_from_env().await?;
-                // Override model if specified
-                if let Some(ref model_str) = self.model_name {
-                    tracing::info!("[PersonaBackendAgent] Using Claude model: {}", model_str);
-                    agent = agent.with_model(model_str);
-                }
-                agent.execute(payload).await
-            }
-            PersonaBackend::GeminiCli => {
-                let mut agent = GeminiAgent::new();
-                // Set workspace root and enhanced PATH if provided
-                if let Some(workspace) = workspace_root {
-                    let env_settings = self.env_settings.read().await;
-                    let enhanced_path = build_enhanced_path(&workspace, Some(&*env_settings));
-                    agent = agent.with_cwd(workspace).with_env("PATH", enhanced_path);
-                }
-                // Apply model if specified
-                if let Some(ref model_str) = self.model_name {
-                    tracing::info!("[PersonaBackendAgent] Using Gemini model: {}", model_str);
-                    agent = agent.with_model_str(model_str);
-                }
-                agent.execute(payload).await
-            }
-            PersonaBackend::GeminiApi => {
-                let mut agent = GeminiApiAgent::try_from_env().await?;
-                // Override model if specified
-                if let Some(ref model_str) = self.model_name {
-                    tracing::info!("[PersonaBackendAgent] Using Gemini model: {}", model_str);
-                    agent = agent.with_model(model_str);
-                }
-                // Apply Gemini options if specified
-                if let Some(ref options) = self.gemini_options {
-                    if let Some(ref thinking_level) = options.thinking_level {
-                        tracing::info!(
-                            "[PersonaBackendAgent] Setting Gemini thinking level: {}",
-                            thinking_level
-                        );
-                        agent = agent.with_thinking_level(thinking_level);
-                    }
-                    if let Some(google_search) = options.google_search {
-                        tracing::info!(
-                            "[PersonaBackendAgent] Setting Gemini Google Search: {}",
-                            google_search
-                        );
-                        agent = agent.with_google_search(google_search);
-                    }
-                }
-                agent.execute(payload).await
-            }
-            PersonaBackend::OpenAiApi => {
-                let mut agent = OpenAIApiAgent::try_from_env().await?;
-                // Override model if specified
-                if let Some(ref model_str) = self.model_name {
-                    tracing::info!("[PersonaBackendAgent] Using OpenAI model: {}", model_str);
-                    agent = agent.with_model(model_str);
-                }
-                agent.execute(payload).await
-            }
-            PersonaBackend::CodexCli => {
-                let mut agent = CodexAgent::new();
-                // Set workspace root and enhanced PATH if provided
-                if let Some(workspace) = workspace_root {
-                    let env_settings = self.env_settings.read().await;
-                    let enhanced_path = build_enhanced_path(&workspace, Some(&*env_settings));
-                    agent = agent.with_cwd(workspace).with_env("PATH", enhanced_path);
-                }
-                // Apply model if specified
-                if let Some(ref model_str) = self.model_name {
-                    tracing::info!("[PersonaBackendAgent] Using Codex model: {}", model_str);
-                    agent = agent.with_model_str(model_str);
-                }
-                agent.execute(payload).await
-            }
-            PersonaBackend::KaibaApi => {
-                let mut agent = KaibaApiAgent::try_from_env().await?;
-                // Override Rei ID if specified in kaiba_options
-                if let Some(ref kaiba_opts) = self.kaiba_options
-                    && let Some(ref rei_id) = kaiba_opts.rei_id
-                {
-                    tracing::info!("[PersonaBackendAgent] Using Kaiba Rei ID: {}", rei_id);
-                    agent = agent.with_rei_id(rei_id);
-                }
-                agent.execute(payload).await
-            }
-        }
-    }
+/// Arguments of the `add_participant` command, as the frontend sends them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddParticipantArgs {
+    /// ID of the persona to add
+    pub persona_id: String,
 }
 
-#[async_trait::async_trait]
-impl Agent for PersonaBackendAgent {
-    type Output = String;
-    type Expertise = String;
-
-    fn expertise(&self) -> &String {
-        // Using lazy_static pattern for static storage with dynamic dispatch
-        use std::sync::OnceLock;
-        static EXPERTISE_CLAUDE_CLI: OnceLock<String> = OnceLock::new();
-        static EXPERTISE_CLAUDE_API: OnceLock<String> = OnceLock::new();
-        static EXPERTISE_GEMINI_CLI: OnceLock<String> = OnceLock::new();
-        static EXPERTISE_GEMINI_API: OnceLock<String> = OnceLock::new();
-        static EXPERTISE_OPENAI_API: OnceLock<String> = OnceLock::new();
-        static EXPERTISE_CODEX_CLI: OnceLock<String> = OnceLock::new();
-        static EXPERTISE_KAIBA_API: OnceLock<String> = OnceLock::new();
-
-        match self.backend {
-            PersonaBackend::ClaudeCli => {
-                EXPERTISE_CLAUDE_CLI.get_or_init(|| "Claude CLI persona agent".to_string())
-            }
-            PersonaBackend::ClaudeApi => {
-                EXPERTISE_CLAUDE_API.get_or_init(|| "Claude API persona agent".to_string())
-            }
-            PersonaBackend::GeminiCli => {
-                EXPERTISE_GEMINI_CLI.get_or_init(|| "Gemini CLI persona agent".to_string())
-            }
-            PersonaBackend::GeminiApi => {
-                EXPERTISE_GEMINI_API.get_or_init(|| "Gemini API persona agent".to_string())
-            }
-            PersonaBackend::OpenAiApi => {
-                EXPERTISE_OPENAI_API.get_or_init(|| "OpenAI API persona agent".to_string())
-            }
-            PersonaBackend::CodexCli => {
-                EXPERTISE_CODEX_CLI.get_or_init(|| "Codex CLI persona agent".to_string())
-            }
-            PersonaBackend::KaibaApi => EXPERTISE_KAIBA_API
-                .get_or_init(|| "Kaiba API persona agent (with persistent memory)".to_string()),
-        }
-    }
+/// Creates the agents that speak for personas.
+///
+/// Installed with [`InteractionManager::with_agent_factory`]. Without a factory,
+/// each persona's agent talks to its configured backend.
+pub trait AgentFactory: Send + Sync {
+    /// Creates the agent for `persona`; `keep_history` tells whether the agent
+    /// should keep its own chat history.
+    fn create_agent(
+        &self,
+        persona: &PersonaDomain,
+        keep_history: bool,
+    ) -> Box<dyn Agent<Output = String, Expertise = String>>;
 
-    async fn execute(&self, payload: Payload) -> Result<Self::Output, AgentError> {
-        // Read workspace_root from shared state
-        let workspace_root = self.workspace_root.read().await.clone();
-        tracing::info!(
-            "[PersonaBackendAgent::execute] Read workspace_root from Arc: {:?}",
-            workspace_root
-        );
-        self.execute_with_workspace(payload, workspace_root).await
+    /// Prepares the agent of an API-backed `persona` ahead of its first turn
+    /// (see [`InteractionManager::warmup_agents`]).
+    ///
+    /// The default does nothing.
+    fn warm_up(&self, _persona: &PersonaDomain) -> Result<(), String> {
+        Ok(())
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn agent_for_persona(
     persona: &PersonaDomain,
+    runtime: &RuntimeCaps,
     workspace_root: Arc<RwLock<Option<PathBuf>>>,
     env_settings: Arc<RwLock<EnvSettings>>,
+    default_models: &HashMap<PersonaBackend, String>,
+    keep_history: bool,
+    captures: BackendCaptures,
+    offline_mode: OfflineMode,
+    random_seed: Arc<RwLock<Option<u64>>>,
+    warm_clients: WarmClients,
 ) -> Box<dyn Agent<Output = String, Expertise = String>> {
     use llm_toolkit::agent::chat::Chat;
     use llm_toolkit::agent::persona::ContextConfig;
 
     let backend_agent = PersonaBackendAgent::new(
         persona.backend.clone(),
-        persona.model_name.clone(),
+        persona.resolved_model_name(default_models),
         persona.gemini_options.clone(),
         persona.kaiba_options.clone(),
         workspace_root,
         env_settings,
-    );
-
-    let llm_persona = domain_to_llm_persona(persona);
+        captures,
+    )
+    .with_reviewer_only(persona.reviewer_only)
+    .with_offline_mode(offline_mode)
+    .with_random_seed(random_seed)
+    .with_warm_clients(warm_clients);
+
+    let llm_persona = domain_to_llm_persona(persona, runtime);
     let mut chat = Chat::new(backend_agent).with_persona(llm_persona);
 
     // ClaudeCode backend の場合のみ ContextConfig を適用
@@ -391,7 +351,222 @@ fn agent_for_persona(
         chat = chat.with_context_config(config);
     }
 
-    chat.with_history(true).build()
+    chat.with_history(keep_history).build()
+}
+
+/// Agent wrapper that attaches session-scoped context to every payload.
+///
+/// The restored history and talk style are attached per agent instead of
+/// dialogue-wide, so personas with a Clean context override can be left out.
+/// Clean agents also have other agents' live turns stripped from the payload.
+struct ScopedContextAgent {
+    inner: Box<dyn Agent<Output = String, Expertise = String>>,
+    context: Option<String>,
+    clean: bool,
+}
+
+#[async_trait::async_trait]
+impl Agent for ScopedContextAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        self.inner.expertise()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn capabilities(&self) -> Option<Vec<llm_toolkit::agent::Capability>> {
+        self.inner.capabilities()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        let payload = if self.clean {
+            without_agent_turns(&payload)
+        } else {
+            payload
+        };
+        let payload = match &self.context {
+            Some(context) => payload.with_context(context.clone()),
+            None => payload,
+        };
+        self.inner.execute(payload).await
+    }
+
+    async fn is_available(&self) -> Result<(), AgentError> {
+        self.inner.is_available().await
+    }
+}
+
+/// Result of a turn whose dialogue was dropped while the turn was prepared.
+fn dialogue_invalidated() -> InteractionResult {
+    InteractionResult::NewMessage(
+        "Error: Dialogue was invalidated during initialization (possible race condition)"
+            .to_string(),
+    )
+}
+
+/// Rebuilds a payload without the messages spoken by agents.
+fn without_agent_turns(payload: &Payload) -> Payload {
+    rebuild_payload(payload, |content| match content {
+        PayloadContent::Message {
+            speaker: Speaker::Agent { .. },
+            ..
+        } => None,
+        content => Some(content),
+    })
+}
+
+/// Rebuilds a payload from its contexts and the contents kept by `keep`.
+pub(crate) fn rebuild_payload(
+    payload: &Payload,
+    keep: impl FnMut(PayloadContent) -> Option<PayloadContent>,
+) -> Payload {
+    let base = payload
+        .all_contexts()
+        .iter()
+        .cloned()
+        .fold(Payload::new(), Payload::push_context);
+    payload
+        .contents()
+        .iter()
+        .cloned()
+        .filter_map(keep)
+        .fold(base, |payload, content| match content {
+            PayloadContent::Message {
+                speaker,
+                content,
+                metadata,
+            } => payload.add_message_with_metadata(speaker, content, metadata),
+            PayloadContent::Text(text) => payload.with_text(text),
+            PayloadContent::Attachment(attachment) => payload.with_attachment(attachment),
+            PayloadContent::Participants(participants) => payload.with_participants(participants),
+            PayloadContent::Document(document) => payload.with_document(document),
+            PayloadContent::Context(context) => payload.with_context(context),
+        })
+}
+
+/// Adds a persona's style calibration hint to its payload while one is active.
+struct CalibratedAgent {
+    inner: Box<dyn Agent<Output = String, Expertise = String>>,
+    persona_id: String,
+    calibration: Arc<RwLock<Option<StyleCalibration>>>,
+}
+
+#[async_trait::async_trait]
+impl Agent for CalibratedAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        self.inner.expertise()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn capabilities(&self) -> Option<Vec<llm_toolkit::agent::Capability>> {
+        self.inner.capabilities()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        let hint = self
+            .calibration
+            .read()
+            .await
+            .as_ref()
+            .and_then(|calibration| calibration.hint_for(&self.persona_id));
+        let payload = match hint {
+            Some(hint) => payload.with_context(hint.to_string()),
+            None => payload,
+        };
+        self.inner.execute(payload).await
+    }
+
+    async fn is_available(&self) -> Result<(), AgentError> {
+        self.inner.is_available().await
+    }
+}
+
+/// Wraps a persona's agent with the session context it should receive.
+///
+/// Talk style follows the persona's effective context mode (its override, or the
+/// session's mode when unset). The restored history and other agents' live
+/// turns are withheld only from personas explicitly overridden to Clean; pinned
+/// messages reach every persona.
+fn scope_agent_context(
+    persona: &PersonaDomain,
+    agent: Box<dyn Agent<Output = String, Expertise = String>>,
+    session_mode: ContextMode,
+    talk_style: Option<&TalkStyle>,
+    history_context: Option<&str>,
+    pinned_context: Option<&str>,
+) -> Box<dyn Agent<Output = String, Expertise = String>> {
+    let mut context = Vec::new();
+    if matches!(
+        persona.effective_context_mode(session_mode),
+        ContextMode::Rich
+    ) && let Some(style) = talk_style
+    {
+        context.push(style.to_prompt());
+    }
+    let clean = persona.has_clean_context_override();
+    if !clean && let Some(history) = history_context {
+        context.push(history.to_string());
+    }
+    if clean && let Some(pinned) = pinned_context {
+        context.push(pinned.to_string());
+    }
+
+    if context.is_empty() && !clean {
+        agent
+    } else {
+        Box::new(ScopedContextAgent {
+            inner: agent,
+            context: (!context.is_empty()).then(|| context.join("\n\n")),
+            clean,
+        })
+    }
+}
+
+/// Formats restored dialogue turns as a conversation log for agent context.
+fn format_history_context(history: &[DialogueTurn]) -> String {
+    let messages: Vec<(Speaker, String)> = history
+        .iter()
+        .map(|turn| (turn.speaker.clone(), turn.content.clone()))
+        .collect();
+    format!(
+        "# Previous Conversation History\n\n\
+         The following is the conversation history from previous sessions. \
+         Please use this context to maintain continuity in the discussion.\n\n\
+         {}---\nEnd of previous conversation. Continue from here.\n",
+        format_messages_to_prompt(&messages)
+    )
+}
+
+/// Formats pinned turns for agents that receive no conversation history.
+fn format_pinned_context(pinned: &[DialogueTurn]) -> String {
+    let messages: Vec<(Speaker, String)> = pinned
+        .iter()
+        .map(|turn| (turn.speaker.clone(), turn.content.clone()))
+        .collect();
+    format!(
+        "# Pinned Notes\n\n\
+         The following messages were pinned and stay relevant for the rest of the conversation.\n\n\
+         {}",
+        format_messages_to_prompt(&messages)
+    )
 }
 
 /// Represents a single message in a dialogue conversation.
@@ -405,6 +580,257 @@ pub struct DialogueMessage {
     pub author: String,
     /// The content of the message.
     pub content: String,
+    /// Set when the message reports a failed agent turn instead of a reply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<TurnFailure>,
+    /// Set when the message only reports that the author started (`true`) or
+    /// finished (`false`) generating; such messages carry no content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<bool>,
+    /// Set on thinking messages reporting progress of a turn still in flight.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heartbeat: Option<Heartbeat>,
+    /// Duration, token usage and estimated cost of the turn that produced this reply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_stats: Option<TurnStats>,
+}
+
+impl DialogueMessage {
+    /// Creates a thinking indicator event for `author`.
+    pub fn thinking(session_id: &str, author: String, active: bool) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            author,
+            content: String::new(),
+            failure: None,
+            thinking: Some(active),
+            heartbeat: None,
+            turn_stats: None,
+        }
+    }
+
+    /// Creates a heartbeat event for `author`, whose turn is still running.
+    pub fn heartbeat(session_id: &str, author: String, heartbeat: Heartbeat) -> Self {
+        Self {
+            heartbeat: Some(heartbeat),
+            ..Self::thinking(session_id, author, true)
+        }
+    }
+
+    /// Returns whether this message is a thinking indicator event, heartbeats included.
+    pub fn is_thinking(&self) -> bool {
+        self.thinking.is_some()
+    }
+
+    /// Converts the message into the streaming event sent to the frontend.
+    pub fn to_streaming_kind(&self) -> StreamingDialogueTurnKind {
+        if let Some(heartbeat) = &self.heartbeat {
+            return StreamingDialogueTurnKind::Working {
+                author: self.author.clone(),
+                elapsed_secs: heartbeat.elapsed_secs,
+                last_output_line: heartbeat.last_output_line.clone(),
+            };
+        }
+        if let Some(active) = self.thinking {
+            return StreamingDialogueTurnKind::Thinking {
+                author: self.author.clone(),
+                active,
+            };
+        }
+        match &self.failure {
+            Some(failure) => StreamingDialogueTurnKind::Error {
+                message: self.content.clone(),
+                retryable: failure.retryable,
+                wire_log_entry_id: failure.wire_log_entry_id.clone(),
+            },
+            None => StreamingDialogueTurnKind::Chunk {
+                author: self.author.clone(),
+                content: self.content.clone(),
+                turn_stats: self.turn_stats.clone(),
+            },
+        }
+    }
+}
+
+/// Classification of a failed agent turn reported through a [`DialogueMessage`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TurnFailure {
+    /// Whether retrying is likely to succeed (rate limits, transient backend errors)
+    pub retryable: bool,
+    /// Wire log entry of the failed backend exchange, if the session's wire log is on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wire_log_entry_id: Option<String>,
+}
+
+impl TurnFailure {
+    /// Classifies an agent error.
+    pub fn of(error: &AgentError) -> Self {
+        Self {
+            retryable: FailureKind::of(error) == FailureKind::RateLimited || error.is_retryable(),
+            wire_log_entry_id: None,
+        }
+    }
+
+    /// Message reporting the failure to the user.
+    fn message(&self, error: &AgentError) -> String {
+        let mut message = format!("{}\n\nPlease check the logs for more details.", error);
+        if let Some(entry_id) = &self.wire_log_entry_id {
+            message.push_str(&format!("\nWire log entry: {}", entry_id));
+        }
+        message
+    }
+}
+
+/// A system message to record with
+/// [`InteractionManager::add_system_conversation_messages`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemMessageEntry {
+    pub content: String,
+    /// Frontend message type, e.g. `context_info` or `shell_output`
+    pub message_type: Option<String>,
+    pub error_severity: Option<ErrorSeverity>,
+}
+
+impl SystemMessageEntry {
+    fn into_message(self, attachments: Vec<String>) -> ConversationMessage {
+        let source = SourceRef::for_system_message(
+            self.message_type.as_deref(),
+            &self.content,
+            &attachments,
+        );
+        ConversationMessage {
+            role: MessageRole::System,
+            content: self.content,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            metadata: MessageMetadata {
+                system_event_type: Some(SystemEventType::Notification),
+                error_severity: self.error_severity,
+                system_message_type: self.message_type,
+                include_in_dialogue: true,
+                llm_debug_info: None,
+                recovered_partial: false,
+                pinned: false,
+                split_from_single_response: false,
+                spawned_task_ids: Vec::new(),
+                in_response_to: None,
+                source,
+                sources: Vec::new(),
+                full_content_path: None,
+                turn_stats: None,
+            },
+            attachments,
+        }
+    }
+}
+
+/// Returns true for system messages agents must see on their next turn.
+fn is_context_info(message: &ConversationMessage) -> bool {
+    matches!(
+        message.metadata.system_message_type.as_deref(),
+        Some("context_info" | "shell_output")
+    )
+}
+
+/// Builds a system notification shown to the user but not sent to agents.
+fn notification_message(content: String) -> ConversationMessage {
+    ConversationMessage {
+        role: MessageRole::System,
+        content,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        metadata: MessageMetadata {
+            system_event_type: Some(SystemEventType::Notification),
+            error_severity: None,
+            system_message_type: None,
+            include_in_dialogue: false,
+            llm_debug_info: None,
+            recovered_partial: false,
+            pinned: false,
+            split_from_single_response: false,
+            spawned_task_ids: Vec::new(),
+            in_response_to: None,
+            source: None,
+            sources: Vec::new(),
+            full_content_path: None,
+            turn_stats: None,
+        },
+        attachments: vec![],
+    }
+}
+
+/// Keeps the first `max_participants` of `participant_ids`, in join order.
+///
+/// Returns a notification naming the participants left out, looked up in
+/// `names` and falling back to their IDs, or `None` if all of them fit.
+fn seat_within_cap(
+    participant_ids: &mut Vec<String>,
+    max_participants: usize,
+    names: &HashMap<String, String>,
+) -> Option<ConversationMessage> {
+    if participant_ids.len() <= max_participants {
+        return None;
+    }
+    let left_out: Vec<&str> = participant_ids[max_participants..]
+        .iter()
+        .map(|id| names.get(id).unwrap_or(id).as_str())
+        .collect();
+    tracing::warn!(
+        "[InteractionManager] Roster exceeds the maximum of {} participants; leaving out {}",
+        max_participants,
+        left_out.join(", ")
+    );
+    let notice = notification_message(format!(
+        "参加者の上限（{}人）を超えるため、{} はこの会話に参加していません。\
+         設定で max_participants を引き上げると、参加させられます。",
+        max_participants,
+        left_out.join("、")
+    ));
+    participant_ids.truncate(max_participants);
+    Some(notice)
+}
+
+/// Largest attached file whose text is compared as part of its source.
+const MAX_SOURCE_FILE_BYTES: u64 = 256 * 1024;
+
+/// Reads an attached file as text for reuse detection.
+///
+/// Returns `None` for missing, oversized or non-UTF-8 files.
+async fn read_source_file(path: &str) -> Option<String> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_SOURCE_FILE_BYTES {
+        return None;
+    }
+    tokio::fs::read_to_string(path).await.ok()
+}
+
+/// A participant of a restored session whose persona no longer exists.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreWarning {
+    /// ID of the missing persona
+    pub persona_id: String,
+    /// Name recorded in the session, or the ID when the session has none
+    pub persona_name: String,
+    /// Explanation shown to the user
+    pub message: String,
+}
+
+/// Display metadata for session participants, keyed by persona ID.
+///
+/// Field names match the corresponding [`Session`] fields so the frontend can
+/// merge a refresh result into a loaded session directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipantMetadata {
+    /// Persona ID (or user name) -> display name
+    pub participants: HashMap<String, String>,
+    /// Persona ID -> icon
+    pub participant_icons: HashMap<String, String>,
+    /// Persona ID -> base color
+    pub participant_colors: HashMap<String, String>,
+    /// Persona ID -> backend (e.g., "claude_api")
+    pub participant_backends: HashMap<String, String>,
+    /// Persona ID -> model name
+    pub participant_models: HashMap<String, Option<String>>,
 }
 
 /// Result of handling user input in a stateful conversation.
@@ -458,6 +884,10 @@ pub struct InteractionManager {
     execution_strategy: Arc<RwLock<ExecutionModel>>,
     /// Active participant persona IDs (restored from session or populated dynamically)
     restored_participant_ids: Arc<RwLock<Option<Vec<String>>>>,
+    /// Restored conversation history attached to the agents of the current dialogue
+    restored_history_context: Arc<RwLock<Option<String>>>,
+    /// Pinned messages attached to agents that receive no restored history
+    pinned_history_context: Arc<RwLock<Option<String>>>,
     /// System messages (join/leave notifications, etc.)
     system_messages: Arc<RwLock<Vec<ConversationMessage>>>,
     /// Conversation mode (controls verbosity and style)
@@ -476,6 +906,86 @@ pub struct InteractionManager {
     context_mode: Arc<RwLock<ContextMode>>,
     /// Sandbox state for git worktree-based isolated development
     sandbox_state: Arc<RwLock<Option<orcs_core::session::SandboxState>>>,
+    /// Whether a background dialogue is currently generating responses
+    is_generating: Arc<AtomicBool>,
+    /// Participant names/icons/colors as of the last refresh
+    participant_metadata: Arc<RwLock<ParticipantMetadata>>,
+    /// Style calibration from user feedback (None when disabled for this session)
+    style_calibration: Arc<RwLock<Option<StyleCalibration>>>,
+    /// Speakers of recent turns, used to shorten the participants block
+    participant_activity: Arc<RwLock<ParticipantActivity>>,
+    /// Failures of agent turns in the latest dialogue round
+    round_failures: Arc<RwLock<Vec<FailureKind>>>,
+    /// Raw CLI output of the latest turns by persona ID (LLM debug mode only)
+    raw_cli_outputs: Arc<RwLock<HashMap<String, LlmDebugInfo>>>,
+    /// Duration, token usage and cost of the latest turns by persona ID
+    turn_stats: TurnStatsLog,
+    /// Mirrors agent turns to a recovery file until the session is saved
+    stream_recovery: Option<StreamRecoveryWriter>,
+    /// Opt-in per-session log of raw backend exchanges
+    wire_log: Option<WireLog>,
+    /// App-level default model per backend, used when a persona has no model
+    default_models: Arc<RwLock<HashMap<PersonaBackend, String>>>,
+    /// Reports persona turn starts to the dialogue round currently streaming
+    thinking_signal: ThinkingSignal,
+    /// Whether replies speaking for several personas are split per persona
+    response_splitting: Arc<RwLock<bool>>,
+    /// User whose input is handled next (None: the user service's current user)
+    acting_user: Arc<RwLock<Option<String>>>,
+    /// Creates persona agents instead of the configured backends (None: backends)
+    agent_factory: Option<Arc<dyn AgentFactory>>,
+    /// Shared notes document shown to agents in Rich context mode
+    pinboard: Arc<RwLock<Option<String>>>,
+    /// Whether agents may append to the pinboard with `<Pinboard>` blocks
+    pinboard_agent_edits: Arc<RwLock<bool>>,
+    /// Set when agents edit the pinboard mid-round; the dialogue is rebuilt
+    /// before its next use
+    pinboard_changed: Arc<AtomicBool>,
+    /// App-wide offline mode; API-backed participants sit out while it is on
+    offline_mode: OfflineMode,
+    /// Whether the cached dialogue was built while offline mode was on
+    dialogue_built_offline: Arc<AtomicBool>,
+    /// Whether the conversation-mode instruction also applies in Clean context mode
+    apply_conversation_mode_in_clean: Arc<RwLock<bool>>,
+    /// Whether personas matching an uncovered topic are suggested after user turns
+    participant_suggestions: Arc<RwLock<bool>>,
+    /// Progress of the conversation script running in this session
+    script_run: Arc<RwLock<Option<ScriptRun>>>,
+    /// Participants answering while focus mode is on (None: everyone)
+    focused_participant_ids: Arc<RwLock<Option<Vec<String>>>>,
+    /// Participants outside the focus mentioned in the latest input; they
+    /// answer that turn only
+    focus_guests: Arc<RwLock<Vec<String>>>,
+    /// Seed passed to backends that support one, for reproducible replies
+    random_seed: Arc<RwLock<Option<u64>>>,
+    /// API clients built by `warmup_agents`, taken by each backend's first turn
+    warm_clients: WarmClients,
+    /// Whether Rich-mode turns tell agents the current time
+    clock_context_enabled: Arc<RwLock<bool>>,
+    /// Clock context block of the round currently running, added to each agent's prompt
+    clock_context_slot: ClockContextSlot,
+    /// Source of the current time for the clock context
+    clock: Arc<dyn Clock>,
+    /// Reads attached files before a turn
+    attachment_reader: Arc<dyn AttachmentReader>,
+    /// Whether fenced `plan` blocks in agent replies are offered for confirmation
+    plan_detection: Arc<RwLock<bool>>,
+    /// Whether long non-prose pastes are stored and sent as attachments
+    paste_attachment_enabled: Arc<RwLock<bool>>,
+    /// Length in characters above which a paste is sent as an attachment
+    paste_attachment_threshold: Arc<RwLock<usize>>,
+    /// Storage for pastes sent as attachments (none: pastes are sent as is)
+    workspace_storage: Option<Arc<dyn WorkspaceStorageService>>,
+    /// Personas whose suggestions the user dismissed (runtime only)
+    dismissed_suggestions: Arc<RwLock<HashSet<String>>>,
+    /// Set once the restored participants were checked against the persona repository
+    restore_validated: Arc<AtomicBool>,
+    /// Restored participants whose personas no longer exist
+    restore_warnings: Arc<RwLock<Vec<RestoreWarning>>>,
+    /// Number of [`Self::invalidate_dialogue`] calls, for diagnostics
+    dialogue_invalidations: Arc<AtomicUsize>,
+    /// Sections changed since the session was last saved
+    dirty_sections: Arc<DirtySections>,
 }
 
 impl InteractionManager {
@@ -518,6 +1028,8 @@ impl InteractionManager {
             env_settings: Arc::new(RwLock::new(env_settings)),
             execution_strategy: Arc::new(RwLock::new(ExecutionModel::Broadcast)),
             restored_participant_ids: Arc::new(RwLock::new(None)),
+            restored_history_context: Arc::new(RwLock::new(None)),
+            pinned_history_context: Arc::new(RwLock::new(None)),
             system_messages: Arc::new(RwLock::new(Vec::new())),
             conversation_mode: Arc::new(RwLock::new(ConversationMode::default())),
             talk_style: Arc::new(RwLock::new(None)),
@@ -527,6 +1039,47 @@ impl InteractionManager {
             is_muted: Arc::new(RwLock::new(false)),
             context_mode: Arc::new(RwLock::new(ContextMode::default())),
             sandbox_state: Arc::new(RwLock::new(None)),
+            is_generating: Arc::new(AtomicBool::new(false)),
+            participant_metadata: Arc::new(RwLock::new(ParticipantMetadata::default())),
+            style_calibration: Arc::new(RwLock::new(None)),
+            participant_activity: Arc::new(RwLock::new(ParticipantActivity::new(
+                DEFAULT_PARTICIPANT_ACTIVITY_WINDOW,
+            ))),
+            round_failures: Arc::new(RwLock::new(Vec::new())),
+            raw_cli_outputs: Arc::new(RwLock::new(HashMap::new())),
+            turn_stats: TurnStatsLog::default(),
+            stream_recovery: None,
+            wire_log: None,
+            default_models: Arc::new(RwLock::new(HashMap::new())),
+            thinking_signal: ThinkingSignal::default(),
+            response_splitting: Arc::new(RwLock::new(true)),
+            acting_user: Arc::new(RwLock::new(None)),
+            agent_factory: None,
+            pinboard: Arc::new(RwLock::new(None)),
+            pinboard_agent_edits: Arc::new(RwLock::new(false)),
+            apply_conversation_mode_in_clean: Arc::new(RwLock::new(true)),
+            participant_suggestions: Arc::new(RwLock::new(false)),
+            script_run: Arc::new(RwLock::new(None)),
+            focused_participant_ids: Arc::new(RwLock::new(None)),
+            focus_guests: Arc::new(RwLock::new(Vec::new())),
+            random_seed: Arc::new(RwLock::new(None)),
+            warm_clients: WarmClients::default(),
+            clock_context_enabled: Arc::new(RwLock::new(true)),
+            clock_context_slot: ClockContextSlot::default(),
+            clock: Arc::new(SystemClock),
+            attachment_reader: Arc::new(FsAttachmentReader),
+            plan_detection: Arc::new(RwLock::new(false)),
+            paste_attachment_enabled: Arc::new(RwLock::new(true)),
+            paste_attachment_threshold: Arc::new(RwLock::new(DEFAULT_PASTE_ATTACHMENT_THRESHOLD)),
+            workspace_storage: None,
+            dismissed_suggestions: Arc::new(RwLock::new(HashSet::new())),
+            restore_validated: Arc::new(AtomicBool::new(true)),
+            restore_warnings: Arc::new(RwLock::new(Vec::new())),
+            pinboard_changed: Arc::new(AtomicBool::new(false)),
+            offline_mode: OfflineMode::default(),
+            dialogue_built_offline: Arc::new(AtomicBool::new(false)),
+            dialogue_invalidations: Arc::new(AtomicUsize::new(0)),
+            dirty_sections: Arc::default(),
         }
     }
 
@@ -544,15 +1097,22 @@ impl InteractionManager {
     /// This creates new Agent instances. History is stored separately
     /// in persona_histories and included in prompts manually.
     pub fn from_session(
-        data: Session,
+        mut data: Session,
         persona_repository: Arc<dyn PersonaRepository>,
         user_service: Arc<dyn UserService>,
         env_settings: EnvSettings,
     ) -> Self {
+        data.relocate_legacy_error_history();
         let restored_ids = if data.active_participant_ids.is_empty() {
             None
         } else {
-            Some(data.active_participant_ids)
+            let mut ids = data.active_participant_ids;
+            data.system_messages.extend(seat_within_cap(
+                &mut ids,
+                env_settings.max_participants,
+                &data.participants,
+            ));
+            Some(ids)
         };
 
         Self {
@@ -568,6 +1128,8 @@ impl InteractionManager {
             env_settings: Arc::new(RwLock::new(env_settings)),
             execution_strategy: Arc::new(RwLock::new(data.execution_strategy)),
             restored_participant_ids: Arc::new(RwLock::new(restored_ids)),
+            restored_history_context: Arc::new(RwLock::new(None)),
+            pinned_history_context: Arc::new(RwLock::new(None)),
             system_messages: Arc::new(RwLock::new(data.system_messages)),
             conversation_mode: Arc::new(RwLock::new(data.conversation_mode)),
             talk_style: Arc::new(RwLock::new(data.talk_style)),
@@ -577,45 +1139,721 @@ impl InteractionManager {
             is_muted: Arc::new(RwLock::new(data.is_muted)),
             context_mode: Arc::new(RwLock::new(data.context_mode)),
             sandbox_state: Arc::new(RwLock::new(data.sandbox_state)),
+            is_generating: Arc::new(AtomicBool::new(false)), // Never generating when restored from disk
+            participant_metadata: Arc::new(RwLock::new(ParticipantMetadata {
+                participants: data.participants,
+                participant_icons: data.participant_icons,
+                participant_colors: data.participant_colors,
+                participant_backends: data.participant_backends,
+                participant_models: data.participant_models,
+            })),
+            // Feedback counts are not persisted; only the opt-in survives a restart
+            style_calibration: Arc::new(RwLock::new(
+                data.style_calibration_enabled
+                    .then(StyleCalibration::default),
+            )),
+            participant_activity: Arc::new(RwLock::new(ParticipantActivity::new(
+                DEFAULT_PARTICIPANT_ACTIVITY_WINDOW,
+            ))),
+            round_failures: Arc::new(RwLock::new(Vec::new())),
+            raw_cli_outputs: Arc::new(RwLock::new(HashMap::new())),
+            turn_stats: TurnStatsLog::default(),
+            stream_recovery: None,
+            wire_log: None,
+            default_models: Arc::new(RwLock::new(HashMap::new())),
+            thinking_signal: ThinkingSignal::default(),
+            response_splitting: Arc::new(RwLock::new(data.response_splitting_enabled)),
+            acting_user: Arc::new(RwLock::new(None)),
+            agent_factory: None,
+            pinboard: Arc::new(RwLock::new(data.pinboard.clone())),
+            pinboard_agent_edits: Arc::new(RwLock::new(data.pinboard_agent_edits_enabled)),
+            apply_conversation_mode_in_clean: Arc::new(RwLock::new(
+                data.apply_conversation_mode_in_clean,
+            )),
+            participant_suggestions: Arc::new(RwLock::new(data.participant_suggestions_enabled)),
+            script_run: Arc::new(RwLock::new(data.script_run.clone())),
+            focused_participant_ids: Arc::new(RwLock::new(data.focused_participant_ids.clone())),
+            focus_guests: Arc::new(RwLock::new(Vec::new())),
+            random_seed: Arc::new(RwLock::new(data.random_seed)),
+            warm_clients: WarmClients::default(),
+            clock_context_enabled: Arc::new(RwLock::new(data.clock_context_enabled)),
+            clock_context_slot: ClockContextSlot::default(),
+            clock: Arc::new(SystemClock),
+            attachment_reader: Arc::new(FsAttachmentReader),
+            plan_detection: Arc::new(RwLock::new(data.plan_detection_enabled)),
+            paste_attachment_enabled: Arc::new(RwLock::new(data.paste_attachment_enabled)),
+            paste_attachment_threshold: Arc::new(RwLock::new(data.paste_attachment_threshold)),
+            workspace_storage: None,
+            dismissed_suggestions: Arc::new(RwLock::new(HashSet::new())),
+            restore_validated: Arc::new(AtomicBool::new(false)),
+            restore_warnings: Arc::new(RwLock::new(Vec::new())),
+            pinboard_changed: Arc::new(AtomicBool::new(false)),
+            offline_mode: OfflineMode::default(),
+            dialogue_built_offline: Arc::new(AtomicBool::new(false)),
+            dialogue_invalidations: Arc::new(AtomicUsize::new(0)),
+            dirty_sections: Arc::default(),
         }
     }
 
-    /// Resolves a persona name to its UUID.
+    /// Records every agent turn with `writer` so output survives a crash
+    /// before the session is saved.
     ///
-    /// This is used to convert speaker names to persona IDs.
-    async fn get_persona_id_by_name(&self, name: &str) -> Option<String> {
-        let personas = self.persona_repository.get_all().await.ok()?;
-        personas
-            .iter()
-            .find(|p| p.name == name)
-            .map(|p| p.id.clone())
+    /// Covers all dialogue paths (input, background, AutoChat, polls); whoever
+    /// saves the session calls [`StreamRecoveryWriter::complete`] afterwards.
+    pub fn with_stream_recovery(mut self, writer: StreamRecoveryWriter) -> Self {
+        self.stream_recovery = Some(writer);
+        self
     }
 
-    /// Rebuilds dialogue history from persona_histories and system_messages for restoration.
-    ///
-    /// This method converts the stored conversation messages into DialogueTurn format,
-    /// sorted by timestamp, to restore the conversation context when recreating a Dialogue.
+    /// Logs persona backend exchanges to `log` while this session's wire log is on.
     ///
-    /// # Returns
+    /// Failed turns then link to the wire log entry of the failed exchange.
+    pub fn with_wire_log(mut self, log: WireLog) -> Self {
+        self.wire_log = Some(log);
+        self
+    }
+
+    /// Classifies a failed turn, linking the wire log entry of the failed exchange.
+    fn turn_failure(&self, error: &AgentError) -> TurnFailure {
+        TurnFailure {
+            wire_log_entry_id: self
+                .wire_log
+                .as_ref()
+                .and_then(|log| log.take_failure(&self.session_id)),
+            ..TurnFailure::of(error)
+        }
+    }
+
+    /// Uses `models` as the per-backend default for personas without a model.
+    pub fn with_default_models(mut self, models: HashMap<PersonaBackend, String>) -> Self {
+        self.default_models = Arc::new(RwLock::new(models));
+        self
+    }
+
+    /// Shares the app-wide offline mode switch.
     ///
-    /// A vector of DialogueTurn representing the full conversation history.
-    async fn rebuild_dialogue_history(&self) -> Vec<DialogueTurn> {
-        let histories = self.persona_histories.read().await;
+    /// While it is on, participants with an API backend answer through their
+    /// CLI `fallback_backend` or sit out with a warning. Turning it off brings
+    /// them back on the next turn.
+    pub fn with_offline_mode(mut self, offline_mode: OfflineMode) -> Self {
+        self.offline_mode = offline_mode;
+        self
+    }
 
-        // Flatten all messages with (persona_id, timestamp, message)
-        let mut all_messages: Vec<(String, String, ConversationMessage)> = Vec::new();
+    /// Reads the current time for the clock context from `clock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
 
-        // Add messages from persona_histories
-        for (persona_id, messages) in histories.iter() {
-            for msg in messages {
-                all_messages.push((persona_id.clone(), msg.timestamp.clone(), msg.clone()));
-            }
-        }
+    /// Reads attached files with `reader` instead of from the file system.
+    pub fn with_attachment_reader(mut self, reader: Arc<dyn AttachmentReader>) -> Self {
+        self.attachment_reader = reader;
+        self
+    }
 
-        // Add system_messages that should be included in dialogue
+    /// Stores long non-prose pastes in `storage`, in the session's workspace.
+    ///
+    /// Without a storage, pastes are always sent as typed.
+    pub fn with_workspace_storage(mut self, storage: Arc<dyn WorkspaceStorageService>) -> Self {
+        self.workspace_storage = Some(storage);
+        self
+    }
+
+    /// Creates persona agents with `factory` instead of their configured backends.
+    pub fn with_agent_factory(mut self, factory: Arc<dyn AgentFactory>) -> Self {
+        self.agent_factory = Some(factory);
+        self
+    }
+
+    /// Wraps `agent` with the per-turn layers every participant gets: style
+    /// calibration, the participant digest, turn stats, the clock context and
+    /// thinking reports.
+    fn wrap_persona_agent(
+        &self,
+        persona: &PersonaDomain,
+        agent: Box<dyn Agent<Output = String, Expertise = String>>,
+    ) -> Box<dyn Agent<Output = String, Expertise = String>> {
+        let agent = Box::new(CalibratedAgent {
+            inner: agent,
+            persona_id: persona.id.clone(),
+            calibration: self.style_calibration.clone(),
+        });
+        let agent = Box::new(ParticipantDigestAgent {
+            inner: agent,
+            persona_name: persona.name.clone(),
+            activity: self.participant_activity.clone(),
+        });
+        let agent = Box::new(TurnStatsAgent {
+            inner: agent,
+            persona_id: persona.id.clone(),
+            log: self.turn_stats.clone(),
+        });
+        let agent = Box::new(ClockContextAgent {
+            inner: agent,
+            slot: self.clock_context_slot.clone(),
+        });
+        Box::new(ThinkingReportAgent {
+            inner: agent,
+            persona_name: persona.name.clone(),
+            signal: self.thinking_signal.clone(),
+            heartbeat: persona.backend.is_cli(),
+        })
+    }
+
+    /// Creates the agent for `persona`, with the agent factory if one is installed.
+    fn create_agent(
+        &self,
+        persona: &PersonaDomain,
+        runtime: &RuntimeCaps,
+        default_models: &HashMap<PersonaBackend, String>,
+        keep_history: bool,
+    ) -> Box<dyn Agent<Output = String, Expertise = String>> {
+        if let Some(factory) = &self.agent_factory {
+            return factory.create_agent(persona, keep_history);
+        }
+        agent_for_persona(
+            persona,
+            runtime,
+            self.agent_workspace_root.clone(),
+            self.env_settings.clone(),
+            default_models,
+            keep_history,
+            BackendCaptures {
+                raw_output: Some(self.raw_output_capture(&persona.id)),
+                wire_log: self.wire_log.clone().map(|log| WireLogTarget {
+                    log,
+                    session_id: self.session_id.clone(),
+                    persona_id: persona.id.clone(),
+                }),
+                usage: Some(UsageCapture {
+                    persona_id: persona.id.clone(),
+                    log: self.turn_stats.clone(),
+                }),
+            },
+            self.offline_mode.clone(),
+            self.random_seed.clone(),
+            self.warm_clients.clone(),
+        )
+    }
+
+    /// Replaces the per-backend default models.
+    ///
+    /// Agents are rebuilt with the new models on the next interaction.
+    pub async fn set_default_models(&self, models: HashMap<PersonaBackend, String>) {
+        *self.default_models.write().await = models;
+        self.invalidate_dialogue().await;
+    }
+
+    /// Adds an agent turn to history and records it for crash recovery.
+    ///
+    /// Returns the speaker's persona ID and the message to stream to the UI.
+    async fn commit_agent_turn(
+        &self,
+        speaker_name: &str,
+        content: &str,
+    ) -> (String, DialogueMessage) {
+        self.commit_turn_as(speaker_name, content, false, None)
+            .await
+    }
+
+    /// Commits an agent turn, first splitting a reply that speaks for several
+    /// participants into one turn per persona when splitting is enabled.
+    ///
+    /// `in_response_to` (the persona ID of the agent the reply reacts to) is
+    /// recorded on the speaker's own turn only, not on parts split out for
+    /// other personas. Parts are returned in reply order; see
+    /// [`response_split::split_response`].
+    async fn commit_agent_turns(
+        &self,
+        speaker_name: &str,
+        content: &str,
+        participant_names: &[String],
+        in_response_to: Option<&str>,
+    ) -> Vec<(String, DialogueMessage)> {
+        let parts = if *self.response_splitting.read().await {
+            response_split::split_response(content, speaker_name, participant_names)
+        } else {
+            None
+        };
+        let Some(parts) = parts else {
+            return vec![
+                self.commit_turn_as(speaker_name, content, false, in_response_to)
+                    .await,
+            ];
+        };
+
+        tracing::info!(
+            "[DIALOGUE] Splitting reply of {} into {} parts",
+            speaker_name,
+            parts.len()
+        );
+        let mut committed = Vec::with_capacity(parts.len());
+        for part in parts {
+            let split = part.author != speaker_name;
+            let in_response_to = if split { None } else { in_response_to };
+            committed.push(
+                self.commit_turn_as(&part.author, &part.content, split, in_response_to)
+                    .await,
+            );
+        }
+        committed
+    }
+
+    /// Returns the persona ID of `speaker_name`, reusing the one resolved for
+    /// their own turn among `turns` instead of looking it up again.
+    async fn speaker_id(&self, speaker_name: &str, turns: &[(String, DialogueMessage)]) -> String {
+        if let Some((persona_id, _)) = turns.iter().find(|(_, m)| m.author == speaker_name) {
+            return persona_id.clone();
+        }
+        self.get_persona_id_by_name(speaker_name)
+            .await
+            .unwrap_or_else(|| speaker_name.to_string())
+    }
+
+    /// Adds a turn to `author`'s history and records it for crash recovery.
+    ///
+    /// Parts split out of a reply are flagged as such, and only the reply's own
+    /// author receives the captured CLI output. Files the turn reports with
+    /// `<GeneratedFile>` blocks become attachments of its message.
+    async fn commit_turn_as(
+        &self,
+        speaker_name: &str,
+        content: &str,
+        split_from_single_response: bool,
+        in_response_to: Option<&str>,
+    ) -> (String, DialogueMessage) {
+        // Convert speaker name to persona_id (UUID)
+        let persona_id = self
+            .get_persona_id_by_name(speaker_name)
+            .await
+            .unwrap_or_else(|| speaker_name.to_string());
+
+        let generated_files = generated_files::extract_reported_files(
+            content,
+            self.agent_workspace_root.read().await.as_deref(),
+        );
+        if !generated_files.is_empty() {
+            tracing::info!(
+                "[InteractionManager] {} reported {} generated file(s)",
+                speaker_name,
+                generated_files.len()
+            );
+        }
+        self.add_to_history(
+            &persona_id,
+            MessageRole::Assistant,
+            content,
+            Some(generated_files),
+        )
+        .await;
+        self.apply_pinboard_appends(speaker_name, content).await;
+        self.participant_activity.write().await.record(speaker_name);
+        let turn_stats = if split_from_single_response {
+            self.flag_split_turn(&persona_id).await;
+            None
+        } else {
+            self.attach_raw_cli_output(&persona_id).await;
+            self.attach_turn_stats(&persona_id).await
+        };
+        if let Some(responder) = in_response_to {
+            self.mark_reaction_turn(&persona_id, responder).await;
+        }
+        self.record_reused_sources(&persona_id, content).await;
+        if let Some(writer) = &self.stream_recovery {
+            writer.record_chunk(&self.session_id, speaker_name, content);
+        }
+
+        let message = DialogueMessage {
+            session_id: self.session_id.clone(),
+            author: speaker_name.to_string(),
+            content: content.to_string(),
+            failure: None,
+            thinking: None,
+            heartbeat: None,
+            turn_stats,
+        };
+        (persona_id, message)
+    }
+
+    /// Appends the `<Pinboard>` blocks of an agent reply to the pinboard and
+    /// records each append as a system event.
+    ///
+    /// Blocks are ignored unless agent edits are enabled for this session.
+    async fn apply_pinboard_appends(&self, speaker_name: &str, content: &str) {
+        let appends = pinboard::extract_appends(content);
+        if appends.is_empty() {
+            return;
+        }
+        if !*self.pinboard_agent_edits.read().await {
+            tracing::info!(
+                "[InteractionManager] Ignoring {} pinboard block(s) from {}: agent edits are disabled",
+                appends.len(),
+                speaker_name
+            );
+            return;
+        }
+
+        let mut board = self.pinboard.write().await;
+        let mut system_messages = self.system_messages.write().await;
+        for text in appends {
+            pinboard::append(&mut board, &text);
+            system_messages.push(ConversationMessage {
+                role: MessageRole::System,
+                content: format!("{} がピンボードに追記しました: {}", speaker_name, text),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                metadata: MessageMetadata {
+                    system_event_type: Some(SystemEventType::PinboardUpdated),
+                    error_severity: None,
+                    system_message_type: None,
+                    include_in_dialogue: false,
+                    llm_debug_info: None,
+                    recovered_partial: false,
+                    pinned: false,
+                    split_from_single_response: false,
+                    spawned_task_ids: Vec::new(),
+                    in_response_to: None,
+                    source: None,
+                    sources: Vec::new(),
+                    full_content_path: None,
+                    turn_stats: None,
+                },
+                attachments: vec![],
+            });
+        }
+        // The dialogue is locked while the round runs, so rebuild it on next use
+        self.pinboard_changed.store(true, Ordering::SeqCst);
+    }
+
+    /// Seeds the built-in personas when the repository has none, e.g. after a
+    /// partial install, and records the seeding in this session.
+    async fn seed_personas_if_missing(&self) {
+        if self
+            .persona_repository
+            .get_all()
+            .await
+            .is_ok_and(|personas| !personas.is_empty())
+        {
+            return;
+        }
+
+        match seed_default_presets(self.persona_repository.as_ref()).await {
+            Ok(PresetSeeding::Seeded(presets)) => {
+                let names: Vec<&str> = presets.iter().map(|p| p.name.as_str()).collect();
+                tracing::info!(
+                    "[InteractionManager] Seeded default personas: {}",
+                    names.join(", ")
+                );
+                self.system_messages
+                    .write()
+                    .await
+                    .push(ConversationMessage {
+                        role: MessageRole::System,
+                        content: format!(
+                            "ペルソナが見つからなかったため、既定のペルソナを追加しました: {}",
+                            names.join(", ")
+                        ),
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                        metadata: MessageMetadata {
+                            system_event_type: Some(SystemEventType::Notification),
+                            error_severity: None,
+                            system_message_type: None,
+                            include_in_dialogue: false,
+                            llm_debug_info: None,
+                            recovered_partial: false,
+                            pinned: false,
+                            split_from_single_response: false,
+                            spawned_task_ids: Vec::new(),
+                            in_response_to: None,
+                            source: None,
+                            sources: Vec::new(),
+                            full_content_path: None,
+                            turn_stats: None,
+                        },
+                        attachments: vec![],
+                    });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(
+                    "[InteractionManager] Failed to seed default personas: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    /// Checks the restored participants against the persona repository, once.
+    ///
+    /// Participants whose personas were deleted are dropped with a system
+    /// message each and recorded as [`RestoreWarning`]s. When none are left, the
+    /// session falls back to the default participants and says so.
+    async fn validate_restored_participants(&self) {
+        if self.restore_validated.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let Some(restored_ids) = self.restored_participant_ids.read().await.clone() else {
+            return;
+        };
+        let Ok(personas) = self.persona_repository.get_all_including_archived().await else {
+            return;
+        };
+        let (surviving, missing): (Vec<String>, Vec<String>) = restored_ids
+            .into_iter()
+            .partition(|id| personas.iter().any(|p| &p.id == id));
+        if missing.is_empty() {
+            return;
+        }
+
+        let names = self.participant_metadata.read().await.participants.clone();
+        let warnings: Vec<RestoreWarning> = missing
+            .into_iter()
+            .map(|persona_id| {
+                let persona_name = names
+                    .get(&persona_id)
+                    .cloned()
+                    .unwrap_or_else(|| persona_id.clone());
+                RestoreWarning {
+                    message: format!(
+                        "参加者 {} のペルソナが見つからないため、この会話から外れました。\
+                         同じ名前でペルソナを作成し直すと、再び参加させられます。",
+                        persona_name
+                    ),
+                    persona_id,
+                    persona_name,
+                }
+            })
+            .collect();
+        tracing::warn!(
+            "[InteractionManager] Session {} references {} deleted persona(s)",
+            self.session_id,
+            warnings.len()
+        );
+
+        let mut notices: Vec<String> = warnings.iter().map(|w| w.message.clone()).collect();
+        if surviving.is_empty() {
+            notices.push(
+                "参加者のペルソナがすべて見つからないため、既定の参加者で会話を続けます。"
+                    .to_string(),
+            );
+            *self.restored_participant_ids.write().await = None;
+        } else {
+            *self.restored_participant_ids.write().await = Some(surviving);
+        }
+        self.system_messages
+            .write()
+            .await
+            .extend(notices.into_iter().map(notification_message));
+        *self.restore_warnings.write().await = warnings;
+    }
+
+    /// Returns the restored participants whose personas no longer exist.
+    ///
+    /// The check runs on first use of the restored session, or now if it has
+    /// not run yet.
+    pub async fn restore_warnings(&self) -> Vec<RestoreWarning> {
+        self.validate_restored_participants().await;
+        self.restore_warnings.read().await.clone()
+    }
+
+    /// Returns whether the persona repository has any persona usable in this
+    /// session's workspace.
+    async fn has_personas(&self) -> bool {
+        self.workspace_personas()
+            .await
+            .is_ok_and(|personas| !personas.is_empty())
+    }
+
+    /// Active personas available in this session's workspace: global personas
+    /// and those scoped to the workspace (see [`PersonaDomain::is_available_in`]).
+    async fn workspace_personas(&self) -> Result<Vec<PersonaDomain>, String> {
+        let workspace_id = self.workspace_id.read().await.clone();
+        let personas = self
+            .persona_repository
+            .get_all()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(personas
+            .into_iter()
+            .filter(|p| p.is_available_in(workspace_id.as_deref()))
+            .collect())
+    }
+
+    /// Marks the persona's latest message as split out of a single reply.
+    async fn flag_split_turn(&self, persona_id: &str) {
+        let mut histories = self.persona_histories.write().await;
+        if let Some(history) = histories.get_mut(persona_id)
+            && let Some(message) = history.last_mut()
+        {
+            message.metadata.split_from_single_response = true;
+            self.dirty_sections.edited(persona_id, history.len() - 1);
+        }
+    }
+
+    /// Records that the persona's latest message reacts to `responder`'s turn.
+    async fn mark_reaction_turn(&self, persona_id: &str, responder: &str) {
+        let mut histories = self.persona_histories.write().await;
+        if let Some(history) = histories.get_mut(persona_id)
+            && let Some(message) = history.last_mut()
+        {
+            message.metadata.in_response_to = Some(responder.to_string());
+            self.dirty_sections.edited(persona_id, history.len() - 1);
+        }
+    }
+
+    /// Records on the persona's latest message which context sources it
+    /// copied passages from.
+    ///
+    /// The most recent sources are compared first. A source's text includes
+    /// its attached files, so reuse of a clipped page or attached document is
+    /// found too. See [`detect_reused_sources`] for the limits.
+    async fn record_reused_sources(&self, persona_id: &str, content: &str) {
+        let candidates: Vec<(SourceRef, String, Vec<String>)> = self
+            .system_messages
+            .read()
+            .await
+            .iter()
+            .rev()
+            .filter_map(|message| {
+                let source = message.metadata.source.clone()?;
+                Some((source, message.content.clone(), message.attachments.clone()))
+            })
+            .take(MAX_SOURCES_PER_TURN)
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+
+        let mut sources = Vec::with_capacity(candidates.len());
+        for (source, mut text, attachments) in candidates {
+            for path in &attachments {
+                if let Some(file_text) = read_source_file(path).await {
+                    text.push('\n');
+                    text.push_str(&file_text);
+                }
+            }
+            sources.push((source, text));
+        }
+        let reused = detect_reused_sources(
+            content,
+            sources.iter().map(|(source, text)| (source, text.as_str())),
+        );
+        if reused.is_empty() {
+            return;
+        }
+
+        tracing::debug!(
+            "[InteractionManager] Turn of {} reused {} source(s)",
+            persona_id,
+            reused.len()
+        );
+        let mut histories = self.persona_histories.write().await;
+        if let Some(history) = histories.get_mut(persona_id)
+            && let Some(message) = history.last_mut()
+        {
+            message.metadata.sources = reused;
+            self.dirty_sections.edited(persona_id, history.len() - 1);
+        }
+    }
+
+    /// Returns whether rounds chain agent turns, each agent after the first
+    /// reacting to the one before it (sequential strategies).
+    async fn chains_agent_turns(&self) -> bool {
+        matches!(
+            *self.execution_strategy.read().await,
+            ExecutionModel::Sequential | ExecutionModel::OrderedSequential(_)
+        )
+    }
+
+    /// Sets the user whose input is handled next.
+    ///
+    /// `None` attributes input to the user service's current user.
+    pub async fn set_acting_user(&self, user_id: Option<String>) {
+        *self.acting_user.write().await = user_id;
+    }
+
+    /// ID of the user whose input is handled next.
+    async fn acting_user_id(&self) -> String {
+        match self.acting_user.read().await.clone() {
+            Some(user_id) => user_id,
+            None => self.user_service.get_user_name(),
+        }
+    }
+
+    /// Name under which a user's turns are attributed in the dialogue.
+    ///
+    /// Uses the user's profile nickname, falling back to the ID for unknown users.
+    fn user_speaker_name(&self, user_id: &str) -> String {
+        let name = self
+            .user_service
+            .get_user(user_id)
+            .map_or_else(|| user_id.to_string(), |profile| profile.nickname);
+        // "You" would read as the agent itself in the transcript
+        if name.eq_ignore_ascii_case("you") {
+            "User".to_string()
+        } else {
+            name
+        }
+    }
+
+    /// Resolves a persona name to its UUID.
+    ///
+    /// This is used to convert speaker names to persona IDs.
+    async fn get_persona_id_by_name(&self, name: &str) -> Option<String> {
+        let personas = self
+            .persona_repository
+            .get_all_including_archived()
+            .await
+            .ok()?;
+        personas
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.id.clone())
+    }
+
+    /// Rebuilds dialogue history from persona_histories and system_messages for restoration.
+    ///
+    /// This method converts the stored conversation messages into DialogueTurn format,
+    /// sorted by timestamp, to restore the conversation context when recreating a Dialogue.
+    ///
+    /// # Returns
+    ///
+    /// A vector of DialogueTurn representing the full conversation history.
+    async fn rebuild_dialogue_history(&self) -> Vec<DialogueTurn> {
+        self.collect_dialogue_history(false).await
+    }
+
+    /// Rebuilds only the pinned messages, which are kept in context even where the
+    /// rest of the history is withheld.
+    async fn rebuild_pinned_history(&self) -> Vec<DialogueTurn> {
+        self.collect_dialogue_history(true).await
+    }
+
+    async fn collect_dialogue_history(&self, pinned_only: bool) -> Vec<DialogueTurn> {
+        let histories = self.persona_histories.read().await;
+
+        // Flatten all messages with (persona_id, timestamp, message)
+        let mut all_messages: Vec<(String, String, ConversationMessage)> = Vec::new();
+
+        // Add messages from persona_histories
+        for (persona_id, messages) in histories.iter() {
+            for msg in messages {
+                if pinned_only && !msg.metadata.pinned {
+                    continue;
+                }
+                all_messages.push((persona_id.clone(), msg.timestamp.clone(), msg.clone()));
+            }
+        }
+
+        // Add system_messages that should be included in dialogue (pinned ones always are)
         let system_msgs = self.system_messages.read().await;
         for msg in system_msgs.iter() {
-            if msg.metadata.include_in_dialogue {
+            let included = if pinned_only {
+                msg.metadata.pinned
+            } else {
+                msg.metadata.include_in_dialogue || msg.metadata.pinned
+            };
+            if included {
                 all_messages.push((
                     "system".to_string(), // Use "system" as pseudo persona_id for system messages
                     msg.timestamp.clone(),
@@ -627,33 +1865,50 @@ impl InteractionManager {
         // Sort by timestamp to maintain chronological order
         all_messages.sort_by(|a, b| a.1.cmp(&b.1));
 
+        // Resolve persona names (including archived personas referenced by old turns)
+        let persona_names: HashMap<String, String> = self
+            .persona_repository
+            .get_all_including_archived()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| (p.id, p.name))
+            .collect();
+
         // Convert to DialogueTurn with explicit Speaker attribution
         all_messages
             .iter()
             .map(|(persona_id, _, msg)| {
                 match msg.role {
                     MessageRole::User => {
-                        // User input with explicit User speaker
-                        let user_name = self.user_service.get_user_name();
+                        // User input, attributed to the user who wrote it
                         DialogueTurn {
-                            speaker: Speaker::user(user_name, "User"),
+                            speaker: Speaker::user(self.user_speaker_name(persona_id), "User"),
                             content: msg.content.clone(),
                         }
                     }
                     MessageRole::Assistant => {
-                        // Assistant response - convert persona_id to Agent speaker
-                        // Note: We cannot await inside map, so we'll use a fallback
-                        // This is acceptable because we're just displaying the history
+                        // Assistant response - convert persona_id to Agent speaker,
+                        // falling back to the raw ID for unknown personas
+                        let speaker_name = persona_names.get(persona_id).unwrap_or(persona_id);
                         DialogueTurn {
-                            speaker: Speaker::agent(persona_id, "Agent"),
+                            speaker: Speaker::agent(speaker_name, "Agent"),
                             content: msg.content.clone(),
                         }
                     }
                     MessageRole::System => {
-                        // System/Error messages included in dialogue history
+                        // System/Error messages included in dialogue history; shell
+                        // output, search results, web clips and attached files are
+                        // quarantined as data
+                        let content = match &msg.metadata.source {
+                            Some(source) if source.kind.is_untrusted() => {
+                                wrap_untrusted(source.kind, &msg.content)
+                            }
+                            _ => msg.content.clone(),
+                        };
                         DialogueTurn {
                             speaker: Speaker::System,
-                            content: msg.content.clone(),
+                            content,
                         }
                     }
                 }
@@ -668,22 +1923,101 @@ impl InteractionManager {
     /// Returns an error if dialogue creation fails.
     async fn ensure_dialogue_initialized(&self) -> Result<(), String> {
         let mut dialogue_guard = self.dialogue.lock().await;
+        if self.pinboard_changed.swap(false, Ordering::SeqCst) {
+            *dialogue_guard = None;
+        }
+        let offline = self.offline_mode.is_enabled();
+        if self.dialogue_built_offline.swap(offline, Ordering::SeqCst) != offline {
+            *dialogue_guard = None;
+        }
         if dialogue_guard.is_some() {
             return Ok(());
         }
 
+        self.seed_personas_if_missing().await;
+        self.validate_restored_participants().await;
+        let runtime = self.runtime_caps().await;
+        let default_models = self.default_models.read().await.clone();
+        let dialogue = self
+            .build_dialogue(&runtime, |persona, keep_history| {
+                self.create_agent(persona, &runtime, &default_models, keep_history)
+            })
+            .await?;
+
+        *dialogue_guard = Some(dialogue);
+        Ok(())
+    }
+
+    /// Builds a dialogue with restored history, context and participants.
+    ///
+    /// The restored history and talk style are attached per agent rather than
+    /// dialogue-wide, so that each persona's `context_mode_override` can be honored:
+    /// - Talk style follows the persona's effective context mode (override, or the
+    ///   session's context mode when unset).
+    /// - A persona overridden to Clean receives neither the restored history nor its
+    ///   own chat history, while its siblings keep the full context.
+    ///
+    /// # Arguments
+    ///
+    /// * `make_agent` - Creates the agent for a persona; the flag tells whether the
+    ///   agent should keep its own chat history
+    async fn build_dialogue<F>(
+        &self,
+        runtime: &RuntimeCaps,
+        make_agent: F,
+    ) -> Result<Dialogue, String>
+    where
+        F: Fn(&PersonaDomain, bool) -> Box<dyn Agent<Output = String, Expertise = String>>,
+    {
+        self.build_dialogue_for(runtime, None, make_agent).await
+    }
+
+    /// Like [`Self::build_dialogue`], keeping only the participants whose
+    /// persona ID or name is in `only` (all participants if `None`).
+    async fn build_dialogue_for<F>(
+        &self,
+        runtime: &RuntimeCaps,
+        only: Option<&[String]>,
+        make_agent: F,
+    ) -> Result<Dialogue, String>
+    where
+        F: Fn(&PersonaDomain, bool) -> Box<dyn Agent<Output = String, Expertise = String>>,
+    {
         let strategy_model = self.execution_strategy.read().await.clone();
 
         // Rebuild dialogue history from persona_histories
         let history_turns = self.rebuild_dialogue_history().await;
+        let history_context =
+            (!history_turns.is_empty()).then(|| format_history_context(&history_turns));
+        *self.restored_history_context.write().await = history_context.clone();
+        self.participant_activity.write().await.reset(
+            history_turns
+                .iter()
+                .filter(|turn| !matches!(turn.speaker, Speaker::System))
+                .map(|turn| turn.speaker.name().to_string()),
+        );
+        let pinned_turns = self.rebuild_pinned_history().await;
+        let pinned_context =
+            (!pinned_turns.is_empty()).then(|| format_pinned_context(&pinned_turns));
+        *self.pinned_history_context.write().await = pinned_context.clone();
 
-        // Read current talk style (only in Rich mode)
         let context_mode = *self.context_mode.read().await;
-        let talk_style = if matches!(context_mode, ContextMode::Rich) {
-            self.talk_style.read().await.clone()
+        let talk_style = self.talk_style.read().await.clone();
+
+        // Explicitly listed participants answer regardless of focus mode
+        let mut personas_to_add = self.participant_personas().await?;
+        let standby = if let Some(only) = only {
+            personas_to_add.retain(|p| {
+                only.iter()
+                    .any(|wanted| *wanted == p.id || wanted.eq_ignore_ascii_case(&p.name))
+            });
+            Vec::new()
         } else {
-            None // Clean mode: no talk style
+            let (responders, standby) = self.split_by_focus(personas_to_add).await;
+            personas_to_add = responders;
+            standby
         };
+        let personas_to_add = self.offline_participants(personas_to_add).await;
 
         // Create dialogue with restored history and context
         let mut dialogue = match strategy_model {
@@ -697,78 +2031,243 @@ impl InteractionManager {
         };
 
         // Apply context settings
-        let mut additional_context = "【協調ガイドライン】\n\
-                 - 複数の AI ペルソナが協力してユーザーをサポートします\n\
-                 - 他の参加者の意見を尊重し、重複を避けて新しい視点を提供してください\n\
-                 - ユーザーのワークスペース環境で実行されています\n\
-                 - 建設的で協調的なコミュニケーションを心がけてください"
-            .to_string();
+        let mut additional_context = self.collaboration_guidelines().await;
 
         if let Some(extension) = self.prompt_extension.read().await.clone()
             && !extension.trim().is_empty()
         {
             additional_context.push_str("\n\n");
-            additional_context.push_str(&extension);
+            additional_context.push_str(&extension);
+        }
+
+        if matches!(context_mode, ContextMode::Rich)
+            && let Some(board) = self.pinboard.read().await.clone()
+            && !board.trim().is_empty()
+        {
+            additional_context.push_str("\n\n");
+            additional_context.push_str(&pinboard::format_context(
+                &board,
+                *self.pinboard_agent_edits.read().await,
+            ));
+        }
+
+        // Participants on standby are not in the dialogue, so list them here
+        if !standby.is_empty() {
+            let names: Vec<&str> = standby.iter().map(|p| p.name.as_str()).collect();
+            additional_context.push_str(&format!(
+                "\n\n【フォーカス中】\n\
+                 - {} も会話の参加者ですが、メンションされるまで応答しません",
+                names.join(", ")
+            ));
+        }
+
+        if runtime.workspace_available {
+            additional_context.push_str("\n\n");
+            additional_context.push_str(generated_files::REPORT_INSTRUCTION);
+        }
+
+        // The conversation-mode instruction lives in the dialogue context only: it
+        // reaches every turn without being recorded in the message history
+        if let Some(instruction) = self.conversation_mode_instruction().await {
+            additional_context.push_str("\n\n");
+            additional_context.push_str(instruction);
         }
 
+        let environment = self
+            .env_settings
+            .read()
+            .await
+            .environment_description
+            .clone()
+            .filter(|description| !description.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_DIALOGUE_ENVIRONMENT.to_string());
+
         dialogue
-            .with_environment("ORCS (Orchestrated Reasoning & Collaboration System) マルチエージェント対話アプリケーション")
+            .with_environment(environment)
             .with_additional_context(additional_context)
             .with_reaction_strategy(ReactionStrategy::ExceptContextInfo);
 
-        // Apply talk style if set
-        if let Some(style) = talk_style {
-            dialogue.with_talk_style(style);
-        }
-
         tracing::info!(
             "[InteractionManager] Restored dialogue with {} history turns",
             history_turns.len()
         );
 
-        let mut dialogue = dialogue.with_history_as_system_prompt(history_turns);
+        for persona in personas_to_add {
+            let agent = make_agent(&persona, !persona.has_clean_context_override());
+            let agent = scope_agent_context(
+                &persona,
+                agent,
+                context_mode,
+                talk_style.as_ref(),
+                history_context.as_deref(),
+                pinned_context.as_deref(),
+            );
+            let agent = self.wrap_persona_agent(&persona, agent);
+            dialogue.add_agent(domain_to_llm_persona(&persona, runtime), agent);
+        }
+
+        // Keep restored_participant_ids for future dialogue recreations
+        // Do NOT clear them - they should persist to maintain participant list
+        // across dialogue invalidations (e.g., when execution strategy changes)
+
+        Ok(dialogue)
+    }
 
+    /// Personas taking part in the dialogue: the restored participants of the
+    /// session, or the default participants of a new one.
+    async fn participant_personas(&self) -> Result<Vec<PersonaDomain>, String> {
         // Check if we have restored participant IDs from session
         let restored_ids_opt = self.restored_participant_ids.read().await.clone();
+        let all_personas = self.workspace_personas().await?;
 
-        let personas_to_add: Vec<PersonaDomain> = if let Some(restored_ids) = restored_ids_opt {
-            // Restore specific participants from session
-            let all_personas = self
-                .persona_repository
-                .get_all()
-                .await
-                .map_err(|e| e.to_string())?;
-            all_personas
-                .into_iter()
-                .filter(|p| restored_ids.contains(&p.id))
-                .collect()
-        } else {
-            // Use default participants
-            self.persona_repository
-                .get_all()
-                .await
-                .map_err(|e| e.to_string())?
+        let Some(restored_ids) = restored_ids_opt else {
+            // Defaults beyond the cap are announced once: the seated ones
+            // become the session's roster
+            let mut personas: Vec<PersonaDomain> = all_personas
                 .into_iter()
                 .filter(|p| p.default_participant)
-                .collect()
+                .collect();
+            let mut ids: Vec<String> = personas.iter().map(|p| p.id.clone()).collect();
+            let names = personas
+                .iter()
+                .map(|p| (p.id.clone(), p.name.clone()))
+                .collect();
+            let max_participants = self.env_settings.read().await.max_participants;
+            if let Some(notice) = seat_within_cap(&mut ids, max_participants, &names) {
+                personas.truncate(max_participants);
+                self.system_messages.write().await.push(notice);
+                *self.restored_participant_ids.write().await = Some(ids);
+            }
+            return Ok(personas);
         };
 
-        for persona in personas_to_add {
-            let llm_persona = domain_to_llm_persona(&persona);
-            let agent = agent_for_persona(
-                &persona,
-                self.agent_workspace_root.clone(),
-                self.env_settings.clone(),
-            );
-            dialogue.add_agent(llm_persona, agent);
+        // Restore specific participants from session
+        let personas = all_personas
+            .into_iter()
+            .filter(|p| restored_ids.contains(&p.id))
+            .collect();
+        Ok(personas)
+    }
+
+    /// Participants usable in offline mode: API-backed personas switch to their
+    /// CLI `fallback_backend`, and those without one are left out with a warning.
+    ///
+    /// Returns `personas` unchanged while offline mode is off.
+    async fn offline_participants(&self, personas: Vec<PersonaDomain>) -> Vec<PersonaDomain> {
+        if !self.offline_mode.is_enabled() {
+            return personas;
         }
 
-        // Keep restored_participant_ids for future dialogue recreations
-        // Do NOT clear them - they should persist to maintain participant list
-        // across dialogue invalidations (e.g., when execution strategy changes)
+        let mut usable = Vec::with_capacity(personas.len());
+        for mut persona in personas {
+            match persona.offline_backend() {
+                Some(backend) => {
+                    if backend != persona.backend {
+                        tracing::info!(
+                            "[InteractionManager] {} uses fallback backend {:?} while offline",
+                            persona.name,
+                            backend
+                        );
+                        persona.backend = backend;
+                        persona.model_name = None;
+                    }
+                    usable.push(persona);
+                }
+                None => {
+                    let error = OrcsError::backend_unavailable(
+                        persona.backend.display_name(),
+                        OFFLINE_REASON,
+                    );
+                    self.push_offline_warning(format!("{}: {}", persona.name, error))
+                        .await;
+                }
+            }
+        }
+        usable
+    }
 
-        *dialogue_guard = Some(dialogue);
-        Ok(())
+    /// Records a Warning system message for a participant left out by offline mode.
+    async fn push_offline_warning(&self, content: String) {
+        tracing::warn!("[InteractionManager] {}", content);
+        self.system_messages
+            .write()
+            .await
+            .push(ConversationMessage {
+                role: MessageRole::System,
+                content,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                metadata: MessageMetadata {
+                    system_event_type: Some(SystemEventType::Notification),
+                    error_severity: Some(ErrorSeverity::Warning),
+                    system_message_type: None,
+                    include_in_dialogue: false,
+                    llm_debug_info: None,
+                    recovered_partial: false,
+                    pinned: false,
+                    split_from_single_response: false,
+                    spawned_task_ids: Vec::new(),
+                    in_response_to: None,
+                    source: None,
+                    sources: Vec::new(),
+                    full_content_path: None,
+                    turn_stats: None,
+                },
+                attachments: vec![],
+            });
+    }
+
+    /// Constructs the API agents of the participants in the background, so the
+    /// first turn does not wait for client construction and authentication.
+    ///
+    /// CLI-backed participants are skipped: their agents start a process per
+    /// turn anyway. Returns at once with the handle of the background task,
+    /// which resolves to the number of agents constructed. Failures are logged,
+    /// never reported.
+    ///
+    /// Nothing is warmed up while offline mode is on.
+    pub async fn warmup_agents(&self) -> tokio::task::JoinHandle<usize> {
+        let personas: Vec<PersonaDomain> = match self.participant_personas().await {
+            Ok(_) if self.offline_mode.is_enabled() => Vec::new(),
+            Ok(personas) => personas
+                .into_iter()
+                .filter(|p| !p.backend.is_cli())
+                .collect(),
+            Err(e) => {
+                tracing::warn!(
+                    "[InteractionManager] Skipping agent warmup, participants unavailable: {}",
+                    e
+                );
+                Vec::new()
+            }
+        };
+        let factory = self.agent_factory.clone();
+        let env_settings = self.env_settings.clone();
+        let warm_clients = self.warm_clients.clone();
+
+        tokio::spawn(async move {
+            let mut warmed_up = 0;
+            for persona in personas {
+                let result = match &factory {
+                    Some(factory) => factory.warm_up(&persona),
+                    None => {
+                        let settings = env_settings.read().await.clone();
+                        warm_up_backend(&persona.backend, &settings, &warm_clients)
+                            .await
+                            .map_err(|e| e.to_string())
+                    }
+                };
+                match result {
+                    Ok(()) => warmed_up += 1,
+                    Err(e) => tracing::warn!(
+                        "[InteractionManager] Warmup of {}'s agent failed: {}",
+                        persona.name,
+                        e
+                    ),
+                }
+            }
+            tracing::debug!("[InteractionManager] Warmed up {} agent(s)", warmed_up);
+            warmed_up
+        })
     }
 
     /// Converts the current state to Session for persistence.
@@ -801,8 +2300,107 @@ impl InteractionManager {
         let internal_workspace_id = self.workspace_id.read().await.clone();
         let final_workspace_id = internal_workspace_id.unwrap_or(workspace_id);
 
-        // Get active participants if dialogue is initialized
-        let active_participant_ids = self.get_active_participants().await.unwrap_or_default();
+        // Reading the roster does not wait for a background dialogue's lock. Unlike
+        // get_active_participants, it seeds no personas: taking a snapshot must not
+        // change the session
+        let active_participant_ids: Vec<String> = self
+            .participant_personas()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|persona| persona.id)
+            .collect();
+
+        let ParticipantMetadata {
+            participants,
+            participant_icons,
+            participant_colors,
+            participant_backends,
+            participant_models,
+        } = self.refresh_participant_metadata().await;
+
+        let conversation_mode = self.conversation_mode.read().await.clone();
+        let talk_style = self.talk_style.read().await.clone();
+        let auto_chat_config = self.auto_chat_config.read().await.clone();
+        let is_muted = *self.is_muted.read().await;
+
+        Session {
+            id: self.session_id.clone(),
+            title,
+            created_at: self.created_at.clone(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            current_persona_id,
+            persona_histories,
+            app_mode,
+            workspace_id: final_workspace_id,
+            active_participant_ids,
+            execution_strategy,
+            system_messages,
+            participants,
+            participant_icons,
+            participant_colors,
+            participant_backends,
+            participant_models,
+            conversation_mode,
+            talk_style,
+            is_favorite: false,
+            is_archived: false,
+            sort_order: None,
+            auto_chat_config,
+            is_muted,
+            context_mode: *self.context_mode.read().await,
+            sandbox_state: self.sandbox_state.read().await.clone(),
+            last_memory_sync_at: None, // Managed by SessionUseCase
+            is_generating: self.is_generating.load(Ordering::SeqCst),
+            style_calibration_enabled: self.style_calibration.read().await.is_some(),
+            response_splitting_enabled: *self.response_splitting.read().await,
+            pinboard: self.pinboard.read().await.clone(),
+            pinboard_agent_edits_enabled: *self.pinboard_agent_edits.read().await,
+            apply_conversation_mode_in_clean: *self.apply_conversation_mode_in_clean.read().await,
+            participant_suggestions_enabled: *self.participant_suggestions.read().await,
+            script_run: self.script_run.read().await.clone(),
+            focused_participant_ids: self.focused_participant_ids.read().await.clone(),
+            random_seed: *self.random_seed.read().await,
+            clock_context_enabled: *self.clock_context_enabled.read().await,
+            plan_detection_enabled: *self.plan_detection.read().await,
+            paste_attachment_enabled: *self.paste_attachment_enabled.read().await,
+            paste_attachment_threshold: *self.paste_attachment_threshold.read().await,
+        }
+    }
+
+    /// Returns the sections of `session` changed since this manager's state
+    /// was last saved, and records `session` as saved.
+    ///
+    /// `session` is the state about to be saved, as built by [`Self::to_session`].
+    /// The first call after creation or restoration asks for a full save. If
+    /// saving fails, call [`Self::mark_unsaved`] so the next save is a full one.
+    pub fn take_dirty_delta(&self, session: &Session) -> SessionDelta {
+        self.dirty_sections.take(session)
+    }
+
+    /// Records `session` as saved in full, e.g. after a direct repository save.
+    pub fn mark_saved(&self, session: &Session) {
+        self.dirty_sections.take(session);
+    }
+
+    /// Forgets the last save, so the next delta asks for a full save.
+    pub fn mark_unsaved(&self) {
+        self.dirty_sections.reset();
+    }
+
+    /// Rebuilds participant names, icons, colors, backends and models from the
+    /// persona repository and updates them in place.
+    ///
+    /// Cheap enough to call after persona edits: the dialogue is left untouched
+    /// and nothing is saved. Returns the refreshed metadata.
+    pub async fn refresh_participant_metadata(&self) -> ParticipantMetadata {
+        let persona_ids: Vec<String> = self
+            .persona_histories
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .collect();
 
         // Build participants map: persona ID -> name
         let mut participants = HashMap::new();
@@ -820,19 +2418,27 @@ impl InteractionManager {
         participants.insert(user_name.clone(), user_name.clone());
         // User has no icon/color/backend/model for now
 
-        // Add all personas from persona_histories (AI participants)
-        if let Ok(all_personas) = self.persona_repository.get_all().await {
-            for persona_id in persona_histories.keys() {
+        let default_models = self.default_models.read().await.clone();
+
+        // Add all personas from persona_histories (AI participants), including
+        // archived personas so old transcripts keep their names
+        if let Ok(all_personas) = self.persona_repository.get_all_including_archived().await {
+            for persona_id in &persona_ids {
                 // Skip user's history key if it exists
                 if persona_id == &user_name {
                     continue;
                 }
+                // Other users who wrote in this session are named by their profile
+                if let Some(profile) = self.user_service.get_user(persona_id) {
+                    participants.insert(persona_id.clone(), profile.nickname);
+                    continue;
+                }
 
                 if let Some(persona) = all_personas.iter().find(|p| &p.id == persona_id) {
                     participants.insert(persona_id.clone(), persona.name.clone());
-                    // Add icon if persona has one
-                    if let Some(icon) = &persona.icon {
-                        participant_icons.insert(persona_id.clone(), icon.clone());
+                    // Add icon if persona has one (uploaded image first, then emoji)
+                    if let Some(icon) = persona.display_icon() {
+                        participant_icons.insert(persona_id.clone(), icon);
                     }
                     // Add base_color if persona has one
                     if let Some(color) = &persona.base_color {
@@ -844,45 +2450,29 @@ impl InteractionManager {
                         .trim_matches('"')
                         .to_string();
                     participant_backends.insert(persona_id.clone(), backend_str);
-                    // Add model_name if persona has one
-                    participant_models.insert(persona_id.clone(), persona.model_name.clone());
+                    // Add the model the persona runs with (own model or app default)
+                    participant_models.insert(
+                        persona_id.clone(),
+                        persona.resolved_model_name(&default_models),
+                    );
                 }
             }
         }
 
-        let conversation_mode = self.conversation_mode.read().await.clone();
-        let talk_style = self.talk_style.read().await.clone();
-        let auto_chat_config = self.auto_chat_config.read().await.clone();
-        let is_muted = *self.is_muted.read().await;
-
-        Session {
-            id: self.session_id.clone(),
-            title,
-            created_at: self.created_at.clone(),
-            updated_at: chrono::Utc::now().to_rfc3339(),
-            current_persona_id,
-            persona_histories,
-            app_mode,
-            workspace_id: final_workspace_id,
-            active_participant_ids,
-            execution_strategy,
-            system_messages,
+        let metadata = ParticipantMetadata {
             participants,
             participant_icons,
             participant_colors,
             participant_backends,
             participant_models,
-            conversation_mode,
-            talk_style,
-            is_favorite: false,
-            is_archived: false,
-            sort_order: None,
-            auto_chat_config,
-            is_muted,
-            context_mode: *self.context_mode.read().await,
-            sandbox_state: self.sandbox_state.read().await.clone(),
-            last_memory_sync_at: None, // Managed by SessionUseCase
-        }
+        };
+        *self.participant_metadata.write().await = metadata.clone();
+        metadata
+    }
+
+    /// Returns participant metadata as of the last refresh.
+    pub async fn participant_metadata(&self) -> ParticipantMetadata {
+        self.participant_metadata.read().await.clone()
     }
 
     /// Returns the session ID.
@@ -902,16 +2492,26 @@ impl InteractionManager {
             workspace_root
         );
 
-        let mut ws_id = self.workspace_id.write().await;
-        *ws_id = workspace_id.clone();
+        let workspace_changed = {
+            let mut ws_id = self.workspace_id.write().await;
+            let changed = *ws_id != workspace_id;
+            *ws_id = workspace_id.clone();
+            changed
+        };
 
         let mut ws_root = self.agent_workspace_root.write().await;
         *ws_root = workspace_root.clone();
+        drop(ws_root);
 
         tracing::info!(
             "[InteractionManager::set_workspace_id] Updated agent_workspace_root to: {:?}",
             workspace_root
         );
+
+        // Workspace-scoped personas of the old workspace leave the dialogue
+        if workspace_changed {
+            self.invalidate_dialogue().await;
+        }
     }
 
     /// Sets the agent workspace root (used for Sandbox mode to change CWD).
@@ -941,10 +2541,9 @@ impl InteractionManager {
         self.agent_workspace_root.read().await.clone()
     }
 
-    /// Returns a list of available persona IDs.
+    /// Returns the IDs of the personas available in this session's workspace.
     pub async fn available_personas(&self) -> Vec<String> {
-        self.persona_repository
-            .get_all()
+        self.workspace_personas()
             .await
             .unwrap_or_default()
             .into_iter()
@@ -954,27 +2553,56 @@ impl InteractionManager {
 
     /// Adds a participant to the dialogue.
     ///
+    /// Does nothing if the persona is already a participant.
+    ///
     /// # Arguments
     ///
     /// * `persona_id` - The ID of the persona to add (e.g., "mai", "yui")
     ///
     /// # Errors
     ///
-    /// Returns an error if the persona is not found or dialogue initialization fails.
+    /// Returns an error if the persona is not found, the session already has
+    /// [`EnvSettings::max_participants`] participants, or dialogue
+    /// initialization fails.
     pub async fn add_participant(&self, persona_id: &str) -> Result<(), String> {
         // Ensure dialogue is initialized
         self.ensure_dialogue_initialized().await?;
 
-        // Find the persona
+        // Find the persona; personas of other workspaces cannot join
         let persona_config = self
-            .persona_repository
-            .get_all()
-            .await
-            .map_err(|e| e.to_string())?
+            .workspace_personas()
+            .await?
             .into_iter()
             .find(|p| p.id == persona_id)
             .ok_or_else(|| format!("Persona with id '{}' not found", persona_id))?;
-        let persona = domain_to_llm_persona(&persona_config);
+        let runtime = self.runtime_caps().await;
+        let persona = domain_to_llm_persona(&persona_config, &runtime);
+
+        // Adding an active participant again would duplicate its agent and join message.
+        // The roster also holds participants on standby under focus mode.
+        let roster_ids: Vec<String> = self
+            .participant_personas()
+            .await?
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        let already_active = roster_ids.contains(&persona_config.id);
+        let participant_count = roster_ids.len();
+        if already_active {
+            tracing::debug!(
+                "[InteractionManager] {} is already a participant",
+                persona_config.name
+            );
+            return Ok(());
+        }
+        let max_participants = self.env_settings.read().await.max_participants;
+        if participant_count >= max_participants {
+            return Err(format!(
+                "Cannot add {}: this session already has the maximum of {} participants. \
+                 Remove a participant first or raise max_participants in the settings.",
+                persona_config.name, max_participants
+            ));
+        }
 
         // Record system message
         let system_msg = ConversationMessage {
@@ -987,6 +2615,15 @@ impl InteractionManager {
                 system_message_type: None,
                 include_in_dialogue: true,
                 llm_debug_info: None,
+                recovered_partial: false,
+                pinned: false,
+                split_from_single_response: false,
+                spawned_task_ids: Vec::new(),
+                in_response_to: None,
+                source: None,
+                sources: Vec::new(),
+                full_content_path: None,
+                turn_stats: None,
             },
             attachments: vec![],
         };
@@ -1003,43 +2640,73 @@ impl InteractionManager {
                 );
             }
         };
-        let agent = agent_for_persona(
+        let agent = self.create_agent(
             &persona_config,
-            self.agent_workspace_root.clone(),
-            self.env_settings.clone(),
+            &runtime,
+            &*self.default_models.read().await,
+            !persona_config.has_clean_context_override(),
+        );
+        let agent = scope_agent_context(
+            &persona_config,
+            agent,
+            *self.context_mode.read().await,
+            self.talk_style.read().await.as_ref(),
+            self.restored_history_context.read().await.as_deref(),
+            self.pinned_history_context.read().await.as_deref(),
         );
+        let agent = self.wrap_persona_agent(&persona_config, agent);
         dialogue.add_agent(persona, agent);
 
         // Update restored_participant_ids to persist across dialogue recreations
-        // Get current active participants and add the new one
-        let all_personas = self.persona_repository.get_all().await.ok();
-        let current_ids = dialogue
-            .participants()
-            .iter()
-            .filter_map(|p| {
-                all_personas
-                    .as_ref()
-                    .and_then(|all| all.iter().find(|persona| persona.name == p.name))
-                    .map(|persona| persona.id.clone())
-            })
-            .collect::<Vec<_>>();
-
+        let mut current_ids = roster_ids;
+        current_ids.push(persona_config.id.clone());
         *self.restored_participant_ids.write().await = Some(current_ids);
 
+        // A persona joining while focus mode is on joins the focus
+        if let Some(focused) = self.focused_participant_ids.write().await.as_mut() {
+            focused.push(persona_config.id.clone());
+        }
+
         Ok(())
     }
 
     /// Removes a participant from the dialogue.
     ///
+    /// With `with_handoff`, the departing persona first gets one final turn to
+    /// write a handoff note (see [`PARTICIPANT_HANDOFF_MESSAGE_TYPE`]).
+    ///
     /// # Arguments
     ///
     /// * `persona_id` - The ID of the persona to remove (e.g., "mai", "yui")
+    /// * `with_handoff` - Whether to record a handoff note before removal
     ///
     /// # Errors
     ///
-    /// Returns an error if the persona is not found, dialogue initialization fails,
-    /// or the participant cannot be removed.
-    pub async fn remove_participant(&self, persona_id: &str) -> Result<(), String> {
+    /// Returns an error if the persona is not found or not a participant, dialogue
+    /// initialization fails, the handoff turn fails, or the participant cannot be
+    /// removed.
+    pub async fn remove_participant(
+        &self,
+        persona_id: &str,
+        with_handoff: bool,
+    ) -> Result<(), String> {
+        let runtime = self.runtime_caps().await;
+        let default_models = self.default_models.read().await.clone();
+        self.remove_participant_with(persona_id, with_handoff, |persona| {
+            self.create_agent(persona, &runtime, &default_models, true)
+        })
+        .await
+    }
+
+    async fn remove_participant_with<F>(
+        &self,
+        persona_id: &str,
+        with_handoff: bool,
+        make_agent: F,
+    ) -> Result<(), String>
+    where
+        F: FnOnce(&PersonaDomain) -> Box<dyn Agent<Output = String, Expertise = String>>,
+    {
         // Ensure dialogue is initialized
         self.ensure_dialogue_initialized().await?;
 
@@ -1052,7 +2719,24 @@ impl InteractionManager {
             .into_iter()
             .find(|p| p.id == persona_id)
             .ok_or_else(|| format!("Persona with id '{}' not found", persona_id))?;
-        let persona = domain_to_llm_persona(&persona_config);
+
+        let roster_ids: Vec<String> = self
+            .participant_personas()
+            .await?
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        if !roster_ids.contains(&persona_config.id) {
+            return Err(format!(
+                "{} is not a participant of this session",
+                persona_config.name
+            ));
+        }
+
+        if with_handoff {
+            self.record_handoff(&persona_config, make_agent(&persona_config))
+                .await?;
+        }
 
         // Record system message
         let system_msg = ConversationMessage {
@@ -1065,6 +2749,15 @@ impl InteractionManager {
                 system_message_type: None,
                 include_in_dialogue: true,
                 llm_debug_info: None,
+                recovered_partial: false,
+                pinned: false,
+                split_from_single_response: false,
+                spawned_task_ids: Vec::new(),
+                in_response_to: None,
+                source: None,
+                sources: Vec::new(),
+                full_content_path: None,
+                turn_stats: None,
             },
             attachments: vec![],
         };
@@ -1081,28 +2774,114 @@ impl InteractionManager {
                 );
             }
         };
-        dialogue
-            .remove_participant(&persona.name)
-            .map_err(|e| e.to_string())?;
-
-        // Update restored_participant_ids to persist across dialogue recreations
-        let all_personas = self.persona_repository.get_all().await.ok();
-        let current_ids = dialogue
+        // Participants on standby under focus mode are not in the dialogue
+        if dialogue
             .participants()
             .iter()
-            .filter_map(|p| {
-                all_personas
-                    .as_ref()
-                    .and_then(|all| all.iter().find(|persona| persona.name == p.name))
-                    .map(|persona| persona.id.clone())
-            })
+            .any(|p| p.name == persona_config.name)
+        {
+            dialogue
+                .remove_participant(&persona_config.name)
+                .map_err(|e| e.to_string())?;
+        }
+
+        // Update restored_participant_ids to persist across dialogue recreations
+        let current_ids = roster_ids
+            .into_iter()
+            .filter(|id| *id != persona_config.id)
             .collect::<Vec<_>>();
+        {
+            let mut focused = self.focused_participant_ids.write().await;
+            if let Some(ids) = focused.as_mut() {
+                ids.retain(|id| *id != persona_config.id);
+                if ids.is_empty() {
+                    *focused = None;
+                }
+            }
+        }
 
         // Always set Some(...) to distinguish between:
         // - None: initial state (use default_participant)
         // - Some(vec![]): user explicitly removed all participants (add nobody)
         *self.restored_participant_ids.write().await = Some(current_ids);
 
+        // The handoff ran outside the dialogue; rebuild so remaining participants see it
+        if with_handoff {
+            *dialogue_guard = None;
+        }
+
+        Ok(())
+    }
+
+    /// Asks a departing persona for a handoff note and records it.
+    ///
+    /// The note is stored as a pinned assistant message, so later prompts keep it
+    /// even where history is withheld, followed by a UI-only system note marking
+    /// the handoff.
+    async fn record_handoff(
+        &self,
+        persona: &PersonaDomain,
+        agent: Box<dyn Agent<Output = String, Expertise = String>>,
+    ) -> Result<(), String> {
+        let history = self.rebuild_dialogue_history().await;
+        let mut payload = Payload::new();
+        if !history.is_empty() {
+            payload = payload.with_context(format_history_context(&history));
+        }
+        let payload = payload.with_message(Speaker::System, PARTICIPANT_HANDOFF_INSTRUCTION);
+
+        let content = agent
+            .execute(payload)
+            .await
+            .map_err(|e| format!("Handoff from {} failed: {}", persona.name, e))?;
+
+        let timestamp = chrono::Utc::now().to_rfc3339();
+        let handoff_metadata = MessageMetadata {
+            system_event_type: None,
+            error_severity: None,
+            system_message_type: Some(PARTICIPANT_HANDOFF_MESSAGE_TYPE.to_string()),
+            include_in_dialogue: true,
+            llm_debug_info: None,
+            recovered_partial: false,
+            pinned: true,
+            split_from_single_response: false,
+            spawned_task_ids: Vec::new(),
+            in_response_to: None,
+            source: None,
+            sources: Vec::new(),
+            full_content_path: None,
+            turn_stats: None,
+        };
+        self.persona_histories
+            .write()
+            .await
+            .entry(persona.id.clone())
+            .or_default()
+            .push(ConversationMessage {
+                role: MessageRole::Assistant,
+                content,
+                timestamp: timestamp.clone(),
+                metadata: handoff_metadata.clone(),
+                attachments: vec![],
+            });
+        self.system_messages
+            .write()
+            .await
+            .push(ConversationMessage {
+                role: MessageRole::System,
+                content: format!("{} の引き継ぎメモを記録しました", persona.name),
+                timestamp,
+                metadata: MessageMetadata {
+                    system_event_type: Some(SystemEventType::Notification),
+                    include_in_dialogue: false,
+                    pinned: false,
+                    split_from_single_response: false,
+                    spawned_task_ids: Vec::new(),
+                    in_response_to: None,
+                    ..handoff_metadata
+                },
+                attachments: vec![],
+            });
         Ok(())
     }
 
@@ -1113,23 +2892,30 @@ impl InteractionManager {
         message_type: Option<String>,
         error_severity: Option<ErrorSeverity>,
     ) {
-        let is_context_info = matches!(
-            message_type.as_deref(),
-            Some("context_info" | "shell_output")
-        );
-        let message = ConversationMessage {
-            role: MessageRole::System,
+        self.add_system_conversation_message_with_attachments(
             content,
-            timestamp: chrono::Utc::now().to_rfc3339(),
-            metadata: MessageMetadata {
-                system_event_type: Some(SystemEventType::Notification),
-                error_severity,
-                system_message_type: message_type,
-                include_in_dialogue: true,
-                llm_debug_info: None,
-            },
-            attachments: vec![],
-        };
+            message_type,
+            error_severity,
+            vec![],
+        )
+        .await;
+    }
+
+    /// Like [`Self::add_system_conversation_message`], attaching workspace files.
+    pub async fn add_system_conversation_message_with_attachments(
+        &self,
+        content: String,
+        message_type: Option<String>,
+        error_severity: Option<ErrorSeverity>,
+        attachments: Vec<String>,
+    ) {
+        let message = SystemMessageEntry {
+            content,
+            message_type,
+            error_severity,
+        }
+        .into_message(attachments);
+        let is_context_info = is_context_info(&message);
 
         self.system_messages.write().await.push(message);
 
@@ -1143,34 +2929,99 @@ impl InteractionManager {
         }
     }
 
+    /// Records several system-level conversation messages at once.
+    ///
+    /// Each entry is stored as by [`Self::add_system_conversation_message`], but
+    /// the dialogue is invalidated only once, after all are appended, if any of
+    /// them is context info. Use this for bulk appends such as a batch of search
+    /// results, where rebuilding the dialogue per item would be wasted work.
+    pub async fn add_system_conversation_messages(&self, entries: Vec<SystemMessageEntry>) {
+        let messages: Vec<ConversationMessage> = entries
+            .into_iter()
+            .map(|entry| entry.into_message(vec![]))
+            .collect();
+        let any_context_info = messages.iter().any(is_context_info);
+
+        self.system_messages.write().await.extend(messages);
+
+        if any_context_info {
+            // Same guarantee as the single-message path: context info is visible
+            // on the next agent turn. Invalidating once covers the whole batch.
+            self.invalidate_dialogue().await;
+        }
+    }
+
     /// Returns a list of active participant IDs.
     ///
+    /// This is the full roster: participants on standby because of a focus or
+    /// left out of the dialogue by offline mode are still included.
+    ///
     /// # Errors
     ///
-    /// Returns an error if dialogue initialization fails.
+    /// Returns an error if the personas cannot be loaded.
     pub async fn get_active_participants(&self) -> Result<Vec<String>, String> {
-        self.ensure_dialogue_initialized().await?;
+        // The roster does not depend on the dialogue, so its lock is not needed
+        self.seed_personas_if_missing().await;
+        self.validate_restored_participants().await;
 
-        let dialogue_guard = self.dialogue.lock().await;
-        let dialogue = match dialogue_guard.as_ref() {
-            Some(d) => d,
-            None => {
-                return Err(
-                    "Dialogue was invalidated during initialization (possible race condition)"
-                        .to_string(),
-                );
-            }
-        };
+        Ok(self
+            .participant_personas()
+            .await?
+            .into_iter()
+            .map(|persona| persona.id)
+            .collect())
+    }
 
-        // Convert participant names to persona UUIDs
-        let mut participant_ids = Vec::new();
-        for persona in dialogue.participants().iter() {
-            if let Some(id) = self.get_persona_id_by_name(&persona.name).await {
-                participant_ids.push(id);
-            }
-        }
+    /// Returns the union of the effective capabilities of all active participants.
+    ///
+    /// Capabilities reflect the current runtime configuration (workspace root and
+    /// web search setting) as well as each persona's backend.
+    pub async fn session_capabilities(&self) -> CapabilityFlags {
+        let participant_ids = self.get_active_participants().await.unwrap_or_default();
+
+        let runtime = self.runtime_caps().await;
+        self.persona_repository
+            .get_all_including_archived()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|p| participant_ids.contains(&p.id))
+            .fold(CapabilityFlags::default(), |flags, p| {
+                flags.union(effective_capabilities(p, &runtime).flags)
+            })
+    }
+
+    /// Returns the model each active participant runs with, keyed by persona ID.
+    ///
+    /// A persona's own model wins over the app default for its backend; without
+    /// either, the backend's built-in default is reported.
+    pub async fn effective_models(&self) -> HashMap<String, String> {
+        let participant_ids = self.get_active_participants().await.unwrap_or_default();
+
+        let default_models = self.default_models.read().await.clone();
+        self.persona_repository
+            .get_all_including_archived()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| participant_ids.contains(&p.id))
+            .map(|p| {
+                let model = p.resolved_model_name(&default_models).unwrap_or_else(|| {
+                    supported_models::backend_default_model(&p.backend).to_string()
+                });
+                (p.id, model)
+            })
+            .collect()
+    }
 
-        Ok(participant_ids)
+    /// Returns the collaboration guidelines agents get under the current
+    /// execution strategy, honoring the workspace's override for it.
+    ///
+    /// See [`collaboration_guidelines`].
+    pub async fn collaboration_guidelines(&self) -> String {
+        let strategy = self.execution_strategy.read().await.clone();
+        let workspace_root = self.agent_workspace_root.read().await.clone();
+        collaboration_guidelines::effective(&strategy, workspace_root.as_deref()).await
     }
 
     /// Sets the execution strategy for the dialogue.
@@ -1204,6 +3055,15 @@ impl InteractionManager {
                 system_message_type: None,
                 include_in_dialogue: true,
                 llm_debug_info: None,
+                recovered_partial: false,
+                pinned: false,
+                split_from_single_response: false,
+                spawned_task_ids: Vec::new(),
+                in_response_to: None,
+                source: None,
+                sources: Vec::new(),
+                full_content_path: None,
+                turn_stats: None,
             },
             attachments: vec![],
         };
@@ -1228,8 +3088,10 @@ impl InteractionManager {
     /// # Note
     ///
     /// This affects how AI agents respond to prevent response escalation.
-    /// The mode's system instruction will be injected on the next interaction.
+    /// The dialogue is rebuilt with the mode's system instruction on the next
+    /// interaction.
     pub async fn set_conversation_mode(&self, mode: ConversationMode) {
+        let previous = self.conversation_mode_instruction().await;
         // Record system message for mode change
         let mode_str = match mode {
             ConversationMode::Detailed => "詳細",
@@ -1248,12 +3110,22 @@ impl InteractionManager {
                 system_message_type: None,
                 include_in_dialogue: true,
                 llm_debug_info: None,
+                recovered_partial: false,
+                pinned: false,
+                split_from_single_response: false,
+                spawned_task_ids: Vec::new(),
+                in_response_to: None,
+                source: None,
+                sources: Vec::new(),
+                full_content_path: None,
+                turn_stats: None,
             },
             attachments: vec![],
         };
         self.system_messages.write().await.push(system_msg);
 
         *self.conversation_mode.write().await = mode;
+        self.refresh_conversation_mode_instruction(previous).await;
     }
 
     /// Gets the current conversation mode.
@@ -1295,6 +3167,15 @@ impl InteractionManager {
                     system_message_type: None,
                     include_in_dialogue: true,
                     llm_debug_info: None,
+                    recovered_partial: false,
+                    pinned: false,
+                    split_from_single_response: false,
+                    spawned_task_ids: Vec::new(),
+                    in_response_to: None,
+                    source: None,
+                    sources: Vec::new(),
+                    full_content_path: None,
+                    turn_stats: None,
                 },
                 attachments: vec![],
             };
@@ -1319,8 +3200,26 @@ impl InteractionManager {
     }
 
     /// Sets the AutoChat configuration.
-    pub async fn set_auto_chat_config(&self, config: Option<AutoChatConfig>) {
+    ///
+    /// `max_iterations` is clamped to [`EnvSettings::max_auto_chat_iterations`].
+    /// Toggling web search invalidates the dialogue so personas advertise the new
+    /// capabilities.
+    pub async fn set_auto_chat_config(&self, mut config: Option<AutoChatConfig>) {
+        if let Some(config) = config.as_mut() {
+            let limit = self.env_settings.read().await.max_auto_chat_iterations;
+            if let Some(requested) = config.clamp_max_iterations(limit) {
+                tracing::warn!(
+                    "[AutoChat] max_iterations {} exceeds the limit of {}, clamping",
+                    requested,
+                    limit
+                );
+            }
+        }
+        let web_search_before = self.runtime_caps().await.web_search_enabled;
         *self.auto_chat_config.write().await = config;
+        if self.runtime_caps().await.web_search_enabled != web_search_before {
+            self.invalidate_dialogue().await;
+        }
     }
 
     /// Gets the current AutoChat configuration.
@@ -1338,6 +3237,40 @@ impl InteractionManager {
         *self.auto_chat_iteration.write().await = iteration;
     }
 
+    /// Replaces the conversation and participants with those of `session`.
+    ///
+    /// Used when another session's history is merged into this one; the
+    /// dialogue is invalidated so the next turn is built from the new history.
+    pub async fn replace_conversation(&self, session: &Session) {
+        // The merged roster appends the other session's participants, so it may exceed the cap
+        let max_participants = self.env_settings.read().await.max_participants;
+        let mut system_messages = session.system_messages.clone();
+        let restored_ids = (!session.active_participant_ids.is_empty()).then(|| {
+            let mut ids = session.active_participant_ids.clone();
+            system_messages.extend(seat_within_cap(
+                &mut ids,
+                max_participants,
+                &session.participants,
+            ));
+            ids
+        });
+        *self.persona_histories.write().await = session.persona_histories.clone();
+        *self.system_messages.write().await = system_messages;
+        self.dirty_sections.reset();
+        *self.restored_participant_ids.write().await = restored_ids;
+        *self.focused_participant_ids.write().await = session.focused_participant_ids.clone();
+        self.focus_guests.write().await.clear();
+        self.restore_validated.store(false, Ordering::SeqCst);
+        *self.participant_metadata.write().await = ParticipantMetadata {
+            participants: session.participants.clone(),
+            participant_icons: session.participant_icons.clone(),
+            participant_colors: session.participant_colors.clone(),
+            participant_backends: session.participant_backends.clone(),
+            participant_models: session.participant_models.clone(),
+        };
+        self.invalidate_dialogue().await;
+    }
+
     /// Invalidates the current dialogue, forcing it to be recreated with latest persona settings.
     ///
     /// This should be called when:
@@ -1347,6 +3280,11 @@ impl InteractionManager {
     /// The dialogue will be recreated with the latest settings on the next interaction.
     pub async fn invalidate_dialogue(&self) {
         *self.dialogue.lock().await = None;
+        let count = self.dialogue_invalidations.fetch_add(1, Ordering::SeqCst) + 1;
+        tracing::debug!(
+            "[InteractionManager] Dialogue invalidated ({} times this session)",
+            count
+        );
     }
 
     /// Toggles mute status and returns the new value.
@@ -1361,19 +3299,602 @@ impl InteractionManager {
         *self.is_muted.read().await
     }
 
-    /// Sets the mute status.
-    pub async fn set_mute(&self, muted: bool) {
-        *self.is_muted.write().await = muted;
+    /// Sets the mute status.
+    pub async fn set_mute(&self, muted: bool) {
+        *self.is_muted.write().await = muted;
+    }
+
+    /// Gets whether personas adapt their style to user feedback.
+    pub async fn is_style_calibration_enabled(&self) -> bool {
+        self.style_calibration.read().await.is_some()
+    }
+
+    /// Enables or disables style calibration. Disabling drops recorded feedback.
+    pub async fn set_style_calibration_enabled(&self, enabled: bool) {
+        let mut calibration = self.style_calibration.write().await;
+        if calibration.is_some() != enabled {
+            *calibration = enabled.then(StyleCalibration::default);
+        }
+    }
+
+    /// Gets whether replies speaking for several personas are split per persona.
+    pub async fn is_response_splitting_enabled(&self) -> bool {
+        *self.response_splitting.read().await
+    }
+
+    /// Enables or disables splitting replies that speak for several personas.
+    pub async fn set_response_splitting_enabled(&self, enabled: bool) {
+        *self.response_splitting.write().await = enabled;
+    }
+
+    /// Gets the seed passed to backends that support one.
+    pub async fn random_seed(&self) -> Option<u64> {
+        *self.random_seed.read().await
+    }
+
+    /// Sets (or clears, with `None`) the seed passed to backends that support
+    /// one. Applies from the next turn; other backends ignore it.
+    pub async fn set_random_seed(&self, seed: Option<u64>) {
+        *self.random_seed.write().await = seed;
+    }
+
+    /// Gets whether Rich-mode turns tell agents the current time.
+    pub async fn is_clock_context_enabled(&self) -> bool {
+        *self.clock_context_enabled.read().await
+    }
+
+    /// Enables or disables the clock context. Applies from the next turn.
+    pub async fn set_clock_context_enabled(&self, enabled: bool) {
+        *self.clock_context_enabled.write().await = enabled;
+    }
+
+    /// Gets whether fenced `plan` blocks in agent replies are offered for confirmation.
+    pub async fn is_plan_detection_enabled(&self) -> bool {
+        *self.plan_detection.read().await
+    }
+
+    /// Enables or disables plan detection.
+    ///
+    /// While it is on, a user turn whose replies contain a ` ```plan ` block
+    /// ends in [`InteractionResult::ModeChanged`] to
+    /// [`AppMode::AwaitingConfirmation`] with the block's steps.
+    pub async fn set_plan_detection_enabled(&self, enabled: bool) {
+        *self.plan_detection.write().await = enabled;
+    }
+
+    /// Gets whether long non-prose pastes are sent as attachments, and the
+    /// length in characters above which they are.
+    pub async fn paste_attachment_settings(&self) -> (bool, usize) {
+        (
+            *self.paste_attachment_enabled.read().await,
+            *self.paste_attachment_threshold.read().await,
+        )
+    }
+
+    /// Sets whether long non-prose pastes are sent as attachments, and above
+    /// which length in characters. Applies from the next user message.
+    ///
+    /// Such a paste is stored as a workspace file, attached to the message and
+    /// replaced by an excerpt (see [`paste_attachment`]).
+    pub async fn set_paste_attachment(&self, enabled: bool, threshold: usize) {
+        *self.paste_attachment_enabled.write().await = enabled;
+        *self.paste_attachment_threshold.write().await = threshold;
+    }
+
+    /// Stores `input` as a workspace file if it is a long non-prose paste.
+    ///
+    /// Returns the excerpt to send in its place and the stored file's path, or
+    /// `None` to send the input as typed: the feature is off, the input is
+    /// short or prose, or there is no workspace to store it in.
+    async fn attach_long_paste(&self, input: &str, author: &str) -> Option<(String, String)> {
+        let (enabled, threshold) = self.paste_attachment_settings().await;
+        if !enabled || !paste_attachment::should_attach(input, threshold) {
+            return None;
+        }
+        let storage = self.workspace_storage.as_ref()?;
+        let workspace_id = self.workspace_id.read().await.clone()?;
+
+        let file_name = paste_attachment::file_name(self.clock.now());
+        match storage
+            .add_file_from_bytes(
+                &workspace_id,
+                &file_name,
+                input.as_bytes(),
+                Some(self.session_id.clone()),
+                None,
+                Some(author.to_string()),
+            )
+            .await
+        {
+            Ok(file) => Some((
+                paste_attachment::excerpt(input, &file.name),
+                file.path.to_string_lossy().into_owned(),
+            )),
+            Err(e) => {
+                tracing::warn!(
+                    "[InteractionManager] Failed to store long paste, sending it as typed: {}",
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Builds this turn's clock context block, or `None` when agents don't get
+    /// one (the toggle is off or the session is in Clean context mode).
+    ///
+    /// Call before the new user input is added to the history, so the gap is
+    /// measured from the previous user message.
+    async fn clock_context(&self) -> Option<String> {
+        if !*self.clock_context_enabled.read().await
+            || !matches!(*self.context_mode.read().await, ContextMode::Rich)
+        {
+            return None;
+        }
+
+        let parse = |timestamp: &str| {
+            chrono::DateTime::parse_from_rfc3339(timestamp)
+                .ok()
+                .map(|time| time.with_timezone(&chrono::Utc))
+        };
+        let last_user_message = self
+            .persona_histories
+            .read()
+            .await
+            .values()
+            .flatten()
+            .filter(|message| message.role == MessageRole::User)
+            .filter_map(|message| parse(&message.timestamp))
+            .max();
+        let timezone = self.user_service.get_user_profile().timezone;
+        Some(clock_context::format_context(
+            self.clock.now(),
+            timezone.as_deref(),
+            parse(&self.created_at),
+            last_user_message,
+        ))
+    }
+
+    /// Gets the session pinboard.
+    pub async fn get_pinboard(&self) -> Option<String> {
+        self.pinboard.read().await.clone()
+    }
+
+    /// Replaces the session pinboard; an empty document clears it.
+    ///
+    /// The dialogue is recreated so the next turn sees the update.
+    pub async fn set_pinboard(&self, content: Option<String>) {
+        *self.pinboard.write().await = content.filter(|text| !text.trim().is_empty());
+        self.invalidate_dialogue().await;
+    }
+
+    /// Gets whether agents may append to the pinboard.
+    pub async fn is_pinboard_agent_edits_enabled(&self) -> bool {
+        *self.pinboard_agent_edits.read().await
+    }
+
+    /// Allows or forbids agents to append to the pinboard.
+    ///
+    /// The dialogue is recreated so agents learn whether they may append.
+    pub async fn set_pinboard_agent_edits_enabled(&self, enabled: bool) {
+        *self.pinboard_agent_edits.write().await = enabled;
+        self.invalidate_dialogue().await;
+    }
+
+    /// Gets whether participants are suggested after user turns.
+    pub async fn is_participant_suggestions_enabled(&self) -> bool {
+        *self.participant_suggestions.read().await
+    }
+
+    /// Enables or disables participant suggestions after user turns.
+    pub async fn set_participant_suggestions_enabled(&self, enabled: bool) {
+        *self.participant_suggestions.write().await = enabled;
+    }
+
+    /// Gets the progress of the conversation script running in this session.
+    pub async fn script_run(&self) -> Option<ScriptRun> {
+        self.script_run.read().await.clone()
+    }
+
+    /// Sets (or clears, with `None`) the running conversation script's progress.
+    pub async fn set_script_run(&self, run: Option<ScriptRun>) {
+        *self.script_run.write().await = run;
+    }
+
+    /// Returns the participants answering while focus mode is on, or `None`
+    /// when everyone answers.
+    pub async fn focused_participant_ids(&self) -> Option<Vec<String>> {
+        self.focused_participant_ids.read().await.clone()
+    }
+
+    /// Narrows the responding participants to `personas` (IDs or names)
+    /// without changing the roster.
+    ///
+    /// The others stay participants: they remain in the participants listing,
+    /// answer for a single turn when mentioned, and answer again once focus
+    /// is cleared. One system message records the change instead of a
+    /// leave/join message per persona.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `personas` is empty or names a persona that is not
+    /// a participant of this session.
+    pub async fn set_focus(&self, personas: Vec<String>) -> Result<(), String> {
+        if personas.is_empty() {
+            return Err("Focus needs at least one participant".to_string());
+        }
+        let participants = self.participant_personas().await?;
+        let mut focused: Vec<&PersonaDomain> = Vec::new();
+        for wanted in &personas {
+            let persona = participants
+                .iter()
+                .find(|p| *wanted == p.id || wanted.eq_ignore_ascii_case(&p.name))
+                .ok_or_else(|| format!("{} is not a participant of this session", wanted))?;
+            if !focused.iter().any(|p| p.id == persona.id) {
+                focused.push(persona);
+            }
+        }
+
+        let names: Vec<&str> = focused.iter().map(|p| p.name.as_str()).collect();
+        *self.focused_participant_ids.write().await =
+            Some(focused.iter().map(|p| p.id.clone()).collect());
+        self.focus_guests.write().await.clear();
+        self.push_focus_message(format!(
+            "{} にフォーカスしました（他の参加者はメンションされたときだけ応答します）",
+            names.join(", ")
+        ))
+        .await;
+        self.invalidate_dialogue().await;
+        Ok(())
+    }
+
+    /// Ends focus mode, so every participant answers again.
+    ///
+    /// Does nothing when focus mode is off.
+    pub async fn clear_focus(&self) {
+        if self.focused_participant_ids.write().await.take().is_none() {
+            return;
+        }
+        self.focus_guests.write().await.clear();
+        self.push_focus_message("フォーカスを解除しました。全員が応答します".to_string())
+            .await;
+        self.invalidate_dialogue().await;
+    }
+
+    /// Records a focus mode change as a system message.
+    async fn push_focus_message(&self, content: String) {
+        self.system_messages
+            .write()
+            .await
+            .push(ConversationMessage {
+                role: MessageRole::System,
+                content,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                metadata: MessageMetadata {
+                    system_event_type: Some(SystemEventType::FocusChanged),
+                    error_severity: None,
+                    system_message_type: None,
+                    include_in_dialogue: true,
+                    llm_debug_info: None,
+                    recovered_partial: false,
+                    pinned: false,
+                    split_from_single_response: false,
+                    spawned_task_ids: Vec::new(),
+                    in_response_to: None,
+                    source: None,
+                    sources: Vec::new(),
+                    full_content_path: None,
+                    turn_stats: None,
+                },
+                attachments: vec![],
+            });
+    }
+
+    /// Lets the participants outside the focus that `input` mentions answer
+    /// this turn; the previous turn's guests return to standby.
+    async fn update_focus_guests(&self, input: &str) {
+        let mut guests = Vec::new();
+        if let Some(focused) = self.focused_participant_ids.read().await.clone()
+            && let Ok(participants) = self.participant_personas().await
+        {
+            let standby: Vec<String> = participants
+                .iter()
+                .filter(|p| !focused.contains(&p.id))
+                .map(|p| p.name.clone())
+                .collect();
+            let mentioned = mentions::mentioned_names(input, &standby);
+            guests = participants
+                .into_iter()
+                .filter(|p| mentioned.contains(&p.name))
+                .map(|p| p.id)
+                .collect();
+        }
+
+        let mut current = self.focus_guests.write().await;
+        if *current != guests {
+            *current = guests;
+            drop(current);
+            self.invalidate_dialogue().await;
+        }
+    }
+
+    /// Splits `personas` into those answering under focus mode and those on
+    /// standby.
+    ///
+    /// Everyone answers while focus mode is off, or if no focused persona is a
+    /// participant anymore.
+    async fn split_by_focus(
+        &self,
+        personas: Vec<PersonaDomain>,
+    ) -> (Vec<PersonaDomain>, Vec<PersonaDomain>) {
+        let Some(focused) = self.focused_participant_ids.read().await.clone() else {
+            return (personas, Vec::new());
+        };
+        if !personas.iter().any(|p| focused.contains(&p.id)) {
+            return (personas, Vec::new());
+        }
+        let guests = self.focus_guests.read().await.clone();
+        personas
+            .into_iter()
+            .partition(|p| focused.contains(&p.id) || guests.contains(&p.id))
+    }
+
+    /// Suggests personas for `input` when no active participant covers its topic.
+    ///
+    /// Returns nothing while suggestions are disabled for the session. Personas
+    /// the user dismissed earlier in this session are left out.
+    pub async fn suggest_participants(&self, input: &str) -> Vec<ParticipantSuggestion> {
+        if !self.is_participant_suggestions_enabled().await {
+            return Vec::new();
+        }
+        let Ok(personas) = self.workspace_personas().await else {
+            return Vec::new();
+        };
+        let Ok(active_ids) = self.get_active_participants().await else {
+            return Vec::new();
+        };
+        let dismissed = self.dismissed_suggestions.read().await;
+
+        suggest_participants(input, &personas, &active_ids, &dismissed)
+            .into_iter()
+            .filter_map(|found| {
+                let persona = personas.iter().find(|p| p.id == found.persona_id)?;
+                Some(ParticipantSuggestion {
+                    persona_id: persona.id.clone(),
+                    name: persona.name.clone(),
+                    role: persona.role.clone(),
+                    icon: persona.icon.clone(),
+                    matched_keywords: found.matched_keywords,
+                    add_participant: AddParticipantArgs {
+                        persona_id: persona.id.clone(),
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Stops suggesting `persona_id` for the rest of this session.
+    pub async fn dismiss_participant_suggestion(&self, persona_id: &str) {
+        self.dismissed_suggestions
+            .write()
+            .await
+            .insert(persona_id.to_string());
+    }
+
+    /// Records user feedback on a persona's latest answer.
+    ///
+    /// Fails when style calibration is disabled for this session.
+    pub async fn record_feedback(
+        &self,
+        persona_id: &str,
+        kind: FeedbackKind,
+    ) -> Result<(), String> {
+        let event = match self.style_calibration.write().await.as_mut() {
+            Some(calibration) => calibration.record_feedback(persona_id, kind),
+            None => return Err("Style calibration is not enabled for this session".to_string()),
+        };
+        if let Some(event) = event {
+            self.record_calibration_event(event).await;
+        }
+        Ok(())
+    }
+
+    /// Current calibration state per persona (empty when disabled).
+    pub async fn style_calibration_states(&self) -> Vec<PersonaCalibrationState> {
+        self.style_calibration
+            .read()
+            .await
+            .as_ref()
+            .map(StyleCalibration::states)
+            .unwrap_or_default()
+    }
+
+    /// Session state that participants' capability claims are computed from.
+    ///
+    /// Claims are fixed when an agent is built, so they follow the workspace and
+    /// web search setting as of dialogue construction.
+    async fn runtime_caps(&self) -> RuntimeCaps {
+        RuntimeCaps {
+            workspace_available: self.agent_workspace_root.read().await.is_some(),
+            // Web search needs the network, so it is off while offline mode is on
+            web_search_enabled: !self.offline_mode.is_enabled()
+                && self
+                    .auto_chat_config
+                    .read()
+                    .await
+                    .as_ref()
+                    .is_none_or(|config| config.web_search_enabled),
+        }
+    }
+
+    /// Interval of heartbeats for running CLI turns, or `None` when disabled.
+    async fn cli_heartbeat_interval(&self) -> Option<std::time::Duration> {
+        let secs = self.env_settings.read().await.cli_heartbeat_interval_secs;
+        (secs > 0).then(|| std::time::Duration::from_secs(secs))
+    }
+
+    /// Where agents spawning their own CLI processes report output, keyed by
+    /// persona name, for the heartbeats of running turns.
+    ///
+    /// See [`heartbeat::run_reporting_output`].
+    pub fn cli_output_activity(&self) -> OutputActivity {
+        self.thinking_signal.output.clone()
+    }
+
+    fn raw_output_capture(&self, persona_id: &str) -> RawOutputCapture {
+        RawOutputCapture {
+            persona_id: persona_id.to_string(),
+            user_service: self.user_service.clone(),
+            outputs: self.raw_cli_outputs.clone(),
+        }
+    }
+
+    /// Moves the persona's captured CLI output onto its latest history message.
+    async fn attach_raw_cli_output(&self, persona_id: &str) {
+        let Some(info) = self.raw_cli_outputs.write().await.remove(persona_id) else {
+            return;
+        };
+        let mut histories = self.persona_histories.write().await;
+        if let Some(history) = histories.get_mut(persona_id)
+            && let Some(message) = history.last_mut()
+        {
+            message.metadata.llm_debug_info = Some(info);
+            self.dirty_sections.edited(persona_id, history.len() - 1);
+        }
+    }
+
+    /// Moves the stats of the persona's latest turn onto its latest history
+    /// message, returning them for the streamed turn.
+    async fn attach_turn_stats(&self, persona_id: &str) -> Option<TurnStats> {
+        let stats = self.turn_stats.write().await.remove(persona_id)?;
+        let mut histories = self.persona_histories.write().await;
+        if let Some(history) = histories.get_mut(persona_id)
+            && let Some(message) = history.last_mut()
+        {
+            message.metadata.turn_stats = Some(stats.clone());
+            self.dirty_sections.edited(persona_id, history.len() - 1);
+        }
+        Some(stats)
+    }
+
+    async fn record_calibration_turn(&self, persona_id: &str) {
+        let event = self
+            .style_calibration
+            .write()
+            .await
+            .as_mut()
+            .and_then(|calibration| calibration.record_turn(persona_id));
+        if let Some(event) = event {
+            self.record_calibration_event(event).await;
+        }
+    }
+
+    /// Records a calibration change as a system message so it can be audited.
+    async fn record_calibration_event(&self, event: CalibrationEvent) {
+        let (CalibrationEvent::Triggered { persona_id, kind }
+        | CalibrationEvent::Decayed { persona_id, kind }) = &event;
+        let name = self
+            .participant_metadata
+            .read()
+            .await
+            .participants
+            .get(persona_id)
+            .cloned()
+            .unwrap_or_else(|| persona_id.clone());
+        let content = match &event {
+            CalibrationEvent::Triggered { .. } => format!(
+                "{} の応答スタイルを調整します: {}",
+                name,
+                kind.instruction().unwrap_or_default()
+            ),
+            CalibrationEvent::Decayed { .. } => {
+                format!("{} の応答スタイル調整を解除しました", name)
+            }
+        };
+        tracing::info!("[InteractionManager] Style calibration: {:?}", event);
+
+        self.system_messages
+            .write()
+            .await
+            .push(ConversationMessage {
+                role: MessageRole::System,
+                content,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                metadata: MessageMetadata {
+                    system_event_type: Some(SystemEventType::StyleCalibrationChanged),
+                    error_severity: None,
+                    system_message_type: None,
+                    include_in_dialogue: false,
+                    llm_debug_info: None,
+                    recovered_partial: false,
+                    pinned: false,
+                    split_from_single_response: false,
+                    spawned_task_ids: Vec::new(),
+                    in_response_to: None,
+                    source: None,
+                    sources: Vec::new(),
+                    full_content_path: None,
+                    turn_stats: None,
+                },
+                attachments: vec![],
+            });
+    }
+
+    /// Gets the current context mode.
+    pub async fn get_context_mode(&self) -> ContextMode {
+        *self.context_mode.read().await
+    }
+
+    /// Sets the context mode.
+    pub async fn set_context_mode(&self, mode: ContextMode) {
+        let previous = self.conversation_mode_instruction().await;
+        *self.context_mode.write().await = mode;
+        self.refresh_conversation_mode_instruction(previous).await;
+    }
+
+    /// Gets whether the conversation-mode instruction applies in Clean context mode.
+    pub async fn is_conversation_mode_applied_in_clean(&self) -> bool {
+        *self.apply_conversation_mode_in_clean.read().await
+    }
+
+    /// Sets whether the conversation-mode instruction applies in Clean context mode.
+    ///
+    /// Disabling it restores the earlier behavior where Clean mode also dropped
+    /// the conversation mode's length and style limits.
+    pub async fn set_conversation_mode_applied_in_clean(&self, applied: bool) {
+        let previous = self.conversation_mode_instruction().await;
+        *self.apply_conversation_mode_in_clean.write().await = applied;
+        self.refresh_conversation_mode_instruction(previous).await;
+    }
+
+    /// Returns the conversation-mode instruction for the dialogue context.
+    ///
+    /// The instruction shapes the output rather than adding context, so it is
+    /// sent in both context modes unless the session opted out for Clean mode.
+    async fn conversation_mode_instruction(&self) -> Option<&'static str> {
+        let applies = match *self.context_mode.read().await {
+            ContextMode::Rich => true,
+            ContextMode::Clean => *self.apply_conversation_mode_in_clean.read().await,
+        };
+        if !applies {
+            return None;
+        }
+        self.conversation_mode.read().await.system_instruction()
     }
 
-    /// Gets the current context mode.
-    pub async fn get_context_mode(&self) -> ContextMode {
-        *self.context_mode.read().await
+    /// Drops the dialogue when the effective conversation-mode instruction
+    /// changed, so the next turn is built with the new one.
+    async fn refresh_conversation_mode_instruction(&self, previous: Option<&'static str>) {
+        if self.conversation_mode_instruction().await != previous {
+            *self.dialogue.lock().await = None;
+        }
     }
 
-    /// Sets the context mode.
-    pub async fn set_context_mode(&self, mode: ContextMode) {
-        *self.context_mode.write().await = mode;
+    /// Sets how many recent turns a persona keeps its full participant entry after
+    /// speaking. Quieter personas are listed as a one-line descriptor; `0` always
+    /// lists every participant in full.
+    pub async fn set_participant_activity_window(&self, turns: usize) {
+        self.participant_activity.write().await.set_window(turns);
     }
 
     /// Sets the sandbox state for git worktree-based isolated development.
@@ -1416,7 +3937,7 @@ impl InteractionManager {
         };
 
         if let Some(messages) = histories.get_mut(persona_id) {
-            for message in messages.iter_mut() {
+            for (index, message) in messages.iter_mut().enumerate() {
                 let msg_timestamp_prefix = if message.timestamp.len() >= 23 {
                     &message.timestamp[..23]
                 } else {
@@ -1425,6 +3946,7 @@ impl InteractionManager {
 
                 if msg_timestamp_prefix == timestamp_prefix {
                     message.content = new_content;
+                    self.dirty_sections.edited(persona_id, index);
                     // Invalidate dialogue cache so changes are reflected
                     drop(histories);
                     self.invalidate_dialogue().await;
@@ -1440,6 +3962,55 @@ impl InteractionManager {
         }
     }
 
+    /// Records that a task was spawned from a message of this session.
+    ///
+    /// The message is looked up by timestamp in persona histories and system
+    /// messages; without a timestamp, the latest message is used. The task ID is
+    /// added to the message's `spawned_task_ids`.
+    ///
+    /// # Returns
+    ///
+    /// The task's origin, or `None` if no matching message exists.
+    pub async fn link_spawned_task(
+        &self,
+        message_timestamp: Option<&str>,
+        task_id: &str,
+    ) -> Option<TaskOrigin> {
+        let mut histories = self.persona_histories.write().await;
+        let mut system_messages = self.system_messages.write().await;
+
+        let mut candidates = histories
+            .iter_mut()
+            .flat_map(|(author, messages)| {
+                messages
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(index, message)| (Some((author.as_str(), index)), message))
+            })
+            .chain(system_messages.iter_mut().map(|message| (None, message)));
+        let (position, message) = match message_timestamp {
+            Some(timestamp) => candidates.find(|(_, message)| message.has_timestamp(timestamp)),
+            None => candidates.max_by(|(_, a), (_, b)| a.timestamp.cmp(&b.timestamp)),
+        }?;
+        let author = position.map_or("system", |(author, _)| author);
+
+        if !message
+            .metadata
+            .spawned_task_ids
+            .iter()
+            .any(|id| id == task_id)
+        {
+            message.metadata.spawned_task_ids.push(task_id.to_string());
+            if let Some((author, index)) = position {
+                self.dirty_sections.edited(author, index);
+            }
+        }
+        Some(TaskOrigin {
+            message_timestamp: message.timestamp.clone(),
+            author: author.to_string(),
+        })
+    }
+
     /// Handles user input based on the current application mode.
     ///
     /// # Arguments
@@ -1491,6 +4062,89 @@ impl InteractionManager {
         }
     }
 
+    /// Handles user input in a detached background task.
+    ///
+    /// The dialogue runs without requiring a live subscriber: the session is marked
+    /// as generating before the task is spawned, `persist` is called once up front,
+    /// after every turn as it arrives, and once more after the generating flag is
+    /// cleared. Callers can navigate away and later poll the persisted session or
+    /// reattach via `is_generating`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The current application mode
+    /// * `input` - The user's input string
+    /// * `file_paths` - Optional list of file paths to attach
+    /// * `on_turn` - Called for each dialogue turn before it is persisted
+    /// * `persist` - Saves the current session state (called from the background task)
+    ///
+    /// # Returns
+    ///
+    /// A handle resolving to the `InteractionResult` once the dialogue finishes, or
+    /// `None` if the session is already generating.
+    pub async fn handle_input_background<F, P, Fut>(
+        self: &Arc<Self>,
+        mode: AppMode,
+        input: String,
+        file_paths: Option<Vec<String>>,
+        on_turn: F,
+        persist: P,
+    ) -> Option<tokio::task::JoinHandle<InteractionResult>>
+    where
+        F: Fn(&DialogueMessage) + Send + Sync + 'static,
+        P: Fn() -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send,
+    {
+        // Claim the session before spawning so concurrent callers cannot both start
+        if self
+            .is_generating
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return None;
+        }
+
+        // Snapshot participants before the dialogue lock is held for generation
+        if let Ok(participant_ids) = self.get_active_participants().await {
+            *self.restored_participant_ids.write().await = Some(participant_ids);
+        }
+
+        let manager = Arc::clone(self);
+        Some(tokio::spawn(async move {
+            persist().await;
+
+            let (turn_tx, mut turn_rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+            let dialogue =
+                manager.handle_input_with_streaming(&mode, &input, file_paths, move |turn| {
+                    on_turn(turn);
+                    // Thinking indicators carry nothing to save
+                    if !turn.is_thinking() {
+                        let _ = turn_tx.send(());
+                    }
+                });
+            let persist_turns = async {
+                while turn_rx.recv().await.is_some() {
+                    persist().await;
+                }
+            };
+            let (result, ()) = tokio::join!(dialogue, persist_turns);
+
+            manager.is_generating.store(false, Ordering::SeqCst);
+            persist().await;
+
+            tracing::info!(
+                "[InteractionManager] Background dialogue finished for session {}",
+                manager.session_id
+            );
+            result
+        }))
+    }
+
+    /// Returns whether a background dialogue is currently generating responses.
+    pub async fn is_generating(&self) -> bool {
+        self.is_generating.load(Ordering::SeqCst)
+    }
+
     /// Handles a system message that triggers dialogue continuation.
     ///
     /// # Arguments
@@ -1516,42 +4170,47 @@ impl InteractionManager {
                 session_id: self.session_id.clone(),
                 author: "System".to_string(),
                 content: message.to_string(),
+                failure: None,
+                thinking: None,
+                heartbeat: None,
+                turn_stats: None,
             };
             callback(&system_msg);
         }
 
         // Run the dialogue with system speaker
         let mut dialogue_guard = self.dialogue.lock().await;
-        let dialogue = match dialogue_guard.as_mut() {
-            Some(d) => d,
-            None => {
-                drop(dialogue_guard);
-                return InteractionResult::NewMessage(
-                    "Error: Dialogue was invalidated during initialization (possible race condition)"
-                        .to_string(),
-                );
-            }
+        let Some(dialogue) = dialogue_guard.as_mut() else {
+            return dialogue_invalidated();
         };
 
         let speaker = Speaker::System;
-        let mut payload = Payload::new().with_message(speaker, message);
+        let payload = Payload::new().with_message(speaker, message);
+        let clock_context = self.clock_context().await;
 
-        // Prepend conversation mode system instruction if available (Rich mode only)
-        let context_mode = *self.context_mode.read().await;
-        if matches!(context_mode, ContextMode::Rich) {
-            let conversation_mode = self.conversation_mode.read().await;
-            if let Some(instruction) = conversation_mode.system_instruction() {
-                payload = payload.prepend_system(instruction);
-            }
-            drop(conversation_mode);
-        }
+        let participant_names: Vec<String> = dialogue
+            .participants()
+            .iter()
+            .map(|persona| persona.name.clone())
+            .collect();
 
         // Create a partial session for incremental turn processing
+        // Begin the round first: broadcast agents start as soon as the session exists
+        let mut thinking = self
+            .thinking_signal
+            .begin_round(&self.session_id, self.cli_heartbeat_interval().await);
+        // Per-turn context, never stored in the history
+        let _clock_context = self.clock_context_slot.begin_round(clock_context);
         let mut session = dialogue.partial_session(payload);
         let mut messages = Vec::new();
+        self.round_failures.write().await.clear();
+        self.raw_cli_outputs.write().await.clear();
+        self.turn_stats.write().await.clear();
+        let chains_turns = self.chains_agent_turns().await;
+        let mut previous_speaker: Option<String> = None;
 
         // Process each turn as it becomes available
-        while let Some(result) = session.next_turn().await {
+        while let Some(result) = thinking.next_turn(&mut session, on_turn.as_ref()).await {
             match result {
                 Ok(turn) => {
                     let speaker_name = turn.speaker.name();
@@ -1562,70 +4221,56 @@ impl InteractionManager {
                         preview
                     );
 
-                    // Convert speaker name to persona_id (UUID)
-                    let persona_id = self
-                        .get_persona_id_by_name(speaker_name)
-                        .await
-                        .unwrap_or_else(|| speaker_name.to_string());
-
-                    // Add each response to history using persona_id
-                    self.add_to_history(&persona_id, MessageRole::Assistant, &turn.content, None)
+                    let in_response_to = previous_speaker.as_deref().filter(|_| chains_turns);
+                    let turns = self
+                        .commit_agent_turns(
+                            speaker_name,
+                            &turn.content,
+                            &participant_names,
+                            in_response_to,
+                        )
                         .await;
+                    previous_speaker = Some(self.speaker_id(speaker_name, &turns).await);
+                    for (persona_id, message) in turns {
+                        self.record_calibration_turn(&persona_id).await;
 
-                    // Create DialogueMessage for UI display
-                    let message = DialogueMessage {
-                        session_id: self.session_id.clone(),
-                        author: speaker_name.to_string(),
-                        content: turn.content.clone(),
-                    };
-
-                    // Call the streaming callback if provided
-                    if let Some(ref callback) = on_turn {
-                        callback(&message);
+                        // Call the streaming callback if provided
+                        if let Some(ref callback) = on_turn {
+                            callback(&message);
+                        }
+                        messages.push(message);
                     }
-
-                    messages.push(message);
+                    thinking.finish(speaker_name, on_turn.as_ref());
                 }
                 Err(e) => {
                     tracing::error!("[DIALOGUE] Agent execution failed: {}", e);
+                    self.round_failures.write().await.push(FailureKind::of(&e));
 
-                    let error_msg = format!("{}\n\nPlease check the logs for more details.", e);
+                    let failure = self.turn_failure(&e);
+                    let error_msg = failure.message(&e);
 
-                    // Emit error as a system message via callback if provided
+                    // Emit the error with its retry hint via callback if provided
                     if let Some(ref callback) = on_turn {
                         let error_turn = DialogueMessage {
                             session_id: self.session_id.clone(),
                             author: String::new(),
                             content: error_msg.clone(),
+                            failure: Some(failure),
+                            thinking: None,
+                            heartbeat: None,
+                            turn_stats: None,
                         };
                         callback(&error_turn);
                     }
+                    thinking.finish_all(on_turn.as_ref());
 
-                    // Add error to history for persistence with metadata
-                    let error_history = ConversationMessage {
-                        role: MessageRole::System,
-                        content: error_msg.clone(),
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        metadata: MessageMetadata {
-                            system_event_type: None,
-                            error_severity: Some(ErrorSeverity::Critical),
-                            system_message_type: None,
-                            include_in_dialogue: true,
-                            llm_debug_info: None,
-                        },
-                        attachments: vec![],
-                    };
-                    self.persona_histories
-                        .write()
-                        .await
-                        .entry("Error".to_string())
-                        .or_insert_with(Vec::new)
-                        .push(error_history);
+                    self.record_error(&error_msg).await;
 
                     return InteractionResult::NewDialogueMessages(Vec::new());
                 }
             }
         }
+        thinking.finish_all(on_turn.as_ref());
 
         InteractionResult::NewDialogueMessages(messages)
     }
@@ -1656,11 +4301,40 @@ impl InteractionManager {
         // Check if session is muted - if so, only add to history but don't run AI
         let is_muted = self.is_muted().await;
 
+        // Built before the input joins the history, which it measures the gap from
+        let clock_context = self.clock_context().await;
+
+        // Long non-prose pastes go out as an excerpt plus the stored full text
+        let user_id = self.acting_user_id().await;
+        let stored_paste = if add_to_history {
+            self.attach_long_paste(input, &user_id).await
+        } else {
+            None
+        };
+        let (input, file_paths) = match &stored_paste {
+            Some((excerpt, path)) => {
+                let mut paths = file_paths.unwrap_or_default();
+                paths.push(path.clone());
+                (excerpt.as_str(), Some(paths))
+            }
+            None => (input, file_paths),
+        };
+
         // Add user input to history BEFORE checking mute (so user's message is saved)
-        let user_name = self.user_service.get_user_name();
         if add_to_history {
-            self.add_to_history(&user_name, MessageRole::User, input, file_paths.clone())
-                .await;
+            let metadata = MessageMetadata {
+                full_content_path: stored_paste.as_ref().map(|(_, path)| path.clone()),
+                ..MessageMetadata::default()
+            };
+            self.add_to_history_with_metadata(
+                &user_id,
+                MessageRole::User,
+                input,
+                file_paths.clone(),
+                metadata,
+            )
+            .await;
+            self.participant_activity.write().await.record(&user_id);
         }
 
         // If muted, return early without running dialogue
@@ -1669,52 +4343,75 @@ impl InteractionManager {
             return InteractionResult::NoOp;
         }
 
+        // Participants outside the focus answer when mentioned
+        self.update_focus_guests(input).await;
+
         // Ensure dialogue is initialized
         if let Err(e) = self.ensure_dialogue_initialized().await {
             return InteractionResult::NewMessage(format!("Error initializing dialogue: {}", e));
         }
-        let user_name_str = if user_name.to_lowercase() == "you" {
-            tracing::warn!(
-                "[InteractionManager] Detected user name 'You', which may cause speaker attribution issues."
-            );
-            "User"
-        } else {
-            &user_name
+        let speaker = Speaker::user(self.user_speaker_name(&user_id), "User");
+
+        // The payload is prepared without holding the dialogue lock, so reading
+        // large attachments does not block other operations on the session
+        let participant_names: Vec<String> = match self.dialogue.lock().await.as_ref() {
+            Some(dialogue) => dialogue
+                .participants()
+                .iter()
+                .map(|persona| persona.name.clone())
+                .collect(),
+            None => return dialogue_invalidated(),
         };
-        let speaker = Speaker::user(user_name_str, "User");
-
-        // Run the dialogue with the user's input using partial_session for streaming
-        let mut dialogue_guard = self.dialogue.lock().await;
-        let dialogue = match dialogue_guard.as_mut() {
-            Some(d) => d,
-            None => {
-                drop(dialogue_guard);
-                return InteractionResult::NewMessage(
-                    "Error: Dialogue was invalidated during initialization (possible race condition)"
-                        .to_string(),
-                );
+        if participant_names.is_empty() {
+            if !self.has_personas().await {
+                return InteractionResult::NewMessage(NO_PERSONAS_MESSAGE.to_string());
             }
+            // Every participant was removed: say so instead of an empty round
+            self.system_messages
+                .write()
+                .await
+                .push(ConversationMessage {
+                    role: MessageRole::System,
+                    content: NO_PARTICIPANTS_MESSAGE.to_string(),
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    metadata: MessageMetadata {
+                        system_event_type: Some(SystemEventType::Notification),
+                        error_severity: Some(ErrorSeverity::Warning),
+                        include_in_dialogue: false,
+                        ..MessageMetadata::default()
+                    },
+                    attachments: vec![],
+                });
+            return InteractionResult::NewMessage(NO_PARTICIPANTS_MESSAGE.to_string());
+        }
+
+        // Resolve @everyone / @here before the dialogue picks mentioned responders
+        let routed_input = if matches!(
+            *self.execution_strategy.read().await,
+            ExecutionModel::Mentioned { .. }
+        ) {
+            let activity = self.participant_activity.read().await;
+            let active: Vec<String> = participant_names
+                .iter()
+                .filter(|name| activity.spoke_recently(name))
+                .cloned()
+                .collect();
+            mentions::expand_special_mentions(input, &participant_names, &active)
+        } else {
+            input.to_string()
         };
 
         // Note: Dialogue/Persona agents handle speaker attribution internally
-        let mut payload = Payload::new().with_message(speaker, input);
-
-        // Prepend conversation mode system instruction if available (Rich mode only)
-        let context_mode = *self.context_mode.read().await;
-        if matches!(context_mode, ContextMode::Rich) {
-            let conversation_mode = self.conversation_mode.read().await;
-            if let Some(instruction) = conversation_mode.system_instruction() {
-                payload = payload.prepend_system(instruction);
-            }
-            drop(conversation_mode);
-        }
+        let mut payload = Payload::new().with_message(speaker, routed_input);
 
         // Add file attachments if provided
-        if let Some(paths) = file_paths {
-            for path in paths {
-                tracing::info!("[InteractionManager] Attaching file: {}", path);
-                payload = payload.with_attachment(Attachment::local(path));
-            }
+        let attachments = attachment_reading::read_attachments(
+            Arc::clone(&self.attachment_reader),
+            file_paths.unwrap_or_default(),
+        )
+        .await;
+        for attachment in attachments {
+            payload = payload.with_attachment(attachment);
         }
 
         // Debug: Log payload content before partial_session
@@ -1724,13 +4421,30 @@ impl InteractionManager {
             payload.clone()
         );
 
+        // Run the dialogue with the user's input using partial_session for streaming
+        let mut dialogue_guard = self.dialogue.lock().await;
+        let Some(dialogue) = dialogue_guard.as_mut() else {
+            return dialogue_invalidated();
+        };
+
         // Create a partial session for incremental turn processing
         // partial_session now accepts impl Into<Payload>, so both String and Payload work
+        // Begin the round first: broadcast agents start as soon as the session exists
+        let mut thinking = self
+            .thinking_signal
+            .begin_round(&self.session_id, self.cli_heartbeat_interval().await);
+        // Per-turn context, never stored in the history
+        let _clock_context = self.clock_context_slot.begin_round(clock_context);
         let mut session = dialogue.partial_session(payload);
         let mut messages = Vec::new();
+        self.round_failures.write().await.clear();
+        self.raw_cli_outputs.write().await.clear();
+        self.turn_stats.write().await.clear();
+        let chains_turns = self.chains_agent_turns().await;
+        let mut previous_speaker: Option<String> = None;
 
         // Process each turn as it becomes available
-        while let Some(result) = session.next_turn().await {
+        while let Some(result) = thinking.next_turn(&mut session, on_turn.as_ref()).await {
             match result {
                 Ok(turn) => {
                     // Log the turn for debugging sequential execution with timestamp
@@ -1742,73 +4456,67 @@ impl InteractionManager {
                         preview
                     );
 
-                    // Convert speaker name to persona_id (UUID)
-                    let persona_id = self
-                        .get_persona_id_by_name(speaker_name)
-                        .await
-                        .unwrap_or_else(|| speaker_name.to_string());
-
-                    // Add each response to history using persona_id
-                    self.add_to_history(&persona_id, MessageRole::Assistant, &turn.content, None)
+                    let in_response_to = previous_speaker.as_deref().filter(|_| chains_turns);
+                    let turns = self
+                        .commit_agent_turns(
+                            speaker_name,
+                            &turn.content,
+                            &participant_names,
+                            in_response_to,
+                        )
                         .await;
-
-                    // Create DialogueMessage for UI display
-                    let message = DialogueMessage {
-                        session_id: self.session_id.clone(),
-                        author: speaker_name.to_string(),
-                        content: turn.content.clone(),
-                    };
-
-                    // Call the streaming callback if provided
-                    if let Some(ref callback) = on_turn {
-                        callback(&message);
+                    previous_speaker = Some(self.speaker_id(speaker_name, &turns).await);
+                    for (_, message) in turns {
+                        // Call the streaming callback if provided
+                        if let Some(ref callback) = on_turn {
+                            callback(&message);
+                        }
+                        messages.push(message);
                     }
-
-                    messages.push(message);
+                    thinking.finish(speaker_name, on_turn.as_ref());
                 }
                 Err(e) => {
                     // Log the error for debugging
                     tracing::error!("[DIALOGUE] Agent execution failed: {}", e);
+                    self.round_failures.write().await.push(FailureKind::of(&e));
 
                     // Create a user-friendly error message
-                    let error_msg = format!("{}\n\nPlease check the logs for more details.", e);
+                    let failure = self.turn_failure(&e);
+                    let error_msg = failure.message(&e);
 
-                    // Emit error as a system message via callback if provided
+                    // Emit the error with its retry hint via callback if provided
                     if let Some(ref callback) = on_turn {
                         let error_turn = DialogueMessage {
                             session_id: self.session_id.clone(),
-                            author: String::new(), // Empty author for error messages
+                            author: String::new(),
                             content: error_msg.clone(),
+                            failure: Some(failure),
+                            thinking: None,
+                            heartbeat: None,
+                            turn_stats: None,
                         };
                         callback(&error_turn);
                     }
+                    thinking.finish_all(on_turn.as_ref());
 
-                    // Add error to history for persistence with metadata
-                    let error_history = ConversationMessage {
-                        role: MessageRole::System,
-                        content: error_msg.clone(),
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        metadata: MessageMetadata {
-                            system_event_type: None,
-                            error_severity: Some(ErrorSeverity::Critical),
-                            system_message_type: None,
-                            include_in_dialogue: true,
-                            llm_debug_info: None,
-                        },
-                        attachments: vec![],
-                    };
-                    self.persona_histories
-                        .write()
-                        .await
-                        .entry("Error".to_string())
-                        .or_insert_with(Vec::new)
-                        .push(error_history);
+                    self.record_error(&error_msg).await;
 
                     // Return empty dialogue messages (error already streamed via callback)
                     return InteractionResult::NewDialogueMessages(Vec::new());
                 }
             }
         }
+        thinking.finish_all(on_turn.as_ref());
+
+        // The latest plan proposed this round waits for the user's confirmation
+        if *self.plan_detection.read().await
+            && let Some(plan) = messages
+                .iter()
+                .rev()
+                .find_map(|message| plan_block::extract_plan(&message.content))
+        {
+            return InteractionResult::ModeChanged(AppMode::AwaitingConfirmation { plan });
+        }
 
         InteractionResult::NewDialogueMessages(messages)
     }
@@ -1820,7 +4528,9 @@ impl InteractionManager {
     /// * `initial_input` - The user's initial input to start the auto-chat
     /// * `file_paths` - Optional list of file paths to attach (only for initial input)
     /// * `on_turn` - Callback function called for each dialogue turn as it becomes available
-    /// * `cancel_flag` - Optional atomic flag to check for cancellation
+    /// * `on_progress` - Callback called after each iteration with its number and the delay
+    ///   before the next one (zero after the last)
+    /// * `cancel_flag` - Optional atomic flag to check for cancellation, also during the delay
     ///
     /// # Returns
     ///
@@ -1831,15 +4541,20 @@ impl InteractionManager {
     /// - Iteration 1: Uses `initial_input` from the user
     /// - Iteration 2+: Uses empty string (agents continue discussion based on context)
     /// - Stops when: max_iterations reached OR user calls stop (via set_auto_chat_iteration(None)) OR cancel_flag is set
-    pub async fn execute_auto_chat<F>(
+    ///   OR an agent says one of the configured `stop_phrases`
+    /// - Once done, a completion message is persisted; a run that ended before `max_iterations`
+    ///   is reported as stopped early, with the reason (cancelled or stop phrase)
+    pub async fn execute_auto_chat<F, P>(
         &self,
         initial_input: &str,
         file_paths: Option<Vec<String>>,
         on_turn: F,
+        on_progress: P,
         cancel_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
     ) -> InteractionResult
     where
         F: Fn(&DialogueMessage),
+        P: Fn(i32, std::time::Duration),
     {
         // Get AutoChat configuration
         let config = match self.get_auto_chat_config().await {
@@ -1861,8 +4576,10 @@ impl InteractionManager {
         // Set initial iteration
         self.set_auto_chat_iteration(Some(0)).await;
 
+        let mut pacer = AutoChatPacer::new(&config);
         let mut current_iteration = 0;
         let mut last_result = InteractionResult::NoOp;
+        let mut stop = AutoChatStop::Finished;
 
         while current_iteration < config.max_iterations {
             // Check cancellation flag
@@ -1870,12 +4587,14 @@ impl InteractionManager {
                 && flag.load(std::sync::atomic::Ordering::SeqCst)
             {
                 tracing::info!("[AutoChat] Cancelled by user");
+                stop = AutoChatStop::Cancelled;
                 break;
             }
 
             // Check if user manually stopped (set_auto_chat_iteration(None))
             if self.get_auto_chat_iteration().await.is_none() {
                 tracing::info!("[AutoChat] Manually stopped by user");
+                stop = AutoChatStop::Cancelled;
                 break;
             }
 
@@ -1890,6 +4609,7 @@ impl InteractionManager {
             );
 
             // Execute dialogue iteration
+            let iteration_started = tokio::time::Instant::now();
             if current_iteration == 1 {
                 // First iteration: use user's actual input
                 last_result = self
@@ -1908,8 +4628,42 @@ impl InteractionManager {
                     .await;
             }
 
-            // Optional: Add delay between iterations to avoid overwhelming the UI
-            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+            // Stop if any agent said a configured stop phrase during this iteration
+            let stop_phrase_hit = match last_result {
+                InteractionResult::NewDialogueMessages(ref messages) => {
+                    messages.iter().find_map(|m| {
+                        config
+                            .matching_stop_phrase(&m.content)
+                            .map(|phrase| (phrase.to_string(), m.author.clone()))
+                    })
+                }
+                _ => None,
+            };
+            if let Some((phrase, author)) = stop_phrase_hit {
+                tracing::info!(
+                    "[AutoChat] Stop phrase \"{}\" said by {}, stopping",
+                    phrase,
+                    author
+                );
+                stop = AutoChatStop::StopPhrase { phrase, author };
+                break;
+            }
+
+            // Pace iterations to stay within backend rate limits
+            let delay = if current_iteration < config.max_iterations {
+                let failures = self.round_failures.read().await.clone();
+                pacer.next_delay(iteration_started.elapsed(), &failures)
+            } else {
+                std::time::Duration::ZERO
+            };
+            on_progress(current_iteration, delay);
+            if !delay.is_zero()
+                && auto_chat_pacing::sleep_unless_cancelled(delay, cancel_flag.as_deref()).await
+            {
+                tracing::info!("[AutoChat] Cancelled by user during delay");
+                stop = AutoChatStop::Cancelled;
+                break;
+            }
 
             // For user_interrupt mode, check if iteration counter was cleared
             if matches!(
@@ -1918,6 +4672,7 @@ impl InteractionManager {
             ) && self.get_auto_chat_iteration().await.is_none()
             {
                 tracing::info!("[AutoChat] User interrupt detected");
+                stop = AutoChatStop::Cancelled;
                 break;
             }
         }
@@ -1926,15 +4681,14 @@ impl InteractionManager {
         self.set_auto_chat_iteration(None).await;
 
         tracing::info!(
-            "[AutoChat] Completed after {} iterations",
-            current_iteration
+            "[AutoChat] Ended after {}/{} iterations ({:?})",
+            current_iteration,
+            config.max_iterations,
+            stop
         );
 
         // Persist AutoChat completion message to session history
-        let completion_content = format!(
-            "✅ AutoChat completed after {} iterations.",
-            current_iteration
-        );
+        let completion_content = stop.completion_message(current_iteration, config.max_iterations);
         self.add_system_conversation_message(
             completion_content,
             Some("auto_chat_completion".to_string()),
@@ -1945,6 +4699,255 @@ impl InteractionManager {
         last_result
     }
 
+    /// Runs a structured vote among the active participants.
+    ///
+    /// Every participant answers the poll once (in participant order, without
+    /// seeing each other's votes) and is asked to reply with exactly one option
+    /// plus a one-sentence rationale. Replies are stored as normal assistant
+    /// messages; the tally is stored as a System message with message type
+    /// `poll_result`. Everything is streamed through `on_turn`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the poll is malformed or the dialogue cannot be initialized.
+    pub async fn run_poll<F>(
+        &self,
+        question: &str,
+        options: Vec<String>,
+        on_turn: Option<F>,
+    ) -> Result<poll::PollResult, String>
+    where
+        F: Fn(&DialogueMessage),
+    {
+        let question = question.trim();
+        let options: Vec<String> = options
+            .iter()
+            .map(|o| o.trim().to_string())
+            .filter(|o| !o.is_empty())
+            .collect();
+        poll::validate_poll(question, &options)?;
+
+        self.ensure_dialogue_initialized().await?;
+
+        let prompt = poll::poll_prompt(question, &options);
+        self.add_system_conversation_message(prompt.clone(), Some("system".to_string()), None)
+            .await;
+        if let Some(ref callback) = on_turn {
+            callback(&DialogueMessage {
+                session_id: self.session_id.clone(),
+                author: "System".to_string(),
+                content: prompt.clone(),
+                failure: None,
+                thinking: None,
+                heartbeat: None,
+                turn_stats: None,
+            });
+        }
+
+        let mut votes = Vec::new();
+        {
+            let mut dialogue_guard = self.dialogue.lock().await;
+            let dialogue = dialogue_guard.as_mut().ok_or(
+                "Dialogue was invalidated during initialization (possible race condition)",
+            )?;
+
+            let payload = Payload::new().with_message(Speaker::System, prompt.as_str());
+            let mut thinking = self
+                .thinking_signal
+                .begin_round(&self.session_id, self.cli_heartbeat_interval().await);
+            let mut session =
+                dialogue.partial_session_with_order(payload, BroadcastOrder::ParticipantOrder);
+            self.raw_cli_outputs.write().await.clear();
+            self.turn_stats.write().await.clear();
+
+            while let Some(result) = thinking.next_turn(&mut session, on_turn.as_ref()).await {
+                let turn = match result {
+                    Ok(turn) => turn,
+                    Err(e) => {
+                        // A failed voter is simply not counted; the others still vote
+                        tracing::error!("[Poll] Agent execution failed: {}", e);
+                        continue;
+                    }
+                };
+                let speaker_name = turn.speaker.name().to_string();
+                // Votes are never split: each reply counts as its author's ballot
+                let (_, message) = self.commit_agent_turn(&speaker_name, &turn.content).await;
+                if let Some(ref callback) = on_turn {
+                    callback(&message);
+                }
+                thinking.finish(&speaker_name, on_turn.as_ref());
+
+                votes.push(poll::PollVote {
+                    voter: speaker_name,
+                    choice: poll::parse_vote(&turn.content, &options),
+                    response: turn.content,
+                });
+            }
+            thinking.finish_all(on_turn.as_ref());
+        }
+
+        let result = poll::PollResult::tally(question, &options, votes);
+        let summary = result.summary();
+        self.add_system_conversation_message(
+            summary.clone(),
+            Some("poll_result".to_string()),
+            None,
+        )
+        .await;
+        if let Some(ref callback) = on_turn {
+            callback(&DialogueMessage {
+                session_id: self.session_id.clone(),
+                author: "System".to_string(),
+                content: summary,
+                failure: None,
+                thinking: None,
+                heartbeat: None,
+                turn_stats: None,
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Runs one round in which the given participants each answer `prompt` once.
+    ///
+    /// `participants` are persona IDs or names of active participants; an
+    /// empty list addresses all of them. The prompt is stored as a System
+    /// message and the replies as normal assistant messages, all streamed
+    /// through `on_turn`. A targeted round runs in a dialogue of its own; the
+    /// session dialogue is rebuilt afterwards so everyone sees the replies.
+    ///
+    /// Failed turns are recorded as errors and skipped. Once `cancel_flag` is
+    /// set, the round stops after the current turn.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dialogue cannot be initialized or none of the
+    /// listed participants is active.
+    pub async fn run_targeted_round<F>(
+        &self,
+        participants: &[String],
+        prompt: &str,
+        cancel_flag: Option<&AtomicBool>,
+        on_turn: Option<F>,
+    ) -> Result<Vec<DialogueMessage>, String>
+    where
+        F: Fn(&DialogueMessage),
+    {
+        self.ensure_dialogue_initialized().await?;
+
+        let mut targeted = if participants.is_empty() {
+            None
+        } else {
+            let runtime = self.runtime_caps().await;
+            let default_models = self.default_models.read().await.clone();
+            let dialogue = self
+                .build_dialogue_for(&runtime, Some(participants), |persona, keep_history| {
+                    self.create_agent(persona, &runtime, &default_models, keep_history)
+                })
+                .await?;
+            if dialogue.participants().is_empty() {
+                return Err(format!(
+                    "None of {} is an active participant",
+                    participants.join(", ")
+                ));
+            }
+            Some(dialogue)
+        };
+
+        self.add_system_conversation_message(prompt.to_string(), Some("system".to_string()), None)
+            .await;
+        if let Some(ref callback) = on_turn {
+            callback(&DialogueMessage {
+                session_id: self.session_id.clone(),
+                author: "System".to_string(),
+                content: prompt.to_string(),
+                failure: None,
+                thinking: None,
+                heartbeat: None,
+                turn_stats: None,
+            });
+        }
+
+        let mut replies = Vec::new();
+        {
+            let mut dialogue_guard = self.dialogue.lock().await;
+            let dialogue = match targeted.as_mut() {
+                Some(dialogue) => dialogue,
+                None => dialogue_guard.as_mut().ok_or(
+                    "Dialogue was invalidated during initialization (possible race condition)",
+                )?,
+            };
+            let participant_names: Vec<String> = dialogue
+                .participants()
+                .iter()
+                .map(|persona| persona.name.clone())
+                .collect();
+
+            let payload = Payload::new().with_message(Speaker::System, prompt);
+            let mut thinking = self
+                .thinking_signal
+                .begin_round(&self.session_id, self.cli_heartbeat_interval().await);
+            let mut session =
+                dialogue.partial_session_with_order(payload, BroadcastOrder::ParticipantOrder);
+            self.round_failures.write().await.clear();
+            self.raw_cli_outputs.write().await.clear();
+            self.turn_stats.write().await.clear();
+
+            while let Some(result) = thinking.next_turn(&mut session, on_turn.as_ref()).await {
+                match result {
+                    Ok(turn) => {
+                        let speaker_name = turn.speaker.name();
+                        let turns = self
+                            .commit_agent_turns(
+                                speaker_name,
+                                &turn.content,
+                                &participant_names,
+                                None,
+                            )
+                            .await;
+                        for (_, message) in turns {
+                            if let Some(ref callback) = on_turn {
+                                callback(&message);
+                            }
+                            replies.push(message);
+                        }
+                        thinking.finish(speaker_name, on_turn.as_ref());
+                    }
+                    Err(e) => {
+                        // A failed participant is skipped; the others still answer
+                        tracing::error!("[TargetedRound] Agent execution failed: {}", e);
+                        self.round_failures.write().await.push(FailureKind::of(&e));
+                        let failure = self.turn_failure(&e);
+                        let error_msg = failure.message(&e);
+                        if let Some(ref callback) = on_turn {
+                            callback(&DialogueMessage {
+                                session_id: self.session_id.clone(),
+                                author: String::new(),
+                                content: error_msg.clone(),
+                                failure: Some(failure),
+                                thinking: None,
+                                heartbeat: None,
+                                turn_stats: None,
+                            });
+                        }
+                        self.record_error(&error_msg).await;
+                    }
+                }
+                if cancel_flag.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+                    tracing::info!("[TargetedRound] Cancelled by user");
+                    break;
+                }
+            }
+            thinking.finish_all(on_turn.as_ref());
+        }
+
+        if targeted.is_some() {
+            self.invalidate_dialogue().await;
+        }
+        Ok(replies)
+    }
+
     /// Handles input when awaiting plan confirmation.
     fn handle_awaiting_confirmation(&self, input: &str, plan: &Plan) -> InteractionResult {
         let trimmed = input.trim().to_lowercase();
@@ -1958,6 +4961,36 @@ impl InteractionManager {
         }
     }
 
+    /// Records a failed agent turn as a critical system message, so it is
+    /// persisted and stays in the dialogue history.
+    async fn record_error(&self, error_msg: &str) {
+        self.system_messages
+            .write()
+            .await
+            .push(ConversationMessage {
+                role: MessageRole::System,
+                content: error_msg.to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                metadata: MessageMetadata {
+                    system_event_type: None,
+                    error_severity: Some(ErrorSeverity::Critical),
+                    system_message_type: None,
+                    include_in_dialogue: true,
+                    llm_debug_info: None,
+                    recovered_partial: false,
+                    pinned: false,
+                    split_from_single_response: false,
+                    spawned_task_ids: Vec::new(),
+                    in_response_to: None,
+                    source: None,
+                    sources: Vec::new(),
+                    full_content_path: None,
+                    turn_stats: None,
+                },
+                attachments: vec![],
+            });
+    }
+
     /// Adds a message to the conversation history.
     async fn add_to_history(
         &self,
@@ -1965,6 +4998,26 @@ impl InteractionManager {
         role: MessageRole,
         content: &str,
         attachments: Option<Vec<String>>,
+    ) {
+        // User/Assistant messages with default metadata
+        self.add_to_history_with_metadata(
+            persona_id,
+            role,
+            content,
+            attachments,
+            MessageMetadata::default(),
+        )
+        .await;
+    }
+
+    /// Adds a message with `metadata` to the history of `persona_id`.
+    async fn add_to_history_with_metadata(
+        &self,
+        persona_id: &str,
+        role: MessageRole,
+        content: &str,
+        attachments: Option<Vec<String>>,
+        metadata: MessageMetadata,
     ) {
         let mut histories = self.persona_histories.write().await;
         let history = histories
@@ -1974,8 +5027,8 @@ impl InteractionManager {
         history.push(ConversationMessage {
             role,
             content: content.to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-            metadata: MessageMetadata::default(), // User/Assistant messages with default metadata
+            timestamp: self.clock.now().to_rfc3339(),
+            metadata,
             attachments: attachments.unwrap_or_default(),
         });
     }
@@ -2005,3 +5058,41 @@ impl orcs_core::session::InteractionManagerTrait for InteractionManager {
         self.set_workspace_id(workspace_id, workspace_root).await
     }
 }
+
+/// Why an AutoChat run ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AutoChatStop {
+    /// The loop ran until `max_iterations`.
+    Finished,
+    /// The user cancelled or stopped the run.
+    Cancelled,
+    /// An agent said one of the configured stop phrases.
+    StopPhrase { phrase: String, author: String },
+}
+
+impl AutoChatStop {
+    /// Completion message persisted once the run ends.
+    ///
+    /// A run that ended before `max_iterations` is reported as stopped early,
+    /// with the reason; otherwise it is reported as completed.
+    fn completion_message(&self, iterations: i32, max_iterations: i32) -> String {
+        let early = iterations < max_iterations;
+        match self {
+            Self::StopPhrase { phrase, author } if early => format!(
+                "⏹️ AutoChat stopped early after {}/{} iterations (stop phrase \"{}\" said by {}).",
+                iterations, max_iterations, phrase, author
+            ),
+            Self::StopPhrase { phrase, author } => format!(
+                "✅ AutoChat completed after {} iterations (stop phrase \"{}\" said by {}).",
+                iterations, phrase, author
+            ),
+            Self::Cancelled if early => format!(
+                "⏹️ AutoChat stopped early after {}/{} iterations (cancelled).",
+                iterations, max_iterations
+            ),
+            Self::Cancelled | Self::Finished => {
+                format!("✅ AutoChat completed after {} iterations.", iterations)
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Special mentions for Mentioned routing.
+//!
+//! `@everyone` addresses every participant and `@here` the participants that
+//! spoke within the activity window. Both are expanded to explicit `@Name`
+//! mentions before the dialogue resolves who responds; any other unknown
+//! `@token` is left for the dialogue to ignore.
+//!
+//! [`mentioned_names`] finds explicit mentions, e.g. of participants that sit
+//! out while focus mode is on.
+
+/// Mention addressing all participants.
+pub const EVERYONE: &str = "everyone";
+
+/// Mention addressing the currently active participants.
+pub const HERE: &str = "here";
+
+/// Characters that end a mention, matching the dialogue's mention parser.
+const MENTION_DELIMITERS: &str = "@,.!?;:()[]{}<>\"'`/\\|";
+
+fn ends_mention(c: char) -> bool {
+    c.is_whitespace() || MENTION_DELIMITERS.contains(c)
+}
+
+/// Replaces `@everyone` and `@here` in `text` with mentions of the targeted
+/// participants.
+///
+/// `@here` is kept as is when no participant is active, so it resolves to no
+/// one rather than to everyone.
+pub fn expand_special_mentions(text: &str, participants: &[String], active: &[String]) -> String {
+    let mention_all = |names: &[String]| {
+        names
+            .iter()
+            .map(|name| format!("@{}", name))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('@') {
+        expanded.push_str(&rest[..at]);
+        let after = &rest[at + 1..];
+        let end = after.find(ends_mention).unwrap_or(after.len());
+        let token = &after[..end];
+        let targets = match token {
+            EVERYONE => participants,
+            HERE => active,
+            _ => &[],
+        };
+        if targets.is_empty() {
+            expanded.push('@');
+            expanded.push_str(token);
+        } else {
+            expanded.push_str(&mention_all(targets));
+        }
+        rest = &after[end..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Returns the names in `candidates` that `text` mentions as `@Name`, in
+/// order of first mention.
+pub fn mentioned_names(text: &str, candidates: &[String]) -> Vec<String> {
+    let mut mentioned: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(at) = rest.find('@') {
+        let after = &rest[at + 1..];
+        let end = after.find(ends_mention).unwrap_or(after.len());
+        let token = &after[..end];
+        if let Some(name) = candidates.iter().find(|name| *name == token)
+            && !mentioned.contains(name)
+        {
+            mentioned.push(name.clone());
+        }
+        rest = &after[end..];
+    }
+    mentioned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_expands_everyone_and_here() {
+        let participants = names(&["Mai", "Yui", "Rin"]);
+        let active = names(&["Yui"]);
+
+        assert_eq!(
+            expand_special_mentions("@everyone thoughts?", &participants, &active),
+            "@Mai @Yui @Rin thoughts?"
+        );
+        assert_eq!(
+            expand_special_mentions("@here, any update?", &participants, &active),
+            "@Yui, any update?"
+        );
+    }
+
+    #[test]
+    fn test_leaves_other_tokens_untouched() {
+        let participants = names(&["Mai"]);
+
+        assert_eq!(
+            expand_special_mentions(
+                "mail me@example.com or ask @nobody or @everyoneelse @",
+                &participants,
+                &[]
+            ),
+            "mail me@example.com or ask @nobody or @everyoneelse @"
+        );
+        assert_eq!(
+            expand_special_mentions("@here anyone?", &participants, &[]),
+            "@here anyone?"
+        );
+    }
+
+    #[test]
+    fn test_finds_mentioned_candidates() {
+        let candidates = names(&["Mai", "Rin"]);
+
+        assert_eq!(
+            mentioned_names("@Rin, and @Mai? @Rin again", &candidates),
+            names(&["Rin", "Mai"])
+        );
+        assert!(mentioned_names("Rin @Yui @Mail", &candidates).is_empty());
+    }
+}
@@ -3,19 +3,20 @@
 //! This agent calls the OpenAI Chat Completions API directly without CLI dependency.
 //! Configuration priority: secret.json > environment variables
 
+use crate::turn_stats::{TokenUsage, UsageSink};
 use async_trait::async_trait;
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use llm_toolkit::agent::{Agent, AgentError, Payload};
 use llm_toolkit::attachment::Attachment;
 use orcs_core::secret::SecretService;
-use orcs_infrastructure::SecretServiceImpl;
+use orcs_infrastructure::{SecretServiceImpl, WireTap};
 use reqwest::{Client, StatusCode, header::HeaderValue};
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::time::Duration;
 
-const DEFAULT_OPENAI_MODEL: &str = "gpt-5";
+pub(crate) const DEFAULT_OPENAI_MODEL: &str = "gpt-5";
 const BASE_URL: &str = "https://api.openai.com/v1/chat/completions";
 
 /// Agent implementation that talks to the OpenAI HTTP API.
@@ -25,6 +26,9 @@ pub struct OpenAIApiAgent {
     api_key: String,
     model: String,
     max_tokens: Option<u32>,
+    seed: Option<u64>,
+    wire_tap: Option<WireTap>,
+    usage_sink: Option<UsageSink>,
 }
 
 impl OpenAIApiAgent {
@@ -35,6 +39,9 @@ impl OpenAIApiAgent {
             api_key: api_key.into(),
             model: model.into(),
             max_tokens: None,
+            seed: None,
+            wire_tap: None,
+            usage_sink: None,
         }
     }
 
@@ -67,6 +74,12 @@ impl OpenAIApiAgent {
         Ok(Self::new(api_key, model))
     }
 
+    /// Replaces the HTTP client (e.g. one configured with proxy settings).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Overrides the model after construction.
     pub fn with_model(mut self, model: impl Into<String>) -> Self {
         self.model = model.into();
@@ -79,6 +92,40 @@ impl OpenAIApiAgent {
         self
     }
 
+    /// Sets the sampling seed for best-effort reproducible responses.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Records each request and its raw response to the session's wire log.
+    pub fn with_wire_tap(mut self, tap: WireTap) -> Self {
+        self.wire_tap = Some(tap.with_secret(self.api_key.clone()));
+        self
+    }
+
+    /// Reports the token usage of each response to `sink`.
+    pub fn with_usage_sink(mut self, sink: UsageSink) -> Self {
+        self.usage_sink = Some(sink);
+        self
+    }
+
+    fn record_usage(&self, input_tokens: u64, output_tokens: u64) {
+        if let Some(sink) = &self.usage_sink {
+            sink.record(TokenUsage {
+                model: self.model.clone(),
+                input_tokens,
+                output_tokens,
+            });
+        }
+    }
+
+    fn record_exchange(&self, body: &ChatCompletionRequest, outcome: Result<&str, &str>) {
+        if let Some(tap) = &self.wire_tap {
+            tap.record_json(BASE_URL, body, outcome);
+        }
+    }
+
     async fn build_messages(&self, payload: &Payload) -> Result<Vec<ChatMessage>, AgentError> {
         let mut content_parts = Vec::new();
 
@@ -139,6 +186,15 @@ impl OpenAIApiAgent {
         }))
     }
 
+    async fn build_request(&self, payload: &Payload) -> Result<ChatCompletionRequest, AgentError> {
+        Ok(ChatCompletionRequest {
+            model: self.model.clone(),
+            messages: self.build_messages(payload).await?,
+            max_tokens: self.max_tokens,
+            seed: self.seed,
+        })
+    }
+
     async fn send_request(&self, body: &ChatCompletionRequest) -> Result<String, AgentError> {
         let response = self
             .client
@@ -153,7 +209,8 @@ impl OpenAIApiAgent {
                 message: format!("OpenAI API request failed: {err}"),
                 is_retryable: err.is_connect() || err.is_timeout(),
                 retry_after: None,
-            })?;
+            })
+            .inspect_err(|err| self.record_exchange(body, Err(&err.to_string())))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -162,13 +219,21 @@ impl OpenAIApiAgent {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Failed to read OpenAI error body".to_string());
+            self.record_exchange(body, Err(&body_text));
             return Err(map_http_error(status, body_text, retry_after));
         }
 
-        let parsed: ChatCompletionResponse = response
-            .json()
+        let response_text = response
+            .text()
             .await
+            .map_err(|err| AgentError::Other(format!("Failed to read OpenAI response: {err}")))
+            .inspect_err(|err| self.record_exchange(body, Err(&err.to_string())))?;
+        self.record_exchange(body, Ok(&response_text));
+        let parsed: ChatCompletionResponse = serde_json::from_str(&response_text)
             .map_err(|err| AgentError::Other(format!("Failed to parse OpenAI response: {err}")))?;
+        if let Some(usage) = &parsed.usage {
+            self.record_usage(usage.prompt_tokens, usage.completion_tokens);
+        }
 
         extract_text_response(parsed)
     }
@@ -188,14 +253,7 @@ impl Agent for OpenAIApiAgent {
     }
 
     async fn execute(&self, payload: Payload) -> Result<Self::Output, AgentError> {
-        let messages = self.build_messages(&payload).await?;
-
-        let request = ChatCompletionRequest {
-            model: self.model.clone(),
-            messages,
-            max_tokens: self.max_tokens,
-        };
-
+        let request = self.build_request(&payload).await?;
         self.send_request(&request).await
     }
 }
@@ -206,6 +264,8 @@ struct ChatCompletionRequest {
     messages: Vec<ChatMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -252,6 +312,13 @@ struct ImageUrl {
 #[derive(Deserialize)]
 struct ChatCompletionResponse {
     choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct Usage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
 }
 
 #[derive(Deserialize)]
@@ -324,3 +391,22 @@ fn parse_retry_after(header: Option<&HeaderValue>) -> Option<Duration> {
     // Retry-After HTTP-date parsing is omitted for simplicity
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn request_body(agent: &OpenAIApiAgent) -> serde_json::Value {
+        let request = agent.build_request(&Payload::text("hello")).await.unwrap();
+        serde_json::to_value(&request).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_seed_is_sent_in_the_request_body() {
+        let agent = OpenAIApiAgent::new("key", "gpt-5").with_seed(42);
+        assert_eq!(request_body(&agent).await["seed"], 42);
+
+        let unseeded = OpenAIApiAgent::new("key", "gpt-5");
+        assert!(request_body(&unseeded).await.get("seed").is_none());
+    }
+}
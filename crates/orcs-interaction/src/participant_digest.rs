@@ -0,0 +1,250 @@
+//! Participant digests for personas that have been quiet for a while.
+//!
+//! Every persona's agent is wrapped in a [`ParticipantDigestAgent`], which
+//! replaces the descriptions of participants outside the session's
+//! [`ParticipantActivity`] window with a short placeholder, keeping long
+//! rosters from crowding the prompt.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use llm_toolkit::agent::dialogue::ParticipantInfo;
+use llm_toolkit::agent::{Agent, AgentError, Payload, PayloadContent};
+use tokio::sync::RwLock;
+
+use crate::rebuild_payload;
+
+/// Participant description shown in place of the full one for quiet personas.
+const INACTIVE_PARTICIPANT_DESCRIPTION: &str = "(inactive recently)";
+
+/// Speakers of the most recent dialogue turns, oldest first.
+#[derive(Debug, Clone)]
+pub(crate) struct ParticipantActivity {
+    /// Number of turns a persona stays "active" after speaking (0 disables digesting)
+    window: usize,
+    speakers: VecDeque<String>,
+}
+
+impl ParticipantActivity {
+    pub(crate) fn new(window: usize) -> Self {
+        Self {
+            window,
+            speakers: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn set_window(&mut self, window: usize) {
+        self.window = window;
+        self.trim();
+    }
+
+    /// Records the speaker of a new turn.
+    pub(crate) fn record(&mut self, speaker: &str) {
+        self.speakers.push_back(speaker.to_string());
+        self.trim();
+    }
+
+    /// Replaces the recorded turns, e.g. when the dialogue is rebuilt from history.
+    pub(crate) fn reset(&mut self, speakers: impl IntoIterator<Item = String>) {
+        self.speakers = speakers.into_iter().collect();
+        self.trim();
+    }
+
+    pub(crate) fn spoke_recently(&self, name: &str) -> bool {
+        self.speakers.iter().any(|speaker| speaker == name)
+    }
+
+    fn trim(&mut self) {
+        let excess = self.speakers.len().saturating_sub(self.window);
+        self.speakers.drain(..excess);
+    }
+}
+
+/// Shortens the participants block to one-line descriptors for personas that
+/// have been quiet for a while.
+///
+/// A persona keeps its full entry while it spoke within the activity window,
+/// speaks or is mentioned in the current payload, or is the receiving persona.
+pub(crate) struct ParticipantDigestAgent {
+    pub(crate) inner: Box<dyn Agent<Output = String, Expertise = String>>,
+    pub(crate) persona_name: String,
+    pub(crate) activity: Arc<RwLock<ParticipantActivity>>,
+}
+
+#[async_trait::async_trait]
+impl Agent for ParticipantDigestAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        self.inner.expertise()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn capabilities(&self) -> Option<Vec<llm_toolkit::agent::Capability>> {
+        self.inner.capabilities()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        let payload = {
+            let activity = self.activity.read().await;
+            if activity.window == 0 || payload.participants().is_none() {
+                payload
+            } else {
+                digest_participants(&payload, &self.persona_name, &activity)
+            }
+        };
+        self.inner.execute(payload).await
+    }
+
+    async fn is_available(&self) -> Result<(), AgentError> {
+        self.inner.is_available().await
+    }
+}
+
+/// Rewrites the participants of `payload`, compressing those inactive recently.
+fn digest_participants(
+    payload: &Payload,
+    self_name: &str,
+    activity: &ParticipantActivity,
+) -> Payload {
+    let messages = payload.to_messages();
+    let current_text = payload.to_text();
+    let is_active = |name: &str| {
+        name == self_name
+            || activity.spoke_recently(name)
+            || current_text.contains(name)
+            || messages
+                .iter()
+                .any(|m| m.speaker.name() == name || m.content.contains(name))
+    };
+
+    rebuild_payload(payload, |content| match content {
+        PayloadContent::Participants(participants) => Some(PayloadContent::Participants(
+            participants
+                .into_iter()
+                .map(|participant| {
+                    if is_active(&participant.name) {
+                        participant
+                    } else {
+                        ParticipantInfo {
+                            description: INACTIVE_PARTICIPANT_DESCRIPTION.to_string(),
+                            capabilities: None,
+                            ..participant
+                        }
+                    }
+                })
+                .collect(),
+        )),
+        content => Some(content),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_PARTICIPANT_ACTIVITY_WINDOW;
+    use llm_toolkit::agent::dialogue::Speaker;
+    use llm_toolkit::agent::persona::{Persona as LlmPersona, PersonaAgent};
+
+    /// Agent that records the rendered prompt text it receives.
+    struct PromptSpy {
+        expertise: String,
+        prompts: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Agent for PromptSpy {
+        type Output = String;
+        type Expertise = String;
+
+        fn expertise(&self) -> &String {
+            &self.expertise
+        }
+
+        async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+            self.prompts.lock().unwrap().push(payload.to_text());
+            Ok("ok".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inactive_participants_are_digested() {
+        let names = ["Aoi", "Ren", "Mai", "Kai", "Sora", "Yuki", "Hana", "Riku"];
+        let participants: Vec<ParticipantInfo> = names
+            .iter()
+            .map(|name| {
+                ParticipantInfo::new(
+                    *name,
+                    "Engineer",
+                    format!(
+                        "{} has spent a decade on distributed systems, reviews every design \
+                         for failure modes, and prefers small incremental changes.",
+                        name
+                    ),
+                )
+            })
+            .collect();
+        let payload = Payload::new().with_participants(participants).with_message(
+            Speaker::user("You", "User"),
+            "@Mai can you check the retry logic?",
+        );
+
+        let mut activity = ParticipantActivity::new(DEFAULT_PARTICIPANT_ACTIVITY_WINDOW);
+        // Kai spoke long ago and has since fallen out of the window
+        activity.record("Kai");
+        for _ in 0..DEFAULT_PARTICIPANT_ACTIVITY_WINDOW {
+            activity.record("You");
+            activity.record("Ren");
+        }
+        let activity = Arc::new(RwLock::new(activity));
+
+        let prompts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let persona_agent = || {
+            Box::new(PersonaAgent::new(
+                PromptSpy {
+                    expertise: "Engineer".to_string(),
+                    prompts: prompts.clone(),
+                },
+                LlmPersona::new("Aoi", "Engineer"),
+            )) as Box<dyn Agent<Output = String, Expertise = String>>
+        };
+
+        persona_agent().execute(payload.clone()).await.unwrap();
+        ParticipantDigestAgent {
+            inner: persona_agent(),
+            persona_name: "Aoi".to_string(),
+            activity,
+        }
+        .execute(payload)
+        .await
+        .unwrap();
+
+        let prompts = prompts.lock().unwrap();
+        let (full, digested) = (&prompts[0], &prompts[1]);
+        assert!(
+            digested.len() < full.len(),
+            "digest should shrink the prompt ({} >= {} bytes)",
+            digested.len(),
+            full.len()
+        );
+        // Self, recent speakers and mentioned personas keep their full entry
+        for name in ["Aoi", "Ren", "Mai"] {
+            assert!(digested.contains(&format!("{} has spent a decade", name)));
+        }
+        for name in ["Kai", "Sora", "Yuki", "Hana", "Riku"] {
+            assert!(!digested.contains(&format!("{} has spent a decade", name)));
+            assert!(digested.lines().any(|line| {
+                line.starts_with(&format!("- **{}", name))
+                    && line.ends_with(&format!("Engineer: {}", INACTIVE_PARTICIPANT_DESCRIPTION))
+            }));
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Long user pastes sent as attachments.
+//!
+//! A log, stack trace or data dump pasted into the input would fill the
+//! context of every participant on every turn. When a paste is longer than
+//! the session's threshold and does not read as prose, its full text is
+//! stored as a workspace file and attached to the message, whose content
+//! becomes a short excerpt: a preamble naming the file, then the first and
+//! last [`EXCERPT_LINES`] lines.
+
+use chrono::{DateTime, Utc};
+
+/// Lines kept from each end of a paste in its excerpt.
+pub const EXCERPT_LINES: usize = 20;
+
+/// Largest share of symbol characters (digits included) in prose.
+const MAX_PROSE_SYMBOL_RATIO: f64 = 0.05;
+
+/// Smallest average length of the non-blank lines of prose, whose
+/// paragraphs are long lines, unlike code, logs and tables.
+const MIN_PROSE_LINE_CHARS: usize = 60;
+
+/// Excerpt lines longer than this are cut.
+const MAX_EXCERPT_LINE_CHARS: usize = 200;
+
+/// Punctuation prose uses freely; it does not count as symbols.
+const PROSE_PUNCTUATION: &[char] = &[
+    '.', ',', ';', ':', '!', '?', '\'', '"', '-', '(', ')', '。', '、', '「', '」', '・', '’', '“',
+    '”', '—',
+];
+
+/// Whether `text` reads as prose rather than code, logs or data.
+///
+/// Prose has at most 5% symbol characters (anything but letters, whitespace
+/// and [`PROSE_PUNCTUATION`]) and its non-blank lines average at least 60
+/// characters. Empty text counts as prose.
+pub fn looks_like_prose(text: &str) -> bool {
+    let mut visible = 0usize;
+    let mut symbols = 0usize;
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        visible += 1;
+        if !c.is_alphabetic() && !PROSE_PUNCTUATION.contains(&c) {
+            symbols += 1;
+        }
+    }
+    if visible == 0 {
+        return true;
+    }
+    if symbols as f64 / visible as f64 > MAX_PROSE_SYMBOL_RATIO {
+        return false;
+    }
+
+    let (lines, chars) = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .fold((0usize, 0usize), |(lines, chars), line| {
+            (lines + 1, chars + line.trim().chars().count())
+        });
+    chars / lines >= MIN_PROSE_LINE_CHARS
+}
+
+/// Whether `input` should be stored and sent as an attachment: it is longer
+/// than `threshold` characters and does not read as prose.
+pub fn should_attach(input: &str, threshold: usize) -> bool {
+    input.chars().count() > threshold && !looks_like_prose(input)
+}
+
+/// Name of the workspace file storing a paste made at `now`.
+pub fn file_name(now: DateTime<Utc>) -> String {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!("paste-{}-{}.txt", now.format("%Y%m%d-%H%M%S"), &suffix[..8])
+}
+
+/// Builds the message content sent in place of `input`, stored as `file_name`.
+///
+/// # Example output
+///
+/// ```text
+/// [Long paste: 1200 lines, 48210 characters. The full text is attached as
+/// `paste-20250314-093000-1a2b3c4d.txt`; its first and last 20 lines follow.]
+///
+/// <first 20 lines>
+/// [... 1160 lines omitted ...]
+/// <last 20 lines>
+/// ```
+pub fn excerpt(input: &str, file_name: &str) -> String {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut excerpt = format!(
+        "[Long paste: {} lines, {} characters. The full text is attached as `{}`; \
+         its first and last {} lines follow.]\n\n",
+        lines.len(),
+        input.chars().count(),
+        file_name,
+        EXCERPT_LINES
+    );
+
+    let shown: Vec<String> = if lines.len() <= EXCERPT_LINES * 2 {
+        lines.iter().map(|line| cut_line(line)).collect()
+    } else {
+        let omitted = lines.len() - EXCERPT_LINES * 2;
+        lines[..EXCERPT_LINES]
+            .iter()
+            .map(|line| cut_line(line))
+            .chain(std::iter::once(format!(
+                "[... {} lines omitted ...]",
+                omitted
+            )))
+            .chain(
+                lines[lines.len() - EXCERPT_LINES..]
+                    .iter()
+                    .map(|line| cut_line(line)),
+            )
+            .collect()
+    };
+    excerpt.push_str(&shown.join("\n"));
+    excerpt
+}
+
+fn cut_line(line: &str) -> String {
+    match line.char_indices().nth(MAX_EXCERPT_LINE_CHARS) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// `lines` lines of `width` characters, `symbols` of which are `#`.
+    fn block(lines: usize, width: usize, symbols: usize) -> String {
+        let line = format!("{}{}", "#".repeat(symbols), "a".repeat(width - symbols));
+        vec![line; lines].join("\n")
+    }
+
+    #[test]
+    fn test_looks_like_prose_symbol_ratio_boundary() {
+        // 5 symbols in 100 visible characters is still prose, 6 is not
+        assert!(looks_like_prose(&block(10, 100, 5)));
+        assert!(!looks_like_prose(&block(10, 100, 6)));
+
+        // Digits count as symbols; prose punctuation and whitespace do not
+        let numbers = format!("{}{}", "1".repeat(6), "a".repeat(94));
+        assert!(!looks_like_prose(&numbers));
+        let sentence =
+            "Well, this is (mostly) a sentence: it ends here! Doesn't it? Yes. ".repeat(4);
+        assert!(looks_like_prose(&sentence));
+    }
+
+    #[test]
+    fn test_looks_like_prose_line_length_boundary() {
+        // Blank lines and surrounding whitespace do not shorten the average
+        assert!(looks_like_prose(&block(10, 60, 0).replace('\n', "\n\n   ")));
+        assert!(!looks_like_prose(&block(10, 59, 0)));
+
+        let code = "fn main() {\n    let x = vec![1, 2, 3];\n    println!(\"{:?}\", x);\n}\n";
+        assert!(!looks_like_prose(code));
+        assert!(looks_like_prose(""));
+    }
+
+    #[test]
+    fn test_should_attach_needs_length_and_non_prose() {
+        let log = block(100, 80, 10);
+        let length = log.chars().count();
+        assert!(should_attach(&log, length - 1));
+        assert!(!should_attach(&log, length));
+
+        let prose = block(100, 80, 0);
+        assert!(!should_attach(&prose, 10));
+    }
+
+    #[test]
+    fn test_excerpt_keeps_both_ends() {
+        let input: String = (1..=100).map(|n| format!("line {}\n", n)).collect();
+        let excerpt = excerpt(&input, "paste.txt");
+
+        assert!(excerpt.starts_with("[Long paste: 100 lines, "));
+        assert!(excerpt.contains("attached as `paste.txt`"));
+        assert!(excerpt.contains("\nline 1\n"));
+        assert!(excerpt.contains("\nline 20\n[... 60 lines omitted ...]\nline 81\n"));
+        assert!(excerpt.ends_with("\nline 100"));
+        assert!(!excerpt.contains("line 21\n"));
+
+        // Short pastes are shown whole, long lines cut
+        let wide = "x".repeat(MAX_EXCERPT_LINE_CHARS + 50);
+        let excerpt = super::excerpt(&wide, "paste.txt");
+        assert!(!excerpt.contains("omitted"));
+        assert!(excerpt.ends_with(&format!("{}…", "x".repeat(MAX_EXCERPT_LINE_CHARS))));
+    }
+
+    #[test]
+    fn test_file_name() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 14, 9, 30, 0).unwrap();
+        let name = file_name(now);
+        assert!(name.starts_with("paste-20250314-093000-"));
+        assert!(name.ends_with(".txt"));
+        assert_ne!(name, file_name(now));
+    }
+}
@@ -0,0 +1,131 @@
+//! The session pinboard: a notes document shared by the user and agents.
+//!
+//! In Rich context mode the pinboard is shown to every agent. When the session
+//! allows it, agents append to it by writing `<Pinboard>text</Pinboard>`
+//! blocks in their replies.
+
+/// Maximum number of characters of the pinboard included in agent context.
+///
+/// Longer pinboards are cut from the start, keeping the most recent notes.
+pub const MAX_PINBOARD_CONTEXT_CHARS: usize = 8_000;
+
+const OPEN_TAG: &str = "<Pinboard>";
+const CLOSE_TAG: &str = "</Pinboard>";
+
+/// Extracts the texts of `<Pinboard>` blocks in an agent reply.
+///
+/// Texts are trimmed; empty blocks and a trailing unclosed block are ignored.
+pub fn extract_appends(content: &str) -> Vec<String> {
+    let mut appends = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find(OPEN_TAG) {
+        let after_open = &rest[start + OPEN_TAG.len()..];
+        let Some(end) = after_open.find(CLOSE_TAG) else {
+            break;
+        };
+        let text = after_open[..end].trim();
+        if !text.is_empty() {
+            appends.push(text.to_string());
+        }
+        rest = &after_open[end + CLOSE_TAG.len()..];
+    }
+    appends
+}
+
+/// Appends `text` to the pinboard as a new paragraph.
+pub fn append(pinboard: &mut Option<String>, text: &str) {
+    match pinboard {
+        Some(existing) if !existing.trim().is_empty() => {
+            existing.truncate(existing.trim_end().len());
+            existing.push_str("\n\n");
+            existing.push_str(text);
+        }
+        _ => *pinboard = Some(text.to_string()),
+    }
+}
+
+/// Formats the pinboard as a delimited block for agent context.
+///
+/// Keeps at most [`MAX_PINBOARD_CONTEXT_CHARS`] characters from the end of the
+/// pinboard. Agents are told how to append only when `agent_edits` is set.
+pub fn format_context(pinboard: &str, agent_edits: bool) -> String {
+    let pinboard = pinboard.trim();
+    let char_count = pinboard.chars().count();
+    let body = if char_count > MAX_PINBOARD_CONTEXT_CHARS {
+        let skip = char_count - MAX_PINBOARD_CONTEXT_CHARS;
+        let start = pinboard
+            .char_indices()
+            .nth(skip)
+            .map_or(pinboard.len(), |(index, _)| index);
+        format!(
+            "[... {} earlier characters omitted]\n{}",
+            skip,
+            &pinboard[start..]
+        )
+    } else {
+        pinboard.to_string()
+    };
+
+    let mut context = format!(
+        "【Session Pinboard】\n\
+         Shared notes for this session. Treat them as agreed reference material.\n\
+         ----- BEGIN PINBOARD -----\n\
+         {}\n\
+         ----- END PINBOARD -----",
+        body
+    );
+    if agent_edits {
+        context.push_str(
+            "\nTo add a note to the pinboard, write it inside \
+             <Pinboard>note text</Pinboard> in your reply.",
+        );
+    }
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_appends_skips_empty_and_unclosed_blocks() {
+        let content = "Agreed.\n<Pinboard> Use UTC everywhere </Pinboard>\n\
+                       <Pinboard></Pinboard> and <Pinboard>Ship on Friday</Pinboard>\n\
+                       <Pinboard>never closed";
+        assert_eq!(
+            extract_appends(content),
+            vec![
+                "Use UTC everywhere".to_string(),
+                "Ship on Friday".to_string()
+            ]
+        );
+        assert!(extract_appends("no blocks here").is_empty());
+    }
+
+    #[test]
+    fn test_append_separates_paragraphs() {
+        let mut pinboard = None;
+        append(&mut pinboard, "first");
+        append(&mut pinboard, "second");
+        assert_eq!(pinboard.as_deref(), Some("first\n\nsecond"));
+    }
+
+    #[test]
+    fn test_format_context_caps_length_keeping_the_tail() {
+        let pinboard = format!("{}END", "あ".repeat(MAX_PINBOARD_CONTEXT_CHARS));
+        let context = format_context(&pinboard, false);
+
+        assert!(context.contains("[... 3 earlier characters omitted]"));
+        assert!(context.contains("END\n----- END PINBOARD -----"));
+        assert!(!context.contains("<Pinboard>"));
+        let body = context
+            .split("----- BEGIN PINBOARD -----\n")
+            .nth(1)
+            .and_then(|rest| rest.split("\n----- END PINBOARD -----").next())
+            .unwrap();
+        let kept = body.lines().nth(1).unwrap();
+        assert_eq!(kept.chars().count(), MAX_PINBOARD_CONTEXT_CHARS);
+
+        assert!(format_context("short", true).contains("<Pinboard>note text</Pinboard>"));
+    }
+}
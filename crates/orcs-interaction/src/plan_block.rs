@@ -0,0 +1,92 @@
+//! Plans proposed by agents in fenced `plan` blocks.
+//!
+//! When plan detection is on for a session, an agent reply containing
+//!
+//! ````text
+//! ```plan
+//! 1. Write the parser
+//! 2. Add tests
+//! ```
+//! ````
+//!
+//! puts the session into confirmation mode with those steps; confirming
+//! dispatches each step as a task.
+
+use orcs_core::session::Plan;
+
+const OPEN_FENCE: &str = "```plan";
+const CLOSE_FENCE: &str = "```";
+
+/// Parses the first `plan` block in `content` with at least one step.
+///
+/// Each non-empty line is a step, with list markers (`-`, `*`, `1.`, `1)`,
+/// `[ ]`) removed. An unclosed block runs to the end of the reply.
+pub fn extract_plan(content: &str) -> Option<Plan> {
+    let mut rest = content;
+    while let Some(start) = rest.find(OPEN_FENCE) {
+        let after_open = &rest[start + OPEN_FENCE.len()..];
+        // The fence must end its line (```planning is not a plan block)
+        let body_start = after_open.find('\n')?;
+        if !after_open[..body_start].trim().is_empty() {
+            rest = after_open;
+            continue;
+        }
+        let body = &after_open[body_start + 1..];
+        let (block, remaining) = match body.find(CLOSE_FENCE) {
+            Some(end) => (&body[..end], &body[end + CLOSE_FENCE.len()..]),
+            None => (body, ""),
+        };
+
+        let steps: Vec<String> = block.lines().filter_map(parse_step).collect();
+        if !steps.is_empty() {
+            return Some(Plan { steps });
+        }
+        rest = remaining;
+    }
+    None
+}
+
+/// Returns the step on `line` without its list marker, or `None` if it is blank.
+fn parse_step(line: &str) -> Option<String> {
+    let mut step = line.trim();
+    if let Some(unmarked) = step.strip_prefix(['-', '*', '+']) {
+        step = unmarked.trim_start();
+    } else {
+        let digits = step.len() - step.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits > 0
+            && let Some(unmarked) = step[digits..].strip_prefix(['.', ')'])
+        {
+            step = unmarked.trim_start();
+        }
+    }
+    if let Some(unchecked) = step.strip_prefix("[ ]") {
+        step = unchecked.trim_start();
+    }
+    (!step.is_empty()).then(|| step.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_plan_strips_list_markers() {
+        let content = "Here is what I suggest:\n\n```plan\n1. Write the parser\n2) Add tests\n\n- [ ] Update the docs\n```\n\nThoughts?";
+        assert_eq!(
+            extract_plan(content).unwrap().steps,
+            vec!["Write the parser", "Add tests", "Update the docs"]
+        );
+    }
+
+    #[test]
+    fn test_extract_plan_ignores_other_blocks() {
+        assert_eq!(extract_plan("No plan here."), None);
+        assert_eq!(extract_plan("```rust\nfn main() {}\n```"), None);
+        assert_eq!(extract_plan("```planning\nnot a plan\n```"), None);
+        assert_eq!(extract_plan("```plan\n\n```"), None);
+
+        // An empty block does not hide a later one; an unclosed one runs to the end
+        let content = "```plan\n```\nThen:\n```plan\n- Ship it";
+        assert_eq!(extract_plan(content).unwrap().steps, vec!["Ship it"]);
+    }
+}
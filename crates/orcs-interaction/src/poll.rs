@@ -0,0 +1,243 @@
+//! Inline polls among session participants.
+//!
+//! A poll asks every active participant the same question with a fixed set of
+//! options. Responses are parsed leniently: the option mentioned first in the
+//! reply is the vote, and a reply naming no option counts as an abstention.
+
+use serde::{Deserialize, Serialize};
+
+/// A single participant's vote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollVote {
+    /// Display name of the voter.
+    pub voter: String,
+    /// The chosen option, or `None` for an abstention.
+    pub choice: Option<String>,
+    /// The full response, including the rationale.
+    pub response: String,
+}
+
+/// Number of votes for one option.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollOptionCount {
+    pub option: String,
+    pub votes: usize,
+}
+
+/// Tallied outcome of a poll.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollResult {
+    pub question: String,
+    /// Vote counts in the order the options were given.
+    pub counts: Vec<PollOptionCount>,
+    pub votes: Vec<PollVote>,
+    pub abstentions: usize,
+    /// Options with the most votes; more than one means a tie, empty means no votes.
+    pub winners: Vec<String>,
+}
+
+impl PollResult {
+    /// Tallies votes for the given options.
+    pub fn tally(question: &str, options: &[String], votes: Vec<PollVote>) -> Self {
+        let counts: Vec<PollOptionCount> = options
+            .iter()
+            .map(|option| PollOptionCount {
+                option: option.clone(),
+                votes: votes
+                    .iter()
+                    .filter(|v| v.choice.as_deref() == Some(option.as_str()))
+                    .count(),
+            })
+            .collect();
+        let abstentions = votes.iter().filter(|v| v.choice.is_none()).count();
+        let max = counts.iter().map(|c| c.votes).max().unwrap_or(0);
+        let winners = if max == 0 {
+            Vec::new()
+        } else {
+            counts
+                .iter()
+                .filter(|c| c.votes == max)
+                .map(|c| c.option.clone())
+                .collect()
+        };
+
+        Self {
+            question: question.to_string(),
+            counts,
+            votes,
+            abstentions,
+            winners,
+        }
+    }
+
+    /// Returns true when several options share the highest vote count.
+    pub fn is_tie(&self) -> bool {
+        self.winners.len() > 1
+    }
+
+    /// Human-readable summary stored as the `poll_result` system message.
+    pub fn summary(&self) -> String {
+        let top = self.counts.iter().map(|c| c.votes).max().unwrap_or(0);
+        let outcome = match self.winners.as_slice() {
+            [] => "no votes cast".to_string(),
+            [winner] => format!("{} ({} of {} votes)", winner, top, self.votes.len()),
+            tied => format!("tie between {} ({} votes each)", tied.join(", "), top),
+        };
+
+        let mut lines = vec![
+            format!("📊 Poll result: {}", self.question),
+            format!("Outcome: {}", outcome),
+            String::new(),
+        ];
+        lines.extend(
+            self.counts
+                .iter()
+                .map(|c| format!("- {}: {}", c.option, c.votes)),
+        );
+        if self.abstentions > 0 {
+            lines.push(format!("- Abstain: {}", self.abstentions));
+        }
+        lines.push(String::new());
+        lines.push("Votes:".to_string());
+        lines.extend(self.votes.iter().map(|v| {
+            format!(
+                "- {}: {}",
+                v.voter,
+                v.choice.as_deref().unwrap_or("abstain")
+            )
+        }));
+        lines.join("\n")
+    }
+}
+
+/// Builds the constrained-output instruction sent to every participant.
+pub fn poll_prompt(question: &str, options: &[String]) -> String {
+    format!(
+        "📊 Poll: {}\n\nAnswer with exactly one of: {}\nThen give a one-sentence rationale.",
+        question,
+        options.join(", ")
+    )
+}
+
+/// Parses a `question | option1 | option2 ...` string (the `/poll` argument format).
+pub fn parse_poll_args(args: &str) -> Result<(String, Vec<String>), String> {
+    let mut parts = args.split('|').map(str::trim);
+    let question = parts.next().unwrap_or_default().to_string();
+    let options = parts
+        .filter(|o| !o.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    validate_poll(&question, &options)?;
+    Ok((question, options))
+}
+
+/// Checks that a poll has a question and at least two distinct options.
+pub fn validate_poll(question: &str, options: &[String]) -> Result<(), String> {
+    if question.trim().is_empty() {
+        return Err("Poll question must not be empty".to_string());
+    }
+    let mut seen = std::collections::HashSet::new();
+    let distinct = options
+        .iter()
+        .map(|o| o.trim().to_lowercase())
+        .filter(|o| !o.is_empty() && seen.insert(o.clone()))
+        .count();
+    if distinct < 2 {
+        return Err("A poll needs at least two distinct options".to_string());
+    }
+    Ok(())
+}
+
+/// Returns the option mentioned first in `response`, matched case-insensitively
+/// on word boundaries. Longer options win when two match at the same position.
+///
+/// Only ASCII letters, digits and `_` count as word characters, so options in
+/// scripts written without spaces (e.g. Japanese) still match inside a sentence.
+pub fn parse_vote(response: &str, options: &[String]) -> Option<String> {
+    let haystack = response.to_lowercase();
+    options
+        .iter()
+        .filter_map(|option| {
+            let needle = option.trim().to_lowercase();
+            first_word_match(&haystack, &needle).map(|pos| (pos, needle.len(), option))
+        })
+        .min_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+        .map(|(_, _, option)| option.clone())
+}
+
+fn first_word_match(haystack: &str, needle: &str) -> Option<usize> {
+    if needle.is_empty() {
+        return None;
+    }
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+    haystack
+        .match_indices(needle)
+        .map(|(pos, _)| pos)
+        .find(|&pos| {
+            let before = haystack[..pos].chars().next_back();
+            let after = haystack[pos + needle.len()..].chars().next();
+            !is_word(before) && !is_word(after)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_vote_first_matching_option_wins() {
+        let opts = options(&["Postgres", "SQLite"]);
+        assert_eq!(
+            parse_vote("**SQLite** - simpler than Postgres for now.", &opts),
+            Some("SQLite".to_string())
+        );
+        assert_eq!(
+            parse_vote("postgres. It scales.", &opts),
+            Some("Postgres".to_string())
+        );
+        // Substrings of other words do not count
+        assert_eq!(parse_vote("I'd go with SQLiteX", &opts), None);
+    }
+
+    #[test]
+    fn test_parse_vote_matches_japanese_without_spaces() {
+        let opts = options(&["A案", "B案"]);
+        assert_eq!(
+            parse_vote("A案が良いと思います", &opts),
+            Some("A案".to_string())
+        );
+        let opts = options(&["賛成", "反対"]);
+        assert_eq!(parse_vote("賛成です", &opts), Some("賛成".to_string()));
+        // ASCII boundaries still apply next to Japanese text
+        let opts = options(&["SQLite"]);
+        assert_eq!(parse_vote("SQLiteXが良い", &opts), None);
+    }
+
+    #[test]
+    fn test_parse_vote_prefers_longer_option_at_same_position() {
+        let opts = options(&["A", "A+"]);
+        assert_eq!(parse_vote("A+ because", &opts), Some("A+".to_string()));
+        let opts = options(&["Rust", "Rust async"]);
+        assert_eq!(
+            parse_vote("Rust async, since IO dominates", &opts),
+            Some("Rust async".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_poll_args() {
+        let (question, opts) = parse_poll_args(" Which DB? | Postgres | SQLite | ").unwrap();
+        assert_eq!(question, "Which DB?");
+        assert_eq!(opts, options(&["Postgres", "SQLite"]));
+        assert!(parse_poll_args("Which DB? | Postgres").is_err());
+        assert!(parse_poll_args(" | a | b").is_err());
+        assert!(parse_poll_args("Q | a | A").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Splitting of replies that speak for several personas.
+//!
+//! A backend that sees the whole cast in its context sometimes answers on
+//! behalf of other participants ("Mai: ... Yui: ..."). Such a reply is split at
+//! lines starting with a participant's name label, so each part can be stored
+//! under the persona it speaks for.
+
+/// One persona's part of a split reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponsePart {
+    /// Name of the participant this part speaks for.
+    pub author: String,
+    /// The part's text, without its name label.
+    pub content: String,
+}
+
+/// Splits `content`, written by `speaker`, at name labels of `participants`.
+///
+/// A label is a line starting with a participant's name and a colon, optionally
+/// in bold (`**Mai**:` or `**Mai:**`). Names match case-insensitively, and lines
+/// inside fenced code blocks are never labels. Text before the first label
+/// stays with `speaker`.
+///
+/// Returns `None`, leaving the reply as-is, when no label names a participant
+/// other than `speaker` or when a label could name more than one participant.
+pub fn split_response(
+    content: &str,
+    speaker: &str,
+    participants: &[String],
+) -> Option<Vec<ResponsePart>> {
+    let mut parts: Vec<ResponsePart> = Vec::new();
+    let mut author = speaker.to_string();
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+
+        let label = if in_fence { None } else { parse_label(trimmed) };
+        let Some((name, rest)) = label else {
+            lines.push(line);
+            continue;
+        };
+
+        let mut matches = participants
+            .iter()
+            .filter(|participant| participant.eq_ignore_ascii_case(name));
+        let Some(participant) = matches.next() else {
+            lines.push(line);
+            continue;
+        };
+        if matches.next().is_some() {
+            return None;
+        }
+
+        push_part(&mut parts, &author, &lines);
+        author = participant.clone();
+        lines = vec![rest];
+    }
+    push_part(&mut parts, &author, &lines);
+
+    parts
+        .iter()
+        .any(|part| part.author != speaker)
+        .then_some(parts)
+}
+
+/// Parses a `Name: text` label line into the name and the text after the colon.
+fn parse_label(line: &str) -> Option<(&str, &str)> {
+    let line = line
+        .strip_prefix("**")
+        .or_else(|| line.strip_prefix("__"))
+        .unwrap_or(line);
+    let (name, rest) = line.split_once(':')?;
+    let name = name
+        .strip_suffix("**")
+        .or_else(|| name.strip_suffix("__"))
+        .unwrap_or(name)
+        .trim();
+    if name.is_empty() {
+        return None;
+    }
+    let rest = rest
+        .strip_prefix("**")
+        .or_else(|| rest.strip_prefix("__"))
+        .unwrap_or(rest);
+    Some((name, rest.trim_start()))
+}
+
+/// Appends the collected lines as a part of `author`, merging with the previous
+/// part of the same author. Blank parts are dropped.
+fn push_part(parts: &mut Vec<ResponsePart>, author: &str, lines: &[&str]) {
+    let content = lines.join("\n").trim().to_string();
+    if content.is_empty() {
+        return;
+    }
+    match parts.last_mut() {
+        Some(last) if last.author == author => {
+            last.content.push_str("\n\n");
+            last.content.push_str(&content);
+        }
+        _ => parts.push(ResponsePart {
+            author: author.to_string(),
+            content,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cast() -> Vec<String> {
+        vec!["Mai".to_string(), "Yui".to_string(), "Ren".to_string()]
+    }
+
+    fn part(author: &str, content: &str) -> ResponsePart {
+        ResponsePart {
+            author: author.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_splits_labels_of_other_participants() {
+        let content = "Mai: I'd start with the parser.\n\
+                       Yui: Agreed, and add tests first.\n\
+                       More from Yui.\n\
+                       **Ren**: Let's ship it.";
+
+        let parts = split_response(content, "Mai", &cast()).unwrap();
+
+        assert_eq!(
+            parts,
+            vec![
+                part("Mai", "I'd start with the parser."),
+                part("Yui", "Agreed, and add tests first.\nMore from Yui."),
+                part("Ren", "Let's ship it."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_leading_text_stays_with_speaker() {
+        let content = "Quick summary first.\n\n**yui:** I can take the review.";
+
+        let parts = split_response(content, "Mai", &cast()).unwrap();
+
+        assert_eq!(
+            parts,
+            vec![
+                part("Mai", "Quick summary first."),
+                part("Yui", "I can take the review."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_labels_in_code_blocks_are_ignored() {
+        let content = "Here is the config:\n\
+                       ```yaml\n\
+                       Yui: enabled\n\
+                       Ren: disabled\n\
+                       ```\n\
+                       Note: both keys are required.";
+
+        assert_eq!(split_response(content, "Mai", &cast()), None);
+    }
+
+    #[test]
+    fn test_own_or_unknown_labels_leave_content_untouched() {
+        let content = "Mai: Here is my take.\nNote: this is important.\nTODO: follow up.";
+
+        assert_eq!(split_response(content, "Mai", &cast()), None);
+    }
+
+    #[test]
+    fn test_ambiguous_label_leaves_content_untouched() {
+        let participants = vec!["Mai".to_string(), "Yui".to_string(), "YUI".to_string()];
+        let content = "Sure.\nYui: I'll do it.";
+
+        assert_eq!(split_response(content, "Mai", &participants), None);
+    }
+}
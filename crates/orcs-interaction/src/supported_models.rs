@@ -72,7 +72,10 @@
 //! 4. **Web search agent** (`orcs-core/src/agent/web_search.rs`)
 //!    - `DEFAULT_MODEL` — uses Gemini for web search; keep on stable version
 //!
-//! 5. **This documentation** (`orcs-interaction/src/supported_models.rs`)
+//! 5. **Price table** (`orcs-interaction/src/turn_stats.rs`)
+//!    - `MODEL_PRICES` - USD per million input/output tokens, used for turn cost estimates
+//!
+//! 6. **This documentation** (`orcs-interaction/src/supported_models.rs`)
 //!    - Update the model table above
 //!
 //! ## Verification
@@ -95,3 +98,26 @@
 //! - Stable versions (e.g., `gemini-2.5-flash`) are preferred for defaults over preview versions.
 //! - For Gemini, keep `DEFAULT_GEMINI_MODEL` on a stable version; the `GEMINI_3_PRO_MODEL`
 //!   constant is used by `try_gemini_3_from_env()` for explicit Gemini 3 usage.
+//! - [`backend_default_model`] maps each backend to its `DEFAULT_*_MODEL` constant, so
+//!   it follows those constants without a separate update.
+
+use orcs_core::persona::PersonaBackend;
+
+use crate::claude_api_agent::DEFAULT_CLAUDE_MODEL;
+use crate::gemini_api_agent::DEFAULT_GEMINI_MODEL;
+use crate::openai_api_agent::DEFAULT_OPENAI_MODEL;
+
+/// Returns the model a backend runs when neither the persona nor the app
+/// configuration names one.
+///
+/// CLI backends are assumed to run the same default as their API counterpart;
+/// Kaiba executes with Claude.
+pub fn backend_default_model(backend: &PersonaBackend) -> &'static str {
+    match backend {
+        PersonaBackend::ClaudeCli | PersonaBackend::ClaudeApi | PersonaBackend::KaibaApi => {
+            DEFAULT_CLAUDE_MODEL
+        }
+        PersonaBackend::GeminiCli | PersonaBackend::GeminiApi => DEFAULT_GEMINI_MODEL,
+        PersonaBackend::OpenAiApi | PersonaBackend::CodexCli => DEFAULT_OPENAI_MODEL,
+    }
+}
@@ -0,0 +1,375 @@
+//! Test support for driving the interaction layer without real backends.
+//!
+//! Available to this crate's tests and, with the `test-util` feature, to other
+//! crates. A [`ScriptedAgentFactory`] installed on the manager replaces every
+//! persona's backend with a [`ScriptedAgent`] that plays back queued replies
+//! and errors; personas come from a [`MockPersonaRepository`], and streamed
+//! events are captured with a [`RecordingCallback`].
+//!
+//! ```ignore
+//! let mai = ScriptedAgent::new().reply("Parser first.");
+//! let repository = MockPersonaRepository::new().with_persona(persona("mai", "Mai"));
+//! let agents = ScriptedAgentFactory::new().with_agent("mai", mai.clone());
+//! let manager = scripted_session(repository, agents);
+//!
+//! let events = RecordingCallback::new();
+//! manager
+//!     .handle_input_with_streaming(&AppMode::Idle, "Plan?", None, events.callback())
+//!     .await;
+//! assert_eq!(events.replies(), vec![("Mai".to_string(), "Parser first.".to_string())]);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use llm_toolkit::agent::{Agent, AgentError, Payload};
+use orcs_core::clock::Clock;
+use orcs_core::config::EnvSettings;
+use orcs_core::error::Result;
+use orcs_core::persona::{Persona, PersonaBackend, PersonaSource};
+use orcs_core::repository::PersonaRepository;
+use orcs_core::session::Session;
+use orcs_core::user::DefaultUserService;
+
+use crate::{AgentFactory, DialogueMessage, InteractionManager};
+
+/// Creates a default-participant persona fixture with the given ID and name.
+pub fn persona(id: &str, name: &str) -> Persona {
+    Persona {
+        id: id.to_string(),
+        name: name.to_string(),
+        role: format!("{} role", name),
+        background: format!("{} background", name),
+        communication_style: "Concise".to_string(),
+        default_participant: true,
+        source: PersonaSource::User,
+        backend: PersonaBackend::ClaudeCli,
+        model_name: None,
+        icon: None,
+        base_color: None,
+        gemini_options: None,
+        kaiba_options: None,
+        context_mode_override: None,
+        icon_asset: None,
+        workspace_id: None,
+        fallback_backend: None,
+        reviewer_only: false,
+        archived: false,
+    }
+}
+
+/// In-memory persona repository.
+#[derive(Default)]
+pub struct MockPersonaRepository {
+    personas: Mutex<Vec<Persona>>,
+}
+
+impl MockPersonaRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `persona` as stored.
+    pub fn with_persona(self, persona: Persona) -> Self {
+        self.personas.lock().unwrap().push(persona);
+        self
+    }
+
+    /// Adds `persona` as a non-default participant, so it only joins when added.
+    pub fn with_standby_persona(self, persona: Persona) -> Self {
+        self.with_persona(Persona {
+            default_participant: false,
+            ..persona
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonaRepository for MockPersonaRepository {
+    async fn find_by_id(&self, persona_id: &str) -> Result<Option<Persona>> {
+        let personas = self.personas.lock().unwrap();
+        Ok(personas.iter().find(|p| p.id == persona_id).cloned())
+    }
+
+    async fn save(&self, persona: &Persona) -> Result<()> {
+        let mut personas = self.personas.lock().unwrap();
+        match personas.iter_mut().find(|p| p.id == persona.id) {
+            Some(existing) => *existing = persona.clone(),
+            None => personas.push(persona.clone()),
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, persona_id: &str) -> Result<()> {
+        self.personas.lock().unwrap().retain(|p| p.id != persona_id);
+        Ok(())
+    }
+
+    async fn get_all(&self) -> Result<Vec<Persona>> {
+        let personas = self.personas.lock().unwrap();
+        Ok(personas.iter().filter(|p| !p.archived).cloned().collect())
+    }
+
+    async fn get_all_including_archived(&self) -> Result<Vec<Persona>> {
+        Ok(self.personas.lock().unwrap().clone())
+    }
+
+    async fn save_all(&self, personas: &[Persona]) -> Result<()> {
+        for persona in personas {
+            self.save(persona).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Agent that plays back a script of replies and errors, one per call.
+///
+/// Clones share the script and the record of received prompts, so a test can
+/// keep a handle to an agent it handed to a [`ScriptedAgentFactory`]. Once the
+/// script runs out, every call fails unless a repeated reply is set.
+#[derive(Clone)]
+pub struct ScriptedAgent {
+    expertise: String,
+    script: Arc<Mutex<VecDeque<std::result::Result<String, AgentError>>>>,
+    repeated: Option<String>,
+    latency: Duration,
+    received: Arc<Mutex<Vec<String>>>,
+}
+
+impl Default for ScriptedAgent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptedAgent {
+    pub fn new() -> Self {
+        Self {
+            expertise: "Scripted agent for testing".to_string(),
+            script: Arc::new(Mutex::new(VecDeque::new())),
+            repeated: None,
+            latency: Duration::ZERO,
+            received: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Queues a reply.
+    pub fn reply(self, content: impl Into<String>) -> Self {
+        self.script.lock().unwrap().push_back(Ok(content.into()));
+        self
+    }
+
+    /// Queues an error.
+    pub fn fail(self, error: AgentError) -> Self {
+        self.script.lock().unwrap().push_back(Err(error));
+        self
+    }
+
+    /// Replies with `content` whenever the script has run out.
+    pub fn repeat(mut self, content: impl Into<String>) -> Self {
+        self.repeated = Some(content.into());
+        self
+    }
+
+    /// Waits `latency` before answering each call.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Number of calls so far.
+    pub fn calls(&self) -> usize {
+        self.received.lock().unwrap().len()
+    }
+
+    /// Text of each prompt received so far: its contexts, plain text, then
+    /// its messages.
+    pub fn received(&self) -> Vec<String> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl Agent for ScriptedAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        &self.expertise
+    }
+
+    async fn execute(&self, payload: Payload) -> std::result::Result<String, AgentError> {
+        let messages: Vec<String> = payload
+            .to_messages()
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        self.received.lock().unwrap().push(format!(
+            "{}\n{}\n{}",
+            payload.contexts().join("\n"),
+            payload.to_text(),
+            messages.join("\n")
+        ));
+
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+        let next = self.script.lock().unwrap().pop_front();
+        match (next, &self.repeated) {
+            (Some(result), _) => result,
+            (None, Some(content)) => Ok(content.clone()),
+            (None, None) => Err(AgentError::ExecutionFailed(
+                "ScriptedAgent: script exhausted".to_string(),
+            )),
+        }
+    }
+}
+
+/// Agent factory that hands out a [`ScriptedAgent`] per persona ID.
+///
+/// Personas without a registered agent get one with an empty script, so their
+/// turns fail. Clones share the record of created agents and warmed-up personas.
+#[derive(Clone, Default)]
+pub struct ScriptedAgentFactory {
+    agents: HashMap<String, ScriptedAgent>,
+    created: Arc<Mutex<Vec<(String, bool)>>>,
+    warmed_up: Arc<Mutex<Vec<String>>>,
+}
+
+impl ScriptedAgentFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the persona with `persona_id` speak through `agent`.
+    pub fn with_agent(mut self, persona_id: &str, agent: ScriptedAgent) -> Self {
+        self.agents.insert(persona_id.to_string(), agent);
+        self
+    }
+
+    /// Persona ID and `keep_history` of every agent created so far, in order.
+    pub fn created(&self) -> Vec<(String, bool)> {
+        self.created.lock().unwrap().clone()
+    }
+
+    /// IDs of the personas warmed up so far, in order.
+    pub fn warmed_up(&self) -> Vec<String> {
+        self.warmed_up.lock().unwrap().clone()
+    }
+}
+
+impl AgentFactory for ScriptedAgentFactory {
+    fn create_agent(
+        &self,
+        persona: &Persona,
+        keep_history: bool,
+    ) -> Box<dyn Agent<Output = String, Expertise = String>> {
+        self.created
+            .lock()
+            .unwrap()
+            .push((persona.id.clone(), keep_history));
+        Box::new(self.agents.get(&persona.id).cloned().unwrap_or_default())
+    }
+
+    fn warm_up(&self, persona: &Persona) -> std::result::Result<(), String> {
+        self.warmed_up.lock().unwrap().push(persona.id.clone());
+        Ok(())
+    }
+}
+
+/// Clock that stands still until advanced; share it with clones.
+#[derive(Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl MockClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: chrono::Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// Creates a new session whose personas speak through `agents`.
+pub fn scripted_session(
+    persona_repository: MockPersonaRepository,
+    agents: ScriptedAgentFactory,
+) -> InteractionManager {
+    InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(persona_repository),
+        Arc::new(DefaultUserService),
+        EnvSettings::default(),
+    )
+    .with_agent_factory(Arc::new(agents))
+}
+
+/// Restores `session`, making its personas speak through `agents`.
+pub fn restore_scripted_session(
+    session: Session,
+    persona_repository: MockPersonaRepository,
+    agents: ScriptedAgentFactory,
+) -> InteractionManager {
+    InteractionManager::from_session(
+        session,
+        Arc::new(persona_repository),
+        Arc::new(DefaultUserService),
+        EnvSettings::default(),
+    )
+    .with_agent_factory(Arc::new(agents))
+}
+
+/// Records the events streamed to a turn callback.
+#[derive(Clone, Default)]
+pub struct RecordingCallback {
+    events: Arc<Mutex<Vec<DialogueMessage>>>,
+}
+
+impl RecordingCallback {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a turn callback that records into this recorder.
+    pub fn callback(&self) -> impl Fn(&DialogueMessage) + Clone + Send + Sync + 'static {
+        let events = self.events.clone();
+        move |message: &DialogueMessage| events.lock().unwrap().push(message.clone())
+    }
+
+    /// Every event recorded so far, in order.
+    pub fn events(&self) -> Vec<DialogueMessage> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// `(author, content)` of the recorded replies, without thinking events
+    /// and failures.
+    pub fn replies(&self) -> Vec<(String, String)> {
+        self.events()
+            .into_iter()
+            .filter(|m| !m.is_thinking() && m.failure.is_none())
+            .map(|m| (m.author, m.content))
+            .collect()
+    }
+
+    /// The recorded failure events.
+    pub fn failures(&self) -> Vec<DialogueMessage> {
+        self.events()
+            .into_iter()
+            .filter(|m| m.failure.is_some())
+            .collect()
+    }
+}
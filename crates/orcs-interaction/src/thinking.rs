@@ -0,0 +1,256 @@
+//! Per-persona "thinking" indicator events.
+//!
+//! Each persona's agent is wrapped in a [`ThinkingReportAgent`] that reports
+//! when it starts executing. While a dialogue round streams, a [`ThinkingRound`]
+//! collects those reports and emits a thinking-start event before the persona's
+//! turn, and a thinking-end event once the turn (or an error) has been streamed.
+//! In between, personas that opted into heartbeats get a thinking event with a
+//! [`Heartbeat`] every heartbeat interval.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use llm_toolkit::agent::dialogue::{DialogueSession, DialogueTurn};
+use llm_toolkit::agent::{Agent, AgentError, Payload};
+use tokio::sync::mpsc;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+
+use crate::DialogueMessage;
+use crate::heartbeat::{Heartbeat, OutputActivity};
+
+/// A persona that started generating its turn.
+struct Started {
+    author: String,
+    heartbeat: bool,
+}
+
+/// Channel from persona agents to the dialogue round currently streaming.
+///
+/// Reports are dropped while no round is running (e.g. handoff notes).
+#[derive(Clone, Default)]
+pub(crate) struct ThinkingSignal {
+    sender: Arc<Mutex<Option<mpsc::UnboundedSender<Started>>>>,
+    /// Latest output lines of running CLI processes, shown in heartbeats
+    pub(crate) output: OutputActivity,
+}
+
+impl ThinkingSignal {
+    /// Reports that `author` started generating its turn.
+    fn started(&self, author: &str, heartbeat: bool) {
+        if let Some(sender) = self.sender.lock().unwrap().as_ref() {
+            let _ = sender.send(Started {
+                author: author.to_string(),
+                heartbeat,
+            });
+        }
+    }
+
+    /// Starts collecting reports for one dialogue round.
+    ///
+    /// Heartbeats are emitted every `heartbeat_interval`, or never when `None`.
+    pub(crate) fn begin_round(
+        &self,
+        session_id: &str,
+        heartbeat_interval: Option<Duration>,
+    ) -> ThinkingRound {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        *self.sender.lock().unwrap() = Some(sender);
+        let heartbeat = heartbeat_interval.map(|period| {
+            let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+        ThinkingRound {
+            signal: self.clone(),
+            receiver,
+            session_id: session_id.to_string(),
+            active: Vec::new(),
+            heartbeat,
+        }
+    }
+}
+
+/// A persona whose thinking-start was emitted but not yet its thinking-end.
+struct ActiveTurn {
+    author: String,
+    started_at: Instant,
+    heartbeat: bool,
+}
+
+/// Tracks which personas are generating during one dialogue round.
+///
+/// Stops collecting reports when dropped.
+pub(crate) struct ThinkingRound {
+    signal: ThinkingSignal,
+    receiver: mpsc::UnboundedReceiver<Started>,
+    session_id: String,
+    active: Vec<ActiveTurn>,
+    heartbeat: Option<Interval>,
+}
+
+impl ThinkingRound {
+    /// Waits for the next turn of `session`, emitting thinking-start events
+    /// as personas begin generating.
+    ///
+    /// Every persona that started before the turn is announced before it returns,
+    /// and heartbeats are emitted while waiting.
+    pub(crate) async fn next_turn<F>(
+        &mut self,
+        session: &mut DialogueSession<'_>,
+        on_turn: Option<&F>,
+    ) -> Option<Result<DialogueTurn, AgentError>>
+    where
+        F: Fn(&DialogueMessage),
+    {
+        let next = session.next_turn();
+        tokio::pin!(next);
+        let result = loop {
+            tokio::select! {
+                biased;
+                Some(started) = self.receiver.recv() => self.start(started, on_turn),
+                () = tick(&mut self.heartbeat) => self.beat(on_turn),
+                result = &mut next => break result,
+            }
+        };
+        while let Ok(started) = self.receiver.try_recv() {
+            self.start(started, on_turn);
+        }
+        result
+    }
+
+    /// Emits the thinking-end event of `author` if it is still marked as generating.
+    pub(crate) fn finish<F>(&mut self, author: &str, on_turn: Option<&F>)
+    where
+        F: Fn(&DialogueMessage),
+    {
+        if let Some(index) = self.active.iter().position(|turn| turn.author == author) {
+            let turn = self.active.remove(index);
+            self.end(turn, on_turn);
+        }
+    }
+
+    /// Emits thinking-end events for every persona still marked as generating.
+    pub(crate) fn finish_all<F>(&mut self, on_turn: Option<&F>)
+    where
+        F: Fn(&DialogueMessage),
+    {
+        for turn in std::mem::take(&mut self.active) {
+            self.end(turn, on_turn);
+        }
+    }
+
+    fn start<F>(&mut self, started: Started, on_turn: Option<&F>)
+    where
+        F: Fn(&DialogueMessage),
+    {
+        if self.active.iter().any(|turn| turn.author == started.author) {
+            return;
+        }
+        // Output of an earlier run must not show up as this run's progress
+        self.signal.output.clear(&started.author);
+        self.emit(
+            DialogueMessage::thinking(&self.session_id, started.author.clone(), true),
+            on_turn,
+        );
+        self.active.push(ActiveTurn {
+            author: started.author,
+            started_at: Instant::now(),
+            heartbeat: started.heartbeat,
+        });
+    }
+
+    fn end<F>(&self, turn: ActiveTurn, on_turn: Option<&F>)
+    where
+        F: Fn(&DialogueMessage),
+    {
+        self.signal.output.clear(&turn.author);
+        self.emit(
+            DialogueMessage::thinking(&self.session_id, turn.author, false),
+            on_turn,
+        );
+    }
+
+    /// Emits a heartbeat for every active persona that opted into heartbeats.
+    fn beat<F>(&self, on_turn: Option<&F>)
+    where
+        F: Fn(&DialogueMessage),
+    {
+        for turn in self.active.iter().filter(|turn| turn.heartbeat) {
+            let heartbeat = Heartbeat {
+                // Ticks are timed from the round start, just before the turn's
+                // own start, so truncating would report the same second twice
+                elapsed_secs: turn.started_at.elapsed().as_secs_f64().round() as u64,
+                last_output_line: self.signal.output.last_line(&turn.author),
+            };
+            self.emit(
+                DialogueMessage::heartbeat(&self.session_id, turn.author.clone(), heartbeat),
+                on_turn,
+            );
+        }
+    }
+
+    fn emit<F>(&self, message: DialogueMessage, on_turn: Option<&F>)
+    where
+        F: Fn(&DialogueMessage),
+    {
+        if let Some(callback) = on_turn {
+            callback(&message);
+        }
+    }
+}
+
+/// Waits for the next heartbeat tick, or forever when heartbeats are off.
+async fn tick(heartbeat: &mut Option<Interval>) {
+    match heartbeat {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+impl Drop for ThinkingRound {
+    fn drop(&mut self) {
+        *self.signal.sender.lock().unwrap() = None;
+    }
+}
+
+/// Reports to the running dialogue round when this persona starts its turn.
+pub(crate) struct ThinkingReportAgent {
+    pub(crate) inner: Box<dyn Agent<Output = String, Expertise = String>>,
+    pub(crate) persona_name: String,
+    pub(crate) signal: ThinkingSignal,
+    /// Whether the round emits heartbeats while this persona's turn runs
+    pub(crate) heartbeat: bool,
+}
+
+#[async_trait::async_trait]
+impl Agent for ThinkingReportAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        self.inner.expertise()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn capabilities(&self) -> Option<Vec<llm_toolkit::agent::Capability>> {
+        self.inner.capabilities()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        self.signal.started(&self.persona_name, self.heartbeat);
+        self.inner.execute(payload).await
+    }
+
+    async fn is_available(&self) -> Result<(), AgentError> {
+        self.inner.is_available().await
+    }
+}
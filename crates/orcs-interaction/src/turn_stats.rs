@@ -0,0 +1,207 @@
+//! Per-turn duration, token usage and cost of persona agents.
+//!
+//! Each persona's agent is wrapped in a [`TurnStatsAgent`] that times its
+//! turns. API agents report the tokens of each response to a [`UsageSink`],
+//! and the persona's backend agent prices them with [`estimate_cost_microusd`]
+//! before adding them to the turn's stats. CLI backends report no usage, so
+//! their stats carry the duration only.
+//!
+//! Stats are kept per persona ID until the turn is added to history, where
+//! they land in the message's `turn_stats`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use llm_toolkit::agent::{Agent, AgentError, Payload};
+use orcs_core::session::TurnStats;
+use tokio::sync::RwLock;
+
+/// Tokens an API backend reported for one response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenUsage {
+    /// Model that answered, as named in the request.
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// Where an API agent reports the token usage of its latest response.
+#[derive(Debug, Clone, Default)]
+pub struct UsageSink(Arc<Mutex<Option<TokenUsage>>>);
+
+impl UsageSink {
+    /// Records the usage of a response, replacing any earlier one.
+    pub fn record(&self, usage: TokenUsage) {
+        *self.0.lock().unwrap() = Some(usage);
+    }
+
+    /// Takes the recorded usage, leaving the sink empty.
+    pub fn take(&self) -> Option<TokenUsage> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// API prices in USD per million input and output tokens, by model ID prefix.
+///
+/// The longest matching prefix wins. Update alongside the model lists in
+/// [`crate::supported_models`].
+const MODEL_PRICES: &[(&str, f64, f64)] = &[
+    ("claude-opus-4-6", 5.0, 25.0),
+    ("claude-opus-4-5", 5.0, 25.0),
+    ("claude-opus-4-1", 15.0, 75.0),
+    ("claude-opus-4-2025", 15.0, 75.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-haiku-4-5", 1.0, 5.0),
+    ("gemini-3.1-pro", 2.0, 12.0),
+    ("gemini-3-pro", 2.0, 12.0),
+    ("gemini-3-flash", 0.5, 3.0),
+    ("gemini-2.5-pro", 1.25, 10.0),
+    ("gemini-2.5-flash", 0.3, 2.5),
+    ("gemini-2.5-flash-lite", 0.1, 0.4),
+    ("gpt-5.2-pro", 21.0, 168.0),
+    ("gpt-5.2", 1.75, 14.0),
+    ("gpt-5-mini", 0.25, 2.0),
+    ("gpt-5", 1.25, 10.0),
+    ("gpt-4.1-mini", 0.4, 1.6),
+    ("gpt-4.1", 2.0, 8.0),
+];
+
+/// Estimated cost of a response in millionths of a US dollar, or `None` when
+/// the model has no known price.
+pub fn estimate_cost_microusd(model: &str, input_tokens: u64, output_tokens: u64) -> Option<u64> {
+    let (_, input_price, output_price) = MODEL_PRICES
+        .iter()
+        .filter(|(prefix, _, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())?;
+    // A price per million tokens is the price per token in micro-dollars
+    let cost = input_tokens as f64 * input_price + output_tokens as f64 * output_price;
+    Some(cost.round() as u64)
+}
+
+/// Stats of the latest turn by persona ID, taken when the turn is added to history.
+pub(crate) type TurnStatsLog = Arc<RwLock<HashMap<String, TurnStats>>>;
+
+/// Adds the token usage reported by a persona's API backend to its turn stats.
+#[derive(Clone)]
+pub(crate) struct UsageCapture {
+    pub(crate) persona_id: String,
+    pub(crate) log: TurnStatsLog,
+}
+
+impl std::fmt::Debug for UsageCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsageCapture")
+            .field("persona_id", &self.persona_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl UsageCapture {
+    pub(crate) async fn record(&self, usage: TokenUsage) {
+        let mut log = self.log.write().await;
+        let stats = log.entry(self.persona_id.clone()).or_default();
+        stats.input_tokens = Some(usage.input_tokens);
+        stats.output_tokens = Some(usage.output_tokens);
+        stats.estimated_cost_microusd =
+            estimate_cost_microusd(&usage.model, usage.input_tokens, usage.output_tokens);
+    }
+}
+
+/// Times the turns of a persona's agent and records their duration in `log`.
+///
+/// The usage recorded by the backend during the turn is kept; stats of failed
+/// turns are dropped.
+pub(crate) struct TurnStatsAgent {
+    pub(crate) inner: Box<dyn Agent<Output = String, Expertise = String>>,
+    pub(crate) persona_id: String,
+    pub(crate) log: TurnStatsLog,
+}
+
+#[async_trait::async_trait]
+impl Agent for TurnStatsAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        self.inner.expertise()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn capabilities(&self) -> Option<Vec<llm_toolkit::agent::Capability>> {
+        self.inner.capabilities()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        self.log.write().await.remove(&self.persona_id);
+        let started_at = Instant::now();
+        let result = self.inner.execute(payload).await;
+        let duration_ms = started_at.elapsed().as_millis() as u64;
+
+        let mut log = self.log.write().await;
+        if result.is_ok() {
+            log.entry(self.persona_id.clone()).or_default().duration_ms = duration_ms;
+        } else {
+            log.remove(&self.persona_id);
+        }
+        result
+    }
+
+    async fn is_available(&self) -> Result<(), AgentError> {
+        self.inner.is_available().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_uses_longest_matching_prefix() {
+        // 1,000 input tokens at $0.30/M and 500 output tokens at $2.50/M
+        assert_eq!(
+            estimate_cost_microusd("gemini-2.5-flash", 1_000, 500),
+            Some(1_550)
+        );
+        // Not priced as gemini-2.5-flash
+        assert_eq!(
+            estimate_cost_microusd("gemini-2.5-flash-lite", 1_000, 500),
+            Some(300)
+        );
+        assert_eq!(
+            estimate_cost_microusd("claude-sonnet-4-5-20250929", 2_000, 1_000),
+            Some(21_000)
+        );
+        assert_eq!(estimate_cost_microusd("gpt-5-mini", 0, 0), Some(0));
+        assert_eq!(estimate_cost_microusd("local-llama", 1_000, 500), None);
+    }
+
+    #[tokio::test]
+    async fn test_usage_capture_prices_reported_tokens() {
+        let log = TurnStatsLog::default();
+        let capture = UsageCapture {
+            persona_id: "mai".to_string(),
+            log: log.clone(),
+        };
+
+        capture
+            .record(TokenUsage {
+                model: "claude-opus-4-6".to_string(),
+                input_tokens: 1_000,
+                output_tokens: 200,
+            })
+            .await;
+
+        let stats = log.read().await["mai"].clone();
+        assert_eq!(stats.input_tokens, Some(1_000));
+        assert_eq!(stats.output_tokens, Some(200));
+        assert_eq!(stats.estimated_cost_microusd, Some(10_000));
+    }
+}
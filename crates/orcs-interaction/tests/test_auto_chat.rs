@@ -0,0 +1,188 @@
+//! AutoChat runs: stop conditions, pacing and configuration limits.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use llm_toolkit::agent::AgentError;
+use orcs_core::config::EnvSettings;
+use orcs_core::session::{AppMode, AutoChatConfig, MessageRole, Session};
+use orcs_core::user::DefaultUserService;
+use orcs_interaction::InteractionManager;
+use orcs_interaction::testing::{
+    MockPersonaRepository, ScriptedAgent, ScriptedAgentFactory, persona, scripted_session,
+};
+
+async fn snapshot(manager: &InteractionManager) -> Session {
+    manager.to_session(AppMode::Idle, "ws".to_string()).await
+}
+
+fn mai_session(mai: ScriptedAgent) -> InteractionManager {
+    scripted_session(
+        MockPersonaRepository::new().with_persona(persona("mai", "Mai")),
+        ScriptedAgentFactory::new().with_agent("mai", mai),
+    )
+}
+
+fn rate_limited() -> AgentError {
+    AgentError::ProcessError {
+        status_code: Some(429),
+        message: "Too many requests".to_string(),
+        is_retryable: false,
+        retry_after: None,
+    }
+}
+
+/// Collects formatted log output for assertions.
+struct LogWriter(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auto_chat_stops_on_stop_phrase() {
+    let manager =
+        mai_session(ScriptedAgent::new().repeat("That settles it. Final Answer: ship it"));
+    manager
+        .set_auto_chat_config(Some(AutoChatConfig {
+            max_iterations: 5,
+            stop_phrases: vec!["FINAL ANSWER".to_string(), "会話終了".to_string()],
+            ..Default::default()
+        }))
+        .await;
+
+    manager
+        .execute_auto_chat("Let's decide", None, |_| {}, |_, _| {}, None)
+        .await;
+
+    let session = snapshot(&manager).await;
+    let replies = session
+        .persona_histories
+        .values()
+        .flatten()
+        .filter(|m| m.role == MessageRole::Assistant)
+        .count();
+    assert_eq!(replies, 1);
+    assert!(manager.get_auto_chat_iteration().await.is_none());
+    assert!(
+        session
+            .system_messages
+            .iter()
+            .any(|m| m.content.contains("stop phrase"))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cancelled_auto_chat_reports_stopping_early() {
+    let manager = mai_session(ScriptedAgent::new().repeat("Let's keep going."));
+    manager
+        .set_auto_chat_config(Some(AutoChatConfig {
+            max_iterations: 5,
+            iteration_delay_ms: Some(0),
+            ..Default::default()
+        }))
+        .await;
+
+    // Cancel once the second iteration is done
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let flag = cancel_flag.clone();
+    manager
+        .execute_auto_chat(
+            "Let's decide",
+            None,
+            |_| {},
+            move |iteration, _| {
+                if iteration == 2 {
+                    flag.store(true, Ordering::SeqCst);
+                }
+            },
+            Some(cancel_flag),
+        )
+        .await;
+
+    let session = snapshot(&manager).await;
+    let completion = session
+        .system_messages
+        .iter()
+        .find(|m| m.metadata.system_message_type.as_deref() == Some("auto_chat_completion"))
+        .unwrap();
+    assert_eq!(
+        completion.content,
+        "⏹️ AutoChat stopped early after 2/5 iterations (cancelled)."
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_auto_chat_backs_off_after_rate_limits() {
+    let manager = mai_session(
+        ScriptedAgent::new()
+            .fail(rate_limited())
+            .fail(rate_limited())
+            .repeat("Continuing"),
+    );
+    manager
+        .set_auto_chat_config(Some(AutoChatConfig {
+            max_iterations: 5,
+            iteration_delay_ms: Some(100),
+            adaptive_delay: true,
+            ..Default::default()
+        }))
+        .await;
+
+    let delays = Mutex::new(Vec::new());
+    manager
+        .execute_auto_chat(
+            "Let's decide",
+            None,
+            |_| {},
+            |_, delay| delays.lock().unwrap().push(delay.as_millis()),
+            None,
+        )
+        .await;
+
+    // Two 429s double the spacing; clean iterations halve it back toward 100ms
+    assert_eq!(delays.into_inner().unwrap(), vec![1000, 2000, 1000, 500, 0]);
+}
+
+#[tokio::test]
+async fn test_auto_chat_iterations_are_clamped_to_the_configured_cap() {
+    let manager = InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(MockPersonaRepository::new()),
+        Arc::new(DefaultUserService),
+        EnvSettings {
+            max_auto_chat_iterations: 50,
+            ..Default::default()
+        },
+    );
+
+    let logs = Arc::new(Mutex::new(Vec::new()));
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || LogWriter(writer.clone()))
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    manager
+        .set_auto_chat_config(Some(AutoChatConfig {
+            max_iterations: 1000,
+            ..Default::default()
+        }))
+        .await;
+
+    let config = manager.get_auto_chat_config().await.unwrap();
+    assert_eq!(config.max_iterations, 50);
+    let logs = String::from_utf8(logs.lock().unwrap().clone()).unwrap();
+    assert!(
+        logs.contains("WARN") && logs.contains("max_iterations 1000 exceeds the limit of 50"),
+        "{logs}"
+    );
+}
@@ -0,0 +1,682 @@
+//! Session participants: joining, leaving, caps, restores and metadata.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use orcs_core::config::EnvSettings;
+use orcs_core::persona::{Persona, PersonaBackend, get_default_presets};
+use orcs_core::repository::PersonaRepository;
+use orcs_core::session::{
+    AppMode, AutoChatConfig, ContextMode, ConversationMessage, MessageMetadata, MessageRole,
+    Session, SystemEventType,
+};
+use orcs_core::user::DefaultUserService;
+use orcs_interaction::testing::{
+    MockPersonaRepository, ScriptedAgent, ScriptedAgentFactory, persona, restore_scripted_session,
+    scripted_session,
+};
+use orcs_interaction::{
+    InteractionManager, InteractionResult, NO_PARTICIPANTS_MESSAGE, NO_PERSONAS_MESSAGE,
+    PARTICIPANT_HANDOFF_MESSAGE_TYPE,
+};
+
+async fn snapshot(manager: &InteractionManager) -> Session {
+    manager.to_session(AppMode::Idle, "ws".to_string()).await
+}
+
+fn new_session(
+    repository: Arc<MockPersonaRepository>,
+    settings: EnvSettings,
+) -> InteractionManager {
+    InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        repository,
+        Arc::new(DefaultUserService),
+        settings,
+    )
+}
+
+/// Restores a session in which `persona_id` has replied once.
+async fn restore_with_reply(
+    repository: Arc<MockPersonaRepository>,
+    persona_id: &str,
+) -> InteractionManager {
+    let mut session = snapshot(&new_session(repository.clone(), EnvSettings::default())).await;
+    session.persona_histories.insert(
+        persona_id.to_string(),
+        vec![ConversationMessage {
+            role: MessageRole::Assistant,
+            content: "Hi".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            metadata: MessageMetadata::default(),
+            attachments: vec![],
+        }],
+    );
+    InteractionManager::from_session(
+        session,
+        repository,
+        Arc::new(DefaultUserService),
+        EnvSettings::default(),
+    )
+}
+
+/// Restores a session whose participants include `active_ids`, named via `participants`.
+async fn restore_with_participants(
+    repository: Arc<MockPersonaRepository>,
+    active_ids: &[&str],
+    participants: &[(&str, &str)],
+) -> InteractionManager {
+    let mut session = snapshot(&new_session(repository.clone(), EnvSettings::default())).await;
+    session.active_participant_ids = active_ids.iter().map(|id| id.to_string()).collect();
+    session.participants = participants
+        .iter()
+        .map(|(id, name)| (id.to_string(), name.to_string()))
+        .collect();
+    InteractionManager::from_session(
+        session,
+        repository,
+        Arc::new(DefaultUserService),
+        EnvSettings::default(),
+    )
+}
+
+fn system_contents(session: &Session) -> Vec<String> {
+    session
+        .system_messages
+        .iter()
+        .map(|m| m.content.clone())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_archived_persona_keeps_name_in_old_session() {
+    let repository = Arc::new(MockPersonaRepository::new().with_persona(Persona {
+        default_participant: false,
+        archived: true,
+        ..persona("retired", "Retired Reviewer")
+    }));
+
+    let manager = restore_with_reply(repository, "retired").await;
+
+    let session = snapshot(&manager).await;
+    assert_eq!(
+        session.participants.get("retired").map(String::as_str),
+        Some("Retired Reviewer")
+    );
+    assert!(
+        !manager
+            .available_personas()
+            .await
+            .contains(&"retired".to_string())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_adding_same_participant_twice_is_noop() {
+    let manager = scripted_session(
+        MockPersonaRepository::new().with_standby_persona(persona("reviewer", "Reviewer")),
+        ScriptedAgentFactory::new(),
+    );
+    manager.add_participant("reviewer").await.unwrap();
+    manager.add_participant("reviewer").await.unwrap();
+
+    assert_eq!(
+        manager.get_active_participants().await.unwrap(),
+        vec!["reviewer".to_string()]
+    );
+    let joins = snapshot(&manager)
+        .await
+        .system_messages
+        .iter()
+        .filter(|m| m.metadata.system_event_type == Some(SystemEventType::ParticipantJoined))
+        .count();
+    assert_eq!(joins, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_adding_participant_beyond_cap_is_rejected() {
+    let repository = MockPersonaRepository::new()
+        .with_standby_persona(persona("mai", "Mai"))
+        .with_standby_persona(persona("yui", "Yui"))
+        .with_standby_persona(persona("ren", "Ren"));
+    let manager = new_session(
+        Arc::new(repository),
+        EnvSettings {
+            max_participants: 2,
+            ..Default::default()
+        },
+    );
+
+    manager.add_participant("mai").await.unwrap();
+    manager.add_participant("yui").await.unwrap();
+    let error = manager.add_participant("ren").await.unwrap_err();
+
+    assert!(error.contains("Ren"), "{}", error);
+    assert!(error.contains("maximum of 2 participants"), "{}", error);
+    let mut participants = manager.get_active_participants().await.unwrap();
+    participants.sort();
+    assert_eq!(participants, vec!["mai".to_string(), "yui".to_string()]);
+    // Re-adding an active participant at the cap is still a no-op
+    manager.add_participant("mai").await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merged_and_default_rosters_respect_the_cap() {
+    let repository = MockPersonaRepository::new()
+        .with_persona(persona("mai", "Mai"))
+        .with_persona(persona("yui", "Yui"))
+        .with_persona(persona("ren", "Ren"));
+    let manager = new_session(
+        Arc::new(repository),
+        EnvSettings {
+            max_participants: 2,
+            ..Default::default()
+        },
+    );
+
+    let cap_notices = |session: &Session| -> Vec<String> {
+        session
+            .system_messages
+            .iter()
+            .filter(|m| m.content.contains("参加者の上限（2人）"))
+            .map(|m| m.content.clone())
+            .collect()
+    };
+
+    // Three default participants: the one left out is announced once
+    let defaults = manager.get_active_participants().await.unwrap();
+    assert_eq!(defaults.len(), 2);
+    assert_eq!(manager.get_active_participants().await.unwrap(), defaults);
+    let session = snapshot(&manager).await;
+    let notices = cap_notices(&session);
+    assert_eq!(notices.len(), 1);
+    let left_out = [("mai", "Mai"), ("yui", "Yui"), ("ren", "Ren")]
+        .into_iter()
+        .find(|(id, _)| !defaults.iter().any(|d| d == id))
+        .unwrap();
+    assert!(notices[0].contains(left_out.1), "{}", notices[0]);
+
+    // A merge appends the other session's participants after this one's
+    let mut merged = session;
+    merged.system_messages.clear();
+    merged
+        .participants
+        .insert("yui".to_string(), "Yui".to_string());
+    merged.active_participant_ids = vec!["ren".to_string(), "mai".to_string(), "yui".to_string()];
+    manager.replace_conversation(&merged).await;
+
+    let session = snapshot(&manager).await;
+    let notices = cap_notices(&session);
+    assert_eq!(notices.len(), 1);
+    assert!(notices[0].contains("Yui"), "{}", notices[0]);
+    assert_eq!(session.active_participant_ids.len(), 2);
+    let mut participants = manager.get_active_participants().await.unwrap();
+    participants.sort();
+    assert_eq!(participants, vec!["mai".to_string(), "ren".to_string()]);
+    assert!(manager.add_participant("yui").await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_restored_roster_over_the_cap_names_those_left_out() {
+    let repository = Arc::new(
+        MockPersonaRepository::new()
+            .with_persona(persona("mai", "Mai"))
+            .with_persona(persona("yui", "Yui"))
+            .with_persona(persona("ren", "Ren")),
+    );
+    let mut session = snapshot(&new_session(repository.clone(), EnvSettings::default())).await;
+    session.active_participant_ids = vec!["yui".to_string(), "ren".to_string(), "mai".to_string()];
+    session.participants = HashMap::from([
+        ("yui".to_string(), "Yui".to_string()),
+        ("ren".to_string(), "Ren".to_string()),
+        ("mai".to_string(), "Mai".to_string()),
+    ]);
+
+    let manager = InteractionManager::from_session(
+        session,
+        repository,
+        Arc::new(DefaultUserService),
+        EnvSettings {
+            max_participants: 1,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        manager.get_active_participants().await.unwrap(),
+        vec!["yui"]
+    );
+    let session = snapshot(&manager).await;
+    let notice = session
+        .system_messages
+        .iter()
+        .find(|m| m.content.contains("参加者の上限（1人）"))
+        .unwrap();
+    assert!(notice.content.contains("Ren、Mai"), "{}", notice.content);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_handoff_is_pinned_in_history_after_removal() {
+    let repository = MockPersonaRepository::new()
+        .with_persona(persona("reviewer", "Reviewer"))
+        .with_persona(Persona {
+            context_mode_override: Some(ContextMode::Clean),
+            ..persona("coder", "Coder")
+        });
+    let reviewer = ScriptedAgent::new()
+        .reply("Reviewer reply")
+        .reply("Reviewer handoff reply");
+    let coder = ScriptedAgent::new().repeat("Coder reply");
+    let manager = scripted_session(
+        repository,
+        ScriptedAgentFactory::new()
+            .with_agent("reviewer", reviewer.clone())
+            .with_agent("coder", coder.clone()),
+    );
+    manager
+        .handle_input(&AppMode::Idle, "Review the parser")
+        .await;
+
+    manager.remove_participant("reviewer", true).await.unwrap();
+
+    let handoff_prompt = reviewer.received().last().unwrap().clone();
+    assert!(handoff_prompt.contains("handoff note"));
+    assert!(handoff_prompt.contains("Review the parser"));
+    let session = snapshot(&manager).await;
+    assert!(
+        session.persona_histories["reviewer"]
+            .iter()
+            .any(|m| m.content == "Reviewer handoff reply")
+    );
+    // The handoff note is shown in the UI only
+    let note = session
+        .system_messages
+        .iter()
+        .find(|m| {
+            m.metadata.system_message_type.as_deref() == Some(PARTICIPANT_HANDOFF_MESSAGE_TYPE)
+        })
+        .unwrap();
+    assert!(!note.metadata.include_in_dialogue);
+    assert_eq!(
+        manager.get_active_participants().await.unwrap(),
+        vec!["coder".to_string()]
+    );
+
+    // The Coder receives no history, but still gets the pinned handoff
+    manager.handle_input(&AppMode::Idle, "Next step").await;
+
+    let prompt = coder.received().last().unwrap().clone();
+    assert!(prompt.contains("Reviewer handoff reply"));
+    assert!(!prompt.contains("Review the parser"));
+    assert!(!prompt.contains(&note.content));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_capabilities_union_of_participants() {
+    let repository = MockPersonaRepository::new()
+        .with_persona(persona("coder", "Coder"))
+        .with_persona(Persona {
+            backend: PersonaBackend::OpenAiApi,
+            ..persona("advisor", "Advisor")
+        });
+    let agents = ScriptedAgentFactory::new()
+        .with_agent("coder", ScriptedAgent::new().repeat("Done."))
+        .with_agent("advisor", ScriptedAgent::new().repeat("Agreed."));
+    let manager = scripted_session(repository, agents.clone());
+
+    assert!(!PersonaBackend::OpenAiApi.capability_flags().file_write);
+    // Without a workspace root, no participant can touch files
+    let capabilities = manager.session_capabilities().await;
+    assert!(!capabilities.file_write);
+
+    let workspace = tempfile::TempDir::new().unwrap();
+    manager
+        .set_agent_workspace_root(Some(workspace.path().to_path_buf()))
+        .await;
+    let capabilities = manager.session_capabilities().await;
+    assert!(capabilities.file_write);
+    // The Claude CLI allowlist has no Bash tool
+    assert!(!capabilities.command_execute);
+
+    // Changing the web search setting rebuilds the participants' agents
+    manager.handle_input(&AppMode::Idle, "Hello").await;
+    assert_eq!(agents.created().len(), 2);
+    manager
+        .set_auto_chat_config(Some(AutoChatConfig {
+            web_search_enabled: false,
+            ..AutoChatConfig::default()
+        }))
+        .await;
+    manager.handle_input(&AppMode::Idle, "Again").await;
+    assert_eq!(agents.created().len(), 4);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_refresh_participant_metadata_picks_up_icon_change() {
+    let mut mai = Persona {
+        default_participant: false,
+        icon: Some("🦊".to_string()),
+        ..persona("mai", "Mai")
+    };
+    let repository = Arc::new(MockPersonaRepository::new().with_persona(mai.clone()));
+    let manager = restore_with_reply(repository.clone(), "mai").await;
+    let session = snapshot(&manager).await;
+    assert_eq!(session.participant_icons.get("mai").unwrap(), "🦊");
+
+    mai.icon = Some("🐺".to_string());
+    repository.save(&mai).await.unwrap();
+
+    // Metadata stays as-is until refreshed
+    let before = manager.participant_metadata().await;
+    assert_eq!(before.participant_icons.get("mai").unwrap(), "🦊");
+
+    let refreshed = manager.refresh_participant_metadata().await;
+    assert_eq!(refreshed.participant_icons.get("mai").unwrap(), "🐺");
+    assert_eq!(manager.participant_metadata().await, refreshed);
+
+    let session = snapshot(&manager).await;
+    assert_eq!(session.participant_icons.get("mai").unwrap(), "🐺");
+
+    // An uploaded icon wins over the emoji; removing it falls back again
+    mai.icon_asset = Some("ab12.png".to_string());
+    repository.save(&mai).await.unwrap();
+    let refreshed = manager.refresh_participant_metadata().await;
+    assert_eq!(
+        refreshed.participant_icons.get("mai").unwrap(),
+        "asset:ab12.png"
+    );
+
+    mai.icon_asset = None;
+    repository.save(&mai).await.unwrap();
+    let refreshed = manager.refresh_participant_metadata().await;
+    assert_eq!(refreshed.participant_icons.get("mai").unwrap(), "🐺");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_participant_models_use_app_default_model() {
+    let mut mai = Persona {
+        default_participant: false,
+        backend: PersonaBackend::GeminiApi,
+        ..persona("mai", "Mai")
+    };
+    let repository = Arc::new(MockPersonaRepository::new().with_persona(mai.clone()));
+    let manager = restore_with_reply(repository.clone(), "mai")
+        .await
+        .with_default_models(HashMap::from([(
+            PersonaBackend::GeminiApi,
+            "gemini-2.5-flash".to_string(),
+        )]));
+    let model = |metadata: &orcs_interaction::ParticipantMetadata| {
+        metadata.participant_models.get("mai").unwrap().clone()
+    };
+
+    let metadata = manager.refresh_participant_metadata().await;
+    assert_eq!(model(&metadata).as_deref(), Some("gemini-2.5-flash"));
+
+    // Persona model overrides the app default
+    mai.model_name = Some("gemini-2.5-pro".to_string());
+    repository.save(&mai).await.unwrap();
+    let metadata = manager.refresh_participant_metadata().await;
+    assert_eq!(model(&metadata).as_deref(), Some("gemini-2.5-pro"));
+
+    // Without either, the library default applies (recorded as None)
+    mai.model_name = None;
+    repository.save(&mai).await.unwrap();
+    manager.set_default_models(HashMap::new()).await;
+    let metadata = manager.refresh_participant_metadata().await;
+    assert_eq!(model(&metadata), None);
+}
+
+#[tokio::test]
+async fn test_effective_models_fall_back_to_backend_default() {
+    let repository = MockPersonaRepository::new()
+        .with_persona(Persona {
+            backend: PersonaBackend::ClaudeApi,
+            ..persona("mai", "Mai")
+        })
+        .with_persona(Persona {
+            backend: PersonaBackend::GeminiApi,
+            model_name: Some("gemini-2.5-pro".to_string()),
+            ..persona("yui", "Yui")
+        })
+        .with_persona(Persona {
+            backend: PersonaBackend::OpenAiApi,
+            ..persona("rin", "Rin")
+        });
+    let manager = new_session(Arc::new(repository), EnvSettings::default()).with_default_models(
+        HashMap::from([(PersonaBackend::OpenAiApi, "gpt-5-mini".to_string())]),
+    );
+
+    let models = manager.effective_models().await;
+    assert_eq!(
+        models,
+        HashMap::from([
+            ("mai".to_string(), "claude-sonnet-4-6".to_string()),
+            ("yui".to_string(), "gemini-2.5-pro".to_string()),
+            ("rin".to_string(), "gpt-5-mini".to_string()),
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_participant_suggestions_are_opt_in_and_respect_dismissal() {
+    let repository = || {
+        MockPersonaRepository::new()
+            .with_persona(Persona {
+                role: "Frontend Engineer".to_string(),
+                background: "Builds React components and CSS layouts.".to_string(),
+                ..persona("mai", "Mai")
+            })
+            .with_standby_persona(Persona {
+                role: "Database Engineer".to_string(),
+                background: "Tunes PostgreSQL queries and indexes.".to_string(),
+                ..persona("yui", "Yui")
+            })
+    };
+    let question = "Why is this PostgreSQL query so slow?";
+
+    let manager = scripted_session(repository(), ScriptedAgentFactory::new());
+    assert!(manager.suggest_participants(question).await.is_empty());
+
+    manager.set_participant_suggestions_enabled(true).await;
+    let suggestions = manager.suggest_participants(question).await;
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].name, "Yui");
+    assert_eq!(
+        serde_json::to_value(&suggestions[0].add_participant).unwrap(),
+        serde_json::json!({ "personaId": "yui" })
+    );
+    // Suggestions are never acted on
+    assert_eq!(
+        manager.get_active_participants().await.unwrap(),
+        vec!["mai"]
+    );
+
+    // The opt-in is persisted, dismissals only last for the running session
+    manager.dismiss_participant_suggestion("yui").await;
+    assert!(manager.suggest_participants(question).await.is_empty());
+    let session = snapshot(&manager).await;
+    assert!(session.participant_suggestions_enabled);
+    let restored = restore_scripted_session(session, repository(), ScriptedAgentFactory::new());
+    assert_eq!(restored.suggest_participants(question).await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_restore_warns_about_deleted_participant_personas() {
+    let presets = get_default_presets();
+    let repository = Arc::new(MockPersonaRepository::new().with_persona(presets[0].clone()));
+    let kept = presets[0].id.as_str();
+
+    let manager = restore_with_participants(
+        repository,
+        &[kept, "deleted-persona"],
+        &[
+            (kept, presets[0].name.as_str()),
+            ("deleted-persona", "Ghost"),
+        ],
+    )
+    .await;
+
+    let warnings = manager.restore_warnings().await;
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].persona_id, "deleted-persona");
+    assert_eq!(warnings[0].persona_name, "Ghost");
+    assert!(warnings[0].message.contains("Ghost"));
+    assert_eq!(
+        manager.get_active_participants().await.unwrap(),
+        vec![kept.to_string()]
+    );
+    assert_eq!(
+        system_contents(&snapshot(&manager).await),
+        vec![warnings[0].message.clone()]
+    );
+}
+
+#[tokio::test]
+async fn test_restore_without_surviving_participants_uses_defaults() {
+    let presets = get_default_presets();
+    let repository = Arc::new(MockPersonaRepository::new());
+    repository.save_all(&presets).await.unwrap();
+
+    let manager = restore_with_participants(
+        repository,
+        &["deleted-persona"],
+        &[("deleted-persona", "Ghost")],
+    )
+    .await;
+
+    let mut participants = manager.get_active_participants().await.unwrap();
+    participants.sort();
+    let mut defaults: Vec<String> = presets
+        .iter()
+        .filter(|p| p.default_participant)
+        .map(|p| p.id.clone())
+        .collect();
+    defaults.sort();
+    assert!(!defaults.is_empty());
+    assert_eq!(participants, defaults);
+
+    let notices = system_contents(&snapshot(&manager).await);
+    assert_eq!(notices.len(), 2);
+    assert!(notices[0].contains("Ghost"));
+    assert!(notices[1].contains("既定の参加者"));
+    assert_eq!(manager.restore_warnings().await.len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_empty_persona_repository_is_seeded_once() {
+    let repository = Arc::new(MockPersonaRepository::new());
+    let agents = ScriptedAgentFactory::new();
+    let manager = new_session(repository.clone(), EnvSettings::default())
+        .with_agent_factory(Arc::new(agents.clone()));
+
+    manager.handle_input(&AppMode::Idle, "Hello").await;
+    manager.invalidate_dialogue().await;
+    manager.handle_input(&AppMode::Idle, "Hello again").await;
+
+    let seeded = repository.get_all().await.unwrap();
+    assert_eq!(seeded.len(), get_default_presets().len());
+    // Both dialogues seat every seeded persona
+    assert_eq!(agents.created().len(), 2 * seeded.len());
+    // The seeded personas have no scripts, so their turns fail; that aside,
+    // the seeding is announced once
+    let notices: Vec<String> = snapshot(&manager)
+        .await
+        .system_messages
+        .iter()
+        .filter(|m| m.metadata.error_severity.is_none())
+        .map(|m| m.content.clone())
+        .collect();
+    assert_eq!(notices.len(), 1);
+    assert!(notices[0].contains("Jordan"));
+}
+
+#[tokio::test]
+async fn test_input_without_personas_explains_the_silence() {
+    /// Repository that has no personas and refuses writes.
+    struct ReadOnlyPersonaRepository;
+
+    #[async_trait::async_trait]
+    impl PersonaRepository for ReadOnlyPersonaRepository {
+        async fn find_by_id(&self, _: &str) -> orcs_core::error::Result<Option<Persona>> {
+            Ok(None)
+        }
+        async fn save(&self, _: &Persona) -> orcs_core::error::Result<()> {
+            Err(orcs_core::OrcsError::io("permission denied"))
+        }
+        async fn delete(&self, _: &str) -> orcs_core::error::Result<()> {
+            Ok(())
+        }
+        async fn get_all(&self) -> orcs_core::error::Result<Vec<Persona>> {
+            Ok(Vec::new())
+        }
+        async fn get_all_including_archived(&self) -> orcs_core::error::Result<Vec<Persona>> {
+            Ok(Vec::new())
+        }
+        async fn save_all(&self, _: &[Persona]) -> orcs_core::error::Result<()> {
+            Err(orcs_core::OrcsError::io("permission denied"))
+        }
+    }
+
+    let manager = InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(ReadOnlyPersonaRepository),
+        Arc::new(DefaultUserService),
+        EnvSettings::default(),
+    );
+
+    let result = manager
+        .handle_input_with_streaming(&AppMode::Idle, "Hello", None, |_| {})
+        .await;
+
+    assert_eq!(
+        result,
+        InteractionResult::NewMessage(NO_PERSONAS_MESSAGE.to_string())
+    );
+    let session = snapshot(&manager).await;
+    assert!(session.system_messages.is_empty());
+    // The user's message is still kept
+    assert!(
+        session
+            .persona_histories
+            .values()
+            .flatten()
+            .any(|m| m.content == "Hello")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_input_without_participants_gets_a_notice() {
+    let mai = ScriptedAgent::new().repeat("Hi!");
+    let manager = scripted_session(
+        MockPersonaRepository::new().with_persona(persona("mai", "Mai")),
+        ScriptedAgentFactory::new().with_agent("mai", mai.clone()),
+    );
+    manager.remove_participant("mai", false).await.unwrap();
+    assert!(manager.get_active_participants().await.unwrap().is_empty());
+
+    let result = manager
+        .handle_input_with_streaming(&AppMode::Idle, "Anyone there?", None, |_| {})
+        .await;
+
+    assert_eq!(
+        result,
+        InteractionResult::NewMessage(NO_PARTICIPANTS_MESSAGE.to_string())
+    );
+    assert_eq!(mai.calls(), 0);
+    let session = snapshot(&manager).await;
+    let notice = session.system_messages.last().unwrap();
+    assert_eq!(notice.content, NO_PARTICIPANTS_MESSAGE);
+    assert_eq!(
+        notice.metadata.system_event_type,
+        Some(SystemEventType::Notification)
+    );
+    // The user's message is still kept
+    assert!(
+        session
+            .persona_histories
+            .values()
+            .flatten()
+            .any(|m| m.content == "Anyone there?")
+    );
+}
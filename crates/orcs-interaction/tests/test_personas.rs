@@ -40,6 +40,12 @@ async fn test_save_and_load_personas() {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         },
         Persona {
             id: uuid::Uuid::new_v4().to_string(),
@@ -55,6 +61,12 @@ async fn test_save_and_load_personas() {
             base_color: None,
             gemini_options: None,
             kaiba_options: None,
+            context_mode_override: None,
+            icon_asset: None,
+            workspace_id: None,
+            fallback_backend: None,
+            reviewer_only: false,
+            archived: false,
         },
     ];
 
@@ -101,6 +113,12 @@ async fn test_persona_fields() {
         base_color: None,
         gemini_options: None,
         kaiba_options: None,
+        context_mode_override: None,
+        icon_asset: None,
+        workspace_id: None,
+        fallback_backend: None,
+        reviewer_only: false,
+        archived: false,
     };
 
     // Save
@@ -148,6 +166,12 @@ async fn test_multiple_personas_stored_separately() {
         base_color: None,
         gemini_options: None,
         kaiba_options: None,
+        context_mode_override: None,
+        icon_asset: None,
+        workspace_id: None,
+        fallback_backend: None,
+        reviewer_only: false,
+        archived: false,
     };
 
     let persona2 = Persona {
@@ -164,15 +188,21 @@ async fn test_multiple_personas_stored_separately() {
         base_color: None,
         gemini_options: None,
         kaiba_options: None,
+        context_mode_override: None,
+        icon_asset: None,
+        workspace_id: None,
+        fallback_backend: None,
+        reviewer_only: false,
+        archived: false,
     };
 
     // Save first persona
-    repo.save_all(&[persona1.clone()])
+    repo.save_all(std::slice::from_ref(&persona1))
         .await
         .expect("Should save first persona");
 
     // Save second persona
-    repo.save_all(&[persona2.clone()])
+    repo.save_all(std::slice::from_ref(&persona2))
         .await
         .expect("Should save second persona");
 
@@ -0,0 +1,139 @@
+//! Structured polls among the session's participants.
+
+use std::sync::{Arc, Mutex};
+
+use orcs_core::session::{AppMode, MessageRole};
+use orcs_infrastructure::{FlushPolicy, PartialTurnStore, StreamRecoveryWriter};
+use orcs_interaction::testing::{
+    MockPersonaRepository, ScriptedAgent, ScriptedAgentFactory, persona, scripted_session,
+};
+use orcs_interaction::{DialogueMessage, InteractionManager};
+
+/// A session whose participants reply to the poll with the given answers.
+fn poll_session(replies: &[(&str, &str)]) -> InteractionManager {
+    let mut personas = MockPersonaRepository::new();
+    let mut agents = ScriptedAgentFactory::new();
+    for &(name, reply) in replies {
+        let id = name.to_lowercase();
+        personas = personas.with_persona(persona(&id, name));
+        agents = agents.with_agent(&id, ScriptedAgent::new().reply(reply));
+    }
+    scripted_session(personas, agents)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_poll_tallies_votes_and_abstentions() {
+    let manager = poll_session(&[
+        ("Mai", "SQLite - simplest to operate."),
+        ("Ken", "I'd pick **sqlite**, Postgres is overkill."),
+        ("Rin", "Hard to say without load numbers."),
+    ]);
+
+    let streamed = Mutex::new(Vec::new());
+    let result = manager
+        .run_poll(
+            "Which database?",
+            vec!["Postgres".to_string(), "SQLite".to_string()],
+            Some(|m: &DialogueMessage| {
+                if m.thinking.is_none() {
+                    streamed.lock().unwrap().push(m.author.clone());
+                }
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.winners, vec!["SQLite".to_string()]);
+    assert_eq!(result.abstentions, 1);
+    let rin = result.votes.iter().find(|v| v.voter == "Rin").unwrap();
+    assert_eq!(rin.choice, None);
+
+    // Prompt, three replies and the tally are all streamed
+    assert_eq!(streamed.lock().unwrap().len(), 5);
+
+    let session = manager.to_session(AppMode::Idle, "ws".to_string()).await;
+    let replies = session
+        .persona_histories
+        .values()
+        .flatten()
+        .filter(|m| m.role == MessageRole::Assistant)
+        .count();
+    assert_eq!(replies, 3);
+
+    let tally = session
+        .system_messages
+        .iter()
+        .find(|m| m.metadata.system_message_type.as_deref() == Some("poll_result"))
+        .unwrap();
+    assert!(tally.content.contains("Rin: abstain"));
+    assert!(tally.content.contains("SQLite (2 of 3 votes)"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_agent_turns_are_recorded_for_crash_recovery() {
+    let store_dir = tempfile::TempDir::new().unwrap();
+    let store = Arc::new(PartialTurnStore::new(Some(store_dir.path())).await.unwrap());
+    let writer = StreamRecoveryWriter::spawn(
+        store.clone(),
+        FlushPolicy {
+            max_chunks: 1,
+            max_interval: std::time::Duration::from_secs(60),
+        },
+    );
+    let manager =
+        poll_session(&[("Mai", "SQLite"), ("Ken", "Postgres")]).with_stream_recovery(writer);
+
+    // No streaming callback: recording must not depend on a subscriber
+    manager
+        .run_poll(
+            "Which database?",
+            vec!["Postgres".to_string(), "SQLite".to_string()],
+            None::<fn(&DialogueMessage)>,
+        )
+        .await
+        .unwrap();
+
+    let mut recorded = Vec::new();
+    for _ in 0..100 {
+        if let Some(turn) = store.load(manager.session_id()).await.unwrap()
+            && turn.chunks.len() == 2
+        {
+            recorded = turn.chunks.into_iter().map(|c| c.author).collect();
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    recorded.sort();
+    assert_eq!(recorded, vec!["Ken".to_string(), "Mai".to_string()]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_poll_reports_tie() {
+    let manager = poll_session(&[
+        ("Mai", "Ship now. Users are waiting."),
+        ("Ken", "Wait, the tests are flaky."),
+    ]);
+
+    let result = manager
+        .run_poll(
+            "Release today?",
+            vec!["Ship now".to_string(), "Wait".to_string()],
+            None::<fn(&DialogueMessage)>,
+        )
+        .await
+        .unwrap();
+
+    assert!(result.is_tie());
+    assert!(result.summary().contains("tie between Ship now, Wait"));
+
+    assert!(
+        manager
+            .run_poll(
+                "Release today?",
+                vec!["Ship now".to_string()],
+                None::<fn(&DialogueMessage)>
+            )
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,521 @@
+//! What reaches each persona's prompt: history, environment, session modes and
+//! per-persona hints.
+
+use std::sync::{Arc, Mutex};
+
+use llm_toolkit::agent::dialogue::ExecutionModel;
+use llm_toolkit::agent::history::HistoryAwareAgent;
+use llm_toolkit::agent::{Agent, AgentError, Payload};
+use orcs_core::clock::Clock;
+use orcs_core::config::{DebugSettings, EnvSettings, MemorySyncSettings};
+use orcs_core::persona::Persona;
+use orcs_core::session::{
+    AppMode, AutoChatConfig, ContextMode, ConversationMessage, ConversationMode, FeedbackKind,
+    MessageMetadata, MessageRole, Session, SystemEventType,
+};
+use orcs_core::user::{DefaultUserService, UserProfile, UserService};
+use orcs_interaction::testing::{
+    MockClock, MockPersonaRepository, ScriptedAgent, ScriptedAgentFactory, persona,
+    restore_scripted_session, scripted_session,
+};
+use orcs_interaction::{AgentFactory, DEFAULT_DIALOGUE_ENVIRONMENT, InteractionManager};
+
+async fn snapshot(manager: &InteractionManager) -> Session {
+    manager.to_session(AppMode::Idle, "ws".to_string()).await
+}
+
+/// User service for a team; the current user is "alice".
+struct TeamUserService;
+
+#[async_trait::async_trait]
+impl UserService for TeamUserService {
+    fn get_user_name(&self) -> String {
+        "alice".to_string()
+    }
+
+    fn get_user_profile(&self) -> UserProfile {
+        self.get_user("alice").unwrap()
+    }
+
+    fn get_user(&self, id: &str) -> Option<UserProfile> {
+        let nickname = match id {
+            "alice" => "Alice",
+            "bob" => "Bob",
+            _ => return None,
+        };
+        Some(UserProfile {
+            nickname: nickname.to_string(),
+            background: String::new(),
+            timezone: Some("+09:00".to_string()),
+        })
+    }
+
+    fn get_debug_settings(&self) -> DebugSettings {
+        DebugSettings::default()
+    }
+
+    async fn update_debug_settings(
+        &self,
+        _enable_llm_debug: bool,
+        _log_level: String,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+
+    fn get_memory_sync_settings(&self) -> MemorySyncSettings {
+        MemorySyncSettings::default()
+    }
+}
+
+/// Records the speaker of the latest message of every prompt.
+struct SpeakerSpyAgent {
+    speakers: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Agent for SpeakerSpyAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        static EXPERTISE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+        EXPERTISE.get_or_init(|| "Speaker spy for testing".to_string())
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        if let Some(message) = payload.to_messages().last() {
+            let speaker = message.speaker.name().to_string();
+            self.speakers.lock().unwrap().push(speaker);
+        }
+        Ok("Noted.".to_string())
+    }
+}
+
+struct SpeakerSpyFactory {
+    speakers: Arc<Mutex<Vec<String>>>,
+}
+
+impl AgentFactory for SpeakerSpyFactory {
+    fn create_agent(
+        &self,
+        _persona: &Persona,
+        _keep_history: bool,
+    ) -> Box<dyn Agent<Output = String, Expertise = String>> {
+        Box::new(SpeakerSpyAgent {
+            speakers: self.speakers.clone(),
+        })
+    }
+}
+
+/// Agent that records every payload it receives, messages included.
+struct PayloadSpy {
+    expertise: String,
+    payloads: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Agent for PayloadSpy {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        &self.expertise
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        self.payloads.lock().unwrap().push(format!("{:?}", payload));
+        Ok("ok".to_string())
+    }
+}
+
+/// Factory whose agents record their payloads, wrapped in chat history the
+/// way real backends are.
+struct HistorySpyFactory {
+    payloads: Arc<Mutex<Vec<String>>>,
+}
+
+impl AgentFactory for HistorySpyFactory {
+    fn create_agent(
+        &self,
+        persona: &Persona,
+        keep_history: bool,
+    ) -> Box<dyn Agent<Output = String, Expertise = String>> {
+        let spy = PayloadSpy {
+            expertise: persona.role.clone(),
+            payloads: self.payloads.clone(),
+        };
+        if keep_history {
+            Box::new(HistoryAwareAgent::new(spy))
+        } else {
+            Box::new(spy)
+        }
+    }
+}
+
+/// Runs a user turn with `input`, then one continuation turn started by a
+/// system message.
+async fn run_with_continuation(manager: &InteractionManager, input: &str) {
+    manager
+        .set_auto_chat_config(Some(AutoChatConfig {
+            max_iterations: 2,
+            iteration_delay_ms: Some(0),
+            ..Default::default()
+        }))
+        .await;
+    manager
+        .execute_auto_chat(input, None, |_| {}, |_, _| {}, None)
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_clean_override_persona_receives_no_history() {
+    let repository = || {
+        MockPersonaRepository::new()
+            .with_persona(Persona {
+                context_mode_override: Some(ContextMode::Clean),
+                ..persona("reviewer", "Reviewer")
+            })
+            .with_persona(persona("coder", "Coder"))
+    };
+    let mut session = snapshot(&scripted_session(repository(), ScriptedAgentFactory::new())).await;
+    session.persona_histories.insert(
+        DefaultUserService.get_user_name(),
+        vec![ConversationMessage {
+            role: MessageRole::User,
+            content: "Earlier question".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            metadata: MessageMetadata::default(),
+            attachments: vec![],
+        }],
+    );
+    let reviewer = ScriptedAgent::new().repeat("Reviewer reply");
+    let coder = ScriptedAgent::new().repeat("Coder reply");
+    let agents = ScriptedAgentFactory::new()
+        .with_agent("reviewer", reviewer.clone())
+        .with_agent("coder", coder.clone());
+    let manager = restore_scripted_session(session, repository(), agents.clone());
+
+    manager.handle_input(&AppMode::Idle, "New question").await;
+    manager
+        .handle_input(&AppMode::Idle, "Follow-up question")
+        .await;
+
+    let (reviewer, coder) = (reviewer.received(), coder.received());
+    assert!(!reviewer[0].contains("Earlier question"));
+    assert!(coder[0].contains("Earlier question"));
+    assert!(reviewer[0].contains("New question"));
+
+    assert!(reviewer[1].contains("Follow-up question"));
+    assert!(!reviewer[1].contains("Coder reply"));
+    assert!(coder[1].contains("Reviewer reply"));
+
+    let created = agents.created();
+    assert!(created.contains(&("reviewer".to_string(), false)));
+    assert!(created.contains(&("coder".to_string(), true)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_custom_environment_description_is_applied_to_dialogue() {
+    let coder = ScriptedAgent::new().repeat("Hi!");
+    let manager = InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(MockPersonaRepository::new().with_persona(persona("coder", "Coder"))),
+        Arc::new(DefaultUserService),
+        EnvSettings {
+            environment_description: Some("Acme Assistant workspace".to_string()),
+            ..Default::default()
+        },
+    )
+    .with_agent_factory(Arc::new(
+        ScriptedAgentFactory::new().with_agent("coder", coder.clone()),
+    ));
+
+    manager.handle_input(&AppMode::Idle, "Hello").await;
+
+    let prompt = &coder.received()[0];
+    assert!(prompt.contains("Acme Assistant workspace"));
+    assert!(!prompt.contains(DEFAULT_DIALOGUE_ENVIRONMENT));
+}
+
+#[tokio::test]
+async fn test_user_turns_are_attributed_to_the_acting_user() {
+    let speakers = Arc::new(Mutex::new(Vec::new()));
+    let manager = InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(MockPersonaRepository::new().with_persona(persona("mai", "Mai"))),
+        Arc::new(TeamUserService),
+        EnvSettings::default(),
+    )
+    .with_agent_factory(Arc::new(SpeakerSpyFactory {
+        speakers: speakers.clone(),
+    }));
+
+    manager.set_acting_user(Some("bob".to_string())).await;
+    manager.handle_input(&AppMode::Idle, "Bob here").await;
+    manager.set_acting_user(None).await;
+    manager.handle_input(&AppMode::Idle, "Alice here").await;
+
+    assert_eq!(*speakers.lock().unwrap(), vec!["Bob", "Alice"]);
+
+    // The history keeps each turn with its author
+    let session = snapshot(&manager).await;
+    let user_turns = |user_id: &str| -> Vec<String> {
+        session.persona_histories[user_id]
+            .iter()
+            .filter(|m| m.role == MessageRole::User)
+            .map(|m| m.content.clone())
+            .collect()
+    };
+    assert_eq!(user_turns("bob"), vec!["Bob here"]);
+    assert_eq!(user_turns("alice"), vec!["Alice here"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_style_calibration_hint_reaches_only_that_persona() {
+    let reviewer = ScriptedAgent::new().repeat("Reviewer reply");
+    let coder = ScriptedAgent::new().repeat("Coder reply");
+    let manager = scripted_session(
+        MockPersonaRepository::new()
+            .with_persona(persona("reviewer", "Reviewer"))
+            .with_persona(persona("coder", "Coder")),
+        ScriptedAgentFactory::new()
+            .with_agent("reviewer", reviewer.clone())
+            .with_agent("coder", coder.clone()),
+    );
+    assert!(
+        manager
+            .record_feedback("reviewer", FeedbackKind::TooLong)
+            .await
+            .is_err()
+    );
+    manager.set_style_calibration_enabled(true).await;
+
+    manager.handle_input(&AppMode::Idle, "Hello").await;
+    for _ in 0..3 {
+        manager
+            .record_feedback("reviewer", FeedbackKind::TooLong)
+            .await
+            .unwrap();
+    }
+    manager.handle_input(&AppMode::Idle, "Again").await;
+
+    let hint = FeedbackKind::TooLong.instruction().unwrap();
+    assert_eq!((reviewer.calls(), coder.calls()), (2, 2));
+    assert!(reviewer.received()[1].contains(hint));
+    assert!(!coder.received()[1].contains(hint));
+
+    let session = snapshot(&manager).await;
+    assert!(session.style_calibration_enabled);
+    assert!(session.system_messages.iter().any(|m| {
+        m.metadata.system_event_type == Some(SystemEventType::StyleCalibrationChanged)
+    }));
+}
+
+#[tokio::test]
+async fn test_everyone_mention_reaches_all_participants() {
+    let [mai, yui, rin] =
+        ["Mai", "Yui", "Rin"].map(|name| ScriptedAgent::new().repeat(format!("{} here.", name)));
+    let manager = scripted_session(
+        MockPersonaRepository::new()
+            .with_persona(persona("mai", "Mai"))
+            .with_persona(persona("yui", "Yui"))
+            .with_persona(persona("rin", "Rin")),
+        ScriptedAgentFactory::new()
+            .with_agent("mai", mai.clone())
+            .with_agent("yui", yui.clone())
+            .with_agent("rin", rin.clone()),
+    );
+    manager
+        .set_execution_strategy(ExecutionModel::Mentioned {
+            strategy: Default::default(),
+        })
+        .await;
+    let calls = || [mai.calls(), yui.calls(), rin.calls()];
+
+    manager
+        .handle_input_with_streaming(&AppMode::Idle, "@Mai ready?", None, |_| {})
+        .await;
+    assert_eq!(calls(), [1, 0, 0]);
+
+    manager
+        .handle_input_with_streaming(&AppMode::Idle, "@everyone thoughts?", None, |_| {})
+        .await;
+    assert_eq!(calls(), [2, 1, 1]);
+}
+
+#[tokio::test]
+async fn test_conversation_mode_instruction_applies_in_clean_mode() {
+    let instruction = ConversationMode::Brief.system_instruction().unwrap();
+    for applied_in_clean in [true, false] {
+        let payloads = Arc::new(Mutex::new(Vec::new()));
+        let manager = InteractionManager::new_session(
+            uuid::Uuid::new_v4().to_string(),
+            Arc::new(MockPersonaRepository::new().with_persona(persona("mai", "Mai"))),
+            Arc::new(DefaultUserService),
+            EnvSettings::default(),
+        )
+        .with_agent_factory(Arc::new(HistorySpyFactory {
+            payloads: payloads.clone(),
+        }));
+        manager.set_context_mode(ContextMode::Clean).await;
+        manager.set_conversation_mode(ConversationMode::Brief).await;
+        manager
+            .set_conversation_mode_applied_in_clean(applied_in_clean)
+            .await;
+
+        run_with_continuation(&manager, "Hello").await;
+
+        let payloads = payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 2);
+        for payload in payloads.iter() {
+            assert_eq!(payload.contains(instruction), applied_in_clean);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_conversation_mode_instruction_is_sent_once_per_prompt() {
+    let brief = ConversationMode::Brief.system_instruction().unwrap();
+    let concise = ConversationMode::Concise.system_instruction().unwrap();
+    let payloads = Arc::new(Mutex::new(Vec::new()));
+    let manager = InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(MockPersonaRepository::new().with_persona(persona("mai", "Mai"))),
+        Arc::new(DefaultUserService),
+        EnvSettings::default(),
+    )
+    .with_agent_factory(Arc::new(HistorySpyFactory {
+        payloads: payloads.clone(),
+    }));
+    manager.set_conversation_mode(ConversationMode::Brief).await;
+
+    for turn in 1..=3 {
+        manager
+            .handle_input(&AppMode::Idle, &format!("Question {}", turn))
+            .await;
+    }
+    run_with_continuation(&manager, "Question 4").await;
+    {
+        let payloads = payloads.lock().unwrap();
+        assert_eq!(payloads.len(), 5);
+        let last = payloads.last().unwrap();
+        // Earlier turns are replayed as history, the instruction is not
+        assert!(last.contains("Question 4"));
+        assert_eq!(last.matches(brief).count(), 1);
+    }
+
+    // A mode change mid-session takes effect on the very next turn
+    manager
+        .set_conversation_mode(ConversationMode::Concise)
+        .await;
+    manager.handle_input(&AppMode::Idle, "Question 5").await;
+    let payloads = payloads.lock().unwrap();
+    assert_eq!(payloads.len(), 6);
+    let last = payloads.last().unwrap();
+    assert_eq!(last.matches(concise).count(), 1);
+    assert!(!last.contains(brief));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_clock_context_reflects_the_clock_and_respects_the_toggle() {
+    let clock = MockClock::new(chrono::Utc::now());
+    let mai = ScriptedAgent::new().repeat("Noted.");
+    let manager = InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(MockPersonaRepository::new().with_persona(persona("mai", "Mai"))),
+        Arc::new(TeamUserService),
+        EnvSettings::default(),
+    )
+    .with_agent_factory(Arc::new(
+        ScriptedAgentFactory::new().with_agent("mai", mai.clone()),
+    ))
+    .with_clock(Arc::new(clock.clone()));
+    let now_line = |clock: &MockClock| {
+        let tokyo = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+        format!(
+            "Now: {} UTC+09:00",
+            clock.now().with_timezone(&tokyo).format("%Y-%m-%d %H:%M")
+        )
+    };
+
+    manager.handle_input(&AppMode::Idle, "First").await;
+    let first = &mai.received()[0];
+    assert!(first.contains(&format!("{}; session started just now", now_line(&clock))));
+    assert!(!first.contains("last user message"));
+
+    clock.advance(chrono::Duration::minutes(125));
+    manager.handle_input(&AppMode::Idle, "Second").await;
+    assert!(mai.received()[1].contains(&format!(
+        "{}; session started 2h ago; last user message 2h ago",
+        now_line(&clock)
+    )));
+
+    // Clean context mode and the session toggle both leave it out
+    manager.set_context_mode(ContextMode::Clean).await;
+    manager.handle_input(&AppMode::Idle, "Third").await;
+    assert!(!mai.received()[2].contains("Now:"));
+    manager.set_context_mode(ContextMode::Rich).await;
+    manager.set_clock_context_enabled(false).await;
+    manager.handle_input(&AppMode::Idle, "Fourth").await;
+    assert!(!mai.received()[3].contains("Now:"));
+
+    // Never persisted with the history
+    let session = snapshot(&manager).await;
+    assert!(!session.clock_context_enabled);
+    assert!(
+        session
+            .persona_histories
+            .values()
+            .flatten()
+            .all(|message| !message.content.contains("Now:"))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_agent_pinboard_appends_require_permission() {
+    let repository = || MockPersonaRepository::new().with_persona(persona("mai", "Mai"));
+    for allowed in [false, true] {
+        let mai = ScriptedAgent::new()
+            .reply("Agreed.\n<Pinboard>Use UTC everywhere</Pinboard>")
+            .repeat("Noted.");
+        let agents = ScriptedAgentFactory::new().with_agent("mai", mai.clone());
+        let manager = scripted_session(repository(), agents.clone());
+        manager
+            .set_pinboard(Some("Decisions so far".to_string()))
+            .await;
+        manager.set_pinboard_agent_edits_enabled(allowed).await;
+
+        manager
+            .handle_input_with_streaming(&AppMode::Idle, "Which timezone?", None, |_| {})
+            .await;
+        manager
+            .handle_input_with_streaming(&AppMode::Idle, "Next topic", None, |_| {})
+            .await;
+
+        let session = snapshot(&manager).await;
+        let audit_events = session
+            .system_messages
+            .iter()
+            .filter(|m| m.metadata.system_event_type == Some(SystemEventType::PinboardUpdated))
+            .count();
+        let pinboard = "Decisions so far\n\nUse UTC everywhere";
+        if allowed {
+            assert_eq!(manager.get_pinboard().await.as_deref(), Some(pinboard));
+            assert_eq!(audit_events, 1);
+            // The next turn rebuilds the dialogue to show the updated pinboard
+            assert_eq!(agents.created().len(), 2);
+            assert!(mai.received()[1].contains(pinboard));
+        } else {
+            assert_eq!(
+                manager.get_pinboard().await.as_deref(),
+                Some("Decisions so far")
+            );
+            assert_eq!(audit_events, 0);
+            assert_eq!(agents.created().len(), 1);
+        }
+
+        let restored = restore_scripted_session(session, repository(), ScriptedAgentFactory::new());
+        assert_eq!(restored.get_pinboard().await, manager.get_pinboard().await);
+        assert_eq!(restored.is_pinboard_agent_edits_enabled().await, allowed);
+    }
+}
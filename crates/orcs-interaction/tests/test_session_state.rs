@@ -0,0 +1,294 @@
+//! Session bookkeeping outside of turns: spawned task links, dialogue
+//! invalidation and the deltas written by autosave.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use orcs_core::config::EnvSettings;
+use orcs_core::repository::SessionRepository;
+use orcs_core::session::{AppMode, ConversationMessage, MessageMetadata, MessageRole, Session};
+use orcs_core::user::DefaultUserService;
+use orcs_interaction::testing::{
+    MockPersonaRepository, ScriptedAgent, ScriptedAgentFactory, persona, scripted_session,
+};
+use orcs_interaction::{InteractionManager, SystemMessageEntry};
+
+async fn snapshot(manager: &InteractionManager) -> Session {
+    manager.to_session(AppMode::Idle, "ws".to_string()).await
+}
+
+/// Collects formatted log output for assertions.
+struct LogWriter(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Write operations received by a test session repository.
+#[derive(Default)]
+struct OperationLog {
+    operations: Mutex<Vec<String>>,
+}
+
+impl OperationLog {
+    fn push(&self, operation: String) {
+        self.operations.lock().unwrap().push(operation);
+    }
+
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.operations.lock().unwrap())
+    }
+}
+
+/// Implements only the required methods, like backends predating delta saves.
+#[derive(Default)]
+struct LegacySessionRepository {
+    log: OperationLog,
+}
+
+#[async_trait::async_trait]
+impl SessionRepository for LegacySessionRepository {
+    async fn find_by_id(&self, _session_id: &str) -> orcs_core::error::Result<Option<Session>> {
+        Ok(None)
+    }
+
+    async fn save(&self, _session: &Session) -> orcs_core::error::Result<()> {
+        self.log.push("save".to_string());
+        Ok(())
+    }
+
+    async fn delete(&self, _session_id: &str) -> orcs_core::error::Result<()> {
+        Ok(())
+    }
+
+    async fn list_all(&self) -> orcs_core::error::Result<Vec<Session>> {
+        Ok(vec![])
+    }
+}
+
+/// Writes deltas through its own append and metadata operations.
+#[derive(Default)]
+struct DeltaAwareSessionRepository {
+    log: OperationLog,
+}
+
+#[async_trait::async_trait]
+impl SessionRepository for DeltaAwareSessionRepository {
+    async fn find_by_id(&self, _session_id: &str) -> orcs_core::error::Result<Option<Session>> {
+        Ok(None)
+    }
+
+    async fn save(&self, _session: &Session) -> orcs_core::error::Result<()> {
+        self.log.push("save".to_string());
+        Ok(())
+    }
+
+    async fn append_messages(
+        &self,
+        _session: &Session,
+        appended: &HashMap<String, std::ops::Range<usize>>,
+    ) -> orcs_core::error::Result<()> {
+        let mut appended: Vec<_> = appended.iter().collect();
+        appended.sort_by_key(|(persona_id, _)| persona_id.as_str());
+        for (persona_id, range) in appended {
+            self.log.push(format!("append {} {:?}", persona_id, range));
+        }
+        Ok(())
+    }
+
+    async fn save_metadata(&self, _session: &Session) -> orcs_core::error::Result<()> {
+        self.log.push("metadata".to_string());
+        Ok(())
+    }
+
+    async fn delete(&self, _session_id: &str) -> orcs_core::error::Result<()> {
+        Ok(())
+    }
+
+    async fn list_all(&self) -> orcs_core::error::Result<Vec<Session>> {
+        Ok(vec![])
+    }
+}
+
+/// Saves the manager's changes the way the autosaver does.
+async fn autosave(manager: &InteractionManager, repository: &dyn SessionRepository) {
+    let session = snapshot(manager).await;
+    let delta = manager.take_dirty_delta(&session);
+    repository.save_delta(&session, &delta).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_spawned_tasks_are_linked_to_their_message() {
+    let message = |content: &str, timestamp: &str| ConversationMessage {
+        role: MessageRole::Assistant,
+        content: content.to_string(),
+        timestamp: timestamp.to_string(),
+        metadata: MessageMetadata::default(),
+        attachments: vec![],
+    };
+    let mut session = snapshot(&scripted_session(
+        MockPersonaRepository::new(),
+        ScriptedAgentFactory::new(),
+    ))
+    .await;
+    session.persona_histories.insert(
+        "mai".to_string(),
+        vec![
+            message(
+                "Let's refactor the parser.",
+                "2026-01-16T14:09:41.495123+00:00",
+            ),
+            message("And add tests.", "2026-01-16T14:10:02.000000+00:00"),
+        ],
+    );
+    let manager = InteractionManager::from_session(
+        session,
+        Arc::new(MockPersonaRepository::new()),
+        Arc::new(DefaultUserService),
+        EnvSettings::default(),
+    );
+    manager
+        .add_system_conversation_message("Executing task".to_string(), None, None)
+        .await;
+
+    // The frontend sends millisecond timestamps with a 'Z' suffix
+    let origin = manager
+        .link_spawned_task(Some("2026-01-16T14:09:41.495Z"), "task-1")
+        .await
+        .unwrap();
+    assert_eq!(origin.author, "mai");
+    assert_eq!(origin.message_timestamp, "2026-01-16T14:09:41.495123+00:00");
+
+    // Without a timestamp, the latest message is the origin
+    let latest = manager.link_spawned_task(None, "task-2").await.unwrap();
+    assert_eq!(latest.author, "system");
+
+    assert!(
+        manager
+            .link_spawned_task(Some("2025-01-01T00:00:00.000Z"), "task-3")
+            .await
+            .is_none()
+    );
+
+    let session = snapshot(&manager).await;
+    let spawned = &session.persona_histories["mai"][0]
+        .metadata
+        .spawned_task_ids;
+    assert_eq!(spawned, &vec!["task-1".to_string()]);
+    assert_eq!(
+        session.system_messages[0].metadata.spawned_task_ids,
+        vec!["task-2".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_batched_context_info_invalidates_dialogue_once() {
+    let manager = scripted_session(MockPersonaRepository::new(), ScriptedAgentFactory::new());
+    let entry = |content: &str, message_type: &str| SystemMessageEntry {
+        content: content.to_string(),
+        message_type: Some(message_type.to_string()),
+        error_severity: None,
+    };
+
+    let logs = Arc::new(Mutex::new(Vec::new()));
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_max_level(tracing::Level::DEBUG)
+        .with_writer(move || LogWriter(writer.clone()))
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+    let invalidations = || {
+        String::from_utf8(logs.lock().unwrap().clone())
+            .unwrap()
+            .matches("Dialogue invalidated")
+            .count()
+    };
+
+    manager
+        .add_system_conversation_messages(vec![
+            entry("Result 1", "context_info"),
+            entry("Result 2", "context_info"),
+            entry("Result 3", "context_info"),
+        ])
+        .await;
+    assert_eq!(invalidations(), 1);
+    let contents: Vec<String> = snapshot(&manager)
+        .await
+        .system_messages
+        .iter()
+        .map(|m| m.content.clone())
+        .collect();
+    assert_eq!(contents, vec!["Result 1", "Result 2", "Result 3"]);
+
+    // Batches without context info leave the dialogue alone, single writes
+    // of context info still invalidate each time
+    manager
+        .add_system_conversation_messages(vec![entry("Saved file", "file_save")])
+        .await;
+    assert_eq!(invalidations(), 1);
+    manager
+        .add_system_conversation_message("$ ls".to_string(), Some("shell_output".to_string()), None)
+        .await;
+    assert_eq!(invalidations(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_autosave_appends_new_messages_and_falls_back_to_full_save() {
+    let delta_aware = DeltaAwareSessionRepository::default();
+    let legacy = LegacySessionRepository::default();
+    for (repository, log, append, metadata) in [
+        (
+            &delta_aware as &dyn SessionRepository,
+            &delta_aware.log,
+            &["append mai 1..2", "append user 1..2"][..],
+            "metadata",
+        ),
+        (&legacy, &legacy.log, &["save"][..], "save"),
+    ] {
+        let manager = scripted_session(
+            MockPersonaRepository::new().with_persona(persona("mai", "Mai")),
+            ScriptedAgentFactory::new().with_agent("mai", ScriptedAgent::new().repeat("Done.")),
+        );
+
+        // The first save writes the whole session
+        autosave(&manager, repository).await;
+        assert_eq!(log.take(), ["save"]);
+        autosave(&manager, repository).await;
+        assert!(log.take().is_empty(), "nothing changed, nothing written");
+
+        // The first turn also adds mai to the participants
+        manager.handle_input(&AppMode::Idle, "Start").await;
+        autosave(&manager, repository).await;
+        assert_eq!(log.take(), ["save"]);
+
+        manager.handle_input(&AppMode::Idle, "Finish it").await;
+        autosave(&manager, repository).await;
+        assert_eq!(log.take(), append);
+
+        // Editing a saved message cannot be appended
+        let timestamp = snapshot(&manager).await.persona_histories["mai"]
+            .iter()
+            .find(|m| m.role == MessageRole::Assistant)
+            .unwrap()
+            .timestamp
+            .clone();
+        manager
+            .update_message_content("mai", &timestamp, "Done, and tested.".to_string())
+            .await
+            .unwrap();
+        autosave(&manager, repository).await;
+        assert_eq!(log.take(), ["save"]);
+
+        manager.set_mute(true).await;
+        autosave(&manager, repository).await;
+        assert_eq!(log.take(), [metadata]);
+    }
+}
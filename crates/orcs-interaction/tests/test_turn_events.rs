@@ -0,0 +1,618 @@
+//! Events streamed while a turn runs and what each turn leaves in the session:
+//! thinking and heartbeat events, errors, split replies and debug captures.
+
+use std::sync::{Arc, Mutex};
+
+use llm_toolkit::agent::dialogue::ExecutionModel;
+use llm_toolkit::agent::{Agent, AgentError, Payload};
+use orcs_core::config::{DebugSettings, EnvSettings, MemorySyncSettings};
+use orcs_core::persona::Persona;
+use orcs_core::session::{AppMode, ErrorSeverity, MessageRole, Session};
+use orcs_core::user::{DefaultUserService, UserProfile, UserService};
+use orcs_infrastructure::{WireLog, WireLogEntry};
+use orcs_interaction::heartbeat::{self, OutputActivity};
+use orcs_interaction::testing::{
+    MockPersonaRepository, ScriptedAgent, ScriptedAgentFactory, persona, restore_scripted_session,
+    scripted_session,
+};
+use orcs_interaction::{
+    AgentFactory, InteractionManager, InteractionResult, StreamingDialogueTurnKind, TurnFailure,
+};
+
+async fn snapshot(manager: &InteractionManager) -> Session {
+    manager.to_session(AppMode::Idle, "ws".to_string()).await
+}
+
+fn mai_session(mai: ScriptedAgent) -> InteractionManager {
+    scripted_session(
+        MockPersonaRepository::new().with_persona(persona("mai", "Mai")),
+        ScriptedAgentFactory::new().with_agent("mai", mai),
+    )
+}
+
+fn rate_limited() -> AgentError {
+    AgentError::ProcessError {
+        status_code: Some(429),
+        message: "Too many requests".to_string(),
+        is_retryable: false,
+        retry_after: None,
+    }
+}
+
+/// Runs a turn, returning the kinds of the events streamed during it.
+async fn streamed_turn(
+    manager: &InteractionManager,
+    input: &str,
+) -> Vec<StreamingDialogueTurnKind> {
+    let streamed = Mutex::new(Vec::new());
+    manager
+        .handle_input_with_streaming(&AppMode::Idle, input, None, |turn| {
+            streamed.lock().unwrap().push(turn.to_streaming_kind());
+        })
+        .await;
+    streamed.into_inner().unwrap()
+}
+
+/// User service with LLM debug mode switched on.
+struct LlmDebugUserService;
+
+#[async_trait::async_trait]
+impl UserService for LlmDebugUserService {
+    fn get_user_name(&self) -> String {
+        "user".to_string()
+    }
+
+    fn get_user_profile(&self) -> UserProfile {
+        UserProfile::default()
+    }
+
+    fn get_debug_settings(&self) -> DebugSettings {
+        DebugSettings {
+            enable_llm_debug: true,
+            ..Default::default()
+        }
+    }
+
+    async fn update_debug_settings(
+        &self,
+        _enable_llm_debug: bool,
+        _log_level: String,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+
+    fn get_memory_sync_settings(&self) -> MemorySyncSettings {
+        MemorySyncSettings::default()
+    }
+}
+
+/// Logs a failed exchange to the session's wire log, then fails.
+struct WireLoggedFailingAgent {
+    log: WireLog,
+    session_id: String,
+}
+
+#[async_trait::async_trait]
+impl Agent for WireLoggedFailingAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        static EXPERTISE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+        EXPERTISE.get_or_init(|| "Failing agent for testing".to_string())
+    }
+
+    async fn execute(&self, payload: Payload) -> Result<String, AgentError> {
+        let tap = self.log.tap(&self.session_id, "mai", "claude_api").unwrap();
+        tap.record(
+            "https://api.example.com",
+            &payload.to_text(),
+            Err(r#"{"error":"overloaded"}"#),
+        );
+        Err(AgentError::ExecutionFailed("Overloaded".to_string()))
+    }
+}
+
+struct WireLoggedFailingFactory {
+    log: WireLog,
+    session_id: String,
+}
+
+impl AgentFactory for WireLoggedFailingFactory {
+    fn create_agent(
+        &self,
+        _persona: &Persona,
+        _keep_history: bool,
+    ) -> Box<dyn Agent<Output = String, Expertise = String>> {
+        Box::new(WireLoggedFailingAgent {
+            log: self.log.clone(),
+            session_id: self.session_id.clone(),
+        })
+    }
+}
+
+/// Runs a slow shell script, reporting its output like a CLI backend would.
+struct SlowScriptAgent {
+    script: &'static str,
+    activity: OutputActivity,
+}
+
+#[async_trait::async_trait]
+impl Agent for SlowScriptAgent {
+    type Output = String;
+    type Expertise = String;
+
+    fn expertise(&self) -> &String {
+        static EXPERTISE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+        EXPERTISE.get_or_init(|| "Slow CLI agent for testing".to_string())
+    }
+
+    async fn execute(&self, _payload: Payload) -> Result<String, AgentError> {
+        let output = heartbeat::run_reporting_output(
+            tokio::process::Command::new("sh").args(["-c", self.script]),
+            &self.activity,
+            "Mai",
+        )
+        .await
+        .map_err(|e| AgentError::ExecutionFailed(e.to_string()))?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+struct SlowScriptFactory {
+    script: &'static str,
+    activity: OutputActivity,
+}
+
+impl AgentFactory for SlowScriptFactory {
+    fn create_agent(
+        &self,
+        _persona: &Persona,
+        _keep_history: bool,
+    ) -> Box<dyn Agent<Output = String, Expertise = String>> {
+        Box::new(SlowScriptAgent {
+            script: self.script,
+            activity: self.activity.clone(),
+        })
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_background_input_persists_turns_without_subscriber() {
+    let manager = Arc::new(mai_session(ScriptedAgent::new().reply("Background reply")));
+
+    let snapshots: Arc<Mutex<Vec<Session>>> = Default::default();
+    let persist = {
+        let manager = manager.clone();
+        let snapshots = snapshots.clone();
+        move || {
+            let manager = manager.clone();
+            let snapshots = snapshots.clone();
+            async move {
+                let session = snapshot(&manager).await;
+                snapshots.lock().unwrap().push(session);
+            }
+        }
+    };
+
+    let handle = manager
+        .handle_input_background(AppMode::Idle, "hello".to_string(), None, |_| {}, persist)
+        .await
+        .unwrap();
+    // The session is claimed before the task runs, so a second start is refused
+    assert!(manager.is_generating().await);
+    assert!(
+        manager
+            .handle_input_background(
+                AppMode::Idle,
+                "again".to_string(),
+                None,
+                |_| {},
+                || async {}
+            )
+            .await
+            .is_none()
+    );
+    let result = handle.await.unwrap();
+
+    assert!(matches!(result, InteractionResult::NewDialogueMessages(ref m) if m.len() == 1));
+    assert!(!manager.is_generating().await);
+
+    let snapshots = snapshots.lock().unwrap();
+    assert!(snapshots.len() >= 3);
+    assert!(snapshots[0].is_generating);
+    assert!(!snapshots.last().unwrap().is_generating);
+
+    // The turn was persisted while generation was still in progress
+    let has_reply = |session: &Session| {
+        session
+            .persona_histories
+            .values()
+            .flatten()
+            .any(|m| m.role == MessageRole::Assistant && m.content == "Background reply")
+    };
+    assert!(snapshots.iter().any(|s| s.is_generating && has_reply(s)));
+    assert!(!has_reply(&snapshots[0]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_agent_errors_are_recorded_as_system_messages() {
+    let manager = mai_session(ScriptedAgent::new().fail(rate_limited()));
+
+    manager.handle_input(&AppMode::Idle, "Hello").await;
+
+    let session = snapshot(&manager).await;
+    assert!(
+        !session
+            .persona_histories
+            .contains_key(orcs_core::session::LEGACY_ERROR_HISTORY_KEY)
+    );
+    assert!(
+        !session
+            .participants
+            .contains_key(orcs_core::session::LEGACY_ERROR_HISTORY_KEY)
+    );
+    let errors: Vec<String> = session
+        .system_messages
+        .iter()
+        .filter(|m| m.metadata.error_severity == Some(ErrorSeverity::Critical))
+        .map(|m| m.content.clone())
+        .collect();
+    assert_eq!(errors.len(), 1);
+
+    // Restoring keeps the error in the dialogue history
+    let mai = ScriptedAgent::new().reply("Retrying.");
+    let restored = restore_scripted_session(
+        session,
+        MockPersonaRepository::new().with_persona(persona("mai", "Mai")),
+        ScriptedAgentFactory::new().with_agent("mai", mai.clone()),
+    );
+    restored.handle_input(&AppMode::Idle, "Again").await;
+    assert!(mai.received()[0].contains(&errors[0]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rate_limit_error_streams_retryable_error() {
+    let manager = mai_session(ScriptedAgent::new().fail(rate_limited()));
+
+    let streamed = streamed_turn(&manager, "Hello").await;
+
+    let errors: Vec<&StreamingDialogueTurnKind> = streamed
+        .iter()
+        .filter(|kind| matches!(kind, StreamingDialogueTurnKind::Error { .. }))
+        .collect();
+    assert_eq!(errors.len(), 1, "{streamed:?}");
+    match errors[0] {
+        StreamingDialogueTurnKind::Error {
+            message, retryable, ..
+        } => {
+            assert!(message.contains("Too many requests"));
+            assert!(retryable);
+        }
+        other => panic!("expected an error turn, got {:?}", other),
+    }
+
+    assert!(
+        !TurnFailure::of(&AgentError::ExecutionFailed("Invalid API key".to_string())).retryable
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_failed_turn_links_to_its_wire_log_entry() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let wire_log = WireLog::spawn(Some(temp_dir.path())).await.unwrap();
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let manager = InteractionManager::new_session(
+        session_id.clone(),
+        Arc::new(MockPersonaRepository::new().with_persona(persona("mai", "Mai"))),
+        Arc::new(DefaultUserService),
+        EnvSettings::default(),
+    )
+    .with_wire_log(wire_log.clone())
+    .with_agent_factory(Arc::new(WireLoggedFailingFactory {
+        log: wire_log.clone(),
+        session_id: session_id.clone(),
+    }));
+    wire_log.enable(&session_id, 1);
+
+    let streamed = streamed_turn(&manager, "Hello").await;
+
+    wire_log.flush().await;
+    let logged = std::fs::read_to_string(wire_log.path(&session_id)).unwrap();
+    let entry: WireLogEntry = serde_json::from_str(logged.lines().next().unwrap()).unwrap();
+    assert_eq!(entry.error.as_deref(), Some(r#"{"error":"overloaded"}"#));
+    let error = streamed
+        .iter()
+        .find(|kind| matches!(kind, StreamingDialogueTurnKind::Error { .. }))
+        .unwrap();
+    match error {
+        StreamingDialogueTurnKind::Error {
+            wire_log_entry_id, ..
+        } => assert_eq!(wire_log_entry_id.as_deref(), Some(entry.id.as_str())),
+        other => panic!("expected an error turn, got {:?}", other),
+    }
+    let session = snapshot(&manager).await;
+    assert!(
+        session
+            .system_messages
+            .last()
+            .unwrap()
+            .content
+            .ends_with(&format!("Wire log entry: {}", entry.id))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_thinking_events_wrap_each_turn() {
+    for strategy in [ExecutionModel::Broadcast, ExecutionModel::Sequential] {
+        let manager = scripted_session(
+            MockPersonaRepository::new()
+                .with_persona(persona("mai", "Mai"))
+                .with_persona(persona("yui", "Yui")),
+            ScriptedAgentFactory::new()
+                .with_agent("mai", ScriptedAgent::new().reply("Mai reply"))
+                .with_agent("yui", ScriptedAgent::new().reply("Yui reply")),
+        );
+        manager.set_execution_strategy(strategy).await;
+
+        let streamed = streamed_turn(&manager, "Hello").await;
+
+        for name in ["Mai", "Yui"] {
+            let position = |wanted: &dyn Fn(&StreamingDialogueTurnKind) -> bool| {
+                let positions: Vec<usize> = streamed
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, kind)| wanted(kind))
+                    .map(|(i, _)| i)
+                    .collect();
+                assert_eq!(positions.len(), 1, "{name}: {streamed:?}");
+                positions[0]
+            };
+            let start = position(
+                &|kind| matches!(kind, StreamingDialogueTurnKind::Thinking { author, active: true } if author == name),
+            );
+            let chunk = position(
+                &|kind| matches!(kind, StreamingDialogueTurnKind::Chunk { author, .. } if author == name),
+            );
+            let end = position(
+                &|kind| matches!(kind, StreamingDialogueTurnKind::Thinking { author, active: false } if author == name),
+            );
+            assert!(start < chunk && chunk < end, "{name}: {streamed:?}");
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_thinking_ends_when_turn_errors() {
+    let manager = mai_session(
+        ScriptedAgent::new().fail(AgentError::ExecutionFailed("Backend crashed".to_string())),
+    );
+
+    let streamed = streamed_turn(&manager, "Hello").await;
+
+    assert_eq!(streamed.len(), 3, "{streamed:?}");
+    assert!(matches!(
+        &streamed[0],
+        StreamingDialogueTurnKind::Thinking { author, active: true } if author == "Mai"
+    ));
+    assert!(matches!(
+        &streamed[1],
+        StreamingDialogueTurnKind::Error { .. }
+    ));
+    assert!(matches!(
+        &streamed[2],
+        StreamingDialogueTurnKind::Thinking { author, active: false } if author == "Mai"
+    ));
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli_turn_emits_heartbeats_until_it_completes() {
+    let manager = InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(MockPersonaRepository::new().with_persona(persona("mai", "Mai"))),
+        Arc::new(DefaultUserService),
+        EnvSettings {
+            cli_heartbeat_interval_secs: 1,
+            ..Default::default()
+        },
+    );
+    let factory = SlowScriptFactory {
+        script: "echo 'Reading src/lib.rs'; sleep 1.5; \
+                 echo 'Calling api with sk-abcdefghijklmnopqrstuv'; sleep 2; \
+                 echo 'Done'",
+        activity: manager.cli_output_activity(),
+    };
+    let manager = manager.with_agent_factory(Arc::new(factory));
+
+    let streamed = streamed_turn(&manager, "Refactor").await;
+
+    let heartbeats: Vec<(u64, Option<String>)> = streamed
+        .iter()
+        .filter_map(|kind| match kind {
+            StreamingDialogueTurnKind::Working {
+                author,
+                elapsed_secs,
+                last_output_line,
+            } if author == "Mai" => Some((*elapsed_secs, last_output_line.clone())),
+            _ => None,
+        })
+        .collect();
+    // One heartbeat per second of the 3.5 second run
+    assert!((3..=4).contains(&heartbeats.len()), "{streamed:?}");
+    assert!(heartbeats.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(heartbeats[0].1.as_deref(), Some("Reading src/lib.rs"));
+    assert_eq!(
+        heartbeats.last().unwrap().1.as_deref(),
+        Some("Calling api with [REDACTED]")
+    );
+
+    // Heartbeats come between the thinking events and stop once the turn is done
+    let first_heartbeat = streamed
+        .iter()
+        .position(|kind| matches!(kind, StreamingDialogueTurnKind::Working { .. }))
+        .unwrap();
+    let thinking_end = streamed
+        .iter()
+        .position(|kind| {
+            matches!(
+                kind,
+                StreamingDialogueTurnKind::Thinking { active: false, .. }
+            )
+        })
+        .unwrap();
+    assert!(matches!(
+        &streamed[0],
+        StreamingDialogueTurnKind::Thinking { active: true, .. }
+    ));
+    assert!(first_heartbeat > 0);
+    assert!(
+        streamed[thinking_end..]
+            .iter()
+            .all(|kind| !matches!(kind, StreamingDialogueTurnKind::Working { .. })),
+        "{streamed:?}"
+    );
+    assert_eq!(manager.cli_output_activity().last_line("Mai"), None);
+
+    // Heartbeats are never stored in the conversation
+    let session = snapshot(&manager).await;
+    assert!(
+        session
+            .persona_histories
+            .values()
+            .flatten()
+            .all(|message| !message.content.is_empty())
+    );
+}
+
+#[tokio::test]
+async fn test_reply_speaking_for_several_personas_is_split() {
+    for splitting in [true, false] {
+        let manager = scripted_session(
+            MockPersonaRepository::new()
+                .with_persona(persona("mai", "Mai"))
+                .with_persona(persona("yui", "Yui")),
+            ScriptedAgentFactory::new()
+                .with_agent(
+                    "mai",
+                    ScriptedAgent::new().reply("Mai: Parser first.\nYui: Tests first."),
+                )
+                .with_agent("yui", ScriptedAgent::new().reply("Sure.")),
+        );
+        manager
+            .set_execution_strategy(ExecutionModel::Sequential)
+            .await;
+        manager.set_response_splitting_enabled(splitting).await;
+
+        let streamed: Vec<(String, String)> = streamed_turn(&manager, "Hello")
+            .await
+            .into_iter()
+            .filter_map(|kind| match kind {
+                StreamingDialogueTurnKind::Chunk {
+                    author, content, ..
+                } => Some((author, content)),
+                _ => None,
+            })
+            .collect();
+        let session = snapshot(&manager).await;
+        let turns_of = |persona_id: &str| -> Vec<(String, bool)> {
+            session
+                .persona_histories
+                .get(persona_id)
+                .into_iter()
+                .flatten()
+                .map(|m| (m.content.clone(), m.metadata.split_from_single_response))
+                .collect()
+        };
+
+        if splitting {
+            assert_eq!(
+                streamed[..2],
+                [
+                    ("Mai".to_string(), "Parser first.".to_string()),
+                    ("Yui".to_string(), "Tests first.".to_string()),
+                ]
+            );
+            assert_eq!(turns_of("mai"), vec![("Parser first.".to_string(), false)]);
+            assert_eq!(turns_of("yui")[0], ("Tests first.".to_string(), true));
+        } else {
+            assert_eq!(
+                streamed[0],
+                (
+                    "Mai".to_string(),
+                    "Mai: Parser first.\nYui: Tests first.".to_string()
+                )
+            );
+            assert!(turns_of("yui").iter().all(|(_, split)| !split));
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reported_generated_files_become_attachments() {
+    let manager = mai_session(
+        ScriptedAgent::new()
+            .reply("Created the report.\n<GeneratedFile>out/report.md</GeneratedFile>"),
+    );
+    let workspace = tempfile::TempDir::new().unwrap();
+    manager
+        .set_agent_workspace_root(Some(workspace.path().to_path_buf()))
+        .await;
+
+    manager
+        .handle_input_with_streaming(&AppMode::Idle, "Write a report", None, |_| {})
+        .await;
+
+    let session = snapshot(&manager).await;
+    let reply = session.persona_histories["mai"].last().unwrap();
+    assert_eq!(reply.role, MessageRole::Assistant);
+    assert_eq!(
+        reply.attachments,
+        vec![
+            workspace
+                .path()
+                .join("out/report.md")
+                .to_string_lossy()
+                .into_owned()
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_raw_cli_output_is_captured_in_debug_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Workspace `bin/` comes first on the CLI's PATH, so this stands in for `claude`
+    let workspace = tempfile::TempDir::new().unwrap();
+    let bin_dir = workspace.path().join("bin");
+    std::fs::create_dir(&bin_dir).unwrap();
+    let script = bin_dir.join("claude");
+    std::fs::write(&script, "#!/bin/sh\nprintf 'raw stdout from cli'\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    // No agent factory: the persona runs on its Claude CLI backend
+    let manager = InteractionManager::new_session(
+        uuid::Uuid::new_v4().to_string(),
+        Arc::new(MockPersonaRepository::new().with_persona(persona("coder", "Coder"))),
+        Arc::new(LlmDebugUserService),
+        EnvSettings::default(),
+    );
+    manager
+        .set_workspace_id(
+            Some("workspace".to_string()),
+            Some(workspace.path().to_path_buf()),
+        )
+        .await;
+
+    manager.handle_input(&AppMode::Idle, "Hello").await;
+
+    let session = snapshot(&manager).await;
+    let reply = session.persona_histories["coder"]
+        .iter()
+        .find(|m| m.role == MessageRole::Assistant)
+        .unwrap();
+    let info = reply.metadata.llm_debug_info.as_ref().unwrap();
+    assert_eq!(info.raw_response, "raw stdout from cli");
+    assert!(info.prompt.contains("Hello"));
+}
@@ -3,10 +3,17 @@ use std::sync::atomic::AtomicBool;
 
 use anyhow::{Result, anyhow};
 use orcs_application::session::{SessionMetadataService, SessionUpdater};
-use orcs_application::{AdhocPersonaService, SessionUseCase, UtilityAgentService};
+use orcs_application::{
+    ActivityService, AdhocPersonaService, CodeApplyService, CommandPaletteService,
+    ConversationScriptService, IntegrityService, PendingActionService, PersonaService,
+    QuickActionSeeder, SessionUseCase, TaskLinkService, UtilityAgentService,
+    WorkspaceDigestService,
+};
 use orcs_core::{
+    conversation_script::ConversationScriptRepository,
     dialogue::DialoguePresetRepository,
-    persona::{PersonaRepository, get_default_presets},
+    offline::OfflineMode,
+    persona::{PersonaRepository, PresetSeeding, seed_default_presets},
     quick_action::QuickActionRepository,
     repository::SessionRepository,
     secret::SecretService,
@@ -19,9 +26,12 @@ use orcs_core::{
 };
 use orcs_execution::{TaskExecutor, tracing_layer::OrchestratorEvent};
 use orcs_infrastructure::{
-    AppStateService, AsyncDirDialoguePresetRepository, AsyncDirPersonaRepository,
-    AsyncDirSessionRepository, AsyncDirSlashCommandRepository, AsyncDirTaskRepository,
-    ConfigService, FileQuickActionRepository, SecretServiceImpl, paths::OrcsPaths,
+    AppStateService, AppStateSnapshotStore, AsyncDirConversationScriptRepository,
+    AsyncDirDialoguePresetRepository, AsyncDirPersonaRepository, AsyncDirSessionRepository,
+    AsyncDirSlashCommandRepository, AsyncDirTaskRepository, CachedPersonaRepository,
+    CompositePersonaRepository, ConfigService, FileQuickActionRepository, FlushPolicy,
+    HttpPersonaRepository, PartialTurnStore, PersonaIconStore, SecretServiceImpl,
+    StreamRecoveryWriter, WebClipService, WireLog, WorkspaceIgnore, paths::OrcsPaths,
     user_service::ConfigBasedUserService, workspace_storage_service::FileSystemWorkspaceManager,
 };
 use tokio::sync::{Mutex, mpsc::UnboundedSender};
@@ -143,7 +153,46 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
             .await
             .expect("Failed to initialize persona repository"),
     );
-    let persona_repository: Arc<dyn PersonaRepository> = persona_repository_concrete.clone();
+
+    // Seed the personas directory with default personas if it's empty on first run.
+    // Archived personas count as existing so defaults aren't re-seeded over them, and
+    // the seeding marker keeps presets the user deleted from coming back.
+    // Only local personas are considered, so a remote source never suppresses seeding.
+    // A failure is not fatal: sessions retry seeding and explain when nobody can reply.
+    match seed_default_presets(persona_repository_concrete.as_ref()).await {
+        Ok(PresetSeeding::Seeded(presets)) => {
+            tracing::info!("[Bootstrap] Seeded {} default personas", presets.len());
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("[Bootstrap] Failed to seed default personas: {}", e),
+    }
+
+    // Initialize ConfigService
+    let config_service = Arc::new(ConfigService::new());
+
+    // Layer shared team personas under the local ones when a remote source is configured
+    let composite_persona_repository = match config_service.get_config().remote_persona_url {
+        Some(url) if !url.trim().is_empty() => {
+            match HttpPersonaRepository::with_default_cache(url.trim()) {
+                Ok(remote) => Some(Arc::new(CompositePersonaRepository::new(
+                    persona_repository_concrete.clone(),
+                    Arc::new(remote),
+                ))),
+                Err(e) => {
+                    tracing::warn!("[Bootstrap] Remote personas disabled: {}", e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let persona_source: Arc<dyn PersonaRepository> = match &composite_persona_repository {
+        Some(composite) => composite.clone(),
+        None => persona_repository_concrete.clone(),
+    };
+    // Every turn resolves personas several times; serve those reads from memory
+    let persona_cache = Arc::new(CachedPersonaRepository::new(persona_source));
+    let persona_repository: Arc<dyn PersonaRepository> = persona_cache.clone();
 
     // Create AdhocPersonaService
     let adhoc_persona_service = Arc::new(AdhocPersonaService::new(persona_repository.clone()));
@@ -182,16 +231,15 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
     let dialogue_preset_repository: Arc<dyn DialoguePresetRepository> =
         dialogue_preset_repository_concrete.clone();
 
-    // Seed the personas directory with default personas if it's empty on first run.
-    if let Ok(personas) = persona_repository.get_all().await
-        && personas.is_empty()
-    {
-        let default_presets = get_default_presets();
-        if let Err(e) = persona_repository.save_all(&default_presets).await {
-            // This is a critical failure on startup, so we panic.
-            panic!("Failed to seed default personas: {}", e);
-        }
-    }
+    // Initialize AsyncDirConversationScriptRepository
+    let conversation_script_repository: Arc<dyn ConversationScriptRepository> = Arc::new(
+        AsyncDirConversationScriptRepository::new(None)
+            .await
+            .expect("Failed to initialize conversation script repository"),
+    );
+    let conversation_script_service = Arc::new(ConversationScriptService::new(
+        conversation_script_repository,
+    ));
 
     // Create AsyncDirSessionRepository at default location
     let session_repository = Arc::new(
@@ -206,9 +254,14 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
             .await
             .expect("Failed to initialize AppStateService"),
     );
+    let app_state_snapshot_store = Arc::new(
+        AppStateSnapshotStore::new(None)
+            .await
+            .expect("Failed to initialize app state snapshot store"),
+    );
 
-    // Initialize ConfigService
-    let config_service = Arc::new(ConfigService::new());
+    // App-wide offline mode switch, shared by everything that uses the network
+    let offline_mode = OfflineMode::new(app_state_service.get_offline_mode().await);
 
     // Ensure default workspace exists (before session restoration)
     let default_workspace_id =
@@ -223,17 +276,90 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
         .await
         .expect("Failed to replace placeholder sessions");
 
+    // Create PersonaService for archive / force-delete and icon upload flows
+    let persona_icon_store = Arc::new(
+        PersonaIconStore::new(None)
+            .await
+            .expect("Failed to initialize persona icon store"),
+    );
+    let persona_service = Arc::new(
+        PersonaService::new(
+            persona_repository.clone(),
+            session_repository.clone(),
+            persona_icon_store,
+        )
+        .with_default_backend(
+            config_service
+                .get_config()
+                .env_settings
+                .default_persona_backend,
+        ),
+    );
+
     // Create SessionMetadataService for session metadata operations
     let session_updater = SessionUpdater::new(session_repository.clone());
     let session_metadata_service = Arc::new(SessionMetadataService::new(session_updater));
 
+    // Recovery files for output shown during turns interrupted by a crash
+    let partial_turn_store = Arc::new(
+        PartialTurnStore::new(None)
+            .await
+            .expect("Failed to initialize stream recovery store"),
+    );
+    let stream_recovery_writer =
+        StreamRecoveryWriter::spawn(partial_turn_store.clone(), FlushPolicy::default());
+
+    // Opt-in per-session log of raw backend requests and responses
+    let wire_log = WireLog::spawn(None)
+        .await
+        .expect("Failed to initialize wire log");
+
+    // Create QuickAction Repository
+    let quick_action_repository_concrete = Arc::new(
+        FileQuickActionRepository::new()
+            .await
+            .expect("Failed to initialize Quick Action Repository"),
+    );
+    let quick_action_repository =
+        quick_action_repository_concrete.clone() as Arc<dyn QuickActionRepository>;
+
+    // Create QuickActionSeeder for project-type default quick actions
+    let quick_action_seeder = Arc::new(QuickActionSeeder::new(
+        slash_command_repository.clone(),
+        quick_action_repository.clone(),
+    ));
+
     // Create SessionUseCase for coordinated session-workspace management
-    let session_usecase = Arc::new(SessionUseCase::new(
-        session_repository.clone(),
-        workspace_storage_service.clone(),
-        app_state_service.clone(),
-        persona_repository.clone(),
-        user_service.clone(),
+    let session_usecase = Arc::new(
+        SessionUseCase::new(
+            session_repository.clone(),
+            workspace_storage_service.clone(),
+            app_state_service.clone(),
+            persona_repository.clone(),
+            user_service.clone(),
+        )
+        .with_partial_turn_store(partial_turn_store)
+        .with_stream_recovery(stream_recovery_writer.clone())
+        .with_wire_log(wire_log.clone())
+        .with_offline_mode(offline_mode.clone())
+        .with_quick_action_seeder(quick_action_seeder.clone())
+        .with_agent_warmup(true),
+    );
+
+    // Create PendingActionService for confirmation gates on agent-triggered commands
+    let pending_action_ttl_secs = config_service
+        .get_config()
+        .shell_policy
+        .pending_action_ttl_secs;
+    let pending_action_service = Arc::new(PendingActionService::new(
+        session_usecase.clone(),
+        pending_action_ttl_secs,
+    ));
+
+    // Create CodeApplyService for applying and verifying agent-proposed code blocks
+    let code_apply_service = Arc::new(CodeApplyService::new(
+        session_usecase.clone(),
+        pending_action_service.clone(),
     ));
 
     // Create Task Repository
@@ -244,8 +370,47 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
     );
     let task_repository = task_repository_concrete.clone() as Arc<dyn TaskRepository>;
 
+    // Create IntegrityService for orphaned data scans and cleanup
+    let integrity_service = Arc::new(IntegrityService::new(
+        session_repository.clone(),
+        task_repository.clone(),
+        workspace_storage_service.clone(),
+        workspace_storage_service
+            .workspaces_root_path()
+            .to_path_buf(),
+    ));
+
+    // Create ActivityService for the workspace activity heatmap
+    let activity_service = Arc::new(ActivityService::new(
+        session_repository.clone(),
+        task_repository.clone(),
+    ));
+
+    // Create WorkspaceDigestService for daily/weekly workspace digests
+    let workspace_digest_service = Arc::new(WorkspaceDigestService::new(
+        session_repository.clone(),
+        task_repository.clone(),
+        workspace_storage_service.clone(),
+    ));
+
+    // Create TaskLinkService for message <-> task links
+    let task_link_service = Arc::new(TaskLinkService::new(
+        session_repository.clone(),
+        task_repository.clone(),
+    ));
+
+    // Create CommandPaletteService for the Cmd-K palette
+    let command_palette_service = Arc::new(CommandPaletteService::new(
+        slash_command_repository.clone(),
+        session_repository.clone(),
+        persona_repository.clone(),
+        workspace_storage_service.clone(),
+        task_repository.clone(),
+    ));
+
     // Create UtilityAgentService for lightweight LLM operations
-    let utility_service = Arc::new(UtilityAgentService::new());
+    let utility_service =
+        Arc::new(UtilityAgentService::new().with_offline_mode(offline_mode.clone()));
 
     // Create TaskExecutor with all services
     let task_executor = Arc::new(
@@ -255,15 +420,6 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
             .with_utility_service(utility_service.clone()),
     );
 
-    // Create QuickAction Repository
-    let quick_action_repository_concrete = Arc::new(
-        FileQuickActionRepository::new()
-            .await
-            .expect("Failed to initialize Quick Action Repository"),
-    );
-    let quick_action_repository =
-        quick_action_repository_concrete.clone() as Arc<dyn QuickActionRepository>;
-
     // Try to restore last session using SessionUseCase
     let restored = session_usecase.restore_last_session().await.ok().flatten();
 
@@ -338,7 +494,10 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
         app_mode,
         persona_repository,
         persona_repository_concrete,
+        composite_persona_repository,
+        persona_cache,
         adhoc_persona_service,
+        persona_service,
         user_service,
         secret_service,
         workspace_storage_service: workspace_storage_service.clone(),
@@ -346,7 +505,9 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
         slash_command_repository_concrete,
         dialogue_preset_repository,
         dialogue_preset_repository_concrete,
+        conversation_script_service,
         app_state_service: app_state_service.clone(),
+        app_state_snapshot_store,
         config_service,
         task_repository,
         task_repository_concrete,
@@ -355,6 +516,20 @@ pub async fn bootstrap(event_tx: UnboundedSender<OrchestratorEvent>) -> AppBoots
         cancel_flag: Arc::new(AtomicBool::new(false)),
         quick_action_repository,
         quick_action_repository_concrete,
+        quick_action_seeder,
+        pending_action_service,
+        code_apply_service,
+        integrity_service,
+        activity_service,
+        workspace_digest_service,
+        task_link_service,
+        command_palette_service,
+        utility_service,
+        web_clip_service: Arc::new(WebClipService::new().with_offline_mode(offline_mode.clone())),
+        stream_recovery_writer,
+        wire_log,
+        offline_mode,
+        workspace_ignore: Arc::new(WorkspaceIgnore::new()),
     };
 
     AppBootstrap { app_state }
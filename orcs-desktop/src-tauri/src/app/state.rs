@@ -2,18 +2,24 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use orcs_application::session::SessionMetadataService;
-use orcs_application::{AdhocPersonaService, SessionUseCase};
+use orcs_application::{
+    ActivityService, AdhocPersonaService, CodeApplyService, CommandPaletteService,
+    ConversationScriptService, IntegrityService, PendingActionService, PersonaService,
+    QuickActionSeeder, SessionUseCase, TaskLinkService, UtilityAgentService,
+    WorkspaceDigestService,
+};
 use orcs_core::{
-    dialogue::DialoguePresetRepository, persona::PersonaRepository,
+    dialogue::DialoguePresetRepository, offline::OfflineMode, persona::PersonaRepository,
     quick_action::QuickActionRepository, secret::SecretService, session::AppMode,
     slash_command::SlashCommandRepository, task::TaskRepository, user::UserService,
 };
 use orcs_execution::TaskExecutor;
 use orcs_execution::tracing_layer::OrchestratorEvent;
 use orcs_infrastructure::{
-    AppStateService, AsyncDirDialoguePresetRepository, AsyncDirPersonaRepository,
-    AsyncDirSessionRepository, AsyncDirSlashCommandRepository, AsyncDirTaskRepository,
-    ConfigService, FileQuickActionRepository,
+    AppStateService, AppStateSnapshotStore, AsyncDirDialoguePresetRepository,
+    AsyncDirPersonaRepository, AsyncDirSessionRepository, AsyncDirSlashCommandRepository,
+    AsyncDirTaskRepository, CachedPersonaRepository, CompositePersonaRepository, ConfigService,
+    FileQuickActionRepository, StreamRecoveryWriter, WebClipService, WireLog, WorkspaceIgnore,
     workspace_storage_service::FileSystemWorkspaceManager,
 };
 use tokio::sync::Mutex;
@@ -27,7 +33,12 @@ pub struct AppState {
     pub app_mode: Mutex<AppMode>,
     pub persona_repository: Arc<dyn PersonaRepository>,
     pub persona_repository_concrete: Arc<AsyncDirPersonaRepository>,
+    /// Set when a remote persona source is configured; `persona_repository` reads through it.
+    pub composite_persona_repository: Option<Arc<CompositePersonaRepository>>,
+    /// Read cache behind `persona_repository`; invalidate after changes made around it.
+    pub persona_cache: Arc<CachedPersonaRepository>,
     pub adhoc_persona_service: Arc<AdhocPersonaService>,
+    pub persona_service: Arc<PersonaService>,
     pub user_service: Arc<dyn UserService>,
     pub secret_service: Arc<dyn SecretService>,
     pub workspace_storage_service: Arc<FileSystemWorkspaceManager>,
@@ -36,7 +47,11 @@ pub struct AppState {
     pub dialogue_preset_repository: Arc<dyn DialoguePresetRepository>,
     #[allow(dead_code)]
     pub dialogue_preset_repository_concrete: Arc<AsyncDirDialoguePresetRepository>,
+    /// Lists conversation scripts and runs them against sessions.
+    pub conversation_script_service: Arc<ConversationScriptService>,
     pub app_state_service: Arc<AppStateService>,
+    /// Named checkpoints of the app state that can be restored on demand.
+    pub app_state_snapshot_store: Arc<AppStateSnapshotStore>,
     pub config_service: Arc<ConfigService>,
     pub task_repository: Arc<dyn TaskRepository>,
     pub task_repository_concrete: Arc<AsyncDirTaskRepository>,
@@ -46,4 +61,22 @@ pub struct AppState {
     pub quick_action_repository: Arc<dyn QuickActionRepository>,
     #[allow(dead_code)]
     pub quick_action_repository_concrete: Arc<FileQuickActionRepository>,
+    pub quick_action_seeder: Arc<QuickActionSeeder>,
+    pub pending_action_service: Arc<PendingActionService>,
+    pub code_apply_service: Arc<CodeApplyService>,
+    pub integrity_service: Arc<IntegrityService>,
+    pub activity_service: Arc<ActivityService>,
+    pub workspace_digest_service: Arc<WorkspaceDigestService>,
+    pub task_link_service: Arc<TaskLinkService>,
+    pub command_palette_service: Arc<CommandPaletteService>,
+    pub utility_service: Arc<UtilityAgentService>,
+    pub web_clip_service: Arc<WebClipService>,
+    /// Mirrors streamed output to recovery files until the turn is saved.
+    pub stream_recovery_writer: StreamRecoveryWriter,
+    /// Per-session opt-in log of raw backend requests and responses.
+    pub wire_log: WireLog,
+    /// App-wide switch that blocks every network-using backend.
+    pub offline_mode: OfflineMode,
+    /// `.orcsignore` rules of workspace roots, reloaded when the files change.
+    pub workspace_ignore: Arc<WorkspaceIgnore>,
 }
@@ -1,5 +1,6 @@
 use orcs_core::state::{model::AppState as CoreAppState, repository::StateRepository};
 use orcs_core::workspace::manager::WorkspaceStorageService;
+use orcs_infrastructure::AppStateSnapshotInfo;
 use tauri::{AppHandle, Emitter, State};
 
 use crate::app::AppState;
@@ -118,6 +119,98 @@ pub async fn clear_active_session_in_app_state(
 
     Ok(())
 }
+
+/// Returns whether offline mode is on (for the offline indicator).
+#[tauri::command]
+pub async fn get_offline_mode(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.offline_mode.is_enabled())
+}
+
+/// Turns offline mode on or off, persists it, and emits an update event.
+///
+/// Takes effect at once: sessions rebuild their dialogue on the next turn,
+/// so no restart is needed in either direction.
+#[tauri::command]
+pub async fn set_offline_mode(
+    enabled: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .app_state_service
+        .set_offline_mode(enabled)
+        .await
+        .map_err(|e| e.to_string())?;
+    state.offline_mode.set(enabled);
+
+    let updated_state = state
+        .app_state_service
+        .get_state()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    app.emit("app-state:update", &updated_state)
+        .map_err(|e| format!("Failed to emit app-state:update: {}", e))?;
+
+    Ok(())
+}
+
+/// Saves the current app state as a named snapshot, replacing one of the same name.
+#[tauri::command]
+pub async fn create_app_state_snapshot(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<AppStateSnapshotInfo, String> {
+    let snapshot = state.app_state_service.snapshot().await;
+    state
+        .app_state_snapshot_store
+        .save(&name, snapshot)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lists the saved app state snapshots, newest first.
+#[tauri::command]
+pub async fn list_app_state_snapshots(
+    state: State<'_, AppState>,
+) -> Result<Vec<AppStateSnapshotInfo>, String> {
+    state
+        .app_state_snapshot_store
+        .list()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restores a named snapshot as the current app state and emits an update event.
+#[tauri::command]
+pub async fn restore_app_state_snapshot(
+    name: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<CoreAppState, String> {
+    let snapshot = state
+        .app_state_snapshot_store
+        .load(&name)
+        .await
+        .map_err(|e| e.to_string())?;
+    state
+        .app_state_service
+        .restore(snapshot)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let updated_state = state
+        .app_state_service
+        .get_state()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    app.emit("app-state:update", &updated_state)
+        .map_err(|e| format!("Failed to emit app-state:update: {}", e))?;
+
+    Ok(updated_state)
+}
+
 // ============================================================================
 // Tab management commands
 // ============================================================================
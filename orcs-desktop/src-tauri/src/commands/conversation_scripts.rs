@@ -0,0 +1,80 @@
+use orcs_application::ScriptOutcome;
+use orcs_core::conversation_script::ConversationScript;
+use orcs_interaction::{DialogueMessage, StreamingDialogueTurn};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::app::AppState;
+use crate::commands::session::{editable_session, persist_turns};
+
+/// Returns a callback streaming script turns as `dialogue-turn` events.
+fn emit_turns(app: AppHandle) -> impl Fn(&DialogueMessage) {
+    move |turn: &DialogueMessage| {
+        let streaming_turn = StreamingDialogueTurn {
+            session_id: turn.session_id.clone(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            kind: turn.to_streaming_kind(),
+        };
+
+        if let Err(e) = app.emit("dialogue-turn", streaming_turn) {
+            eprintln!("[TAURI] Failed to emit dialogue-turn event: {}", e);
+        }
+    }
+}
+
+/// Gets all conversation scripts (system + user)
+#[tauri::command]
+pub async fn list_conversation_scripts(
+    state: State<'_, AppState>,
+) -> Result<Vec<ConversationScript>, String> {
+    state
+        .conversation_script_service
+        .list()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Runs a conversation script in the active session up to its first pause.
+///
+/// Posted messages and replies are streamed as `dialogue-turn` events. The
+/// script stops early when cancelled (see `cancel_current_operation`) or muted.
+#[tauri::command]
+pub async fn run_conversation_script(
+    script_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ScriptOutcome, String> {
+    let manager = editable_session(&state).await?;
+
+    let outcome = state
+        .conversation_script_service
+        .run(
+            &manager,
+            &script_id,
+            Some(state.cancel_flag.as_ref()),
+            emit_turns(app),
+        )
+        .await;
+
+    // Save the progress even if a step failed, so the script can be resumed
+    persist_turns(manager.session_id(), &state).await;
+
+    outcome.map_err(|e| e.to_string())
+}
+
+/// Resumes the conversation script paused or interrupted in the active session.
+#[tauri::command]
+pub async fn advance_script(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ScriptOutcome, String> {
+    let manager = editable_session(&state).await?;
+
+    let outcome = state
+        .conversation_script_service
+        .advance(&manager, Some(state.cancel_flag.as_ref()), emit_turns(app))
+        .await;
+
+    persist_turns(manager.session_id(), &state).await;
+
+    outcome.map_err(|e| e.to_string())
+}
@@ -0,0 +1,28 @@
+//! Session export Tauri commands.
+
+use std::path::PathBuf;
+
+use orcs_application::{ExportFilter, ExportSummary, SessionExportService};
+use tauri::State;
+
+use crate::app::AppState;
+
+/// Exports sessions as JSONL (one JSON object per message) to `dest_path`.
+///
+/// When `redact` is true, the built-in redaction rules (API keys, tokens,
+/// emails) are applied to message content.
+#[tauri::command]
+pub async fn export_sessions_jsonl(
+    filter: ExportFilter,
+    dest_path: String,
+    redact: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ExportSummary, String> {
+    let service = SessionExportService::new(state.session_repository.clone())
+        .with_redaction(redact.unwrap_or(false));
+
+    service
+        .export_sessions_jsonl(&filter, &PathBuf::from(dest_path))
+        .await
+        .map_err(|e| e.to_string())
+}
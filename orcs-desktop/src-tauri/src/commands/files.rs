@@ -1,11 +1,13 @@
 use std::path::Path;
 use std::process::Command;
 
+use orcs_application::{ApplyReport, ApplyRequest};
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, State};
 use tokio::fs;
 
 use crate::app::AppState;
+use crate::commands::pending_actions::emit_pending_action_created;
 
 /// ファイルプレビューデータ
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -153,6 +155,52 @@ pub async fn save_code_snippet(
     Ok(())
 }
 
+/// Writes a code block of a message to a workspace file and runs a
+/// verification command, reporting the outcome to the session as context info.
+///
+/// A verification command the shell policy considers destructive is queued as
+/// a pending action instead of running.
+#[tauri::command]
+pub async fn apply_and_verify(
+    session_id: String,
+    message_timestamp: String,
+    block_index: usize,
+    target_path: String,
+    verify_command: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ApplyReport, String> {
+    let shell_policy = state.config_service.get_config().shell_policy;
+    let report = state
+        .code_apply_service
+        .apply_and_verify(
+            ApplyRequest {
+                session_id: session_id.clone(),
+                message_timestamp,
+                block_index,
+                target_path,
+                verify_command,
+            },
+            &shell_policy,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(action) = &report.pending_action {
+        emit_pending_action_created(&app, action.clone());
+    }
+    let app_mode = state.app_mode.lock().await.clone();
+    if let Err(e) = state
+        .session_usecase
+        .save_session(&session_id, app_mode)
+        .await
+    {
+        tracing::warn!("[CodeApply] Failed to save session: {}", e);
+    }
+
+    Ok(report)
+}
+
 /// Opens a terminal in the specified directory.
 ///
 /// Uses the terminal application configured in `config.toml` if available,
@@ -0,0 +1,47 @@
+//! Data integrity Tauri commands.
+
+use orcs_application::{IntegrityCleanResult, IntegrityReport, IntegritySelection};
+use orcs_infrastructure::ValidationReport;
+use orcs_infrastructure::storage_repository::StorageRepository;
+use tauri::State;
+
+use crate::app::AppState;
+
+/// Scans stored data for orphaned sessions, tasks, uploads and sandbox worktrees.
+///
+/// Read-only: nothing is modified.
+#[tauri::command]
+pub async fn scan_data_integrity(state: State<'_, AppState>) -> Result<IntegrityReport, String> {
+    state
+        .integrity_service
+        .scan()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Cleans the selected orphaned items and returns a per-item result.
+///
+/// Only items reported by a fresh scan are touched; placeholder-workspace
+/// sessions are always skipped.
+#[tauri::command]
+pub async fn clean_data_integrity(
+    selection: IntegritySelection,
+    state: State<'_, AppState>,
+) -> Result<Vec<IntegrityCleanResult>, String> {
+    state
+        .integrity_service
+        .clean(selection)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Checks that every stored session loads and migrates to the current schema.
+///
+/// Read-only: failing sessions are reported with their error, not repaired.
+#[tauri::command]
+pub async fn validate_all_sessions(
+    state: State<'_, AppState>,
+) -> Result<Vec<ValidationReport>, String> {
+    let sessions_dir = state.session_repository.base_dir();
+    Ok(orcs_infrastructure::validate_all_sessions(sessions_dir).await)
+}
@@ -1,8 +1,13 @@
 pub mod app_state;
+pub mod conversation_scripts;
 pub mod dialogue_presets;
+pub mod export;
 pub mod files;
 pub mod git;
+pub mod integrity;
+pub mod palette;
 pub mod paths;
+pub mod pending_actions;
 pub mod personas;
 pub mod quick_actions;
 pub mod search;
@@ -19,12 +24,34 @@ pub fn handlers() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sy
         session::list_sessions,
         tasks::get_tasks_snapshot,
         tasks::list_tasks,
+        tasks::get_task_report,
         tasks::delete_task,
+        tasks::get_tasks_for_message,
+        tasks::get_origin_for_task,
+        tasks::promote_dry_run,
         personas::create_adhoc_persona,
         personas::save_adhoc_persona,
+        personas::get_default_participants,
+        personas::search_personas,
         session::switch_session,
+        session::open_session_readonly,
+        session::close_readonly_session,
+        session::promote_readonly_to_active,
         session::get_session,
         session::delete_session,
+        session::prune_archived_sessions,
+        session::move_session,
+        session::merge_sessions,
+        session::compact_session,
+        session::get_turn_distribution,
+        session::get_session_cost_breakdown,
+        session::get_conversation,
+        session::get_message_sources,
+        session::clip_url,
+        session::extract_decision_record,
+        session::enable_wire_log,
+        session::disable_wire_log,
+        session::get_wire_log_path,
         session::rename_session,
         session::toggle_session_favorite,
         session::toggle_session_archive,
@@ -33,14 +60,26 @@ pub fn handlers() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sy
         session::append_system_messages,
         session::get_active_session,
         personas::get_personas,
+        personas::refresh_remote_personas,
         personas::save_persona,
+        personas::clone_persona,
         personas::delete_persona,
+        personas::archive_persona,
+        personas::unarchive_persona,
+        personas::upload_persona_icon,
+        personas::get_persona_icon,
+        personas::sweep_persona_icons,
         personas::get_persona_backend_options,
+        personas::get_default_models,
+        personas::set_default_models,
         personas::create_persona,
         dialogue_presets::get_dialogue_presets,
         dialogue_presets::save_dialogue_preset,
         dialogue_presets::delete_dialogue_preset,
         dialogue_presets::apply_dialogue_preset,
+        conversation_scripts::list_conversation_scripts,
+        conversation_scripts::run_conversation_script,
+        conversation_scripts::advance_script,
         user::get_user_nickname,
         user::get_user_profile,
         user::get_debug_settings,
@@ -50,10 +89,41 @@ pub fn handlers() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sy
         session::add_participant,
         session::remove_participant,
         session::get_active_participants,
+        session::set_session_focus,
+        session::clear_session_focus,
+        session::get_session_focus,
+        session::get_session_capabilities,
+        session::get_effective_models,
+        session::get_restore_warnings,
+        session::refresh_participant_metadata,
         session::toggle_mute,
         session::get_mute_status,
+        session::set_style_calibration,
+        session::record_message_feedback,
+        session::get_style_calibration,
+        session::set_response_splitting,
+        session::get_response_splitting,
+        session::set_random_seed,
+        session::get_random_seed,
+        session::set_clock_context,
+        session::get_clock_context,
+        session::set_plan_detection,
+        session::get_plan_detection,
+        session::set_paste_attachment,
+        session::get_paste_attachment,
+        session::preview_collaboration_guidelines,
+        session::get_full_message,
+        session::set_pinboard,
+        session::get_pinboard,
+        session::set_pinboard_agent_edits,
+        session::get_pinboard_agent_edits,
         session::get_context_mode,
         session::set_context_mode,
+        session::get_conversation_mode_in_clean,
+        session::set_conversation_mode_in_clean,
+        session::get_participant_suggestions,
+        session::set_participant_suggestions,
+        session::dismiss_participant_suggestion,
         session::set_execution_strategy,
         session::get_execution_strategy,
         session::set_conversation_mode,
@@ -84,6 +154,7 @@ pub fn handlers() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sy
         workspaces::toggle_favorite_workspace,
         workspaces::delete_workspace,
         workspaces::list_workspace_files,
+        workspaces::get_effective_ignore_rules,
         workspaces::upload_file_to_workspace,
         workspaces::upload_file_from_bytes,
         workspaces::delete_file_from_workspace,
@@ -94,12 +165,17 @@ pub fn handlers() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sy
         workspaces::move_workspace_file_sort_order,
         workspaces::copy_file_to_workspace,
         workspaces::investigate_workspace,
+        workspaces::get_workspace_activity,
+        workspaces::generate_workspace_digest,
+        workspaces::get_workspace_session_counts,
         files::read_workspace_file,
         files::get_file_preview_data,
         files::save_code_snippet,
+        files::apply_and_verify,
         files::open_terminal,
         session::publish_session_event,
         session::handle_input,
+        session::handle_input_background,
         slash_commands::list_slash_commands,
         slash_commands::get_slash_command,
         slash_commands::create_slash_command,
@@ -107,19 +183,25 @@ pub fn handlers() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sy
         slash_commands::remove_slash_command,
         slash_commands::expand_command_template,
         slash_commands::execute_shell_command,
+        slash_commands::gate_agent_slash_command,
         slash_commands::execute_task_command,
         slash_commands::execute_action_command,
         slash_commands::execute_pipeline_command,
         slash_commands::toggle_slash_command_favorite,
         slash_commands::update_slash_command_sort_order,
         slash_commands::toggle_slash_command_include_in_system_prompt,
+        pending_actions::list_pending_actions,
+        pending_actions::approve_pending_action,
+        pending_actions::deny_pending_action,
         quick_actions::get_quick_actions,
         quick_actions::save_quick_actions,
         quick_actions::update_quick_action_slot,
+        quick_actions::reseed_quick_actions,
         session::get_auto_chat_config,
         session::update_auto_chat_config,
         session::get_auto_chat_status,
         session::start_auto_chat,
+        session::run_session_poll,
         session::generate_summary,
         session::generate_action_plan,
         session::generate_expertise,
@@ -131,11 +213,21 @@ pub fn handlers() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sy
         session::get_sandbox_state,
         session::update_message_content,
         search::execute_search,
+        palette::palette_query,
+        export::export_sessions_jsonl,
+        integrity::scan_data_integrity,
+        integrity::clean_data_integrity,
+        integrity::validate_all_sessions,
         app_state::get_app_state_snapshot,
         app_state::set_last_selected_workspace,
         app_state::clear_last_selected_workspace,
         app_state::set_active_session_in_app_state,
         app_state::clear_active_session_in_app_state,
+        app_state::get_offline_mode,
+        app_state::set_offline_mode,
+        app_state::create_app_state_snapshot,
+        app_state::list_app_state_snapshots,
+        app_state::restore_app_state_snapshot,
         app_state::open_tab,
         app_state::close_tab,
         app_state::set_active_tab,
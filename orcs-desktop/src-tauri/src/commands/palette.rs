@@ -0,0 +1,24 @@
+//! Tauri commands for the Cmd-K command palette.
+
+use orcs_application::PaletteItem;
+use tauri::State;
+
+use crate::app::AppState;
+
+/// Returns the `limit` best palette matches for `query` across commands,
+/// sessions, personas, workspace files and tasks.
+///
+/// An empty query returns the most recently updated sessions and tasks.
+#[tauri::command]
+pub async fn palette_query(
+    query: String,
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<PaletteItem>, String> {
+    let active_session_id = state.session_usecase.active_session_id().await;
+    state
+        .command_palette_service
+        .query(&query, limit, active_session_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,106 @@
+//! Pending action Tauri commands.
+//!
+//! Agent-triggered commands that hit a confirmation gate are queued as pending
+//! actions. These commands let the user list, approve or deny them.
+
+use orcs_core::pending_action::{PendingAction, PendingActionStatus};
+use orcs_interaction::{StreamingDialogueTurn, StreamingDialogueTurnKind};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::app::AppState;
+use crate::commands::slash_commands::run_shell_command;
+
+/// Emits a `dialogue-turn` event announcing a new pending action.
+pub(crate) fn emit_pending_action_created(app: &AppHandle, action: PendingAction) {
+    let event = StreamingDialogueTurn {
+        session_id: action.session_id.clone(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        kind: StreamingDialogueTurnKind::PendingAction { action },
+    };
+
+    if let Err(e) = app.emit("dialogue-turn", event) {
+        eprintln!("[TAURI] Failed to emit pending action event: {}", e);
+    }
+}
+
+/// Emits a `dialogue-turn` event announcing that a pending action was resolved.
+pub(crate) fn emit_pending_action_resolved(
+    app: &AppHandle,
+    action: &PendingAction,
+    status: PendingActionStatus,
+) {
+    let event = StreamingDialogueTurn {
+        session_id: action.session_id.clone(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        kind: StreamingDialogueTurnKind::PendingActionResolved {
+            action_id: action.id.clone(),
+            status,
+        },
+    };
+
+    if let Err(e) = app.emit("dialogue-turn", event) {
+        eprintln!("[TAURI] Failed to emit pending action resolution: {}", e);
+    }
+}
+
+/// Lists all pending actions awaiting confirmation.
+#[tauri::command]
+pub async fn list_pending_actions(
+    state: State<'_, AppState>,
+) -> Result<Vec<PendingAction>, String> {
+    Ok(state.pending_action_service.list().await)
+}
+
+/// Approves a pending action, executes it and posts the result as context info.
+#[tauri::command]
+pub async fn approve_pending_action(
+    id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PendingAction, String> {
+    let app_state: &AppState = &state;
+    let action = state
+        .pending_action_service
+        .approve(&id, move |action| async move {
+            run_shell_command(action.rendered_command, action.working_dir, app_state)
+                .await?
+                .into_output()
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    emit_pending_action_resolved(&app, &action, PendingActionStatus::Approved);
+    save_action_session(&state, &action).await;
+
+    Ok(action)
+}
+
+/// Denies a pending action and notifies the agent that it was blocked.
+#[tauri::command]
+pub async fn deny_pending_action(
+    id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PendingAction, String> {
+    let action = state
+        .pending_action_service
+        .deny(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    emit_pending_action_resolved(&app, &action, PendingActionStatus::Denied);
+    save_action_session(&state, &action).await;
+
+    Ok(action)
+}
+
+async fn save_action_session(state: &AppState, action: &PendingAction) {
+    let app_mode = state.app_mode.lock().await.clone();
+    if let Err(e) = state
+        .session_usecase
+        .save_session(&action.session_id, app_mode)
+        .await
+    {
+        tracing::warn!("[PendingAction] Failed to save session: {}", e);
+    }
+}
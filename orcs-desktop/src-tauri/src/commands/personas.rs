@@ -1,6 +1,11 @@
-use orcs_core::persona::{Persona, PersonaBackend};
+use std::collections::HashMap;
+
+use orcs_application::PersonaSessionReference;
+use orcs_core::persona::{Persona, PersonaBackend, PersonaOverrides};
 use orcs_core::session::PLACEHOLDER_WORKSPACE_ID;
 use orcs_core::workspace::manager::WorkspaceStorageService;
+use orcs_infrastructure::RemoteRefreshOutcome;
+use orcs_infrastructure::user_service::{load_root_config, save_root_config};
 use tauri::State;
 
 use crate::app::AppState;
@@ -77,6 +82,29 @@ pub async fn create_adhoc_persona(
     Ok(persona)
 }
 
+/// Lists personas that join new sessions by default
+#[tauri::command]
+pub async fn get_default_participants(state: State<'_, AppState>) -> Result<Vec<Persona>, String> {
+    state
+        .adhoc_persona_service
+        .default_participants()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Searches personas by name, role, or background
+#[tauri::command]
+pub async fn search_personas(
+    query: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Persona>, String> {
+    state
+        .adhoc_persona_service
+        .search_personas(&query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Saves an adhoc persona to permanent user persona storage
 #[tauri::command]
 pub async fn save_adhoc_persona(
@@ -89,6 +117,7 @@ pub async fn save_adhoc_persona(
         .await
         .map_err(|e| e.to_string())?;
 
+    state.persona_cache.invalidate();
     if let Some(manager) = state.session_usecase.active_session().await {
         manager.invalidate_dialogue().await;
     }
@@ -96,25 +125,78 @@ pub async fn save_adhoc_persona(
     Ok(persona)
 }
 
-/// Gets all personas from the single source of truth
+/// Clones a persona with the given fields changed, returning the new persona
 #[tauri::command]
-pub async fn get_personas(state: State<'_, AppState>) -> Result<Vec<Persona>, String> {
+pub async fn clone_persona(
+    persona_id: String,
+    overrides: PersonaOverrides,
+    state: State<'_, AppState>,
+) -> Result<Persona, String> {
     state
-        .persona_repository
-        .get_all()
+        .adhoc_persona_service
+        .clone_persona(&persona_id, overrides)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Gets all personas from the single source of truth
+///
+/// Archived personas are excluded unless `include_archived` is true. With a
+/// `workspace_id`, only global personas and those scoped to that workspace are
+/// returned.
+#[tauri::command]
+pub async fn get_personas(
+    include_archived: Option<bool>,
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Persona>, String> {
+    let result = if include_archived.unwrap_or(false) {
+        state.persona_repository.get_all_including_archived().await
+    } else {
+        state.persona_repository.get_all().await
+    };
+    let personas = result.map_err(|e| e.to_string())?;
+    Ok(match workspace_id {
+        Some(workspace_id) => personas
+            .into_iter()
+            .filter(|p| p.is_available_in(Some(&workspace_id)))
+            .collect(),
+        None => personas,
+    })
+}
+
+/// Re-fetches personas from the configured remote source
+#[tauri::command]
+pub async fn refresh_remote_personas(
+    state: State<'_, AppState>,
+) -> Result<RemoteRefreshOutcome, String> {
+    let composite = state
+        .composite_persona_repository
+        .as_ref()
+        .ok_or("No remote persona source configured")?;
+    let outcome = composite
+        .refresh_remote()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    state.persona_cache.invalidate();
+    if let Some(manager) = state.session_usecase.active_session().await {
+        manager.invalidate_dialogue().await;
+    }
+
+    Ok(outcome)
+}
+
 /// Saves a single persona configuration
 #[tauri::command]
 pub async fn save_persona(persona: Persona, state: State<'_, AppState>) -> Result<(), String> {
     state
-        .persona_repository
+        .persona_service
         .save(&persona)
         .await
         .map_err(|e| e.to_string())?;
 
+    state.persona_cache.invalidate();
     if let Some(manager) = state.session_usecase.active_session().await {
         manager.invalidate_dialogue().await;
     }
@@ -123,59 +205,184 @@ pub async fn save_persona(persona: Persona, state: State<'_, AppState>) -> Resul
 }
 
 /// Deletes a persona by ID
+///
+/// Archives the persona by default so sessions referencing it keep resolving
+/// its name. With `force`, the persona is permanently removed and the sessions
+/// that still reference it are returned.
 #[tauri::command]
-pub async fn delete_persona(persona_id: String, state: State<'_, AppState>) -> Result<(), String> {
-    state
-        .persona_repository
-        .delete(&persona_id)
+pub async fn delete_persona(
+    persona_id: String,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PersonaSessionReference>, String> {
+    let references = if force.unwrap_or(false) {
+        state
+            .persona_service
+            .force_delete(&persona_id)
+            .await
+            .map_err(|e| e.to_string())?
+    } else {
+        state
+            .persona_service
+            .archive(&persona_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        Vec::new()
+    };
+
+    state.persona_cache.invalidate();
+    if let Some(manager) = state.session_usecase.active_session().await {
+        manager.invalidate_dialogue().await;
+    }
+
+    Ok(references)
+}
+
+/// Archives a persona (hidden from pickers, kept resolvable for old sessions)
+#[tauri::command]
+pub async fn archive_persona(
+    persona_id: String,
+    state: State<'_, AppState>,
+) -> Result<Persona, String> {
+    let persona = state
+        .persona_service
+        .archive(&persona_id)
         .await
         .map_err(|e| e.to_string())?;
 
+    state.persona_cache.invalidate();
     if let Some(manager) = state.session_usecase.active_session().await {
         manager.invalidate_dialogue().await;
     }
 
-    Ok(())
+    Ok(persona)
 }
 
-/// Gets all available persona backend options
+/// Restores an archived persona
 #[tauri::command]
-pub async fn get_persona_backend_options() -> Result<Vec<(String, String)>, String> {
-    Ok(PersonaBackend::all_variants())
+pub async fn unarchive_persona(
+    persona_id: String,
+    state: State<'_, AppState>,
+) -> Result<Persona, String> {
+    let persona = state
+        .persona_service
+        .unarchive(&persona_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    state.persona_cache.invalidate();
+    if let Some(manager) = state.session_usecase.active_session().await {
+        manager.invalidate_dialogue().await;
+    }
+
+    Ok(persona)
 }
 
-/// Creates a new persona from a CreatePersonaRequest (unified creation logic)
+/// Uploads a PNG, JPEG or WebP image as a persona's icon
+///
+/// The image is downscaled and stored as a content-addressed asset; the
+/// updated persona is returned with its `icon_asset` set.
 #[tauri::command]
-pub async fn create_persona(
-    request: orcs_core::persona::CreatePersonaRequest,
+pub async fn upload_persona_icon(
+    persona_id: String,
+    bytes: Vec<u8>,
+    mime: String,
     state: State<'_, AppState>,
 ) -> Result<Persona, String> {
-    // Validate request
-    request.validate()?;
+    let persona = state
+        .persona_service
+        .upload_icon(&persona_id, &bytes, &mime)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(manager) = state.session_usecase.active_session().await {
+        manager.refresh_participant_metadata().await;
+    }
 
-    // Convert to Persona (UUID auto-generated if needed)
-    let persona = request.into_persona();
+    Ok(persona)
+}
 
-    // Save to repository
-    let mut all_personas = state
-        .persona_repository
-        .get_all()
+/// Gets the PNG bytes of a persona's uploaded icon, or None if it only has an emoji
+#[tauri::command]
+pub async fn get_persona_icon(
+    persona_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<Vec<u8>>, String> {
+    state
+        .persona_service
+        .icon(&persona_id)
         .await
-        .map_err(|e| format!("Failed to load personas: {}", e))?;
+        .map_err(|e| e.to_string())
+}
 
-    // Check for duplicate ID
-    if all_personas.iter().any(|p| p.id == persona.id) {
-        return Err(format!("Persona with ID '{}' already exists", persona.id));
+/// Deletes uploaded icons no persona references, returning the removed asset names
+#[tauri::command]
+pub async fn sweep_persona_icons(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    state
+        .persona_service
+        .sweep_icon_assets()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets all available persona backend options
+#[tauri::command]
+pub async fn get_persona_backend_options() -> Result<Vec<(String, String)>, String> {
+    Ok(PersonaBackend::all_variants())
+}
+
+/// Gets the app-level default model for each backend
+#[tauri::command]
+pub async fn get_default_models(
+    state: State<'_, AppState>,
+) -> Result<HashMap<PersonaBackend, String>, String> {
+    Ok(state.config_service.get_config().default_models)
+}
+
+/// Sets the app-level default model for each backend
+///
+/// Blank entries are dropped; other entries must be known to the backend's model catalog.
+#[tauri::command]
+pub async fn set_default_models(
+    default_models: HashMap<PersonaBackend, String>,
+    state: State<'_, AppState>,
+) -> Result<HashMap<PersonaBackend, String>, String> {
+    let mut models = HashMap::new();
+    for (backend, model) in default_models {
+        let model = model.trim();
+        if model.is_empty() {
+            continue;
+        }
+        backend.validate_model_name(model)?;
+        models.insert(backend, model.to_string());
     }
 
-    all_personas.push(persona.clone());
+    let mut config = load_root_config()?;
+    config.default_models = models.clone();
+    save_root_config(config)?;
+    state.config_service.invalidate_cache();
 
     state
-        .persona_repository
-        .save_all(&all_personas)
+        .session_usecase
+        .apply_default_models(models.clone())
+        .await;
+
+    Ok(models)
+}
+
+/// Creates a new persona from a CreatePersonaRequest (unified creation logic)
+#[tauri::command]
+pub async fn create_persona(
+    request: orcs_core::persona::CreatePersonaRequest,
+    state: State<'_, AppState>,
+) -> Result<Persona, String> {
+    let persona = state
+        .persona_service
+        .create(request)
         .await
-        .map_err(|e| format!("Failed to save persona: {}", e))?;
+        .map_err(|e| e.to_string())?;
 
+    state.persona_cache.invalidate();
     // Invalidate dialogue cache to reflect new persona
     if let Some(manager) = state.session_usecase.active_session().await {
         manager.invalidate_dialogue().await;
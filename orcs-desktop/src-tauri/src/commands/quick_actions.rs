@@ -1,6 +1,7 @@
 //! Quick Action Dock Tauri commands.
 
 use orcs_core::quick_action::QuickActionConfig;
+use orcs_core::workspace::manager::WorkspaceStorageService;
 use tauri::State;
 
 use crate::app::AppState;
@@ -56,3 +57,23 @@ pub async fn update_quick_action_slot(
 
     Ok(config)
 }
+
+/// Binds the default quick actions for the workspace's project type to empty slots.
+#[tauri::command]
+pub async fn reseed_quick_actions(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<QuickActionConfig, String> {
+    let workspace = state
+        .workspace_storage_service
+        .get_workspace(&workspace_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Workspace not found: {}", workspace_id))?;
+
+    state
+        .quick_action_seeder
+        .seed(&workspace)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -73,7 +73,8 @@ pub async fn execute_search(
     }
 
     // Execute search using RipgrepSearchService
-    let search_service = RipgrepSearchService::new();
+    let search_service =
+        RipgrepSearchService::new().with_workspace_ignore(state.workspace_ignore.clone());
     let result = search_service
         .search(
             &request.query,
@@ -94,6 +95,11 @@ async fn execute_memory_search(
     request: &SearchRequest,
     state: &State<'_, AppState>,
 ) -> Result<SearchResult, String> {
+    state
+        .offline_mode
+        .ensure_online("Memory search")
+        .map_err(|e| e.to_string())?;
+
     // Get current workspace to find kaiba_rei_id
     let workspace = get_current_workspace(state).await?;
 
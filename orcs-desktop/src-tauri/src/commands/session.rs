@@ -1,25 +1,57 @@
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::SystemTime;
 
 use chrono::Utc;
 use llm_toolkit::ToPrompt;
 use llm_toolkit::agent::dialogue::{ExecutionModel, TalkStyle};
+use orcs_application::{DecisionRecord, SessionCompaction, TurnDistribution};
+use orcs_core::error::OrcsError;
+use orcs_core::persona::{CapabilityFlags, PersonaValidationError};
 use orcs_core::schema::{ExecutionModelType, TalkStyleType};
 use orcs_core::session::{
-    AppMode, AutoChatConfig, ConversationMode, ErrorSeverity, ModeratorAction,
-    PLACEHOLDER_WORKSPACE_ID, Session, SessionEvent, SessionRepository,
+    AppMode, AutoChatConfig, ConversationMode, ConversationTurn, CostBreakdown, ErrorSeverity,
+    FeedbackKind, ModeratorAction, PLACEHOLDER_WORKSPACE_ID, PersonaCalibrationState, Session,
+    SessionEvent, SessionRepository, SourceRef,
 };
 use orcs_core::slash_command::{CommandType, SlashCommand, builtin_commands};
 use orcs_core::task::{Task, TaskStatus};
+use orcs_core::workspace::UploadedFile;
 use orcs_core::workspace::manager::WorkspaceStorageService;
 use orcs_execution::tracing_layer::OrchestratorEventBuilder;
-use orcs_interaction::InteractionResult;
+use orcs_interaction::{
+    InteractionManager, InteractionResult, ParticipantMetadata, PollResult, RestoreWarning,
+    SystemMessageEntry,
+};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::process::Command;
 use uuid::Uuid;
 
 use crate::app::AppState;
 
+/// Serializes an error so the frontend can match on its variant.
+fn structured_error(error: OrcsError) -> String {
+    serde_json::to_string(&error).unwrap_or_else(|_| error.to_string())
+}
+
+/// Returns the active session for a command that mutates it.
+///
+/// Fails with a serialized `OrcsError::ReadOnly` while that session is open read-only.
+pub(crate) async fn editable_session(state: &AppState) -> Result<Arc<InteractionManager>, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or_else(|| "No active session".to_string())?;
+    state
+        .session_usecase
+        .ensure_editable(manager.session_id())
+        .await
+        .map_err(structured_error)?;
+    Ok(manager)
+}
+
 /// Serializable version of DialogueMessage for Tauri IPC
 #[derive(Serialize, Clone)]
 pub struct SerializableDialogueMessage {
@@ -60,6 +92,15 @@ pub struct GeminiOptions {
     pub google_search: Option<bool>,
 }
 
+/// Whether and above which length long non-prose pastes become attachments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasteAttachmentSettings {
+    pub enabled: bool,
+    /// Length in characters
+    pub threshold: usize,
+}
+
 /// Serializable version of InteractionResult for Tauri IPC
 #[derive(Serialize)]
 #[serde(tag = "type", content = "data")]
@@ -193,17 +234,74 @@ pub async fn switch_session(
     Ok(session)
 }
 
+/// Opens a session for reading only, leaving the active session loaded
+#[tauri::command]
+pub async fn open_session_readonly(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<Session, String> {
+    let session = state
+        .session_usecase
+        .open_session_readonly(&session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(state
+        .session_usecase
+        .enrich_session_participants(session)
+        .await)
+}
+
+/// Closes the read-only view without switching sessions
+#[tauri::command]
+pub async fn close_readonly_session(state: State<'_, AppState>) -> Result<(), String> {
+    state.session_usecase.close_readonly_session().await;
+    Ok(())
+}
+
+/// Opens the read-only session for editing and makes it active
+#[tauri::command]
+pub async fn promote_readonly_to_active(
+    session_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Session, String> {
+    let session = state
+        .session_usecase
+        .promote_readonly_to_active(&session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    *state.app_mode.lock().await = session.app_mode.clone();
+
+    use orcs_core::state::repository::StateRepository;
+    if let Ok(app_state) = state.app_state_service.get_state().await {
+        let _ = app.emit("app-state:update", &app_state);
+    }
+
+    Ok(session)
+}
+
 /// Gets a session by ID without switching to it
+///
+/// A session open read-only is served from its view instead of storage.
 #[tauri::command]
 pub async fn get_session(
     session_id: String,
     state: State<'_, AppState>,
 ) -> Result<Option<Session>, String> {
-    let session = state
-        .session_repository
-        .find_by_id(&session_id)
+    let session = match state
+        .session_usecase
+        .readonly_session()
         .await
-        .map_err(|e| e.to_string())?;
+        .filter(|view| view.id() == session_id)
+    {
+        Some(view) => Some(view.session().clone()),
+        None => state
+            .session_repository
+            .find_by_id(&session_id)
+            .await
+            .map_err(|e| e.to_string())?,
+    };
 
     // Enrich with participant info if session exists
     match session {
@@ -225,6 +323,240 @@ pub async fn delete_session(session_id: String, state: State<'_, AppState>) -> R
         .map_err(|e| e.to_string())
 }
 
+/// Deletes archived, non-favorite sessions not updated for `older_than_days`
+///
+/// Returns the IDs of the deleted sessions.
+#[tauri::command]
+pub async fn prune_archived_sessions(
+    older_than_days: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    state
+        .session_usecase
+        .prune_archived(older_than_days)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Moves a session to a different workspace
+#[tauri::command]
+pub async fn move_session(
+    session_id: String,
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Session, String> {
+    state
+        .session_usecase
+        .move_session(&session_id, &workspace_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merges another session's history into a session, optionally archiving the other
+#[tauri::command]
+pub async fn merge_sessions(
+    primary_id: String,
+    secondary_id: String,
+    delete_secondary: bool,
+    state: State<'_, AppState>,
+) -> Result<Session, String> {
+    let session = state
+        .session_usecase
+        .merge_sessions(&primary_id, &secondary_id, delete_secondary)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(state
+        .session_usecase
+        .enrich_session_participants(session)
+        .await)
+}
+
+/// Rewrites a session file in the latest schema, dropping redundant system messages
+#[tauri::command]
+pub async fn compact_session(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<SessionCompaction, String> {
+    state
+        .session_usecase
+        .compact_session(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the assistant turns per persona of a session with a fairness score
+#[tauri::command]
+pub async fn get_turn_distribution(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<TurnDistribution, String> {
+    let turns = state
+        .session_usecase
+        .turn_distribution(&session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(TurnDistribution::new(turns))
+}
+
+/// Gets the duration, token usage and estimated cost of a session's agent turns
+/// per persona and per day
+#[tauri::command]
+pub async fn get_session_cost_breakdown(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<CostBreakdown, String> {
+    state
+        .session_usecase
+        .cost_breakdown(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the conversation of a session as chronological turns with resolved authors
+#[tauri::command]
+pub async fn get_conversation(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ConversationTurn>, String> {
+    state
+        .session_usecase
+        .get_conversation(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the context sources (shell output, search results, web clips, attached
+/// files) the message at `timestamp` copied passages from
+#[tauri::command]
+pub async fn get_message_sources(
+    session_id: String,
+    timestamp: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SourceRef>, String> {
+    state
+        .session_usecase
+        .message_sources(&session_id, &timestamp)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Extracts an ADR-style decision record from a session into workspace storage
+///
+/// `range` limits the transcript to messages between two inclusive timestamps.
+#[tauri::command]
+pub async fn extract_decision_record(
+    session_id: String,
+    range: Option<(String, String)>,
+    state: State<'_, AppState>,
+) -> Result<DecisionRecord, String> {
+    let record = state
+        .session_usecase
+        .extract_decision_record(
+            &session_id,
+            range
+                .as_ref()
+                .map(|(from, to)| (from.as_str(), to.as_str())),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_session(&session_id, app_mode)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(record)
+}
+
+/// Enables the wire log of a session, returning when it expires (RFC 3339)
+///
+/// `ttl_hours` defaults to, and is capped at, 24 hours.
+#[tauri::command]
+pub async fn enable_wire_log(
+    session_id: String,
+    ttl_hours: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let expires_at = state.wire_log.enable(
+        &session_id,
+        ttl_hours.unwrap_or(orcs_infrastructure::MAX_WIRE_LOG_TTL_HOURS),
+    );
+    tracing::info!(
+        "[WireLog] Enabled for session {} until {}",
+        session_id,
+        expires_at
+    );
+    Ok(expires_at.to_rfc3339())
+}
+
+/// Disables the wire log of a session; queued entries are dropped
+#[tauri::command]
+pub async fn disable_wire_log(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.wire_log.disable(&session_id);
+    Ok(())
+}
+
+/// Returns the path of a session's wire log file (JSONL, may not exist yet)
+#[tauri::command]
+pub async fn get_wire_log_path(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    Ok(state
+        .wire_log
+        .path(&session_id)
+        .to_string_lossy()
+        .to_string())
+}
+
+/// Clips a web page into a session as a stored document with a summary
+#[tauri::command]
+pub async fn clip_url(
+    session_id: String,
+    url: String,
+    state: State<'_, AppState>,
+) -> Result<UploadedFile, String> {
+    let clip = state
+        .web_clip_service
+        .clip(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+    for warning in &clip.warnings {
+        tracing::warn!("[clip_url] {}: {}", url, warning);
+    }
+
+    // Fall back to the lead paragraph when the summary agent is unavailable
+    let summary = match state
+        .utility_service
+        .generate_title(&clip.text, "article", true, false)
+        .await
+    {
+        Ok(response) => response.description,
+        Err(e) => {
+            tracing::warn!("[clip_url] Summary generation failed: {}", e);
+            None
+        }
+    };
+
+    let file = state
+        .session_usecase
+        .add_web_clip(&session_id, &clip, summary)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_session(&session_id, app_mode)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(file)
+}
+
 /// Renames a session
 #[tauri::command]
 pub async fn rename_session(
@@ -302,15 +634,16 @@ pub async fn append_system_messages(
         .await
         .ok_or_else(|| "No active session".to_string())?;
 
-    for message in messages {
-        let PersistedSystemMessage {
-            content,
-            message_type,
-            severity,
-        } = message;
+    let entries = messages
+        .into_iter()
+        .map(|message| {
+            let PersistedSystemMessage {
+                content,
+                message_type,
+                severity,
+            } = message;
 
-        let severity_enum =
-            severity
+            let error_severity = severity
                 .as_ref()
                 .map(|s| s.to_lowercase())
                 .and_then(|level| match level.as_str() {
@@ -320,10 +653,14 @@ pub async fn append_system_messages(
                     _ => None,
                 });
 
-        manager
-            .add_system_conversation_message(content, message_type, severity_enum)
-            .await;
-    }
+            SystemMessageEntry {
+                content,
+                message_type,
+                error_severity,
+            }
+        })
+        .collect();
+    manager.add_system_conversation_messages(entries).await;
 
     let app_mode = state.app_mode.lock().await.clone();
     state
@@ -344,13 +681,14 @@ pub async fn publish_session_event(
         SessionEvent::UserInput {
             content,
             attachments,
+            confirm_ignored,
         } => {
             let paths = if attachments.is_empty() {
                 None
             } else {
                 Some(attachments)
             };
-            handle_input(content, paths, app, state).await
+            handle_input(content, paths, Some(confirm_ignored), app, state).await
         }
         SessionEvent::SystemEvent {
             content,
@@ -639,9 +977,15 @@ pub async fn get_active_session(state: State<'_, AppState>) -> Result<Option<Ses
 }
 
 /// Executes a message content as a task using TaskExecutor
+///
+/// The task is linked to the message at `origin_message_timestamp`, or to the
+/// latest message of the session when omitted. A dry run describes what each
+/// step would do instead of doing it.
 #[tauri::command]
 pub async fn execute_message_as_task(
     message_content: String,
+    origin_message_timestamp: Option<String>,
+    dry_run: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
     let manager = state
@@ -681,13 +1025,19 @@ pub async fn execute_message_as_task(
     // Build thread context from session's Summary and recent messages
     let thread_context = build_thread_context_for_task(&session);
 
+    let (task_id, origin) =
+        super::tasks::link_new_task(&manager, origin_message_timestamp.as_deref(), &state).await;
+
     state
         .task_executor
-        .execute_from_message_with_context(
+        .execute_from_message_with_origin(
+            task_id,
             session_id,
             message_content,
             workspace_root,
             thread_context,
+            origin,
+            dry_run.unwrap_or(false),
         )
         .await
         .map_err(|e| e.to_string())
@@ -756,11 +1106,7 @@ fn build_thread_context_for_task(session: &Session) -> Option<String> {
 /// Adds a participant to the active session
 #[tauri::command]
 pub async fn add_participant(persona_id: String, state: State<'_, AppState>) -> Result<(), String> {
-    let manager = state
-        .session_usecase
-        .active_session()
-        .await
-        .ok_or("No active session")?;
+    let manager = editable_session(&state).await?;
 
     manager
         .add_participant(&persona_id)
@@ -774,19 +1120,18 @@ pub async fn add_participant(persona_id: String, state: State<'_, AppState>) ->
 }
 
 /// Removes a participant from the active session
+///
+/// With `with_handoff`, the persona first writes a handoff note for the remaining participants.
 #[tauri::command]
 pub async fn remove_participant(
     persona_id: String,
+    with_handoff: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let manager = state
-        .session_usecase
-        .active_session()
-        .await
-        .ok_or("No active session")?;
+    let manager = editable_session(&state).await?;
 
     manager
-        .remove_participant(&persona_id)
+        .remove_participant(&persona_id, with_handoff.unwrap_or(false))
         .await
         .map_err(|e| e.to_string())?;
 
@@ -808,60 +1153,458 @@ pub async fn get_active_participants(state: State<'_, AppState>) -> Result<Vec<S
     manager.get_active_participants().await
 }
 
-/// Toggles mute status for the active session and returns the new value
+/// Lets only the given participants (IDs or names) respond until focus is cleared
 #[tauri::command]
-pub async fn toggle_mute(state: State<'_, AppState>) -> Result<bool, String> {
-    let manager = state
+pub async fn set_session_focus(
+    persona_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_focus(persona_ids).await?;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
         .session_usecase
-        .active_session()
+        .save_active_session(app_mode)
         .await
-        .ok_or("No active session")?;
+        .map_err(|e| e.to_string())
+}
 
-    let is_muted = manager.toggle_mute().await;
+/// Clears the focus so every participant responds again
+#[tauri::command]
+pub async fn clear_session_focus(state: State<'_, AppState>) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
 
-    // Save session
-    let app_mode = state.app_mode.lock().await.clone();
-    let _ = state.session_usecase.save_active_session(app_mode).await;
+    manager.clear_focus().await;
 
-    Ok(is_muted)
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
 }
 
-/// Gets the mute status for the active session
+/// Gets the focused participant IDs of the active session, if focus is on
 #[tauri::command]
-pub async fn get_mute_status(state: State<'_, AppState>) -> Result<bool, String> {
+pub async fn get_session_focus(state: State<'_, AppState>) -> Result<Option<Vec<String>>, String> {
     let manager = state
         .session_usecase
         .active_session()
         .await
         .ok_or("No active session")?;
 
-    Ok(manager.is_muted().await)
+    Ok(manager.focused_participant_ids().await)
 }
 
-/// Gets the context mode for the active session
+/// Gets the union of capabilities supported by the active session's participants
 #[tauri::command]
-pub async fn get_context_mode(state: State<'_, AppState>) -> Result<String, String> {
+pub async fn get_session_capabilities(
+    state: State<'_, AppState>,
+) -> Result<CapabilityFlags, String> {
     let manager = state
         .session_usecase
         .active_session()
         .await
         .ok_or("No active session")?;
 
-    let mode = manager.get_context_mode().await;
-    Ok(match mode {
-        orcs_core::session::ContextMode::Rich => "rich".to_string(),
-        orcs_core::session::ContextMode::Clean => "clean".to_string(),
+    Ok(manager.session_capabilities().await)
+}
+
+/// Gets the model each participant of the active session runs with, keyed by persona ID
+#[tauri::command]
+pub async fn get_effective_models(
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, String>, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.effective_models().await)
+}
+
+/// Gets the participants of a loaded session whose personas were deleted
+#[tauri::command]
+pub async fn get_restore_warnings(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<RestoreWarning>, String> {
+    let manager = state
+        .session_usecase
+        .loaded_session(&session_id)
+        .await
+        .ok_or_else(|| format!("Session {} is not loaded", session_id))?;
+
+    Ok(manager.restore_warnings().await)
+}
+
+/// Rebuilds participant names/icons/colors of the active session from the persona
+/// repository (e.g. after a persona edit) without rebuilding the dialogue or saving
+#[tauri::command]
+pub async fn refresh_participant_metadata(
+    state: State<'_, AppState>,
+) -> Result<ParticipantMetadata, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.refresh_participant_metadata().await)
+}
+
+/// Toggles mute status for the active session and returns the new value
+#[tauri::command]
+pub async fn toggle_mute(state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = editable_session(&state).await?;
+
+    let is_muted = manager.toggle_mute().await;
+
+    // Save session
+    let app_mode = state.app_mode.lock().await.clone();
+    let _ = state.session_usecase.save_active_session(app_mode).await;
+
+    Ok(is_muted)
+}
+
+/// Gets the mute status for the active session
+#[tauri::command]
+pub async fn get_mute_status(state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.is_muted().await)
+}
+
+/// Style calibration status of the active session
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StyleCalibrationStatus {
+    pub enabled: bool,
+    pub personas: Vec<PersonaCalibrationState>,
+}
+
+/// Enables or disables style calibration for the active session
+#[tauri::command]
+pub async fn set_style_calibration(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_style_calibration_enabled(enabled).await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Records user feedback on a persona's latest answer
+#[tauri::command]
+pub async fn record_message_feedback(
+    persona_id: String,
+    kind: FeedbackKind,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.record_feedback(&persona_id, kind).await?;
+
+    // Persist any calibration change message
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the style calibration status for the active session
+#[tauri::command]
+pub async fn get_style_calibration(
+    state: State<'_, AppState>,
+) -> Result<StyleCalibrationStatus, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(StyleCalibrationStatus {
+        enabled: manager.is_style_calibration_enabled().await,
+        personas: manager.style_calibration_states().await,
+    })
+}
+
+/// Enables or disables splitting replies that speak for several personas
+#[tauri::command]
+pub async fn set_response_splitting(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_response_splitting_enabled(enabled).await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets whether the active session splits replies that speak for several personas
+#[tauri::command]
+pub async fn get_response_splitting(state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.is_response_splitting_enabled().await)
+}
+
+/// Sets (or clears, with `None`) the seed passed to backends that support one
+#[tauri::command]
+pub async fn set_random_seed(seed: Option<u64>, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_random_seed(seed).await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the seed the active session passes to backends that support one
+#[tauri::command]
+pub async fn get_random_seed(state: State<'_, AppState>) -> Result<Option<u64>, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.random_seed().await)
+}
+
+/// Enables or disables the current-time context given to agents
+#[tauri::command]
+pub async fn set_clock_context(enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_clock_context_enabled(enabled).await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets whether the active session gives agents the current time
+#[tauri::command]
+pub async fn get_clock_context(state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.is_clock_context_enabled().await)
+}
+
+/// Enables or disables turning `plan` blocks in agent replies into confirmable plans
+#[tauri::command]
+pub async fn set_plan_detection(enabled: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_plan_detection_enabled(enabled).await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets whether the active session turns `plan` blocks into confirmable plans
+#[tauri::command]
+pub async fn get_plan_detection(state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.is_plan_detection_enabled().await)
+}
+
+/// Sets whether and above which length long non-prose pastes are stored as
+/// workspace files and sent as an excerpt plus attachment
+#[tauri::command]
+pub async fn set_paste_attachment(
+    settings: PasteAttachmentSettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if settings.threshold == 0 {
+        return Err("Threshold must be at least 1 character".to_string());
+    }
+    let manager = editable_session(&state).await?;
+
+    manager
+        .set_paste_attachment(settings.enabled, settings.threshold)
+        .await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the paste-to-attachment settings of the active session
+#[tauri::command]
+pub async fn get_paste_attachment(
+    state: State<'_, AppState>,
+) -> Result<PasteAttachmentSettings, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    let (enabled, threshold) = manager.paste_attachment_settings().await;
+    Ok(PasteAttachmentSettings { enabled, threshold })
+}
+
+/// Previews the collaboration guidelines agents of the active session get
+/// under its current execution strategy
+#[tauri::command]
+pub async fn preview_collaboration_guidelines(
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.collaboration_guidelines().await)
+}
+
+/// Gets the full text of the message at `timestamp`, reading long pastes
+/// back from the file they were stored in
+#[tauri::command]
+pub async fn get_full_message(
+    session_id: String,
+    timestamp: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    state
+        .session_usecase
+        .full_message(&session_id, &timestamp)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replaces the pinboard of the active session (an empty document clears it)
+#[tauri::command]
+pub async fn set_pinboard(
+    content: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_pinboard(content).await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the pinboard of the active session
+#[tauri::command]
+pub async fn get_pinboard(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.get_pinboard().await)
+}
+
+/// Allows or forbids agents to append to the active session's pinboard
+#[tauri::command]
+pub async fn set_pinboard_agent_edits(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_pinboard_agent_edits_enabled(enabled).await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets whether agents may append to the active session's pinboard
+#[tauri::command]
+pub async fn get_pinboard_agent_edits(state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.is_pinboard_agent_edits_enabled().await)
+}
+
+/// Gets the context mode for the active session
+#[tauri::command]
+pub async fn get_context_mode(state: State<'_, AppState>) -> Result<String, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    let mode = manager.get_context_mode().await;
+    Ok(match mode {
+        orcs_core::session::ContextMode::Rich => "rich".to_string(),
+        orcs_core::session::ContextMode::Clean => "clean".to_string(),
     })
 }
 
 /// Sets the context mode for the active session
 #[tauri::command]
 pub async fn set_context_mode(mode: String, state: State<'_, AppState>) -> Result<(), String> {
-    let manager = state
-        .session_usecase
-        .active_session()
-        .await
-        .ok_or("No active session")?;
+    let manager = editable_session(&state).await?;
 
     let context_mode = match mode.as_str() {
         "rich" => orcs_core::session::ContextMode::Rich,
@@ -878,18 +1621,87 @@ pub async fn set_context_mode(mode: String, state: State<'_, AppState>) -> Resul
     Ok(())
 }
 
-/// Sets the execution strategy for the active session
+/// Sets whether the conversation-mode instruction applies in Clean context mode
 #[tauri::command]
-pub async fn set_execution_strategy(
-    strategy: ExecutionModelType,
+pub async fn set_conversation_mode_in_clean(
+    applied: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager
+        .set_conversation_mode_applied_in_clean(applied)
+        .await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets whether the conversation-mode instruction applies in Clean context mode
+#[tauri::command]
+pub async fn get_conversation_mode_in_clean(state: State<'_, AppState>) -> Result<bool, String> {
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    Ok(manager.is_conversation_mode_applied_in_clean().await)
+}
+
+/// Enables or disables participant suggestions for the active session
+#[tauri::command]
+pub async fn set_participant_suggestions(
+    enabled: bool,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
+    manager.set_participant_suggestions_enabled(enabled).await;
+
+    let app_mode = state.app_mode.lock().await.clone();
+    state
+        .session_usecase
+        .save_active_session(app_mode)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets whether participant suggestions are enabled for the active session
+#[tauri::command]
+pub async fn get_participant_suggestions(state: State<'_, AppState>) -> Result<bool, String> {
     let manager = state
         .session_usecase
         .active_session()
         .await
         .ok_or("No active session")?;
 
+    Ok(manager.is_participant_suggestions_enabled().await)
+}
+
+/// Stops suggesting a persona for the rest of the active session
+#[tauri::command]
+pub async fn dismiss_participant_suggestion(
+    persona_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+    manager.dismiss_participant_suggestion(&persona_id).await;
+    Ok(())
+}
+
+/// Sets the execution strategy for the active session
+#[tauri::command]
+pub async fn set_execution_strategy(
+    strategy: ExecutionModelType,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let manager = editable_session(&state).await?;
+
     // Convert ExecutionModelType (Anti-Corruption Layer) to ExecutionModel (llm-toolkit)
     let execution_model: ExecutionModel = strategy.into();
     manager.set_execution_strategy(execution_model).await;
@@ -919,11 +1731,7 @@ pub async fn get_execution_strategy(
 /// Sets the conversation mode for the active session
 #[tauri::command]
 pub async fn set_conversation_mode(mode: String, state: State<'_, AppState>) -> Result<(), String> {
-    let manager = state
-        .session_usecase
-        .active_session()
-        .await
-        .ok_or("No active session")?;
+    let manager = editable_session(&state).await?;
 
     let conversation_mode = match mode.as_str() {
         "normal" => ConversationMode::Normal,
@@ -968,11 +1776,7 @@ pub async fn set_talk_style(
     style: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let manager = state
-        .session_usecase
-        .active_session()
-        .await
-        .ok_or("No active session")?;
+    let manager = editable_session(&state).await?;
 
     // Parse TalkStyle using schema-bridge v0.2 string_conversion
     let talk_style = style
@@ -1008,41 +1812,134 @@ pub async fn get_talk_style(state: State<'_, AppState>) -> Result<Option<String>
     Ok(style_str)
 }
 
+/// Refuses attachments that the `.orcsignore` of the session's workspace
+/// excludes, unless the user confirmed them.
+async fn check_ignored_attachments(
+    manager: &InteractionManager,
+    file_paths: Option<&Vec<String>>,
+    confirm_ignored: Option<bool>,
+    state: &AppState,
+) -> Result<(), String> {
+    if confirm_ignored == Some(true) {
+        return Ok(());
+    }
+    let (Some(paths), Some(root)) = (file_paths, manager.get_agent_workspace_root().await) else {
+        return Ok(());
+    };
+    let ignored = state.workspace_ignore.ignored_paths(&root, paths);
+    if ignored.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "These attachments are excluded by .orcsignore; confirm to attach them anyway: {}",
+        ignored.join(", ")
+    ))
+}
+
 /// Handles user input
+///
+/// Attachments excluded by the workspace's `.orcsignore` are refused unless
+/// `confirm_ignored` is set.
 #[tauri::command]
 pub async fn handle_input(
     input: String,
     file_paths: Option<Vec<String>>,
+    confirm_ignored: Option<bool>,
     app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<SerializableInteractionResult, String> {
-    let manager = state
+    let manager = editable_session(&state).await?;
+    check_ignored_attachments(&manager, file_paths.as_ref(), confirm_ignored, &state).await?;
+
+    apply_slash_command_prompt_extension(&manager, &state).await;
+
+    let current_mode = state.app_mode.lock().await.clone();
+
+    let processed_input = preprocess_input(&input, &state).await;
+    emit_participant_suggestions(&app, &manager, &processed_input).await;
+
+    let result = manager
+        .handle_input_with_streaming(
+            &current_mode,
+            &processed_input,
+            file_paths,
+            stream_turn(app),
+        )
+        .await;
+
+    finish_turn(&result, manager.session_id(), &state).await;
+
+    Ok(result.into())
+}
+
+/// Handles user input in the background so the user can navigate away.
+///
+/// Turns are persisted as they arrive and the session reports `isGenerating`
+/// until the dialogue finishes. A `Final` dialogue-turn event is emitted on
+/// completion so a reattached view can reload the session.
+#[tauri::command]
+pub async fn handle_input_background(
+    input: String,
+    file_paths: Option<Vec<String>>,
+    confirm_ignored: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let manager = editable_session(&state).await?;
+    check_ignored_attachments(&manager, file_paths.as_ref(), confirm_ignored, &state).await?;
+    let session_id = manager.session_id().to_string();
+
+    apply_slash_command_prompt_extension(&manager, &state).await;
+
+    let current_mode = state.app_mode.lock().await.clone();
+    let processed_input = preprocess_input(&input, &state).await;
+    emit_participant_suggestions(&app, &manager, &processed_input).await;
+    let handle = state
         .session_usecase
-        .active_session()
+        .handle_input_background(
+            current_mode,
+            processed_input,
+            file_paths,
+            stream_turn(app.clone()),
+        )
         .await
-        .ok_or("No active session")?;
+        .map_err(|e| e.to_string())?;
 
-    // Only inject SlashCommand prompt extension in Rich mode
-    let context_mode = manager.get_context_mode().await;
-    if matches!(context_mode, orcs_core::session::ContextMode::Rich) {
-        let slash_commands = state
-            .slash_command_repository
-            .list_commands()
-            .await
-            .unwrap_or_else(|e| {
-                eprintln!("[handle_input] Failed to list commands: {}", e);
-                Vec::new()
-            });
-        let prompt_extension = build_slash_command_prompt(&slash_commands);
-        manager.set_prompt_extension(prompt_extension).await;
-    } else {
-        // Clean mode: no prompt extension
-        manager.set_prompt_extension(None).await;
-    }
+    let event_session_id = session_id.clone();
+    tokio::spawn(async move {
+        use orcs_interaction::{StreamingDialogueTurn, StreamingDialogueTurnKind};
 
-    let current_mode = state.app_mode.lock().await.clone();
+        let kind = match handle.await {
+            Ok(result) => {
+                let state = app.state::<AppState>();
+                finish_turn(&result, &event_session_id, &state).await;
+                StreamingDialogueTurnKind::Final
+            }
+            Err(e) => StreamingDialogueTurnKind::Error {
+                message: format!("Background dialogue failed: {}", e),
+                retryable: false,
+                wire_log_entry_id: None,
+            },
+        };
+        let event = StreamingDialogueTurn {
+            session_id: event_session_id,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            kind,
+        };
+        if let Err(e) = app.emit("dialogue-turn", event) {
+            eprintln!("[TAURI] Failed to emit background completion: {}", e);
+        }
+    });
 
-    let processed_input = if input.trim().starts_with('/') {
+    Ok(session_id)
+}
+
+/// Expands slash commands typed directly into the input box.
+///
+/// Prompt and shell commands are expanded into the text sent to the agents;
+/// other input is returned unchanged.
+async fn preprocess_input(input: &str, state: &AppState) -> String {
+    if input.trim().starts_with('/') {
         let trimmed = input.trim();
         let cmd_end = trimmed.find(' ').unwrap_or(trimmed.len());
         let cmd_name = &trimmed[1..cmd_end];
@@ -1059,12 +1956,15 @@ pub async fn handle_input(
 
         // Check for built-in entity commands first (critical commands that should always work)
         match cmd_name {
-            "create-persona" => match execute_create_persona(args, &state).await {
+            "create-persona" => match execute_create_persona(args, state).await {
                 Ok(persona) => format!(
                     "✅ Successfully created persona '{}'\n\nID: {}\nRole: {}\nBackend: {:?}\n\nThe persona is now available in the Personas panel.",
                     persona.name, persona.id, persona.role, persona.backend
                 ),
-                Err(e) => format!("❌ Failed to create persona: {}", e),
+                Err(e) => {
+                    report_rejected_persona(&e, state).await;
+                    format!("❌ Failed to create persona: {}", e)
+                }
             },
             "create-slash-command" => {
                 "❌ /create-slash-command is not yet implemented.\n\nPlease create slash commands manually in ~/.orcs/slash_commands/ for now.".to_string()
@@ -1145,88 +2045,122 @@ pub async fn handle_input(
             }
         }
     } else {
-        input.clone()
-    };
+        input.to_string()
+    }
+}
 
-    let app_clone = app.clone();
-    let result = manager
-        .handle_input_with_streaming(&current_mode, &processed_input, file_paths, move |turn| {
-            use orcs_interaction::{StreamingDialogueTurn, StreamingDialogueTurnKind};
-
-            let now = SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap();
-            let preview: String = turn.content.chars().take(50).collect();
-            eprintln!(
-                "[TAURI] [{}.{:03}] Streaming turn: {} - {}...",
-                now.as_secs(),
-                now.subsec_millis(),
-                turn.author,
-                preview
-            );
-
-            // Convert DialogueMessage to StreamingDialogueTurn for frontend
-            let streaming_turn = StreamingDialogueTurn {
-                session_id: turn.session_id.clone(),
-                timestamp: chrono::Utc::now().to_rfc3339(),
-                kind: StreamingDialogueTurnKind::Chunk {
-                    author: turn.author.clone(),
-                    content: turn.content.clone(),
-                },
-            };
+/// Builds the per-turn callback shared by foreground and background input.
+///
+/// Each turn is emitted to the frontend as a `dialogue-turn` chunk. Crash
+/// recovery is recorded by the session's manager itself.
+fn stream_turn(
+    app: AppHandle,
+) -> impl Fn(&orcs_interaction::DialogueMessage) + Send + Sync + 'static {
+    move |turn| {
+        use orcs_interaction::StreamingDialogueTurn;
+
+        let now = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap();
+        let preview: String = turn.content.chars().take(50).collect();
+        eprintln!(
+            "[TAURI] [{}.{:03}] Streaming turn: {} - {}...",
+            now.as_secs(),
+            now.subsec_millis(),
+            turn.author,
+            preview
+        );
 
-            if let Err(e) = app_clone.emit("dialogue-turn", streaming_turn) {
-                eprintln!("[TAURI] Failed to emit dialogue-turn event: {}", e);
-            }
-        })
-        .await;
+        // Convert DialogueMessage to StreamingDialogueTurn for frontend
+        let streaming_turn = StreamingDialogueTurn {
+            session_id: turn.session_id.clone(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            kind: turn.to_streaming_kind(),
+        };
 
-    if let InteractionResult::ModeChanged(ref new_mode) = result {
-        *state.app_mode.lock().await = new_mode.clone();
+        if let Err(e) = app.emit("dialogue-turn", streaming_turn) {
+            eprintln!("[TAURI] Failed to emit dialogue-turn event: {}", e);
+        }
     }
-
-    let app_mode = state.app_mode.lock().await.clone();
-    let _ = state.session_usecase.save_active_session(app_mode).await;
-
-    Ok(result.into())
 }
 
-/// Helper function to create a persona from JSON arguments
-async fn execute_create_persona(
-    args: &str,
-    state: &State<'_, AppState>,
-) -> Result<orcs_core::persona::Persona, String> {
-    use orcs_core::persona::CreatePersonaRequest;
-
-    // Parse JSON into CreatePersonaRequest
-    let request: CreatePersonaRequest =
-        serde_json::from_str(args).map_err(|e| format!("Invalid JSON: {}", e))?;
+/// Applies the outcome of a finished input to the session it ran in.
+///
+/// A mode change only updates the app mode while that session is still active.
+/// Emits a `ParticipantSuggestion` dialogue-turn event when personas outside
+/// the session match the user's input better than its participants.
+///
+/// Does nothing unless the session opted in to suggestions.
+async fn emit_participant_suggestions(app: &AppHandle, manager: &InteractionManager, input: &str) {
+    use orcs_interaction::{StreamingDialogueTurn, StreamingDialogueTurnKind};
 
-    // Validate request
-    request.validate()?;
+    let suggestions = manager.suggest_participants(input).await;
+    if suggestions.is_empty() {
+        return;
+    }
+    let event = StreamingDialogueTurn {
+        session_id: manager.session_id().to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        kind: StreamingDialogueTurnKind::ParticipantSuggestion { suggestions },
+    };
+    if let Err(e) = app.emit("dialogue-turn", event) {
+        eprintln!("[TAURI] Failed to emit participant suggestions: {}", e);
+    }
+}
 
-    // Convert to Persona (UUID auto-generated if needed)
-    let persona = request.into_persona();
+async fn finish_turn(result: &InteractionResult, session_id: &str, state: &AppState) {
+    if let InteractionResult::ModeChanged(new_mode) = result
+        && state.session_usecase.active_session_id().await.as_deref() == Some(session_id)
+    {
+        *state.app_mode.lock().await = new_mode.clone();
+    }
+    persist_turns(session_id, state).await;
+}
 
-    // Save to repository
-    let mut all_personas = state
-        .persona_repository
-        .get_all()
+/// Saves the session the turns ran in and drops its recovery file once the
+/// save succeeds.
+pub(crate) async fn persist_turns(session_id: &str, state: &AppState) {
+    let app_mode = state.app_mode.lock().await.clone();
+    if state
+        .session_usecase
+        .save_session(session_id, app_mode)
         .await
-        .map_err(|e| format!("Failed to load personas: {}", e))?;
-
-    // Check for duplicate ID
-    if all_personas.iter().any(|p| p.id == persona.id) {
-        return Err(format!("Persona with ID '{}' already exists", persona.id));
+        .is_ok()
+    {
+        // The turns are persisted; their recovery file is no longer needed
+        state.stream_recovery_writer.complete(session_id);
     }
+}
 
-    all_personas.push(persona.clone());
+/// Injects the SlashCommand prompt extension (Rich mode only).
+async fn apply_slash_command_prompt_extension(
+    manager: &orcs_interaction::InteractionManager,
+    state: &AppState,
+) {
+    let context_mode = manager.get_context_mode().await;
+    if matches!(context_mode, orcs_core::session::ContextMode::Rich) {
+        let slash_commands = state
+            .slash_command_repository
+            .list_commands()
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!("[handle_input] Failed to list commands: {}", e);
+                Vec::new()
+            });
+        let prompt_extension = build_slash_command_prompt(&slash_commands);
+        manager.set_prompt_extension(prompt_extension).await;
+    } else {
+        // Clean mode: no prompt extension
+        manager.set_prompt_extension(None).await;
+    }
+}
 
-    state
-        .persona_repository
-        .save_all(&all_personas)
-        .await
-        .map_err(|e| format!("Failed to save persona: {}", e))?;
+/// Helper function to create a persona from JSON arguments
+async fn execute_create_persona(
+    args: &str,
+    state: &AppState,
+) -> anyhow::Result<orcs_core::persona::Persona> {
+    let persona = state.persona_service.create_from_json(args).await?;
 
     // Invalidate dialogue cache to reflect new persona
     if let Some(manager) = state.session_usecase.active_session().await {
@@ -1236,6 +2170,23 @@ async fn execute_create_persona(
     Ok(persona)
 }
 
+/// Shows why a `/create-persona` definition was rejected as a System message
+/// in the active session.
+async fn report_rejected_persona(error: &anyhow::Error, state: &AppState) {
+    let Some(invalid) = error.downcast_ref::<PersonaValidationError>() else {
+        return;
+    };
+    if let Some(manager) = state.session_usecase.active_session().await {
+        manager
+            .add_system_conversation_message(
+                invalid.to_string(),
+                Some("error".to_string()),
+                Some(ErrorSeverity::Warning),
+            )
+            .await;
+    }
+}
+
 /// Helper function to execute shell commands
 async fn execute_shell_command(command: &str, working_dir: Option<&str>) -> Result<String, String> {
     #[cfg(target_os = "windows")]
@@ -1316,11 +2267,7 @@ pub async fn update_auto_chat_config(
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     // Get the current active session's manager
-    let manager = state
-        .session_usecase
-        .active_session()
-        .await
-        .ok_or_else(|| "No active session".to_string())?;
+    let manager = editable_session(&state).await?;
 
     // Verify session ID matches
     if manager.session_id() != session_id {
@@ -1373,14 +2320,12 @@ pub async fn get_auto_chat_status(
 pub async fn start_auto_chat(
     input: String,
     file_paths: Option<Vec<String>>,
+    confirm_ignored: Option<bool>,
     app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<SerializableInteractionResult, String> {
-    let manager = state
-        .session_usecase
-        .active_session()
-        .await
-        .ok_or("No active session")?;
+    let manager = editable_session(&state).await?;
+    check_ignored_attachments(&manager, file_paths.as_ref(), confirm_ignored, &state).await?;
 
     tracing::info!(
         "[AutoChat] Starting with input: {}",
@@ -1394,6 +2339,7 @@ pub async fn start_auto_chat(
 
     let app_clone = app.clone();
     let app_clone2 = app.clone();
+    let app_clone3 = app.clone();
     let session_id_clone = session_id.clone();
 
     let result = manager
@@ -1401,22 +2347,36 @@ pub async fn start_auto_chat(
             &input,
             file_paths,
             move |turn| {
-                use orcs_interaction::{StreamingDialogueTurn, StreamingDialogueTurnKind};
+                use orcs_interaction::StreamingDialogueTurn;
 
                 // Convert DialogueMessage to StreamingDialogueTurn for frontend
                 let streaming_turn = StreamingDialogueTurn {
                     session_id: turn.session_id.clone(),
                     timestamp: chrono::Utc::now().to_rfc3339(),
-                    kind: StreamingDialogueTurnKind::Chunk {
-                        author: turn.author.clone(),
-                        content: turn.content.clone(),
-                    },
+                    kind: turn.to_streaming_kind(),
                 };
 
                 if let Err(e) = app_clone.emit("dialogue-turn", streaming_turn) {
                     eprintln!("[TAURI] Failed to emit dialogue-turn event: {}", e);
                 }
             },
+            move |current_iteration, delay| {
+                use orcs_interaction::{StreamingDialogueTurn, StreamingDialogueTurnKind};
+
+                let progress_event = StreamingDialogueTurn {
+                    session_id: session_id.clone(),
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    kind: StreamingDialogueTurnKind::AutoChatProgress {
+                        current_iteration,
+                        max_iterations,
+                        delay_ms: delay.as_millis() as u64,
+                    },
+                };
+
+                if let Err(e) = app_clone3.emit("dialogue-turn", progress_event) {
+                    eprintln!("[TAURI] Failed to emit AutoChat progress event: {}", e);
+                }
+            },
             Some(state.cancel_flag.clone()),
         )
         .await;
@@ -1435,13 +2395,53 @@ pub async fn start_auto_chat(
         eprintln!("[TAURI] Failed to emit AutoChat completion event: {}", e);
     }
 
-    // Save the session after AutoChat completes
-    let app_mode = state.app_mode.lock().await.clone();
-    let _ = state.session_usecase.save_active_session(app_mode).await;
+    // Save the session AutoChat ran in, even if another one is active by now
+    persist_turns(&session_id_clone, &state).await;
 
     Ok(result.into())
 }
 
+/// Runs a poll among the active session's participants and returns the tally.
+///
+/// Each reply and the final tally are streamed as `dialogue-turn` events.
+#[tauri::command]
+pub async fn run_session_poll(
+    question: String,
+    options: Vec<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PollResult, String> {
+    use orcs_interaction::StreamingDialogueTurn;
+
+    let manager = state
+        .session_usecase
+        .active_session()
+        .await
+        .ok_or("No active session")?;
+
+    let result = manager
+        .run_poll(
+            &question,
+            options,
+            Some(move |turn: &orcs_interaction::DialogueMessage| {
+                let streaming_turn = StreamingDialogueTurn {
+                    session_id: turn.session_id.clone(),
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    kind: turn.to_streaming_kind(),
+                };
+
+                if let Err(e) = app.emit("dialogue-turn", streaming_turn) {
+                    eprintln!("[TAURI] Failed to emit dialogue-turn event: {}", e);
+                }
+            }),
+        )
+        .await?;
+
+    persist_turns(manager.session_id(), &state).await;
+
+    Ok(result)
+}
+
 // ============================================================================
 // Session Support Commands (Summary, ActionPlan)
 // ============================================================================
@@ -1482,6 +2482,9 @@ pub async fn generate_summary(
         execution_details: None,
         strategy: None,
         journal_log: None,
+        origin_message_timestamp: None,
+        origin_author: None,
+        dry_run: false,
     };
 
     // Save and emit task created event
@@ -1584,6 +2587,9 @@ pub async fn generate_action_plan(
         execution_details: None,
         strategy: None,
         journal_log: None,
+        origin_message_timestamp: None,
+        origin_author: None,
+        dry_run: false,
     };
 
     // Save and emit task created event
@@ -1687,6 +2693,9 @@ pub async fn generate_expertise(
         execution_details: None,
         strategy: None,
         journal_log: None,
+        origin_message_timestamp: None,
+        origin_author: None,
+        dry_run: false,
     };
 
     // Save and emit task created event
@@ -1789,6 +2798,9 @@ pub async fn generate_concept_issue(
         execution_details: None,
         strategy: None,
         journal_log: None,
+        origin_message_timestamp: None,
+        origin_author: None,
+        dry_run: false,
     };
 
     // Save and emit task created event
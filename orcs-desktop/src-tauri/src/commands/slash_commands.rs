@@ -1,21 +1,27 @@
 use std::process::Command;
 
 use chrono::Utc;
+use orcs_application::PendingActionRequest;
 use orcs_application::SessionSupportAgentService;
 use orcs_core::agent::build_enhanced_path;
+use orcs_core::pending_action::{
+    CommandSource, DestructiveCommandDetector, PendingAction, PendingActionKind,
+};
 use orcs_core::session::PLACEHOLDER_WORKSPACE_ID;
-use orcs_core::slash_command::{CommandType, CreateSlashCommandRequest, SlashCommand};
+use orcs_core::slash_command::{
+    CommandType, CreateSlashCommandRequest, SlashCommand, SlashCommandContentType,
+    SlashCommandResult,
+};
 use orcs_core::task::{Task, TaskStatus};
 use orcs_core::workspace::manager::WorkspaceStorageService;
 use orcs_execution::tracing_layer::OrchestratorEventBuilder;
 use serde::Serialize;
-use tauri::State;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 
 use crate::app::AppState;
-use crate::slash_commands::{
-    ExpandedSlashCommand, expand_slash_command, get_git_branch, get_git_status,
-};
+use crate::commands::pending_actions::emit_pending_action_created;
+use crate::slash_commands::{expand_slash_command, get_git_branch, get_git_status};
 
 /// Persona info for action result display
 #[derive(Debug, Clone, Serialize)]
@@ -68,6 +74,7 @@ pub async fn create_slash_command(
 
     // Convert to SlashCommand
     let command = request.into_slash_command();
+    command.validate().map_err(|e| e.to_string())?;
 
     // Check for duplicate name
     if let Ok(Some(_)) = state
@@ -88,12 +95,13 @@ pub async fn create_slash_command(
     Ok(command)
 }
 
-/// Saves a slash command (add or update)
+/// Saves a slash command (add or update), rejecting invalid definitions
 #[tauri::command]
 pub async fn save_slash_command(
     command: SlashCommand,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    command.validate().map_err(|e| e.to_string())?;
     state
         .slash_command_repository
         .save_command(command)
@@ -117,7 +125,7 @@ pub async fn expand_command_template(
     command_name: String,
     args: Option<String>,
     state: State<'_, AppState>,
-) -> Result<ExpandedSlashCommand, String> {
+) -> Result<SlashCommandResult, String> {
     // Get the command
     let command = state
         .slash_command_repository
@@ -126,17 +134,20 @@ pub async fn expand_command_template(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Command not found: {}", command_name))?;
 
-    let expanded =
-        expand_slash_command(&command, args.as_deref().unwrap_or_default(), &state).await?;
-
-    Ok(expanded)
+    expand_slash_command(&command, args.as_deref().unwrap_or_default(), &state).await
 }
 
 /// Executes a task workflow command
+///
+/// The task is linked to the message at `origin_message_timestamp`, or to the
+/// latest message of the session when omitted. A dry run describes what each
+/// step would do instead of doing it.
 #[tauri::command]
 pub async fn execute_task_command(
     command_name: String,
     args: Option<String>,
+    origin_message_timestamp: Option<String>,
+    dry_run: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
     use orcs_core::slash_command::CommandType;
@@ -202,10 +213,21 @@ pub async fn execute_task_command(
     // Prepare message content for task execution
     let message_content = args.unwrap_or_else(|| command.content.clone());
 
+    let (task_id, origin) =
+        super::tasks::link_new_task(&manager, origin_message_timestamp.as_deref(), &state).await;
+
     // Execute task using TaskExecutor (same as execute_message_as_task)
     state
         .task_executor
-        .execute_from_message(session_id, message_content, workspace_root)
+        .execute_from_message_with_origin(
+            task_id,
+            session_id,
+            message_content,
+            workspace_root,
+            None,
+            origin,
+            dry_run.unwrap_or(false),
+        )
         .await
         .map_err(|e| e.to_string())
 }
@@ -285,6 +307,9 @@ pub async fn execute_action_command(
         execution_details: None,
         strategy: None,
         journal_log: None,
+        origin_message_timestamp: None,
+        origin_author: None,
+        dry_run: false,
     };
 
     // Save initial task and send event
@@ -608,6 +633,9 @@ pub async fn execute_pipeline_command(
         execution_details: None,
         strategy: None,
         journal_log: None,
+        origin_message_timestamp: None,
+        origin_author: None,
+        dry_run: false,
     };
 
     // Save and emit task created event
@@ -817,14 +845,156 @@ fn extract_recent_messages(thread_content: &str, count: usize) -> String {
 }
 
 /// Executes a shell command and returns the output
+///
+/// When `requested_by` is set (agent-triggered execution), the command is checked
+/// against the shell policy. Gated commands are queued as pending actions and a
+/// notice is returned instead of the command output. User-typed commands bypass
+/// the gate.
 #[tauri::command]
 pub async fn execute_shell_command(
     command: String,
     working_dir: Option<String>,
+    command_name: Option<String>,
+    requested_by: Option<String>,
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<String, String> {
+) -> Result<SlashCommandResult, String> {
     // Replace {prev_output} with empty string when not in pipeline context
     let command = command.replace("{prev_output}", "");
+    let label = command_name.clone().unwrap_or_else(|| command.clone());
+
+    if let Some(requested_by) = requested_by {
+        let slash_command = match &command_name {
+            Some(name) => state
+                .slash_command_repository
+                .get_command(name)
+                .await
+                .map_err(|e| e.to_string())?,
+            None => None,
+        };
+        let detector = DestructiveCommandDetector::from_policy(
+            &state.config_service.get_config().shell_policy,
+        );
+
+        if detector.requires_confirmation(CommandSource::Agent, slash_command.as_ref(), &command) {
+            let action = queue_pending_action(
+                PendingActionKind::Shell,
+                requested_by,
+                command,
+                command_name,
+                None,
+                working_dir,
+                &app,
+                &state,
+            )
+            .await?;
+            return Ok(SlashCommandResult::success(
+                label,
+                pending_notice(&action),
+                SlashCommandContentType::Notice,
+            ));
+        }
+    }
+
+    let mut result = run_shell_command(command, working_dir, &state).await?;
+    result.command = label;
+    Ok(result)
+}
+
+/// Queues a non-shell slash command issued by an agent if it requires confirmation.
+///
+/// Returns the pending action when the command was gated; the UI re-dispatches
+/// the command once the user approves it. Shell commands are gated by
+/// [`execute_shell_command`] instead.
+#[tauri::command]
+pub async fn gate_agent_slash_command(
+    command_name: String,
+    args: Option<String>,
+    requested_by: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<PendingAction>, String> {
+    let command = state
+        .slash_command_repository
+        .get_command(&command_name)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Command not found: {}", command_name))?;
+    let rendered_command = match args.as_deref().filter(|a| !a.is_empty()) {
+        Some(args) => format!("/{} {}", command_name, args),
+        None => format!("/{}", command_name),
+    };
+
+    let detector =
+        DestructiveCommandDetector::from_policy(&state.config_service.get_config().shell_policy);
+    if !detector.requires_confirmation(CommandSource::Agent, Some(&command), &rendered_command) {
+        return Ok(None);
+    }
+
+    let action = queue_pending_action(
+        PendingActionKind::SlashCommand,
+        requested_by,
+        rendered_command,
+        Some(command_name),
+        args,
+        None,
+        &app,
+        &state,
+    )
+    .await?;
+    Ok(Some(action))
+}
+
+/// Returns the notice shown in place of a gated command's output.
+fn pending_notice(action: &PendingAction) -> String {
+    format!(
+        "⏸ `{}` requires user confirmation and was not executed yet (pending action {}).",
+        action.rendered_command, action.id
+    )
+}
+
+/// Queues a pending action for the active session and announces it to the UI.
+#[allow(clippy::too_many_arguments)]
+async fn queue_pending_action(
+    kind: PendingActionKind,
+    requested_by: String,
+    rendered_command: String,
+    command_name: Option<String>,
+    args: Option<String>,
+    working_dir: Option<String>,
+    app: &AppHandle,
+    state: &AppState,
+) -> Result<PendingAction, String> {
+    let session_id = state
+        .session_usecase
+        .active_session_id()
+        .await
+        .ok_or_else(|| "No active session".to_string())?;
+    let action = state
+        .pending_action_service
+        .enqueue(PendingActionRequest {
+            kind,
+            session_id,
+            requested_by,
+            rendered_command,
+            command_name,
+            args,
+            working_dir,
+        })
+        .await;
+    emit_pending_action_created(app, action.clone());
+    Ok(action)
+}
+
+/// Runs a shell command in the given directory (or the active session's workspace).
+///
+/// A non-zero exit is reported through `success`; `Err` means the command
+/// could not be started at all.
+pub(crate) async fn run_shell_command(
+    command: String,
+    working_dir: Option<String>,
+    state: &AppState,
+) -> Result<SlashCommandResult, String> {
     tracing::info!("execute_shell_command: Command: {}", command);
     tracing::info!(
         "execute_shell_command: Working dir provided: {:?}",
@@ -902,12 +1072,10 @@ pub async fn execute_shell_command(
         .output()
         .map_err(|e| format!("Failed to execute command: {}", e))?;
 
-    if output.status.success() {
-        String::from_utf8(output.stdout).map_err(|e| format!("Failed to parse output: {}", e))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Command failed: {}", stderr))
-    }
+    Ok(
+        SlashCommandResult::from_process_output(command, &output)
+            .with_working_dir(Some(actual_dir)),
+    )
 }
 
 /// Toggles the favorite status of a slash command
@@ -1,5 +1,11 @@
+use orcs_application::TaskOriginMessage;
+use orcs_core::repository::SessionRepository;
+use orcs_core::session::PLACEHOLDER_WORKSPACE_ID;
+use orcs_core::task::TaskOrigin;
 use orcs_infrastructure::storage_repository::StorageRepository;
+use orcs_interaction::InteractionManager;
 use tauri::State;
+use uuid::Uuid;
 
 use crate::app::AppState;
 
@@ -25,6 +31,21 @@ pub async fn list_tasks(state: State<'_, AppState>) -> Result<Vec<orcs_core::tas
         .map_err(|e| e.to_string())
 }
 
+/// Renders a Markdown report of a task execution
+#[tauri::command]
+pub async fn get_task_report(
+    task_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let task = state
+        .task_repository
+        .find_by_id(&task_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Task not found: {}", task_id))?;
+    Ok(orcs_core::task::format_task_report(&task))
+}
+
 /// Deletes a task by ID
 #[tauri::command]
 pub async fn delete_task(task_id: String, state: State<'_, AppState>) -> Result<(), String> {
@@ -35,6 +56,107 @@ pub async fn delete_task(task_id: String, state: State<'_, AppState>) -> Result<
         .map_err(|e| e.to_string())
 }
 
+/// Lists the tasks spawned from a message of a session, oldest first
+#[tauri::command]
+pub async fn get_tasks_for_message(
+    session_id: String,
+    message_timestamp: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<orcs_core::task::Task>, String> {
+    state
+        .task_link_service
+        .tasks_for_message(&session_id, &message_timestamp)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Gets the conversation message a task was spawned from, if any
+#[tauri::command]
+pub async fn get_origin_for_task(
+    task_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<TaskOriginMessage>, String> {
+    state
+        .task_link_service
+        .origin_for_task(&task_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-executes a dry-run task for real with the strategy it planned
+#[tauri::command]
+pub async fn promote_dry_run(
+    task_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let task = state
+        .task_repository
+        .find_by_id(&task_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Task not found: {}", task_id))?;
+
+    // Run in the workspace of the session the dry run belongs to
+    let workspace_id = state
+        .session_repository
+        .find_by_id(&task.session_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|session| session.workspace_id)
+        .filter(|workspace_id| workspace_id != PLACEHOLDER_WORKSPACE_ID);
+    let workspace_root = match workspace_id {
+        Some(workspace_id) => match state
+            .workspace_storage_service
+            .get_workspace(&workspace_id)
+            .await
+        {
+            Ok(workspace) => workspace.map(|workspace| workspace.root_path),
+            Err(e) => {
+                tracing::warn!("Failed to get workspace: {}, using None", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    state
+        .task_executor
+        .promote_dry_run(&task_id, workspace_root)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Picks the ID of a task about to be spawned and links it to its originating
+/// message (the latest message when no timestamp is given).
+///
+/// The link is saved with the active session right away; failing to link only
+/// logs a warning, as the task can run without it.
+pub(crate) async fn link_new_task(
+    manager: &InteractionManager,
+    origin_message_timestamp: Option<&str>,
+    state: &AppState,
+) -> (String, Option<TaskOrigin>) {
+    let task_id = Uuid::new_v4().to_string();
+    let origin = manager
+        .link_spawned_task(origin_message_timestamp, &task_id)
+        .await;
+
+    match &origin {
+        Some(_) => {
+            let app_mode = state.app_mode.lock().await.clone();
+            if let Err(e) = state.session_usecase.save_active_session(app_mode).await {
+                tracing::warn!("Failed to save task link for {}: {}", task_id, e);
+            }
+        }
+        None => tracing::warn!(
+            "Originating message not found for task {} (timestamp: {:?})",
+            task_id,
+            origin_message_timestamp
+        ),
+    }
+    (task_id, origin)
+}
+
 /// Gets the tasks directory path
 #[tauri::command]
 pub async fn get_tasks_directory(state: State<'_, AppState>) -> Result<String, String> {
@@ -1,11 +1,15 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use llm_toolkit::agent::Agent;
 use llm_toolkit::agent::impls::claude_code::ClaudeCodeAgent;
+use orcs_application::{ActivityHistogram, DigestPeriod, WorkspaceDigest};
 use orcs_core::agent::build_enhanced_path;
+use orcs_core::repository::SessionRepository;
 use orcs_core::session::PLACEHOLDER_WORKSPACE_ID;
 use orcs_core::state::repository::StateRepository;
 use orcs_core::workspace::{UploadedFile, Workspace, manager::WorkspaceStorageService};
+use orcs_infrastructure::EffectiveIgnoreRules;
 use tauri::{AppHandle, Emitter, State};
 
 use crate::app::AppState;
@@ -78,11 +82,25 @@ pub async fn create_workspace(
     state: State<'_, AppState>,
 ) -> Result<Workspace, String> {
     let path = PathBuf::from(root_path);
-    state
+    let workspace = state
         .workspace_storage_service
         .get_or_create_workspace(&path)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    // Quick actions are a convenience; failing to seed them must not block the workspace
+    if let Err(e) = state
+        .quick_action_seeder
+        .seed_new_workspace(&workspace)
+        .await
+    {
+        println!(
+            "[Backend] Failed to seed quick actions for workspace {}: {}",
+            workspace.id, e
+        );
+    }
+
+    Ok(workspace)
 }
 
 /// Creates a new workspace and immediately creates a session associated with it.
@@ -286,6 +304,8 @@ pub async fn delete_workspace(
 }
 
 /// Lists all files in a workspace
+///
+/// Files inside the workspace root that its `.orcsignore` excludes are left out.
 #[tauri::command]
 pub async fn list_workspace_files(
     workspace_id: String,
@@ -298,10 +318,33 @@ pub async fn list_workspace_files(
         .map_err(|e| e.to_string())?;
 
     Ok(workspace
-        .map(|w| w.resources.uploaded_files)
+        .map(|w| {
+            let root = w.root_path;
+            w.resources
+                .uploaded_files
+                .into_iter()
+                .filter(|file| !state.workspace_ignore.is_ignored(&root, &file.path))
+                .collect()
+        })
         .unwrap_or_default())
 }
 
+/// Gets the `.orcsignore` rules a workspace applies, for debugging
+#[tauri::command]
+pub async fn get_effective_ignore_rules(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<EffectiveIgnoreRules, String> {
+    let workspace = state
+        .workspace_storage_service
+        .get_workspace(&workspace_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Workspace not found")?;
+
+    Ok(state.workspace_ignore.effective_rules(&workspace.root_path))
+}
+
 /// Uploads a file to a workspace
 #[tauri::command]
 pub async fn upload_file_to_workspace(
@@ -688,3 +731,60 @@ pub async fn copy_file_to_workspace(
 
     Ok(result)
 }
+
+/// Returns per-day and per-hour activity counts of a workspace for the last `days` days.
+///
+/// `tz_offset_minutes` is the local offset from UTC (`-new Date().getTimezoneOffset()`).
+#[tauri::command]
+pub async fn get_workspace_activity(
+    workspace_id: String,
+    days: u32,
+    tz_offset_minutes: i32,
+    state: State<'_, AppState>,
+) -> Result<ActivityHistogram, String> {
+    state
+        .activity_service
+        .get_workspace_activity(&workspace_id, days, tz_offset_minutes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Generates the digest of a workspace for the last full day or week and saves
+/// it as Markdown in workspace storage.
+///
+/// Emits `workspace-digest:progress` after each session. Cancelled with
+/// `cancel_current_operation`; calling again resumes the cancelled run.
+#[tauri::command]
+pub async fn generate_workspace_digest(
+    workspace_id: String,
+    period: DigestPeriod,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceDigest, String> {
+    state
+        .workspace_digest_service
+        .generate_workspace_digest(
+            &workspace_id,
+            period,
+            Some(state.cancel_flag.clone()),
+            |progress| {
+                if let Err(e) = app.emit("workspace-digest:progress", progress) {
+                    tracing::error!("Failed to emit workspace-digest:progress: {}", e);
+                }
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the number of sessions in each workspace, keyed by workspace ID
+#[tauri::command]
+pub async fn get_workspace_session_counts(
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, usize>, String> {
+    state
+        .session_repository
+        .count_by_workspace()
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -70,6 +70,13 @@ fn main() {
         let session_usecase_for_setup = bootstrap.app_state.session_usecase.clone();
         let app_state_service_for_setup = bootstrap.app_state.app_state_service.clone();
         let user_service_for_setup = bootstrap.app_state.user_service.clone();
+        let pending_action_service_for_setup = bootstrap.app_state.pending_action_service.clone();
+        let session_idle_timeout_secs = bootstrap
+            .app_state
+            .config_service
+            .get_config()
+            .env_settings
+            .session_idle_timeout_secs;
 
         // Flag to track if state has been saved during shutdown
         let state_saved = Arc::new(AtomicBool::new(false));
@@ -103,6 +110,30 @@ fn main() {
                     println!("[EventListener] Orchestrator event listener stopped");
                 });
 
+                // Periodically expire pending actions that were never approved or denied
+                let handle_for_pending = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(15));
+                    loop {
+                        interval.tick().await;
+                        for action in pending_action_service_for_setup.expire_stale().await {
+                            commands::pending_actions::emit_pending_action_resolved(
+                                &handle_for_pending,
+                                &action,
+                                orcs_core::pending_action::PendingActionStatus::Expired,
+                            );
+                        }
+                    }
+                });
+
+                // Save and unload sessions left unused longer than the configured timeout
+                let session_usecase_for_reaper = session_usecase_for_setup.clone();
+                tauri::async_runtime::spawn(async move {
+                    session_usecase_for_reaper.start_idle_session_reaper(
+                        tokio::time::Duration::from_secs(session_idle_timeout_secs),
+                    );
+                });
+
                 // Set up memory sync service (Kaiba) and error callback
                 let handle_for_memory_sync = app.handle().clone();
                 let session_usecase_for_memory = session_usecase_for_setup.clone();
@@ -3,25 +3,20 @@
 use std::process::Command;
 
 use orcs_core::session::PLACEHOLDER_WORKSPACE_ID;
-use orcs_core::slash_command::{CommandType, SlashCommand};
+use orcs_core::slash_command::{
+    CommandArgs, CommandType, SlashCommand, SlashCommandContentType, SlashCommandResult,
+    expand_named_placeholders, named_placeholders,
+};
 use orcs_core::workspace::manager::WorkspaceStorageService;
-use serde::Serialize;
 use tauri::State;
 
 use crate::app::AppState;
 
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ExpandedSlashCommand {
-    pub content: String,
-    pub working_dir: Option<String>,
-}
-
 pub async fn expand_slash_command(
     command: &SlashCommand,
     args: &str,
     state: &State<'_, AppState>,
-) -> Result<ExpandedSlashCommand, String> {
+) -> Result<SlashCommandResult, String> {
     tracing::info!("expand_slash_command: Command name: {}", command.name);
     tracing::info!(
         "expand_slash_command: Command type: {:?}",
@@ -35,6 +30,14 @@ pub async fn expand_slash_command(
     let trimmed_args = args.trim();
 
     let mut content = match command.command_type {
+        CommandType::Prompt if !named_placeholders(&command.content).is_empty() => {
+            // Named {{placeholders}} take their values from --key value args;
+            // whatever is left over fills {args}
+            let parsed = CommandArgs::parse(trimmed_args);
+            expand_named_placeholders(&command.content, &parsed)
+                .map_err(|e| format!("/{}: {}", command.name, e))?
+                .replace("{args}", &parsed.positional)
+        }
         CommandType::Prompt => {
             if command.content.contains("{args}") {
                 command.content.replace("{args}", trimmed_args)
@@ -156,10 +159,14 @@ pub async fn expand_slash_command(
 
     tracing::info!("expand_slash_command: Final working_dir: {:?}", working_dir);
 
-    Ok(ExpandedSlashCommand {
-        content,
-        working_dir,
-    })
+    let content_type = match command.command_type {
+        CommandType::Shell => SlashCommandContentType::ShellCommand,
+        _ => SlashCommandContentType::Prompt,
+    };
+    Ok(
+        SlashCommandResult::success(&command.name, content, content_type)
+            .with_working_dir(working_dir),
+    )
 }
 
 // Helper functions
@@ -23,6 +23,7 @@ import { IconPlus } from '@tabler/icons-react';
 import { useDisclosure } from '@mantine/hooks';
 import "./App.css";
 import { Message, MessageType, StreamingDialogueTurn } from "./types/message";
+import type { PendingAction } from "./types/pending_action";
 import { StatusInfo, getDefaultStatus } from "./types/status";
 import { Task } from "./types/task";
 import { Session } from "./types/session";
@@ -31,7 +32,7 @@ import { GitInfo } from "./types/git";
 import { Navbar } from "./components/navigation/Navbar";
 import { WorkspaceSwitcher } from "./components/workspace/WorkspaceSwitcher";
 import { SettingsMenu } from "./components/settings/SettingsMenu";
-import { parseCommand, extractSlashCommands } from "./utils/commandParser";
+import { parseCommand, extractSlashCommands, isUntrustedMessage } from "./utils/commandParser";
 import { extractMentions, normalizeMentionsInText } from "./utils/mentionParser";
 import { handleAndPersistSystemMessage, conversationMessage } from "./utils/systemMessage";
 import { useSessions } from "./hooks/useSessions";
@@ -605,6 +606,8 @@ function App() {
   const addMessageToTabRef = useRef(addMessageToTab);
   const getTabBySessionIdRef = useRef(getTabBySessionId);
   const personasRef = useRef(personas);
+  // Personas currently generating, per session
+  const thinkingAuthorsRef = useRef<Map<string, string[]>>(new Map());
 
   const currentSessionIdRef = useRef(currentSessionId);
   const handleSlashCommandRef =
@@ -642,6 +645,43 @@ function App() {
 
     console.log('[EFFECT] Setting up dialogue-turn listener');
 
+    const pendingActionNotificationId = (actionId: string) => `pending-action-${actionId}`;
+
+    // Approve or deny an agent-triggered command from its notification
+    const resolvePendingAction = async (action: PendingAction, approve: boolean) => {
+      try {
+        if (!approve) {
+          await invoke<PendingAction>('deny_pending_action', { id: action.id });
+          return;
+        }
+        if (action.kind === 'slashCommand' && action.sessionId !== currentSessionIdRef.current) {
+          notifications.show({
+            title: 'Switch session to approve',
+            message: `Open the session where ${action.requestedBy} requested ${action.renderedCommand} to run it.`,
+            color: 'yellow',
+          });
+          return;
+        }
+        const approved = await invoke<PendingAction>('approve_pending_action', { id: action.id });
+        if (approved.kind === 'slashCommand' && approved.commandName) {
+          const args = approved.args ? ` ${approved.args}` : '';
+          await handleSlashCommandRef.current?.(`/${approved.commandName}${args}`, {
+            source: 'agent',
+            actorName: approved.requestedBy,
+            autoSubmit: true,
+            approved: true,
+          });
+        }
+      } catch (error) {
+        console.error('[STREAM] Failed to resolve pending action:', error);
+        notifications.show({
+          title: 'Pending action failed',
+          message: String(error),
+          color: 'red',
+        });
+      }
+    };
+
     const setupListener = async () => {
       unlisten = await listen<StreamingDialogueTurn>('dialogue-turn', (event) => {
         const turn = event.payload;
@@ -678,6 +718,7 @@ function App() {
               baseColor: persona?.base_color,
               backend: persona?.backend,
               modelName: persona?.model_name,
+              turnStats: turn.turn_stats,
             };
 
             addMessageToTabRef.current(targetTab.id, newMessage);
@@ -688,7 +729,11 @@ function App() {
               turn.sessionId === currentSessionIdRef.current &&
               handleSlashCommandRef.current
             ) {
-              const detectedCommands = extractSlashCommands(turn.content);
+              // Commands parroted from shell output or web content are not run
+              const untrustedSources = targetTab.messages
+                .filter(isUntrustedMessage)
+                .map((message) => message.text);
+              const detectedCommands = extractSlashCommands(turn.content, untrustedSources);
               console.log("detectedCommands", detectedCommands);
               if (detectedCommands.length > 0) {
                 const actorName = turn.author || 'Agent';
@@ -713,6 +758,28 @@ function App() {
             break;
           }
 
+          case 'Thinking': {
+            const authors = (thinkingAuthorsRef.current.get(turn.sessionId) ?? []).filter(
+              (author) => author !== turn.author
+            );
+            if (turn.active) {
+              authors.push(turn.author);
+            }
+            thinkingAuthorsRef.current.set(turn.sessionId, authors);
+            // The overall thinking state is cleared when the input finishes
+            if (authors.length > 0) {
+              setTabThinking(targetTab.id, true, authors.join(', '));
+            }
+            break;
+          }
+
+          case 'Working': {
+            // Heartbeat of a long-running CLI turn; only refreshes the indicator
+            const hint = turn.last_output_line ? ` – ${turn.last_output_line}` : '';
+            setTabThinking(targetTab.id, true, `${turn.author} (${turn.elapsed_secs}s${hint})`);
+            break;
+          }
+
           case 'Error': {
             console.log('[STREAM] Error received:', turn.message);
 
@@ -729,9 +796,11 @@ function App() {
             // Show error toast only for active session
             if (isActiveSession) {
               notifications.show({
-                title: 'Agent Error',
-                message: turn.message,
-                color: 'red',
+                title: turn.retryable ? 'Agent Error (temporary)' : 'Agent Error',
+                message: turn.retryable
+                  ? `${turn.message}\n\nThis looks temporary. Try sending again in a moment.`
+                  : turn.message,
+                color: turn.retryable ? 'orange' : 'red',
                 icon: '❌',
                 autoClose: 10000,
               });
@@ -745,7 +814,7 @@ function App() {
             break;
 
           case 'AutoChatProgress':
-            console.log('[STREAM] AutoChat progress:', turn.currentIteration, '/', turn.maxIterations);
+            console.log('[STREAM] AutoChat progress:', turn.currentIteration, '/', turn.maxIterations, 'next in', turn.delayMs, 'ms');
             // Update TabContext AutoChat iteration state
             // TODO: Implement setTabAutoChatIteration call here
             break;
@@ -770,6 +839,36 @@ function App() {
             addMessageToTabRef.current(targetTab.id, completionMessage);
             break;
 
+          case 'PendingAction': {
+            const action = turn.action;
+            notifications.show({
+              id: pendingActionNotificationId(action.id),
+              title: `${action.requestedBy} wants to run a command`,
+              color: 'orange',
+              autoClose: false,
+              withCloseButton: false,
+              message: (
+                <Stack gap="xs">
+                  <Text size="sm" ff="monospace">{action.renderedCommand}</Text>
+                  <Group gap="xs">
+                    <Button size="xs" color="green" onClick={() => void resolvePendingAction(action, true)}>
+                      Approve
+                    </Button>
+                    <Button size="xs" variant="default" onClick={() => void resolvePendingAction(action, false)}>
+                      Deny
+                    </Button>
+                  </Group>
+                </Stack>
+              ),
+            });
+            break;
+          }
+
+          case 'PendingActionResolved':
+            console.log('[STREAM] Pending action resolved:', turn.action_id, turn.status);
+            notifications.hide(pendingActionNotificationId(turn.action_id));
+            break;
+
           default:
             console.warn('[STREAM] Unknown turn type:', (turn as any).type);
         }
@@ -1436,9 +1535,20 @@ function App() {
           attachments: filePaths.length > 0 ? filePaths : undefined,
         };
 
-        const result = await invoke<InteractionResult>('publish_session_event', {
-          event: sessionEvent,
-        });
+        let result: InteractionResult;
+        try {
+          result = await invoke<InteractionResult>('publish_session_event', {
+            event: sessionEvent,
+          });
+        } catch (error) {
+          // Attachments excluded by .orcsignore need the user's confirmation
+          if (!String(error).includes('.orcsignore') || !window.confirm(String(error))) {
+            throw error;
+          }
+          result = await invoke<InteractionResult>('publish_session_event', {
+            event: { ...sessionEvent, confirm_ignored: true },
+          });
+        }
 
         if (result.type === 'NewDialogueMessages') {
           console.log('[BATCH] Received', result.data.length, 'messages (already streamed)');
@@ -1842,9 +1952,9 @@ function App() {
     try {
       addMessage('system', 'SYSTEM', `🚀 Executing task: "${message.text.slice(0, 50)}..."`);
 
-      // TODO: Backend command implementation
       const result = await invoke<string>('execute_message_as_task', {
         messageContent: message.text,
+        originMessageTimestamp: message.timestamp.toISOString(),
       });
 
       addMessage('system', 'SYSTEM', `✅ Task completed: ${result}`);
@@ -10,19 +10,33 @@ export type ConversationModeType = 'detailed' | 'normal' | 'concise' | 'brief' |
 
 export type PresetSourceType = 'system' | 'user';
 
-export type AppState = { lastSelectedWorkspaceId: string | null; defaultWorkspaceId: string | null; activeSessionId: string | null; openTabs: { id: string; sessionId: string; workspaceId: string; lastAccessedAt: number; order: number; input: string | null; attachedFilePaths: string[] | null; autoMode: boolean | null; autoChatIteration: number | null; isDirty: boolean | null; }[]; activeTabId: string | null; };
+export type AppState = { lastSelectedWorkspaceId: string | null; defaultWorkspaceId: string | null; activeSessionId: string | null; openTabs: { id: string; sessionId: string; workspaceId: string; lastAccessedAt: number; order: number; input: string | null; attachedFilePaths: string[] | null; autoMode: boolean | null; autoChatIteration: number | null; isDirty: boolean | null; }[]; activeTabId: string | null; offlineMode: boolean; };
 
 export type OpenTab = { id: string; sessionId: string; workspaceId: string; lastAccessedAt: number; order: number; input: string | null; attachedFilePaths: string[] | null; autoMode: boolean | null; autoChatIteration: number | null; isDirty: boolean | null; };
 
 export type MessageRole = 'User' | 'Assistant' | 'System';
 
-export type SystemEventType = 'participant_joined' | 'participant_left' | 'execution_strategy_changed' | 'mode_changed' | 'workspace_switched' | 'notification';
+export type SystemEventType = 'participant_joined' | 'participant_left' | 'execution_strategy_changed' | 'mode_changed' | 'workspace_switched' | 'notification' | 'style_calibration_changed' | 'pinboard_updated' | 'focus_changed';
 
 export type ErrorSeverity = 'critical' | 'warning' | 'info';
 
-export type MessageMetadata = { systemEventType: 'participant_joined' | 'participant_left' | 'execution_strategy_changed' | 'mode_changed' | 'workspace_switched' | 'notification' | null; errorSeverity: 'critical' | 'warning' | 'info' | null; systemMessageType: string | null; includeInDialogue: boolean; llmDebugInfo: { prompt: string; rawResponse: string; model: string | null; } | null; };
+export type SourceKind = 'shell_output' | 'search_result' | 'web_clip' | 'attachment' | 'context_info';
 
-export type ConversationMessage = { role: 'User' | 'Assistant' | 'System'; content: string; timestamp: string; metadata: { systemEventType: 'participant_joined' | 'participant_left' | 'execution_strategy_changed' | 'mode_changed' | 'workspace_switched' | 'notification' | null; errorSeverity: 'critical' | 'warning' | 'info' | null; systemMessageType: string | null; includeInDialogue: boolean; llmDebugInfo: { prompt: string; rawResponse: string; model: string | null; } | null; }; attachments: string[]; };
+export type SourceRef = { sourceId: string; kind: 'shell_output' | 'search_result' | 'web_clip' | 'attachment' | 'context_info'; label: string; };
+
+export type TurnStats = { durationMs: number; inputTokens: number | null; outputTokens: number | null; estimatedCostMicrousd: number | null; };
+
+export type MessageMetadata = { systemEventType: 'participant_joined' | 'participant_left' | 'execution_strategy_changed' | 'mode_changed' | 'workspace_switched' | 'notification' | 'style_calibration_changed' | 'pinboard_updated' | 'focus_changed' | null; errorSeverity: 'critical' | 'warning' | 'info' | null; systemMessageType: string | null; includeInDialogue: boolean; llmDebugInfo: { prompt: string; rawResponse: string; model: string | null; } | null; recoveredPartial: boolean; pinned: boolean; splitFromSingleResponse: boolean; spawnedTaskIds: string[]; inResponseTo: string | null; source: { sourceId: string; kind: 'shell_output' | 'search_result' | 'web_clip' | 'attachment' | 'context_info'; label: string; } | null; sources: { sourceId: string; kind: 'shell_output' | 'search_result' | 'web_clip' | 'attachment' | 'context_info'; label: string; }[]; fullContentPath: string | null; turnStats: { durationMs: number; inputTokens: number | null; outputTokens: number | null; estimatedCostMicrousd: number | null; } | null; };
+
+export type ConversationMessage = { role: 'User' | 'Assistant' | 'System'; content: string; timestamp: string; metadata: { systemEventType: 'participant_joined' | 'participant_left' | 'execution_strategy_changed' | 'mode_changed' | 'workspace_switched' | 'notification' | 'style_calibration_changed' | 'pinboard_updated' | 'focus_changed' | null; errorSeverity: 'critical' | 'warning' | 'info' | null; systemMessageType: string | null; includeInDialogue: boolean; llmDebugInfo: { prompt: string; rawResponse: string; model: string | null; } | null; recoveredPartial: boolean; pinned: boolean; splitFromSingleResponse: boolean; spawnedTaskIds: string[]; inResponseTo: string | null; source: { sourceId: string; kind: 'shell_output' | 'search_result' | 'web_clip' | 'attachment' | 'context_info'; label: string; } | null; sources: { sourceId: string; kind: 'shell_output' | 'search_result' | 'web_clip' | 'attachment' | 'context_info'; label: string; }[]; fullContentPath: string | null; turnStats: { durationMs: number; inputTokens: number | null; outputTokens: number | null; estimatedCostMicrousd: number | null; } | null; }; attachments: string[]; };
+
+export type CostTotals = { turns: number; durationMs: number; inputTokens: number; outputTokens: number; estimatedCostMicrousd: number; };
+
+export type PersonaCost = { personaId: string; personaName: string; totals: { turns: number; durationMs: number; inputTokens: number; outputTokens: number; estimatedCostMicrousd: number; }; };
+
+export type DailyCost = { date: string; totals: { turns: number; durationMs: number; inputTokens: number; outputTokens: number; estimatedCostMicrousd: number; }; };
+
+export type CostBreakdown = { total: { turns: number; durationMs: number; inputTokens: number; outputTokens: number; estimatedCostMicrousd: number; }; byPersona: { personaId: string; personaName: string; totals: { turns: number; durationMs: number; inputTokens: number; outputTokens: number; estimatedCostMicrousd: number; }; }[]; byDay: { date: string; totals: { turns: number; durationMs: number; inputTokens: number; outputTokens: number; estimatedCostMicrousd: number; }; }[]; };
 
 export type Plan = { steps: string[]; };
 
@@ -30,7 +44,7 @@ export type AppMode = 'Idle' | 'AwaitingConfirmation';
 
 export type ConversationMode = 'detailed' | 'normal' | 'concise' | 'brief' | 'discussion';
 
-export type AutoChatConfig = { max_iterations: number; stop_condition: 'iteration_count' | 'user_interrupt'; web_search_enabled: boolean; };
+export type AutoChatConfig = { max_iterations: number; stop_condition: 'iteration_count' | 'user_interrupt'; web_search_enabled: boolean; stop_phrases: string[]; iteration_delay_ms: number | null; adaptive_delay: boolean; };
 
 export type StopCondition = 'iteration_count' | 'user_interrupt';
 
@@ -42,7 +56,7 @@ export type SessionType = { id: string; title: string; createdAt: string; update
 
 export type TaskStatus = 'Pending' | 'Running' | 'Completed' | 'Failed';
 
-export type TaskType = { id: string; sessionId: string; title: string; description: string; status: 'Pending' | 'Running' | 'Completed' | 'Failed'; createdAt: string; updatedAt: string; completedAt: string | null; stepsExecuted: number; stepsSkipped: number; contextKeys: number; error: string | null; result: string | null; };
+export type TaskType = { id: string; sessionId: string; title: string; description: string; status: 'Pending' | 'Running' | 'Completed' | 'Failed'; createdAt: string; updatedAt: string; completedAt: string | null; stepsExecuted: number; stepsSkipped: number; contextKeys: number; error: string | null; result: string | null; originMessageTimestamp: string | null; originAuthor: string | null; dryRun: boolean; };
 
 export type Workspace = { id: string; name: string; rootPath: string; workspaceDir: string; resources: { uploadedFiles: { id: string; name: string; path: string; mimeType: string; size: number; uploadedAt: number; sessionId: string | null; messageTimestamp: string | null; author: string | null; isArchived: boolean; isFavorite: boolean; isDefaultAttachment: boolean; sortOrder: number | null; }[]; tempFiles: { id: string; path: string; purpose: string; createdAt: number; autoDelete: boolean; }[]; }; projectContext: { languages: string[]; buildSystem: string | null; description: string | null; repositoryUrl: string | null; metadata: Record<string, string>; }; lastAccessed: number; isFavorite: boolean; lastActiveSessionId: string | null; kaibaReiId: string | null; };
 
@@ -1,5 +1,5 @@
 import React, { useState, useEffect } from 'react';
-import { Modal, NumberInput, Select, Switch, Button, Stack, Text, Divider, Group } from '@mantine/core';
+import { Modal, NumberInput, Select, Switch, TagsInput, Button, Stack, Text, Divider, Group } from '@mantine/core';
 import type { AutoChatConfig, StopCondition } from '../../types/session';
 
 interface AutoChatSettingsModalProps {
@@ -18,6 +18,7 @@ const DEFAULT_CONFIG: AutoChatConfig = {
   max_iterations: 5,
   stop_condition: 'iteration_count',
   web_search_enabled: true,
+  stop_phrases: [],
 };
 
 export const AutoChatSettingsModal: React.FC<AutoChatSettingsModalProps> = ({
@@ -92,6 +93,14 @@ export const AutoChatSettingsModal: React.FC<AutoChatSettingsModalProps> = ({
           }
         />
 
+        <TagsInput
+          label="Stop Phrases"
+          description="Stop when any agent says one of these (case-insensitive), e.g. FINAL ANSWER"
+          placeholder="Type a phrase and press Enter"
+          value={formData.stop_phrases ?? []}
+          onChange={(value) => setFormData({ ...formData, stop_phrases: value })}
+        />
+
         <Divider label="Inherited Settings" />
 
         <Text size="sm" c="dimmed">
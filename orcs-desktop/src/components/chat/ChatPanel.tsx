@@ -39,7 +39,7 @@ import type { Agent, PersonaConfig } from '../../types/agent';
 import { getCurrentMention } from '../../utils/mentionParser';
 import type { AutoChatConfig, ContextMode } from '../../types/session';
 import type { Message } from '../../types/message';
-import type { SlashCommand } from '../../types/slash_command';
+import type { SlashCommand, SlashCommandResult } from '../../types/slash_command';
 import { notifications } from '@mantine/notifications';
 
 interface ChatPanelProps {
@@ -449,7 +449,7 @@ export const ChatPanel = memo(function ChatPanel({
       }
 
       // Expand the command template
-      const expanded = await invoke<{ content: string; workingDir?: string }>('expand_command_template', {
+      const expanded = await invoke<SlashCommandResult>('expand_command_template', {
         commandName,
         args: null,
       });
@@ -457,14 +457,18 @@ export const ChatPanel = memo(function ChatPanel({
       // Handle based on command type
       if (command.type === 'prompt') {
         // For prompt commands, send the expanded content as user input
-        handleInputChange(expanded.content);
+        handleInputChange(expanded.output);
       } else if (command.type === 'shell') {
         // For shell commands, execute and show output
         setTabThinking(tab.id, true, `Running /${commandName}`, true);
-        const output = await invoke<string>('execute_shell_command', {
-          command: expanded.content,
+        const result = await invoke<SlashCommandResult>('execute_shell_command', {
+          command: expanded.output,
           workingDir: expanded.workingDir,
         });
+        if (!result.success) {
+          throw new Error(`Command failed: ${result.output}`);
+        }
+        const output = result.output;
 
         // Add output as system message
         const outputMessage: Message = {
@@ -573,6 +577,7 @@ export const ChatPanel = memo(function ChatPanel({
             max_iterations: 5,
             stop_condition: 'iteration_count',
             web_search_enabled: true,
+            stop_phrases: [],
           };
           setAutoChatConfig(defaultConfig);
 
@@ -592,6 +597,7 @@ export const ChatPanel = memo(function ChatPanel({
           max_iterations: 5,
           stop_condition: 'iteration_count',
           web_search_enabled: true,
+          stop_phrases: [],
         };
         setAutoChatConfig(defaultConfig);
       }
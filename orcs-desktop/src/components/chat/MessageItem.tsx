@@ -2,7 +2,7 @@ import React, { useState } from 'react';
 import { Paper, Text, Group, Badge, Avatar, Box, ActionIcon, CopyButton, Tooltip, Anchor, Image, Stack, Collapse, Code } from '@mantine/core';
 import { IconDeviceFloppy, IconRocket, IconCommand, IconUser, IconCheck, IconClipboard, IconChevronDown, IconChevronUp, IconBug, IconRefresh, IconSquareCheck, IconSquare } from '@tabler/icons-react';
 import { invoke } from '@tauri-apps/api/core';
-import { Message, getMessageStyle } from '../../types/message';
+import { Message, formatTurnStats, getMessageStyle } from '../../types/message';
 import { MarkdownRenderer } from '../markdown/MarkdownRenderer';
 import { useDebugStore } from '../../stores/debugStore';
 
@@ -503,6 +503,11 @@ export function MessageItem({ message, onSaveToWorkspace, onExecuteAsTask, onCre
                   {formatModelName(message.modelName)}
                 </Badge>
               )}
+              {message.turnStats && (
+                <Badge size="xs" color="gray" variant="light">
+                  {formatTurnStats(message.turnStats)}
+                </Badge>
+              )}
             </Group>
 
             {/* アクションボタン */}
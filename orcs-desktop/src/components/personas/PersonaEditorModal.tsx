@@ -17,6 +17,12 @@ const CLAUDE_MODEL_OPTIONS = [
   { value: 'claude-opus-4-20250514', label: 'Claude Opus 4.0 (2025-05-14)' },
 ];
 
+const CONTEXT_MODE_OPTIONS = [
+  { value: '', label: 'Follow session' },
+  { value: 'rich', label: 'Rich (full context)' },
+  { value: 'clean', label: 'Clean (expertise only)' },
+];
+
 const GEMINI_MODEL_OPTIONS = [
   { value: 'gemini-3.1-pro-preview', label: 'Gemini 3.1 Pro Preview 🧠' },
   { value: 'gemini-3-pro-preview', label: 'Gemini 3 Pro Preview' },
@@ -70,6 +76,7 @@ export const PersonaEditorModal: React.FC<PersonaEditorModalProps> = ({
     base_color: undefined,
     gemini_options: undefined,
     kaiba_options: undefined,
+    context_mode_override: undefined,
   });
 
   // Fetch backend options on mount
@@ -103,6 +110,7 @@ export const PersonaEditorModal: React.FC<PersonaEditorModalProps> = ({
         base_color: persona.base_color,
         gemini_options: persona.gemini_options,
         kaiba_options: persona.kaiba_options,
+        context_mode_override: persona.context_mode_override,
       });
     } else {
       setFormData({
@@ -118,6 +126,7 @@ export const PersonaEditorModal: React.FC<PersonaEditorModalProps> = ({
         base_color: undefined,
         gemini_options: undefined,
         kaiba_options: undefined,
+        context_mode_override: undefined,
       });
     }
   }, [persona]);
@@ -150,6 +159,7 @@ export const PersonaEditorModal: React.FC<PersonaEditorModalProps> = ({
         base_color: formData.base_color || undefined,
         gemini_options: formData.gemini_options || undefined,
         kaiba_options: formData.kaiba_options || undefined,
+        context_mode_override: formData.context_mode_override || undefined,
       };
 
       onSave(updatedPersona);
@@ -170,6 +180,7 @@ export const PersonaEditorModal: React.FC<PersonaEditorModalProps> = ({
         base_color: formData.base_color || undefined,
         gemini_options: formData.gemini_options || undefined,
         kaiba_options: formData.kaiba_options || undefined,
+        context_mode_override: formData.context_mode_override || undefined,
       };
 
       // Call unified create_persona command
@@ -355,6 +366,17 @@ export const PersonaEditorModal: React.FC<PersonaEditorModalProps> = ({
           autosize
         />
 
+        <Select
+          label="Context Mode"
+          description="Clean runs this persona with expertise only: no restored history or talk style, even when the session is Rich"
+          data={CONTEXT_MODE_OPTIONS}
+          value={formData.context_mode_override || ''}
+          onChange={(value) => setFormData({
+            ...formData,
+            context_mode_override: (value || undefined) as PersonaConfig['context_mode_override'],
+          })}
+        />
+
         <Switch
           label="Default Participant"
           description="Include this persona in discussions by default"
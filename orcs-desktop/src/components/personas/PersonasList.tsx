@@ -1,6 +1,6 @@
 import { useState, useEffect } from 'react';
 import { Stack, ScrollArea, Group, Text, Box, Checkbox, ActionIcon, Tooltip, Badge, Modal, Button } from '@mantine/core';
-import { IconPlus, IconPencil, IconTrash, IconDeviceFloppy } from '@tabler/icons-react';
+import { IconPlus, IconPencil, IconTrash, IconDeviceFloppy, IconRefresh } from '@tabler/icons-react';
 import { invoke } from '@tauri-apps/api/core';
 import { PersonaConfig } from '../../types/agent';
 import { PersonaEditorModal } from './PersonaEditorModal';
@@ -36,13 +36,14 @@ export function PersonasList({
   onRefreshSessions,
 }: PersonasListProps) {
   // Use personaStore for save operations
-  const { addPersona, updatePersona, deletePersona, saveAdhocPersona } = usePersonaStore();
+  const { addPersona, updatePersona, deletePersona, saveAdhocPersona, refreshRemotePersonas } = usePersonaStore();
 
   // Use props if provided, otherwise maintain local state for backwards compatibility
   const [personaConfigs, setPersonaConfigs] = useState<PersonaConfig[]>([]);
   const [activeParticipantIds, setActiveParticipantIds] = useState<string[]>([]);
 
   const personas = propsPersonas ?? personaConfigs;
+  const hasRemotePersonas = personas.some(p => p.source === 'Remote');
   const activeIds = propsActiveParticipantIds ?? activeParticipantIds;
   const [isModalOpen, setIsModalOpen] = useState(false);
   const [editingPersona, setEditingPersona] = useState<Partial<PersonaConfig> | null>(null);
@@ -228,6 +229,13 @@ export function PersonasList({
                 🔶 Adhoc
               </Badge>
             )}
+            {persona.source === 'Remote' && (
+              <Tooltip label="Shared team persona (read-only)" withArrow>
+                <Badge size="xs" color="cyan" variant="light">
+                  🌐 Remote
+                </Badge>
+              </Tooltip>
+            )}
             <Badge size="xs" color={persona.backend === 'gemini_cli' ? 'violet' : 'gray'}>
               {BACKEND_LABELS[persona.backend] || 'Claude CLI'}
             </Badge>
@@ -287,26 +295,30 @@ export function PersonasList({
               </ActionIcon>
             </Tooltip>
           )}
-          <Tooltip label="Edit" withArrow>
-            <ActionIcon
-              variant="subtle"
-              color="blue"
-              size="sm"
-              onClick={() => handleOpenModal(persona)}
-            >
-              <IconPencil size={14} />
-            </ActionIcon>
-          </Tooltip>
-          <Tooltip label="Delete" withArrow>
-            <ActionIcon
-              variant="subtle"
-              color="red"
-              size="sm"
-              onClick={() => handleRequestDelete(persona.id)}
-            >
-              <IconTrash size={14} />
-            </ActionIcon>
-          </Tooltip>
+          {persona.source !== 'Remote' && (
+            <>
+              <Tooltip label="Edit" withArrow>
+                <ActionIcon
+                  variant="subtle"
+                  color="blue"
+                  size="sm"
+                  onClick={() => handleOpenModal(persona)}
+                >
+                  <IconPencil size={14} />
+                </ActionIcon>
+              </Tooltip>
+              <Tooltip label="Delete" withArrow>
+                <ActionIcon
+                  variant="subtle"
+                  color="red"
+                  size="sm"
+                  onClick={() => handleRequestDelete(persona.id)}
+                >
+                  <IconTrash size={14} />
+                </ActionIcon>
+              </Tooltip>
+            </>
+          )}
         </Group>
       </Group>
     );
@@ -326,6 +338,34 @@ export function PersonasList({
                 <IconPlus size={16} />
               </ActionIcon>
             </Tooltip>
+            {hasRemotePersonas && (
+              <Tooltip label="Refresh remote personas" withArrow>
+                <ActionIcon
+                  variant="subtle"
+                  color="cyan"
+                  onClick={async () => {
+                    try {
+                      const outcome = await refreshRemotePersonas();
+                      if (onRefresh) await onRefresh();
+                      const message = outcome.status === 'offline'
+                        ? `🌐 Remote personas offline, using snapshot (${outcome.personaCount})`
+                        : `🌐 Remote personas refreshed (${outcome.personaCount})`;
+                      onMessage && await handleAndPersistSystemMessage(
+                        conversationMessage(message, outcome.status === 'offline' ? 'warning' : 'info'),
+                        onMessage, invoke
+                      );
+                    } catch (error) {
+                      onMessage && await handleAndPersistSystemMessage(
+                        conversationMessage(`❌ Failed to refresh remote personas: ${error}`, 'error'),
+                        onMessage, invoke
+                      );
+                    }
+                  }}
+                >
+                  <IconRefresh size={16} />
+                </ActionIcon>
+              </Tooltip>
+            )}
           </Group>
           <Text size="sm" c="dimmed">
             {activeIds.length} participating
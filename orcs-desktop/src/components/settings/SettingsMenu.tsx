@@ -26,6 +26,7 @@ interface SettingsMenuProps {
 interface UserProfile {
   nickname: string;
   background: string;
+  timezone?: string;
 }
 
 /**
@@ -17,8 +17,10 @@ import type { MessageType } from '../types/message';
 import type { StatusInfo } from '../types/status';
 import type { Workspace } from '../types/workspace';
 import type { UploadedFile } from '../types/workspace';
-import type { SlashCommand, ExpandedSlashCommand, ActionCommandResult } from '../types/slash_command';
+import type { SlashCommand, SlashCommandResult, ActionCommandResult } from '../types/slash_command';
 import type { SearchResult } from '../types/search';
+import type { DecisionRecord, PollResult } from '../types/session';
+import type { PendingAction } from '../types/pending_action';
 
 export interface SlashCommandResult {
   nextInput: string | null;
@@ -31,6 +33,8 @@ export interface SlashCommandOptions {
   source?: SlashCommandSource;
   actorName?: string;
   autoSubmit?: boolean;
+  /** The user already approved this agent command; skip confirmation gates */
+  approved?: boolean;
 }
 
 interface UseSlashCommandsProps {
@@ -80,7 +84,10 @@ export function useSlashCommands({
       rawInput: string,
       options: SlashCommandOptions = {}
     ): Promise<SlashCommandResult> => {
-      const { source = 'user', actorName, autoSubmit = false } = options;
+      const { source = 'user', actorName, autoSubmit = false, approved = false } = options;
+      // Agent-triggered commands go through the backend confirmation gates
+      const requestedBy =
+        source === 'agent' && !approved ? (actorName ?? 'Agent') : null;
       const parsed = parseCommand(rawInput);
 
       if (!parsed.isCommand || !parsed.command) {
@@ -142,7 +149,7 @@ export function useSlashCommands({
               const agentList = personas.length > 0
                 ? personas.map((p) => {
                     const icon = p.icon || '🤖';
-                    const source = p.source === 'Adhoc' ? '(Adhoc)' : '';
+                    const source = p.source === 'Adhoc' ? '(Adhoc)' : p.source === 'Remote' ? '(Remote)' : '';
                     return `${icon} ${p.name} - ${p.role} ${source}`;
                   }).join('\n')
                 : 'No agents available';
@@ -163,22 +170,33 @@ export function useSlashCommands({
             await saveCurrentSession();
             break;
 
-          case 'task':
-            if (parsed.args && parsed.args.length > 0) {
-              const taskDescription = parsed.args.join(' ');
+          case 'task': {
+            const dryRun = parsed.args?.includes('--dry-run') ?? false;
+            const taskArgs = (parsed.args ?? []).filter((arg) => arg !== '--dry-run');
+            if (taskArgs.length > 0) {
+              const taskDescription = taskArgs.join(' ');
               try {
                 await handleAndPersistSystemMessage(
-                  conversationMessage(`Executing task: ${taskDescription}`, 'info', '🚀'),
+                  conversationMessage(
+                    `${dryRun ? 'Dry-running' : 'Executing'} task: ${taskDescription}`,
+                    'info',
+                    dryRun ? '🔍' : '🚀'
+                  ),
                   addMessage,
                   invoke
                 );
 
                 const taskResult = await invoke<string>('execute_message_as_task', {
                   messageContent: taskDescription,
+                  dryRun,
                 });
 
                 await handleAndPersistSystemMessage(
-                  conversationMessage(`Task completed:\n${taskResult}`, 'info', '✅'),
+                  conversationMessage(
+                    `${dryRun ? 'Dry run' : 'Task'} completed:\n${taskResult}`,
+                    'info',
+                    dryRun ? '🔍' : '✅'
+                  ),
                   addMessage,
                   invoke
                 );
@@ -192,13 +210,103 @@ export function useSlashCommands({
               }
             } else {
               await handleAndPersistSystemMessage(
-                conversationMessage('Usage: /task <description>\nExample: /task Create a README file', 'error'),
+                conversationMessage(
+                  'Usage: /task [--dry-run] <description>\nExample: /task --dry-run Create a README file',
+                  'error'
+                ),
+                addMessage,
+                invoke
+              );
+            }
+            await saveCurrentSession();
+            break;
+          }
+
+          case 'poll': {
+            const [question = '', ...options] = (parsed.args ?? [])
+              .join(' ')
+              .split('|')
+              .map((part) => part.trim());
+            const pollOptions = options.filter((option) => option.length > 0);
+            if (!question || pollOptions.length < 2) {
+              await handleAndPersistSystemMessage(
+                conversationMessage(
+                  'Usage: /poll <question> | <option1> | <option2>\nExample: /poll Which database? | Postgres | SQLite',
+                  'error'
+                ),
+                addMessage,
+                invoke
+              );
+              await saveCurrentSession();
+              break;
+            }
+            try {
+              // Replies and the tally arrive through dialogue-turn events
+              await invoke<PollResult>('run_session_poll', { question, options: pollOptions });
+            } catch (error) {
+              console.error('Failed to run poll:', error);
+              await handleAndPersistSystemMessage(
+                conversationMessage(`Poll failed: ${error}`, 'error', '❌'),
+                addMessage,
+                invoke
+              );
+            }
+            await saveCurrentSession();
+            break;
+          }
+
+          case 'focus': {
+            const names = (parsed.args ?? []).map((arg) => arg.replace(/^@/, '')).filter(Boolean);
+            try {
+              // The backend posts the focus change as a system message
+              if (names.length === 0 || (names.length === 1 && names[0].toLowerCase() === 'off')) {
+                await invoke('clear_session_focus');
+              } else {
+                await invoke('set_session_focus', { personaIds: names });
+              }
+            } catch (error) {
+              console.error('Failed to change focus:', error);
+              await handleAndPersistSystemMessage(
+                conversationMessage(`Focus failed: ${error}`, 'error', '❌'),
+                addMessage,
+                invoke
+              );
+            }
+            await saveCurrentSession();
+            break;
+          }
+
+          case 'decision': {
+            if (!currentSessionId) {
+              await handleAndPersistSystemMessage(
+                conversationMessage('No active session to extract a decision record from', 'error'),
+                addMessage,
+                invoke
+              );
+              break;
+            }
+            try {
+              // The backend stores the record and persists the announcement
+              const record = await invoke<DecisionRecord>('extract_decision_record', {
+                sessionId: currentSessionId,
+                range: null,
+              });
+              addMessage(
+                'system',
+                'System',
+                `📋 Decision record: ${record.title}\nDecision: ${record.decision}`
+              );
+            } catch (error) {
+              console.error('Failed to extract decision record:', error);
+              await handleAndPersistSystemMessage(
+                conversationMessage(`Decision record extraction failed: ${error}`, 'error', '❌'),
                 addMessage,
                 invoke
               );
             }
             await saveCurrentSession();
             break;
+          }
 
           case 'expert':
             if (parsed.args && parsed.args.length > 0) {
@@ -934,14 +1042,36 @@ Generate the BlueprintWorkflow now.`;
           // カスタムコマンド実行
           const argsStr = parsed.args ? parsed.args.join(' ') : '';
 
+          // Shell commands are gated by execute_shell_command itself
+          if (requestedBy && customCommand.type !== 'shell') {
+            const pendingAction = await invoke<PendingAction | null>('gate_agent_slash_command', {
+              commandName: parsed.command,
+              args: argsStr || null,
+              requestedBy,
+            });
+            if (pendingAction) {
+              await handleAndPersistSystemMessage(
+                conversationMessage(
+                  `/${parsed.command} requires your confirmation and was not executed yet.`,
+                  'info',
+                  '⏸'
+                ),
+                addMessage,
+                invoke
+              );
+              await saveCurrentSession();
+              return { nextInput: null, suppressUserMessage: true };
+            }
+          }
+
           try {
-            const expanded = await invoke<ExpandedSlashCommand>('expand_command_template', {
+            const expanded = await invoke<SlashCommandResult>('expand_command_template', {
               commandName: parsed.command,
               args: argsStr || null,
             });
 
             if (customCommand.type === 'prompt') {
-              const expandedPrompt = expanded.content.trim();
+              const expandedPrompt = expanded.output.trim();
               nextInput = expandedPrompt ? `${expandedPrompt}${commandProvenance}` : '';
               if (!nextInput.trim()) {
                 await handleAndPersistSystemMessage(
@@ -1101,10 +1231,16 @@ Generate the BlueprintWorkflow now.`;
                         }
                         shellContent = shellContent.replace(/{args}/g, stepArgs);
 
-                        stepOutput = await invoke<string>('execute_shell_command', {
+                        const shellResult = await invoke<SlashCommandResult>('execute_shell_command', {
                           command: shellContent,
                           working_dir: stepCmd.workingDir ?? null,
+                          commandName: step.commandName,
+                          requestedBy,
                         });
+                        if (!shellResult.success) {
+                          throw new Error(`Command failed: ${shellResult.output}`);
+                        }
+                        stepOutput = shellResult.output;
 
                         await handleAndPersistSystemMessage(
                           shellOutputMessage(`/${step.commandName} ($ ${shellContent})\n\`\`\`\n${stepOutput}\n\`\`\``),
@@ -1119,11 +1255,11 @@ Generate the BlueprintWorkflow now.`;
                         }
                         promptContent = promptContent.replace(/{args}/g, stepArgs);
 
-                        const expanded = await invoke<ExpandedSlashCommand>('expand_command_template', {
+                        const expanded = await invoke<SlashCommandResult>('expand_command_template', {
                           commandName: step.commandName,
                           args: stepArgs,
                         });
-                        stepOutput = expanded.content;
+                        stepOutput = expanded.output;
 
                         // Replace {prev_output} in expanded content if chainOutput
                         if (pipelineConfig.chainOutput) {
@@ -1203,12 +1339,17 @@ Generate the BlueprintWorkflow now.`;
             } else {
               // Shell command execution
               try {
-                const executionOutput = await invoke<string>('execute_shell_command', {
-                  command: expanded.content,
+                const executionResult = await invoke<SlashCommandResult>('execute_shell_command', {
+                  command: expanded.output,
                   working_dir: expanded.workingDir ?? null,
+                  commandName: parsed.command,
+                  requestedBy,
                 });
-                const trimmedOutput = executionOutput.trim();
-                const shellHeader = `/${parsed.command} ($ ${expanded.content.trim()})`;
+                if (!executionResult.success) {
+                  throw new Error(`Command failed: ${executionResult.output}`);
+                }
+                const trimmedOutput = executionResult.output.trim();
+                const shellHeader = `/${parsed.command} ($ ${expanded.output.trim()})`;
                 const shellBody = trimmedOutput
                   ? `\`\`\`\n${trimmedOutput}\n\`\`\``
                   : '_No output_';
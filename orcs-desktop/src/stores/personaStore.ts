@@ -8,7 +8,7 @@
 
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
-import type { PersonaConfig } from '../types/agent';
+import type { PersonaConfig, RemoteRefreshOutcome } from '../types/agent';
 
 export interface PersonaStore {
   // State
@@ -20,7 +20,9 @@ export interface PersonaStore {
   addPersona: (persona: PersonaConfig) => Promise<void>;
   updatePersona: (persona: PersonaConfig) => Promise<void>;
   deletePersona: (personaId: string) => Promise<void>;
+  unarchivePersona: (personaId: string) => Promise<void>;
   saveAdhocPersona: (personaId: string) => Promise<void>;
+  refreshRemotePersonas: () => Promise<RemoteRefreshOutcome>;
 
   // Getters
   getPersonaById: (personaId: string) => PersonaConfig | undefined;
@@ -83,6 +85,11 @@ export const usePersonaStore = create<PersonaStore>((set, get) => ({
         ),
       }));
 
+      // Pick up icon/color changes in the active session without a full reload
+      await invoke('refresh_participant_metadata').catch(() => {
+        // No active session
+      });
+
       console.log('[PersonaStore] Persona updated successfully');
     } catch (error) {
       console.error('[PersonaStore] Failed to update persona:', error);
@@ -90,6 +97,7 @@ export const usePersonaStore = create<PersonaStore>((set, get) => ({
     }
   },
 
+  // Archives by default (backend keeps the persona resolvable for old sessions)
   deletePersona: async (personaId: string) => {
     console.log('[PersonaStore] Deleting persona:', personaId);
 
@@ -107,6 +115,22 @@ export const usePersonaStore = create<PersonaStore>((set, get) => ({
     }
   },
 
+  unarchivePersona: async (personaId: string) => {
+    console.log('[PersonaStore] Unarchiving persona:', personaId);
+
+    try {
+      await invoke('unarchive_persona', { personaId });
+
+      // Reload personas to include the restored persona
+      await get().loadPersonas();
+
+      console.log('[PersonaStore] Persona unarchived successfully');
+    } catch (error) {
+      console.error('[PersonaStore] Failed to unarchive persona:', error);
+      throw error;
+    }
+  },
+
   saveAdhocPersona: async (personaId: string) => {
     console.log('[PersonaStore] Saving adhoc persona:', personaId);
 
@@ -123,6 +147,23 @@ export const usePersonaStore = create<PersonaStore>((set, get) => ({
     }
   },
 
+  refreshRemotePersonas: async () => {
+    console.log('[PersonaStore] Refreshing remote personas...');
+
+    try {
+      const outcome = await invoke<RemoteRefreshOutcome>('refresh_remote_personas');
+
+      // Reload so shadowing against local personas is re-applied
+      await get().loadPersonas();
+
+      console.log('[PersonaStore] Remote personas refreshed:', outcome.status);
+      return outcome;
+    } catch (error) {
+      console.error('[PersonaStore] Failed to refresh remote personas:', error);
+      throw error;
+    }
+  },
+
   // Getters
   getPersonaById: (personaId: string) => {
     const state = get();
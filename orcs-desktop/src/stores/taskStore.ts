@@ -13,6 +13,7 @@ interface TaskStore {
   initialize: () => Promise<void>;
   refreshTasks: () => Promise<void>;
   deleteTask: (taskId: string) => Promise<void>;
+  promoteDryRun: (taskId: string) => Promise<string>;
 
   // Getters
   getTask: (taskId: string) => Task | undefined;
@@ -70,6 +71,7 @@ export const useTaskStore = create<TaskStore>((set, get) => ({
             error: payload.fields.error,
             result: payload.fields.result,
             executionDetails: payload.fields.executionDetails,
+            dryRun: payload.fields.dryRun ?? false,
           };
 
           const newTasks = new Map(state.tasks);
@@ -158,6 +160,12 @@ export const useTaskStore = create<TaskStore>((set, get) => ({
     });
   },
 
+  promoteDryRun: async (taskId: string) => {
+    console.log('[TaskStore] Promoting dry run:', taskId);
+    // The real run is a new task, picked up through task-event
+    return invoke<string>('promote_dry_run', { taskId });
+  },
+
   getTask: (taskId: string) => {
     return get().tasks.get(taskId);
   },
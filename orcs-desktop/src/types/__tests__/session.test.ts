@@ -0,0 +1,103 @@
+import { describe, it, expect } from 'vitest';
+import { exportSessionToMarkdown, type ConversationMessage, type Session } from '../session';
+
+function sessionWithIcons(): Session {
+  return {
+    id: 'session-1',
+    title: 'Planning',
+    createdAt: '2025-03-14T09:00:00Z',
+    updatedAt: '2025-03-14T09:05:00Z',
+    currentPersonaId: 'mai',
+    personaHistories: {
+      You: [{ role: 'User', content: 'Where do we start?', timestamp: '2025-03-14T09:01:00Z' }],
+      mai: [{ role: 'Assistant', content: 'Parser first.', timestamp: '2025-03-14T09:02:00Z' }],
+      yui: [{ role: 'Assistant', content: 'Tests first.', timestamp: '2025-03-14T09:03:00Z' }],
+    },
+    appMode: { type: 'Idle' },
+    workspaceId: 'ws-1',
+    activeParticipantIds: ['mai', 'yui'],
+    executionStrategy: 'broadcast',
+    systemMessages: [],
+    participants: { mai: 'Mai', yui: 'Yui' },
+    participantIcons: { mai: '🦊', yui: 'icons/yui.png' },
+    participantColors: {},
+  } as unknown as Session;
+}
+
+describe('exportSessionToMarkdown', () => {
+  it('adds participant icons next to agent names when enabled', () => {
+    const { content } = exportSessionToMarkdown(sessionWithIcons(), 'Alice', {
+      includeIcons: true,
+    });
+
+    expect(content).toContain('🦊 **Mai**');
+    expect(content).toContain('![Yui](icons/yui.png) **Yui**');
+    expect(content).toMatch(/^\*\*Alice\*\*/m);
+  });
+
+  it('leaves icons out by default', () => {
+    const { content } = exportSessionToMarkdown(sessionWithIcons(), 'Alice');
+
+    expect(content).not.toContain('🦊');
+    expect(content).not.toContain('icons/yui.png');
+    expect(content).toMatch(/^\*\*Mai\*\*/m);
+  });
+});
+
+describe('mergeConsecutive export option', () => {
+  function sessionWithRuns(): Session {
+    const turn = (content: string, minute: number) => ({
+      role: 'Assistant',
+      content,
+      timestamp: `2025-03-14T09:0${minute}:00Z`,
+    });
+    return {
+      ...sessionWithIcons(),
+      personaHistories: {
+        mai: [turn('First.', 1), turn('Second.', 2), turn('Third.', 3), turn('Back again.', 5)],
+        yui: [turn('My turn.', 4)],
+      },
+    } as unknown as Session;
+  }
+
+  it('merges consecutive turns of one author until another author speaks', () => {
+    const { content } = exportSessionToMarkdown(sessionWithRuns(), 'Alice', {
+      mergeConsecutive: true,
+    });
+
+    expect(content).toContain('First.\n\nSecond.\n\nThird.\n\n---');
+    expect(content.match(/^\*\*Mai\*\*/gm)).toHaveLength(2);
+    expect(content.match(/^\*\*Yui\*\*/gm)).toHaveLength(1);
+    expect(content.indexOf('Third.')).toBeLessThan(content.indexOf('My turn.'));
+    expect(content.indexOf('My turn.')).toBeLessThan(content.indexOf('Back again.'));
+  });
+
+  it('keeps one block per message by default', () => {
+    const { content } = exportSessionToMarkdown(sessionWithRuns(), 'Alice');
+
+    expect(content.match(/^\*\*Mai\*\*/gm)).toHaveLength(4);
+  });
+});
+
+describe('turn stats in export', () => {
+  it('adds the stats of agent turns under their author line', () => {
+    const session = sessionWithIcons();
+    session.personaHistories.mai[0].metadata = {
+      turnStats: {
+        durationMs: 1_234,
+        inputTokens: 1_000,
+        outputTokens: 200,
+        estimatedCostMicrousd: 6_000,
+      },
+    } as unknown as ConversationMessage['metadata'];
+    session.personaHistories.yui[0].metadata = {
+      turnStats: { durationMs: 4_000 },
+    } as unknown as ConversationMessage['metadata'];
+
+    const { content } = exportSessionToMarkdown(session, 'Alice');
+
+    expect(content).toContain('**Mai** _(');
+    expect(content).toContain('> 1.2s · 1,200 tok · $0.0060');
+    expect(content).toContain('> 4.0s\n');
+  });
+});
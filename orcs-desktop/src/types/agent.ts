@@ -1,3 +1,5 @@
+import type { ContextMode } from './session';
+
 /**
  * エージェントのステータス
  */
@@ -45,6 +47,14 @@ export interface KaibaOptions {
   rei_id?: string; // Rei ID for the Kaiba persona
 }
 
+/** Result of re-fetching the remote persona index */
+export interface RemoteRefreshOutcome {
+  url: string;
+  status: 'updated' | 'not_modified' | 'offline';
+  personaCount: number;
+  fetchedAt?: string;
+}
+
 export interface PersonaConfig {
   id: string;
   name: string;
@@ -52,13 +62,17 @@ export interface PersonaConfig {
   background: string;
   communication_style: string;
   default_participant: boolean;
-  source: 'System' | 'User' | 'Adhoc';
+  source: 'System' | 'User' | 'Adhoc' | 'Remote';
   backend: PersonaBackend;
   model_name?: string;
+  fallback_backend?: PersonaBackend; // CLI backend used while offline mode is on
   icon?: string;
   base_color?: string;
   gemini_options?: GeminiOptions;
   kaiba_options?: KaibaOptions;
+  context_mode_override?: ContextMode; // overrides the session's context mode when set
+  archived?: boolean; // hidden from pickers, kept resolvable for old sessions
+  workspace_id?: string; // only available in this workspace's sessions; unset means global
 }
 
 /**
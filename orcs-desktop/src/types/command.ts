@@ -31,9 +31,9 @@ export const COMMAND_DEFINITIONS: CommandDefinition[] = [
   {
     name: 'task',
     icon: '✅',
-    description: 'Create a new task with the specified description',
-    usage: '/task <description>',
-    examples: ['/task Implement login feature', '/task Fix bug in parser'],
+    description: 'Create a new task with the specified description (--dry-run to preview it without changes)',
+    usage: '/task [--dry-run] <description>',
+    examples: ['/task Implement login feature', '/task --dry-run Fix bug in parser'],
   },
   {
     name: 'mode',
@@ -108,6 +108,29 @@ export const COMMAND_DEFINITIONS: CommandDefinition[] = [
     examples: ['/blueprint Create technical article about Rust', '/blueprint Refactor auth module'],
     argsDescription: 'Task or discussion context to convert into Blueprint',
   },
+  {
+    name: 'focus',
+    icon: '🎯',
+    description: 'Let only the named participants respond until focus is cleared',
+    usage: '/focus [name ...|off]',
+    examples: ['/focus Yui', '/focus Yui Mai', '/focus off'],
+    argsDescription: 'Participant names; none or off clears the focus',
+  },
+  {
+    name: 'poll',
+    icon: '🗳️',
+    description: 'Ask every participant to vote on one option and tally the result',
+    usage: '/poll <question> | <option1> | <option2> [| ...]',
+    examples: ['/poll Which database? | Postgres | SQLite', '/poll Release today? | Ship now | Wait'],
+    argsDescription: 'Question followed by at least two options, separated by |',
+  },
+  {
+    name: 'decision',
+    icon: '📋',
+    description: 'Extract an ADR-style decision record from this session into workspace storage',
+    usage: '/decision',
+    examples: ['/decision'],
+  },
   {
     name: 'create-persona',
     icon: '👤',
@@ -1,3 +1,6 @@
+import type { PendingAction, PendingActionStatus } from './pending_action';
+import type { SourceRef, TurnStats } from '../bindings/generated';
+
 /**
  * メッセージタイプの定義
  */
@@ -18,22 +21,46 @@ export type StreamingDialogueTurnKind =
       type: 'Chunk';
       author: string;
       content: string;
+      turn_stats?: TurnStats; // duration, tokens and estimated cost of the agent turn
     }
   | {
       type: 'Final';
     }
+  | {
+      type: 'Thinking';
+      author: string;
+      active: boolean; // true when the persona starts generating, false once its turn is done
+    }
+  | {
+      type: 'Working';
+      author: string;
+      elapsed_secs: number; // whole seconds since the CLI-backed persona started its turn
+      last_output_line?: string; // latest output line of the run, secrets scrubbed
+    }
   | {
       type: 'Error';
       message: string;
+      retryable: boolean; // transient failure (e.g. rate limit); resending may succeed
+      wire_log_entry_id?: string; // wire log entry of the failed request, if the session's wire log is on
     }
   | {
       type: 'AutoChatProgress';
       currentIteration: number; // was current_iteration
       maxIterations: number; // was max_iterations
+      delayMs: number; // was delay_ms
     }
   | {
       type: 'AutoChatComplete';
       totalIterations: number; // was total_iterations
+    }
+  | {
+      type: 'PendingAction';
+      action: PendingAction;
+    }
+  | {
+      type: 'PendingActionResolved';
+      action_id: string;
+      status: PendingActionStatus;
     };
 
 /**
@@ -62,6 +89,7 @@ export interface Message {
   backend?: string; // Optional backend type (e.g., "claude_api", "gemini_cli")
   modelName?: string | null; // Optional model name (e.g., "claude-sonnet-4-5-20250929")
   attachments?: AttachedFile[]; // 添付ファイル情報
+  turnStats?: TurnStats; // Duration, tokens and estimated cost of the agent turn
 }
 
 /**
@@ -82,6 +110,29 @@ export interface MessageMetadata {
   agentId?: string;
   status?: 'pending' | 'running' | 'completed' | 'failed';
   llmDebugInfo?: LlmDebugInfo;
+  spawnedTaskIds?: string[]; // Tasks spawned from this message
+  inResponseTo?: string; // Persona ID of the agent this turn reacted to
+  source?: SourceRef; // Source this context message provides
+  sources?: SourceRef[]; // Sources this turn copied passages from
+  fullContentPath?: string; // File holding the full text of a paste shown as an excerpt
+  turnStats?: TurnStats; // Duration, tokens and estimated cost of the agent turn
+}
+
+/**
+ * Formats turn stats as a compact badge label, e.g. `1.2s · 1,200 tok · $0.0150`.
+ *
+ * Token and cost parts are left out when the backend did not report them (CLI backends).
+ */
+export function formatTurnStats(stats: TurnStats): string {
+  const parts = [`${(stats.durationMs / 1000).toFixed(1)}s`];
+  if (stats.inputTokens != null || stats.outputTokens != null) {
+    const tokens = (stats.inputTokens ?? 0) + (stats.outputTokens ?? 0);
+    parts.push(`${tokens.toLocaleString('en-US')} tok`);
+  }
+  if (stats.estimatedCostMicrousd != null) {
+    parts.push(`$${(stats.estimatedCostMicrousd / 1_000_000).toFixed(4)}`);
+  }
+  return parts.join(' · ');
 }
 
 /**
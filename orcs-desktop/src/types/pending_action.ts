@@ -0,0 +1,22 @@
+/**
+ * Agent-triggered command waiting for user confirmation
+ *
+ * Mirrors orcs_core::pending_action::PendingAction (camelCase)
+ */
+export interface PendingAction {
+  id: string;
+  /** 'shell' runs in the backend; 'slashCommand' is re-dispatched by the UI */
+  kind: PendingActionKind;
+  sessionId: string;
+  requestedBy: string;
+  renderedCommand: string;
+  commandName?: string;
+  args?: string;
+  workingDir?: string;
+  createdAt: string;
+  expiresAt: string;
+}
+
+export type PendingActionKind = 'shell' | 'slashCommand';
+
+export type PendingActionStatus = 'approved' | 'denied' | 'expired';
@@ -1,4 +1,5 @@
 import { GeminiOptions, KaibaOptions } from './agent';
+import type { ContextMode } from './session';
 
 /**
  * Unified request model for creating a persona.
@@ -27,6 +28,9 @@ export interface CreatePersonaRequest {
   /** Optional specific model name */
   model_name?: string;
 
+  /** CLI backend used instead of an API backend while offline mode is on */
+  fallback_backend?: 'claude_cli' | 'gemini_cli' | 'codex_cli';
+
   /** Optional visual icon/emoji */
   icon?: string;
 
@@ -38,4 +42,12 @@ export interface CreatePersonaRequest {
 
   /** Kaiba-specific options (Rei ID for persistent memory persona) */
   kaiba_options?: KaibaOptions;
+
+  /**
+   * Context mode override for this persona.
+   * Unset follows the session's context mode. 'clean' withholds the restored
+   * conversation history and talk style from this persona only, even in a
+   * 'rich' session; other participants keep the full context.
+   */
+  context_mode_override?: ContextMode;
 }
@@ -11,6 +11,7 @@ export type {
   SessionType,
 } from '../bindings/generated';
 
+import { formatTurnStats } from './message';
 import type { Message, MessageType } from './message';
 import type { ConversationMessage, MessageMetadata, Plan, AutoChatConfig, SandboxState } from '../bindings/generated';
 
@@ -55,6 +56,7 @@ export interface Session {
   isMuted?: boolean; // was is_muted
   contextMode?: ContextMode; // was context_mode
   sandboxState?: SandboxState; // was sandbox_state (optional - only present in sandbox mode)
+  isGenerating?: boolean; // true while a background dialogue is still running
 }
 
 /**
@@ -64,6 +66,60 @@ export interface Session {
  */
 export type ContextMode = 'rich' | 'clean';
 
+/**
+ * Capabilities supported by at least one active participant of a session.
+ * Returned by the `get_session_capabilities` command.
+ */
+export interface SessionCapabilities {
+  fileRead: boolean;
+  fileWrite: boolean;
+  commandExecute: boolean;
+  webSearch: boolean;
+}
+
+/** Error returned by commands that mutate a session while it is open read-only */
+export interface ReadOnlyError {
+  ReadOnly: { session_id: string };
+}
+
+/**
+ * Participant display metadata keyed by persona ID.
+ * Returned by the `refresh_participant_metadata` command; field names match Session.
+ */
+export interface ParticipantMetadata {
+  participants: Record<string, string>;
+  participantIcons: Record<string, string>;
+  participantColors: Record<string, string>;
+  participantBackends: Record<string, string>;
+  participantModels: Record<string, string | null>;
+}
+
+/**
+ * ADR-style record of a decision reached in a session.
+ * Returned by the `extract_decision_record` command.
+ */
+export interface DecisionRecord {
+  title: string;
+  context: string;
+  optionsConsidered: string[];
+  decision: string;
+  consequences: string[];
+  dissentingOpinions: { author: string; opinion: string }[];
+}
+
+/**
+ * Tallied outcome of a participant poll.
+ * Returned by the `run_session_poll` command.
+ */
+export interface PollResult {
+  question: string;
+  counts: { option: string; votes: number }[];
+  votes: { voter: string; choice: string | null; response: string }[];
+  abstentions: number;
+  /** Options with the most votes; more than one means a tie, empty means no votes */
+  winners: string[];
+}
+
 /**
  * Conversation message metadata (alias for backward compatibility)
  * @deprecated Use MessageMetadata from generated types
@@ -248,17 +304,6 @@ export function convertToUIMessageWithAuthor(
   participantModels: Record<string, string | null> = {},
   userNickname: string = 'You'
 ): Message {
-  // Check if this is an error message (special authorId "Error")
-  if (authorId === 'Error') {
-    return {
-      id: `${msg.timestamp}-${Math.random()}`,
-      type: 'error',
-      author: '',
-      text: msg.content,
-      timestamp: new Date(msg.timestamp),
-    };
-  }
-
   const messageType = resolveMessageType(msg);
 
   // Resolve author name based on message role
@@ -308,6 +353,7 @@ export function convertToUIMessageWithAuthor(
     backend,
     modelName,
     attachments,
+    turnStats: msg.metadata?.turnStats ?? undefined,
   };
 }
 
@@ -354,12 +400,55 @@ export interface SessionExportResult {
   latestMessageTimestamp: string | null;
 }
 
+export interface SessionExportOptions {
+  /** Show each agent's icon (emoji or image path/URL) before their name */
+  includeIcons?: boolean;
+  /** Merge consecutive messages of one author into a single block */
+  mergeConsecutive?: boolean;
+}
+
+/**
+ * Merges runs of consecutive messages from the same author into one message
+ * whose content joins theirs with blank lines. The merged message keeps the
+ * first message's timestamp and collects every attachment.
+ */
+export function mergeConsecutiveMessages(items: MessageWithAuthor[]): MessageWithAuthor[] {
+  const merged: MessageWithAuthor[] = [];
+  for (const item of items) {
+    const previous = merged[merged.length - 1];
+    if (
+      previous &&
+      previous.authorId === item.authorId &&
+      previous.message.role === item.message.role
+    ) {
+      previous.message = {
+        ...previous.message,
+        content: `${previous.message.content}\n\n${item.message.content}`,
+        attachments: [...(previous.message.attachments ?? []), ...(item.message.attachments ?? [])],
+      };
+    } else {
+      merged.push({ ...item });
+    }
+  }
+  return merged;
+}
+
+/**
+ * Formats a participant icon for Markdown: image paths and URLs become
+ * inline images, anything else (emoji) is kept as text.
+ */
+function formatIconMarkdown(icon: string, author: string): string {
+  const isImage = /^(https?:|data:image\/)|[\/\\]|\.(png|jpe?g|gif|svg|webp)$/i.test(icon);
+  return isImage ? `![${author}](${icon})` : icon;
+}
+
 /**
  * セッションをMarkdown形式でエクスポート
  */
 export function exportSessionToMarkdown(
   session: Session,
-  userNickname: string = 'You'
+  userNickname: string = 'You',
+  options: SessionExportOptions = {}
 ): SessionExportResult {
   const messagesWithAuthors = getAllMessagesWithAuthors(session);
   const exportDate = new Date().toISOString();
@@ -389,13 +478,10 @@ export function exportSessionToMarkdown(
   lines.push('---');
   lines.push('');
 
-  // Messages
-  for (const { message, authorId } of messagesWithAuthors) {
-    // Skip system messages for cleaner export
-    if (message.role === 'System') {
-      continue;
-    }
-
+  // Messages (system messages are skipped for cleaner export)
+  const exported = messagesWithAuthors.filter(({ message }) => message.role !== 'System');
+  const blocks = options.mergeConsecutive ? mergeConsecutiveMessages(exported) : exported;
+  for (const { message, authorId } of blocks) {
     // Resolve author name
     let author: string;
     if (message.role === 'User') {
@@ -407,8 +493,15 @@ export function exportSessionToMarkdown(
     // Format timestamp
     const timestamp = new Date(message.timestamp).toLocaleString();
 
-    // Author line
-    lines.push(`**${author}** _(${timestamp})_`);
+    // Author line (agent icons come from the participant map)
+    const icon = options.includeIcons && message.role !== 'User'
+      ? session.participantIcons?.[authorId]
+      : undefined;
+    const iconPrefix = icon ? `${formatIconMarkdown(icon, author)} ` : '';
+    lines.push(`${iconPrefix}**${author}** _(${timestamp})_`);
+    if (message.metadata?.turnStats) {
+      lines.push(`> ${formatTurnStats(message.metadata.turnStats)}`);
+    }
     lines.push('');
 
     // Content
@@ -3,6 +3,8 @@ export type SessionEvent =
       type: 'user_input';
       content: string;
       attachments?: string[];
+      /** Attach files excluded by the workspace's .orcsignore anyway */
+      confirm_ignored?: boolean;
     }
   | {
       type: 'system_event';
@@ -80,10 +80,21 @@ export interface SlashCommand {
   isFavorite?: boolean;
   /** Sort order within favorites (lower = higher priority) */
   sortOrder?: number;
+  /** Whether agent-triggered executions must be approved by the user first */
+  requiresConfirmation?: boolean;
 }
 
-export interface ExpandedSlashCommand {
-  content: string;
+export type SlashCommandContentType = 'prompt' | 'shell_command' | 'shell_output' | 'notice';
+
+/**
+ * Structured result of expanding or executing a slash command
+ */
+export interface SlashCommandResult {
+  command: string;
+  success: boolean;
+  /** Expanded text or captured output (stderr when a shell command failed) */
+  output: string;
+  contentType: SlashCommandContentType;
   workingDir?: string;
 }
 
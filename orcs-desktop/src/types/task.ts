@@ -20,7 +20,8 @@ export interface TaskProgress {
  */
 export interface Task extends TaskType {
   // TaskType already has: id, sessionId, title, description, status, createdAt, updatedAt,
-  // completedAt, stepsExecuted, stepsSkipped, contextKeys, error, result
+  // completedAt, stepsExecuted, stepsSkipped, contextKeys, error, result,
+  // originMessageTimestamp, originAuthor, dryRun
 
   // Additional fields from full domain model (not in TaskType):
   executionDetails?: ExecutionDetails;
@@ -6,3 +6,21 @@ export type {
   ProjectContext,
   TempFile,
 } from '../bindings/generated';
+
+/** Activity counts for one heatmap bucket */
+export interface ActivityCounts {
+  userMessages: number;
+  assistantMessages: number;
+  tasksCompleted: number;
+}
+
+/** Workspace activity returned by `get_workspace_activity` */
+export interface ActivityHistogram {
+  workspaceId: string;
+  tzOffsetMinutes: number;
+  /** One entry per local day, oldest first, ending today */
+  daily: Array<ActivityCounts & { date: string }>;
+  /** 24 entries, hour 0 first */
+  hourly: Array<ActivityCounts & { hour: number }>;
+  totals: ActivityCounts;
+}
@@ -1,4 +1,14 @@
 import { getBuiltinCommandNames, generateCommandHelp } from '../types/command';
+import type { Message } from '../types/message';
+
+/**
+ * Quarantine markers around third-party content (shell output, search
+ * results, web clips, attached files) in agent context.
+ * Mirrors `UNTRUSTED_CONTENT_OPEN` / `UNTRUSTED_CONTENT_CLOSE` in
+ * orcs_core::session::untrusted.
+ */
+export const UNTRUSTED_CONTENT_OPEN = '<UntrustedContent';
+export const UNTRUSTED_CONTENT_CLOSE = '</UntrustedContent>';
 
 /**
  * コマンドの解析結果
@@ -47,16 +57,65 @@ export function isValidCommand(command: string): boolean {
   return getBuiltinCommandNames().includes(command);
 }
 
+/**
+ * Whether a chat message carries third-party content agents see quarantined.
+ */
+export function isUntrustedMessage(message: Message): boolean {
+  const kind = message.metadata?.source?.kind;
+  return message.type === 'shell_output' || (kind !== undefined && kind !== 'context_info');
+}
+
+/**
+ * Character ranges of `text` inside quarantine markers. An unclosed marker
+ * runs to the end of the text.
+ */
+function quarantinedRanges(text: string): Array<[number, number]> {
+  const ranges: Array<[number, number]> = [];
+  const lower = text.toLowerCase();
+  const open = new RegExp(`${UNTRUSTED_CONTENT_OPEN}\\b[^>]*>`, 'gi');
+  let match: RegExpExecArray | null;
+  while ((match = open.exec(text)) !== null) {
+    const close = lower.indexOf(UNTRUSTED_CONTENT_CLOSE.toLowerCase(), open.lastIndex);
+    const end = close === -1 ? text.length : close + UNTRUSTED_CONTENT_CLOSE.length;
+    ranges.push([match.index, end]);
+    open.lastIndex = end;
+  }
+  return ranges;
+}
+
+/** Drops layout whitespace so re-indented copies of a block still match. */
+function normalizeBlock(block: string): string {
+  return block
+    .replace(/>\s+/g, '>')
+    .replace(/\s+</g, '<')
+    .replace(/\s+/g, ' ')
+    .trim()
+    .toLowerCase();
+}
+
 /**
  * Agentレスポンスなど任意のテキストから SlashCommand を抽出する
  * - `<Slash><Name>...` フォーマットのみに対応（行頭 `/command` パターンは誤爆防止のため削除）
+ * - Blocks inside quarantine markers, or copied from `untrustedSources` (the
+ *   text of third-party context messages), are echoed data and never extracted
  */
-export function extractSlashCommands(text: string): string[] {
+export function extractSlashCommands(text: string, untrustedSources: string[] = []): string[] {
   const commands: string[] = [];
+  const quarantined = quarantinedRanges(text);
+  const sources = untrustedSources.map(normalizeBlock);
 
   // XML-style blocks only
   const slashBlocks = text.matchAll(/<Slash>([\s\S]*?)<\/Slash>/gi);
   for (const block of slashBlocks) {
+    const start = block.index ?? 0;
+    const echoed =
+      quarantined.some(([from, to]) => start >= from && start < to) ||
+      sources.some((source) => source.includes(normalizeBlock(block[0])));
+    if (echoed) {
+      console.warn('[commandParser] Ignoring command echoed from untrusted content:', block[0]);
+      continue;
+    }
+
     const inner = block[1];
     const nameMatch = inner.match(/<Name>([\s\S]*?)<\/Name>/i);
     if (!nameMatch) continue;